@@ -21,29 +21,42 @@ Register-ArgumentCompleter -Native -CommandName 'feroxbuster' -ScriptBlock {
 
     $completions = @(switch ($command) {
         'feroxbuster' {
-            [CompletionResult]::new('-u', 'u', [CompletionResultType]::ParameterName, 'The target URL (required, unless [--stdin || --resume-from] used)')
-            [CompletionResult]::new('--url', 'url', [CompletionResultType]::ParameterName, 'The target URL (required, unless [--stdin || --resume-from] used)')
+            [CompletionResult]::new('-u', 'u', [CompletionResultType]::ParameterName, 'The target URL (required, unless [--stdin || --resume-from || --import-nmap] used)')
+            [CompletionResult]::new('--url', 'url', [CompletionResultType]::ParameterName, 'The target URL (required, unless [--stdin || --resume-from || --import-nmap] used)')
             [CompletionResult]::new('--resume-from', 'resume-from', [CompletionResultType]::ParameterName, 'State file from which to resume a partially complete scan (ex. --resume-from ferox-1606586780.state)')
+            [CompletionResult]::new('--import-nmap', 'import-nmap', [CompletionResultType]::ParameterName, 'Read http(s) targets from the open ports in Nmap/Masscan XML output (ex. --import-nmap scan.xml)')
+            [CompletionResult]::new('--ports', 'ports', [CompletionResultType]::ParameterName, 'Scan each target host on every given port, guessing the scheme from the port (ex. --ports 80,443,8080,8443)')
+            [CompletionResult]::new('--scheme-probes', 'scheme-probes', [CompletionResultType]::ParameterName, 'Schemes and ports tried, in order, for targets given without a scheme (default: https:443 http:80)')
             [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'Proxy to use for requests (ex: http(s)://host:port, socks5(h)://host:port)')
             [CompletionResult]::new('--proxy', 'proxy', [CompletionResultType]::ParameterName, 'Proxy to use for requests (ex: http(s)://host:port, socks5(h)://host:port)')
+            [CompletionResult]::new('--proxy-failover', 'proxy-failover', [CompletionResultType]::ParameterName, 'Proxies to fail over to, in order, when the current one keeps refusing connections')
             [CompletionResult]::new('-P', 'P', [CompletionResultType]::ParameterName, 'Send only unfiltered requests through a Replay Proxy, instead of all requests')
             [CompletionResult]::new('--replay-proxy', 'replay-proxy', [CompletionResultType]::ParameterName, 'Send only unfiltered requests through a Replay Proxy, instead of all requests')
             [CompletionResult]::new('-R', 'R', [CompletionResultType]::ParameterName, 'Status Codes to send through a Replay Proxy when found (default: --status-codes value)')
             [CompletionResult]::new('--replay-codes', 'replay-codes', [CompletionResultType]::ParameterName, 'Status Codes to send through a Replay Proxy when found (default: --status-codes value)')
+            [CompletionResult]::new('--replay-headers', 'replay-headers', [CompletionResultType]::ParameterName, 'HTTP headers added to requests sent through a Replay Proxy (ex: --replay-headers X-Tag:ferox)')
+            [CompletionResult]::new('--replay-client-cert', 'replay-client-cert', [CompletionResultType]::ParameterName, 'PEM encoded certificate for mTLS on requests sent through a Replay Proxy (default: --client-cert)')
+            [CompletionResult]::new('--replay-client-key', 'replay-client-key', [CompletionResultType]::ParameterName, 'PEM encoded private key that goes with --replay-client-cert')
             [CompletionResult]::new('-a', 'a', [CompletionResultType]::ParameterName, 'Sets the User-Agent (default: feroxbuster/2.10.0)')
             [CompletionResult]::new('--user-agent', 'user-agent', [CompletionResultType]::ParameterName, 'Sets the User-Agent (default: feroxbuster/2.10.0)')
             [CompletionResult]::new('-x', 'x', [CompletionResultType]::ParameterName, 'File extension(s) to search for (ex: -x php -x pdf js)')
             [CompletionResult]::new('--extensions', 'extensions', [CompletionResultType]::ParameterName, 'File extension(s) to search for (ex: -x php -x pdf js)')
+            [CompletionResult]::new('--extension-order', 'extension-order', [CompletionResultType]::ParameterName, 'Order in which each word''s extensions are requested; interleaved/shuffled spread them across words (default: sequential)')
             [CompletionResult]::new('-m', 'm', [CompletionResultType]::ParameterName, 'Which HTTP request method(s) should be sent (default: GET)')
             [CompletionResult]::new('--methods', 'methods', [CompletionResultType]::ParameterName, 'Which HTTP request method(s) should be sent (default: GET)')
             [CompletionResult]::new('--data', 'data', [CompletionResultType]::ParameterName, 'Request''s Body; can read data from a file if input starts with an @ (ex: @post.bin)')
-            [CompletionResult]::new('-H', 'H', [CompletionResultType]::ParameterName, 'Specify HTTP headers to be used in each request (ex: -H Header:val -H ''stuff: things'')')
-            [CompletionResult]::new('--headers', 'headers', [CompletionResultType]::ParameterName, 'Specify HTTP headers to be used in each request (ex: -H Header:val -H ''stuff: things'')')
+            [CompletionResult]::new('-H', 'H', [CompletionResultType]::ParameterName, 'Specify HTTP headers to be used in each request (ex: -H Header:val -H ''stuff: things''); {{uuid}}, {{timestamp}}, and {{counter}} in a value are expanded per request')
+            [CompletionResult]::new('--headers', 'headers', [CompletionResultType]::ParameterName, 'Specify HTTP headers to be used in each request (ex: -H Header:val -H ''stuff: things''); {{uuid}}, {{timestamp}}, and {{counter}} in a value are expanded per request')
+            [CompletionResult]::new('--request-id', 'request-id', [CompletionResultType]::ParameterName, 'Tag each request with a unique id in the given header, for correlation in the target''s logs (ex: --request-id X-Ferox-Scan-Id)')
             [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'Specify HTTP cookies to be used in each request (ex: -b stuff=things)')
             [CompletionResult]::new('--cookies', 'cookies', [CompletionResultType]::ParameterName, 'Specify HTTP cookies to be used in each request (ex: -b stuff=things)')
             [CompletionResult]::new('-Q', 'Q', [CompletionResultType]::ParameterName, 'Request''s URL query parameters (ex: -Q token=stuff -Q secret=key)')
             [CompletionResult]::new('--query', 'query', [CompletionResultType]::ParameterName, 'Request''s URL query parameters (ex: -Q token=stuff -Q secret=key)')
+            [CompletionResult]::new('--encode', 'encode', [CompletionResultType]::ParameterName, 'Encode each word before it''s added to the URL; slashes are left alone (default: none)')
+            [CompletionResult]::new('--evasion', 'evasion', [CompletionResultType]::ParameterName, 'Mutate each request''s path to get past WAFs: light, moderate, aggressive, a single transform (dup-slash, dot-segment, path-params, trailing-junk), or a profile from [evasion_profiles] in ferox-config.toml')
+            [CompletionResult]::new('--accept-encoding', 'accept-encoding', [CompletionResultType]::ParameterName, 'Value of the Accept-Encoding header, i.e. identity or ''gzip, deflate'' (default: not sent; -H takes precedence)')
             [CompletionResult]::new('--dont-scan', 'dont-scan', [CompletionResultType]::ParameterName, 'URL(s) or Regex Pattern(s) to exclude from recursion/scans')
+            [CompletionResult]::new('--dont-scan-regex', 'dont-scan-regex', [CompletionResultType]::ParameterName, 'Regex Pattern(s) matched against every url before it''s requested or recursed into; matches are never touched (ex: --dont-scan-regex ''/(logout|delete)'')')
             [CompletionResult]::new('-S', 'S', [CompletionResultType]::ParameterName, 'Filter out messages of a particular size (ex: -S 5120 -S 4927,1970)')
             [CompletionResult]::new('--filter-size', 'filter-size', [CompletionResultType]::ParameterName, 'Filter out messages of a particular size (ex: -S 5120 -S 4927,1970)')
             [CompletionResult]::new('-X', 'X', [CompletionResultType]::ParameterName, 'Filter out messages via regular expression matching on the response''s body (ex: -X ''^ignore me$'')')
@@ -55,56 +68,153 @@ Register-ArgumentCompleter -Native -CommandName 'feroxbuster' -ScriptBlock {
             [CompletionResult]::new('-C', 'C', [CompletionResultType]::ParameterName, 'Filter out status codes (deny list) (ex: -C 200 -C 401)')
             [CompletionResult]::new('--filter-status', 'filter-status', [CompletionResultType]::ParameterName, 'Filter out status codes (deny list) (ex: -C 200 -C 401)')
             [CompletionResult]::new('--filter-similar-to', 'filter-similar-to', [CompletionResultType]::ParameterName, 'Filter out pages that are similar to the given page (ex. --filter-similar-to http://site.xyz/soft404)')
+            [CompletionResult]::new('--filter-category', 'filter-category', [CompletionResultType]::ParameterName, 'Filter out responses tagged with the given categories (ex. --filter-category backup-file,api-spec)')
             [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 'Status Codes to include (allow list) (default: All Status Codes)')
             [CompletionResult]::new('--status-codes', 'status-codes', [CompletionResultType]::ParameterName, 'Status Codes to include (allow list) (default: All Status Codes)')
             [CompletionResult]::new('-T', 'T', [CompletionResultType]::ParameterName, 'Number of seconds before a client''s request times out (default: 7)')
             [CompletionResult]::new('--timeout', 'timeout', [CompletionResultType]::ParameterName, 'Number of seconds before a client''s request times out (default: 7)')
+            [CompletionResult]::new('--connect-timeout', 'connect-timeout', [CompletionResultType]::ParameterName, 'Number of seconds a client may spend connecting, counted as connect-timeout errors (default: --timeout)')
+            [CompletionResult]::new('--read-timeout', 'read-timeout', [CompletionResultType]::ParameterName, 'Number of seconds a response body may stall, counted as read-timeout errors (default: --timeout)')
+            [CompletionResult]::new('--host-header', 'host-header', [CompletionResultType]::ParameterName, 'Send HOST as the Host header (and TLS SNI) while connecting to the --url target, i.e. an origin IP behind a CDN')
+            [CompletionResult]::new('--sni', 'sni', [CompletionResultType]::ParameterName, 'Present NAME via TLS SNI instead of the --host-header value')
+            [CompletionResult]::new('--doh', 'doh', [CompletionResultType]::ParameterName, 'Resolve target host names through a DNS-over-HTTPS endpoint (ex: --doh https://1.1.1.1/dns-query)')
             [CompletionResult]::new('--server-certs', 'server-certs', [CompletionResultType]::ParameterName, 'Add custom root certificate(s) for servers with unknown certificates')
             [CompletionResult]::new('--client-cert', 'client-cert', [CompletionResultType]::ParameterName, 'Add a PEM encoded certificate for mutual authentication (mTLS)')
             [CompletionResult]::new('--client-key', 'client-key', [CompletionResultType]::ParameterName, 'Add a PEM encoded private key for mutual authentication (mTLS)')
+            [CompletionResult]::new('--ca-bundle', 'ca-bundle', [CompletionResultType]::ParameterName, 'Trust every certificate in a PEM encoded CA bundle (i.e. a TLS intercepting proxy''s CA)')
+            [CompletionResult]::new('--pin-sha256', 'pin-sha256', [CompletionResultType]::ParameterName, 'Only scan https targets whose certificate chain includes a certificate with the given SHA-256 fingerprint (hex, colons optional)')
             [CompletionResult]::new('-t', 't', [CompletionResultType]::ParameterName, 'Number of concurrent threads (default: 50)')
             [CompletionResult]::new('--threads', 'threads', [CompletionResultType]::ParameterName, 'Number of concurrent threads (default: 50)')
             [CompletionResult]::new('-d', 'd', [CompletionResultType]::ParameterName, 'Maximum recursion depth, a depth of 0 is infinite recursion (default: 4)')
             [CompletionResult]::new('--depth', 'depth', [CompletionResultType]::ParameterName, 'Maximum recursion depth, a depth of 0 is infinite recursion (default: 4)')
-            [CompletionResult]::new('-L', 'L', [CompletionResultType]::ParameterName, 'Limit total number of concurrent scans (default: 0, i.e. no limit)')
-            [CompletionResult]::new('--scan-limit', 'scan-limit', [CompletionResultType]::ParameterName, 'Limit total number of concurrent scans (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--inherit-headers', 'inherit-headers', [CompletionResultType]::ParameterName, 'Response headers from a found directory to send with requests made by its recursive scan; Set-Cookie is sent as cookies (ex: --inherit-headers Set-Cookie,X-CSRF-Token)')
+            [CompletionResult]::new('--extract-page-limit', 'extract-page-limit', [CompletionResultType]::ParameterName, 'Limit number of extracted links requested per page (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--extract-scan-limit', 'extract-scan-limit', [CompletionResultType]::ParameterName, 'Limit number of extracted links requested per scan (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--extract-limit', 'extract-limit', [CompletionResultType]::ParameterName, 'Limit number of extracted links requested across all scans (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--extract-depth', 'extract-depth', [CompletionResultType]::ParameterName, 'Stop extracting links from directories reached through this many extractions (default: 0, i.e. no limit)')
+            [CompletionResult]::new('-L', 'L', [CompletionResultType]::ParameterName, 'Limit total number of concurrent scans, others wait as pending (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--scan-limit', 'scan-limit', [CompletionResultType]::ParameterName, 'Limit total number of concurrent scans, others wait as pending (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--recursion-strategy', 'recursion-strategy', [CompletionResultType]::ParameterName, 'Order in which pending scans found by recursion start under --scan-limit; breadth or depth first (default: bfs)')
+            [CompletionResult]::new('--shuffle', 'shuffle', [CompletionResultType]::ParameterName, 'Request the wordlist in a random order per scan, reproducible from SEED (default: random seed, saved for --resume-from)')
+            [CompletionResult]::new('--preview', 'preview', [CompletionResultType]::ParameterName, 'Print the first N fully-built requests without sending them, then ask whether to start the scan')
             [CompletionResult]::new('--parallel', 'parallel', [CompletionResultType]::ParameterName, 'Run parallel feroxbuster instances (one child process per url passed via stdin)')
             [CompletionResult]::new('--rate-limit', 'rate-limit', [CompletionResultType]::ParameterName, 'Limit number of requests per second (per directory) (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--rate-limit-global', 'rate-limit-global', [CompletionResultType]::ParameterName, 'Limit number of requests per second across all scans combined (default: 0, i.e. no limit)')
+            [CompletionResult]::new('--bandwidth-limit', 'bandwidth-limit', [CompletionResultType]::ParameterName, 'Limit the bytes per second received across all scans, measured from response sizes (ex: --bandwidth-limit 512K)')
+            [CompletionResult]::new('--pacing', 'pacing', [CompletionResultType]::ParameterName, 'Send requests in bursts with quiet periods between them, across all scans: low-and-slow (20/10m), quiet (100/2m), burst (1000/30s), or N/TIME_SPEC (ex: --pacing 50/5m)')
             [CompletionResult]::new('--time-limit', 'time-limit', [CompletionResultType]::ParameterName, 'Limit total run time of all scans (ex: --time-limit 10m)')
-            [CompletionResult]::new('-w', 'w', [CompletionResultType]::ParameterName, 'Path or URL of the wordlist')
-            [CompletionResult]::new('--wordlist', 'wordlist', [CompletionResultType]::ParameterName, 'Path or URL of the wordlist')
+            [CompletionResult]::new('-w', 'w', [CompletionResultType]::ParameterName, 'Path or URL of the wordlist; - reads it from STDIN, plugin:FILE has a lua script''s wordlist() generate it, builtin:common|api use one built into feroxbuster')
+            [CompletionResult]::new('--wordlist', 'wordlist', [CompletionResultType]::ParameterName, 'Path or URL of the wordlist; - reads it from STDIN, plugin:FILE has a lua script''s wordlist() generate it, builtin:common|api use one built into feroxbuster')
+            [CompletionResult]::new('--fuzz-wordlist', 'fuzz-wordlist', [CompletionResultType]::ParameterName, 'Wordlist for a keyword other than FUZZ (ex: --fuzz-wordlist FUZZ2=users.txt); FUZZ markers in the url, headers, or --data are filled in from the wordlists instead of adding words to the url')
+            [CompletionResult]::new('--fuzz-mode', 'fuzz-mode', [CompletionResultType]::ParameterName, 'How FUZZ keyword wordlists are combined: every combination, or line by line (default: clusterbomb)')
+            [CompletionResult]::new('--tune-strategy', 'tune-strategy', [CompletionResultType]::ParameterName, 'Algorithm --auto-tune uses to pick a new scan rate (default: heap)')
+            [CompletionResult]::new('--tune-on-latency', 'tune-on-latency', [CompletionResultType]::ParameterName, 'Also trigger --auto-tune/--auto-bail when the p95 response time rises past a multiple of the target''s baseline (ex: --tune-on-latency 2x-baseline)')
+            [CompletionResult]::new('--policy-403-ratio', 'policy-403-ratio', [CompletionResultType]::ParameterName, 'Ratio of a scan''s requests that must be 403s to trigger --auto-tune/--auto-bail (default: 0.9)')
+            [CompletionResult]::new('--policy-429-ratio', 'policy-429-ratio', [CompletionResultType]::ParameterName, 'Ratio of a scan''s requests that must be 429s to trigger --auto-tune/--auto-bail (default: 0.3)')
+            [CompletionResult]::new('--policy-status', 'policy-status', [CompletionResultType]::ParameterName, 'Status codes that trigger --auto-tune/--auto-bail the same way 429s do, using --policy-429-ratio (ex: --policy-status 503,520)')
+            [CompletionResult]::new('--policy-error-threshold', 'policy-error-threshold', [CompletionResultType]::ParameterName, 'Minimum number of errors a scan must see to trigger --auto-tune/--auto-bail; half of --threads is used when higher (default: 25)')
+            [CompletionResult]::new('--policy-error-weight', 'policy-error-weight', [CompletionResultType]::ParameterName, 'How much each error of a category (dns, connect, tls, connect-timeout, timeout, read-timeout, proxy, body-read, ...) counts toward --policy-error-threshold (ex: --policy-error-weight timeout=2,tls=0)')
+            [CompletionResult]::new('--tune-step', 'tune-step', [CompletionResultType]::ParameterName, 'Requests per second added after each error-free interval by --tune-strategy aimd (default: 1/20th of the scan rate)')
+            [CompletionResult]::new('--tune-floor', 'tune-floor', [CompletionResultType]::ParameterName, 'Lowest requests per second --tune-strategy aimd will drop to (default: 1)')
+            [CompletionResult]::new('--tune-ceiling', 'tune-ceiling', [CompletionResultType]::ParameterName, 'Highest requests per second --tune-strategy aimd will climb to (default: the scan rate when tuning started)')
+            [CompletionResult]::new('--auto-requeue', 'auto-requeue', [CompletionResultType]::ParameterName, 'Like --auto-bail, but restart a bailed scan at half its rate after a cooldown, up to ATTEMPTS times')
+            [CompletionResult]::new('--auto-resume', 'auto-resume', [CompletionResultType]::ParameterName, 'Pause all scans after SECONDS of nothing but connection errors (i.e. VPN drop), resuming once the target responds again (default: 0, i.e. disabled)')
+            [CompletionResult]::new('--canary', 'canary', [CompletionResultType]::ParameterName, 'Periodically request a known-good PATH (or url) on each target''s host, pausing the host''s scans while it looks banned (ex: --canary /)')
+            [CompletionResult]::new('--canary-interval', 'canary-interval', [CompletionResultType]::ParameterName, 'Number of seconds between --canary requests (default: 30)')
+            [CompletionResult]::new('--stability-interval', 'stability-interval', [CompletionResultType]::ParameterName, 'Number of seconds between --stability-check rounds (default: 60)')
+            [CompletionResult]::new('--search-regex', 'search-regex', [CompletionResultType]::ParameterName, 'Report matches of REGEX in every response body, filtered ones included (ex: --search-regex ''api[_-]?key|BEGIN RSA'')')
+            [CompletionResult]::new('--entropy-threshold', 'entropy-threshold', [CompletionResultType]::ParameterName, 'Bits of entropy per character a base64 string needs for --entropy-secrets to report it; hex strings are scaled to match (default: 4.5)')
+            [CompletionResult]::new('--plugin', 'plugin', [CompletionResultType]::ParameterName, 'Lua script(s) defining before_request, on_response, and/or on_finding hooks')
+            [CompletionResult]::new('--plugin-timeout', 'plugin-timeout', [CompletionResultType]::ParameterName, 'Milliseconds a single plugin hook may run before it''s aborted (default: 100)')
+            [CompletionResult]::new('--category-rule', 'category-rule', [CompletionResultType]::ParameterName, 'Tag responses whose url matches REGEX with CATEGORY (ex: --category-rule ''login-page:(?i)/(login|signin)'')')
+            [CompletionResult]::new('--rules', 'rules', [CompletionResultType]::ParameterName, 'TOML file of rules matching url/header/body regexes that tag findings, set their severity, and raise alerts')
+            [CompletionResult]::new('--junit', 'junit', [CompletionResultType]::ParameterName, 'Write a JUnit XML report with a test case per forbidden rule (--rules), failed when the rule matched')
+            [CompletionResult]::new('--exit-code', 'exit-code', [CompletionResultType]::ParameterName, 'Exit with CODE when the scan ends a certain way; conditions: rule (a forbidden rule matched), errors (see --exit-error-ratio), bail (a scan was bailed on) (ex: --exit-code errors=3,bail=4)')
+            [CompletionResult]::new('--exit-error-ratio', 'exit-error-ratio', [CompletionResultType]::ParameterName, 'Share of requests that must fail for --exit-code errors=CODE to apply (default: 0.1)')
+            [CompletionResult]::new('--favicon-db', 'favicon-db', [CompletionResultType]::ParameterName, 'Extra HASH,TECHNOLOGY pairs (one per line) to check --favicon hashes against')
+            [CompletionResult]::new('--baseline', 'baseline', [CompletionResultType]::ParameterName, 'A previous scan''s --json output or .state file; known urls are requested with If-None-Match/If-Modified-Since and 304s are reported as unchanged')
+            [CompletionResult]::new('--baseline-url', 'baseline-url', [CompletionResultType]::ParameterName, 'A page (e.g. the app''s default page) fetched once; every response''s body is given a rough diff ratio against it (0.0 same, 1.0 nothing in common)')
+            [CompletionResult]::new('--reuse-signatures', 'reuse-signatures', [CompletionResultType]::ParameterName, 'A previous scan''s .state file; directories it already learned the 404 responses of skip the wildcard heuristics')
+            [CompletionResult]::new('--prune-after', 'prune-after', [CompletionResultType]::ParameterName, 'Number of fruitless requests after which --prune-extensions drops an extension (default: 250)')
+            [CompletionResult]::new('--bypass-limit', 'bypass-limit', [CompletionResultType]::ParameterName, 'Maximum number of forbidden urls to attempt bypasses against (default: 50)')
+            [CompletionResult]::new('--bypass-rate', 'bypass-rate', [CompletionResultType]::ParameterName, 'Maximum number of 403 bypass requests per second, on top of the scan''s own (default: 10, 0 disables)')
+            [CompletionResult]::new('--archive-max-size', 'archive-max-size', [CompletionResultType]::ParameterName, 'Largest archive --archive-peek downloads (default: 10M)')
             [CompletionResult]::new('-I', 'I', [CompletionResultType]::ParameterName, 'File extension(s) to Ignore while collecting extensions (only used with --collect-extensions)')
             [CompletionResult]::new('--dont-collect', 'dont-collect', [CompletionResultType]::ParameterName, 'File extension(s) to Ignore while collecting extensions (only used with --collect-extensions)')
             [CompletionResult]::new('-o', 'o', [CompletionResultType]::ParameterName, 'Output file to write results to (use w/ --json for JSON entries)')
             [CompletionResult]::new('--output', 'output', [CompletionResultType]::ParameterName, 'Output file to write results to (use w/ --json for JSON entries)')
             [CompletionResult]::new('--debug-log', 'debug-log', [CompletionResultType]::ParameterName, 'Output file to write log entries (use w/ --json for JSON entries)')
+            [CompletionResult]::new('--burp-export', 'burp-export', [CompletionResultType]::ParameterName, 'Export discovered resources, w/ full request/response pairs, as Burp Suite XML')
+            [CompletionResult]::new('--har-export', 'har-export', [CompletionResultType]::ParameterName, 'Export discovered resources, w/ full request/response pairs, as an HTTP Archive (HAR)')
+            [CompletionResult]::new('--quiet-stream', 'quiet-stream', [CompletionResultType]::ParameterName, 'Write every response to FILE, including filtered ones (tagged with the filter that removed them); the terminal still shows only findings')
+            [CompletionResult]::new('--traffic-log', 'traffic-log', [CompletionResultType]::ParameterName, 'Log every request sent (w/ sensitive headers redacted) and the response''s metadata to files in DIRECTORY')
+            [CompletionResult]::new('--traffic-log-max', 'traffic-log-max', [CompletionResultType]::ParameterName, 'Start a new --traffic-log file once the current one reaches this size (ex: --traffic-log-max 1G)')
+            [CompletionResult]::new('--policy-audit', 'policy-audit', [CompletionResultType]::ParameterName, 'Write every auto-tune/bail/requeue policy decision, w/ the counters that triggered it, to FILE as NDJSON')
+            [CompletionResult]::new('--screenshots', 'screenshots', [CompletionResultType]::ParameterName, 'Screenshot findings with headless Chrome/Chromium, saving the images and an index.html that links them to DIRECTORY')
+            [CompletionResult]::new('--chrome', 'chrome', [CompletionResultType]::ParameterName, 'Chrome/Chromium executable used for --screenshots (default: first one found on the PATH)')
+            [CompletionResult]::new('--screenshot-codes', 'screenshot-codes', [CompletionResultType]::ParameterName, 'Status codes of findings worth a screenshot (default: 200)')
+            [CompletionResult]::new('--screenshot-types', 'screenshot-types', [CompletionResultType]::ParameterName, 'Content types of findings worth a screenshot, matched against the Content-Type header (default: text/html)')
+            [CompletionResult]::new('--pipe-to', 'pipe-to', [CompletionResultType]::ParameterName, 'Spawn COMMAND and write discovered resources to its stdin as JSON lines; COMMAND is restarted if it exits early (ex: --pipe-to "jq -r .url")')
+            [CompletionResult]::new('--api-export', 'api-export', [CompletionResultType]::ParameterName, 'POST findings to a vulnerability management api once the scan ends; a token in FEROX_API_TOKEN is sent along')
+            [CompletionResult]::new('--api-export-format', 'api-export-format', [CompletionResultType]::ParameterName, 'generic: a json array of findings per request; dojo: a DefectDojo import-scan upload (default: generic)')
+            [CompletionResult]::new('--api-template', 'api-template', [CompletionResultType]::ParameterName, 'JSON template sent for each finding, with {{url}}, {{status}}, {{tags}}, {{severity}}, ... filled in (generic format)')
+            [CompletionResult]::new('--api-batch-size', 'api-batch-size', [CompletionResultType]::ParameterName, 'Number of findings sent per --api-export request (default: 100)')
+            [CompletionResult]::new('--dojo-engagement', 'dojo-engagement', [CompletionResultType]::ParameterName, 'DefectDojo engagement that findings are imported into (--api-export-format dojo)')
+            [CompletionResult]::new('--summary', 'summary', [CompletionResultType]::ParameterName, 'Write a JSON summary of the scan (targets, durations, request counts, status codes, bails, filters, top findings) once it ends')
+            [CompletionResult]::new('--record', 'record', [CompletionResultType]::ParameterName, 'Record every response, in the order received, for `feroxbuster replay FILE`')
             [CompletionResult]::new('--stdin', 'stdin', [CompletionResultType]::ParameterName, 'Read url(s) from STDIN')
             [CompletionResult]::new('--burp', 'burp', [CompletionResultType]::ParameterName, 'Set --proxy to http://127.0.0.1:8080 and set --insecure to true')
             [CompletionResult]::new('--burp-replay', 'burp-replay', [CompletionResultType]::ParameterName, 'Set --replay-proxy to http://127.0.0.1:8080 and set --insecure to true')
             [CompletionResult]::new('--smart', 'smart', [CompletionResultType]::ParameterName, 'Set --auto-tune, --collect-words, and --collect-backups to true')
             [CompletionResult]::new('--thorough', 'thorough', [CompletionResultType]::ParameterName, 'Use the same settings as --smart and set --collect-extensions to true')
+            [CompletionResult]::new('--proxy-failover-direct', 'proxy-failover-direct', [CompletionResultType]::ParameterName, 'Connect directly once every proxy has failed, instead of turning requests into errors')
             [CompletionResult]::new('-A', 'A', [CompletionResultType]::ParameterName, 'Use a random User-Agent')
             [CompletionResult]::new('--random-agent', 'random-agent', [CompletionResultType]::ParameterName, 'Use a random User-Agent')
+            [CompletionResult]::new('--safe-methods-only', 'safe-methods-only', [CompletionResultType]::ParameterName, 'Refuse to send anything but GET/HEAD and warn about words that look like state-changing actions (logout, delete, ...)')
+            [CompletionResult]::new('--raw-requests', 'raw-requests', [CompletionResultType]::ParameterName, 'Send words the url parser would rewrite (spaces, control characters, \, #, ../) byte for byte over a minimal HTTP/1.1 client')
             [CompletionResult]::new('-f', 'f', [CompletionResultType]::ParameterName, 'Append / to each request''s URL')
             [CompletionResult]::new('--add-slash', 'add-slash', [CompletionResultType]::ParameterName, 'Append / to each request''s URL')
+            [CompletionResult]::new('--dual-slash', 'dual-slash', [CompletionResultType]::ParameterName, 'Request each word both with and without a trailing /, only recursing into word/ when its response differs from word''s')
+            [CompletionResult]::new('--range-probe', 'range-probe', [CompletionResultType]::ParameterName, 'Send GETs with a Range: bytes=0-0 header first, only fetching full bodies for responses that aren''t filtered by status code or size')
+            [CompletionResult]::new('--decompress', 'decompress', [CompletionResultType]::ParameterName, 'Decompress gzip/deflate bodies before filtering and extraction; sizes are those of the decompressed bodies')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'Allow client to follow redirects')
             [CompletionResult]::new('--redirects', 'redirects', [CompletionResultType]::ParameterName, 'Allow client to follow redirects')
             [CompletionResult]::new('-k', 'k', [CompletionResultType]::ParameterName, 'Disables TLS certificate validation in the client')
             [CompletionResult]::new('--insecure', 'insecure', [CompletionResultType]::ParameterName, 'Disables TLS certificate validation in the client')
+            [CompletionResult]::new('-4', '4', [CompletionResultType]::ParameterName, 'Only connect to targets over IPv4')
+            [CompletionResult]::new('--ipv4', 'ipv4', [CompletionResultType]::ParameterName, 'Only connect to targets over IPv4')
+            [CompletionResult]::new('-6', '6', [CompletionResultType]::ParameterName, 'Only connect to targets over IPv6')
+            [CompletionResult]::new('--ipv6', 'ipv6', [CompletionResultType]::ParameterName, 'Only connect to targets over IPv6')
+            [CompletionResult]::new('--fan-out-ips', 'fan-out-ips', [CompletionResultType]::ParameterName, 'Spread requests across every address the target resolves to, and report findings that differ between them')
             [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'Do not scan recursively')
             [CompletionResult]::new('--no-recursion', 'no-recursion', [CompletionResultType]::ParameterName, 'Do not scan recursively')
             [CompletionResult]::new('--force-recursion', 'force-recursion', [CompletionResultType]::ParameterName, 'Force recursion attempts on all ''found'' endpoints (still respects recursion depth)')
+            [CompletionResult]::new('--directories-first', 'directories-first', [CompletionResultType]::ParameterName, 'Request every word without extensions, across all recursion levels, before coming back for the extensions')
             [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'Extract links from response body (html, javascript, etc...); make new requests based on findings (default: true)')
             [CompletionResult]::new('--extract-links', 'extract-links', [CompletionResultType]::ParameterName, 'Extract links from response body (html, javascript, etc...); make new requests based on findings (default: true)')
             [CompletionResult]::new('--dont-extract-links', 'dont-extract-links', [CompletionResultType]::ParameterName, 'Don''t extract links from response body (html, javascript, etc...)')
+            [CompletionResult]::new('--crawl', 'crawl', [CompletionResultType]::ParameterName, 'Don''t use the wordlist; only follow links extracted from each target, up to --depth links deep')
             [CompletionResult]::new('--auto-tune', 'auto-tune', [CompletionResultType]::ParameterName, 'Automatically lower scan rate when an excessive amount of errors are encountered')
             [CompletionResult]::new('--auto-bail', 'auto-bail', [CompletionResultType]::ParameterName, 'Automatically stop scanning when an excessive amount of errors are encountered')
+            [CompletionResult]::new('--stability-check', 'stability-check', [CompletionResultType]::ParameterName, 'Periodically re-request a sample of earlier findings, flagging the ones that come back differently (i.e. A/B backends)')
+            [CompletionResult]::new('--server-alerts', 'server-alerts', [CompletionResultType]::ParameterName, 'Alert when a host''s Server/X-Powered-By header changes mid-scan; counts toward --auto-tune/--auto-bail')
+            [CompletionResult]::new('--entropy-secrets', 'entropy-secrets', [CompletionResultType]::ParameterName, 'Report high entropy base64/hex strings (likely keys and tokens) found in text responses, filtered ones included')
+            [CompletionResult]::new('--categorize', 'categorize', [CompletionResultType]::ParameterName, 'Tag responses with built-in categories (backup-file, config-file, admin-panel, api-spec, directory-listing)')
+            [CompletionResult]::new('--fail-on-rule', 'fail-on-rule', [CompletionResultType]::ParameterName, 'Exit with status 2 (or --exit-code rule=CODE) when any forbidden rule (--rules) matched a finding')
             [CompletionResult]::new('-D', 'D', [CompletionResultType]::ParameterName, 'Don''t auto-filter wildcard responses')
             [CompletionResult]::new('--dont-filter', 'dont-filter', [CompletionResultType]::ParameterName, 'Don''t auto-filter wildcard responses')
+            [CompletionResult]::new('--detect-case', 'detect-case', [CompletionResultType]::ParameterName, 'Detect targets that ignore case (i.e. IIS) and lowercase/dedupe the wordlist when found')
+            [CompletionResult]::new('--favicon', 'favicon', [CompletionResultType]::ParameterName, 'Fetch each host''s /favicon.ico and report its Shodan style hash (http.favicon.hash), along with the technology it belongs to when known')
+            [CompletionResult]::new('--well-known', 'well-known', [CompletionResultType]::ParameterName, 'Request each host''s /.well-known/ resources (security.txt, openid-configuration, ...) and follow the urls inside of them')
+            [CompletionResult]::new('--bucket-listing', 'bucket-listing', [CompletionResultType]::ParameterName, 'Check whether S3/GCS/Azure buckets found in extracted links publicly list their contents')
             [CompletionResult]::new('-E', 'E', [CompletionResultType]::ParameterName, 'Automatically discover extensions and add them to --extensions (unless they''re in --dont-collect)')
             [CompletionResult]::new('--collect-extensions', 'collect-extensions', [CompletionResultType]::ParameterName, 'Automatically discover extensions and add them to --extensions (unless they''re in --dont-collect)')
+            [CompletionResult]::new('--prune-extensions', 'prune-extensions', [CompletionResultType]::ParameterName, 'Stop requesting an extension on a host once it''s been requested --prune-after times without a single finding')
             [CompletionResult]::new('-B', 'B', [CompletionResultType]::ParameterName, 'Automatically request likely backup extensions for "found" urls')
             [CompletionResult]::new('--collect-backups', 'collect-backups', [CompletionResultType]::ParameterName, 'Automatically request likely backup extensions for "found" urls')
+            [CompletionResult]::new('--bypass-403', 'bypass-403', [CompletionResultType]::ParameterName, 'Request 403 bypass variants (case tricks, %2e segments, trailing %20, X-Original-URL, etc...) of forbidden urls and report the ones that get a different status')
+            [CompletionResult]::new('--vcs-check', 'vcs-check', [CompletionResultType]::ParameterName, 'Check .git/.svn/.hg findings for a usable repository (HEAD, config, refs; at most 3 requests each) and report exposed ones as high severity')
+            [CompletionResult]::new('--archive-peek', 'archive-peek', [CompletionResultType]::ParameterName, 'Download archives that were found (zip, jar, war, tar, tar.gz, etc...) and list the names of the files inside, without extracting them')
             [CompletionResult]::new('-g', 'g', [CompletionResultType]::ParameterName, 'Automatically discover important words from within responses and add them to the wordlist')
             [CompletionResult]::new('--collect-words', 'collect-words', [CompletionResultType]::ParameterName, 'Automatically discover important words from within responses and add them to the wordlist')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'Increase verbosity level (use -vv or more for greater effect. [CAUTION] 4 -v''s is probably too much)')
@@ -113,6 +223,10 @@ Register-ArgumentCompleter -Native -CommandName 'feroxbuster' -ScriptBlock {
             [CompletionResult]::new('-q', 'q', [CompletionResultType]::ParameterName, 'Hide progress bars and banner (good for tmux windows w/ notifications)')
             [CompletionResult]::new('--quiet', 'quiet', [CompletionResultType]::ParameterName, 'Hide progress bars and banner (good for tmux windows w/ notifications)')
             [CompletionResult]::new('--json', 'json', [CompletionResultType]::ParameterName, 'Emit JSON logs to --output and --debug-log instead of normal text')
+            [CompletionResult]::new('--punycode', 'punycode', [CompletionResultType]::ParameterName, 'Display internationalized domain names as punycode instead of unicode (requests always use punycode)')
+            [CompletionResult]::new('--titles', 'titles', [CompletionResultType]::ParameterName, 'Show the <title> and meta generator of HTML responses alongside the results (--json output also gets their charset)')
+            [CompletionResult]::new('--fuzzy-hash', 'fuzzy-hash', [CompletionResultType]::ParameterName, 'Add a simhash of each response''s body to --json output (next to its SHA-256), the same fuzzy hash --filter-similar-to compares')
+            [CompletionResult]::new('--traffic-log-bodies', 'traffic-log-bodies', [CompletionResultType]::ParameterName, 'Include full response bodies in the --traffic-log')
             [CompletionResult]::new('--no-state', 'no-state', [CompletionResultType]::ParameterName, 'Disable state output file (*.state)')
             [CompletionResult]::new('-U', 'U', [CompletionResultType]::ParameterName, 'Update feroxbuster to the latest version')
             [CompletionResult]::new('--update', 'update', [CompletionResultType]::ParameterName, 'Update feroxbuster to the latest version')
@@ -120,6 +234,34 @@ Register-ArgumentCompleter -Native -CommandName 'feroxbuster' -ScriptBlock {
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             [CompletionResult]::new('-V', 'V', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'Print version')
+            [CompletionResult]::new('replay', 'replay', [CompletionResultType]::ParameterValue, 'Run the responses of a --record session through the filters and output given before `replay`, without making any requests')
+            [CompletionResult]::new('selftest', 'selftest', [CompletionResultType]::ParameterValue, 'Scan a built-in test server (404s, redirects, 429s, wildcard pages) with the settings given before `selftest`, and check that the results come out as expected')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'feroxbuster;replay' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'feroxbuster;selftest' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'feroxbuster;help' {
+            [CompletionResult]::new('replay', 'replay', [CompletionResultType]::ParameterValue, 'Run the responses of a --record session through the filters and output given before `replay`, without making any requests')
+            [CompletionResult]::new('selftest', 'selftest', [CompletionResultType]::ParameterValue, 'Scan a built-in test server (404s, redirects, 429s, wildcard pages) with the settings given before `selftest`, and check that the results come out as expected')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'feroxbuster;help;replay' {
+            break
+        }
+        'feroxbuster;help;selftest' {
+            break
+        }
+        'feroxbuster;help;help' {
             break
         }
     })
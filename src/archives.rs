@@ -0,0 +1,478 @@
+//! listing the contents of archives that were found (--archive-peek)
+//!
+//! an exposed backup.zip is only as interesting as what's in it. findings that look like zip
+//! (zip, jar, war, ear, apk) or tar (tar, tar.gz, tgz) archives are downloaded, as long as they're
+//! no larger than --archive-max-size, and the names of the files inside are listed alongside the
+//! finding and written to --output (as `{"type":"archive",...}` with --json). nothing is ever
+//! extracted, and the download is thrown away once it's been listed
+//!
+//! the archive's own bytes decide how it's read, a catch-all page served at backup.zip is left
+//! alone; no more than MAX_ARCHIVES archives are downloaded per run
+use std::{
+    collections::HashSet,
+    fmt,
+    io::{Cursor, Read},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use flate2::read::GzDecoder;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::{
+    config::OutputLevel,
+    event_handlers::{
+        Command::{self, AddToUsizeField},
+        Handles,
+    },
+    response::FeroxResponse,
+    send_command,
+    statistics::StatField::TotalExpected,
+    traffic::size_in_bytes,
+    traits::FeroxSerialize,
+    utils::{fmt_err, logged_request},
+    DEFAULT_METHOD,
+};
+
+/// number of archives downloaded per run
+const MAX_ARCHIVES: usize = 20;
+
+/// number of file names kept per archive; the total is still counted
+const MAX_LISTED: usize = 100;
+
+/// number of file names printed to the terminal per archive, --output gets all that were kept
+const MAX_PRINTED: usize = 10;
+
+/// url endings of archives that are listed
+const ARCHIVE_EXTENSIONS: [&str; 8] = [
+    ".zip", ".jar", ".war", ".ear", ".apk", ".tar", ".tar.gz", ".tgz",
+];
+
+/// Archive listings made so far (--archive-peek)
+#[derive(Debug, Default)]
+pub struct ArchivePeeks {
+    /// archives that were already downloaded, by url
+    peeked: Mutex<HashSet<String>>,
+
+    /// downloads that are still running
+    pending: Mutex<Vec<JoinHandle<()>>>,
+}
+
+/// How an archive is packed, as told by its first few bytes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// zip, and everything built on it (jar, war, ear, apk)
+    Zip,
+
+    /// gzipped tarball
+    TarGz,
+
+    /// uncompressed tarball
+    Tar,
+}
+
+/// implementation of ArchiveFormat
+impl ArchiveFormat {
+    /// the format of the given archive, if it's one that can be listed
+    fn sniff(body: &[u8]) -> Option<Self> {
+        if body.starts_with(b"PK\x03\x04") || body.starts_with(b"PK\x05\x06") {
+            Some(Self::Zip)
+        } else if body.starts_with(b"\x1f\x8b") {
+            Some(Self::TarGz)
+        } else if body.get(257..262) == Some(b"ustar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Display implementation for ArchiveFormat
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zip => write!(f, "zip"),
+            Self::TarGz => write!(f, "tar.gz"),
+            Self::Tar => write!(f, "tar"),
+        }
+    }
+}
+
+/// The files inside an archive that was found
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"archive"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// url of the archive
+    pub url: String,
+
+    /// how the archive is packed
+    pub format: ArchiveFormat,
+
+    /// size of the archive, in bytes
+    pub size: usize,
+
+    /// number of files in the archive
+    pub entries: usize,
+
+    /// names of the files in the archive, up to MAX_LISTED of them
+    pub files: Vec<String>,
+}
+
+/// FeroxSerialize implementation for ArchiveInfo
+impl FeroxSerialize for ArchiveInfo {
+    /// summary line followed by the first few file names, i.e.
+    /// `ARC zip 1337b http://localhost/backup.zip [2 files]`
+    fn as_str(&self) -> String {
+        let mut text = format!(
+            "{} {} {}b {} {}\n",
+            style("ARC").bright().magenta(),
+            self.format,
+            self.size,
+            style(&self.url).yellow(),
+            style(format!("[{} files]", self.entries)).dim()
+        );
+
+        for name in self.files.iter().take(MAX_PRINTED) {
+            text.push_str(&format!("    {name}\n"));
+        }
+
+        if self.entries > MAX_PRINTED {
+            let more = format!("... and {} more", self.entries - MAX_PRINTED);
+            text.push_str(&format!("    {}\n", style(more).dim()));
+        }
+
+        text
+    }
+
+    /// Create an NDJSON representation of the archive
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the listing of {} to JSON",
+                self.url
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// whether the given url looks like it points at an archive that can be listed
+pub fn is_archive(url: &Url) -> bool {
+    let path = url.path().to_ascii_lowercase();
+    ARCHIVE_EXTENSIONS
+        .iter()
+        .any(|extension| path.ends_with(extension))
+}
+
+/// number of files in the given archive, along with the first MAX_LISTED of their names
+fn list(body: &[u8], format: ArchiveFormat) -> Result<(usize, Vec<String>)> {
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = zip::ZipArchive::new(Cursor::new(body))?;
+            let entries = archive.len();
+
+            // in the order they're stored, file_names() has none
+            let files = (0..entries.min(MAX_LISTED))
+                .map(|index| Ok(archive.by_index_raw(index)?.name().to_string()))
+                .collect::<Result<_>>()?;
+
+            Ok((entries, files))
+        }
+        ArchiveFormat::TarGz => list_tar(GzDecoder::new(body)),
+        ArchiveFormat::Tar => list_tar(body),
+    }
+}
+
+/// `list` for tarballs, which have to be read through from start to end
+fn list_tar(reader: impl Read) -> Result<(usize, Vec<String>)> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = 0;
+    let mut files = Vec::new();
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        entries += 1;
+
+        if files.len() < MAX_LISTED {
+            files.push(entry.path()?.to_string_lossy().to_string());
+        }
+    }
+
+    Ok((entries, files))
+}
+
+/// download the given archive, giving up on it once it's larger than the given number of bytes
+async fn download(url: &Url, max_size: u64, handles: Arc<Handles>) -> Result<Vec<u8>> {
+    // need to manually adjust stats
+    send_command!(handles.stats.tx, AddToUsizeField(TotalExpected, 1));
+
+    let mut response = logged_request(url, DEFAULT_METHOD, None, handles).await?;
+
+    if response.status() != StatusCode::OK {
+        bail!("{} came back with a {}", url, response.status());
+    }
+
+    if response.content_length().unwrap_or_default() > max_size {
+        bail!("{} is larger than --archive-max-size", url);
+    }
+
+    let mut body = Vec::new();
+
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+
+        if body.len() as u64 > max_size {
+            bail!("{} is larger than --archive-max-size", url);
+        }
+    }
+
+    Ok(body)
+}
+
+/// download and list the given archive, reporting its contents
+async fn peek(url: Url, handles: Arc<Handles>) {
+    log::trace!("enter: peek({})", url);
+
+    let max_size = size_in_bytes(&handles.config.archive_max_size).unwrap_or_default();
+
+    let body = match download(&url, max_size, handles.clone()).await {
+        Ok(body) => body,
+        Err(e) => {
+            log::info!("Not listing {}: {}", url, e);
+            return;
+        }
+    };
+
+    let Some(format) = ArchiveFormat::sniff(&body) else {
+        log::info!("{} doesn't look like an archive, not listing it", url);
+        return;
+    };
+
+    let size = body.len();
+
+    // reading through a tarball is cpu bound, keep it off of the scan's threads
+    let listed = tokio::task::spawn_blocking(move || list(&body, format)).await;
+
+    let (entries, files) = match listed {
+        Ok(Ok(listed)) => listed,
+        Ok(Err(e)) => {
+            log::warn!("Could not list {}: {}", url, e);
+            return;
+        }
+        Err(e) => {
+            log::warn!("Could not list {}: {}", url, e);
+            return;
+        }
+    };
+
+    let info = ArchiveInfo {
+        kind: String::from("archive"),
+        url: url.to_string(),
+        format,
+        size,
+        entries,
+        files,
+    };
+
+    log::info!("{:?}", info);
+
+    handles
+        .output
+        .send(Command::ReportArchive(Box::new(info)))
+        .unwrap_or_default();
+
+    log::trace!("exit: peek");
+}
+
+/// queue up a listing of the given finding, if it looks like an archive that wasn't listed yet
+/// and MAX_ARCHIVES hasn't been reached; does nothing unless --archive-peek is used
+pub fn on_finding(response: &FeroxResponse, handles: Arc<Handles>) {
+    if !handles.config.archive_peek
+        || *response.status() != StatusCode::OK
+        || !is_archive(response.url())
+    {
+        return;
+    }
+
+    let url = response.url().clone();
+
+    let Ok(mut peeked) = handles.features.archives.peeked.lock() else {
+        return;
+    };
+
+    if peeked.contains(url.as_str()) {
+        return;
+    }
+
+    if peeked.len() >= MAX_ARCHIVES {
+        log::debug!("archive limit reached, not listing {}", url);
+        return;
+    }
+
+    peeked.insert(url.to_string());
+    drop(peeked);
+
+    let task = tokio::spawn(peek(url, handles.clone()));
+
+    if let Ok(mut pending) = handles.features.archives.pending.lock() {
+        pending.push(task);
+    }
+}
+
+/// wait for every queued listing to finish, so their findings make it out before the output
+/// handler is closed
+pub async fn finish(handles: &Handles) {
+    loop {
+        let tasks: Vec<_> = match handles.features.archives.pending.lock() {
+            Ok(mut pending) => pending.drain(..).collect(),
+            Err(_) => return,
+        };
+
+        if tasks.is_empty() {
+            return;
+        }
+
+        for task in tasks {
+            task.await.unwrap_or_default();
+        }
+    }
+}
+
+/// whether archive listings are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    /// a zip archive holding the given files
+    fn zip_of(names: &[&str]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+        for name in names {
+            writer
+                .start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(b"contents").unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// a tarball holding the given files
+    fn tar_of(names: &[&str]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+
+        for name in names {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(8);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, b"contents".as_slice())
+                .unwrap();
+        }
+
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    /// archives are recognized by their extension, regardless of case
+    fn is_archive_checks_extension() {
+        for url in [
+            "http://localhost/backup.zip",
+            "http://localhost/app.JAR",
+            "http://localhost/site.tar.gz?x=1",
+            "http://localhost/site.tgz",
+        ] {
+            assert!(is_archive(&Url::parse(url).unwrap()), "{url}");
+        }
+
+        assert!(!is_archive(&Url::parse("http://localhost/zip").unwrap()));
+        assert!(!is_archive(&Url::parse("http://localhost/a.gz").unwrap()));
+    }
+
+    #[test]
+    /// every format is told apart by its bytes, and anything else is left alone
+    fn sniff_reads_magic_bytes() {
+        let tar = tar_of(&["a"]);
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&tar).unwrap();
+
+        assert_eq!(
+            ArchiveFormat::sniff(&zip_of(&["a"])),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::sniff(&gz.finish().unwrap()),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(ArchiveFormat::sniff(&tar), Some(ArchiveFormat::Tar));
+        assert_eq!(ArchiveFormat::sniff(b"<html>not found</html>"), None);
+    }
+
+    #[test]
+    /// file names are listed in order, up to MAX_LISTED, while every file is counted
+    fn list_names_files() {
+        let zip = zip_of(&["index.php", "config/database.php"]);
+        assert_eq!(
+            list(&zip, ArchiveFormat::Zip).unwrap(),
+            (
+                2,
+                vec![
+                    String::from("index.php"),
+                    String::from("config/database.php")
+                ]
+            )
+        );
+
+        let names: Vec<_> = (0..MAX_LISTED + 5).map(|i| format!("file{i}")).collect();
+        let names: Vec<_> = names.iter().map(String::as_str).collect();
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&tar_of(&names)).unwrap();
+
+        let (entries, files) = list(&gz.finish().unwrap(), ArchiveFormat::TarGz).unwrap();
+        assert_eq!(entries, MAX_LISTED + 5);
+        assert_eq!(files.len(), MAX_LISTED);
+        assert_eq!(files[0], "file0");
+    }
+
+    #[test]
+    /// the listing serializes with its type; the terminal only gets the first few names
+    fn archive_info_serializes_with_type() {
+        let info = ArchiveInfo {
+            kind: String::from("archive"),
+            url: String::from("http://localhost/backup.zip"),
+            format: ArchiveFormat::Zip,
+            size: 1337,
+            entries: MAX_PRINTED + 2,
+            files: (0..MAX_PRINTED + 2).map(|i| format!("file{i}")).collect(),
+        };
+
+        let json = info.as_json().unwrap();
+        assert!(json.starts_with(
+            r#"{"type":"archive","url":"http://localhost/backup.zip","format":"zip""#
+        ));
+        assert!(json.contains(&format!("file{}", MAX_PRINTED + 1)));
+
+        let text = info.as_str();
+        assert!(text.contains("file0"));
+        assert!(!text.contains(&format!("file{}", MAX_PRINTED + 1)));
+        assert!(text.contains("... and 2 more"));
+    }
+}
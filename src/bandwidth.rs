@@ -0,0 +1,226 @@
+//! bytes per second throttling (--bandwidth-limit)
+//!
+//! a response's size is only known once it's been received, so each request sets aside the
+//! average size of the responses seen so far, and the difference is settled once its response
+//! is in; a run of large responses leaves the bucket in debt, and every scan waits for the debt
+//! to be paid off before sending its next request. the allowance never builds up past one
+//! second's worth of bytes
+use std::{sync::Mutex, time::Instant};
+
+use anyhow::{Context, Result};
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    config::Configuration, response::FeroxResponse, traffic::size_in_bytes, utils::fmt_err,
+};
+
+/// --bandwidth-limit; shared by every scan
+#[derive(Debug, Default)]
+pub struct Bandwidth {
+    /// `None` when --bandwidth-limit wasn't used
+    bucket: Option<Mutex<ByteBucket>>,
+}
+
+/// Token bucket where a token is a byte, allowed to go into debt
+#[derive(Debug)]
+struct ByteBucket {
+    /// bytes added to the bucket per second, also the most it can hold
+    rate: f64,
+
+    /// bytes that can be received right now, negative when in debt
+    available: f64,
+
+    /// when `available` was last brought up to date
+    updated: Instant,
+
+    /// number of responses paid for
+    responses: u64,
+
+    /// total size of the responses paid for
+    received: u64,
+}
+
+/// implementation of ByteBucket
+impl ByteBucket {
+    /// new bucket for the given number of bytes per second, starting out full
+    fn new(rate: u64) -> Self {
+        Self {
+            rate: rate as f64,
+            available: rate as f64,
+            updated: Instant::now(),
+            responses: 0,
+            received: 0,
+        }
+    }
+
+    /// add the bytes earned since the last update
+    fn refill(&mut self, now: Instant) {
+        let earned = now.duration_since(self.updated).as_secs_f64() * self.rate;
+        self.available = (self.available + earned).min(self.rate);
+        self.updated = now;
+    }
+
+    /// average size of the responses paid for so far
+    fn average(&self) -> u64 {
+        self.received.checked_div(self.responses).unwrap_or(0)
+    }
+
+    /// set aside the expected size of a response that's about to be requested
+    fn reserve(&mut self, now: Instant) -> u64 {
+        self.refill(now);
+        let reserved = self.average();
+        self.available -= reserved as f64;
+        reserved
+    }
+
+    /// pay for a response of the given number of bytes, less what was set aside for it
+    fn consume(&mut self, bytes: u64, reserved: u64, now: Instant) {
+        self.refill(now);
+        self.available -= bytes as f64 - reserved as f64;
+        self.responses += 1;
+        self.received += bytes;
+    }
+
+    /// how long until the bucket is out of debt
+    fn wait_time(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / self.rate)
+        }
+    }
+}
+
+/// set up the --bandwidth-limit bucket; nothing is throttled when it wasn't used
+pub fn initialize(config: &Configuration) -> Result<Bandwidth> {
+    if config.bandwidth_limit.is_empty() {
+        return Ok(Bandwidth::default());
+    }
+
+    let rate = size_in_bytes(&config.bandwidth_limit)
+        .filter(|rate| *rate > 0)
+        .with_context(|| {
+            fmt_err(&format!(
+                "Invalid bandwidth limit given: {}",
+                config.bandwidth_limit
+            ))
+        })?;
+
+    Ok(Bandwidth {
+        bucket: Some(Mutex::new(ByteBucket::new(rate))),
+    })
+}
+
+/// implementation of Bandwidth
+impl Bandwidth {
+    /// wait until the bytes received so far are within --bandwidth-limit, then set aside the
+    /// expected size of the next response; the amount set aside is handed back to `consume`
+    pub async fn throttle(&self) -> u64 {
+        let Some(bucket) = &self.bucket else {
+            return 0;
+        };
+
+        loop {
+            let wait = match bucket.lock() {
+                Ok(mut bucket) => match bucket.wait_time(Instant::now()) {
+                    wait if wait.is_zero() => return bucket.reserve(Instant::now()),
+                    wait => wait,
+                },
+                Err(_) => return 0,
+            };
+
+            // other scans may have gotten in first while waiting, so check again
+            sleep(wait).await;
+        }
+    }
+
+    /// count the given response against --bandwidth-limit, `reserved` being what `throttle`
+    /// set aside for it
+    pub fn consume(&self, response: &FeroxResponse, reserved: u64) {
+        if let Some(Ok(mut bucket)) = self.bucket.as_ref().map(Mutex::lock) {
+            bucket.consume(response_size(response), reserved, Instant::now());
+        }
+    }
+}
+
+/// number of bytes the given response took up on the wire; the body plus its headers, status
+/// line overhead isn't worth counting
+fn response_size(response: &FeroxResponse) -> u64 {
+    let headers: usize = response
+        .headers()
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len() + 4) // ': ' and '\r\n'
+        .sum();
+
+    response.content_length() + headers as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// a full bucket doesn't wait, going into debt waits for the debt to be paid off
+    fn byte_bucket_waits_off_debt() {
+        let start = Instant::now();
+        let mut bucket = ByteBucket::new(1000);
+        bucket.updated = start;
+
+        assert!(bucket.wait_time(start).is_zero());
+
+        bucket.consume(1500, 0, start);
+        assert_eq!(bucket.wait_time(start), Duration::from_millis(500));
+
+        // a quarter second later, half the debt is paid off
+        let later = start + Duration::from_millis(250);
+        assert_eq!(bucket.wait_time(later), Duration::from_millis(250));
+    }
+
+    #[test]
+    /// idle time doesn't earn more than one second's worth of bytes
+    fn byte_bucket_caps_allowance() {
+        let start = Instant::now();
+        let mut bucket = ByteBucket::new(1000);
+        bucket.updated = start;
+
+        let later = start + Duration::from_secs(10);
+        bucket.consume(2000, 0, later);
+        assert_eq!(bucket.wait_time(later), Duration::from_secs(1));
+    }
+
+    #[test]
+    /// requests set aside the average response size, which is settled once the response is in
+    fn byte_bucket_reserves_average_size() {
+        let start = Instant::now();
+        let mut bucket = ByteBucket::new(1000);
+        bucket.updated = start;
+
+        // nothing seen yet, nothing to set aside
+        assert_eq!(bucket.reserve(start), 0);
+        bucket.consume(200, 0, start);
+        bucket.consume(400, 0, start);
+        assert_eq!(bucket.available, 400.0);
+
+        let reserved = bucket.reserve(start);
+        assert_eq!(reserved, 300);
+        assert_eq!(bucket.available, 100.0);
+
+        bucket.consume(100, reserved, start);
+        assert_eq!(bucket.available, 300.0);
+    }
+
+    #[test]
+    /// headers are counted along with the body
+    fn response_size_counts_headers_and_body() {
+        let response: FeroxResponse = serde_json::from_value(serde_json::json!({
+            "url": "http://localhost/",
+            "content_length": 100,
+            "headers": { "server": "nginx" }
+        }))
+        .unwrap();
+
+        assert_eq!(response_size(&response), 100 + 6 + 5 + 4);
+    }
+}
@@ -1,14 +1,17 @@
 use super::entry::BannerEntry;
 use crate::{
+    client::IpFamily,
     config::Configuration,
+    evasion,
     event_handlers::Handles,
     utils::{logged_request, parse_url_with_raw_path, status_colorizer},
-    DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD, DEFAULT_STATUS_CODES, VERSION,
+    DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD, DEFAULT_STATUS_CODES, HIGH_429_RATIO,
+    HIGH_ERROR_RATIO, MIN_ERROR_THRESHOLD, VERSION,
 };
 use anyhow::{bail, Result};
 use console::{style, Emoji};
 use serde_json::Value;
-use std::{io::Write, sync::Arc};
+use std::{collections::BTreeMap, io::Write, sync::Arc};
 
 /// Url used to query github's api; specifically used to look for the latest tagged release name
 pub const UPDATE_URL: &str = "https://api.github.com/repos/epi052/feroxbuster/releases/latest";
@@ -31,6 +34,18 @@ pub struct Banner {
     /// all live targets
     targets: Vec<BannerEntry>,
 
+    /// represents Configuration.import_nmap
+    import_nmap: BannerEntry,
+
+    /// represents Configuration.ports
+    ports: BannerEntry,
+
+    /// represents Configuration.scheme_probes
+    scheme_probes: BannerEntry,
+
+    /// schemes detected for targets given without one
+    scheme_detections: Vec<BannerEntry>,
+
     /// represents Configuration.status_codes
     status_codes: BannerEntry,
 
@@ -43,9 +58,18 @@ pub struct Banner {
     /// represents Configuration.wordlist
     wordlist: BannerEntry,
 
+    /// represents Configuration.fuzz_wordlists and Configuration.fuzz_mode
+    fuzz_wordlists: BannerEntry,
+
     /// represents Configuration.timeout
     timeout: BannerEntry,
 
+    /// represents Configuration.connect_timeout
+    connect_timeout: BannerEntry,
+
+    /// represents Configuration.read_timeout
+    read_timeout: BannerEntry,
+
     /// represents Configuration.user_agent
     user_agent: BannerEntry,
 
@@ -58,9 +82,21 @@ pub struct Banner {
     /// represents Configuration.proxy
     proxy: BannerEntry,
 
+    /// represents Configuration.proxy_failover
+    proxy_failover: BannerEntry,
+
+    /// represents Configuration.proxy_failover_direct
+    proxy_failover_direct: BannerEntry,
+
     /// represents Configuration.client_key
     client_key: BannerEntry,
 
+    /// represents Configuration.ca_bundle
+    ca_bundle: BannerEntry,
+
+    /// represents Configuration.pin_sha256
+    pin_sha256: BannerEntry,
+
     /// represents Configuration.client_cert
     client_cert: BannerEntry,
 
@@ -73,9 +109,21 @@ pub struct Banner {
     /// represents Configuration.replay_codes
     replay_codes: BannerEntry,
 
+    /// represents Configuration.replay_headers
+    replay_headers: Vec<BannerEntry>,
+
+    /// represents Configuration.replay_client_cert
+    replay_client_cert: BannerEntry,
+
+    /// represents Configuration.replay_client_key
+    replay_client_key: BannerEntry,
+
     /// represents Configuration.headers
     headers: Vec<BannerEntry>,
 
+    /// represents Configuration.request_id
+    request_id: BannerEntry,
+
     /// represents Configuration.filter_size
     filter_size: Vec<BannerEntry>,
 
@@ -91,30 +139,154 @@ pub struct Banner {
     /// represents Configuration.filter_regex
     filter_regex: Vec<BannerEntry>,
 
+    /// represents Configuration.filter_category
+    filter_category: Vec<BannerEntry>,
+
     /// represents Configuration.extract_links
     extract_links: BannerEntry,
 
+    /// represents Configuration.extract_page_limit, extract_scan_limit, extract_limit, and
+    /// extract_depth
+    extraction_limits: BannerEntry,
+
+    /// represents Configuration.crawl
+    crawl: BannerEntry,
+
     /// represents Configuration.json
     json: BannerEntry,
 
+    /// represents Configuration.punycode
+    punycode: BannerEntry,
+
+    /// represents Configuration.fuzzy_hash
+    fuzzy_hash: BannerEntry,
+
+    /// represents Configuration.titles
+    titles: BannerEntry,
+
     /// represents Configuration.output
     output: BannerEntry,
 
     /// represents Configuration.debug_log
     debug_log: BannerEntry,
 
+    /// represents Configuration.burp_export
+    burp_export: BannerEntry,
+
+    /// represents Configuration.har_export
+    har_export: BannerEntry,
+
+    /// represents Configuration.quiet_stream
+    quiet_stream: BannerEntry,
+
+    /// represents Configuration.traffic_log
+    traffic_log: BannerEntry,
+
+    /// represents Configuration.traffic_log_max
+    traffic_log_max: BannerEntry,
+
+    /// represents Configuration.traffic_log_bodies
+    traffic_log_bodies: BannerEntry,
+
+    /// represents Configuration.policy_audit
+    policy_audit: BannerEntry,
+
+    /// represents Configuration.screenshots
+    screenshots: BannerEntry,
+
+    /// represents Configuration.chrome
+    chrome: BannerEntry,
+
+    /// represents Configuration.screenshot_codes and Configuration.screenshot_types
+    screenshot_rule: BannerEntry,
+
+    /// represents Configuration.pipe_to
+    pipe_to: BannerEntry,
+
+    /// represents Configuration.api_export
+    api_export: BannerEntry,
+
+    /// represents Configuration.api_export_format
+    api_export_format: BannerEntry,
+
+    /// represents Configuration.api_template
+    api_template: BannerEntry,
+
+    /// represents Configuration.api_batch_size
+    api_batch_size: BannerEntry,
+
+    /// represents Configuration.dojo_engagement
+    dojo_engagement: BannerEntry,
+
+    /// represents Configuration.summary
+    summary: BannerEntry,
+
+    /// represents Configuration.record
+    record: BannerEntry,
+
+    /// represents Configuration.plugins
+    plugins: Vec<BannerEntry>,
+
+    /// represents Configuration.plugin_timeout
+    plugin_timeout: BannerEntry,
+
+    /// represents Configuration.categorize
+    categorize: BannerEntry,
+
+    /// represents Configuration.category_rules
+    category_rules: Vec<BannerEntry>,
+
+    /// represents Configuration.rules
+    rules: BannerEntry,
+
+    /// represents Configuration.junit
+    junit: BannerEntry,
+
+    /// represents Configuration.fail_on_rule
+    fail_on_rule: BannerEntry,
+
+    /// represents Configuration.exit_codes
+    exit_codes: BannerEntry,
+
+    /// represents Configuration.exit_error_ratio
+    exit_error_ratio: BannerEntry,
+
     /// represents Configuration.extensions
     extensions: BannerEntry,
 
+    /// represents Configuration.extension_order
+    extension_order: BannerEntry,
+
     /// represents Configuration.methods
     methods: BannerEntry,
 
+    /// represents Configuration.safe_methods_only
+    safe_methods_only: BannerEntry,
+
+    /// represents Configuration.raw_requests
+    raw_requests: BannerEntry,
+
     /// represents Configuration.data
     data: BannerEntry,
 
     /// represents Configuration.insecure
     insecure: BannerEntry,
 
+    /// represents Configuration.host_header
+    host_header: BannerEntry,
+
+    /// represents Configuration.sni
+    sni: BannerEntry,
+
+    /// represents Configuration.doh
+    doh: BannerEntry,
+
+    /// represents Configuration.ipv4 and Configuration.ipv6
+    ip_family: BannerEntry,
+
+    /// represents Configuration.fan_out_ips
+    fan_out_ips: BannerEntry,
+
     /// represents Configuration.redirects
     redirects: BannerEntry,
 
@@ -130,27 +302,141 @@ pub struct Banner {
     /// represents Configuration.add_slash
     add_slash: BannerEntry,
 
+    /// represents Configuration.dual_slash
+    dual_slash: BannerEntry,
+
+    /// represents Configuration.detect_case
+    detect_case: BannerEntry,
+
+    /// represents Configuration.favicon
+    favicon: BannerEntry,
+
+    /// represents Configuration.favicon_db
+    favicon_db: BannerEntry,
+
+    /// represents Configuration.well_known
+    well_known: BannerEntry,
+
+    /// represents Configuration.bucket_listing
+    bucket_listing: BannerEntry,
+
+    /// represents Configuration.baseline
+    baseline: BannerEntry,
+
+    /// represents Configuration.baseline_url
+    baseline_url: BannerEntry,
+
+    /// represents Configuration.reuse_signatures
+    reuse_signatures: BannerEntry,
+
+    /// represents Configuration.encode
+    encode: BannerEntry,
+
+    /// represents Configuration.evasion
+    evasion: BannerEntry,
+
+    /// represents Configuration.range_probe
+    range_probe: BannerEntry,
+
+    /// represents Configuration.accept_encoding
+    accept_encoding: BannerEntry,
+
+    /// represents Configuration.decompress
+    decompress: BannerEntry,
+
     /// represents Configuration.no_recursion
     no_recursion: BannerEntry,
 
     /// represents Configuration.scan_limit
     scan_limit: BannerEntry,
 
+    /// represents Configuration.recursion_strategy
+    recursion_strategy: BannerEntry,
+
+    /// represents Configuration.shuffle_seed
+    shuffle_seed: BannerEntry,
+
+    /// represents Configuration.preview
+    preview: BannerEntry,
+
     /// represents Configuration.time_limit
     time_limit: BannerEntry,
 
     /// represents Configuration.rate_limit
     rate_limit: BannerEntry,
 
+    /// represents Configuration.rate_limit_global
+    rate_limit_global: BannerEntry,
+
+    /// represents Configuration.bandwidth_limit
+    bandwidth_limit: BannerEntry,
+
+    /// represents Configuration.pacing
+    pacing: BannerEntry,
+
+    /// represents Configuration.auto_resume
+    auto_resume: BannerEntry,
+
+    /// represents Configuration.canary
+    canary: BannerEntry,
+
+    /// represents Configuration.canary_interval
+    canary_interval: BannerEntry,
+
+    /// represents Configuration.stability_check and Configuration.stability_interval
+    stability_check: BannerEntry,
+
+    /// represents Configuration.server_alerts
+    server_alerts: BannerEntry,
+
+    /// represents Configuration.search_regex
+    search_regex: Vec<BannerEntry>,
+
+    /// represents Configuration.entropy_secrets
+    entropy_secrets: BannerEntry,
+
     /// represents Configuration.parallel
     parallel: BannerEntry,
 
     /// represents Configuration.auto_tune
     auto_tune: BannerEntry,
 
+    /// represents Configuration.tune_strategy
+    tune_strategy: BannerEntry,
+
+    /// represents Configuration.tune_on_latency
+    tune_on_latency: BannerEntry,
+
+    /// represents Configuration.policy_403_ratio
+    policy_403_ratio: BannerEntry,
+
+    /// represents Configuration.policy_429_ratio
+    policy_429_ratio: BannerEntry,
+
+    /// represents Configuration.policy_status
+    policy_status: BannerEntry,
+
+    /// represents Configuration.policy_error_threshold
+    policy_error_threshold: BannerEntry,
+
+    /// represents Configuration.policy_error_weights
+    policy_error_weights: BannerEntry,
+
+    /// represents Configuration.tune_step
+    tune_step: BannerEntry,
+
+    /// represents Configuration.tune_floor
+    tune_floor: BannerEntry,
+
+    /// represents Configuration.tune_ceiling
+    tune_ceiling: BannerEntry,
+
     /// represents Configuration.auto_bail
     auto_bail: BannerEntry,
 
+    /// represents Configuration.auto_requeue
+    auto_requeue: BannerEntry,
+
     /// represents Configuration.url_denylist
     url_denylist: Vec<BannerEntry>,
 
@@ -163,17 +449,36 @@ pub struct Banner {
     /// represents Configuration.collect_extensions
     collect_extensions: BannerEntry,
 
+    /// represents Configuration.prune_extensions and Configuration.prune_after
+    prune_extensions: BannerEntry,
+
     /// represents Configuration.dont_collect
     dont_collect: BannerEntry,
 
     /// represents Configuration.collect_backups
     collect_backups: BannerEntry,
 
+    /// represents Configuration.bypass_403, Configuration.bypass_limit and
+    /// Configuration.bypass_rate
+    bypass_403: BannerEntry,
+
+    /// represents Configuration.vcs_check
+    vcs_check: BannerEntry,
+
+    /// represents Configuration.archive_peek and Configuration.archive_max_size
+    archive_peek: BannerEntry,
+
     /// represents Configuration.collect_words
     collect_words: BannerEntry,
 
     /// represents Configuration.collect_words
     force_recursion: BannerEntry,
+
+    /// represents Configuration.directories_first
+    directories_first: BannerEntry,
+
+    /// represents Configuration.inherit_headers
+    inherit_headers: BannerEntry,
 }
 
 /// implementation of Banner
@@ -185,6 +490,7 @@ impl Banner {
         let mut code_filters = Vec::new();
         let mut replay_codes = Vec::new();
         let mut headers = Vec::new();
+        let mut replay_headers = Vec::new();
         let mut filter_size = Vec::new();
         let mut filter_similar = Vec::new();
         let mut filter_word_count = Vec::new();
@@ -196,6 +502,28 @@ impl Banner {
             targets.push(BannerEntry::new("🎯", "Target Url", target));
         }
 
+        let import_nmap = BannerEntry::new("🗺", "Nmap Import", &config.import_nmap);
+
+        let ports = BannerEntry::new(
+            "🔌",
+            "Ports",
+            &format!(
+                "[{}]",
+                config
+                    .ports
+                    .iter()
+                    .map(|port| port.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+
+        let scheme_probes = BannerEntry::new(
+            "🔀",
+            "Scheme Probes",
+            &format!("[{}]", config.scheme_probes.join(", ")),
+        );
+
         for denied_url in &config.url_denylist {
             url_denylist.push(BannerEntry::new(
                 "🚫",
@@ -258,6 +586,14 @@ impl Banner {
             ));
         }
 
+        for (name, value) in &config.replay_headers {
+            replay_headers.push(BannerEntry::new(
+                "🤯",
+                "Replay Header",
+                &format!("{name}: {value}"),
+            ));
+        }
+
         for filter in &config.filter_size {
             filter_size.push(BannerEntry::new("💢", "Size Filter", &filter.to_string()));
         }
@@ -286,6 +622,12 @@ impl Banner {
             filter_regex.push(BannerEntry::new("💢", "Regex Filter", filter));
         }
 
+        let filter_category = config
+            .filter_category
+            .iter()
+            .map(|category| BannerEntry::new("💢", "Category Filter", category))
+            .collect();
+
         for query in &config.queries {
             queries.push(BannerEntry::new(
                 "🤔",
@@ -324,13 +666,104 @@ impl Banner {
             &config.scan_limit.to_string(),
         );
 
+        let recursion_strategy =
+            BannerEntry::new("🌲", "Recursion Strategy", &config.recursion_strategy);
+
+        let shuffle_seed = BannerEntry::new("🎲", "Shuffle Seed", &config.shuffle_seed.to_string());
+        let preview = BannerEntry::new("🔍", "Preview Requests", &config.preview.to_string());
+
         let force_recursion =
             BannerEntry::new("🤘", "Force Recursion", &config.force_recursion.to_string());
+        let directories_first = BannerEntry::new(
+            "🗺",
+            "Directories First",
+            &config.directories_first.to_string(),
+        );
+        let inherit_headers = BannerEntry::new(
+            "🧬",
+            "Inherited Headers",
+            &config.inherit_headers.join(", "),
+        );
         let replay_proxy = BannerEntry::new("🎥", "Replay Proxy", &config.replay_proxy);
+        let request_id = BannerEntry::new(
+            "🔖",
+            "Request ID Header",
+            &format!("{}: <scan id>-N", config.request_id),
+        );
+        let replay_client_cert = BannerEntry::new(
+            "🏅",
+            "Replay Client Certificate",
+            &config.replay_client_cert,
+        );
+        let replay_client_key =
+            BannerEntry::new("🔑", "Replay Client Key", &config.replay_client_key);
         let auto_tune = BannerEntry::new("🎶", "Auto Tune", &config.auto_tune.to_string());
+        let tune_strategy = BannerEntry::new("🎼", "Tune Strategy", &config.tune_strategy);
+        let tune_on_latency = BannerEntry::new("🐢", "Tune On Latency", &config.tune_on_latency);
+        let policy_403_ratio = BannerEntry::new(
+            "🚫",
+            "Policy 403 Ratio",
+            &config.policy_403_ratio.to_string(),
+        );
+        let policy_429_ratio = BannerEntry::new(
+            "⏳",
+            "Policy 429 Ratio",
+            &config.policy_429_ratio.to_string(),
+        );
+        let policy_status = BannerEntry::new(
+            "🧮",
+            "Policy Status Codes",
+            &format!(
+                "[{}]",
+                config
+                    .policy_status
+                    .iter()
+                    .map(|code| status_colorizer(&code.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+        let policy_error_threshold = BannerEntry::new(
+            "💥",
+            "Policy Error Threshold",
+            &config.policy_error_threshold.to_string(),
+        );
+        let policy_error_weights = BannerEntry::new(
+            "⚖",
+            "Policy Error Weights",
+            &format!(
+                "[{}]",
+                config
+                    .policy_error_weights
+                    .iter()
+                    .collect::<BTreeMap<_, _>>()
+                    .iter()
+                    .map(|(category, weight)| format!("{category}={weight}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+        let tune_step = BannerEntry::new("📈", "Tune Step", &config.tune_step.to_string());
+        let tune_floor = BannerEntry::new("📉", "Tune Floor", &config.tune_floor.to_string());
+        let tune_ceiling = BannerEntry::new("🔝", "Tune Ceiling", &config.tune_ceiling.to_string());
         let auto_bail = BannerEntry::new("🙅", "Auto Bail", &config.auto_bail.to_string());
+        let auto_requeue = BannerEntry::new(
+            "🔁",
+            "Auto Requeue (attempts)",
+            &config.auto_requeue.to_string(),
+        );
         let cfg = BannerEntry::new("💉", "Config File", &config.config);
         let proxy = BannerEntry::new("💎", "Proxy", &config.proxy);
+        let proxy_failover = BannerEntry::new(
+            "🛟",
+            "Proxy Failover",
+            &format!("[{}]", config.proxy_failover.join(", ")),
+        );
+        let proxy_failover_direct = BannerEntry::new(
+            "🛟",
+            "Proxy Failover Direct",
+            &config.proxy_failover_direct.to_string(),
+        );
         let server_certs = BannerEntry::new(
             "🏅",
             "Server Certificates",
@@ -338,26 +771,155 @@ impl Banner {
         );
         let client_cert = BannerEntry::new("🏅", "Client Certificate", &config.client_cert);
         let client_key = BannerEntry::new("🔑", "Client Key", &config.client_key);
+        let ca_bundle = BannerEntry::new("🏛", "CA Bundle", &config.ca_bundle);
+        let pin_sha256 = BannerEntry::new(
+            "📌",
+            "Pinned Certificates",
+            &format!("[{}]", config.pin_sha256.join(", ")),
+        );
         let threads = BannerEntry::new("🚀", "Threads", &config.threads.to_string());
         let wordlist = BannerEntry::new("📖", "Wordlist", &config.wordlist);
+        let fuzz_mode = if config.fuzz_mode.is_empty() {
+            "clusterbomb"
+        } else {
+            config.fuzz_mode.as_str()
+        };
+        let fuzz_wordlists = BannerEntry::new(
+            "🧨",
+            "Fuzz Wordlists",
+            &format!("{} ({fuzz_mode})", config.fuzz_wordlists.join(", ")),
+        );
         let timeout = BannerEntry::new("💥", "Timeout (secs)", &config.timeout.to_string());
+        let connect_timeout = BannerEntry::new(
+            "💥",
+            "Connect Timeout (secs)",
+            &config.connect_timeout.to_string(),
+        );
+        let read_timeout = BannerEntry::new(
+            "💥",
+            "Read Timeout (secs)",
+            &config.read_timeout.to_string(),
+        );
         let user_agent = BannerEntry::new("🦡", "User-Agent", &config.user_agent);
         let random_agent = BannerEntry::new("🦡", "User-Agent", "Random");
         let extract_links =
             BannerEntry::new("🔎", "Extract Links", &config.extract_links.to_string());
+
+        let extraction_limits = [
+            ("page", config.extract_page_limit),
+            ("scan", config.extract_scan_limit),
+            ("total", config.extract_limit),
+            ("depth", config.extract_depth),
+        ]
+        .iter()
+        .filter(|(_, limit)| *limit > 0)
+        .map(|(name, limit)| format!("{name}: {limit}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+        let extraction_limits = BannerEntry::new("🧺", "Extraction Limits", &extraction_limits);
+        let crawl = BannerEntry::new("🕸", "Crawl (no wordlist)", &config.crawl.to_string());
         let json = BannerEntry::new("🧔", "JSON Output", &config.json.to_string());
+        let punycode = BannerEntry::new("🔤", "Punycode Hosts", &config.punycode.to_string());
+        let titles = BannerEntry::new("🏷", "Page Titles", &config.titles.to_string());
+        let fuzzy_hash =
+            BannerEntry::new("🔏", "Fuzzy Hash Bodies", &config.fuzzy_hash.to_string());
         let output = BannerEntry::new("💾", "Output File", &config.output);
         let debug_log = BannerEntry::new("🪲", "Debugging Log", &config.debug_log);
+        let burp_export = BannerEntry::new("🧾", "Burp Export", &config.burp_export);
+        let har_export = BannerEntry::new("📼", "HAR Export", &config.har_export);
+        let quiet_stream = BannerEntry::new("🤫", "Quiet Stream", &config.quiet_stream);
+        let traffic_log = BannerEntry::new("🚦", "Traffic Log", &config.traffic_log);
+        let traffic_log_max =
+            BannerEntry::new("🔄", "Traffic Log Max Size", &config.traffic_log_max);
+        let screenshots = BannerEntry::new("📸", "Screenshots", &config.screenshots);
+        let chrome = BannerEntry::new("🌐", "Screenshot Browser", &config.chrome);
+        let screenshot_rule = BannerEntry::new(
+            "📷",
+            "Screenshot Findings",
+            &format!(
+                "status [{}] and type [{}]",
+                config
+                    .screenshot_codes
+                    .iter()
+                    .map(|code| code.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                config.screenshot_types.join(", ")
+            ),
+        );
+        let traffic_log_bodies = BannerEntry::new(
+            "📃",
+            "Traffic Log Bodies",
+            &config.traffic_log_bodies.to_string(),
+        );
+        let policy_audit = BannerEntry::new("📒", "Policy Audit", &config.policy_audit);
+        let pipe_to = BannerEntry::new("🚰", "Pipe To", &config.pipe_to);
+        let api_export = BannerEntry::new("📮", "API Export", &config.api_export);
+        let api_export_format =
+            BannerEntry::new("📮", "API Export Format", &config.api_export_format);
+        let api_template = BannerEntry::new("📮", "API Template", &config.api_template);
+        let api_batch_size =
+            BannerEntry::new("📮", "API Batch Size", &config.api_batch_size.to_string());
+        let dojo_engagement =
+            BannerEntry::new("📮", "DefectDojo Engagement", &config.dojo_engagement);
+        let summary = BannerEntry::new("📋", "Summary", &config.summary);
+        let record = BannerEntry::new("📼", "Session Recording", &config.record);
+        let plugins = config
+            .plugins
+            .iter()
+            .map(|plugin| BannerEntry::new("🧩", "Plugin", plugin))
+            .collect();
+        let plugin_timeout = BannerEntry::new(
+            "⏲",
+            "Plugin Timeout (ms)",
+            &config.plugin_timeout.to_string(),
+        );
+        let categorize = BannerEntry::new("🗂", "Categorize", &config.categorize.to_string());
+        let category_rules = config
+            .category_rules
+            .iter()
+            .map(|rule| BannerEntry::new("🗂", "Category Rule", rule))
+            .collect();
+        let rules = BannerEntry::new("📏", "Rules", &config.rules);
+        let junit = BannerEntry::new("📏", "JUnit Report", &config.junit);
+        let fail_on_rule = BannerEntry::new("📏", "Fail On Rule", &config.fail_on_rule.to_string());
+        let exit_codes = BannerEntry::new(
+            "🚪",
+            "Exit Codes",
+            &format!(
+                "[{}]",
+                config
+                    .exit_codes
+                    .iter()
+                    .collect::<BTreeMap<_, _>>()
+                    .iter()
+                    .map(|(condition, code)| format!("{condition}={code}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+        let exit_error_ratio = BannerEntry::new(
+            "🚪",
+            "Exit Error Ratio",
+            &config.exit_error_ratio.to_string(),
+        );
         let extensions = BannerEntry::new(
             "💲",
             "Extensions",
             &format!("[{}]", config.extensions.join(", ")),
         );
+        let extension_order = BannerEntry::new("🧶", "Extension Order", &config.extension_order);
         let methods = BannerEntry::new(
             "🏁",
             "HTTP methods",
             &format!("[{}]", config.methods.join(", ")),
         );
+        let safe_methods_only = BannerEntry::new(
+            "🦺",
+            "Safe Methods Only",
+            &config.safe_methods_only.to_string(),
+        );
+        let raw_requests = BannerEntry::new("🔩", "Raw Requests", &config.raw_requests.to_string());
 
         let dont_collect = if config.dont_collect == DEFAULT_IGNORED_EXTENSIONS {
             // default has 30+ extensions, just trim it up
@@ -386,70 +948,268 @@ impl Banner {
             .replace('\r', "");
         let data = BannerEntry::new("💣", "HTTP Body", &data);
         let insecure = BannerEntry::new("🔓", "Insecure", &config.insecure.to_string());
+        let host_header = BannerEntry::new("🏠", "Host Header", &config.host_header);
+        let sni = BannerEntry::new("🪪", "TLS SNI", &config.sni);
+        let doh = BannerEntry::new("🛰", "DNS-over-HTTPS", &config.doh);
+        let ip_family = BannerEntry::new(
+            "🔢",
+            "IP Family",
+            &IpFamily::from_flags(config.ipv4, config.ipv6)
+                .map_or_else(String::new, |family| format!("{} only", family.name())),
+        );
+        let fan_out_ips = BannerEntry::new("🔱", "IP Fan-out", &config.fan_out_ips.to_string());
         let redirects = BannerEntry::new("📍", "Follow Redirects", &config.redirects.to_string());
         let dont_filter =
             BannerEntry::new("🤪", "Filter Wildcards", &(!config.dont_filter).to_string());
         let add_slash = BannerEntry::new("🪓", "Add Slash", &config.add_slash.to_string());
+        let dual_slash = BannerEntry::new("🔀", "Dual Slash", &config.dual_slash.to_string());
+        let detect_case = BannerEntry::new("🔡", "Detect Case", &config.detect_case.to_string());
+        let favicon = BannerEntry::new("🧿", "Favicon Hashes", &config.favicon.to_string());
+        let favicon_db = BannerEntry::new("🗃", "Favicon Database", &config.favicon_db);
+        let well_known = BannerEntry::new("🗝", "Well-Known URIs", &config.well_known.to_string());
+        let bucket_listing =
+            BannerEntry::new("🪣", "Bucket Listing", &config.bucket_listing.to_string());
+        let baseline = BannerEntry::new("📑", "Baseline", &config.baseline);
+        let baseline_url = BannerEntry::new("🆚", "Baseline Url", &config.baseline_url);
+        let reuse_signatures =
+            BannerEntry::new("🧾", "Reused Signatures", &config.reuse_signatures);
+        let encode = BannerEntry::new("🔣", "Word Encoding", &config.encode);
+        let evasion = match evasion::profile(&config.evasion, config) {
+            Ok(transforms) => format!(
+                "{} ({})",
+                config.evasion,
+                transforms
+                    .iter()
+                    .map(evasion::Transform::name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Err(_) => config.evasion.clone(),
+        };
+        let evasion = BannerEntry::new("🥷", "Evasion Profile", &evasion);
+        let range_probe = BannerEntry::new("📏", "Range Probe", &config.range_probe.to_string());
+        let accept_encoding = BannerEntry::new("📦", "Accept-Encoding", &config.accept_encoding);
+        let decompress = BannerEntry::new("📤", "Decompress", &config.decompress.to_string());
         let time_limit = BannerEntry::new("🕖", "Time Limit", &config.time_limit);
         let parallel = BannerEntry::new("🛤", "Parallel Scans", &config.parallel.to_string());
         let rate_limit =
             BannerEntry::new("🚧", "Requests per Second", &config.rate_limit.to_string());
+        let rate_limit_global = BannerEntry::new(
+            "🌍",
+            "Requests per Second (all scans)",
+            &config.rate_limit_global.to_string(),
+        );
+        let bandwidth_limit =
+            BannerEntry::new("📶", "Bandwidth Limit (per sec)", &config.bandwidth_limit);
+        let pacing = BannerEntry::new("💤", "Pacing", &config.pacing);
+        let auto_resume =
+            BannerEntry::new("🔌", "Auto Resume (secs)", &config.auto_resume.to_string());
+        let canary = BannerEntry::new("🐤", "Canary", &config.canary);
+        let canary_interval = BannerEntry::new(
+            "🐤",
+            "Canary Interval (secs)",
+            &config.canary_interval.to_string(),
+        );
+        let stability_check = BannerEntry::new(
+            "🎢",
+            "Stability Check (secs)",
+            &config.stability_interval.to_string(),
+        );
+        let server_alerts = BannerEntry::new(
+            "📡",
+            "Server Change Alerts",
+            &config.server_alerts.to_string(),
+        );
+        let search_regex = config
+            .search_regex
+            .iter()
+            .map(|pattern| BannerEntry::new("🔍", "Search Regex", pattern))
+            .collect();
+        let entropy_secrets = BannerEntry::new(
+            "🔐",
+            "Entropy Threshold (bits)",
+            &config.entropy_threshold.to_string(),
+        );
         let collect_extensions = BannerEntry::new(
             "💰",
             "Collect Extensions",
             &config.collect_extensions.to_string(),
         );
+        let prune_extensions = BannerEntry::new(
+            "✂",
+            "Prune Extensions After (reqs)",
+            &config.prune_after.to_string(),
+        );
         let collect_backups =
             BannerEntry::new("🏦", "Collect Backups", &config.collect_backups.to_string());
 
+        let bypass_rate = if config.bypass_rate > 0 {
+            format!("{} req/s", config.bypass_rate)
+        } else {
+            String::from("unlimited")
+        };
+        let bypass_403 = BannerEntry::new(
+            "🚪",
+            "403 Bypasses",
+            &format!("up to {} urls, {}", config.bypass_limit, bypass_rate),
+        );
+
+        let vcs_check = BannerEntry::new("🌳", "VCS Exposure Check", &config.vcs_check.to_string());
+
+        let archive_peek = BannerEntry::new(
+            "🗜",
+            "Archive Listing",
+            &format!("up to {}", config.archive_max_size),
+        );
+
         let collect_words =
             BannerEntry::new("🤑", "Collect Words", &config.collect_words.to_string());
 
         Self {
             targets,
+            import_nmap,
+            ports,
+            scheme_probes,
+            scheme_detections: Vec::new(),
             status_codes,
             threads,
             wordlist,
+            fuzz_wordlists,
             filter_status,
             timeout,
+            connect_timeout,
+            read_timeout,
             user_agent,
             random_agent,
             auto_bail,
+            auto_requeue,
             auto_tune,
+            tune_strategy,
+            tune_on_latency,
+            policy_403_ratio,
+            policy_429_ratio,
+            policy_status,
+            policy_error_threshold,
+            policy_error_weights,
+            tune_step,
+            tune_floor,
+            tune_ceiling,
             proxy,
+            proxy_failover,
+            proxy_failover_direct,
             client_cert,
             client_key,
+            ca_bundle,
+            pin_sha256,
             server_certs,
             replay_codes,
             replay_proxy,
+            replay_headers,
+            replay_client_cert,
+            replay_client_key,
             headers,
+            request_id,
             filter_size,
             filter_similar,
             filter_word_count,
             filter_line_count,
             filter_regex,
+            filter_category,
             extract_links,
+            extraction_limits,
+            crawl,
             parallel,
             json,
+            punycode,
+            fuzzy_hash,
+            titles,
             queries,
             output,
             debug_log,
+            burp_export,
+            har_export,
+            quiet_stream,
+            traffic_log,
+            traffic_log_max,
+            traffic_log_bodies,
+            policy_audit,
+            screenshots,
+            chrome,
+            screenshot_rule,
+            pipe_to,
+            api_export,
+            api_export_format,
+            api_template,
+            api_batch_size,
+            dojo_engagement,
+            summary,
+            record,
+            plugins,
+            plugin_timeout,
+            categorize,
+            category_rules,
+            rules,
+            junit,
+            fail_on_rule,
+            exit_codes,
+            exit_error_ratio,
             extensions,
+            extension_order,
             methods,
+            safe_methods_only,
+            raw_requests,
             data,
             insecure,
+            host_header,
+            sni,
+            doh,
+            ip_family,
+            fan_out_ips,
             dont_filter,
             redirects,
             verbosity,
             add_slash,
+            dual_slash,
+            detect_case,
+            favicon,
+            favicon_db,
+            well_known,
+            bucket_listing,
+            baseline,
+            baseline_url,
+            reuse_signatures,
+            encode,
+            evasion,
+            range_probe,
+            accept_encoding,
+            decompress,
             no_recursion,
             rate_limit,
+            rate_limit_global,
+            bandwidth_limit,
+            pacing,
+            auto_resume,
+            canary,
+            canary_interval,
+            stability_check,
+            server_alerts,
+            search_regex,
+            entropy_secrets,
             scan_limit,
+            recursion_strategy,
+            shuffle_seed,
+            preview,
             force_recursion,
+            directories_first,
+            inherit_headers,
             time_limit,
             url_denylist,
             collect_extensions,
+            prune_extensions,
             collect_backups,
+            bypass_403,
+            vcs_check,
+            archive_peek,
             collect_words,
             dont_collect,
             config: cfg,
@@ -489,6 +1249,24 @@ by Ben "epi" Risher {}                 ver: {}"#,
         format!("{bottom}\n{instructions}\n{addl_section}")
     }
 
+    /// list the schemes detected for schemeless targets (target as given, target that was
+    /// settled on)
+    pub fn add_scheme_detections(&mut self, detections: &[(String, String)]) {
+        self.scheme_detections = detections
+            .iter()
+            .map(|(given, detected)| {
+                BannerEntry::new("🔎", "Detected Scheme", &format!("{given} => {detected}"))
+            })
+            .collect();
+    }
+
+    /// show the id of this run in the --request-id entry, the part every header value sent has
+    /// in common
+    pub fn add_scan_id(&mut self, header: &str, scan_id: &str) {
+        self.request_id =
+            BannerEntry::new("🔖", "Request ID Header", &format!("{header}: {scan_id}-N"));
+    }
+
     /// Makes a request to the given url, expecting to receive a JSON response that contains a field
     /// named `tag_name` that holds a value representing the latest tagged release of this tool.
     ///
@@ -542,6 +1320,22 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{target}")?;
         }
 
+        if !config.import_nmap.is_empty() {
+            writeln!(&mut writer, "{}", self.import_nmap)?;
+        }
+
+        if !config.ports.is_empty() {
+            writeln!(&mut writer, "{}", self.ports)?;
+        }
+
+        if config.scheme_probes != ["https:443", "http:80"] {
+            writeln!(&mut writer, "{}", self.scheme_probes)?;
+        }
+
+        for detection in &self.scheme_detections {
+            writeln!(&mut writer, "{detection}")?;
+        }
+
         for denied_url in &self.url_denylist {
             writeln!(&mut writer, "{denied_url}")?;
         }
@@ -549,6 +1343,10 @@ by Ben "epi" Risher {}                 ver: {}"#,
         writeln!(&mut writer, "{}", self.threads)?;
         writeln!(&mut writer, "{}", self.wordlist)?;
 
+        if !config.fuzz_wordlists.is_empty() {
+            writeln!(&mut writer, "{}", self.fuzz_wordlists)?;
+        }
+
         if config.filter_status.is_empty() {
             // -C and -s are mutually exclusive, and -s meaning changes when -C is used
             // so only print one or the other
@@ -559,6 +1357,14 @@ by Ben "epi" Risher {}                 ver: {}"#,
 
         writeln!(&mut writer, "{}", self.timeout)?;
 
+        if config.connect_timeout > 0 {
+            writeln!(&mut writer, "{}", self.connect_timeout)?;
+        }
+
+        if config.read_timeout > 0 {
+            writeln!(&mut writer, "{}", self.read_timeout)?;
+        }
+
         if config.random_agent {
             writeln!(&mut writer, "{}", self.random_agent)?;
         } else {
@@ -574,6 +1380,14 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.proxy)?;
         }
 
+        if !config.proxy_failover.is_empty() {
+            writeln!(&mut writer, "{}", self.proxy_failover)?;
+        }
+
+        if config.proxy_failover_direct {
+            writeln!(&mut writer, "{}", self.proxy_failover_direct)?;
+        }
+
         if !config.client_cert.is_empty() {
             writeln!(&mut writer, "{}", self.client_cert)?;
         }
@@ -586,17 +1400,38 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.server_certs)?;
         }
 
+        if !config.ca_bundle.is_empty() {
+            writeln!(&mut writer, "{}", self.ca_bundle)?;
+        }
+
+        if !config.pin_sha256.is_empty() {
+            writeln!(&mut writer, "{}", self.pin_sha256)?;
+        }
+
         if !config.replay_proxy.is_empty() {
             // i include replay codes logic here because in config.rs, replay codes are set to the
             // value in status codes, meaning it's never empty
             writeln!(&mut writer, "{}", self.replay_proxy)?;
             writeln!(&mut writer, "{}", self.replay_codes)?;
+
+            for header in &self.replay_headers {
+                writeln!(&mut writer, "{header}")?;
+            }
+
+            if !config.replay_client_cert.is_empty() {
+                writeln!(&mut writer, "{}", self.replay_client_cert)?;
+                writeln!(&mut writer, "{}", self.replay_client_key)?;
+            }
         }
 
         for header in &self.headers {
             writeln!(&mut writer, "{header}")?;
         }
 
+        if !config.request_id.is_empty() {
+            writeln!(&mut writer, "{}", self.request_id)?;
+        }
+
         for filter in &self.filter_size {
             writeln!(&mut writer, "{filter}")?;
         }
@@ -617,14 +1452,42 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{filter}")?;
         }
 
+        for filter in &self.filter_category {
+            writeln!(&mut writer, "{filter}")?;
+        }
+
         if config.extract_links {
             writeln!(&mut writer, "{}", self.extract_links)?;
+
+            if config.extract_page_limit > 0
+                || config.extract_scan_limit > 0
+                || config.extract_limit > 0
+                || config.extract_depth > 0
+            {
+                writeln!(&mut writer, "{}", self.extraction_limits)?;
+            }
+
+            if config.crawl {
+                writeln!(&mut writer, "{}", self.crawl)?;
+            }
         }
 
         if config.json {
             writeln!(&mut writer, "{}", self.json)?;
         }
 
+        if config.punycode {
+            writeln!(&mut writer, "{}", self.punycode)?;
+        }
+
+        if config.fuzzy_hash {
+            writeln!(&mut writer, "{}", self.fuzzy_hash)?;
+        }
+
+        if config.titles {
+            writeln!(&mut writer, "{}", self.titles)?;
+        }
+
         for query in &self.queries {
             writeln!(&mut writer, "{query}")?;
         }
@@ -637,20 +1500,144 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.debug_log)?;
         }
 
+        if !config.burp_export.is_empty() {
+            writeln!(&mut writer, "{}", self.burp_export)?;
+        }
+
+        if !config.har_export.is_empty() {
+            writeln!(&mut writer, "{}", self.har_export)?;
+        }
+
+        if !config.quiet_stream.is_empty() {
+            writeln!(&mut writer, "{}", self.quiet_stream)?;
+        }
+
+        if !config.traffic_log.is_empty() {
+            writeln!(&mut writer, "{}", self.traffic_log)?;
+
+            if !config.traffic_log_max.is_empty() {
+                writeln!(&mut writer, "{}", self.traffic_log_max)?;
+            }
+
+            if config.traffic_log_bodies {
+                writeln!(&mut writer, "{}", self.traffic_log_bodies)?;
+            }
+        }
+
+        if !config.policy_audit.is_empty() {
+            writeln!(&mut writer, "{}", self.policy_audit)?;
+        }
+
+        if !config.screenshots.is_empty() {
+            writeln!(&mut writer, "{}", self.screenshots)?;
+
+            if !config.chrome.is_empty() {
+                writeln!(&mut writer, "{}", self.chrome)?;
+            }
+
+            writeln!(&mut writer, "{}", self.screenshot_rule)?;
+        }
+
+        if !config.pipe_to.is_empty() {
+            writeln!(&mut writer, "{}", self.pipe_to)?;
+        }
+
+        if !config.api_export.is_empty() {
+            writeln!(&mut writer, "{}", self.api_export)?;
+
+            if !config.api_export_format.is_empty() {
+                writeln!(&mut writer, "{}", self.api_export_format)?;
+            }
+
+            if !config.api_template.is_empty() {
+                writeln!(&mut writer, "{}", self.api_template)?;
+            }
+
+            writeln!(&mut writer, "{}", self.api_batch_size)?;
+
+            if !config.dojo_engagement.is_empty() {
+                writeln!(&mut writer, "{}", self.dojo_engagement)?;
+            }
+        }
+
+        if !config.summary.is_empty() {
+            writeln!(&mut writer, "{}", self.summary)?;
+        }
+
+        if !config.record.is_empty() {
+            writeln!(&mut writer, "{}", self.record)?;
+        }
+
+        for plugin in &self.plugins {
+            writeln!(&mut writer, "{plugin}")?;
+        }
+
+        if !config.plugins.is_empty() {
+            writeln!(&mut writer, "{}", self.plugin_timeout)?;
+        }
+
+        if config.categorize {
+            writeln!(&mut writer, "{}", self.categorize)?;
+        }
+
+        for rule in &self.category_rules {
+            writeln!(&mut writer, "{rule}")?;
+        }
+
+        if !config.rules.is_empty() {
+            writeln!(&mut writer, "{}", self.rules)?;
+        }
+
+        if !config.junit.is_empty() {
+            writeln!(&mut writer, "{}", self.junit)?;
+        }
+
+        if config.fail_on_rule {
+            writeln!(&mut writer, "{}", self.fail_on_rule)?;
+        }
+
+        if !config.exit_codes.is_empty() {
+            writeln!(&mut writer, "{}", self.exit_codes)?;
+
+            if config.exit_codes.contains_key("errors") {
+                writeln!(&mut writer, "{}", self.exit_error_ratio)?;
+            }
+        }
+
         if !config.extensions.is_empty() {
             writeln!(&mut writer, "{}", self.extensions)?;
         }
 
+        if !config.extension_order.is_empty() {
+            writeln!(&mut writer, "{}", self.extension_order)?;
+        }
+
         if config.collect_extensions {
             // dont-collect is active only when collect-extensions is used
             writeln!(&mut writer, "{}", self.collect_extensions)?;
             writeln!(&mut writer, "{}", self.dont_collect)?;
         }
 
+        if config.prune_extensions {
+            writeln!(&mut writer, "{}", self.prune_extensions)?;
+        }
+
         if config.collect_backups {
             writeln!(&mut writer, "{}", self.collect_backups)?;
         }
 
+        if config.bypass_403 {
+            writeln!(&mut writer, "{}", self.bypass_403)?;
+        }
+
+        if config.vcs_check {
+            writeln!(&mut writer, "{}", self.vcs_check)?;
+        }
+
+        if config.archive_peek {
+            writeln!(&mut writer, "{}", self.archive_peek)?;
+        }
+
         if config.collect_words {
             writeln!(&mut writer, "{}", self.collect_words)?;
         }
@@ -659,6 +1646,14 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.methods)?;
         }
 
+        if config.safe_methods_only {
+            writeln!(&mut writer, "{}", self.safe_methods_only)?;
+        }
+
+        if config.raw_requests {
+            writeln!(&mut writer, "{}", self.raw_requests)?;
+        }
+
         if !config.data.is_empty() {
             writeln!(&mut writer, "{}", self.data)?;
         }
@@ -667,11 +1662,70 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.insecure)?;
         }
 
+        if !config.host_header.is_empty() {
+            writeln!(&mut writer, "{}", self.host_header)?;
+
+            if !config.sni.is_empty() {
+                writeln!(&mut writer, "{}", self.sni)?;
+            }
+        }
+
+        if !config.doh.is_empty() {
+            writeln!(&mut writer, "{}", self.doh)?;
+        }
+
+        if config.ipv4 || config.ipv6 {
+            writeln!(&mut writer, "{}", self.ip_family)?;
+        }
+
+        if config.fan_out_ips {
+            writeln!(&mut writer, "{}", self.fan_out_ips)?;
+        }
+
         if config.auto_bail {
             writeln!(&mut writer, "{}", self.auto_bail)?;
         }
+        if config.auto_requeue > 0 {
+            writeln!(&mut writer, "{}", self.auto_requeue)?;
+        }
+        if config.auto_bail || config.auto_tune || config.auto_requeue > 0 {
+            if !config.tune_on_latency.is_empty() {
+                writeln!(&mut writer, "{}", self.tune_on_latency)?;
+            }
+            if config.policy_403_ratio != HIGH_ERROR_RATIO {
+                writeln!(&mut writer, "{}", self.policy_403_ratio)?;
+            }
+            if config.policy_429_ratio != HIGH_429_RATIO {
+                writeln!(&mut writer, "{}", self.policy_429_ratio)?;
+            }
+            if !config.policy_status.is_empty() {
+                writeln!(&mut writer, "{}", self.policy_status)?;
+            }
+            if config.policy_error_threshold != MIN_ERROR_THRESHOLD {
+                writeln!(&mut writer, "{}", self.policy_error_threshold)?;
+            }
+            if !config.policy_error_weights.is_empty() {
+                writeln!(&mut writer, "{}", self.policy_error_weights)?;
+            }
+        }
         if config.auto_tune {
             writeln!(&mut writer, "{}", self.auto_tune)?;
+
+            if !config.tune_strategy.is_empty() {
+                writeln!(&mut writer, "{}", self.tune_strategy)?;
+            }
+
+            if config.tune_strategy == "aimd" {
+                if config.tune_step > 0 {
+                    writeln!(&mut writer, "{}", self.tune_step)?;
+                }
+                if config.tune_floor > 0 {
+                    writeln!(&mut writer, "{}", self.tune_floor)?;
+                }
+                if config.tune_ceiling > 0 {
+                    writeln!(&mut writer, "{}", self.tune_ceiling)?;
+                }
+            }
         }
 
         if config.redirects {
@@ -690,14 +1744,90 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.add_slash)?;
         }
 
+        if config.dual_slash {
+            writeln!(&mut writer, "{}", self.dual_slash)?;
+        }
+
+        if config.detect_case {
+            writeln!(&mut writer, "{}", self.detect_case)?;
+        }
+
+        if config.favicon {
+            writeln!(&mut writer, "{}", self.favicon)?;
+
+            if !config.favicon_db.is_empty() {
+                writeln!(&mut writer, "{}", self.favicon_db)?;
+            }
+        }
+
+        if config.well_known {
+            writeln!(&mut writer, "{}", self.well_known)?;
+        }
+
+        if config.bucket_listing {
+            writeln!(&mut writer, "{}", self.bucket_listing)?;
+        }
+
+        if !config.baseline.is_empty() {
+            writeln!(&mut writer, "{}", self.baseline)?;
+        }
+
+        if !config.baseline_url.is_empty() {
+            writeln!(&mut writer, "{}", self.baseline_url)?;
+        }
+
+        if !config.reuse_signatures.is_empty() {
+            writeln!(&mut writer, "{}", self.reuse_signatures)?;
+        }
+
+        if !config.encode.is_empty() && config.encode != "none" {
+            writeln!(&mut writer, "{}", self.encode)?;
+        }
+
+        if !config.evasion.is_empty() {
+            writeln!(&mut writer, "{}", self.evasion)?;
+        }
+
+        if config.range_probe {
+            writeln!(&mut writer, "{}", self.range_probe)?;
+        }
+
+        if !config.accept_encoding.is_empty() {
+            writeln!(&mut writer, "{}", self.accept_encoding)?;
+        }
+
+        if config.decompress {
+            writeln!(&mut writer, "{}", self.decompress)?;
+        }
+
         writeln!(&mut writer, "{}", self.no_recursion)?;
 
         if config.force_recursion {
             writeln!(&mut writer, "{}", self.force_recursion)?;
         }
 
+        if config.directories_first {
+            writeln!(&mut writer, "{}", self.directories_first)?;
+        }
+
+        if !config.inherit_headers.is_empty() {
+            writeln!(&mut writer, "{}", self.inherit_headers)?;
+        }
+
         if config.scan_limit > 0 {
             writeln!(&mut writer, "{}", self.scan_limit)?;
+
+            if !config.recursion_strategy.is_empty() {
+                writeln!(&mut writer, "{}", self.recursion_strategy)?;
+            }
+        }
+
+        if config.shuffle {
+            writeln!(&mut writer, "{}", self.shuffle_seed)?;
+        }
+
+        if config.preview > 0 {
+            writeln!(&mut writer, "{}", self.preview)?;
         }
 
         if config.parallel > 0 {
@@ -708,6 +1838,43 @@ by Ben "epi" Risher {}                 ver: {}"#,
             writeln!(&mut writer, "{}", self.rate_limit)?;
         }
 
+        if config.rate_limit_global > 0 {
+            writeln!(&mut writer, "{}", self.rate_limit_global)?;
+        }
+
+        if !config.bandwidth_limit.is_empty() {
+            writeln!(&mut writer, "{}", self.bandwidth_limit)?;
+        }
+
+        if !config.pacing.is_empty() {
+            writeln!(&mut writer, "{}", self.pacing)?;
+        }
+
+        if config.auto_resume > 0 {
+            writeln!(&mut writer, "{}", self.auto_resume)?;
+        }
+
+        if !config.canary.is_empty() {
+            writeln!(&mut writer, "{}", self.canary)?;
+            writeln!(&mut writer, "{}", self.canary_interval)?;
+        }
+
+        if config.stability_check {
+            writeln!(&mut writer, "{}", self.stability_check)?;
+        }
+
+        if config.server_alerts {
+            writeln!(&mut writer, "{}", self.server_alerts)?;
+        }
+
+        for pattern in &self.search_regex {
+            writeln!(&mut writer, "{pattern}")?;
+        }
+
+        if config.entropy_secrets {
+            writeln!(&mut writer, "{}", self.entropy_secrets)?;
+        }
+
         if !config.time_limit.is_empty() {
             writeln!(&mut writer, "{}", self.time_limit)?;
         }
@@ -0,0 +1,146 @@
+//! conditional requests against the results of a previous scan (--baseline)
+//!
+//! responses recorded by an earlier run (its --json output or .state file) that carried an
+//! `ETag` or `Last-Modified` header are requested again with `If-None-Match`/`If-Modified-Since`.
+//! a 304 means the resource hasn't changed since then; it's reported with the status and size
+//! seen last time and tagged as unchanged
+use std::{collections::HashMap, fs};
+
+use anyhow::{Context, Result};
+use reqwest::{
+    header::{ETAG, LAST_MODIFIED},
+    StatusCode, Url,
+};
+use serde_json::Value;
+
+use crate::{config::Configuration, response::FeroxResponse, utils::fmt_err};
+
+/// tag attached to responses whose resource hasn't changed since the baseline scan
+pub const UNCHANGED: &str = "unchanged";
+
+/// key under which a response to the given method/url is stored
+fn key(method: &str, url: &str) -> String {
+    format!("{method} {url}")
+}
+
+/// pull every response out of a previous scan's output; .state files hold a single json object
+/// with a list of responses, --json output is one entry per line, only some of which are
+/// responses
+fn parse_responses(contents: &str) -> Vec<FeroxResponse> {
+    if let Ok(state) = serde_json::from_str::<Value>(contents) {
+        if let Some(responses) = state.get("responses").and_then(|r| r.as_array()) {
+            return responses
+                .iter()
+                .filter_map(|response| serde_json::from_value(response.clone()).ok())
+                .collect();
+        }
+    }
+
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|entry| entry["type"] == "response")
+        .filter_map(|entry| serde_json::from_value(entry).ok())
+        .collect()
+}
+
+/// Responses from the baseline scan that can be requested conditionally, keyed by method and
+/// url; empty when --baseline isn't used
+#[derive(Debug, Default)]
+pub struct Baseline {
+    /// the responses, keyed by `key`
+    known: HashMap<String, FeroxResponse>,
+}
+
+/// load the responses from the file given to --baseline; empty when --baseline wasn't used
+pub fn initialize(config: &Configuration) -> Result<Baseline> {
+    if config.baseline.is_empty() {
+        return Ok(Baseline::default());
+    }
+
+    let contents = fs::read_to_string(&config.baseline)
+        .with_context(|| fmt_err(&format!("Could not read baseline {}", config.baseline)))?;
+
+    // responses without a validator can't be requested conditionally, no sense keeping them
+    let known: HashMap<_, _> = parse_responses(&contents)
+        .into_iter()
+        .filter(|response| {
+            response.headers().contains_key(ETAG) || response.headers().contains_key(LAST_MODIFIED)
+        })
+        .map(|response| {
+            (
+                key(response.method().as_str(), response.url().as_str()),
+                response,
+            )
+        })
+        .collect();
+
+    log::info!(
+        "loaded {} conditional responses from {}",
+        known.len(),
+        config.baseline
+    );
+
+    Ok(Baseline { known })
+}
+
+impl Baseline {
+    /// `If-None-Match`/`If-Modified-Since` headers for the given request, built from the
+    /// validators the baseline scan saw; empty when the url isn't part of the baseline
+    pub(crate) fn conditional_headers(&self, url: &Url, method: &str) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+
+        if let Some(known) = self.known.get(&key(method, url.as_str())) {
+            let validators = [
+                (ETAG, "If-None-Match"),
+                (LAST_MODIFIED, "If-Modified-Since"),
+            ];
+
+            for (name, conditional) in validators {
+                if let Some(value) = known.headers().get(name).and_then(|v| v.to_str().ok()) {
+                    headers.push((conditional.to_string(), value.to_string()));
+                }
+            }
+        }
+
+        headers
+    }
+
+    /// when the given response is a 304 to a conditional request, fill it in with what the
+    /// baseline scan saw and tag it as unchanged
+    pub(crate) fn resolve(&self, response: &mut FeroxResponse) {
+        if response.status() != &StatusCode::NOT_MODIFIED {
+            return;
+        }
+
+        let key = key(response.method().as_str(), response.url().as_str());
+
+        if let Some(known) = self.known.get(&key) {
+            response.mark_unchanged(known);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// responses should be found in both --json output and .state files
+    fn baseline_parse_responses_reads_json_and_state_files() {
+        let response = r#"{"type":"response","url":"http://localhost/LICENSE","original_url":"http://localhost","path":"/LICENSE","wildcard":false,"status":200,"method":"GET","content_length":14,"line_count":1,"word_count":4,"headers":{"etag":"\"abc\""},"extension":""}"#;
+        let statistics = r#"{"type":"statistics","timeouts":0}"#;
+
+        let json_output = format!("{response}\n{statistics}\n");
+        let parsed = parse_responses(&json_output);
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].url().as_str(), "http://localhost/LICENSE");
+        assert!(parsed[0].headers().contains_key(ETAG));
+
+        let state_file = format!(r#"{{"version":2,"responses":[{response}]}}"#);
+        assert_eq!(parse_responses(&state_file).len(), 1);
+
+        assert!(parse_responses("not json").is_empty());
+    }
+}
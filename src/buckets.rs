@@ -0,0 +1,371 @@
+//! cloud storage buckets found in extracted links
+//!
+//! links to S3, Google Cloud Storage, and Azure blob storage are out of scope for the scan, but
+//! they're worth knowing about. instead of dropping them along with the rest of the out of scope
+//! links, each bucket is reported once, printed alongside the responses and written to --output
+//! (as `{"type":"bucket",...}` with --json)
+//!
+//! with --bucket-listing, each bucket is also asked for a listing of its contents; a bucket that
+//! answers with one is flagged as publicly listable
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use console::style;
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::OutputLevel,
+    event_handlers::{Command, Handles},
+    traits::FeroxSerialize,
+    utils::{fmt_err, logged_request},
+    DEFAULT_METHOD,
+};
+
+/// Buckets that were already reported, as provider:bucket
+#[derive(Debug, Default)]
+pub struct BucketReports {
+    /// provider:bucket of each bucket seen so far
+    reported: Mutex<HashSet<String>>,
+}
+
+lazy_static! {
+    /// virtual-hosted style s3, i.e. bucket.s3.amazonaws.com or bucket.s3.us-east-2.amazonaws.com
+    static ref S3_VIRTUAL_HOST: Regex =
+        Regex::new(r"^(?P<bucket>[a-z0-9][a-z0-9.-]+)\.s3(?:[.-][a-z0-9-]+)?\.amazonaws\.com$").unwrap();
+
+    /// path style s3, i.e. s3.amazonaws.com/bucket or s3-us-west-2.amazonaws.com/bucket
+    static ref S3_PATH_HOST: Regex = Regex::new(r"^s3(?:[.-][a-z0-9-]+)?\.amazonaws\.com$").unwrap();
+
+    /// virtual-hosted style gcs, i.e. bucket.storage.googleapis.com
+    static ref GCS_VIRTUAL_HOST: Regex =
+        Regex::new(r"^(?P<bucket>[a-z0-9][a-z0-9._-]+)\.storage\.googleapis\.com$").unwrap();
+
+    /// azure blob storage, i.e. account.blob.core.windows.net/container
+    static ref AZURE_HOST: Regex =
+        Regex::new(r"^(?P<account>[a-z0-9]{3,24})\.blob\.core\.windows\.net$").unwrap();
+}
+
+/// Cloud storage provider a bucket belongs to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// Amazon S3
+    S3,
+
+    /// Google Cloud Storage
+    Gcs,
+
+    /// Azure blob storage; the bucket is account/container
+    Azure,
+}
+
+/// Display implementation for Provider
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::S3 => write!(f, "s3"),
+            Self::Gcs => write!(f, "gcs"),
+            Self::Azure => write!(f, "azure"),
+        }
+    }
+}
+
+/// A cloud storage bucket found in an extracted link
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BucketInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"bucket"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// provider the bucket belongs to
+    pub provider: Provider,
+
+    /// name of the bucket (account/container for azure)
+    pub bucket: String,
+
+    /// the link that pointed at the bucket
+    pub url: String,
+
+    /// page the link was found on
+    pub source: String,
+
+    /// whether the bucket lists its contents publicly, `None` unless --bucket-listing was used
+    pub listable: Option<bool>,
+}
+
+/// FeroxSerialize implementation for BucketInfo
+impl FeroxSerialize for BucketInfo {
+    /// one line summary, i.e. `BKT s3 assets-bucket https://assets-bucket.s3.amazonaws.com/app.js
+    /// [listable]`
+    fn as_str(&self) -> String {
+        let listable = match self.listable {
+            Some(true) => format!(" {}", style("[listable]").bright().red()),
+            Some(false) => format!(" {}", style("[not listable]").dim()),
+            None => String::new(),
+        };
+
+        format!(
+            "{} {} {} {}{}\n",
+            style("BKT").bright().magenta(),
+            self.provider,
+            style(&self.bucket).yellow(),
+            self.url,
+            listable
+        )
+    }
+
+    /// Create an NDJSON representation of the bucket
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the {} bucket {} to JSON",
+                self.provider, self.bucket
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// the provider and name of the bucket the given url points at, if any
+pub fn detect(url: &Url) -> Option<(Provider, String)> {
+    let host = url.host_str()?.to_lowercase();
+
+    // first path segment, which is the bucket (or container) for path style urls
+    let first_segment = url
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string);
+
+    if let Some(captures) = S3_VIRTUAL_HOST.captures(&host) {
+        return Some((Provider::S3, captures["bucket"].to_string()));
+    }
+
+    if S3_PATH_HOST.is_match(&host) {
+        return first_segment.map(|bucket| (Provider::S3, bucket));
+    }
+
+    if let Some(captures) = GCS_VIRTUAL_HOST.captures(&host) {
+        return Some((Provider::Gcs, captures["bucket"].to_string()));
+    }
+
+    if host == "storage.googleapis.com" || host == "storage.cloud.google.com" {
+        return first_segment.map(|bucket| (Provider::Gcs, bucket));
+    }
+
+    if let Some(captures) = AZURE_HOST.captures(&host) {
+        let account = &captures["account"];
+
+        return Some(match first_segment {
+            Some(container) => (Provider::Azure, format!("{account}/{container}")),
+            None => (Provider::Azure, account.to_string()),
+        });
+    }
+
+    None
+}
+
+/// url that lists the contents of the given bucket, when the bucket allows it
+fn listing_url(provider: Provider, bucket: &str) -> Option<Url> {
+    let url = match provider {
+        Provider::S3 => format!("https://{bucket}.s3.amazonaws.com/?list-type=2&max-keys=1"),
+        Provider::Gcs => format!("https://storage.googleapis.com/{bucket}/?max-keys=1"),
+        Provider::Azure => {
+            // a listing needs the container, an account on its own can't be checked
+            let (account, container) = bucket.split_once('/')?;
+            format!("https://{account}.blob.core.windows.net/{container}?restype=container&comp=list&maxresults=1")
+        }
+    };
+
+    Url::parse(&url).ok()
+}
+
+/// whether the given bucket answers with a listing of its contents
+async fn is_listable(provider: Provider, bucket: &str, handles: Arc<Handles>) -> Option<bool> {
+    let url = listing_url(provider, bucket)?;
+
+    let response = match logged_request(&url, DEFAULT_METHOD, None, handles).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("Could not check whether {} is listable: {}", url, e);
+            return None;
+        }
+    };
+
+    if !response.status().is_success() {
+        return Some(false);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+
+    Some(body.contains("<ListBucketResult") || body.contains("<EnumerationResults"))
+}
+
+/// report the bucket the given out of scope link points at, once per bucket; does nothing when
+/// the link doesn't point at a bucket
+///
+/// `source` is the page the link was found on
+pub fn observe(link: &Url, source: &str, handles: &Arc<Handles>) {
+    let Some((provider, bucket)) = detect(link) else {
+        return;
+    };
+
+    let first_sighting = handles
+        .features
+        .buckets
+        .reported
+        .lock()
+        .map(|mut reported| reported.insert(format!("{provider}:{bucket}")))
+        .unwrap_or_default();
+
+    if !first_sighting {
+        return;
+    }
+
+    let mut info = BucketInfo {
+        kind: String::from("bucket"),
+        provider,
+        bucket,
+        url: link.to_string(),
+        source: source.to_string(),
+        listable: None,
+    };
+
+    log::info!("{:?}", info);
+
+    if !handles.config.bucket_listing {
+        handles
+            .output
+            .send(Command::ReportBucket(Box::new(info)))
+            .unwrap_or_default();
+        return;
+    }
+
+    let handles = handles.clone();
+
+    tokio::spawn(async move {
+        info.listable = is_listable(info.provider, &info.bucket, handles.clone()).await;
+
+        handles
+            .output
+            .send(Command::ReportBucket(Box::new(info)))
+            .unwrap_or_default();
+    });
+}
+
+/// whether buckets are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// detect the bucket in the given url
+    fn detect_str(url: &str) -> Option<(Provider, String)> {
+        detect(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    /// virtual-hosted and path style urls are recognized for every provider
+    fn detect_finds_buckets() {
+        let expected = [
+            (
+                "https://assets.s3.amazonaws.com/app.js",
+                Provider::S3,
+                "assets",
+            ),
+            (
+                "https://my.assets.s3.us-east-2.amazonaws.com/",
+                Provider::S3,
+                "my.assets",
+            ),
+            (
+                "https://s3-us-west-2.amazonaws.com/backups/db.sql",
+                Provider::S3,
+                "backups",
+            ),
+            (
+                "https://s3.amazonaws.com/media/img.png",
+                Provider::S3,
+                "media",
+            ),
+            (
+                "https://storage.googleapis.com/static-site/index.html",
+                Provider::Gcs,
+                "static-site",
+            ),
+            (
+                "https://uploads.storage.googleapis.com/a.txt",
+                Provider::Gcs,
+                "uploads",
+            ),
+            (
+                "https://acct01.blob.core.windows.net/public/doc.pdf",
+                Provider::Azure,
+                "acct01/public",
+            ),
+        ];
+
+        for (url, provider, bucket) in expected {
+            assert_eq!(
+                detect_str(url),
+                Some((provider, bucket.to_string())),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    /// other hosts, and path style hosts without a bucket, aren't buckets
+    fn detect_ignores_everything_else() {
+        assert!(detect_str("https://example.com/s3.amazonaws.com/").is_none());
+        assert!(detect_str("https://s3.amazonaws.com/").is_none());
+        assert!(detect_str("https://googleapis.com/storage").is_none());
+    }
+
+    #[test]
+    /// each provider is asked for a listing its own way; an azure account needs a container
+    fn listing_urls_per_provider() {
+        assert_eq!(
+            listing_url(Provider::S3, "assets").unwrap().as_str(),
+            "https://assets.s3.amazonaws.com/?list-type=2&max-keys=1"
+        );
+        assert_eq!(
+            listing_url(Provider::Gcs, "static-site").unwrap().as_str(),
+            "https://storage.googleapis.com/static-site/?max-keys=1"
+        );
+        assert!(listing_url(Provider::Azure, "acct01").is_none());
+        assert!(listing_url(Provider::Azure, "acct01/public")
+            .unwrap()
+            .as_str()
+            .contains("restype=container&comp=list"));
+    }
+
+    #[test]
+    /// json output is tagged with its own type
+    fn bucket_info_as_json() {
+        let info = BucketInfo {
+            kind: String::from("bucket"),
+            provider: Provider::S3,
+            bucket: String::from("assets"),
+            url: String::from("https://assets.s3.amazonaws.com/app.js"),
+            source: String::from("http://localhost/"),
+            listable: Some(true),
+        };
+
+        let json = info.as_json().unwrap();
+        assert!(json.starts_with(r#"{"type":"bucket","provider":"s3","bucket":"assets""#));
+        assert!(json.ends_with("\"listable\":true}\n"));
+        assert!(info.as_str().contains("[listable]"));
+    }
+}
@@ -0,0 +1,537 @@
+//! 403 bypass attempts against forbidden urls (--bypass-403)
+//!
+//! every url that comes back as a 403 finding gets a battery of variants thrown at it: case
+//! tricks on its last segment, `%2e;` segments, trailing `%20`/`%09`/`%2e`/`..;/`, a doubled
+//! leading slash, and X-Forwarded-For/X-Real-IP/X-Original-URL/X-Rewrite-URL headers. variants
+//! that come back with a status other than 403 or 404 (and one that the scan would report) are
+//! shown as derived findings of their own, and written to --output (as `{"type":"bypass",...}`
+//! with --json)
+//!
+//! a bare `/%2e/` segment would be folded away by the url parser before it's ever sent, which is
+//! why `/%2e;/` is used in its place
+//!
+//! at most --bypass-limit forbidden urls are attempted, at no more than --bypass-rate requests
+//! per second, so a site full of 403s doesn't turn into a flood of requests
+use std::{
+    cmp::max,
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use console::style;
+use leaky_bucket::RateLimiter;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::{
+    config::{Configuration, OutputLevel},
+    event_handlers::{
+        Command::{self, AddToUsizeField},
+        Handles,
+    },
+    response::FeroxResponse,
+    send_command,
+    statistics::StatField::TotalExpected,
+    traits::FeroxSerialize,
+    utils::{fmt_err, logged_request_with_headers, should_deny_url},
+};
+
+/// address sent in the ip spoofing headers
+const LOCALHOST: &str = "127.0.0.1";
+
+/// Bypass attempts made so far, along with the limiter they share
+#[derive(Default)]
+pub struct BypassAttempts {
+    /// forbidden urls that were already attempted
+    attempted: Mutex<HashSet<String>>,
+
+    /// attempts that are still running
+    pending: Mutex<Vec<JoinHandle<()>>>,
+
+    /// shared by every attempt, enforces --bypass-rate
+    limiter: Option<RateLimiter>,
+}
+
+/// Debug implementation for BypassAttempts; the limiter has none of its own
+impl fmt::Debug for BypassAttempts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BypassAttempts")
+            .field("attempted", &self.attempted)
+            .field("pending", &self.pending)
+            .field("limiter", &self.limiter.is_some())
+            .finish()
+    }
+}
+
+/// A single request to try against a forbidden url
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// short description of the trick, i.e. `trailing %20`
+    pub label: String,
+
+    /// url to request
+    pub url: Url,
+
+    /// headers sent along with the request
+    pub headers: Vec<(String, String)>,
+}
+
+/// implementation of Variant
+impl Variant {
+    /// variant that only changes the path of the given url
+    fn path(url: &Url, label: &str, path: &str) -> Self {
+        let mut changed = url.clone();
+        changed.set_path(path);
+
+        Self {
+            label: label.to_string(),
+            url: changed,
+            headers: Vec::new(),
+        }
+    }
+
+    /// variant that requests the given url with an extra header
+    fn header(url: &Url, name: &str, value: &str) -> Self {
+        Self {
+            label: format!("{name}: {value}"),
+            url: url.clone(),
+            headers: vec![(name.to_string(), value.to_string())],
+        }
+    }
+}
+
+/// A variant that got past a 403
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct BypassInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"bypass"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// forbidden url the variant was derived from
+    pub url: String,
+
+    /// trick that was used
+    pub variant: String,
+
+    /// url that was requested
+    pub request_url: String,
+
+    /// headers sent along with the request, as `Name: value`
+    pub headers: Vec<String>,
+
+    /// http method used
+    pub method: String,
+
+    /// status code the variant got
+    pub status: u16,
+
+    /// size of the variant's response body, in bytes
+    pub content_length: usize,
+}
+
+/// FeroxSerialize implementation for BypassInfo
+impl FeroxSerialize for BypassInfo {
+    /// one line summary, i.e.
+    /// `BYP 200 GET 1337c http://localhost/ADMIN [403 bypass of http://localhost/admin: uppercase]`
+    fn as_str(&self) -> String {
+        format!(
+            "{} {} {:>5} {:>9}c {} {}\n",
+            style("BYP").bright().magenta(),
+            style(self.status).green(),
+            self.method,
+            self.content_length,
+            self.request_url,
+            style(format!("[403 bypass of {}: {}]", self.url, self.variant))
+                .bright()
+                .yellow()
+        )
+    }
+
+    /// Create an NDJSON representation of the bypass
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the bypass of {} to JSON",
+                self.url
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// every variant to try against the given forbidden url; nothing for the root of a site
+pub fn variants(url: &Url) -> Vec<Variant> {
+    let path = url.path();
+    let trimmed = path.trim_end_matches('/');
+    let slash = if path.ends_with('/') { "/" } else { "" };
+
+    let Some((parent, name)) = trimmed.rsplit_once('/') else {
+        return Vec::new();
+    };
+
+    if name.is_empty() {
+        return Vec::new();
+    }
+
+    let mut variants = Vec::new();
+
+    // case tricks, for servers that check the path case sensitively but serve it insensitively
+    let upper = name.to_uppercase();
+    if upper != name {
+        variants.push(Variant::path(
+            url,
+            "uppercase",
+            &format!("{parent}/{upper}{slash}"),
+        ));
+    }
+
+    let mut chars = name.chars();
+    let capitalized = chars
+        .next()
+        .map(|first| format!("{}{}", first.to_uppercase(), chars.as_str()))
+        .unwrap_or_default();
+    if capitalized != name && capitalized != upper {
+        variants.push(Variant::path(
+            url,
+            "capitalized",
+            &format!("{parent}/{capitalized}{slash}"),
+        ));
+    }
+
+    // path normalization tricks
+    variants.push(Variant::path(
+        url,
+        "%2e segment",
+        &format!("{parent}/%2e;/{name}{slash}"),
+    ));
+
+    for (label, suffix) in [
+        ("trailing %20", "%20"),
+        ("trailing %09", "%09"),
+        ("trailing %2e", "%2e"),
+    ] {
+        variants.push(Variant::path(
+            url,
+            label,
+            &format!("{parent}/{name}{suffix}{slash}"),
+        ));
+    }
+
+    variants.push(Variant::path(
+        url,
+        "trailing ..;/",
+        &format!("{trimmed}..;/"),
+    ));
+
+    let toggled = if slash.is_empty() {
+        ("trailing slash", format!("{trimmed}/"))
+    } else {
+        ("no trailing slash", trimmed.to_string())
+    };
+    variants.push(Variant::path(url, toggled.0, &toggled.1));
+
+    variants.push(Variant::path(url, "double slash", &format!("/{path}")));
+
+    // headers trusted by some proxies/frameworks
+    for name in ["X-Forwarded-For", "X-Real-IP"] {
+        variants.push(Variant::header(url, name, LOCALHOST));
+    }
+
+    // request the root and let the rewrite header point to the forbidden path
+    let mut root = url.clone();
+    root.set_path("/");
+
+    let rewritten = match url.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+
+    for name in ["X-Original-URL", "X-Rewrite-URL"] {
+        variants.push(Variant::header(&root, name, &rewritten));
+    }
+
+    variants
+}
+
+/// build the --bypass-rate limiter; same refill approach as the scan's own --rate-limit
+fn build_a_bucket(limit: usize) -> RateLimiter {
+    let refill = max((limit as f64 / 10.0).round() as usize, 1);
+    let interval = if refill == 1 { 1000 } else { 100 };
+
+    RateLimiter::builder()
+        .interval(Duration::from_millis(interval))
+        .refill(refill)
+        .initial(max(limit / 2, 1))
+        .max(limit)
+        .build()
+}
+
+/// set up the --bypass-rate limiter, when bypasses are attempted at all
+pub fn initialize(config: &Configuration) -> BypassAttempts {
+    let limiter =
+        (config.bypass_403 && config.bypass_rate > 0).then(|| build_a_bucket(config.bypass_rate));
+
+    BypassAttempts {
+        limiter,
+        ..Default::default()
+    }
+}
+
+/// whether a variant's status is one the scan would report, following the same -s/-C rules as
+/// the findings themselves; a 404 only means the variant doesn't point at anything
+fn is_reported(status: StatusCode, config: &Configuration) -> bool {
+    if matches!(status, StatusCode::FORBIDDEN | StatusCode::NOT_FOUND) {
+        return false;
+    }
+
+    if !config.filter_status.is_empty() {
+        !config.filter_status.contains(&status.as_u16())
+    } else {
+        config.status_codes.contains(&status.as_u16())
+    }
+}
+
+/// throw every variant at the given forbidden url, reporting the ones that get through
+async fn attempt(url: Url, method: String, handles: Arc<Handles>) {
+    log::trace!("enter: attempt({}, {}, {:?})", url, method, handles);
+
+    let mut variants = variants(&url);
+
+    if !handles.config.url_denylist.is_empty() || !handles.config.regex_denylist.is_empty() {
+        // a variant can be denied where the forbidden url wasn't, i.e. /logout%20 for a
+        // --dont-scan-regex of logout%20; the request would be refused anyway, they're dropped
+        // here so they aren't counted toward the expected total
+        variants.retain(|variant| !should_deny_url(&variant.url, &handles).unwrap_or(false));
+    }
+
+    // need to manually adjust stats
+    send_command!(
+        handles.stats.tx,
+        AddToUsizeField(TotalExpected, variants.len())
+    );
+
+    let limiter = &handles.features.bypass.limiter;
+
+    let data = if handles.config.data.is_empty() {
+        None
+    } else {
+        Some(handles.config.data.as_slice())
+    };
+
+    for variant in variants {
+        if let Some(limiter) = limiter {
+            limiter.acquire_one().await;
+        }
+
+        let response = match logged_request_with_headers(
+            &variant.url,
+            &method,
+            data,
+            &variant.headers,
+            handles.clone(),
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                log::warn!(
+                    "Could not request {} ({}): {}",
+                    variant.url,
+                    variant.label,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let status = response.status();
+
+        if !is_reported(status, &handles.config) {
+            continue;
+        }
+
+        let content_length = response
+            .bytes()
+            .await
+            .map(|body| body.len())
+            .unwrap_or_default();
+
+        let info = BypassInfo {
+            kind: String::from("bypass"),
+            url: url.to_string(),
+            variant: variant.label,
+            request_url: variant.url.to_string(),
+            headers: variant
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect(),
+            method: method.clone(),
+            status: status.as_u16(),
+            content_length,
+        };
+
+        log::info!("{:?}", info);
+
+        handles
+            .output
+            .send(Command::ReportBypass(Box::new(info)))
+            .unwrap_or_default();
+    }
+
+    log::trace!("exit: attempt");
+}
+
+/// queue up bypass attempts against the given finding, if it's a 403 that hasn't been attempted
+/// yet and --bypass-limit hasn't been reached; does nothing unless --bypass-403 is used
+pub fn on_finding(response: &FeroxResponse, handles: Arc<Handles>) {
+    if !handles.config.bypass_403 || *response.status() != StatusCode::FORBIDDEN {
+        return;
+    }
+
+    let url = response.url().clone();
+
+    let bypass = &handles.features.bypass;
+
+    let Ok(mut attempted) = bypass.attempted.lock() else {
+        return;
+    };
+
+    if attempted.contains(url.as_str()) {
+        return;
+    }
+
+    if attempted.len() >= handles.config.bypass_limit {
+        log::debug!("--bypass-limit reached, not attempting bypasses of {}", url);
+        return;
+    }
+
+    attempted.insert(url.to_string());
+    drop(attempted);
+
+    let task = tokio::spawn(attempt(url, response.method().to_string(), handles.clone()));
+
+    if let Ok(mut pending) = bypass.pending.lock() {
+        pending.push(task);
+    }
+}
+
+/// wait for every queued bypass attempt to finish, so their findings make it out before the
+/// output handler is closed
+pub async fn finish(handles: &Handles) {
+    loop {
+        let tasks: Vec<_> = match handles.features.bypass.pending.lock() {
+            Ok(mut pending) => pending.drain(..).collect(),
+            Err(_) => return,
+        };
+
+        if tasks.is_empty() {
+            return;
+        }
+
+        for task in tasks {
+            task.await.unwrap_or_default();
+        }
+    }
+}
+
+/// whether bypasses are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (label, url) of every path variant, for easier comparison
+    fn paths(url: &str) -> Vec<(String, String)> {
+        variants(&Url::parse(url).unwrap())
+            .into_iter()
+            .filter(|variant| variant.headers.is_empty())
+            .map(|variant| (variant.label, variant.url.to_string()))
+            .collect()
+    }
+
+    #[test]
+    /// every path trick should make it through the url parser untouched
+    fn variants_keep_path_tricks() {
+        let paths = paths("http://localhost/api/admin?x=1");
+
+        let expected = [
+            ("uppercase", "http://localhost/api/ADMIN?x=1"),
+            ("capitalized", "http://localhost/api/Admin?x=1"),
+            ("%2e segment", "http://localhost/api/%2e;/admin?x=1"),
+            ("trailing %20", "http://localhost/api/admin%20?x=1"),
+            ("trailing %09", "http://localhost/api/admin%09?x=1"),
+            ("trailing %2e", "http://localhost/api/admin%2e?x=1"),
+            ("trailing ..;/", "http://localhost/api/admin..;/?x=1"),
+            ("trailing slash", "http://localhost/api/admin/?x=1"),
+            ("double slash", "http://localhost//api/admin?x=1"),
+        ];
+
+        assert_eq!(paths.len(), expected.len());
+
+        for ((label, url), (expected_label, expected_url)) in paths.iter().zip(expected) {
+            assert_eq!(label, expected_label);
+            assert_eq!(url, expected_url);
+        }
+    }
+
+    #[test]
+    /// directories keep their trailing slash; the rewrite headers point at the forbidden path
+    /// from the root
+    fn variants_of_directory() {
+        let url = Url::parse("http://localhost/ADMIN/").unwrap();
+        let variants = variants(&url);
+
+        // nothing to uppercase or capitalize
+        assert_eq!(variants[0].url.as_str(), "http://localhost/%2e;/ADMIN/");
+        assert!(variants
+            .iter()
+            .any(|variant| variant.url.as_str() == "http://localhost/ADMIN"));
+
+        let original_url = variants
+            .iter()
+            .find(|variant| variant.label.starts_with("X-Original-URL"))
+            .unwrap();
+        assert_eq!(original_url.url.as_str(), "http://localhost/");
+        assert_eq!(
+            original_url.headers,
+            [(String::from("X-Original-URL"), String::from("/ADMIN/"))]
+        );
+
+        let forwarded = variants
+            .iter()
+            .find(|variant| variant.label.starts_with("X-Forwarded-For"))
+            .unwrap();
+        assert_eq!(forwarded.url, url);
+    }
+
+    #[test]
+    /// the root of a site has nothing to work with
+    fn variants_of_root_are_empty() {
+        assert!(variants(&Url::parse("http://localhost/").unwrap()).is_empty());
+    }
+
+    #[test]
+    /// 403s and 404s are never reported, otherwise the usual -s/-C rules apply
+    fn is_reported_follows_status_codes() {
+        let mut config = Configuration::new().unwrap();
+
+        assert!(is_reported(StatusCode::OK, &config));
+        assert!(!is_reported(StatusCode::FORBIDDEN, &config));
+        assert!(!is_reported(StatusCode::NOT_FOUND, &config));
+
+        config.filter_status = vec![200];
+        assert!(!is_reported(StatusCode::OK, &config));
+        assert!(is_reported(StatusCode::INTERNAL_SERVER_ERROR, &config));
+        assert!(!is_reported(StatusCode::NOT_FOUND, &config));
+        assert!(!is_reported(StatusCode::FORBIDDEN, &config));
+    }
+}
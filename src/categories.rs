@@ -0,0 +1,240 @@
+//! categories attached to responses so that results come pre-triaged (--categorize,
+//! --category-rule)
+//!
+//! each rule pairs a category with a regular expression. built-in rules (--categorize) recognize
+//! backup files, config files, admin panels, api specs, and directory listings; user rules are
+//! given as `CATEGORY:REGEX` and match against the response's url. a response matching a rule is
+//! tagged with the rule's category, which is shown next to it, written to --json output, and can
+//! be filtered out with --filter-category
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+
+use crate::{config::Configuration, response::FeroxResponse, utils::fmt_err};
+
+/// backup copies and archives, i.e. index.php.bak, site.tar.gz
+pub const BACKUP_FILE: &str = "backup-file";
+
+/// files holding configuration, and often credentials, i.e. .env, web.config
+pub const CONFIG_FILE: &str = "config-file";
+
+/// administrative interfaces, i.e. /wp-admin/, /phpmyadmin/
+pub const ADMIN_PANEL: &str = "admin-panel";
+
+/// api descriptions, i.e. /swagger.json, /openapi.yaml
+pub const API_SPEC: &str = "api-spec";
+
+/// pages listing the contents of a directory
+pub const DIRECTORY_LISTING: &str = "directory-listing";
+
+/// the part of a response a rule's regex is matched against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    /// the response's url
+    Url,
+
+    /// the response's body
+    Body,
+}
+
+/// a single category rule
+#[derive(Debug)]
+struct Rule {
+    /// category given to matching responses
+    category: String,
+
+    /// what gets matched
+    target: Target,
+
+    /// the expression itself
+    regex: Regex,
+}
+
+/// implementation of Rule
+impl Rule {
+    /// create a new rule; built-in expressions are known to be valid
+    fn builtin(category: &str, target: Target, regex: &str) -> Self {
+        Self {
+            category: category.to_string(),
+            target,
+            regex: Regex::new(regex).unwrap(),
+        }
+    }
+
+    /// parse a user rule given as CATEGORY:REGEX
+    fn parse(raw: &str) -> Result<Self> {
+        let Some((category, regex)) = raw.split_once(':') else {
+            bail!(fmt_err(&format!(
+                "Category rule {raw} should look like CATEGORY:REGEX"
+            )));
+        };
+
+        let category = category.trim();
+
+        if category.is_empty() || regex.is_empty() {
+            bail!(fmt_err(&format!(
+                "Category rule {raw} needs both a category and a regex"
+            )));
+        }
+
+        let regex = Regex::new(regex)
+            .with_context(|| fmt_err(&format!("Could not compile category rule {raw}")))?;
+
+        Ok(Self {
+            category: category.to_string(),
+            target: Target::Url,
+            regex,
+        })
+    }
+
+    /// whether the given response matches this rule
+    fn matches(&self, response: &FeroxResponse) -> bool {
+        match self.target {
+            Target::Url => self.regex.is_match(response.url().as_str()),
+            Target::Body => self.regex.is_match(response.text()),
+        }
+    }
+}
+
+/// the rules enabled by --categorize
+fn builtin_rules() -> Vec<Rule> {
+    vec![
+        Rule::builtin(
+            BACKUP_FILE,
+            Target::Url,
+            r"(?i)(\.(bak|backup|bkp|old|orig|save|swp|tmp|zip|tar|tgz|gz|7z|rar|sql|dump)|~)(\?.*)?$",
+        ),
+        Rule::builtin(
+            CONFIG_FILE,
+            Target::Url,
+            r"(?i)(\.(env|ini|conf|cfg|config|ya?ml|toml|properties)|/\.ht(access|passwd)|/(wp-)?config\.php|/appsettings(\.\w+)?\.json|/\.git/config)(\?.*)?$",
+        ),
+        Rule::builtin(
+            ADMIN_PANEL,
+            Target::Url,
+            r"(?i)/(admin|administrator|wp-admin|phpmyadmin|pma|adminer|cpanel|manager/html|admin-console|admincp)(/|\.php|\.html?|\.aspx?)?(\?.*)?$",
+        ),
+        Rule::builtin(
+            API_SPEC,
+            Target::Url,
+            r"(?i)/((swagger|openapi)[\w.-]*\.(json|ya?ml)|api-docs|swagger-ui(\.html)?|swagger(/index\.html)?)/?(\?.*)?$",
+        ),
+        Rule::builtin(
+            API_SPEC,
+            Target::Body,
+            r#"^\s*\{\s*"(openapi|swagger)"\s*:\s*"\d"#,
+        ),
+        Rule::builtin(
+            DIRECTORY_LISTING,
+            Target::Body,
+            r"(?i)<title>\s*(index of /|directory listing for /|directory listing -- /)",
+        ),
+    ]
+}
+
+/// Rules responses are checked against; empty unless --categorize or --category-rule is used
+#[derive(Debug, Default)]
+pub struct Categories {
+    /// built-in rules first, then the user's, in the order given
+    rules: Vec<Rule>,
+}
+
+/// load the built-in (--categorize) and user (--category-rule) rules; empty when neither was
+/// used
+pub fn initialize(config: &Configuration) -> Result<Categories> {
+    let mut rules = if config.categorize {
+        builtin_rules()
+    } else {
+        Vec::new()
+    };
+
+    for raw in &config.category_rules {
+        rules.push(Rule::parse(raw)?);
+    }
+
+    if !rules.is_empty() {
+        log::info!("loaded {} category rules", rules.len());
+    }
+
+    Ok(Categories { rules })
+}
+
+/// implementation of Categories
+impl Categories {
+    /// tag the given response with the category of every rule it matches
+    pub fn categorize(&self, response: &mut FeroxResponse) {
+        let categories: Vec<_> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(response))
+            .map(|rule| rule.category.clone())
+            .collect();
+
+        for category in categories {
+            response.add_tag(category);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// categories the built-in rules give a response with the given url and body
+    fn builtin_categories(url: &str, body: &str) -> Vec<String> {
+        let mut response = FeroxResponse::default();
+        response.set_url(url);
+        response.set_text(body);
+
+        builtin_rules()
+            .iter()
+            .filter(|rule| rule.matches(&response))
+            .map(|rule| rule.category.clone())
+            .collect()
+    }
+
+    #[test]
+    /// each built-in category is recognized by its url or body, and ordinary pages aren't
+    fn builtin_rules_categorize_responses() {
+        let expected = [
+            ("http://localhost/index.php.bak", "", BACKUP_FILE),
+            ("http://localhost/site.tar.gz", "", BACKUP_FILE),
+            ("http://localhost/index.php~", "", BACKUP_FILE),
+            ("http://localhost/.env", "", CONFIG_FILE),
+            ("http://localhost/web.config", "", CONFIG_FILE),
+            ("http://localhost/.htpasswd", "", CONFIG_FILE),
+            ("http://localhost/wp-admin/", "", ADMIN_PANEL),
+            ("http://localhost/phpmyadmin", "", ADMIN_PANEL),
+            ("http://localhost/v2/swagger.json", "", API_SPEC),
+            ("http://localhost/api-docs", "", API_SPEC),
+            ("http://localhost/spec", r#"{"openapi": "3.0.1"}"#, API_SPEC),
+            (
+                "http://localhost/files/",
+                "<html><title>Index of /files</title></html>",
+                DIRECTORY_LISTING,
+            ),
+        ];
+
+        for (url, body, category) in expected {
+            assert_eq!(builtin_categories(url, body), [category], "{url}");
+        }
+
+        assert!(builtin_categories("http://localhost/about", "<title>About</title>").is_empty());
+        assert!(builtin_categories("http://localhost/administration-guide", "").is_empty());
+    }
+
+    #[test]
+    /// user rules are CATEGORY:REGEX, matched against the url; anything else is an error
+    fn user_rules_are_parsed() {
+        let rule = Rule::parse("login-page:(?i)/(login|signin)").unwrap();
+        assert_eq!(rule.category, "login-page");
+        assert_eq!(rule.target, Target::Url);
+
+        let mut response = FeroxResponse::default();
+        response.set_url("http://localhost/SignIn");
+        assert!(rule.matches(&response));
+
+        assert!(Rule::parse("no-regex-here").is_err());
+        assert!(Rule::parse(":^/admin").is_err());
+        assert!(Rule::parse("broken:(").is_err());
+    }
+}
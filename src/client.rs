@@ -1,36 +1,406 @@
-use anyhow::{Context, Result};
-use reqwest::header::HeaderMap;
-use reqwest::{redirect::Policy, Client, Proxy};
+use anyhow::{bail, Context, Result};
+use hyper::client::connect::dns::Name;
+use reqwest::header::{HeaderMap, HeaderValue, HOST};
+use reqwest::{
+    dns::{Addrs, Resolve, Resolving},
+    redirect::{Attempt, Policy},
+    Client, Proxy, StatusCode, Url,
+};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{doh::DohResolver, utils};
+
+/// suffix of the names that zone scoped ipv6 targets are addressed to, following the
+/// `fe80--1seth0.ipv6-literal.net` convention; the name never hits DNS or the wire, it's
+/// resolved by the client directly and left out of the TLS handshake
+pub(crate) const IPV6_LITERAL_SUFFIX: &str = "ipv6-literal.net";
+
+/// maximum number of redirects followed for a single request (--redirects)
+pub const MAX_REDIRECTS: usize = 10;
+
+tokio::task_local! {
+    /// redirects followed by the request currently being sent from this task; the redirect
+    /// policy runs on the task awaiting the response, so hops recorded here belong to it
+    pub(crate) static REDIRECT_HOPS: RefCell<Vec<RedirectHop>>;
+}
+
+/// A single redirect followed on the way to a response (--redirects)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectHop {
+    /// url that responded with a redirect
+    pub url: String,
+
+    /// status code of that redirect
+    pub status: StatusCode,
+}
+
+/// Value of the --request-id header sent with a request (`<scan id>-<counter>`); attached to the
+/// response so it makes it into the json output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Hands out --request-id header values
+#[derive(Debug)]
+pub struct RequestIds {
+    /// id of this run, shared by every --request-id header it sends
+    scan_id: String,
+
+    /// number of --request-id headers handed out so far
+    tagged: AtomicUsize,
+}
+
+/// Default implementation for RequestIds; every run gets an id of its own
+impl Default for RequestIds {
+    fn default() -> Self {
+        Self {
+            scan_id: Uuid::new_v4().to_string(),
+            tagged: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// implementation of RequestIds
+impl RequestIds {
+    /// the id of the next request sent
+    pub fn next(&self) -> RequestId {
+        let counter = self.tagged.fetch_add(1, Ordering::Relaxed) + 1;
+        RequestId(format!("{}-{counter}", self.scan_id))
+    }
+
+    /// id of this run, the part every --request-id header has in common
+    pub fn scan_id(&self) -> &str {
+        &self.scan_id
+    }
+}
+
+/// Address a response was served from, when it didn't come through a proxy; attached to the
+/// response so it makes it into the json output
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RemoteIp(pub IpAddr);
+
+/// Address family every connection is limited to (-4/-6)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IpFamily {
+    /// IPv4 only (-4)
+    V4,
+
+    /// IPv6 only (-6)
+    V6,
+}
+
+/// implementation of IpFamily
+impl IpFamily {
+    /// the family picked with -4/-6, `None` when connections may use either
+    pub fn from_flags(ipv4: bool, ipv6: bool) -> Option<Self> {
+        match (ipv4, ipv6) {
+            (true, false) => Some(Self::V4),
+            (false, true) => Some(Self::V6),
+            _ => None,
+        }
+    }
+
+    /// whether the given address belongs to this family
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match self {
+            Self::V4 => ip.is_ipv4(),
+            Self::V6 => ip.is_ipv6(),
+        }
+    }
+
+    /// name of the family, as shown to the user
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::V4 => "IPv4",
+            Self::V6 => "IPv6",
+        }
+    }
+}
+
+/// The system's resolver, with its answers limited to a single address family (-4/-6);
+/// --doh does the same on its own
+#[derive(Debug, Copy, Clone)]
+struct FamilyResolver(IpFamily);
+
+/// implementation of reqwest's Resolve for FamilyResolver
+impl Resolve for FamilyResolver {
+    /// look the name up the usual way, dropping addresses of the other family
+    fn resolve(&self, name: Name) -> Resolving {
+        let family = self.0;
+
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| family.contains(&addr.ip()))
+                .collect::<Vec<_>>();
+
+            if addrs.is_empty() {
+                // worded like the system resolver's failures, so it's counted as a dns error
+                return Err(format!(
+                    "failed to lookup address information: {} has no {} address",
+                    name.as_str(),
+                    family.name()
+                )
+                .into());
+            }
+
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Error raised when a redirect leads back to a url that was already requested in the same
+/// chain; there's no cookie jar to change the server's answer, so it'd only go around again
+#[derive(Debug)]
+pub struct RedirectLoop(pub Url);
+
+impl fmt::Display for RedirectLoop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "redirect loop detected at {}", self.0)
+    }
+}
+
+impl Error for RedirectLoop {}
+
+/// --redirects; record each hop, bail on loops, and give up after `MAX_REDIRECTS`
+fn follow_redirect(attempt: Attempt) -> reqwest::redirect::Action {
+    if let Some(from) = attempt.previous().last() {
+        let hop = RedirectHop {
+            url: from.to_string(),
+            status: attempt.status(),
+        };
+
+        // requests sent outside of make_request have nowhere to record hops, which is fine
+        let _ = REDIRECT_HOPS.try_with(|hops| hops.borrow_mut().push(hop));
+    }
+
+    if attempt.previous().contains(attempt.url()) {
+        let next = attempt.url().clone();
+        return attempt.error(RedirectLoop(next));
+    }
+
+    Policy::limited(MAX_REDIRECTS).redirect(attempt)
+}
+
+/// Connection details for --host-header and zone scoped ipv6 targets (i.e.
+/// `http://[fe80::1%eth0]:8080/`): requests are addressed to `sni` (which is what the TLS
+/// handshake presents, when `presents_sni` is set) but connect to `addr`, the target given via
+/// --url, and carry `host` as their Host header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostOverride {
+    /// name used in request urls, and therefore the TLS SNI
+    pub sni: String,
+
+    /// whether `sni` is presented in the TLS handshake; it isn't when it's only a stand-in for a
+    /// zone scoped ipv6 address, the same as for any other ip address
+    pub presents_sni: bool,
+
+    /// value of the Host header
+    pub host: String,
+
+    /// address that's actually connected to
+    pub addr: SocketAddr,
+}
+
+impl HostOverride {
+    /// given the target url along with --host-header and --sni values, determine where to
+    /// connect and what to call it; `None` when --host-header wasn't used and the target isn't
+    /// a zone scoped ipv6 address
+    pub fn new(target_url: &str, host_header: &str, sni: &str) -> Result<Option<Self>> {
+        let Some(host) = self::host_header(target_url, host_header) else {
+            return Ok(None);
+        };
+
+        let (unscoped, zone) = match split_zone_id(target_url) {
+            Some((unscoped, zone)) => (unscoped, Some(zone)),
+            None => (target_url.to_string(), None),
+        };
+
+        let target = Url::parse(&unscoped)
+            .with_context(|| format!("--host-header requires a valid --url, got {target_url}"))?;
+
+        let mut addr = target
+            .socket_addrs(|| None)
+            .with_context(|| format!("Could not resolve {target_url}"))?
+            .into_iter()
+            .next()
+            .with_context(|| format!("Could not resolve {target_url}"))?;
+
+        if let (Some(zone), SocketAddr::V6(addr)) = (&zone, &mut addr) {
+            addr.set_scope_id(scope_id(zone)?);
+        }
+
+        let mut presents_sni = true;
+
+        let sni = match (&zone, addr) {
+            _ if !sni.is_empty() => sni.to_string(),
+            (Some(zone), SocketAddr::V6(addr)) if host_header.is_empty() => {
+                // nothing to present but the address itself, give it a name that the client
+                // can resolve back to the scoped address; ip addresses aren't sent via SNI, and
+                // neither is the made up name
+                presents_sni = false;
+                let literal = addr.ip().to_string().replace(':', "-");
+                format!("{literal}s{zone}.{IPV6_LITERAL_SUFFIX}").to_lowercase()
+            }
+            _ => {
+                // no separate --sni, present the same name that's in the Host header, minus any
+                // port
+                Url::parse(&format!("http://{host_header}"))
+                    .ok()
+                    .and_then(|url| url.host_str().map(String::from))
+                    .with_context(|| format!("{host_header} is not a valid Host header"))?
+            }
+        };
+
+        if sni.parse::<std::net::IpAddr>().is_ok() {
+            bail!("--sni/--host-header must be a hostname to be presented via SNI, got {sni}");
+        }
+
+        Ok(Some(Self {
+            sni,
+            presents_sni,
+            host,
+            addr,
+        }))
+    }
+
+    /// rewrite the given target url so that it's addressed to the SNI name; the connection
+    /// itself still goes to `addr`
+    pub fn target(&self, target: &str) -> Result<String> {
+        let unscoped = split_zone_id(target).map_or_else(|| target.to_string(), |(url, _)| url);
+        let mut url = Url::parse(&unscoped).with_context(|| format!("Invalid target {target}"))?;
+
+        url.set_host(Some(&self.sni))
+            .with_context(|| format!("Could not address {target} to {}", self.sni))?;
+
+        Ok(url.to_string())
+    }
+}
+
+/// value of the Host header sent with every request to the given target; `None` when it's
+/// simply derived from each request's url
+///
+/// that's the --host-header value when given, otherwise the bracketed address (and non-default
+/// port) of a zone scoped ipv6 target, minus the zone id, i.e. `[fe80::1]:8080`
+pub fn host_header(target_url: &str, host_header: &str) -> Option<String> {
+    if !host_header.is_empty() {
+        return Some(host_header.to_string());
+    }
+
+    let (unscoped, _) = split_zone_id(target_url)?;
+    let url = Url::parse(&unscoped).ok()?;
+    let host = url.host_str()?;
+
+    match url.port() {
+        Some(port) => Some(format!("{host}:{port}")),
+        None => Some(host.to_string()),
+    }
+}
+
+/// split the zone id from a url whose host is a scoped ipv6 literal, i.e.
+/// `http://[fe80::1%eth0]:8080/` -> (`http://[fe80::1]:8080/`, `eth0`); the RFC 6874 form,
+/// where the `%` is encoded as `%25`, is accepted as well
+///
+/// `None` when the url's host isn't scoped
+fn split_zone_id(url: &str) -> Option<(String, String)> {
+    let (_, after_scheme) = url.split_once("//")?;
+    let authority_start = url.len() - after_scheme.len();
+    let authority = after_scheme
+        .find(['/', '?', '#'])
+        .map_or(after_scheme, |index| &after_scheme[..index]);
+
+    let open = authority.find('[')?;
+    let close = open + authority[open..].find(']')?;
+    let percent = open + authority[open..close].find('%')?;
+
+    let zone = &authority[percent + 1..close];
+    let zone = match zone.strip_prefix("25") {
+        Some(encoded) if !encoded.is_empty() => encoded,
+        _ => zone,
+    };
+
+    let unscoped = format!(
+        "{}{}",
+        &url[..authority_start + percent],
+        &url[authority_start + close..]
+    );
+
+    Some((unscoped, zone.to_string()))
+}
+
+/// numeric scope id for the given zone, which is either already a number or the name of a
+/// network interface
+fn scope_id(zone: &str) -> Result<u32> {
+    if let Ok(id) = zone.parse::<u32>() {
+        return Ok(id);
+    }
+
+    if zone.is_empty()
+        || !zone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!("{zone} is not a valid ipv6 zone id");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string(format!("/sys/class/net/{zone}/ifindex"))
+            .ok()
+            .and_then(|index| index.trim().parse::<u32>().ok())
+            .with_context(|| format!("Could not find a network interface named {zone}"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    bail!("Interface names can't be used as zone ids on this platform, use {zone}'s index instead")
+}
 
 /// Create and return an instance of [reqwest::Client](https://docs.rs/reqwest/latest/reqwest/struct.Client.html)
 /// For now, silence clippy for this one
 #[allow(clippy::too_many_arguments)]
 pub fn initialize<I>(
     timeout: u64,
+    connect_timeout: u64,
     user_agent: &str,
     redirects: bool,
     insecure: bool,
     headers: &HashMap<String, String>,
     proxy: Option<&str>,
     server_certs: I,
+    ca_bundle: Option<&str>,
     client_cert: Option<&str>,
     client_key: Option<&str>,
+    host_override: Option<&HostOverride>,
+    doh: Option<&DohResolver>,
+    family: Option<IpFamily>,
 ) -> Result<Client>
 where
     I: IntoIterator,
     I::Item: AsRef<Path> + std::fmt::Debug,
 {
     let policy = if redirects {
-        Policy::limited(10)
+        Policy::custom(follow_redirect)
     } else {
         Policy::none()
     };
 
-    let header_map: HeaderMap = headers.try_into()?;
+    let mut header_map: HeaderMap = headers.try_into()?;
+
+    if let Some(host_override) = host_override {
+        // spelled out rather than derived from the url, which only carries the SNI name (and
+        // possibly a port that the Host header shouldn't include)
+        header_map.insert(HOST, HeaderValue::from_str(&host_override.host)?);
+    }
 
     let mut client = Client::builder()
         .timeout(Duration::new(timeout, 0))
@@ -40,6 +410,17 @@ where
         .redirect(policy)
         .http1_title_case_headers();
 
+    if connect_timeout > 0 {
+        // --connect-timeout; a host that won't even accept the connection is given up on early,
+        // without cutting short slow responses
+        client = client.connect_timeout(Duration::new(connect_timeout, 0));
+    }
+
+    if let Some(max_sockets) = utils::max_open_sockets() {
+        // idle connections hold file descriptors too; don't let the pool keep more than its share
+        client = client.pool_max_idle_per_host(max_sockets);
+    }
+
     if let Some(some_proxy) = proxy {
         if !some_proxy.is_empty() {
             // it's not an empty string; set the proxy
@@ -66,6 +447,13 @@ where
         client = client.add_root_certificate(cert);
     }
 
+    if let Some(bundle_path) = ca_bundle {
+        // --ca-bundle; every certificate in the file is trusted in addition to the built-in roots
+        for cert in ca_bundle_certificates(bundle_path)? {
+            client = client.add_root_certificate(cert);
+        }
+    }
+
     if let (Some(cert_path), Some(key_path)) = (client_cert, client_key) {
         let cert = std::fs::read(cert_path)?;
         let key = std::fs::read(key_path)?;
@@ -80,9 +468,47 @@ where
         client = client.identity(identity);
     }
 
+    // names given to resolve() below are still connected to directly, whichever is used
+    if let Some(resolver) = doh {
+        // --doh used; every name is looked up through the endpoint
+        client = client.dns_resolver(Arc::new(resolver.clone()));
+    } else if let Some(family) = family {
+        // -4/-6 used; the system's answers are filtered down to the one family
+        client = client.dns_resolver(Arc::new(FamilyResolver(family)));
+    }
+
+    if let Some(host_override) = host_override {
+        // --host-header used; requests addressed to the SNI name connect to the real target
+        client = client.resolve(&host_override.sni, host_override.addr);
+
+        if !host_override.presents_sni {
+            // zone scoped ipv6 target; no certificate can be issued to the made up name, so
+            // the chain is still verified but the name isn't, and it stays off the wire
+            client = client.tls_sni(false).danger_accept_invalid_hostnames(true);
+        }
+    }
+
     Ok(client.build()?)
 }
 
+/// read every PEM encoded certificate out of the given bundle (--ca-bundle)
+fn ca_bundle_certificates(bundle_path: &str) -> Result<Vec<reqwest::Certificate>> {
+    let buf = std::fs::read(bundle_path)
+        .with_context(|| format!("Could not read the CA bundle {bundle_path}"))?;
+
+    let certs = openssl::x509::X509::stack_from_pem(&buf)
+        .with_context(|| format!("{bundle_path} is not a valid PEM encoded CA bundle"))?;
+
+    if certs.is_empty() {
+        bail!("{bundle_path} does not contain any PEM encoded certificates");
+    }
+
+    certs
+        .iter()
+        .map(|cert| Ok(reqwest::Certificate::from_der(&cert.to_der()?)?))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,6 +519,7 @@ mod tests {
     fn client_with_bad_proxy() {
         let headers = HashMap::new();
         initialize(
+            0,
             0,
             "stuff",
             true,
@@ -102,6 +529,10 @@ mod tests {
             Vec::<String>::new(),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
     }
@@ -112,6 +543,7 @@ mod tests {
         let headers = HashMap::new();
         let proxy = "http://127.0.0.1:8080";
         initialize(
+            0,
             0,
             "stuff",
             true,
@@ -121,6 +553,10 @@ mod tests {
             Vec::<String>::new(),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
     }
@@ -131,6 +567,7 @@ mod tests {
         let headers = HashMap::new();
 
         initialize(
+            0,
             0,
             "stuff",
             true,
@@ -140,6 +577,10 @@ mod tests {
             vec!["tests/mutual-auth/certs/server/server.crt.1".to_string()],
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
     }
@@ -150,6 +591,7 @@ mod tests {
         let headers = HashMap::new();
 
         initialize(
+            0,
             0,
             "stuff",
             true,
@@ -159,6 +601,10 @@ mod tests {
             vec!["tests/mutual-auth/certs/server/server.der".to_string()],
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
     }
@@ -171,6 +617,7 @@ mod tests {
         println!("{}", std::env::current_dir().unwrap().display());
 
         initialize(
+            0,
             0,
             "stuff",
             true,
@@ -183,6 +630,10 @@ mod tests {
             ],
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
     }
@@ -194,6 +645,7 @@ mod tests {
         let headers = HashMap::new();
 
         initialize(
+            0,
             0,
             "stuff",
             true,
@@ -203,7 +655,299 @@ mod tests {
             vec!["tests/mutual-auth/certs/client/client.key".to_string()],
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
     }
+
+    #[test]
+    /// create client with a pem encoded CA bundle, expect no error
+    fn client_with_valid_ca_bundle() {
+        let headers = HashMap::new();
+
+        initialize(
+            0,
+            0,
+            "stuff",
+            true,
+            false,
+            &headers,
+            None,
+            Vec::<String>::new(),
+            Some("tests/mutual-auth/certs/server/ca.crt"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    /// a bundle without any certificates should say so
+    fn client_with_empty_ca_bundle() {
+        let headers = HashMap::new();
+
+        let err = initialize(
+            0,
+            0,
+            "stuff",
+            true,
+            false,
+            &headers,
+            None,
+            Vec::<String>::new(),
+            Some("tests/mutual-auth/certs/client/client.key"),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("client.key"));
+    }
+
+    #[test]
+    /// --host-header alone should present the Host header's name via SNI and connect to --url
+    fn host_override_defaults_sni_to_host_header() {
+        let host_override = HostOverride::new("https://127.0.0.1:8443/app/", "internal.app", "")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(host_override.sni, "internal.app");
+        assert_eq!(host_override.host, "internal.app");
+        assert_eq!(host_override.addr, "127.0.0.1:8443".parse().unwrap());
+        assert_eq!(
+            host_override.target("https://127.0.0.1:8443/app/").unwrap(),
+            "https://internal.app:8443/app/"
+        );
+    }
+
+    #[test]
+    /// --sni should be used as-is, while any port in --host-header stays out of the SNI name
+    fn host_override_honors_separate_sni() {
+        let host_override = HostOverride::new("http://127.0.0.1", "internal.app:8080", "")
+            .unwrap()
+            .unwrap();
+        assert_eq!(host_override.sni, "internal.app");
+        assert_eq!(host_override.addr, "127.0.0.1:80".parse().unwrap());
+
+        let host_override = HostOverride::new("http://127.0.0.1", "internal.app", "cdn.app")
+            .unwrap()
+            .unwrap();
+        assert_eq!(host_override.sni, "cdn.app");
+        assert_eq!(host_override.host, "internal.app");
+    }
+
+    #[test]
+    /// nothing to override without --host-header, and ip addresses can't be sent via SNI
+    fn host_override_edge_cases() {
+        assert!(HostOverride::new("http://127.0.0.1", "", "cdn.app")
+            .unwrap()
+            .is_none());
+        assert!(HostOverride::new("http://127.0.0.1", "10.0.0.1", "").is_err());
+        assert!(HostOverride::new("not a url", "internal.app", "").is_err());
+    }
+
+    #[test]
+    /// zone scoped ipv6 targets connect to the scoped address under a synthetic name, while the
+    /// Host header carries the bracketed address without its zone
+    fn host_override_handles_zone_scoped_ipv6_targets() {
+        let host_override = HostOverride::new("http://[fe80::1%2]:8080/app/", "", "")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(host_override.sni, "fe80--1s2.ipv6-literal.net");
+        assert_eq!(host_override.host, "[fe80::1]:8080");
+        assert_eq!(host_override.addr, "[fe80::1%2]:8080".parse().unwrap());
+        assert_eq!(
+            host_override
+                .target("http://[fe80::1%2]:8080/app/")
+                .unwrap(),
+            "http://fe80--1s2.ipv6-literal.net:8080/app/"
+        );
+
+        // rfc 6874 encoded zone, default port, and an explicit --host-header
+        let host_override = HostOverride::new("https://[fe80::1%252]:443/", "internal.app", "")
+            .unwrap()
+            .unwrap();
+        assert_eq!(host_override.sni, "internal.app");
+        assert_eq!(host_override.host, "internal.app");
+        assert_eq!(host_override.addr, "[fe80::1%2]:443".parse().unwrap());
+
+        // unscoped ipv6 literals don't need any help
+        assert!(HostOverride::new("http://[::1]:8080/", "", "")
+            .unwrap()
+            .is_none());
+        assert!(HostOverride::new("http://[fe80::1%e$h]/", "", "").is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// https requests to a zone scoped ipv6 target leave its made up name out of the handshake
+    /// and still verify the certificate's chain, without needing -k
+    async fn host_override_keeps_zone_scoped_names_out_of_tls() {
+        use openssl::{
+            asn1::Asn1Time,
+            hash::MessageDigest,
+            pkey::PKey,
+            rsa::Rsa,
+            ssl::{NameType, SslAcceptor, SslMethod},
+            x509::{extension::SubjectAlternativeName, X509NameBuilder, X509},
+        };
+        use std::io::{Read, Write};
+        use std::sync::Mutex;
+
+        let host_override = HostOverride::new("https://[fe80::1%2]/", "", "")
+            .unwrap()
+            .unwrap();
+        assert!(!host_override.presents_sni);
+
+        let with_header = HostOverride::new("https://[fe80::1%2]/", "internal.app", "")
+            .unwrap()
+            .unwrap();
+        assert!(with_header.presents_sni);
+
+        // self-signed certificate for the address itself, trusted below as a server cert
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "fe80::1").unwrap();
+        let name = name.build();
+
+        let mut cert = X509::builder().unwrap();
+        cert.set_version(2).unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        let san = SubjectAlternativeName::new()
+            .ip("fe80::1")
+            .build(&cert.x509v3_context(None, None))
+            .unwrap();
+        cert.append_extension(san).unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+        let cert = cert.build();
+
+        let mut pem = tempfile::NamedTempFile::new().unwrap();
+        pem.write_all(&cert.to_pem().unwrap()).unwrap();
+
+        // stands in for the scoped address, recording whatever name the handshake presents
+        let presented = Arc::new(Mutex::new(None));
+        let recorded = presented.clone();
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        acceptor.set_servername_callback(move |ssl, _| {
+            *recorded.lock().unwrap() = ssl.servername(NameType::HOST_NAME).map(String::from);
+            Ok(())
+        });
+        let acceptor = acceptor.build();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(1).flatten() {
+                if let Ok(mut stream) = acceptor.accept(stream) {
+                    let _ = stream.read(&mut [0; 4096]);
+                    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+                }
+            }
+        });
+
+        let host_override = HostOverride {
+            addr,
+            ..host_override
+        };
+
+        let client = initialize(
+            5,
+            0,
+            "stuff",
+            false,
+            false,
+            &HashMap::new(),
+            None,
+            vec![pem.path().to_str().unwrap().to_string()],
+            None,
+            None,
+            None,
+            Some(&host_override),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let target = host_override
+            .target(&format!("https://[fe80::1%2]:{}/", addr.port()))
+            .unwrap();
+        let response = client.get(target).send().await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*presented.lock().unwrap(), None);
+    }
+
+    #[test]
+    /// zone ids should be split from the host without disturbing the rest of the url
+    fn client_split_zone_id() {
+        assert_eq!(
+            split_zone_id("http://[fe80::1%eth0]:8080/a%20b?c=%25"),
+            Some((
+                "http://[fe80::1]:8080/a%20b?c=%25".to_string(),
+                "eth0".to_string()
+            ))
+        );
+        assert_eq!(
+            split_zone_id("http://user@[fe80::1%25en0]/"),
+            Some(("http://user@[fe80::1]/".to_string(), "en0".to_string()))
+        );
+        assert_eq!(split_zone_id("http://[::1]/%25"), None);
+        assert_eq!(split_zone_id("http://localhost/[a%b]"), None);
+        assert_eq!(
+            host_header("http://[fe80::1%eth0]:8080/", ""),
+            Some("[fe80::1]:8080".to_string())
+        );
+        assert_eq!(host_header("http://[::1]:8080/", ""), None);
+    }
+
+    #[test]
+    /// -4/-6 pick a family, neither (or both, which clap doesn't allow) leaves it open
+    fn ip_family_from_flags() {
+        assert_eq!(IpFamily::from_flags(true, false), Some(IpFamily::V4));
+        assert_eq!(IpFamily::from_flags(false, true), Some(IpFamily::V6));
+        assert_eq!(IpFamily::from_flags(false, false), None);
+
+        assert!(IpFamily::V4.contains(&IpAddr::from([127, 0, 0, 1])));
+        assert!(!IpFamily::V4.contains(&"::1".parse().unwrap()));
+        assert!(IpFamily::V6.contains(&"::1".parse().unwrap()));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// the system's answers should be limited to the one family
+    async fn family_resolver_drops_other_family() {
+        let name = |host: &str| host.parse::<Name>().unwrap();
+
+        let addrs = FamilyResolver(IpFamily::V4)
+            .resolve(name("127.0.0.1"))
+            .await
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(addrs, vec![SocketAddr::from(([127, 0, 0, 1], 0))]);
+
+        let err = FamilyResolver(IpFamily::V6)
+            .resolve(name("127.0.0.1"))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("no IPv6 address"));
+    }
 }
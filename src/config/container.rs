@@ -1,16 +1,22 @@
 use super::utils::{
-    depth, extract_links, ignored_extensions, methods, report_and_exit, save_state,
-    serialized_type, status_codes, threads, timeout, user_agent, wordlist, OutputLevel,
-    RequesterPolicy,
+    api_batch_size, archive_max_size, bypass_limit, bypass_rate, canary_interval, depth,
+    entropy_threshold, exit_error_ratio, extract_links, ignored_extensions, methods,
+    plugin_timeout, policy_403_ratio, policy_429_ratio, policy_error_threshold, prune_after,
+    report_and_exit, save_state, scheme_probes, screenshot_codes, screenshot_types,
+    serialized_type, stability_interval, status_codes, threads, timeout, user_agent, wordlist,
+    OutputLevel, RequesterPolicy,
 };
 use crate::config::determine_output_level;
 use crate::config::utils::determine_requester_policy;
 use crate::{
-    client, parser,
+    client::{self, HostOverride, IpFamily},
+    doh::DohResolver,
+    parser, safety,
     scan_manager::resume_scan,
+    shuffle, tls,
     traits::FeroxSerialize,
     utils::{fmt_err, parse_url_with_raw_path},
-    DEFAULT_CONFIG_NAME,
+    variables, DEFAULT_CONFIG_NAME,
 };
 use anyhow::{anyhow, Context, Result};
 use clap::{parser::ValueSource, ArgMatches};
@@ -21,6 +27,7 @@ use std::{
     collections::HashMap,
     env::{current_dir, current_exe},
     fs::read_to_string,
+    net::{IpAddr, SocketAddr},
     path::PathBuf,
 };
 
@@ -92,6 +99,14 @@ pub struct Configuration {
     #[serde(default = "wordlist")]
     pub wordlist: String,
 
+    /// Extra wordlists for keywords other than FUZZ, each one as KEYWORD=FILE
+    #[serde(default)]
+    pub fuzz_wordlists: Vec<String>,
+
+    /// How the wordlists of FUZZ keywords are combined: clusterbomb or pitchfork
+    #[serde(default)]
+    pub fuzz_mode: String,
+
     /// Path to the config file used
     #[serde(default)]
     pub config: String,
@@ -100,10 +115,31 @@ pub struct Configuration {
     #[serde(default)]
     pub proxy: String,
 
+    /// Proxies to fail over to, in order, once the current one keeps refusing connections
+    #[serde(default)]
+    pub proxy_failover: Vec<String>,
+
+    /// Connect directly once every proxy has failed, instead of turning requests into errors
+    #[serde(default)]
+    pub proxy_failover_direct: bool,
+
     /// Replay Proxy to use for requests (ex: http(s)://host:port, socks5(h)://host:port)
     #[serde(default)]
     pub replay_proxy: String,
 
+    /// HTTP headers added to requests sent through the Replay Proxy, on top of --headers
+    #[serde(default)]
+    pub replay_headers: HashMap<String, String>,
+
+    /// Path to a PEM encoded X509 certificate used for mutual authentication by requests sent
+    /// through the Replay Proxy, in place of --client-cert
+    #[serde(default)]
+    pub replay_client_cert: String,
+
+    /// Path to a PEM encoded PKSC #8 private key that goes with --replay-client-cert
+    #[serde(default)]
+    pub replay_client_key: String,
+
     /// Path to a custom root certificate for connecting to servers with a self-signed certificate
     #[serde(default)]
     pub server_certs: Vec<String>,
@@ -116,10 +152,30 @@ pub struct Configuration {
     #[serde(default)]
     pub client_key: String,
 
+    /// Path to a PEM encoded bundle of CA certificates trusted in addition to the built-in roots
+    #[serde(default)]
+    pub ca_bundle: String,
+
+    /// SHA-256 fingerprints (lowercase hex) of certificates that https targets must present
+    #[serde(default)]
+    pub pin_sha256: Vec<String>,
+
     /// The target URL
     #[serde(default)]
     pub target_url: String,
 
+    /// Nmap/Masscan XML file from which to read targets
+    #[serde(default)]
+    pub import_nmap: String,
+
+    /// Ports on which each target host is scanned
+    #[serde(default)]
+    pub ports: Vec<u16>,
+
+    /// SCHEME:PORT pairs tried, in order, for targets given without a scheme
+    #[serde(default = "scheme_probes")]
+    pub scheme_probes: Vec<String>,
+
     /// Status Codes to include (allow list) (default: 200 204 301 302 307 308 401 403 405)
     #[serde(default = "status_codes")]
     pub status_codes: Vec<u16>,
@@ -140,6 +196,10 @@ pub struct Configuration {
     #[serde(skip)]
     pub replay_client: Option<Client>,
 
+    /// Resolver built from --doh; every client is handed the same one, so they share its cache
+    #[serde(skip)]
+    pub doh_resolver: Option<DohResolver>,
+
     /// Number of concurrent threads (default: 50)
     #[serde(default = "threads")]
     pub threads: usize,
@@ -148,6 +208,16 @@ pub struct Configuration {
     #[serde(default = "timeout")]
     pub timeout: u64,
 
+    /// Number of seconds a client may spend connecting before the request times out, zero leaves
+    /// connecting bound by --timeout alone
+    #[serde(default)]
+    pub connect_timeout: u64,
+
+    /// Number of seconds a response body may go without sending data before the request times
+    /// out, zero leaves reading bound by --timeout alone
+    #[serde(default)]
+    pub read_timeout: u64,
+
     /// Level of verbosity, equates to log level
     #[serde(default)]
     pub verbosity: u8,
@@ -168,10 +238,61 @@ pub struct Configuration {
     #[serde(default)]
     pub auto_bail: bool,
 
+    /// number of times a scan bailed on at the auto_bail thresholds is restarted at a lower
+    /// rate after a cooldown; 0 means bailed scans stay cancelled
+    #[serde(default)]
+    pub auto_requeue: usize,
+
     /// automatically try to lower request rate in order to reduce errors
     #[serde(default)]
     pub auto_tune: bool,
 
+    /// algorithm used by auto_tune to adjust the request rate
+    #[serde(default)]
+    pub tune_strategy: String,
+
+    /// multiple of the target's baseline response time (i.e. 2x-baseline) at which a rising p95
+    /// also triggers auto_tune/auto_bail
+    #[serde(default)]
+    pub tune_on_latency: String,
+
+    /// requests/second added per error-free interval by the aimd tune_strategy; 0 means 1/20th
+    /// of the scan's rate when tuning started
+    #[serde(default)]
+    pub tune_step: usize,
+
+    /// lowest requests/second the aimd tune_strategy will drop to; 0 means 1
+    #[serde(default)]
+    pub tune_floor: usize,
+
+    /// highest requests/second the aimd tune_strategy will climb to; 0 means the scan's rate
+    /// when tuning started
+    #[serde(default)]
+    pub tune_ceiling: usize,
+
+    /// ratio of a scan's requests that must be 403s before auto_tune/auto_bail kicks in
+    #[serde(default = "policy_403_ratio")]
+    pub policy_403_ratio: f64,
+
+    /// ratio of a scan's requests that must be 429s before auto_tune/auto_bail kicks in
+    #[serde(default = "policy_429_ratio")]
+    pub policy_429_ratio: f64,
+
+    /// status codes that, like 429s, trigger auto_tune/auto_bail once they make up
+    /// policy_429_ratio of a scan's requests
+    #[serde(default)]
+    pub policy_status: Vec<u16>,
+
+    /// minimum number of errors a scan must see before auto_tune/auto_bail kicks in; the
+    /// actual threshold is the larger of this and half the number of threads
+    #[serde(default = "policy_error_threshold")]
+    pub policy_error_threshold: usize,
+
+    /// how much each error of a category counts toward auto_tune/auto_bail, keyed by category;
+    /// categories that aren't listed keep their default weight
+    #[serde(default)]
+    pub policy_error_weights: HashMap<String, usize>,
+
     /// more easily differentiate between the three requester policies
     #[serde(skip)]
     pub requester_policy: RequesterPolicy,
@@ -180,6 +301,18 @@ pub struct Configuration {
     #[serde(default)]
     pub json: bool,
 
+    /// Display internationalized domain names as punycode instead of unicode
+    #[serde(default)]
+    pub punycode: bool,
+
+    /// Add a simhash of each response's body to --json output
+    #[serde(default)]
+    pub fuzzy_hash: bool,
+
+    /// Extract the title, meta generator, and charset of html responses into the results
+    #[serde(default)]
+    pub titles: bool,
+
     /// Output file to write results to (default: stdout)
     #[serde(default)]
     pub output: String,
@@ -189,6 +322,129 @@ pub struct Configuration {
     #[serde(default)]
     pub debug_log: String,
 
+    /// File in which to store discovered resources as a Burp Suite XML export
+    #[serde(default)]
+    pub burp_export: String,
+
+    /// File in which to store discovered resources as an HTTP Archive (HAR)
+    #[serde(default)]
+    pub har_export: String,
+
+    /// File that receives every response, including those removed by filters (tagged with the
+    /// filter responsible)
+    #[serde(default)]
+    pub quiet_stream: String,
+
+    /// Directory in which to log every request sent along with the response's metadata
+    #[serde(default)]
+    pub traffic_log: String,
+
+    /// Size at which a new traffic log file is started, i.e. 1G (default: never rotate)
+    #[serde(default)]
+    pub traffic_log_max: String,
+
+    /// Whether full response bodies are written to the traffic log
+    #[serde(default)]
+    pub traffic_log_bodies: bool,
+
+    /// File in which to record every auto-tune/bail/requeue policy decision, along with the
+    /// counters behind it
+    #[serde(default)]
+    pub policy_audit: String,
+
+    /// Directory to which screenshots of findings are written
+    #[serde(default)]
+    pub screenshots: String,
+
+    /// Chrome/Chromium executable used to take screenshots
+    #[serde(default)]
+    pub chrome: String,
+
+    /// Status codes of findings worth a screenshot
+    #[serde(default = "screenshot_codes")]
+    pub screenshot_codes: Vec<u16>,
+
+    /// Content types of findings worth a screenshot
+    #[serde(default = "screenshot_types")]
+    pub screenshot_types: Vec<String>,
+
+    /// Command that receives discovered resources, as NDJSON, on its stdin
+    #[serde(default)]
+    pub pipe_to: String,
+
+    /// Vulnerability management api that receives findings once the scan ends
+    #[serde(default)]
+    pub api_export: String,
+
+    /// Shape of what's sent to --api-export: generic (default) or dojo
+    #[serde(default)]
+    pub api_export_format: String,
+
+    /// JSON template filled in with each finding's values (generic --api-export format)
+    #[serde(default)]
+    pub api_template: String,
+
+    /// Number of findings sent per --api-export request
+    #[serde(default = "api_batch_size")]
+    pub api_batch_size: usize,
+
+    /// DefectDojo engagement that findings are imported into
+    #[serde(default)]
+    pub dojo_engagement: String,
+
+    /// File the JSON summary of the scan is written to once it ends
+    #[serde(default)]
+    pub summary: String,
+
+    /// File every response (and the filters learned while scanning) is recorded to, for `replay`
+    #[serde(default)]
+    pub record: String,
+
+    /// Recording (--record) whose responses are run through the filters and output offline,
+    /// instead of scanning
+    #[serde(skip)]
+    pub replay: String,
+
+    /// Scan the built-in test server instead of any targets, then check the results
+    #[serde(skip)]
+    pub selftest: bool,
+
+    /// Lua scripts whose hooks run against every request, response, and finding
+    #[serde(default)]
+    pub plugins: Vec<String>,
+
+    /// Number of milliseconds a single plugin hook may run before it's aborted
+    #[serde(default = "plugin_timeout")]
+    pub plugin_timeout: u64,
+
+    /// Tag responses with built-in categories (backup-file, config-file, admin-panel, ...)
+    #[serde(default)]
+    pub categorize: bool,
+
+    /// CATEGORY:REGEX rules; responses whose url matches REGEX are tagged with CATEGORY
+    #[serde(default)]
+    pub category_rules: Vec<String>,
+
+    /// TOML file of rules that tag findings, give them a severity, and raise alerts
+    #[serde(default)]
+    pub rules: String,
+
+    /// File the JUnit XML report of forbidden rules is written to
+    #[serde(default)]
+    pub junit: String,
+
+    /// Exit with a non-zero status when a forbidden rule matched
+    #[serde(default)]
+    pub fail_on_rule: bool,
+
+    /// exit code used when the scan ends a certain way, keyed by condition (rule, errors, bail)
+    #[serde(default)]
+    pub exit_codes: HashMap<String, i32>,
+
+    /// share of requests that must fail for the errors exit code to apply
+    #[serde(default = "exit_error_ratio")]
+    pub exit_error_ratio: f64,
+
     /// Sets the User-Agent (default: feroxbuster/VERSION)
     #[serde(default = "user_agent")]
     pub user_agent: String,
@@ -205,14 +461,52 @@ pub struct Configuration {
     #[serde(default)]
     pub insecure: bool,
 
+    /// Host header sent with every request, while still connecting to the target url
+    #[serde(default)]
+    pub host_header: String,
+
+    /// Name presented via TLS SNI when --host-header is used (default: the Host header's name)
+    #[serde(default)]
+    pub sni: String,
+
+    /// DNS-over-HTTPS endpoint that target host names are resolved through
+    #[serde(default)]
+    pub doh: String,
+
+    /// Only connect to IPv4 addresses (-4)
+    #[serde(default)]
+    pub ipv4: bool,
+
+    /// Only connect to IPv6 addresses (-6)
+    #[serde(default)]
+    pub ipv6: bool,
+
+    /// Spread requests across every address the target resolves to, and compare the findings
+    /// between them
+    #[serde(default)]
+    pub fan_out_ips: bool,
+
     /// File extension(s) to search for
     #[serde(default)]
     pub extensions: Vec<String>,
 
+    /// Order in which a word's extensions are requested: sequential, interleaved, or shuffled
+    #[serde(default)]
+    pub extension_order: String,
+
     /// HTTP requests methods(s) to search for
     #[serde(default = "methods")]
     pub methods: Vec<String>,
 
+    /// refuse to send anything but GET and HEAD, and point out words that look like
+    /// state-changing actions
+    #[serde(default)]
+    pub safe_methods_only: bool,
+
+    /// send words the url parser would rewrite byte for byte, over a minimal HTTP/1.1 client
+    #[serde(default)]
+    pub raw_requests: bool,
+
     /// HTTP Body data to send during request
     #[serde(default)]
     pub data: Vec<u8>,
@@ -221,6 +515,11 @@ pub struct Configuration {
     #[serde(default)]
     pub headers: HashMap<String, String>,
 
+    /// Name of a header carrying a unique id (`<scan id>-<counter>`) in every request, so the
+    /// scan's traffic can be correlated in the target's logs
+    #[serde(default)]
+    pub request_id: String,
+
     /// URL query parameters
     #[serde(default)]
     pub queries: Vec<(String, String)>,
@@ -233,10 +532,91 @@ pub struct Configuration {
     #[serde(default = "extract_links")]
     pub extract_links: bool,
 
+    /// Number of extracted links requested per page; 0 means no limit is imposed
+    #[serde(default)]
+    pub extract_page_limit: usize,
+
+    /// Number of extracted links requested per scan; 0 means no limit is imposed
+    #[serde(default)]
+    pub extract_scan_limit: usize,
+
+    /// Number of extracted links requested across all scans; 0 means no limit is imposed
+    #[serde(default)]
+    pub extract_limit: usize,
+
+    /// Number of extractions it may take to reach a directory before its links stop being
+    /// extracted; 0 means no limit is imposed
+    #[serde(default)]
+    pub extract_depth: usize,
+
+    /// Only follow extracted links from each target instead of requesting the wordlist
+    #[serde(default)]
+    pub crawl: bool,
+
     /// Append / to each request
     #[serde(default)]
     pub add_slash: bool,
 
+    /// Request each word both with and without a trailing slash
+    #[serde(default)]
+    pub dual_slash: bool,
+
+    /// Detect targets that ignore case and lowercase/dedupe the wordlist when found
+    #[serde(default)]
+    pub detect_case: bool,
+
+    /// Fetch and hash each host's favicon
+    #[serde(default)]
+    pub favicon: bool,
+
+    /// File of HASH,TECHNOLOGY pairs checked alongside the bundled favicon hashes
+    #[serde(default)]
+    pub favicon_db: String,
+
+    /// Request each host's /.well-known/ resources and follow the urls inside of them
+    #[serde(default)]
+    pub well_known: bool,
+
+    /// Check whether cloud storage buckets found in extracted links list their contents publicly
+    #[serde(default)]
+    pub bucket_listing: bool,
+
+    /// Previous scan's --json output/.state file whose responses are requested conditionally
+    #[serde(default)]
+    pub baseline: String,
+
+    /// Page whose body every response's body is compared against
+    #[serde(default)]
+    pub baseline_url: String,
+
+    /// Previous scan's .state file whose learned 404 signatures are reused
+    #[serde(default)]
+    pub reuse_signatures: String,
+
+    /// Encoding applied to each word before it's added to a url (none, url, double-url, unicode)
+    #[serde(default)]
+    pub encode: String,
+
+    /// Name of the evasion profile whose transforms are applied to each request's path
+    #[serde(default)]
+    pub evasion: String,
+
+    /// Extra evasion profiles, each a list of transform names
+    #[serde(default)]
+    pub evasion_profiles: HashMap<String, Vec<String>>,
+
+    /// Learn status/size via `Range: bytes=0-0` GETs before fetching full bodies
+    #[serde(default)]
+    pub range_probe: bool,
+
+    /// Value of the Accept-Encoding header sent with each request
+    #[serde(default)]
+    pub accept_encoding: String,
+
+    /// Decompress gzip/deflate bodies before filtering and extraction
+    #[serde(default)]
+    pub decompress: bool,
+
     /// Read url(s) from STDIN
     #[serde(default)]
     pub stdin: bool,
@@ -249,6 +629,23 @@ pub struct Configuration {
     #[serde(default)]
     pub scan_limit: usize,
 
+    /// Order in which pending scans found by recursion are started under --scan-limit: bfs or dfs
+    #[serde(default)]
+    pub recursion_strategy: String,
+
+    /// Request the wordlist in a random order per scan (--shuffle)
+    #[serde(default)]
+    pub shuffle: bool,
+
+    /// Seed each scan's --shuffle order is derived from; 0 means one is picked at random
+    #[serde(default)]
+    pub shuffle_seed: u64,
+
+    /// Number of requests shown (without being sent) before asking whether to start the scan;
+    /// 0 means the scan starts right away
+    #[serde(default)]
+    pub preview: usize,
+
     /// Number of parallel scans permitted; a limit of 0 means no limit is imposed
     #[serde(default)]
     pub parallel: usize,
@@ -257,6 +654,60 @@ pub struct Configuration {
     #[serde(default)]
     pub rate_limit: usize,
 
+    /// Number of requests per second permitted across all scans combined, on top of any
+    /// per-directory limit; a limit of 0 means no limit is imposed
+    #[serde(default)]
+    pub rate_limit_global: usize,
+
+    /// Number of bytes per second that may be received across all scans combined, as a size
+    /// spec (i.e. 512K); empty means no limit is imposed
+    #[serde(default)]
+    pub bandwidth_limit: String,
+
+    /// Burst/quiet cycle requests are sent in across all scans, either a named profile or
+    /// N/TIME_SPEC (i.e. 100/5m); empty means requests aren't paced
+    #[serde(default)]
+    pub pacing: String,
+
+    /// Number of seconds of nothing but connection failures after which all scans are paused
+    /// until the target responds again; 0 disables automatic pausing/resuming
+    #[serde(default)]
+    pub auto_resume: u64,
+
+    /// path (or full url) of a known-good page requested periodically on each target's host;
+    /// when it stops looking the way it did at first, the host's scans are paused
+    #[serde(default)]
+    pub canary: String,
+
+    /// number of seconds between canary requests
+    #[serde(default = "canary_interval")]
+    pub canary_interval: u64,
+
+    /// periodically request a sample of earlier findings again, reporting the ones that no
+    /// longer come back the way they did
+    #[serde(default)]
+    pub stability_check: bool,
+
+    /// number of seconds between --stability-check rounds
+    #[serde(default = "stability_interval")]
+    pub stability_interval: u64,
+
+    /// alert when a host's Server/X-Powered-By header changes mid-scan
+    #[serde(default)]
+    pub server_alerts: bool,
+
+    /// patterns searched for in every response body
+    #[serde(default)]
+    pub search_regex: Vec<String>,
+
+    /// report high entropy strings found in text responses
+    #[serde(default)]
+    pub entropy_secrets: bool,
+
+    /// bits of entropy per base64 character needed to be reported by entropy_secrets
+    #[serde(default = "entropy_threshold")]
+    pub entropy_threshold: f64,
+
     /// Filter out messages of a particular size
     #[serde(default)]
     pub filter_size: Vec<u64>,
@@ -298,6 +749,10 @@ pub struct Configuration {
     #[serde(default)]
     pub filter_similar: Vec<String>,
 
+    /// Filter out responses tagged with the given categories
+    #[serde(default)]
+    pub filter_category: Vec<String>,
+
     /// URLs that should never be scanned/recursed into
     #[serde(default)]
     pub url_denylist: Vec<Url>,
@@ -310,6 +765,14 @@ pub struct Configuration {
     #[serde(default)]
     pub collect_extensions: bool,
 
+    /// stop requesting an extension on a host once it's clearly not served there
+    #[serde(default)]
+    pub prune_extensions: bool,
+
+    /// number of requests an extension gets on a host without a finding before it's dropped
+    #[serde(default = "prune_after")]
+    pub prune_after: usize,
+
     /// don't collect any of these extensions when --collect-extensions is used
     #[serde(default = "ignored_extensions")]
     pub dont_collect: Vec<String>,
@@ -318,6 +781,30 @@ pub struct Configuration {
     #[serde(default)]
     pub collect_backups: bool,
 
+    /// Request a battery of 403 bypass variants against each forbidden url
+    #[serde(default)]
+    pub bypass_403: bool,
+
+    /// Maximum number of forbidden urls that get 403 bypass attempts
+    #[serde(default = "bypass_limit")]
+    pub bypass_limit: usize,
+
+    /// Maximum number of 403 bypass requests per second
+    #[serde(default = "bypass_rate")]
+    pub bypass_rate: usize,
+
+    /// Check the repository behind findings in a .git/.svn/.hg directory
+    #[serde(default)]
+    pub vcs_check: bool,
+
+    /// List the files inside archives that were found
+    #[serde(default)]
+    pub archive_peek: bool,
+
+    /// Largest archive that's downloaded to be listed
+    #[serde(default = "archive_max_size")]
+    pub archive_max_size: String,
+
     /// Automatically discover important words from within responses and add them to the wordlist
     #[serde(default)]
     pub collect_words: bool,
@@ -326,6 +813,16 @@ pub struct Configuration {
     #[serde(default)]
     pub force_recursion: bool,
 
+    /// map the site's directories first; extensions are requested once every directory scan
+    /// has requested its words without them
+    #[serde(default)]
+    pub directories_first: bool,
+
+    /// names of response headers that a recursive scan takes from the directory that spawned
+    /// it and sends with its own requests; Set-Cookie becomes a Cookie header
+    #[serde(default)]
+    pub inherit_headers: Vec<String>,
+
     /// Auto update app feature
     #[serde(skip)]
     pub update_app: bool,
@@ -338,6 +835,7 @@ impl Default for Configuration {
         let user_agent = user_agent();
         let client = client::initialize(
             timeout,
+            0,
             &user_agent,
             false,
             false,
@@ -346,6 +844,10 @@ impl Default for Configuration {
             Vec::<String>::new(),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .expect("Could not build client");
         let replay_client = None;
@@ -360,50 +862,162 @@ impl Default for Configuration {
             kind,
             client,
             timeout,
+            connect_timeout: 0,
+            read_timeout: 0,
             user_agent,
             replay_codes,
             status_codes,
             extract_links,
+            extract_page_limit: 0,
+            extract_scan_limit: 0,
+            extract_limit: 0,
+            extract_depth: 0,
+            crawl: false,
             replay_client,
+            doh_resolver: None,
             requester_policy,
             dont_filter: false,
             auto_bail: false,
+            auto_requeue: 0,
             auto_tune: false,
+            tune_strategy: String::new(),
+            tune_on_latency: String::new(),
+            tune_step: 0,
+            tune_floor: 0,
+            tune_ceiling: 0,
+            policy_403_ratio: policy_403_ratio(),
+            policy_429_ratio: policy_429_ratio(),
+            policy_status: Vec::new(),
+            policy_error_threshold: policy_error_threshold(),
+            policy_error_weights: HashMap::new(),
             silent: false,
             quiet: false,
             output_level,
             resumed: false,
             stdin: false,
             json: false,
+            punycode: false,
+            fuzzy_hash: false,
+            titles: false,
             verbosity: 0,
             scan_limit: 0,
+            recursion_strategy: String::new(),
+            shuffle: false,
+            shuffle_seed: 0,
+            preview: 0,
             parallel: 0,
             rate_limit: 0,
+            rate_limit_global: 0,
+            bandwidth_limit: String::new(),
+            pacing: String::new(),
+            auto_resume: 0,
+            canary: String::new(),
+            canary_interval: canary_interval(),
+            stability_check: false,
+            stability_interval: stability_interval(),
+            server_alerts: false,
+            search_regex: Vec::new(),
+            entropy_secrets: false,
+            entropy_threshold: entropy_threshold(),
             add_slash: false,
+            dual_slash: false,
+            detect_case: false,
+            favicon: false,
+            favicon_db: String::new(),
+            well_known: false,
+            bucket_listing: false,
+            baseline: String::new(),
+            baseline_url: String::new(),
+            reuse_signatures: String::new(),
+            encode: String::new(),
+            evasion: String::new(),
+            evasion_profiles: HashMap::new(),
+            range_probe: false,
+            accept_encoding: String::new(),
+            decompress: false,
             insecure: false,
+            host_header: String::new(),
+            sni: String::new(),
+            doh: String::new(),
+            ipv4: false,
+            ipv6: false,
+            fan_out_ips: false,
             redirects: false,
             no_recursion: false,
             random_agent: false,
             collect_extensions: false,
+            prune_extensions: false,
+            prune_after: prune_after(),
             collect_backups: false,
+            bypass_403: false,
+            bypass_limit: bypass_limit(),
+            bypass_rate: bypass_rate(),
+            vcs_check: false,
+            archive_peek: false,
+            archive_max_size: archive_max_size(),
             collect_words: false,
             save_state: true,
             force_recursion: false,
+            directories_first: false,
+            inherit_headers: Vec::new(),
             update_app: false,
             proxy: String::new(),
+            proxy_failover: Vec::new(),
+            proxy_failover_direct: false,
             client_cert: String::new(),
             client_key: String::new(),
+            ca_bundle: String::new(),
+            pin_sha256: Vec::new(),
             config: String::new(),
             output: String::new(),
             debug_log: String::new(),
+            burp_export: String::new(),
+            har_export: String::new(),
+            quiet_stream: String::new(),
+            traffic_log: String::new(),
+            traffic_log_max: String::new(),
+            traffic_log_bodies: false,
+            policy_audit: String::new(),
+            screenshots: String::new(),
+            chrome: String::new(),
+            screenshot_codes: screenshot_codes(),
+            screenshot_types: screenshot_types(),
+            pipe_to: String::new(),
+            api_export: String::new(),
+            api_export_format: String::new(),
+            api_template: String::new(),
+            api_batch_size: api_batch_size(),
+            dojo_engagement: String::new(),
+            summary: String::new(),
+            record: String::new(),
+            replay: String::new(),
+            selftest: false,
+            plugins: Vec::new(),
+            plugin_timeout: plugin_timeout(),
+            categorize: false,
+            category_rules: Vec::new(),
+            rules: String::new(),
+            junit: String::new(),
+            fail_on_rule: false,
+            exit_codes: HashMap::new(),
+            exit_error_ratio: exit_error_ratio(),
             target_url: String::new(),
+            import_nmap: String::new(),
+            ports: Vec::new(),
+            scheme_probes: scheme_probes(),
             time_limit: String::new(),
             resume_from: String::new(),
             replay_proxy: String::new(),
+            replay_headers: HashMap::new(),
+            replay_client_cert: String::new(),
+            replay_client_key: String::new(),
             server_certs: Vec::new(),
             queries: Vec::new(),
             extensions: Vec::new(),
+            extension_order: String::new(),
             methods: methods(),
+            safe_methods_only: false,
+            raw_requests: false,
             data: Vec::new(),
             filter_size: Vec::new(),
             filter_regex: Vec::new(),
@@ -413,10 +1027,14 @@ impl Default for Configuration {
             filter_word_count: Vec::new(),
             filter_status: Vec::new(),
             filter_similar: Vec::new(),
+            filter_category: Vec::new(),
             headers: HashMap::new(),
+            request_id: String::new(),
             depth: depth(),
             threads: threads(),
             wordlist: wordlist(),
+            fuzz_wordlists: Vec::new(),
+            fuzz_mode: String::new(),
             dont_collect: ignored_extensions(),
         }
     }
@@ -429,53 +1047,167 @@ impl Configuration {
     /// - **timeout**: `5` seconds
     /// - **redirects**: `false`
     /// - **extract_links**: `true`
+    /// - **extract_page_limit**: `0` (no limit on links requested per page)
+    /// - **extract_scan_limit**: `0` (no limit on links requested per scan)
+    /// - **extract_limit**: `0` (no limit on links requested overall)
+    /// - **extract_depth**: `0` (no limit on extraction depth)
+    /// - **crawl**: `false`
     /// - **wordlist**: [`DEFAULT_WORDLIST`](constant.DEFAULT_WORDLIST.html)
+    /// - **fuzz_wordlists**: `None` (only FUZZ, fed by the wordlist)
+    /// - **fuzz_mode**: `None` (clusterbomb, every combination of words)
     /// - **config**: `None`
     /// - **threads**: `50`
     /// - **timeout**: `7` seconds
+    /// - **connect_timeout**: `0` (connecting is bound by timeout alone)
+    /// - **read_timeout**: `0` (reading is bound by timeout alone)
     /// - **verbosity**: `0` (no logging enabled)
     /// - **proxy**: `None`
+    /// - **proxy_failover**: `None`
+    /// - **proxy_failover_direct**: `false`
     /// - **status_codes**: [`DEFAULT_RESPONSE_CODES`](constant.DEFAULT_RESPONSE_CODES.html)
     /// - **filter_status**: `None`
     /// - **output**: `None` (print to stdout)
     /// - **debug_log**: `None`
+    /// - **burp_export**: `None`
+    /// - **har_export**: `None`
+    /// - **quiet_stream**: `None`
+    /// - **traffic_log**: `None`
+    /// - **traffic_log_max**: `None` (traffic log files are never rotated)
+    /// - **traffic_log_bodies**: `false`
+    /// - **policy_audit**: `None`
+    /// - **screenshots**: `None`
+    /// - **chrome**: `None` (first Chrome/Chromium found on the PATH)
+    /// - **screenshot_codes**: `[200]`
+    /// - **screenshot_types**: `["text/html"]`
+    /// - **pipe_to**: `None`
+    /// - **api_export**: `None`
+    /// - **api_export_format**: `None` (generic)
+    /// - **api_template**: `None`
+    /// - **api_batch_size**: `100`
+    /// - **dojo_engagement**: `None`
+    /// - **summary**: `None`
+    /// - **record**: `None`
+    /// - **replay**: `None`
+    /// - **selftest**: `false`
+    /// - **plugins**: `None`
+    /// - **plugin_timeout**: `100`
+    /// - **categorize**: `false`
+    /// - **category_rules**: `None`
+    /// - **rules**: `None`
+    /// - **junit**: `None`
+    /// - **fail_on_rule**: `false`
+    /// - **exit_codes**: `None` (exit with 0 whenever the scan runs to completion)
+    /// - **exit_error_ratio**: `0.1`
     /// - **quiet**: `false`
     /// - **silent**: `false`
     /// - **auto_tune**: `false`
+    /// - **tune_strategy**: `None` (walk the limit heap)
+    /// - **tune_on_latency**: `None` (only errors trigger auto_tune/auto_bail)
+    /// - **tune_step**: `0` (1/20th of the rate when tuning started)
+    /// - **tune_floor**: `0` (1 request per second)
+    /// - **tune_ceiling**: `0` (the rate when tuning started)
+    /// - **policy_403_ratio**: `0.9`
+    /// - **policy_429_ratio**: `0.3`
+    /// - **policy_status**: `None` (only 403s and 429s are counted)
+    /// - **policy_error_threshold**: `25`
+    /// - **policy_error_weights**: `None` (proxy, too-large, url-format, and fd errors count 0,
+    ///   everything else 1)
     /// - **auto_bail**: `false`
+    /// - **auto_requeue**: `0` (bailed scans aren't restarted)
     /// - **save_state**: `true`
     /// - **user_agent**: `feroxbuster/VERSION`
     /// - **random_agent**: `false`
     /// - **insecure**: `false` (don't be insecure, i.e. don't allow invalid certs)
+    /// - **host_header**: `None` (Host header matches the target url)
+    /// - **sni**: `None` (SNI matches the Host header)
+    /// - **doh**: `None` (the system's resolver is used)
+    /// - **ipv4**: `false` (either address family is used)
+    /// - **ipv6**: `false` (either address family is used)
+    /// - **fan_out_ips**: `false` (the resolver picks the address to connect to)
     /// - **extensions**: `None`
+    /// - **extension_order**: `None` (sequential)
     /// - **collect_extensions**: `false`
+    /// - **prune_extensions**: `false` (every extension is requested for every word)
+    /// - **prune_after**: `250`
     /// - **collect_backups**: `false`
+    /// - **bypass_403**: `false`
+    /// - **bypass_limit**: `50`
+    /// - **bypass_rate**: `10`
+    /// - **vcs_check**: `false`
+    /// - **archive_peek**: `false`
+    /// - **archive_max_size**: `10M`
     /// - **collect_words**: `false`
     /// - **dont_collect**: [`DEFAULT_IGNORED_EXTENSIONS`](constant.DEFAULT_RESPONSE_CODES.html)
     /// - **methods**: [`DEFAULT_METHOD`](constant.DEFAULT_METHOD.html)
+    /// - **safe_methods_only**: `false` (any method may be sent)
+    /// - **raw_requests**: `false` (every word is sent through the url parser)
     /// - **data**: `None`
     /// - **url_denylist**: `None`
     /// - **regex_denylist**: `None`
     /// - **filter_size**: `None`
     /// - **filter_similar**: `None`
     /// - **filter_regex**: `None`
+    /// - **filter_category**: `None`
     /// - **filter_word_count**: `None`
     /// - **filter_line_count**: `None`
     /// - **headers**: `None`
+    /// - **request_id**: `None` (requests aren't tagged)
     /// - **queries**: `None`
     /// - **no_recursion**: `false` (recursively scan enumerated sub-directories)
     /// - **add_slash**: `false`
+    /// - **dual_slash**: `false`
+    /// - **detect_case**: `false`
+    /// - **favicon**: `false`
+    /// - **favicon_db**: `None`
+    /// - **well_known**: `false`
+    /// - **bucket_listing**: `false`
+    /// - **baseline**: `None`
+    /// - **baseline_url**: `None`
+    /// - **reuse_signatures**: `None`
+    /// - **encode**: `None` (words are used as-is)
+    /// - **evasion**: `None` (paths are used as-is)
+    /// - **evasion_profiles**: `None`
+    /// - **range_probe**: `false`
+    /// - **accept_encoding**: `None` (no Accept-Encoding header is sent)
+    /// - **decompress**: `false`
     /// - **stdin**: `false`
+    /// - **import_nmap**: `None`
+    /// - **ports**: `None` (targets are scanned on the port they were given with)
+    /// - **scheme_probes**: `["https:443", "http:80"]`
     /// - **json**: `false`
+    /// - **punycode**: `false` (internationalized domain names are displayed as unicode)
+    /// - **fuzzy_hash**: `false` (only the SHA-256 of each body is reported)
+    /// - **titles**: `false`
     /// - **dont_filter**: `false` (auto filter wildcard responses)
     /// - **depth**: `4` (maximum recursion depth)
     /// - **force_recursion**: `false` (still respects recursion depth)
+    /// - **directories_first**: `false` (each word's extensions are requested along with it)
+    /// - **inherit_headers**: `None` (recursive scans only send the configured headers)
     /// - **scan_limit**: `0` (no limit on concurrent scans imposed)
+    /// - **recursion_strategy**: `None` (breadth first)
+    /// - **shuffle**: `false`
+    /// - **shuffle_seed**: `0` (picked at random when --shuffle is used)
+    /// - **preview**: `0` (no requests shown before the scan starts)
     /// - **parallel**: `0` (no limit on parallel scans imposed)
     /// - **rate_limit**: `0` (no limit on requests per second imposed)
+    /// - **rate_limit_global**: `0` (no limit on requests per second across all scans imposed)
+    /// - **bandwidth_limit**: `None` (no limit on bytes per second imposed)
+    /// - **pacing**: `None` (requests aren't sent in bursts)
+    /// - **auto_resume**: `0` (scans aren't paused when the target becomes unreachable)
+    /// - **canary**: `None` (scans aren't paused when a host starts banning requests)
+    /// - **canary_interval**: `30`
+    /// - **stability_check**: `false` (findings are only requested once)
+    /// - **stability_interval**: `60`
+    /// - **server_alerts**: `false`
+    /// - **search_regex**: `None` (bodies aren't searched)
+    /// - **entropy_secrets**: `false`
+    /// - **entropy_threshold**: `4.5`
     /// - **time_limit**: `None` (no limit on length of scan imposed)
     /// - **replay_proxy**: `None` (no limit on concurrent scans imposed)
     /// - **replay_codes**: [`DEFAULT_RESPONSE_CODES`](constant.DEFAULT_RESPONSE_CODES.html)
+    /// - **replay_headers**: `None`
+    /// - **replay_client_cert**: `None` (--client-cert is used, if any)
+    /// - **replay_client_key**: `None` (--client-key is used, if any)
     /// - **update_app**: `false`
     ///
     /// After which, any values defined in a
@@ -540,6 +1272,10 @@ impl Configuration {
             // not flipped to false, the program hangs waiting for input from stdin again)
             previous_config.stdin = false;
 
+            Self::seed_shuffle(&mut previous_config);
+            Self::check_safe_methods(&previous_config);
+            Self::check_header_variables(&previous_config);
+
             // clients aren't serialized, have to remake them from the previous config
             Self::try_rebuild_clients(&mut previous_config);
 
@@ -550,12 +1286,122 @@ impl Configuration {
         // merge the cli options into the config file options and return the result
         Self::merge_config(&mut config, cli_config);
 
+        Self::seed_shuffle(&mut config);
+        Self::check_safe_methods(&config);
+        Self::check_header_variables(&config);
+
         // rebuild clients is the last step in either code branch
         Self::try_rebuild_clients(&mut config);
 
         Ok(config)
     }
 
+    /// --shuffle without a seed; the seed picked here is saved in the state file along with the
+    /// rest of the config, so that a resumed scan walks the wordlist in the same order
+    fn seed_shuffle(config: &mut Self) {
+        if config.shuffle && config.shuffle_seed == 0 {
+            config.shuffle_seed = shuffle::random_seed();
+        }
+    }
+
+    /// --safe-methods-only; refuse to start a scan that's configured to send any other method,
+    /// rather than quietly dropping those requests
+    fn check_safe_methods(config: &Self) {
+        if !config.safe_methods_only {
+            return;
+        }
+
+        let refused = safety::unsafe_methods(&config.methods);
+
+        if !refused.is_empty() {
+            report_and_exit(&format!(
+                "--safe-methods-only refuses to send {}; only {} are allowed",
+                refused.join(", "),
+                safety::SAFE_METHODS.join(" and ")
+            ));
+        }
+    }
+
+    /// {{variables}} in header values; an unknown one is refused up front, rather than being
+    /// sent as-is with every request
+    fn check_header_variables(config: &Self) {
+        for (name, value) in &config.headers {
+            if let Err(e) = variables::validate(name, value) {
+                report_and_exit(&e.to_string());
+            }
+        }
+    }
+
+    /// whether any exporter (or --rules, whose body expressions are checked after filtering)
+    /// needs full response bodies, meaning bodies must be kept when responses are sent to the
+    /// output handler
+    pub(crate) fn exports_bodies(&self) -> bool {
+        !self.burp_export.is_empty() || !self.har_export.is_empty() || !self.rules.is_empty()
+    }
+
+    /// build a client with the same settings as the scan's, connecting through the given proxy
+    /// (or directly, when `None`); used to fail over to another proxy (--proxy-failover)
+    pub(crate) fn client_with_proxy(&self, proxy: Option<&str>) -> Result<Client> {
+        let host_override = HostOverride::new(&self.target_url, &self.host_header, &self.sni)?;
+        self.client_with_override(proxy, host_override.as_ref())
+    }
+
+    /// build a client with the same settings as `client`, that connects to the given address
+    /// whenever a request is addressed to the given target's host (--fan-out-ips); the Host
+    /// header and TLS SNI are left as they'd be otherwise
+    pub(crate) fn client_pinned_to(&self, target: &Url, ip: IpAddr) -> Result<Client> {
+        let host = target
+            .host_str()
+            .with_context(|| format!("{target} has no host to pin"))?;
+
+        let port = target
+            .port_or_known_default()
+            .with_context(|| format!("{target} has no known port"))?;
+
+        let pin = HostOverride {
+            sni: host.to_string(),
+            presents_sni: true,
+            host: match target.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            },
+            addr: SocketAddr::new(ip, port),
+        };
+
+        self.client_with_override(None, Some(&pin))
+    }
+
+    /// build a client from this configuration, through the given proxy and with the given
+    /// connection details
+    fn client_with_override(
+        &self,
+        proxy: Option<&str>,
+        host_override: Option<&HostOverride>,
+    ) -> Result<Client> {
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+
+        let client_cert = optional(&self.client_cert);
+        let client_key = optional(&self.client_key);
+        let ca_bundle = optional(&self.ca_bundle);
+
+        client::initialize(
+            self.timeout,
+            self.connect_timeout,
+            &self.user_agent,
+            self.redirects,
+            self.insecure,
+            &self.headers,
+            proxy,
+            &self.server_certs,
+            ca_bundle.as_deref(),
+            client_cert.as_deref(),
+            client_key.as_deref(),
+            host_override,
+            self.doh_resolver.as_ref(),
+            IpFamily::from_flags(self.ipv4, self.ipv6),
+        )
+    }
+
     /// Parse all possible versions of the ferox-config.toml file, adhering to the order of
     /// precedence outlined above
     fn parse_config_files(config: &mut Self) -> Result<()> {
@@ -571,9 +1417,7 @@ impl Configuration {
         //   - current directory
 
         // merge a config found at /etc/feroxbuster/ferox-config.toml
-        let config_file = PathBuf::new()
-            .join("/etc/feroxbuster")
-            .join(DEFAULT_CONFIG_NAME);
+        let config_file = PathBuf::from("/etc/feroxbuster").join(DEFAULT_CONFIG_NAME);
         Self::parse_and_merge_config(config_file, config)?;
 
         // merge a config found at ~/.config/feroxbuster/ferox-config.toml
@@ -610,16 +1454,207 @@ impl Configuration {
         update_config_with_num_type_if_present!(&mut config.parallel, args, "parallel", usize);
         update_config_with_num_type_if_present!(&mut config.depth, args, "depth", usize);
         update_config_with_num_type_if_present!(&mut config.scan_limit, args, "scan_limit", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.extract_page_limit,
+            args,
+            "extract_page_limit",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.extract_scan_limit,
+            args,
+            "extract_scan_limit",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.extract_limit,
+            args,
+            "extract_limit",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.extract_depth,
+            args,
+            "extract_depth",
+            usize
+        );
+        update_config_with_num_type_if_present!(&mut config.shuffle_seed, args, "shuffle", u64);
+        update_config_with_num_type_if_present!(&mut config.preview, args, "preview", usize);
         update_config_with_num_type_if_present!(&mut config.rate_limit, args, "rate_limit", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.rate_limit_global,
+            args,
+            "rate_limit_global",
+            usize
+        );
+        update_config_with_num_type_if_present!(&mut config.tune_step, args, "tune_step", usize);
+        update_config_with_num_type_if_present!(&mut config.tune_floor, args, "tune_floor", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.tune_ceiling,
+            args,
+            "tune_ceiling",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.policy_error_threshold,
+            args,
+            "policy_error_threshold",
+            usize
+        );
+        update_config_with_num_type_if_present!(&mut config.auto_resume, args, "auto_resume", u64);
+        update_config_with_num_type_if_present!(
+            &mut config.canary_interval,
+            args,
+            "canary_interval",
+            u64
+        );
+        update_config_if_present!(&mut config.canary, args, "canary", String);
+
+        if came_from_cli!(args, "stability_check") {
+            config.stability_check = true;
+        }
+
+        update_config_with_num_type_if_present!(
+            &mut config.stability_interval,
+            args,
+            "stability_interval",
+            u64
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.auto_requeue,
+            args,
+            "auto_requeue",
+            usize
+        );
         update_config_if_present!(&mut config.wordlist, args, "wordlist", String);
+        update_config_if_present!(&mut config.fuzz_mode, args, "fuzz_mode", String);
+
+        if let Some(arg) = args.get_many::<String>("fuzz_wordlists") {
+            config.fuzz_wordlists = arg.map(|val| val.to_string()).collect();
+        }
         update_config_if_present!(&mut config.output, args, "output", String);
         update_config_if_present!(&mut config.debug_log, args, "debug_log", String);
+        update_config_if_present!(&mut config.burp_export, args, "burp_export", String);
+        update_config_if_present!(&mut config.har_export, args, "har_export", String);
+        update_config_if_present!(&mut config.quiet_stream, args, "quiet_stream", String);
+        update_config_if_present!(&mut config.traffic_log, args, "traffic_log", String);
+        update_config_if_present!(&mut config.traffic_log_max, args, "traffic_log_max", String);
+        update_config_if_present!(&mut config.policy_audit, args, "policy_audit", String);
+        update_config_if_present!(&mut config.bandwidth_limit, args, "bandwidth_limit", String);
+        update_config_if_present!(&mut config.pacing, args, "pacing", String);
+        update_config_if_present!(&mut config.screenshots, args, "screenshots", String);
+        update_config_if_present!(&mut config.chrome, args, "chrome", String);
+
+        if let Some(arg) = args.get_many::<String>("screenshot_codes") {
+            config.screenshot_codes = arg
+                .map(|code| {
+                    StatusCode::from_bytes(code.as_bytes())
+                        .unwrap_or_else(|e| report_and_exit(&e.to_string()))
+                        .as_u16()
+                })
+                .collect();
+        }
+
+        if let Some(arg) = args.get_many::<String>("screenshot_types") {
+            config.screenshot_types = arg.map(|val| val.to_string()).collect();
+        }
+        update_config_if_present!(&mut config.pipe_to, args, "pipe_to", String);
+        update_config_if_present!(&mut config.api_export, args, "api_export", String);
+        update_config_if_present!(
+            &mut config.api_export_format,
+            args,
+            "api_export_format",
+            String
+        );
+        update_config_if_present!(&mut config.api_template, args, "api_template", String);
+        update_config_with_num_type_if_present!(
+            &mut config.api_batch_size,
+            args,
+            "api_batch_size",
+            usize
+        );
+        update_config_if_present!(&mut config.dojo_engagement, args, "dojo_engagement", String);
+        update_config_if_present!(&mut config.summary, args, "summary", String);
+        update_config_if_present!(&mut config.record, args, "record", String);
+
+        if let Some(replay) = args.subcommand_matches("replay") {
+            update_config_if_present!(&mut config.replay, replay, "session", String);
+        }
+
+        if args.subcommand_matches("selftest").is_some() {
+            config.selftest = true;
+        }
+        update_config_with_num_type_if_present!(
+            &mut config.plugin_timeout,
+            args,
+            "plugin_timeout",
+            u64
+        );
+
+        if let Some(arg) = args.get_many::<String>("plugins") {
+            config.plugins = arg.map(String::from).collect();
+        }
+
+        if came_from_cli!(args, "categorize") {
+            config.categorize = true;
+        }
+
+        if let Some(arg) = args.get_many::<String>("category_rules") {
+            config.category_rules = arg.map(String::from).collect();
+        }
+        update_config_if_present!(&mut config.rules, args, "rules", String);
+        update_config_if_present!(&mut config.junit, args, "junit", String);
+
+        if came_from_cli!(args, "fail_on_rule") {
+            config.fail_on_rule = true;
+        }
+
+        if let Some(arg) = args.get_many::<String>("exit_codes") {
+            for val in arg {
+                let Some((condition, code)) = val.split_once('=') else {
+                    report_and_exit(&format!(
+                        "Expected CONDITION=CODE for --exit-code; received {val}"
+                    ));
+                };
+
+                let code = code.trim().parse::<i32>().unwrap_or_else(|_| {
+                    report_and_exit(&format!(
+                        "Expected a whole number as the exit code of {condition}; received {code}"
+                    ))
+                });
+
+                config
+                    .exit_codes
+                    .insert(condition.trim().to_lowercase(), code);
+            }
+        }
+
+        if let Ok(Some(ratio)) = args.try_get_one::<f64>("exit_error_ratio") {
+            config.exit_error_ratio = *ratio;
+        }
+
+        if let Ok(Some(bits)) = args.try_get_one::<f64>("entropy_threshold") {
+            config.entropy_threshold = *bits;
+        }
+
         update_config_if_present!(&mut config.resume_from, args, "resume_from", String);
 
         if let Ok(Some(inner)) = args.try_get_one::<String>("time_limit") {
             config.time_limit = inner.to_owned();
         }
 
+        if let Ok(Some(inner)) = args.try_get_one::<String>("tune_on_latency") {
+            config.tune_on_latency = inner.to_owned();
+        }
+
+        if let Ok(Some(ratio)) = args.try_get_one::<f64>("policy_403_ratio") {
+            config.policy_403_ratio = *ratio;
+        }
+
+        if let Ok(Some(ratio)) = args.try_get_one::<f64>("policy_429_ratio") {
+            config.policy_429_ratio = *ratio;
+        }
+
         if let Some(arg) = args.get_many::<String>("status_codes") {
             config.status_codes = arg
                 .map(|code| {
@@ -644,6 +1679,36 @@ impl Configuration {
             config.replay_codes = config.status_codes.clone();
         }
 
+        if let Some(arg) = args.get_many::<String>("policy_error_weights") {
+            for val in arg {
+                let Some((category, weight)) = val.split_once('=') else {
+                    report_and_exit(&format!(
+                        "Expected CATEGORY=WEIGHT for --policy-error-weight; received {val}"
+                    ));
+                };
+
+                let weight = weight.trim().parse::<usize>().unwrap_or_else(|_| {
+                    report_and_exit(&format!(
+                        "Expected a whole number as the weight of {category}; received {weight}"
+                    ))
+                });
+
+                config
+                    .policy_error_weights
+                    .insert(category.trim().to_lowercase(), weight);
+            }
+        }
+
+        if let Some(arg) = args.get_many::<String>("policy_status") {
+            config.policy_status = arg
+                .map(|code| {
+                    StatusCode::from_bytes(code.as_bytes())
+                        .unwrap_or_else(|e| report_and_exit(&e.to_string()))
+                        .as_u16()
+                })
+                .collect();
+        }
+
         if let Some(arg) = args.get_many::<String>("filter_status") {
             config.filter_status = arg
                 .map(|code| {
@@ -660,6 +1725,24 @@ impl Configuration {
                 .collect();
         }
 
+        if let Some(arg) = args.get_many::<String>("inherit_headers") {
+            config.inherit_headers = arg.map(|val| val.to_string()).collect();
+        }
+
+        if let Some(arg) = args.get_many::<String>("ports") {
+            config.ports = arg
+                .map(|val| {
+                    val.trim().parse::<u16>().unwrap_or_else(|_| {
+                        report_and_exit(&format!("--ports expects port numbers, got {val}"))
+                    })
+                })
+                .collect();
+        }
+
+        if let Some(arg) = args.get_many::<String>("scheme_probes") {
+            config.scheme_probes = arg.map(|val| val.to_string()).collect();
+        }
+
         if let Some(arg) = args.get_many::<String>("dont_collect") {
             config.dont_collect = arg.map(|val| val.to_string()).collect();
         }
@@ -676,6 +1759,14 @@ impl Configuration {
                 .collect();
         }
 
+        if came_from_cli!(args, "safe_methods_only") {
+            config.safe_methods_only = true;
+        }
+
+        if came_from_cli!(args, "raw_requests") {
+            config.raw_requests = true;
+        }
+
         if let Some(arg) = args.get_one::<String>("data") {
             if let Some(stripped) = arg.strip_prefix('@') {
                 config.data =
@@ -687,6 +1778,8 @@ impl Configuration {
 
         if came_from_cli!(args, "stdin") {
             config.stdin = true;
+        } else if let Some(filename) = args.get_one::<String>("import_nmap") {
+            config.import_nmap = filename.into();
         } else if let Some(url) = args.get_one::<String>("url") {
             config.target_url = url.into();
         }
@@ -735,14 +1828,32 @@ impl Configuration {
             }
         }
 
+        if let Some(arg) = args.get_many::<String>("dont_scan_regex") {
+            // unlike --dont-scan, always a regular expression; no guessing as to whether a
+            // pattern like https?://.*/logout is meant to be a url
+            for denier in arg {
+                let regex = Regex::new(denier).unwrap_or_else(|e| report_and_exit(&e.to_string()));
+
+                config.regex_denylist.push(regex);
+            }
+        }
+
         if let Some(arg) = args.get_many::<String>("filter_regex") {
             config.filter_regex = arg.map(|val| val.to_string()).collect();
         }
 
+        if let Some(arg) = args.get_many::<String>("search_regex") {
+            config.search_regex = arg.map(|val| val.to_string()).collect();
+        }
+
         if let Some(arg) = args.get_many::<String>("filter_similar") {
             config.filter_similar = arg.map(|val| val.to_string()).collect();
         }
 
+        if let Some(arg) = args.get_many::<String>("filter_category") {
+            config.filter_category = arg.map(|val| val.to_string()).collect();
+        }
+
         if let Some(arg) = args.get_many::<String>("filter_size") {
             config.filter_size = arg
                 .map(|size| {
@@ -797,18 +1908,41 @@ impl Configuration {
             config.requester_policy = RequesterPolicy::AutoBail;
         }
 
+        if config.auto_requeue > 0 {
+            config.requester_policy = RequesterPolicy::AutoRequeue;
+        }
+
         if came_from_cli!(args, "no_state") {
             config.save_state = false;
         }
 
+        if came_from_cli!(args, "traffic_log_bodies") {
+            config.traffic_log_bodies = true;
+        }
+
         if came_from_cli!(args, "dont_filter") {
             config.dont_filter = true;
         }
 
+        if came_from_cli!(args, "shuffle") {
+            config.shuffle = true;
+        }
+
         if came_from_cli!(args, "collect_extensions") || came_from_cli!(args, "thorough") {
             config.collect_extensions = true;
         }
 
+        if came_from_cli!(args, "prune_extensions") {
+            config.prune_extensions = true;
+        }
+
+        update_config_with_num_type_if_present!(
+            &mut config.prune_after,
+            args,
+            "prune_after",
+            usize
+        );
+
         if came_from_cli!(args, "collect_backups")
             || came_from_cli!(args, "smart")
             || came_from_cli!(args, "thorough")
@@ -816,6 +1950,38 @@ impl Configuration {
             config.collect_backups = true;
         }
 
+        if came_from_cli!(args, "bypass_403") {
+            config.bypass_403 = true;
+        }
+
+        if came_from_cli!(args, "vcs_check") {
+            config.vcs_check = true;
+        }
+
+        if came_from_cli!(args, "archive_peek") {
+            config.archive_peek = true;
+        }
+
+        update_config_if_present!(
+            &mut config.archive_max_size,
+            args,
+            "archive_max_size",
+            String
+        );
+
+        update_config_with_num_type_if_present!(
+            &mut config.bypass_limit,
+            args,
+            "bypass_limit",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.bypass_rate,
+            args,
+            "bypass_rate",
+            usize
+        );
+
         if came_from_cli!(args, "collect_words")
             || came_from_cli!(args, "smart")
             || came_from_cli!(args, "thorough")
@@ -837,18 +2003,76 @@ impl Configuration {
             config.add_slash = true;
         }
 
+        if came_from_cli!(args, "dual_slash") {
+            config.dual_slash = true;
+        }
+
+        if came_from_cli!(args, "detect_case") {
+            config.detect_case = true;
+        }
+
+        if came_from_cli!(args, "favicon") {
+            config.favicon = true;
+        }
+
+        update_config_if_present!(&mut config.favicon_db, args, "favicon_db", String);
+
+        if came_from_cli!(args, "well_known") {
+            config.well_known = true;
+        }
+
+        if came_from_cli!(args, "bucket_listing") {
+            config.bucket_listing = true;
+        }
+
+        if came_from_cli!(args, "range_probe") {
+            config.range_probe = true;
+        }
+
+        if came_from_cli!(args, "decompress") {
+            config.decompress = true;
+        }
+
         if came_from_cli!(args, "dont_extract_links") {
             config.extract_links = false;
         }
 
+        if came_from_cli!(args, "crawl") {
+            config.crawl = true;
+        }
+
         if came_from_cli!(args, "json") {
             config.json = true;
         }
 
+        if came_from_cli!(args, "punycode") {
+            config.punycode = true;
+        }
+
+        if came_from_cli!(args, "fuzzy_hash") {
+            config.fuzzy_hash = true;
+        }
+
+        if came_from_cli!(args, "titles") {
+            config.titles = true;
+        }
+
+        if came_from_cli!(args, "server_alerts") {
+            config.server_alerts = true;
+        }
+
+        if came_from_cli!(args, "entropy_secrets") {
+            config.entropy_secrets = true;
+        }
+
         if came_from_cli!(args, "force_recursion") {
             config.force_recursion = true;
         }
 
+        if came_from_cli!(args, "directories_first") {
+            config.directories_first = true;
+        }
+
         if came_from_cli!(args, "update_app") {
             config.update_app = true;
         }
@@ -857,11 +2081,79 @@ impl Configuration {
         // organizational breakpoint; all options below alter the Client configuration
         ////
         update_config_if_present!(&mut config.proxy, args, "proxy", String);
+
+        if let Some(arg) = args.get_many::<String>("proxy_failover") {
+            config.proxy_failover = arg.map(String::from).collect();
+        }
+
+        if came_from_cli!(args, "proxy_failover_direct") {
+            config.proxy_failover_direct = true;
+        }
+
+        update_config_if_present!(&mut config.host_header, args, "host_header", String);
+        update_config_if_present!(&mut config.sni, args, "sni", String);
+        update_config_if_present!(&mut config.doh, args, "doh", String);
+
+        if came_from_cli!(args, "ipv4") {
+            config.ipv4 = true;
+        }
+
+        if came_from_cli!(args, "ipv6") {
+            config.ipv6 = true;
+        }
+
+        if came_from_cli!(args, "fan_out_ips") {
+            config.fan_out_ips = true;
+        }
         update_config_if_present!(&mut config.client_cert, args, "client_cert", String);
         update_config_if_present!(&mut config.client_key, args, "client_key", String);
+        update_config_if_present!(&mut config.ca_bundle, args, "ca_bundle", String);
         update_config_if_present!(&mut config.replay_proxy, args, "replay_proxy", String);
+        update_config_if_present!(
+            &mut config.replay_client_cert,
+            args,
+            "replay_client_cert",
+            String
+        );
+        update_config_if_present!(
+            &mut config.replay_client_key,
+            args,
+            "replay_client_key",
+            String
+        );
         update_config_if_present!(&mut config.user_agent, args, "user_agent", String);
+        update_config_if_present!(&mut config.encode, args, "encode", String);
+        update_config_if_present!(&mut config.evasion, args, "evasion", String);
+        update_config_if_present!(&mut config.tune_strategy, args, "tune_strategy", String);
+        update_config_if_present!(&mut config.extension_order, args, "extension_order", String);
+        update_config_if_present!(
+            &mut config.recursion_strategy,
+            args,
+            "recursion_strategy",
+            String
+        );
+        update_config_if_present!(&mut config.baseline, args, "baseline", String);
+        update_config_if_present!(&mut config.baseline_url, args, "baseline_url", String);
+        update_config_if_present!(&mut config.accept_encoding, args, "accept_encoding", String);
+        update_config_if_present!(
+            &mut config.reuse_signatures,
+            args,
+            "reuse_signatures",
+            String
+        );
         update_config_with_num_type_if_present!(&mut config.timeout, args, "timeout", u64);
+        update_config_with_num_type_if_present!(
+            &mut config.connect_timeout,
+            args,
+            "connect_timeout",
+            u64
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.read_timeout,
+            args,
+            "read_timeout",
+            u64
+        );
 
         if came_from_cli!(args, "burp") {
             config.proxy = String::from("http://127.0.0.1:8080");
@@ -900,6 +2192,20 @@ impl Configuration {
             }
         }
 
+        update_config_if_present!(&mut config.request_id, args, "request_id", String);
+
+        if let Some(headers) = args.get_many::<String>("replay_headers") {
+            for val in headers {
+                // same Name:value format as --headers
+                let mut split_val = val.split(':');
+                let name = split_val.next().unwrap().trim();
+                let value = split_val.collect::<Vec<&str>>().join(":");
+                config
+                    .replay_headers
+                    .insert(name.to_string(), value.to_string());
+            }
+        }
+
         if let Some(cookies) = args.get_many::<String>("cookies") {
             config.headers.insert(
                 // we know the header name is always "cookie"
@@ -935,6 +2241,18 @@ impl Configuration {
             }
         }
 
+        if let Some(pins) = args.get_many::<String>("pin_sha256") {
+            for val in pins {
+                let pin = tls::normalize_pin(val).unwrap_or_else(|| {
+                    report_and_exit(&format!(
+                        "--pin-sha256 expects a SHA-256 fingerprint (64 hex characters, colons optional), got {val}"
+                    ))
+                });
+
+                config.pin_sha256.push(pin);
+            }
+        }
+
         config
     }
 
@@ -942,6 +2260,19 @@ impl Configuration {
     /// either the config file or command line arguments; if we have, we need to rebuild
     /// the client and store it in the config struct
     fn try_rebuild_clients(configuration: &mut Configuration) {
+        // --accept-encoding is sent like any other header; one given via -H wins
+        if !configuration.accept_encoding.is_empty()
+            && !configuration
+                .headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case("accept-encoding"))
+        {
+            configuration.headers.insert(
+                String::from("Accept-Encoding"),
+                configuration.accept_encoding.clone(),
+            );
+        }
+
         // check if the proxy and certificate fields are empty
         // and parse them into Some or None variants ahead of time
         // so we may use the is_some method on them instead of
@@ -966,8 +2297,53 @@ impl Configuration {
             Some(configuration.client_key.as_str())
         };
 
+        let ca_bundle = if configuration.ca_bundle.is_empty() {
+            None
+        } else {
+            Some(configuration.ca_bundle.as_str())
+        };
+
+        let host_override = HostOverride::new(
+            &configuration.target_url,
+            &configuration.host_header,
+            &configuration.sni,
+        )
+        .unwrap_or_else(|e| report_and_exit(&e.to_string()));
+
+        let family = IpFamily::from_flags(configuration.ipv4, configuration.ipv6);
+
+        if let Some(family) = family {
+            // -4/-6 only limit what names resolve to; a target given as an address has to
+            // belong to the family already
+            let target_ip = Url::parse(&configuration.target_url).ok().and_then(|url| {
+                url.host_str()?
+                    .trim_matches(['[', ']'])
+                    .parse::<IpAddr>()
+                    .ok()
+            });
+
+            if let Some(ip) = target_ip.filter(|ip| !family.contains(ip)) {
+                report_and_exit(&format!(
+                    "{} is not an {} address, which is all -4/-6 allows",
+                    ip,
+                    family.name()
+                ));
+            }
+        }
+
+        if !configuration.doh.is_empty() {
+            // built here so a bad endpoint is reported like any other bad value
+            configuration.doh_resolver = Some(
+                DohResolver::new(&configuration.doh, family)
+                    .unwrap_or_else(|e| report_and_exit(&e.to_string())),
+            );
+        }
+
+        let doh = configuration.doh_resolver.as_ref();
+
         if proxy.is_some()
             || configuration.timeout != timeout()
+            || configuration.connect_timeout != 0
             || configuration.user_agent != user_agent()
             || configuration.redirects
             || configuration.insecure
@@ -976,34 +2352,61 @@ impl Configuration {
             || !server_certs.is_empty()
             || client_cert.is_some()
             || client_key.is_some()
+            || ca_bundle.is_some()
+            || host_override.is_some()
+            || doh.is_some()
+            || family.is_some()
         {
             configuration.client = client::initialize(
                 configuration.timeout,
+                configuration.connect_timeout,
                 &configuration.user_agent,
                 configuration.redirects,
                 configuration.insecure,
                 &configuration.headers,
                 proxy,
                 server_certs,
+                ca_bundle,
                 client_cert,
                 client_key,
+                host_override.as_ref(),
+                doh,
+                family,
             )
             .expect("Could not rebuild client");
         }
 
         if !configuration.replay_proxy.is_empty() {
-            // only set replay_client when replay_proxy is set
+            // only set replay_client when replay_proxy is set; replayed requests carry their own
+            // headers and client certificate, if given, so they can be told apart at the proxy
+            let mut replay_headers = configuration.headers.clone();
+            replay_headers.extend(configuration.replay_headers.clone());
+
+            let (client_cert, client_key) = if configuration.replay_client_cert.is_empty() {
+                (client_cert, client_key)
+            } else {
+                (
+                    Some(configuration.replay_client_cert.as_str()),
+                    Some(configuration.replay_client_key.as_str()),
+                )
+            };
+
             configuration.replay_client = Some(
                 client::initialize(
                     configuration.timeout,
+                    configuration.connect_timeout,
                     &configuration.user_agent,
                     configuration.redirects,
                     configuration.insecure,
-                    &configuration.headers,
+                    &replay_headers,
                     Some(&configuration.replay_proxy),
                     server_certs,
+                    ca_bundle,
                     client_cert,
                     client_key,
+                    host_override.as_ref(),
+                    doh,
+                    family,
                 )
                 .expect("Could not rebuild client"),
             );
@@ -1012,7 +2415,7 @@ impl Configuration {
 
     /// Given a configuration file's location and an instance of `Configuration`, read in
     /// the config file if found and update the current settings with the settings found therein
-    fn parse_and_merge_config(config_file: PathBuf, mut config: &mut Self) -> Result<()> {
+    fn parse_and_merge_config(config_file: PathBuf, config: &mut Self) -> Result<()> {
         if config_file.exists() {
             // save off a string version of the path before it goes out of scope
             let conf_str = config_file.to_str().unwrap_or("").to_string();
@@ -1038,6 +2441,16 @@ impl Configuration {
         update_if_not_default!(&mut conf.target_url, new.target_url, "");
         update_if_not_default!(&mut conf.time_limit, new.time_limit, "");
         update_if_not_default!(&mut conf.proxy, new.proxy, "");
+        update_if_not_default!(
+            &mut conf.proxy_failover,
+            new.proxy_failover,
+            Vec::<String>::new()
+        );
+        update_if_not_default!(
+            &mut conf.proxy_failover_direct,
+            new.proxy_failover_direct,
+            false
+        );
         update_if_not_default!(
             &mut conf.server_certs,
             new.server_certs,
@@ -1045,24 +2458,96 @@ impl Configuration {
         );
         update_if_not_default!(&mut conf.client_cert, new.client_cert, "");
         update_if_not_default!(&mut conf.client_key, new.client_key, "");
+        update_if_not_default!(&mut conf.ca_bundle, new.ca_bundle, "");
+        update_if_not_default!(&mut conf.pin_sha256, new.pin_sha256, Vec::<String>::new());
         update_if_not_default!(&mut conf.verbosity, new.verbosity, 0);
         update_if_not_default!(&mut conf.silent, new.silent, false);
         update_if_not_default!(&mut conf.quiet, new.quiet, false);
         update_if_not_default!(&mut conf.auto_bail, new.auto_bail, false);
+        update_if_not_default!(&mut conf.auto_requeue, new.auto_requeue, 0);
         update_if_not_default!(&mut conf.auto_tune, new.auto_tune, false);
+        update_if_not_default!(&mut conf.tune_strategy, new.tune_strategy, "");
+        update_if_not_default!(&mut conf.tune_on_latency, new.tune_on_latency, "");
+        update_if_not_default!(&mut conf.tune_step, new.tune_step, 0);
+        update_if_not_default!(&mut conf.tune_floor, new.tune_floor, 0);
+        update_if_not_default!(&mut conf.tune_ceiling, new.tune_ceiling, 0);
+        update_if_not_default!(
+            &mut conf.policy_403_ratio,
+            new.policy_403_ratio,
+            policy_403_ratio()
+        );
+        update_if_not_default!(
+            &mut conf.policy_429_ratio,
+            new.policy_429_ratio,
+            policy_429_ratio()
+        );
+        update_if_not_default!(
+            &mut conf.policy_status,
+            new.policy_status,
+            Vec::<u16>::new()
+        );
+        update_if_not_default!(
+            &mut conf.policy_error_threshold,
+            new.policy_error_threshold,
+            policy_error_threshold()
+        );
+        update_if_not_default!(
+            &mut conf.policy_error_weights,
+            new.policy_error_weights,
+            HashMap::<String, usize>::new()
+        );
         update_if_not_default!(&mut conf.collect_extensions, new.collect_extensions, false);
+        update_if_not_default!(&mut conf.prune_extensions, new.prune_extensions, false);
+        update_if_not_default!(&mut conf.prune_after, new.prune_after, prune_after());
         update_if_not_default!(&mut conf.collect_backups, new.collect_backups, false);
+        update_if_not_default!(&mut conf.bypass_403, new.bypass_403, false);
+        update_if_not_default!(&mut conf.bypass_limit, new.bypass_limit, bypass_limit());
+        update_if_not_default!(&mut conf.bypass_rate, new.bypass_rate, bypass_rate());
+        update_if_not_default!(&mut conf.vcs_check, new.vcs_check, false);
+        update_if_not_default!(&mut conf.archive_peek, new.archive_peek, false);
+        update_if_not_default!(
+            &mut conf.archive_max_size,
+            new.archive_max_size,
+            archive_max_size()
+        );
         update_if_not_default!(&mut conf.collect_words, new.collect_words, false);
         // use updated quiet/silent values to determine output level; same for requester policy
         conf.output_level = determine_output_level(conf.quiet, conf.silent);
-        conf.requester_policy = determine_requester_policy(conf.auto_tune, conf.auto_bail);
+        conf.requester_policy =
+            determine_requester_policy(conf.auto_tune, conf.auto_bail, conf.auto_requeue > 0);
         update_if_not_default!(&mut conf.output, new.output, "");
         update_if_not_default!(&mut conf.redirects, new.redirects, false);
         update_if_not_default!(&mut conf.insecure, new.insecure, false);
+        update_if_not_default!(&mut conf.host_header, new.host_header, "");
+        update_if_not_default!(&mut conf.sni, new.sni, "");
+        update_if_not_default!(&mut conf.doh, new.doh, "");
+
+        if new.ipv4 || new.ipv6 {
+            // -4 and -6 replace one another, otherwise a config file's -4 and a -6 given on the
+            // command line would cancel out
+            conf.ipv4 = new.ipv4;
+            conf.ipv6 = new.ipv6;
+        }
+
+        update_if_not_default!(&mut conf.fan_out_ips, new.fan_out_ips, false);
         update_if_not_default!(&mut conf.force_recursion, new.force_recursion, false);
+        update_if_not_default!(&mut conf.directories_first, new.directories_first, false);
+        update_if_not_default!(
+            &mut conf.inherit_headers,
+            new.inherit_headers,
+            Vec::<String>::new()
+        );
         update_if_not_default!(&mut conf.extract_links, new.extract_links, extract_links());
+        update_if_not_default!(&mut conf.extract_page_limit, new.extract_page_limit, 0);
+        update_if_not_default!(&mut conf.extract_scan_limit, new.extract_scan_limit, 0);
+        update_if_not_default!(&mut conf.extract_limit, new.extract_limit, 0);
+        update_if_not_default!(&mut conf.extract_depth, new.extract_depth, 0);
+        update_if_not_default!(&mut conf.crawl, new.crawl, false);
         update_if_not_default!(&mut conf.extensions, new.extensions, Vec::<String>::new());
+        update_if_not_default!(&mut conf.extension_order, new.extension_order, "");
         update_if_not_default!(&mut conf.methods, new.methods, methods());
+        update_if_not_default!(&mut conf.safe_methods_only, new.safe_methods_only, false);
+        update_if_not_default!(&mut conf.raw_requests, new.raw_requests, false);
         update_if_not_default!(&mut conf.data, new.data, Vec::<u8>::new());
         update_if_not_default!(&mut conf.url_denylist, new.url_denylist, Vec::<Url>::new());
         update_if_not_default!(&mut conf.update_app, new.update_app, false);
@@ -1075,10 +2560,33 @@ impl Configuration {
             conf.regex_denylist = new.regex_denylist;
         }
         update_if_not_default!(&mut conf.headers, new.headers, HashMap::new());
+        update_if_not_default!(&mut conf.request_id, new.request_id, "");
         update_if_not_default!(&mut conf.queries, new.queries, Vec::new());
         update_if_not_default!(&mut conf.no_recursion, new.no_recursion, false);
         update_if_not_default!(&mut conf.add_slash, new.add_slash, false);
+        update_if_not_default!(&mut conf.dual_slash, new.dual_slash, false);
+        update_if_not_default!(&mut conf.detect_case, new.detect_case, false);
+        update_if_not_default!(&mut conf.favicon, new.favicon, false);
+        update_if_not_default!(&mut conf.favicon_db, new.favicon_db, "");
+        update_if_not_default!(&mut conf.well_known, new.well_known, false);
+        update_if_not_default!(&mut conf.bucket_listing, new.bucket_listing, false);
+        update_if_not_default!(&mut conf.baseline, new.baseline, "");
+        update_if_not_default!(&mut conf.baseline_url, new.baseline_url, "");
+        update_if_not_default!(&mut conf.reuse_signatures, new.reuse_signatures, "");
+        update_if_not_default!(&mut conf.encode, new.encode, "");
+        update_if_not_default!(&mut conf.evasion, new.evasion, "");
+        update_if_not_default!(
+            &mut conf.evasion_profiles,
+            new.evasion_profiles,
+            HashMap::new()
+        );
+        update_if_not_default!(&mut conf.range_probe, new.range_probe, false);
+        update_if_not_default!(&mut conf.accept_encoding, new.accept_encoding, "");
+        update_if_not_default!(&mut conf.decompress, new.decompress, false);
         update_if_not_default!(&mut conf.stdin, new.stdin, false);
+        update_if_not_default!(&mut conf.import_nmap, new.import_nmap, "");
+        update_if_not_default!(&mut conf.ports, new.ports, Vec::<u16>::new());
+        update_if_not_default!(&mut conf.scheme_probes, new.scheme_probes, scheme_probes());
         update_if_not_default!(&mut conf.filter_size, new.filter_size, Vec::<u64>::new());
         update_if_not_default!(
             &mut conf.filter_regex,
@@ -1090,6 +2598,11 @@ impl Configuration {
             new.filter_similar,
             Vec::<String>::new()
         );
+        update_if_not_default!(
+            &mut conf.filter_category,
+            new.filter_category,
+            Vec::<String>::new()
+        );
         update_if_not_default!(
             &mut conf.filter_word_count,
             new.filter_word_count,
@@ -1107,19 +2620,123 @@ impl Configuration {
         );
         update_if_not_default!(&mut conf.dont_filter, new.dont_filter, false);
         update_if_not_default!(&mut conf.scan_limit, new.scan_limit, 0);
+        update_if_not_default!(&mut conf.recursion_strategy, new.recursion_strategy, "");
+        update_if_not_default!(&mut conf.shuffle, new.shuffle, false);
+        update_if_not_default!(&mut conf.shuffle_seed, new.shuffle_seed, 0);
+        update_if_not_default!(&mut conf.preview, new.preview, 0);
         update_if_not_default!(&mut conf.parallel, new.parallel, 0);
         update_if_not_default!(&mut conf.rate_limit, new.rate_limit, 0);
+        update_if_not_default!(&mut conf.rate_limit_global, new.rate_limit_global, 0);
+        update_if_not_default!(&mut conf.bandwidth_limit, new.bandwidth_limit, "");
+        update_if_not_default!(&mut conf.pacing, new.pacing, "");
+        update_if_not_default!(&mut conf.auto_resume, new.auto_resume, 0);
+        update_if_not_default!(&mut conf.canary, new.canary, "");
+        update_if_not_default!(
+            &mut conf.canary_interval,
+            new.canary_interval,
+            canary_interval()
+        );
+        update_if_not_default!(&mut conf.stability_check, new.stability_check, false);
+        update_if_not_default!(
+            &mut conf.stability_interval,
+            new.stability_interval,
+            stability_interval()
+        );
+        update_if_not_default!(&mut conf.server_alerts, new.server_alerts, false);
+        update_if_not_default!(
+            &mut conf.search_regex,
+            new.search_regex,
+            Vec::<String>::new()
+        );
+        update_if_not_default!(&mut conf.entropy_secrets, new.entropy_secrets, false);
+        update_if_not_default!(
+            &mut conf.entropy_threshold,
+            new.entropy_threshold,
+            entropy_threshold()
+        );
         update_if_not_default!(&mut conf.replay_proxy, new.replay_proxy, "");
+        update_if_not_default!(&mut conf.replay_headers, new.replay_headers, HashMap::new());
+        update_if_not_default!(&mut conf.replay_client_cert, new.replay_client_cert, "");
+        update_if_not_default!(&mut conf.replay_client_key, new.replay_client_key, "");
         update_if_not_default!(&mut conf.debug_log, new.debug_log, "");
+        update_if_not_default!(&mut conf.burp_export, new.burp_export, "");
+        update_if_not_default!(&mut conf.har_export, new.har_export, "");
+        update_if_not_default!(&mut conf.quiet_stream, new.quiet_stream, "");
+        update_if_not_default!(&mut conf.traffic_log, new.traffic_log, "");
+        update_if_not_default!(&mut conf.traffic_log_max, new.traffic_log_max, "");
+        update_if_not_default!(&mut conf.traffic_log_bodies, new.traffic_log_bodies, false);
+        update_if_not_default!(&mut conf.policy_audit, new.policy_audit, "");
+        update_if_not_default!(&mut conf.screenshots, new.screenshots, "");
+        update_if_not_default!(&mut conf.chrome, new.chrome, "");
+        update_if_not_default!(
+            &mut conf.screenshot_codes,
+            new.screenshot_codes,
+            screenshot_codes()
+        );
+        update_if_not_default!(
+            &mut conf.screenshot_types,
+            new.screenshot_types,
+            screenshot_types()
+        );
+        update_if_not_default!(&mut conf.pipe_to, new.pipe_to, "");
+        update_if_not_default!(&mut conf.api_export, new.api_export, "");
+        update_if_not_default!(&mut conf.api_export_format, new.api_export_format, "");
+        update_if_not_default!(&mut conf.api_template, new.api_template, "");
+        update_if_not_default!(
+            &mut conf.api_batch_size,
+            new.api_batch_size,
+            api_batch_size()
+        );
+        update_if_not_default!(&mut conf.dojo_engagement, new.dojo_engagement, "");
+        update_if_not_default!(&mut conf.summary, new.summary, "");
+        update_if_not_default!(&mut conf.record, new.record, "");
+        update_if_not_default!(&mut conf.replay, new.replay, "");
+        update_if_not_default!(&mut conf.selftest, new.selftest, false);
+        update_if_not_default!(&mut conf.plugins, new.plugins, Vec::<String>::new());
+        update_if_not_default!(
+            &mut conf.plugin_timeout,
+            new.plugin_timeout,
+            plugin_timeout()
+        );
+        update_if_not_default!(&mut conf.categorize, new.categorize, false);
+        update_if_not_default!(
+            &mut conf.category_rules,
+            new.category_rules,
+            Vec::<String>::new()
+        );
+        update_if_not_default!(&mut conf.rules, new.rules, "");
+        update_if_not_default!(&mut conf.junit, new.junit, "");
+        update_if_not_default!(&mut conf.fail_on_rule, new.fail_on_rule, false);
+        update_if_not_default!(
+            &mut conf.exit_codes,
+            new.exit_codes,
+            HashMap::<String, i32>::new()
+        );
+        update_if_not_default!(
+            &mut conf.exit_error_ratio,
+            new.exit_error_ratio,
+            exit_error_ratio()
+        );
         update_if_not_default!(&mut conf.resume_from, new.resume_from, "");
         update_if_not_default!(&mut conf.json, new.json, false);
+        update_if_not_default!(&mut conf.punycode, new.punycode, false);
+        update_if_not_default!(&mut conf.fuzzy_hash, new.fuzzy_hash, false);
+        update_if_not_default!(&mut conf.titles, new.titles, false);
 
         update_if_not_default!(&mut conf.timeout, new.timeout, timeout());
+        update_if_not_default!(&mut conf.connect_timeout, new.connect_timeout, 0);
+        update_if_not_default!(&mut conf.read_timeout, new.read_timeout, 0);
         update_if_not_default!(&mut conf.user_agent, new.user_agent, user_agent());
         update_if_not_default!(&mut conf.random_agent, new.random_agent, false);
         update_if_not_default!(&mut conf.threads, new.threads, threads());
         update_if_not_default!(&mut conf.depth, new.depth, depth());
         update_if_not_default!(&mut conf.wordlist, new.wordlist, wordlist());
+        update_if_not_default!(
+            &mut conf.fuzz_wordlists,
+            new.fuzz_wordlists,
+            Vec::<String>::new()
+        );
+        update_if_not_default!(&mut conf.fuzz_mode, new.fuzz_mode, "");
         update_if_not_default!(&mut conf.status_codes, new.status_codes, status_codes());
         // status_codes() is the default for replay_codes, if they're not provided
         update_if_not_default!(&mut conf.replay_codes, new.replay_codes, status_codes());
@@ -6,4 +6,6 @@ mod utils;
 mod tests;
 
 pub use self::container::Configuration;
-pub use self::utils::{determine_output_level, OutputLevel, RequesterPolicy};
+pub use self::utils::{
+    determine_output_level, determine_requester_policy, OutputLevel, RequesterPolicy,
+};
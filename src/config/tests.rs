@@ -10,55 +10,168 @@ use tempfile::TempDir;
 fn setup_config_test() -> Configuration {
     let data = r#"
             wordlist = "/some/path"
+            fuzz_wordlists = ["FUZZ2=/some/users"]
+            fuzz_mode = "pitchfork"
             status_codes = [201, 301, 401]
             replay_codes = [201, 301]
             threads = 40
             timeout = 5
+            connect_timeout = 2
+            read_timeout = 3
             proxy = "http://127.0.0.1:8080"
             replay_proxy = "http://127.0.0.1:8081"
+            replay_headers = {X-Tag = "ferox"}
+            replay_client_cert = "/some/replay/cert.pem"
+            replay_client_key = "/some/replay/key.pem"
+            proxy_failover = ["http://127.0.0.1:8082", "socks5h://127.0.0.1:9050"]
+            proxy_failover_direct = true
             quiet = true
             silent = true
             auto_tune = true
+            tune_strategy = "gradient"
+            tune_on_latency = "2x-baseline"
+            tune_step = 15
+            policy_403_ratio = 0.95
+            policy_429_ratio = 0.1
+            policy_error_threshold = 100
+            policy_status = [503, 520]
+            policy_error_weights = {timeout = 3, tls = 0}
+            exit_codes = {errors = 3, bail = 4}
+            tune_floor = 20
+            tune_ceiling = 300
             auto_bail = true
+            auto_requeue = 2
             verbosity = 1
             scan_limit = 6
+            recursion_strategy = "dfs"
+            shuffle = true
+            shuffle_seed = 1337
+            preview = 5
             parallel = 14
             rate_limit = 250
+            rate_limit_global = 900
+            bandwidth_limit = "256K"
+            pacing = "low-and-slow"
+            auto_resume = 30
+            canary = "/health"
+            canary_interval = 45
+            stability_check = true
+            stability_interval = 90
+            server_alerts = true
+            search_regex = ["api[_-]?key", "BEGIN RSA"]
+            entropy_secrets = true
+            entropy_threshold = 4.0
             time_limit = "10m"
             output = "/some/otherpath"
             debug_log = "/yet/anotherpath"
+            burp_export = "/some/burp/export.xml"
+            har_export = "/some/har/export.har"
+            quiet_stream = "/some/quiet/stream.txt"
+            traffic_log = "/some/traffic/dir"
+            traffic_log_max = "100M"
+            traffic_log_bodies = true
+            policy_audit = "/some/policy-audit.jsonl"
+            screenshots = "/some/screenshots"
+            chrome = "/usr/bin/chromium"
+            screenshot_codes = [200, 401]
+            screenshot_types = ["text/html", "image/"]
+            pipe_to = "jq -r .url"
+            api_export = "https://dojo.example.com/api/v2/import-scan/"
+            api_export_format = "dojo"
+            api_template = "/some/template.json"
+            api_batch_size = 250
+            dojo_engagement = "42"
+            summary = "/some/summary.json"
+            record = "/some/session.bin"
+            replay = "/some/session.bin"
+            plugins = ["/some/plugin.lua", "/another/plugin.lua"]
+            plugin_timeout = 250
+            categorize = true
+            category_rules = ["login-page:/login"]
+            rules = "/some/rules.toml"
+            junit = "/some/junit.xml"
+            fail_on_rule = true
+            exit_error_ratio = 0.5
             resume_from = "/some/state/file"
             redirects = true
             insecure = true
+            host_header = "internal.app"
+            sni = "cdn.internal.app"
+            doh = "https://1.1.1.1/dns-query"
+            ipv6 = true
+            fan_out_ips = true
             collect_backups = true
+            bypass_403 = true
+            bypass_limit = 20
+            bypass_rate = 5
+            vcs_check = true
+            archive_peek = true
+            archive_max_size = "2M"
             collect_extensions = true
+            prune_extensions = true
+            prune_after = 100
             collect_words = true
             extensions = ["html", "php", "js"]
+            extension_order = "interleaved"
             dont_collect = ["png", "gif", "jpg", "jpeg"]
             methods = ["GET", "PUT", "DELETE"]
+            safe_methods_only = true
+            raw_requests = true
             data = [31, 32, 33, 34]
             url_denylist = ["http://dont-scan.me", "https://also-not.me"]
             regex_denylist = ["/deny.*"]
             headers = {stuff = "things", mostuff = "mothings"}
+            request_id = "X-Ferox-Scan-Id"
             queries = [["name","value"], ["rick", "astley"]]
             no_recursion = true
             add_slash = true
+            dual_slash = true
+            detect_case = true
+            favicon = true
+            favicon_db = "/some/favicons.csv"
+            well_known = true
+            bucket_listing = true
+            baseline = "/some/previous/scan.json"
+            baseline_url = "http://localhost/home"
+            reuse_signatures = "/some/previous/ferox.state"
+            encode = "unicode"
+            evasion = "sneaky"
+            evasion_profiles = {sneaky = ["dot-segment", "path-params"]}
+            range_probe = true
+            accept_encoding = "gzip, deflate"
+            decompress = true
             stdin = true
+            import_nmap = "/some/nmap/scan.xml"
+            ports = [80, 443, 8080]
+            scheme_probes = ["http:8080", "https:8443"]
             dont_filter = true
             extract_links = false
+            extract_page_limit = 25
+            extract_scan_limit = 250
+            extract_limit = 2500
+            extract_depth = 2
+            crawl = true
             json = true
+            punycode = true
+            fuzzy_hash = true
+            titles = true
             save_state = false
             depth = 1
             force_recursion = true
+            directories_first = true
+            inherit_headers = ["Set-Cookie", "X-CSRF-Token"]
             filter_size = [4120]
             filter_regex = ["^ignore me$"]
             filter_similar = ["https://somesite.com/soft404"]
+            filter_category = ["backup-file"]
             filter_word_count = [994, 992]
             filter_line_count = [34]
             filter_status = [201]
             server_certs = ["/some/cert.pem", "/some/other/cert.pem"]
             client_cert = "/some/client/cert.pem"
             client_key = "/some/client/key.pem"
+            ca_bundle = "/some/corp/bundle.pem"
+            pin_sha256 = ["9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"]
         "#;
     let tmp_dir = TempDir::new().unwrap();
     let file = tmp_dir.path().join(DEFAULT_CONFIG_NAME);
@@ -71,58 +184,173 @@ fn setup_config_test() -> Configuration {
 fn default_configuration() {
     let config = Configuration::default();
     assert_eq!(config.wordlist, wordlist());
+    assert_eq!(config.fuzz_wordlists, Vec::<String>::new());
+    assert_eq!(config.fuzz_mode, "");
     assert_eq!(config.proxy, String::new());
     assert_eq!(config.target_url, String::new());
+    assert_eq!(config.import_nmap, String::new());
+    assert_eq!(config.ports, Vec::<u16>::new());
+    assert_eq!(config.scheme_probes, vec!["https:443", "http:80"]);
     assert_eq!(config.time_limit, String::new());
+    assert_eq!(config.auto_resume, 0);
+    assert_eq!(config.canary, String::new());
+    assert_eq!(config.canary_interval, 30);
+    assert!(!config.stability_check);
+    assert_eq!(config.stability_interval, 60);
+    assert!(!config.server_alerts);
+    assert_eq!(config.search_regex, Vec::<String>::new());
+    assert!(!config.entropy_secrets);
+    assert_eq!(config.entropy_threshold, 4.5);
     assert_eq!(config.resume_from, String::new());
     assert_eq!(config.debug_log, String::new());
+    assert_eq!(config.burp_export, String::new());
+    assert_eq!(config.har_export, String::new());
+    assert_eq!(config.quiet_stream, String::new());
+    assert_eq!(config.traffic_log, String::new());
+    assert_eq!(config.traffic_log_max, String::new());
+    assert!(!config.traffic_log_bodies);
+    assert_eq!(config.policy_audit, String::new());
+    assert_eq!(config.screenshots, String::new());
+    assert_eq!(config.chrome, String::new());
+    assert_eq!(config.screenshot_codes, vec![200]);
+    assert_eq!(config.screenshot_types, vec!["text/html"]);
+    assert_eq!(config.pipe_to, String::new());
+    assert_eq!(config.api_export, String::new());
+    assert_eq!(config.api_export_format, String::new());
+    assert_eq!(config.api_template, String::new());
+    assert_eq!(config.api_batch_size, 100);
+    assert_eq!(config.dojo_engagement, String::new());
+    assert!(config.summary.is_empty());
+    assert!(config.record.is_empty());
+    assert!(config.replay.is_empty());
+    assert!(!config.selftest);
+    assert!(config.plugins.is_empty());
+    assert_eq!(config.plugin_timeout, 100);
+    assert!(!config.categorize);
+    assert!(config.category_rules.is_empty());
+    assert!(config.rules.is_empty());
+    assert!(config.junit.is_empty());
+    assert!(!config.fail_on_rule);
+    assert!(config.exit_codes.is_empty());
+    assert_eq!(config.exit_error_ratio, 0.1);
     assert_eq!(config.config, String::new());
     assert_eq!(config.replay_proxy, String::new());
+    assert_eq!(config.proxy_failover, Vec::<String>::new());
+    assert!(!config.proxy_failover_direct);
     assert_eq!(config.status_codes, status_codes());
     assert_eq!(config.replay_codes, config.status_codes);
     assert!(config.replay_client.is_none());
+    assert_eq!(config.replay_headers, HashMap::new());
+    assert_eq!(config.replay_client_cert, String::new());
+    assert_eq!(config.replay_client_key, String::new());
     assert_eq!(config.threads, threads());
     assert_eq!(config.depth, depth());
     assert_eq!(config.timeout, timeout());
+    assert_eq!(config.connect_timeout, 0);
+    assert_eq!(config.read_timeout, 0);
     assert_eq!(config.verbosity, 0);
     assert_eq!(config.scan_limit, 0);
+    assert_eq!(config.recursion_strategy, "");
+    assert!(!config.shuffle);
+    assert_eq!(config.shuffle_seed, 0);
+    assert_eq!(config.preview, 0);
+    assert_eq!(config.rate_limit_global, 0);
+    assert_eq!(config.bandwidth_limit, String::new());
+    assert_eq!(config.pacing, String::new());
     assert!(!config.silent);
     assert!(!config.quiet);
     assert_eq!(config.output_level, OutputLevel::Default);
     assert!(!config.dont_filter);
     assert!(!config.auto_tune);
+    assert_eq!(config.tune_strategy, "");
+    assert_eq!(config.tune_on_latency, "");
+    assert_eq!(config.tune_step, 0);
+    assert_eq!(config.policy_403_ratio, 0.9);
+    assert_eq!(config.policy_429_ratio, 0.3);
+    assert_eq!(config.policy_error_threshold, 25);
+    assert_eq!(config.policy_status, Vec::<u16>::new());
+    assert!(config.policy_error_weights.is_empty());
+    assert_eq!(config.tune_floor, 0);
+    assert_eq!(config.tune_ceiling, 0);
     assert!(!config.auto_bail);
+    assert_eq!(config.auto_requeue, 0);
     assert_eq!(config.requester_policy, RequesterPolicy::Default);
     assert!(!config.no_recursion);
     assert!(!config.random_agent);
     assert!(!config.json);
+    assert!(!config.punycode);
+    assert!(!config.fuzzy_hash);
+    assert!(!config.titles);
     assert!(config.save_state);
     assert!(!config.stdin);
     assert!(!config.add_slash);
+    assert!(!config.dual_slash);
+    assert!(!config.detect_case);
+    assert!(!config.favicon);
+    assert_eq!(config.favicon_db, String::new());
+    assert!(!config.well_known);
+    assert!(!config.bucket_listing);
+    assert_eq!(config.baseline, "");
+    assert_eq!(config.baseline_url, "");
+    assert_eq!(config.reuse_signatures, "");
+    assert_eq!(config.encode, "");
+    assert_eq!(config.evasion, "");
+    assert!(config.evasion_profiles.is_empty());
+    assert!(!config.range_probe);
+    assert_eq!(config.accept_encoding, "");
+    assert!(!config.decompress);
     assert!(!config.force_recursion);
+    assert!(!config.directories_first);
+    assert!(!config.safe_methods_only);
+    assert!(!config.raw_requests);
+    assert_eq!(config.inherit_headers, Vec::<String>::new());
     assert!(!config.redirects);
     assert!(config.extract_links);
+    assert_eq!(config.extract_page_limit, 0);
+    assert_eq!(config.extract_scan_limit, 0);
+    assert_eq!(config.extract_limit, 0);
+    assert_eq!(config.extract_depth, 0);
+    assert!(!config.crawl);
     assert!(!config.insecure);
+    assert_eq!(config.host_header, String::new());
+    assert_eq!(config.sni, String::new());
+    assert_eq!(config.doh, String::new());
+    assert!(!config.ipv4);
+    assert!(!config.ipv6);
+    assert!(!config.fan_out_ips);
     assert!(!config.collect_extensions);
+    assert!(!config.prune_extensions);
+    assert_eq!(config.prune_after, 250);
     assert!(!config.collect_backups);
+    assert!(!config.bypass_403);
+    assert_eq!(config.bypass_limit, 50);
+    assert_eq!(config.bypass_rate, 10);
+    assert!(!config.vcs_check);
+    assert!(!config.archive_peek);
+    assert_eq!(config.archive_max_size, "10M");
     assert!(!config.collect_words);
     assert!(config.regex_denylist.is_empty());
     assert_eq!(config.queries, Vec::new());
     assert_eq!(config.filter_size, Vec::<u64>::new());
     assert_eq!(config.extensions, Vec::<String>::new());
+    assert_eq!(config.extension_order, "");
     assert_eq!(config.methods, vec!["GET"]);
     assert_eq!(config.data, Vec::<u8>::new());
     assert_eq!(config.url_denylist, Vec::<Url>::new());
     assert_eq!(config.dont_collect, ignored_extensions());
     assert_eq!(config.filter_regex, Vec::<String>::new());
     assert_eq!(config.filter_similar, Vec::<String>::new());
+    assert_eq!(config.filter_category, Vec::<String>::new());
     assert_eq!(config.filter_word_count, Vec::<usize>::new());
     assert_eq!(config.filter_line_count, Vec::<usize>::new());
     assert_eq!(config.filter_status, Vec::<u16>::new());
     assert_eq!(config.headers, HashMap::new());
+    assert_eq!(config.request_id, String::new());
     assert_eq!(config.server_certs, Vec::<String>::new());
     assert_eq!(config.client_cert, String::new());
     assert_eq!(config.client_key, String::new());
+    assert_eq!(config.ca_bundle, String::new());
+    assert_eq!(config.pin_sha256, Vec::<String>::new());
 }
 
 #[test]
@@ -132,123 +360,591 @@ fn config_reads_wordlist() {
     assert_eq!(config.wordlist, "/some/path");
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_fuzz_wordlists() {
+    let config = setup_config_test();
+    assert_eq!(config.fuzz_wordlists, vec!["FUZZ2=/some/users"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_fuzz_mode() {
+    let config = setup_config_test();
+    assert_eq!(config.fuzz_mode, "pitchfork");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_debug_log() {
     let config = setup_config_test();
-    assert_eq!(config.debug_log, "/yet/anotherpath");
+    assert_eq!(config.debug_log, "/yet/anotherpath");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_import_nmap() {
+    let config = setup_config_test();
+    assert_eq!(config.import_nmap, "/some/nmap/scan.xml");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_ports() {
+    let config = setup_config_test();
+    assert_eq!(config.ports, vec![80, 443, 8080]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_scheme_probes() {
+    let config = setup_config_test();
+    assert_eq!(config.scheme_probes, vec!["http:8080", "https:8443"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_burp_export() {
+    let config = setup_config_test();
+    assert_eq!(config.burp_export, "/some/burp/export.xml");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_har_export() {
+    let config = setup_config_test();
+    assert_eq!(config.har_export, "/some/har/export.har");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_quiet_stream() {
+    let config = setup_config_test();
+    assert_eq!(config.quiet_stream, "/some/quiet/stream.txt");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_traffic_log() {
+    let config = setup_config_test();
+    assert_eq!(config.traffic_log, "/some/traffic/dir");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_traffic_log_max() {
+    let config = setup_config_test();
+    assert_eq!(config.traffic_log_max, "100M");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_traffic_log_bodies() {
+    let config = setup_config_test();
+    assert!(config.traffic_log_bodies);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_policy_audit() {
+    let config = setup_config_test();
+    assert_eq!(config.policy_audit, "/some/policy-audit.jsonl");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_screenshots() {
+    let config = setup_config_test();
+    assert_eq!(config.screenshots, "/some/screenshots");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_chrome() {
+    let config = setup_config_test();
+    assert_eq!(config.chrome, "/usr/bin/chromium");
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_screenshot_codes() {
+    let config = setup_config_test();
+    assert_eq!(config.screenshot_codes, vec![200, 401]);
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_screenshot_types() {
+    let config = setup_config_test();
+    assert_eq!(config.screenshot_types, vec!["text/html", "image/"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_pipe_to() {
+    let config = setup_config_test();
+    assert_eq!(config.pipe_to, "jq -r .url");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_api_export() {
+    let config = setup_config_test();
+    assert_eq!(
+        config.api_export,
+        "https://dojo.example.com/api/v2/import-scan/"
+    );
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_api_export_format() {
+    let config = setup_config_test();
+    assert_eq!(config.api_export_format, "dojo");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_api_template() {
+    let config = setup_config_test();
+    assert_eq!(config.api_template, "/some/template.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_api_batch_size() {
+    let config = setup_config_test();
+    assert_eq!(config.api_batch_size, 250);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_dojo_engagement() {
+    let config = setup_config_test();
+    assert_eq!(config.dojo_engagement, "42");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_summary() {
+    let config = setup_config_test();
+    assert_eq!(config.summary, "/some/summary.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_record() {
+    let config = setup_config_test();
+    assert_eq!(config.record, "/some/session.bin");
+}
+
+#[test]
+/// replay only comes from the command line, a config file can't turn every run into one
+fn config_ignores_replay() {
+    let config = setup_config_test();
+    assert!(config.replay.is_empty());
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_plugins() {
+    let config = setup_config_test();
+    assert_eq!(
+        config.plugins,
+        vec![
+            "/some/plugin.lua".to_string(),
+            "/another/plugin.lua".to_string()
+        ]
+    );
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_plugin_timeout() {
+    let config = setup_config_test();
+    assert_eq!(config.plugin_timeout, 250);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_categorize() {
+    let config = setup_config_test();
+    assert!(config.categorize);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_category_rules() {
+    let config = setup_config_test();
+    assert_eq!(config.category_rules, vec!["login-page:/login"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_rules() {
+    let config = setup_config_test();
+    assert_eq!(config.rules, "/some/rules.toml");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_junit() {
+    let config = setup_config_test();
+    assert_eq!(config.junit, "/some/junit.xml");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_fail_on_rule() {
+    let config = setup_config_test();
+    assert!(config.fail_on_rule);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_exit_codes() {
+    let config = setup_config_test();
+    assert_eq!(config.exit_codes["errors"], 3);
+    assert_eq!(config.exit_codes["bail"], 4);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_exit_error_ratio() {
+    let config = setup_config_test();
+    assert_eq!(config.exit_error_ratio, 0.5);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_status_codes() {
+    let config = setup_config_test();
+    assert_eq!(config.status_codes, vec![201, 301, 401]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_replay_codes() {
+    let config = setup_config_test();
+    assert_eq!(config.replay_codes, vec![201, 301]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_threads() {
+    let config = setup_config_test();
+    assert_eq!(config.threads, 40);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_depth() {
+    let config = setup_config_test();
+    assert_eq!(config.depth, 1);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_scan_limit() {
+    let config = setup_config_test();
+    assert_eq!(config.scan_limit, 6);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_recursion_strategy() {
+    let config = setup_config_test();
+    assert_eq!(config.recursion_strategy, "dfs");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_shuffle() {
+    let config = setup_config_test();
+    assert!(config.shuffle);
+    assert_eq!(config.shuffle_seed, 1337);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_preview() {
+    let config = setup_config_test();
+    assert_eq!(config.preview, 5);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_parallel() {
+    let config = setup_config_test();
+    assert_eq!(config.parallel, 14);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_rate_limit() {
+    let config = setup_config_test();
+    assert_eq!(config.rate_limit, 250);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_rate_limit_global() {
+    let config = setup_config_test();
+    assert_eq!(config.rate_limit_global, 900);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_bandwidth_limit() {
+    let config = setup_config_test();
+    assert_eq!(config.bandwidth_limit, "256K");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_pacing() {
+    let config = setup_config_test();
+    assert_eq!(config.pacing, "low-and-slow");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_auto_resume() {
+    let config = setup_config_test();
+    assert_eq!(config.auto_resume, 30);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_canary() {
+    let config = setup_config_test();
+    assert_eq!(config.canary, "/health");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_canary_interval() {
+    let config = setup_config_test();
+    assert_eq!(config.canary_interval, 45);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_stability_check() {
+    let config = setup_config_test();
+    assert!(config.stability_check);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_stability_interval() {
+    let config = setup_config_test();
+    assert_eq!(config.stability_interval, 90);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_server_alerts() {
+    let config = setup_config_test();
+    assert!(config.server_alerts);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_search_regex() {
+    let config = setup_config_test();
+    assert_eq!(config.search_regex, vec!["api[_-]?key", "BEGIN RSA"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_entropy_secrets() {
+    let config = setup_config_test();
+    assert!(config.entropy_secrets);
+    assert_eq!(config.entropy_threshold, 4.0);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_timeout() {
+    let config = setup_config_test();
+    assert_eq!(config.timeout, 5);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_connect_timeout() {
+    let config = setup_config_test();
+    assert_eq!(config.connect_timeout, 2);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_read_timeout() {
+    let config = setup_config_test();
+    assert_eq!(config.read_timeout, 3);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_proxy() {
+    let config = setup_config_test();
+    assert_eq!(config.proxy, "http://127.0.0.1:8080");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_replay_proxy() {
+    let config = setup_config_test();
+    assert_eq!(config.replay_proxy, "http://127.0.0.1:8081");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_proxy_failover() {
+    let config = setup_config_test();
+    assert_eq!(
+        config.proxy_failover,
+        vec!["http://127.0.0.1:8082", "socks5h://127.0.0.1:9050"]
+    );
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_proxy_failover_direct() {
+    let config = setup_config_test();
+    assert!(config.proxy_failover_direct);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_status_codes() {
+fn config_reads_silent() {
     let config = setup_config_test();
-    assert_eq!(config.status_codes, vec![201, 301, 401]);
+    assert!(config.silent);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_replay_codes() {
+fn config_reads_force_recursion() {
     let config = setup_config_test();
-    assert_eq!(config.replay_codes, vec![201, 301]);
+    assert!(config.force_recursion);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_threads() {
+fn config_reads_directories_first() {
     let config = setup_config_test();
-    assert_eq!(config.threads, 40);
+    assert!(config.directories_first);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_depth() {
+fn config_reads_inherit_headers() {
     let config = setup_config_test();
-    assert_eq!(config.depth, 1);
+    assert_eq!(config.inherit_headers, vec!["Set-Cookie", "X-CSRF-Token"]);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_scan_limit() {
+fn config_reads_quiet() {
     let config = setup_config_test();
-    assert_eq!(config.scan_limit, 6);
+    assert!(config.quiet);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_parallel() {
+fn config_reads_json() {
     let config = setup_config_test();
-    assert_eq!(config.parallel, 14);
+    assert!(config.json);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_rate_limit() {
+fn config_reads_punycode() {
     let config = setup_config_test();
-    assert_eq!(config.rate_limit, 250);
+    assert!(config.punycode);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_timeout() {
+fn config_reads_fuzzy_hash() {
     let config = setup_config_test();
-    assert_eq!(config.timeout, 5);
+    assert!(config.fuzzy_hash);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_proxy() {
+fn config_reads_titles() {
     let config = setup_config_test();
-    assert_eq!(config.proxy, "http://127.0.0.1:8080");
+    assert!(config.titles);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_replay_proxy() {
+fn config_reads_auto_bail() {
     let config = setup_config_test();
-    assert_eq!(config.replay_proxy, "http://127.0.0.1:8081");
+    assert!(config.auto_bail);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_silent() {
+fn config_reads_auto_requeue() {
     let config = setup_config_test();
-    assert!(config.silent);
+    assert_eq!(config.auto_requeue, 2);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_force_recursion() {
+fn config_reads_auto_tune() {
     let config = setup_config_test();
-    assert!(config.force_recursion);
+    assert!(config.auto_tune);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_quiet() {
+fn config_reads_tune_strategy() {
     let config = setup_config_test();
-    assert!(config.quiet);
+    assert_eq!(config.tune_strategy, "gradient");
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_json() {
+fn config_reads_tune_on_latency() {
     let config = setup_config_test();
-    assert!(config.json);
+    assert_eq!(config.tune_on_latency, "2x-baseline");
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_policy_thresholds() {
+    let config = setup_config_test();
+    assert_eq!(config.policy_403_ratio, 0.95);
+    assert_eq!(config.policy_429_ratio, 0.1);
+    assert_eq!(config.policy_error_threshold, 100);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_auto_bail() {
+fn config_reads_policy_status() {
     let config = setup_config_test();
-    assert!(config.auto_bail);
+    assert_eq!(config.policy_status, vec![503, 520]);
 }
 
 #[test]
 /// parse the test config and see that the value parsed is correct
-fn config_reads_auto_tune() {
+fn config_reads_policy_error_weights() {
     let config = setup_config_test();
-    assert!(config.auto_tune);
+    assert_eq!(config.policy_error_weights["timeout"], 3);
+    assert_eq!(config.policy_error_weights["tls"], 0);
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_aimd_bounds() {
+    let config = setup_config_test();
+    assert_eq!(config.tune_step, 15);
+    assert_eq!(config.tune_floor, 20);
+    assert_eq!(config.tune_ceiling, 300);
 }
 
 #[test]
@@ -279,6 +975,42 @@ fn config_reads_insecure() {
     assert!(config.insecure);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_host_header() {
+    let config = setup_config_test();
+    assert_eq!(config.host_header, "internal.app");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_sni() {
+    let config = setup_config_test();
+    assert_eq!(config.sni, "cdn.internal.app");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_doh() {
+    let config = setup_config_test();
+    assert_eq!(config.doh, "https://1.1.1.1/dns-query");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_ipv6() {
+    let config = setup_config_test();
+    assert!(config.ipv6);
+    assert!(!config.ipv4);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_fan_out_ips() {
+    let config = setup_config_test();
+    assert!(config.fan_out_ips);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_no_recursion() {
@@ -307,6 +1039,114 @@ fn config_reads_add_slash() {
     assert!(config.add_slash);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_dual_slash() {
+    let config = setup_config_test();
+    assert!(config.dual_slash);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_detect_case() {
+    let config = setup_config_test();
+    assert!(config.detect_case);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_favicon() {
+    let config = setup_config_test();
+    assert!(config.favicon);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_favicon_db() {
+    let config = setup_config_test();
+    assert_eq!(config.favicon_db, "/some/favicons.csv");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_well_known() {
+    let config = setup_config_test();
+    assert!(config.well_known);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_bucket_listing() {
+    let config = setup_config_test();
+    assert!(config.bucket_listing);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_baseline() {
+    let config = setup_config_test();
+    assert_eq!(config.baseline, "/some/previous/scan.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_baseline_url() {
+    let config = setup_config_test();
+    assert_eq!(config.baseline_url, "http://localhost/home");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_reuse_signatures() {
+    let config = setup_config_test();
+    assert_eq!(config.reuse_signatures, "/some/previous/ferox.state");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_encode() {
+    let config = setup_config_test();
+    assert_eq!(config.encode, "unicode");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_evasion() {
+    let config = setup_config_test();
+    assert_eq!(config.evasion, "sneaky");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_evasion_profiles() {
+    let config = setup_config_test();
+    assert_eq!(
+        config.evasion_profiles["sneaky"],
+        vec!["dot-segment", "path-params"]
+    );
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_range_probe() {
+    let config = setup_config_test();
+    assert!(config.range_probe);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_accept_encoding() {
+    let config = setup_config_test();
+    assert_eq!(config.accept_encoding, "gzip, deflate");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_decompress() {
+    let config = setup_config_test();
+    assert!(config.decompress);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_extract_links() {
@@ -314,6 +1154,23 @@ fn config_reads_extract_links() {
     assert!(!config.extract_links);
 }
 
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_extraction_limits() {
+    let config = setup_config_test();
+    assert_eq!(config.extract_page_limit, 25);
+    assert_eq!(config.extract_scan_limit, 250);
+    assert_eq!(config.extract_limit, 2500);
+    assert_eq!(config.extract_depth, 2);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_crawl() {
+    let config = setup_config_test();
+    assert!(config.crawl);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_collect_extensions() {
@@ -321,6 +1178,20 @@ fn config_reads_collect_extensions() {
     assert!(config.collect_extensions);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_prune_extensions() {
+    let config = setup_config_test();
+    assert!(config.prune_extensions);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_prune_after() {
+    let config = setup_config_test();
+    assert_eq!(config.prune_after, 100);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_collect_backups() {
@@ -328,6 +1199,42 @@ fn config_reads_collect_backups() {
     assert!(config.collect_backups);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_bypass_403() {
+    let config = setup_config_test();
+    assert!(config.bypass_403);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_bypass_limit() {
+    let config = setup_config_test();
+    assert_eq!(config.bypass_limit, 20);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_bypass_rate() {
+    let config = setup_config_test();
+    assert_eq!(config.bypass_rate, 5);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_vcs_check() {
+    let config = setup_config_test();
+    assert!(config.vcs_check);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_archive_peek() {
+    let config = setup_config_test();
+    assert!(config.archive_peek);
+    assert_eq!(config.archive_max_size, "2M");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_collect_words() {
@@ -342,6 +1249,13 @@ fn config_reads_extensions() {
     assert_eq!(config.extensions, vec!["html", "php", "js"]);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_extension_order() {
+    let config = setup_config_test();
+    assert_eq!(config.extension_order, "interleaved");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_dont_collect() {
@@ -356,6 +1270,20 @@ fn config_reads_methods() {
     assert_eq!(config.methods, vec!["GET", "PUT", "DELETE"]);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_safe_methods_only() {
+    let config = setup_config_test();
+    assert!(config.safe_methods_only);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_raw_requests() {
+    let config = setup_config_test();
+    assert!(config.raw_requests);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_data() {
@@ -400,6 +1328,13 @@ fn config_reads_filter_similar() {
     assert_eq!(config.filter_similar, vec!["https://somesite.com/soft404"]);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_filter_category() {
+    let config = setup_config_test();
+    assert_eq!(config.filter_category, vec!["backup-file"]);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_filter_size() {
@@ -473,6 +1408,46 @@ fn config_reads_client_key() {
     assert_eq!(config.client_key, "/some/client/key.pem");
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_replay_headers() {
+    let config = setup_config_test();
+    let mut headers = HashMap::new();
+    headers.insert("X-Tag".to_string(), "ferox".to_string());
+    assert_eq!(config.replay_headers, headers);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_replay_client_cert() {
+    let config = setup_config_test();
+    assert_eq!(config.replay_client_cert, "/some/replay/cert.pem");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_replay_client_key() {
+    let config = setup_config_test();
+    assert_eq!(config.replay_client_key, "/some/replay/key.pem");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_ca_bundle() {
+    let config = setup_config_test();
+    assert_eq!(config.ca_bundle, "/some/corp/bundle.pem");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_pin_sha256() {
+    let config = setup_config_test();
+    assert_eq!(
+        config.pin_sha256,
+        ["9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"]
+    );
+}
+
 #[test]
 /// parse the test config and see that the values parsed are correct
 fn config_reads_headers() {
@@ -483,6 +1458,13 @@ fn config_reads_headers() {
     assert_eq!(config.headers, headers);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_request_id() {
+    let config = setup_config_test();
+    assert_eq!(config.request_id, "X-Ferox-Scan-Id");
+}
+
 #[test]
 /// parse the test config and see that the values parsed are correct
 fn config_reads_queries() {
@@ -1,6 +1,7 @@
 use crate::{
     utils::{module_colorizer, status_colorizer},
-    DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD, DEFAULT_STATUS_CODES, DEFAULT_WORDLIST, VERSION,
+    DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD, DEFAULT_STATUS_CODES, DEFAULT_WORDLIST,
+    HIGH_429_RATIO, HIGH_ERROR_RATIO, MIN_ERROR_THRESHOLD, VERSION,
 };
 #[cfg(not(test))]
 use std::process::exit;
@@ -20,7 +21,7 @@ pub(super) fn report_and_exit(err: &str) -> ! {
     exit(1);
 }
 
-// functions timeout, threads, status_codes, user_agent, wordlist, save_state, and depth are used to provide
+// functions timeout, plugin_timeout, threads, status_codes, user_agent, wordlist, save_state, and depth are used to provide
 // defaults in the event that a ferox-config.toml is found but one or more of the values below
 // aren't listed in the config.  This way, we get the correct defaults upon Deserialization
 
@@ -34,6 +35,41 @@ pub(super) fn timeout() -> u64 {
     7
 }
 
+/// default canary_interval value, in seconds
+pub(super) fn canary_interval() -> u64 {
+    30
+}
+
+/// default stability_interval value, in seconds
+pub(super) fn stability_interval() -> u64 {
+    60
+}
+
+/// default prune_after value, in requests
+pub(super) fn prune_after() -> usize {
+    250
+}
+
+/// default bypass_limit value, in paths
+pub(super) fn bypass_limit() -> usize {
+    50
+}
+
+/// default bypass_rate value, in requests per second
+pub(super) fn bypass_rate() -> usize {
+    10
+}
+
+/// default plugin_timeout value, in milliseconds
+pub(super) fn plugin_timeout() -> u64 {
+    100
+}
+
+/// default api_batch_size value, in findings per request
+pub(super) fn api_batch_size() -> usize {
+    100
+}
+
 /// default save_state value
 pub(super) fn save_state() -> bool {
     true
@@ -44,6 +80,36 @@ pub(super) fn threads() -> usize {
     50
 }
 
+/// default policy_403_ratio value
+pub(super) fn policy_403_ratio() -> f64 {
+    HIGH_ERROR_RATIO
+}
+
+/// default policy_429_ratio value
+pub(super) fn policy_429_ratio() -> f64 {
+    HIGH_429_RATIO
+}
+
+/// default exit_error_ratio value
+pub(super) fn exit_error_ratio() -> f64 {
+    0.1
+}
+
+/// default archive_max_size value
+pub(super) fn archive_max_size() -> String {
+    String::from("10M")
+}
+
+/// default entropy_threshold value
+pub(super) fn entropy_threshold() -> f64 {
+    4.5
+}
+
+/// default policy_error_threshold value
+pub(super) fn policy_error_threshold() -> usize {
+    MIN_ERROR_THRESHOLD
+}
+
 /// default status codes
 pub(super) fn status_codes() -> Vec<u16> {
     DEFAULT_STATUS_CODES
@@ -61,6 +127,21 @@ pub(super) fn methods() -> Vec<String> {
     vec![DEFAULT_METHOD.to_owned()]
 }
 
+/// default status codes of findings worth a screenshot
+pub(super) fn screenshot_codes() -> Vec<u16> {
+    vec![200]
+}
+
+/// default content types of findings worth a screenshot
+pub(super) fn screenshot_types() -> Vec<String> {
+    vec![String::from("text/html")]
+}
+
+/// default schemes/ports tried for targets given without a scheme
+pub(super) fn scheme_probes() -> Vec<String> {
+    vec![String::from("https:443"), String::from("http:80")]
+}
+
 /// default extensions to ignore while auto-collecting
 pub(super) fn ignored_extensions() -> Vec<String> {
     DEFAULT_IGNORED_EXTENSIONS
@@ -133,6 +214,9 @@ pub enum RequesterPolicy {
     /// automatically bail at certain error thresholds
     AutoBail,
 
+    /// bail at the same thresholds, but restart the scan at a lower rate after a cooldown
+    AutoRequeue,
+
     /// just let that junk run super natural
     Default,
 }
@@ -145,9 +229,17 @@ impl Default for RequesterPolicy {
     }
 }
 
-/// given the current settings for quiet and silent, determine output_level (DRY helper)
-pub fn determine_requester_policy(auto_tune: bool, auto_bail: bool) -> RequesterPolicy {
-    if auto_tune && auto_bail {
+/// given the current settings for auto_tune, auto_bail, and auto_requeue, determine
+/// requester_policy (DRY helper)
+pub fn determine_requester_policy(
+    auto_tune: bool,
+    auto_bail: bool,
+    auto_requeue: bool,
+) -> RequesterPolicy {
+    if auto_requeue {
+        // requeueing is a gentler take on bailing, and wins out over both of the others
+        RequesterPolicy::AutoRequeue
+    } else if auto_tune && auto_bail {
         // user COULD have both as true in config file, take the more aggressive of the two
         RequesterPolicy::AutoBail
     } else if auto_tune {
@@ -182,17 +274,23 @@ mod tests {
     #[test]
     /// test determine_requester_policy returns higher of the two levels if both given values are true
     fn determine_requester_policy_returns_correct_results() {
-        let mut level = determine_requester_policy(true, true);
+        let mut level = determine_requester_policy(true, true, false);
         assert_eq!(level, RequesterPolicy::AutoBail);
 
-        level = determine_requester_policy(false, true);
+        level = determine_requester_policy(false, true, false);
         assert_eq!(level, RequesterPolicy::AutoBail);
 
-        level = determine_requester_policy(false, false);
+        level = determine_requester_policy(false, false, false);
         assert_eq!(level, RequesterPolicy::Default);
 
-        level = determine_requester_policy(true, false);
+        level = determine_requester_policy(true, false, false);
         assert_eq!(level, RequesterPolicy::AutoTune);
+
+        level = determine_requester_policy(true, true, true);
+        assert_eq!(level, RequesterPolicy::AutoRequeue);
+
+        level = determine_requester_policy(false, false, true);
+        assert_eq!(level, RequesterPolicy::AutoRequeue);
     }
 
     #[test]
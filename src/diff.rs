@@ -0,0 +1,159 @@
+//! how different each response is from a known page (--baseline-url)
+//!
+//! the page given to --baseline-url is fetched once before scanning starts. every response after
+//! that gets a rough diff ratio against it, from 0.0 (same words as the baseline) to 1.0 (nothing
+//! in common), written to --output as `baseline_diff` with --json. on template heavy apps, where
+//! every page shares the same chrome, sorting by it brings the pages with content of their own to
+//! the top
+//!
+//! the ratio compares the words of both bodies without regard to their order, which is cheap
+//! enough to run on every response and is all a sort needs
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use anyhow::{bail, Context, Result};
+use reqwest::Url;
+
+use crate::{
+    event_handlers::Handles,
+    utils::{fmt_err, logged_request},
+    DEFAULT_METHOD,
+};
+
+/// Words of the --baseline-url body, only populated when --baseline-url is used
+#[derive(Debug, Default)]
+pub struct BaselineWords {
+    /// `None` until the --baseline-url body is fetched
+    words: RwLock<Option<Words>>,
+}
+
+/// implementation of BaselineWords
+impl BaselineWords {
+    /// how different the given body is from the --baseline-url body; `None` when
+    /// --baseline-url wasn't used
+    pub(crate) fn diff(&self, text: &str) -> Option<f64> {
+        let guard = self.words.read().ok()?;
+        guard.as_ref().map(|words| words.diff_ratio(text))
+    }
+}
+
+/// The words of a body, with how often each one appears
+#[derive(Debug, Default)]
+struct Words {
+    /// number of times each word appears
+    counts: HashMap<String, usize>,
+
+    /// number of words, repeats included
+    total: usize,
+}
+
+/// implementation of Words
+impl Words {
+    /// count the words in the given text
+    fn from(text: &str) -> Self {
+        let mut words = Self::default();
+
+        for word in text.split_whitespace() {
+            *words.counts.entry(word.to_string()).or_default() += 1;
+            words.total += 1;
+        }
+
+        words
+    }
+
+    /// share of the words in both bodies that aren't found in the other, rounded to three
+    /// decimal places; two empty bodies are the same
+    fn diff_ratio(&self, text: &str) -> f64 {
+        let mut remaining = HashMap::<&str, usize>::new();
+        let mut total = 0;
+        let mut common = 0;
+
+        for word in text.split_whitespace() {
+            total += 1;
+
+            let seen = remaining.entry(word).or_default();
+            *seen += 1;
+
+            if *seen <= self.counts.get(word).copied().unwrap_or_default() {
+                common += 1;
+            }
+        }
+
+        if self.total + total == 0 {
+            return 0.0;
+        }
+
+        let ratio = 1.0 - (2 * common) as f64 / (self.total + total) as f64;
+        (ratio * 1000.0).round() / 1000.0
+    }
+}
+
+/// fetch the page given to --baseline-url and keep its words around; does nothing when
+/// --baseline-url wasn't used
+pub async fn initialize(handles: Arc<Handles>) -> Result<()> {
+    if handles.config.baseline_url.is_empty() {
+        return Ok(());
+    }
+
+    let url = Url::parse(&handles.config.baseline_url).with_context(|| {
+        fmt_err(&format!(
+            "Could not parse --baseline-url {}",
+            handles.config.baseline_url
+        ))
+    })?;
+
+    let response = logged_request(&url, DEFAULT_METHOD, None, handles.clone())
+        .await
+        .with_context(|| fmt_err(&format!("Could not fetch --baseline-url {url}")))?;
+
+    if !response.status().is_success() {
+        bail!(fmt_err(&format!(
+            "--baseline-url {} came back with a {}",
+            url,
+            response.status()
+        )));
+    }
+
+    let text = response.text().await?;
+    let words = Words::from(&text);
+
+    log::info!("--baseline-url {} holds {} words", url, words.total);
+
+    if let Ok(mut guard) = handles.features.baseline_words.words.write() {
+        *guard = Some(words);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// the same words in any order are no different, nothing in common is as different as it gets
+    fn diff_ratio_compares_words() {
+        let baseline = Words::from("<nav>home about</nav> <p>welcome</p>");
+
+        assert_eq!(
+            baseline.diff_ratio("<p>welcome</p> <nav>home about</nav>"),
+            0.0
+        );
+        assert_eq!(baseline.diff_ratio("entirely new"), 1.0);
+        assert_eq!(
+            baseline.diff_ratio("<nav>home about</nav> <p>welcome</p> <p>admin</p>"),
+            0.143
+        );
+        assert_eq!(Words::from("").diff_ratio(""), 0.0);
+    }
+
+    #[test]
+    /// repeated words only count as common as often as they're repeated in the baseline
+    fn diff_ratio_counts_repeats() {
+        let baseline = Words::from("row row");
+
+        assert_eq!(baseline.diff_ratio("row row row row"), 0.333);
+    }
+}
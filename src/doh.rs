@@ -0,0 +1,377 @@
+//! resolution of target host names through DNS-over-HTTPS (--doh)
+//!
+//! with --doh, every host name the client connects to is looked up by sending RFC 8484 queries
+//! to the given endpoint instead of going through the system's resolver, for networks where DNS
+//! is broken or watched. answers are cached for as long as their ttl allows, shared by every
+//! client built from the same resolver, and lookups that fail are counted as `doh` errors
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use hyper::client::connect::dns::Name;
+use reqwest::{
+    dns::{Addrs, Resolve, Resolving},
+    header::{ACCEPT, CONTENT_TYPE},
+    Client, Url,
+};
+
+use crate::client::IpFamily;
+
+/// start of every failed lookup's message, which is how failed lookups are told apart from
+/// other dns errors
+pub(crate) const ERROR_PREFIX: &str = "dns-over-https";
+
+/// media type of RFC 8484 queries and answers
+const DNS_MESSAGE: &str = "application/dns-message";
+
+/// record type of ipv4 addresses
+const TYPE_A: u16 = 1;
+
+/// record type of ipv6 addresses
+const TYPE_AAAA: u16 = 28;
+
+/// the only record class that's asked for
+const CLASS_IN: u16 = 1;
+
+/// shortest number of seconds an answer is cached for, whatever its ttl
+const MIN_TTL: u32 = 30;
+
+/// number of seconds a single query may take
+const QUERY_TIMEOUT: u64 = 5;
+
+/// addresses of a single host and when they expire, once it's been looked up; locked while the
+/// host is looked up, so that connections opened at the same time wait for the first one's
+/// answer instead of each sending their own queries
+type Answer = Arc<tokio::sync::Mutex<Option<(Vec<IpAddr>, Instant)>>>;
+
+/// Resolver handed to reqwest that looks every host name up through a DNS-over-HTTPS endpoint;
+/// clones share the same cache
+#[derive(Debug, Clone)]
+pub struct DohResolver {
+    /// where queries are sent, i.e. https://1.1.1.1/dns-query
+    endpoint: Url,
+
+    /// client used to send queries; it resolves the endpoint itself (if it isn't an ip address)
+    /// the usual way
+    client: Client,
+
+    /// the only address family asked for (-4/-6), both when `None`
+    family: Option<IpFamily>,
+
+    /// answers looked up so far, keyed by host name; a lookup only ever waits on its own host
+    cache: Arc<Mutex<HashMap<String, Answer>>>,
+}
+
+/// implementation of DohResolver
+impl DohResolver {
+    /// create a resolver that sends its queries to the given endpoint, only asking for addresses
+    /// of the given family, if any
+    pub fn new(endpoint: &str, family: Option<IpFamily>) -> Result<Self> {
+        let endpoint = Url::parse(endpoint)
+            .with_context(|| format!("Could not parse the --doh endpoint {endpoint}"))?;
+
+        if !matches!(endpoint.scheme(), "https" | "http") {
+            bail!("The --doh endpoint must be an http(s) url, received {endpoint}");
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(QUERY_TIMEOUT))
+            .build()?;
+
+        Ok(Self {
+            endpoint,
+            client,
+            family,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// the cache entry of the given host, added empty if it was never looked up
+    fn answer(&self, host: &str) -> Answer {
+        match self.cache.lock() {
+            Ok(mut cache) => cache.entry(host.to_string()).or_default().clone(),
+            // nothing to share with, the lookup still goes through
+            Err(_) => Answer::default(),
+        }
+    }
+
+    /// every address of the given host; answers are served from the cache while they're fresh
+    pub(crate) async fn lookup(&self, host: &str) -> Result<Vec<IpAddr>> {
+        let host = host.trim_end_matches('.').to_lowercase();
+
+        let answer = self.answer(&host);
+        let mut answer = answer.lock().await;
+
+        if let Some((addrs, expiry)) = answer.as_ref() {
+            if Instant::now() < *expiry {
+                // looked up before, or answered while waiting on the lock
+                return Ok(addrs.clone());
+            }
+        }
+
+        // ipv6 first (RFC 8305), the connector falls back to ipv4 when ipv6 doesn't connect
+        // quickly; the same order the system's resolver gives on a dual-stack host
+        let queries = [(IpFamily::V6, TYPE_AAAA), (IpFamily::V4, TYPE_A)]
+            .into_iter()
+            .filter(|(family, _)| self.family.is_none_or(|only| only == *family))
+            .map(|(_, record_type)| self.query(&host, record_type));
+
+        let mut addrs = Vec::new();
+        let mut ttl = u32::MAX;
+        let mut error = None;
+
+        for result in futures::future::join_all(queries).await {
+            match result {
+                Ok((found, found_ttl)) => {
+                    addrs.extend(found);
+                    ttl = ttl.min(found_ttl);
+                }
+                Err(e) => error = Some(e),
+            }
+        }
+
+        if addrs.is_empty() {
+            // either family is enough to connect, a failure only matters when nothing came back
+            return Err(error.unwrap_or_else(|| anyhow!("{} has no addresses", host)));
+        }
+
+        log::debug!("{} resolved to {:?} through --doh", host, addrs);
+
+        let expiry = Instant::now() + Duration::from_secs(u64::from(ttl.max(MIN_TTL)));
+
+        *answer = Some((addrs.clone(), expiry));
+
+        Ok(addrs)
+    }
+
+    /// send a single query for the given record type, returning the addresses and lowest ttl
+    /// found in the answer
+    async fn query(&self, host: &str, record_type: u16) -> Result<(Vec<IpAddr>, u32)> {
+        let response = self
+            .client
+            .post(self.endpoint.clone())
+            .header(CONTENT_TYPE, DNS_MESSAGE)
+            .header(ACCEPT, DNS_MESSAGE)
+            .body(encode_query(host, record_type)?)
+            .send()
+            .await
+            .with_context(|| format!("Could not reach {}", self.endpoint))?;
+
+        if !response.status().is_success() {
+            bail!("{} answered with a {}", self.endpoint, response.status());
+        }
+
+        parse_answer(&response.bytes().await?, record_type)
+    }
+}
+
+/// implementation of reqwest's Resolve for DohResolver
+impl Resolve for DohResolver {
+    /// look the name up through the endpoint; the port is filled in by the connector
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+
+        Box::pin(async move {
+            let addrs = resolver
+                .lookup(name.as_str())
+                .await
+                .map_err(|e| format!("{ERROR_PREFIX} lookup of {} failed: {e:#}", name.as_str()))?;
+
+            let addrs: Addrs = Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+/// build the wire format query (RFC 1035) for the given host and record type
+fn encode_query(host: &str, record_type: u16) -> Result<Vec<u8>> {
+    // id is left at 0 as RFC 8484 suggests, recursion desired, a single question
+    let mut query = vec![0, 0, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+
+    for label in host.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            bail!("{} is not a valid host name", host);
+        }
+
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+
+    query.push(0);
+
+    if query.len() - 12 > 255 {
+        bail!("{} is too long to be looked up", host);
+    }
+
+    query.extend_from_slice(&record_type.to_be_bytes());
+    query.extend_from_slice(&CLASS_IN.to_be_bytes());
+
+    Ok(query)
+}
+
+/// read a big endian u16 out of the message at the given offset
+fn read_u16(message: &[u8], offset: usize) -> Result<u16> {
+    message
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .context("truncated dns message")
+}
+
+/// offset just past the (possibly compressed) name that starts at the given offset
+fn skip_name(message: &[u8], mut offset: usize) -> Result<usize> {
+    loop {
+        let length = *message.get(offset).context("truncated dns message")?;
+
+        match length {
+            0 => return Ok(offset + 1),
+            // a pointer to a name elsewhere in the message always ends the name
+            _ if length & 0xc0 == 0xc0 => return Ok(offset + 2),
+            _ => offset += 1 + usize::from(length),
+        }
+    }
+}
+
+/// pull the addresses of the given record type out of a wire format answer, along with the
+/// lowest ttl among them
+fn parse_answer(message: &[u8], record_type: u16) -> Result<(Vec<IpAddr>, u32)> {
+    let flags = read_u16(message, 2)?;
+
+    match flags & 0x000f {
+        0 => {}
+        3 => bail!("no such host (NXDOMAIN)"),
+        rcode => bail!("the server answered with rcode {}", rcode),
+    }
+
+    let questions = read_u16(message, 4)?;
+    let answers = read_u16(message, 6)?;
+
+    let mut offset = 12;
+
+    for _ in 0..questions {
+        // name, then type and class
+        offset = skip_name(message, offset)? + 4;
+    }
+
+    let mut addrs = Vec::new();
+    let mut ttl = u32::MAX;
+
+    for _ in 0..answers {
+        offset = skip_name(message, offset)?;
+
+        let kind = read_u16(message, offset)?;
+        let class = read_u16(message, offset + 2)?;
+        let record_ttl = u32::from(read_u16(message, offset + 4)?) << 16
+            | u32::from(read_u16(message, offset + 6)?);
+        let length = usize::from(read_u16(message, offset + 8)?);
+
+        offset += 10;
+
+        let data = message
+            .get(offset..offset + length)
+            .context("truncated dns message")?;
+
+        offset += length;
+
+        // cnames leading up to the addresses are skipped, their targets' records follow them
+        let addr = match (kind, data.len()) {
+            (TYPE_A, 4) if kind == record_type && class == CLASS_IN => {
+                IpAddr::V4(Ipv4Addr::new(data[0], data[1], data[2], data[3]))
+            }
+            (TYPE_AAAA, 16) if kind == record_type && class == CLASS_IN => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(data);
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            _ => continue,
+        };
+
+        addrs.push(addr);
+        ttl = ttl.min(record_ttl);
+    }
+
+    Ok((addrs, ttl))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// answer to an A query for www.example.com; a cname to example.com, then its address
+    fn cname_answer() -> Vec<u8> {
+        let mut message = vec![0, 0, 0x81, 0x80, 0, 1, 0, 2, 0, 0, 0, 0];
+        message.extend(
+            encode_query("www.example.com", TYPE_A)
+                .unwrap()
+                .split_off(12),
+        );
+        // www.example.com (pointer to the question) CNAME example.com (pointer into it)
+        message.extend([0xc0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 16]);
+        // example.com A 93.184.216.34, ttl 300
+        message.extend([0xc0, 16, 0, 1, 0, 1, 0, 0, 1, 44, 0, 4, 93, 184, 216, 34]);
+        message
+    }
+
+    #[test]
+    /// queries are a header asking for recursion, then a single question
+    fn encode_query_builds_a_question() {
+        let query = encode_query("ferox.test", TYPE_AAAA).unwrap();
+
+        assert_eq!(&query[..12], &[0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(&query[12..], b"\x05ferox\x04test\x00\x00\x1c\x00\x01");
+
+        assert!(encode_query("ferox..test", TYPE_A).is_err());
+        assert!(encode_query(&"a".repeat(64), TYPE_A).is_err());
+    }
+
+    #[test]
+    /// addresses are found behind cnames, and only records of the type asked for are kept
+    fn parse_answer_follows_cnames() {
+        let (addrs, ttl) = parse_answer(&cname_answer(), TYPE_A).unwrap();
+
+        assert_eq!(addrs, vec![IpAddr::from([93, 184, 216, 34])]);
+        assert_eq!(ttl, 300);
+
+        let (addrs, _) = parse_answer(&cname_answer(), TYPE_AAAA).unwrap();
+        assert!(addrs.is_empty());
+    }
+
+    #[test]
+    /// error codes and truncated messages are failures, not empty answers
+    fn parse_answer_rejects_failures() {
+        let mut nxdomain = cname_answer();
+        nxdomain[3] |= 3;
+        assert!(parse_answer(&nxdomain, TYPE_A).is_err());
+
+        let answer = cname_answer();
+        assert!(parse_answer(&answer[..answer.len() - 2], TYPE_A).is_err());
+        assert!(parse_answer(&[0, 0], TYPE_A).is_err());
+    }
+
+    #[test]
+    /// clones share their cache, and each host is locked on its own
+    fn doh_resolver_clones_share_answers() {
+        let resolver = DohResolver::new("https://1.1.1.1/dns-query", None).unwrap();
+        let clone = resolver.clone();
+
+        assert!(Arc::ptr_eq(
+            &resolver.answer("ferox.test"),
+            &clone.answer("ferox.test")
+        ));
+        assert!(!Arc::ptr_eq(
+            &resolver.answer("ferox.test"),
+            &clone.answer("other.test")
+        ));
+    }
+
+    #[test]
+    /// only http(s) endpoints are accepted
+    fn doh_resolver_requires_http_endpoints() {
+        assert!(DohResolver::new("https://1.1.1.1/dns-query", None).is_ok());
+        assert!(DohResolver::new("tls://1.1.1.1", None).is_err());
+        assert!(DohResolver::new("not a url", Some(IpFamily::V4)).is_err());
+    }
+}
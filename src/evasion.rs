@@ -0,0 +1,230 @@
+//! request mutations meant to slip past WAFs that match on the literal path (--evasion)
+//!
+//! each request's word (everything the scan adds to the target, extensions included) goes
+//! through the transforms of the chosen profile while its url is formatted:
+//!
+//! - `dup-slash`: slashes are doubled, i.e. `/api/` + `js/app.js` -> `/api//js//app.js`
+//! - `dot-segment`: a `.;` segment is inserted before the word, i.e. `/api/.;/js/app.js`
+//! - `path-params`: a `;x=1` path parameter is added to each segment, i.e. `/api/js;x=1/app.js;x=1`
+//! - `trailing-junk`: `%20` is added to the end of the word, i.e. `/api/js/app.js%20`
+//!
+//! a bare `/./` segment would be folded away by the url parser before it's ever sent, which is
+//! why dot-segment uses `/.;/`; servers that strip path parameters (tomcat, jetty, spring) read
+//! both the same way
+//!
+//! profiles are named lists of transforms. the built-in ones are `light`, `moderate`, and
+//! `aggressive`; more can be added to ferox-config.toml under `[evasion_profiles]`, and the name
+//! of a single transform works as a profile of its own
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+
+use crate::config::Configuration;
+
+/// path parameter added to each segment by `path-params`
+const PATH_PARAM: &str = ";x=1";
+
+/// junk added to the end of the word by `trailing-junk`
+const TRAILING_JUNK: &str = "%20";
+
+/// A single request mutation
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Transform {
+    /// `;x=1` added to each segment
+    PathParams,
+
+    /// `%20` added to the end of the word
+    TrailingJunk,
+
+    /// `.;` segment inserted before the word
+    DotSegment,
+
+    /// every slash doubled
+    DupSlash,
+}
+
+/// implementation of Transform
+impl Transform {
+    /// name used in profiles and the banner
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::PathParams => "path-params",
+            Transform::TrailingJunk => "trailing-junk",
+            Transform::DotSegment => "dot-segment",
+            Transform::DupSlash => "dup-slash",
+        }
+    }
+}
+
+/// FromStr implementation for Transform
+impl FromStr for Transform {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "path-params" => Ok(Transform::PathParams),
+            "trailing-junk" => Ok(Transform::TrailingJunk),
+            "dot-segment" => Ok(Transform::DotSegment),
+            "dup-slash" => Ok(Transform::DupSlash),
+            _ => bail!("{name} is not an evasion transform"),
+        }
+    }
+}
+
+/// built-in profiles
+const PROFILES: [(&str, &[Transform]); 3] = [
+    ("light", &[Transform::DupSlash]),
+    ("moderate", &[Transform::PathParams, Transform::DupSlash]),
+    (
+        "aggressive",
+        &[
+            Transform::PathParams,
+            Transform::TrailingJunk,
+            Transform::DotSegment,
+            Transform::DupSlash,
+        ],
+    ),
+];
+
+/// transforms of the given profile; profiles from the config file win over the built-in ones,
+/// which win over single transforms
+pub fn profile(name: &str, config: &Configuration) -> Result<Vec<Transform>> {
+    let mut transforms = if let Some(custom) = config.evasion_profiles.get(name) {
+        custom
+            .iter()
+            .map(|transform| transform.parse::<Transform>())
+            .collect::<Result<Vec<_>>>()?
+    } else if let Some((_, builtin)) = PROFILES.iter().find(|(builtin, _)| *builtin == name) {
+        builtin.to_vec()
+    } else if let Ok(transform) = name.parse::<Transform>() {
+        vec![transform]
+    } else {
+        bail!("{name} is not a known evasion profile or transform");
+    };
+
+    // transforms always apply in the same order, regardless of how the profile lists them
+    transforms.sort();
+    transforms.dedup();
+
+    Ok(transforms)
+}
+
+/// apply the given transforms to the part of a url's path that came from the word; empty words
+/// (i.e. extracted links) are left alone, as is a trailing slash
+pub fn apply(word: &str, transforms: &[Transform]) -> String {
+    let (body, slash) = match word.strip_suffix('/') {
+        Some(body) => (body, "/"),
+        None => (word, ""),
+    };
+
+    if body.is_empty() || transforms.is_empty() {
+        return word.to_string();
+    }
+
+    let mut segments: Vec<_> = body.split('/').map(String::from).collect();
+
+    for transform in transforms {
+        match transform {
+            Transform::PathParams => segments
+                .iter_mut()
+                .filter(|segment| !segment.is_empty())
+                .for_each(|segment| segment.push_str(PATH_PARAM)),
+            Transform::TrailingJunk => {
+                if let Some(last) = segments.last_mut() {
+                    last.push_str(TRAILING_JUNK);
+                }
+            }
+            Transform::DotSegment => segments.insert(0, String::from(".;")),
+            Transform::DupSlash => {
+                // the leading slash is the extra one between the target and the word
+                let doubled = format!("/{}", segments.join("//"));
+                segments = doubled.split('/').map(String::from).collect();
+            }
+        }
+    }
+
+    format!("{}{slash}", segments.join("/"))
+}
+
+/// transforms of the chosen --evasion profile, looked up before any urls are formatted so that
+/// an unknown profile is reported right away; empty when --evasion wasn't used
+pub fn initialize(config: &Configuration) -> Result<Vec<Transform>> {
+    if config.evasion.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let transforms = profile(&config.evasion, config)?;
+
+    log::info!(
+        "evasion profile {}: {:?}",
+        config.evasion,
+        transforms.iter().map(Transform::name).collect::<Vec<_>>()
+    );
+
+    Ok(transforms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// each transform on its own
+    fn apply_single_transforms() {
+        let apply_one = |word, transform| apply(word, &[transform]);
+
+        assert_eq!(apply_one("js/app.js", Transform::DupSlash), "/js//app.js");
+        assert_eq!(
+            apply_one("js/app.js", Transform::DotSegment),
+            ".;/js/app.js"
+        );
+        assert_eq!(
+            apply_one("js/app.js", Transform::PathParams),
+            "js;x=1/app.js;x=1"
+        );
+        assert_eq!(
+            apply_one("js/app.js", Transform::TrailingJunk),
+            "js/app.js%20"
+        );
+    }
+
+    #[test]
+    /// trailing slashes stay put and empty words are left alone
+    fn apply_keeps_trailing_slash() {
+        let transforms = profile("aggressive", &Configuration::new().unwrap()).unwrap();
+
+        assert_eq!(apply("admin/", &transforms), "/.;//admin;x=1%20/");
+        assert_eq!(apply("", &transforms), "");
+        assert_eq!(apply("/", &transforms), "/");
+    }
+
+    #[test]
+    /// custom profiles win over built-in ones, single transforms work as profiles, and the
+    /// order transforms are given in doesn't matter
+    fn profile_lookup() {
+        let mut config = Configuration::new().unwrap();
+        config.evasion_profiles.insert(
+            String::from("light"),
+            vec![String::from("dup-slash"), String::from("path-params")],
+        );
+
+        assert_eq!(
+            profile("light", &config).unwrap(),
+            [Transform::PathParams, Transform::DupSlash]
+        );
+        assert_eq!(
+            profile("moderate", &config).unwrap(),
+            [Transform::PathParams, Transform::DupSlash]
+        );
+        assert_eq!(
+            profile("dot-segment", &config).unwrap(),
+            [Transform::DotSegment]
+        );
+        assert!(profile("stealthy", &config).is_err());
+
+        config
+            .evasion_profiles
+            .insert(String::from("broken"), vec![String::from("nope")]);
+        assert!(profile("broken", &config).is_err());
+    }
+}
@@ -5,10 +5,22 @@ use tokio::sync::oneshot::Sender;
 
 use crate::response::FeroxResponse;
 use crate::{
+    archives::ArchiveInfo,
+    buckets::BucketInfo,
+    bypass::BypassInfo,
     event_handlers::Handles,
+    fanout::IpDelta,
+    favicon::FaviconInfo,
     message::FeroxMessage,
+    progress::RateLimits,
+    rules::RuleAlert,
+    search::SearchMatch,
+    server_watch::ServerChangeInfo,
+    stability::UnstableInfo,
     statistics::{StatError, StatField},
+    tls::TlsInfo,
     traits::FeroxFilter,
+    vcs::VcsInfo,
 };
 
 /// Protocol definition for updating an event handler via mpsc
@@ -25,8 +37,9 @@ pub enum Command {
 
     /// Create the progress bar (`BarType::Total`) that is updated from the stats thread
     ///
-    /// the u64 value is the offset at which to start the progress bar (can be 0)
-    CreateBar(u64),
+    /// the u64 value is the offset at which to start the progress bar (can be 0), the rate limits
+    /// are shown on it
+    CreateBar(u64, Arc<RateLimits>),
 
     /// Add to a `Stats` field that corresponds to the given `StatField` by the given `usize` value
     AddToUsizeField(StatField, usize),
@@ -34,6 +47,9 @@ pub enum Command {
     /// Subtract from a `Stats` field that corresponds to the given `StatField` by the given `usize` value
     SubtractFromUsizeField(StatField, usize),
 
+    /// Add one to the number of responses removed by the filter with the given description
+    AddFilterHit(String),
+
     /// Update a `Stats` field that corresponds to the given `StatField` by the given `f64` value
     AddToF64Field(StatField, f64),
 
@@ -52,12 +68,19 @@ pub enum Command {
     /// Send a `FeroxResponse` to the output handler for reporting
     Report(Box<FeroxResponse>),
 
+    /// Send a `FeroxResponse` that was removed by a filter to the output handler, along with a
+    /// description of the filter responsible (only sent when --quiet-stream is used)
+    ReportFiltered(Box<FeroxResponse>, String),
+
     /// Send a group of urls to be scanned (only used for the urls passed in explicitly by the user)
     ScanInitialUrls(Vec<String>),
 
     /// Send a single url to be scanned (presumably added from the interactive menu)
     ScanNewUrl(String),
 
+    /// Restart a scan that was set aside by --auto-requeue, picking up where it left off
+    RequeueScan(String),
+
     /// Determine whether or not recursion is appropriate, given a FeroxResponse, if so start a scan
     TryRecursion(Box<FeroxResponse>),
 
@@ -79,6 +102,41 @@ pub enum Command {
     /// Write an arbitrary string to disk
     WriteToDisk(Box<FeroxMessage>),
 
+    /// Report the TLS details of a host to the user and --output
+    ReportTls(Box<TlsInfo>),
+
+    /// Report the favicon fingerprint of a host to the user and --output
+    ReportFavicon(Box<FaviconInfo>),
+
+    /// Report a variant that got past a 403 to the user and --output
+    ReportBypass(Box<BypassInfo>),
+
+    /// Report a host's Server/X-Powered-By header changing mid-scan to the user, --output, and
+    /// the --pipe-to command
+    ReportServerChange(Box<ServerChangeInfo>),
+
+    /// Report a cloud storage bucket found in an extracted link to the user and --output
+    ReportBucket(Box<BucketInfo>),
+
+    /// Report a --search-regex match found in a response body to the user and --output
+    ReportSearchMatch(Box<SearchMatch>),
+
+    /// Report a version control repository confirmed to be exposed to the user and --output
+    ReportVcs(Box<VcsInfo>),
+
+    /// Report the files inside an archive that was found to the user and --output
+    ReportArchive(Box<ArchiveInfo>),
+
+    /// Report a finding that isn't answered the same way by every address of its host to the
+    /// user and --output
+    ReportIpDelta(Box<IpDelta>),
+
+    /// Report a finding that no longer comes back the way it was found to the user and --output
+    ReportUnstable(Box<UnstableInfo>),
+
+    /// Write an alert raised by a --rules rule to --output
+    ReportRuleAlert(Box<RuleAlert>),
+
     /// Break out of the (infinite) mpsc receive loop
     Exit,
 
@@ -1,7 +1,10 @@
 use super::*;
 use crate::config::Configuration;
 use crate::event_handlers::scans::ScanHandle;
-use crate::scan_manager::FeroxScans;
+use crate::features::Features;
+use crate::scan_manager::{CanaryMonitor, FeroxScans, OutageMonitor, ProxyFailover};
+use crate::scanner::RuntimeSettings;
+use crate::utils::max_open_sockets;
 use crate::Joiner;
 #[cfg(test)]
 use crate::{filters::FeroxFilters, statistics::Stats, Command};
@@ -10,6 +13,7 @@ use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
 #[cfg(test)]
 use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::sync::Semaphore;
 
 #[derive(Debug)]
 /// Simple container for multiple JoinHandles
@@ -58,8 +62,32 @@ pub struct Handles {
     /// Handle for recursion
     pub scans: RwLock<Option<ScanHandle>>,
 
-    /// Pointer to the list of words generated from reading in the wordlist
-    pub wordlist: Arc<Vec<String>>,
+    /// Pointer to the list of words generated from reading in the wordlist; may be replaced
+    /// before scanning starts (i.e. by --detect-case)
+    wordlist: RwLock<Arc<Vec<String>>>,
+
+    /// The targets given through -u(s) or --stdin; urls beneath one are requested even when
+    /// --dont-scan denies one of its parents
+    targets: RwLock<Vec<String>>,
+
+    /// Settings that can be changed mid-scan, shared by all Requesters
+    pub runtime: RuntimeSettings,
+
+    /// Tracks whether the target is reachable, used to pause scans during an outage
+    pub outage: OutageMonitor,
+
+    /// Requests in flight across every scan; sized from the open file limit so that running many
+    /// scans at once can't exhaust file descriptors
+    pub sockets: Semaphore,
+
+    /// Tracks each host's canary, used to pause a host's scans while it's banning us
+    pub canary: CanaryMonitor,
+
+    /// Tracks which proxy requests go through, used to move past a failing proxy
+    pub proxies: ProxyFailover,
+
+    /// State kept by the optional features (--traffic-log, --plugin, etc...)
+    pub features: Features,
 }
 
 /// implementation of Handles
@@ -71,14 +99,22 @@ impl Handles {
         output: TermOutHandle,
         config: Arc<Configuration>,
         wordlist: Arc<Vec<String>>,
+        features: Features,
     ) -> Self {
         Self {
             stats,
             filters,
             output,
+            runtime: RuntimeSettings::new(&config),
+            outage: OutageMonitor::new(config.auto_resume),
+            sockets: Semaphore::new(max_open_sockets().unwrap_or(Semaphore::MAX_PERMITS)),
+            canary: CanaryMonitor::new(&config.canary, config.canary_interval),
+            proxies: ProxyFailover::new(&config),
+            features,
             config,
             scans: RwLock::new(None),
-            wordlist,
+            wordlist: RwLock::new(wordlist),
+            targets: RwLock::new(Vec::new()),
         }
     }
 
@@ -94,12 +130,14 @@ impl Handles {
         let stats_handle = StatsHandle::new(Arc::new(Stats::new(configuration.json)), tx.clone());
         let filters_handle = FiltersHandle::new(Arc::new(FeroxFilters::default()), tx.clone());
         let wordlist = Arc::new(vec![String::from("this_is_a_test")]);
+        let features = Features::new(&configuration).unwrap();
         let handles = Self::new(
             stats_handle,
             filters_handle,
             terminal_handle,
             configuration,
             wordlist,
+            features,
         );
         if let Some(sh) = scanned_urls {
             let scan_handle = ScanHandle::new(sh, tx);
@@ -112,11 +150,41 @@ impl Handles {
     pub fn set_scan_handle(&self, handle: ScanHandle) {
         if let Ok(mut guard) = self.scans.write() {
             if guard.is_none() {
-                let _ = std::mem::replace(&mut *guard, Some(handle));
+                let _ = guard.replace(handle);
             }
         }
     }
 
+    /// Get the list of words used for each scan
+    pub fn wordlist(&self) -> Arc<Vec<String>> {
+        match self.wordlist.read() {
+            Ok(guard) => guard.clone(),
+            Err(_) => Arc::new(Vec::new()),
+        }
+    }
+
+    /// Replace the list of words used for each scan; only meaningful before scanning starts
+    pub fn set_wordlist(&self, wordlist: Arc<Vec<String>>) {
+        if let Ok(mut guard) = self.wordlist.write() {
+            *guard = wordlist;
+        }
+    }
+
+    /// Get the targets given through -u(s) or --stdin
+    pub fn targets(&self) -> Vec<String> {
+        match self.targets.read() {
+            Ok(guard) => guard.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Record the targets given through -u(s) or --stdin, once they're known
+    pub fn set_targets(&self, targets: &[String]) {
+        if let Ok(mut guard) = self.targets.write() {
+            *guard = targets.to_vec();
+        }
+    }
+
     /// Helper to easily send a Command over the (locked) underlying CommandSender object
     pub fn send_scan_command(&self, command: Command) -> Result<()> {
         if let Ok(guard) = self.scans.read().as_ref() {
@@ -152,25 +220,32 @@ impl Handles {
 
     /// number of words in the wordlist, multiplied by `expected_num_requests_multiplier`
     pub fn expected_num_requests_per_dir(&self) -> usize {
-        let num_words = self.wordlist.len();
+        let num_words = self.wordlist().len();
         let multiplier = self.expected_num_requests_multiplier();
         multiplier * num_words
     }
 
-    /// number of extensions plus the number of request method types plus any dynamically collected
-    /// extensions
+    /// number of requests made per word: one for the word itself (two with --dual-slash), plus
+    /// one for each extension, dynamically collected or otherwise, all of it for each request
+    /// method
     pub fn expected_num_requests_multiplier(&self) -> usize {
-        let mut multiplier = self.config.extensions.len().max(1);
-
-        if multiplier > 1 {
-            // when we have more than one extension, we need to account for the fact that we'll
-            // be making a request for each extension and the base word (e.g. /foo.html and /foo)
-            multiplier += 1;
+        if self.features.template.is_active() {
+            // each combination is a single url, only the methods multiply it
+            return self.config.methods.len().max(1);
         }
 
-        multiplier *= self.config.methods.len().max(1) * self.num_collected_extensions().max(1);
+        let mut urls_per_word = if self.config.dual_slash { 2 } else { 1 };
+
+        urls_per_word += self.config.extensions.len() + self.num_collected_extensions();
+
+        if (self.config.add_slash || self.config.dual_slash)
+            && self.config.extensions.iter().any(|ext| ext == "/")
+        {
+            // an extension of "/" overlaps with word/, which is only requested once
+            urls_per_word -= 1;
+        }
 
-        multiplier
+        urls_per_word * self.config.methods.len().max(1)
     }
 
     /// Helper to easily get the (locked) underlying FeroxScans object
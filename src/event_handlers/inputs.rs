@@ -1,11 +1,12 @@
 use super::*;
 use crate::{
+    atomic_load,
     progress::PROGRESS_PRINTER,
     scan_manager::{FeroxState, PAUSE_SCAN},
     scanner::RESPONSES,
     statistics::StatError,
     utils::slugify_filename,
-    utils::{open_file, write_to},
+    utils::{ferox_eprint, ferox_print, open_file, write_to},
     SLEEP_DURATION,
 };
 use anyhow::Result;
@@ -23,6 +24,9 @@ use std::{
 /// Atomic boolean flag, used to determine whether or not the terminal input handler should exit
 pub static SCAN_COMPLETE: AtomicBool = AtomicBool::new(false);
 
+/// maximum amount of time to wait on output handlers to finish writing during a graceful shutdown
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Container for filters transmitter and FeroxFilters object
 pub struct TermInputHandler {
     /// handles to other handlers
@@ -33,6 +37,7 @@ pub struct TermInputHandler {
 ///
 /// kicks off the following handlers related to terminal input:
 ///     ctrl+c handler that saves scan state to disk
+///     SIGTERM/SIGHUP handler that saves scan state and flushes results before exiting
 ///     enter handler that listens for enter during scans to drop into interactive scan management menu
 impl TermInputHandler {
     /// Create new event handler
@@ -51,10 +56,13 @@ impl TermInputHandler {
         log::trace!("exit: initialize");
     }
 
-    /// wrapper around sigint_handler and enter_handler
+    /// wrapper around sigint_handler, termination_handler, and enter_handler
     fn start(&self) {
         tokio::task::spawn_blocking(Self::enter_handler);
 
+        #[cfg(unix)]
+        tokio::spawn(Self::termination_handler(self.handles.clone()));
+
         if self.handles.config.save_state {
             // start the ctrl+c handler
             let cloned = self.handles.clone();
@@ -73,46 +81,156 @@ impl TermInputHandler {
         }
     }
 
-    /// Writes the current state of the program to disk (if save_state is true) and then exits
-    pub fn sigint_handler(handles: Arc<Handles>) -> Result<()> {
-        log::trace!("enter: sigint_handler({:?})", handles);
-
-        let filename = if !handles.config.target_url.is_empty() {
+    /// name of the file used to store scan state when a scan is interrupted
+    fn state_filename(handles: &Handles) -> String {
+        if !handles.config.target_url.is_empty() {
             // target url populated
             slugify_filename(&handles.config.target_url, "ferox", "state")
         } else {
             // stdin used
             slugify_filename("stdin", "ferox", "state")
-        };
-
-        let warning = format!(
-            "🚨 Caught {} 🚨 saving scan state to {} ...",
-            style("ctrl+c").yellow(),
-            filename
-        );
-
-        PROGRESS_PRINTER.println(warning);
+        }
+    }
 
+    /// Writes the current state of the program to the given file (if save_state is true)
+    fn save_state(handles: &Handles, filename: &str) -> Result<()> {
         let state = FeroxState::new(
             handles.ferox_scans()?,
             handles.config.clone(),
             &RESPONSES,
             handles.stats.data.clone(),
             handles.filters.data.clone(),
+            handles.features.signatures.snapshot(),
         );
 
         // User didn't set the --no-state flag (so saved_state is still the default true)
         if handles.config.save_state {
-            let state_file = open_file(&filename);
+            let state_file = open_file(filename);
 
             let mut buffered_file = state_file?;
             write_to(&state, &mut buffered_file, true)?;
         }
 
+        Ok(())
+    }
+
+    /// Writes the current state of the program to disk (if save_state is true) and then exits
+    pub fn sigint_handler(handles: Arc<Handles>) -> Result<()> {
+        log::trace!("enter: sigint_handler({:?})", handles);
+
+        let filename = Self::state_filename(&handles);
+
+        let warning = format!(
+            "🚨 Caught {} 🚨 saving scan state to {} ...",
+            style("ctrl+c").yellow(),
+            filename
+        );
+
+        PROGRESS_PRINTER.println(warning);
+
+        Self::save_state(&handles, &filename)?;
+
         log::trace!("exit: sigint_handler (end of program)");
         std::process::exit(1);
     }
 
+    /// Waits for SIGTERM or SIGHUP, then shuts down gracefully and exits
+    ///
+    /// unlike ctrl+c, these usually come from something supervising the process (container
+    /// runtimes, job schedulers, a closing terminal), so the results gathered so far need to make
+    /// it to disk before exiting
+    #[cfg(unix)]
+    async fn termination_handler(handles: Arc<Handles>) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let (Ok(mut sigterm), Ok(mut sighup)) = (
+            signal(SignalKind::terminate()),
+            signal(SignalKind::hangup()),
+        ) else {
+            log::warn!("Could not set SIGTERM/SIGHUP handlers; results may be lost on termination");
+            return;
+        };
+
+        let signal_name = tokio::select! {
+            _ = sigterm.recv() => "SIGTERM",
+            _ = sighup.recv() => "SIGHUP",
+        };
+
+        Self::graceful_shutdown(handles, signal_name)
+            .await
+            .unwrap_or_else(|e| log::error!("Could not shut down gracefully: {}", e));
+
+        log::trace!("exit: termination_handler (end of program)");
+        std::process::exit(1);
+    }
+
+    /// Saves scan state, cancels all active scans, and waits for the output handlers to write
+    /// everything they've been sent so far (output file, exports), then prints a short summary
+    async fn graceful_shutdown(handles: Arc<Handles>, signal_name: &str) -> Result<()> {
+        log::trace!("enter: graceful_shutdown({:?}, {})", handles, signal_name);
+
+        let filename = Self::state_filename(&handles);
+
+        let warning = if handles.config.save_state {
+            format!(
+                "🚨 Caught {} 🚨 saving scan state to {} ...",
+                style(signal_name).yellow(),
+                filename
+            )
+        } else {
+            format!(
+                "🚨 Caught {} 🚨 shutting down ...",
+                style(signal_name).yellow()
+            )
+        };
+
+        ferox_print(&warning, &PROGRESS_PRINTER);
+
+        // state needs to be saved before cancelling scans, otherwise they'd be marked as
+        // cancelled and skipped when resuming
+        Self::save_state(&handles, &filename)
+            .unwrap_or_else(|e| log::error!("Could not save scan state: {}", e));
+
+        for scan in handles.ferox_scans()?.get_active_scans() {
+            scan.abort()
+                .await
+                .unwrap_or_else(|e| log::warn!("Could not cancel {}: {}", scan, e));
+        }
+
+        // the terminal handler finishes the file handler and any exporters when told to exit,
+        // and drops its receiver once that's done
+        if handles.output.send(Command::Exit).is_ok()
+            && tokio::time::timeout(SHUTDOWN_TIMEOUT, handles.output.tx.closed())
+                .await
+                .is_err()
+        {
+            log::warn!("Timed out waiting for output handlers to finish writing results");
+        }
+
+        let summary = format!(
+            "🏁 Stopped after {} requests; {} resources discovered",
+            atomic_load!(handles.stats.data.requests),
+            handles.stats.data.resources_discovered()
+        );
+
+        ferox_print(&summary, &PROGRESS_PRINTER);
+
+        if let Some(status_summary) = handles.ferox_scans()?.status_code_summary() {
+            ferox_eprint(&status_summary, &PROGRESS_PRINTER);
+        }
+
+        if let Some(error_summary) = handles.ferox_scans()?.error_summary() {
+            ferox_eprint(&error_summary, &PROGRESS_PRINTER);
+        }
+
+        if let Some(filter_summary) = handles.stats.data.filter_summary() {
+            ferox_eprint(&filter_summary, &PROGRESS_PRINTER);
+        }
+
+        log::trace!("exit: graceful_shutdown");
+        Ok(())
+    }
+
     /// Handles specific key events triggered by the user over stdin
     fn enter_handler() {
         // todo eventually move away from atomics, the blocking recv is the problem
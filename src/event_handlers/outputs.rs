@@ -6,15 +6,20 @@ use futures::future::{BoxFuture, FutureExt};
 use tokio::sync::{mpsc, oneshot};
 
 use crate::{
+    archives, buckets, bypass,
     config::Configuration,
+    export::{ApiExport, BurpExport, HarExport, PipeSink, QuietStream},
+    fanout, favicon, plugins,
     progress::PROGRESS_PRINTER,
     response::FeroxResponse,
+    rules::{self, RuleAlert},
     scanner::RESPONSES,
-    send_command, skip_fail,
+    search, send_command, server_watch, skip_fail, stability,
     statistics::StatField::{ResourcesDiscovered, TotalExpected},
+    tls,
     traits::FeroxSerialize,
-    utils::{ferox_print, fmt_err, make_request, open_file, write_to},
-    CommandReceiver, CommandSender, Joiner,
+    utils::{ferox_print, fmt_err, make_request, open_file, should_deny_url, write_to},
+    vcs, CommandReceiver, CommandSender, Joiner,
 };
 use std::sync::Arc;
 use url::Url;
@@ -109,6 +114,39 @@ impl FileOutHandler {
                     //  implementing structs
                     skip_fail!(write_to(&*message, &mut file, self.config.json));
                 }
+                Command::ReportTls(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportFavicon(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportBypass(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportServerChange(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportBucket(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportSearchMatch(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportVcs(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportArchive(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportIpDelta(delta) => {
+                    skip_fail!(write_to(&*delta, &mut file, self.config.json));
+                }
+                Command::ReportUnstable(info) => {
+                    skip_fail!(write_to(&*info, &mut file, self.config.json));
+                }
+                Command::ReportRuleAlert(alert) => {
+                    skip_fail!(write_to(&*alert, &mut file, self.config.json));
+                }
                 Command::Exit => {
                     break;
                 }
@@ -141,6 +179,23 @@ pub struct TermOutHandler {
     /// optional file handler task
     file_task: Option<Joiner>,
 
+    /// optional Burp Suite XML exporter, only present when --burp-export is used
+    burp_export: Option<BurpExport>,
+
+    /// optional HTTP Archive exporter, only present when --har-export is used
+    har_export: Option<HarExport>,
+
+    /// optional stream of every response, filtered or not, only present when --quiet-stream is
+    /// used
+    quiet_stream: Option<QuietStream>,
+
+    /// optional external command that receives findings, only present when --pipe-to is used
+    pipe: Option<PipeSink>,
+
+    /// optional vulnerability management api that receives findings at the end of the scan,
+    /// only present when --api-export is used
+    api_export: Option<ApiExport>,
+
     /// pointer to "global" configuration struct
     config: Arc<Configuration>,
 
@@ -152,16 +207,27 @@ pub struct TermOutHandler {
 impl TermOutHandler {
     /// Given a terminal receiver along with a file transmitter and filename, create
     /// an OutputHandler
+    #[allow(clippy::too_many_arguments)]
     fn new(
         receiver: CommandReceiver,
         tx_file: CommandSender,
         file_task: Option<Joiner>,
+        burp_export: Option<BurpExport>,
+        har_export: Option<HarExport>,
+        quiet_stream: Option<QuietStream>,
+        pipe: Option<PipeSink>,
+        api_export: Option<ApiExport>,
         config: Arc<Configuration>,
     ) -> Self {
         Self {
             receiver,
             tx_file,
             file_task,
+            burp_export,
+            har_export,
+            quiet_stream,
+            pipe,
+            api_export,
             config,
             handles: None,
         }
@@ -190,7 +256,62 @@ impl TermOutHandler {
             None
         };
 
-        let mut term_handler = Self::new(rx_term, tx_file.clone(), file_task, config);
+        let burp_export = if !config.burp_export.is_empty() {
+            // --burp-export used, create the export file up front so items can be streamed to it
+            BurpExport::new(&config.burp_export)
+                .map_err(|e| log::error!("Could not create Burp export: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let har_export = if !config.har_export.is_empty() {
+            // --har-export used, same as above
+            HarExport::new(&config.har_export)
+                .map_err(|e| log::error!("Could not create HAR export: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let quiet_stream = if !config.quiet_stream.is_empty() {
+            // --quiet-stream used, same as above
+            QuietStream::new(&config.quiet_stream, config.json)
+                .map_err(|e| log::error!("Could not create quiet stream: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let pipe = if !config.pipe_to.is_empty() {
+            // --pipe-to used, start the command up front so it's ready for the first finding
+            PipeSink::new(&config.pipe_to)
+                .map_err(|e| log::error!("Could not start --pipe-to command: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let api_export = if !config.api_export.is_empty() {
+            // --api-export used, settings are checked up front, findings are sent at the end
+            ApiExport::new(&config)
+                .map_err(|e| log::error!("Could not set up --api-export: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let mut term_handler = Self::new(
+            rx_term,
+            tx_file.clone(),
+            file_task,
+            burp_export,
+            har_export,
+            quiet_stream,
+            pipe,
+            api_export,
+            config,
+        );
         let term_task = tokio::spawn(async move { term_handler.start(tx_stats).await });
 
         let event_handle = TermOutHandle::new(tx_term, tx_file);
@@ -212,6 +333,109 @@ impl TermOutHandler {
                     self.process_response(tx_stats.clone(), resp, ProcessResponseCall::Recursive)
                         .await?;
                 }
+                Command::ReportTls(info) => {
+                    if tls::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportTls(info))?;
+                    }
+                }
+                Command::ReportFavicon(info) => {
+                    if favicon::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportFavicon(info))?;
+                    }
+                }
+                Command::ReportBypass(info) => {
+                    if bypass::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportBypass(info))?;
+                    }
+                }
+                Command::ReportServerChange(info) => {
+                    if server_watch::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if let Some(pipe) = &self.pipe {
+                        pipe.add(&*info).await.unwrap_or_else(|e| {
+                            log::warn!("Could not pipe server change on {}: {}", info.host, e)
+                        });
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportServerChange(info))?;
+                    }
+                }
+                Command::ReportBucket(info) => {
+                    if buckets::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportBucket(info))?;
+                    }
+                }
+                Command::ReportSearchMatch(info) => {
+                    if search::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportSearchMatch(info))?;
+                    }
+                }
+                Command::ReportVcs(info) => {
+                    if vcs::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportVcs(info))?;
+                    }
+                }
+                Command::ReportArchive(info) => {
+                    if archives::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportArchive(info))?;
+                    }
+                }
+                Command::ReportIpDelta(delta) => {
+                    if fanout::is_printed(self.config.output_level) {
+                        ferox_print(&delta.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportIpDelta(delta))?;
+                    }
+                }
+                Command::ReportUnstable(info) => {
+                    if stability::is_printed(self.config.output_level) {
+                        ferox_print(&info.as_str(), &PROGRESS_PRINTER);
+                    }
+
+                    if self.file_task.is_some() {
+                        self.tx_file.send(Command::ReportUnstable(info))?;
+                    }
+                }
+                Command::ReportFiltered(resp, filter) => {
+                    if let Some(quiet_stream) = &self.quiet_stream {
+                        quiet_stream
+                            .add(&resp, Some(&filter))
+                            .unwrap_or_else(|e| log::warn!("Could not stream {}: {}", resp, e));
+                    }
+                }
                 Command::Sync(sender) => {
                     sender.send(true).unwrap_or_default();
                 }
@@ -219,8 +443,26 @@ impl TermOutHandler {
                     self.handles = Some(handles);
                 }
                 Command::Exit => {
-                    if self.file_task.is_some() && self.tx_file.send(Command::Exit).is_ok() {
-                        self.file_task.as_mut().unwrap().await??; // wait for death
+                    if let Some(file_task) = self.file_task.as_mut() {
+                        if self.tx_file.send(Command::Exit).is_ok() {
+                            file_task.await??; // wait for death
+                        }
+                    }
+
+                    if let Some(burp_export) = &self.burp_export {
+                        burp_export.finish()?;
+                    }
+
+                    if let Some(har_export) = &self.har_export {
+                        har_export.finish()?;
+                    }
+
+                    if let Some(pipe) = self.pipe.take() {
+                        pipe.finish().await?;
+                    }
+
+                    if let Some(api_export) = self.api_export.take() {
+                        api_export.finish().await?;
                     }
                     break;
                 }
@@ -231,6 +473,26 @@ impl TermOutHandler {
         Ok(())
     }
 
+    /// print an alert raised by a --rules rule, and send it wherever findings go
+    async fn report_rule_alert(&self, alert: RuleAlert) -> Result<()> {
+        if rules::is_printed(self.config.output_level) {
+            ferox_print(&alert.as_str(), &PROGRESS_PRINTER);
+        }
+
+        if let Some(pipe) = &self.pipe {
+            pipe.add(&alert)
+                .await
+                .unwrap_or_else(|e| log::warn!("Could not pipe alert for {}: {}", alert.url, e));
+        }
+
+        if self.file_task.is_some() {
+            self.tx_file
+                .send(Command::ReportRuleAlert(Box::new(alert)))?;
+        }
+
+        Ok(())
+    }
+
     /// upon receiving a `FeroxResponse` from the mpsc, handle printing, sending to the replay
     /// proxy, checking for backups of the `FeroxResponse`'s url, and tracking the response.
     fn process_response(
@@ -255,10 +517,60 @@ impl TermOutHandler {
             let unknown_sentry = !RESPONSES.contains(&resp); // !contains == unknown
             let should_process_response = contains_sentry && unknown_sentry;
 
+            if let (Some(quiet_stream), true) = (&self.quiet_stream, unknown_sentry) {
+                // --quiet-stream used; responses removed by the status code checks above are
+                // written alongside the findings, tagged accordingly
+                let filtered_by =
+                    (!contains_sentry).then(|| format!("Status code: {}", resp.status().as_u16()));
+
+                quiet_stream
+                    .add(&resp, filtered_by.as_deref())
+                    .unwrap_or_else(|e| log::warn!("Could not stream {}: {}", resp, e));
+            }
+
             if should_process_response {
+                // --rules used; matching rules tag the finding before anything sees it
+                let alerts = match &self.handles {
+                    Some(handles) => handles.features.rules.evaluate(&mut resp),
+                    None => Vec::new(),
+                };
+
                 // print to stdout
                 ferox_print(&resp.as_str(), &PROGRESS_PRINTER);
 
+                for alert in alerts {
+                    self.report_rule_alert(alert).await?;
+                }
+
+                if let Some(handles) = &self.handles {
+                    if let Some(screenshotter) = &handles.features.screenshots {
+                        // --screenshots used; queued up, taken on a thread of its own
+                        screenshotter.add(&resp);
+                    }
+
+                    // --plugin used; on_finding hooks see every finding
+                    plugins::on_finding(&handles.features.plugins, &resp);
+                }
+
+                if let Some(handles) = &self.handles {
+                    // --bypass-403 used; forbidden findings get variants of their own thrown at them
+                    bypass::on_finding(&resp, handles.clone());
+
+                    // --vcs-check used; findings inside a .git/.svn/.hg directory get their
+                    // repository checked
+                    vcs::on_finding(&resp, handles.clone());
+
+                    // --archive-peek used; archives that were found get their contents listed
+                    archives::on_finding(&resp, handles.clone());
+
+                    // --fan-out-ips used; findings get requested from every address of their
+                    // host
+                    fanout::on_finding(&resp, handles.clone());
+
+                    // --stability-check used; findings are kept around to be requested again
+                    stability::on_finding(&resp, handles.clone());
+                }
+
                 send_command!(tx_stats, AddToUsizeField(ResourcesDiscovered, 1));
 
                 if self.file_task.is_some() {
@@ -269,10 +581,47 @@ impl TermOutHandler {
                             fmt_err(&format!("Could not send {resp} to file handler"))
                         })?;
                 }
+
+                if let Some(burp_export) = &self.burp_export {
+                    // --burp-export used, body is still present at this point
+                    burp_export
+                        .add(&resp, &self.config)
+                        .unwrap_or_else(|e| log::warn!("Could not export {}: {}", resp, e));
+                }
+
+                if let Some(har_export) = &self.har_export {
+                    // --har-export used, body is still present at this point
+                    har_export
+                        .add(&resp, &self.config)
+                        .unwrap_or_else(|e| log::warn!("Could not export {}: {}", resp, e));
+                }
+
+                if let Some(pipe) = &self.pipe {
+                    // --pipe-to used; waits here when the command falls behind
+                    pipe.add(&*resp)
+                        .await
+                        .unwrap_or_else(|e| log::warn!("Could not pipe {}: {}", resp, e));
+                }
+
+                if let Some(api_export) = &self.api_export {
+                    // --api-export used; collected now, sent once the scan ends
+                    api_export
+                        .add(&resp)
+                        .unwrap_or_else(|e| log::warn!("Could not export {}: {}", resp, e));
+                }
+
+                if let (false, Some(handles)) = (self.config.summary.is_empty(), &self.handles) {
+                    // --summary used; only the best findings make it into the rollup
+                    handles.features.summary.record(&resp);
+                }
             }
             log::trace!("report complete: {}", resp.url());
 
-            if self.config.replay_client.is_some() && should_process_response {
+            if let (Some(replay_client), true, Some(handles)) = (
+                self.config.replay_client.as_ref(),
+                should_process_response,
+                &self.handles,
+            ) {
                 // replay proxy specified/client created and this response's status code is one that
                 // should be replayed; not using logged_request due to replay proxy client
                 let data = if self.config.data.is_empty() {
@@ -282,25 +631,32 @@ impl TermOutHandler {
                 };
 
                 make_request(
-                    self.config.replay_client.as_ref().unwrap(),
+                    replay_client,
                     resp.url(),
                     resp.method().as_str(),
                     data,
-                    self.config.output_level,
-                    &self.config,
-                    tx_stats.clone(),
+                    &[],
+                    handles,
                 )
                 .await
                 .with_context(|| "Could not replay request through replay proxy")?;
             }
 
-            if self.config.collect_backups
-                && should_process_response
-                && matches!(call_type, ProcessResponseCall::Recursive)
-            {
+            if let (true, true, ProcessResponseCall::Recursive, Some(handles)) = (
+                self.config.collect_backups,
+                should_process_response,
+                call_type,
+                &self.handles,
+            ) {
                 // --collect-backups was used; the response is one we care about, and the function
                 // call came from the loop in `.start` (i.e. recursive was specified)
-                let backup_urls = self.generate_backup_urls(&resp).await;
+                let mut backup_urls = self.generate_backup_urls(&resp).await;
+
+                if !self.config.url_denylist.is_empty() || !self.config.regex_denylist.is_empty() {
+                    // --dont-scan/--dont-scan-regex would refuse to request these anyway,
+                    // they're dropped here so they aren't counted toward the expected total
+                    backup_urls.retain(|url| !should_deny_url(url, handles).unwrap_or(false));
+                }
 
                 // need to manually adjust stats
                 send_command!(tx_stats, AddToUsizeField(TotalExpected, backup_urls.len()));
@@ -311,9 +667,8 @@ impl TermOutHandler {
                         backup_url,
                         resp.method().as_str(),
                         None,
-                        self.config.output_level,
-                        &self.config,
-                        tx_stats.clone(),
+                        &[],
+                        handles,
                     )
                     .await
                     .with_context(|| {
@@ -324,7 +679,7 @@ impl TermOutHandler {
                         backup_response,
                         resp.url().as_str(),
                         resp.method().as_str(),
-                        resp.output_level,
+                        handles,
                     )
                     .await;
 
@@ -381,7 +736,7 @@ impl TermOutHandler {
         let url = response.url();
 
         // confirmed safe: see src/response.rs for comments
-        let filename = url.path_segments().unwrap().last().unwrap();
+        let filename = url.path_segments().unwrap().next_back().unwrap();
 
         if !filename.is_empty() {
             // append rules
@@ -438,6 +793,11 @@ mod tests {
         let toh = TermOutHandler {
             config,
             file_task: None,
+            burp_export: None,
+            har_export: None,
+            quiet_stream: None,
+            pipe: None,
+            api_export: None,
             receiver: rx,
             tx_file,
             handles: Some(handles),
@@ -458,6 +818,11 @@ mod tests {
         let toh = TermOutHandler {
             config,
             file_task: None,
+            burp_export: None,
+            har_export: None,
+            quiet_stream: None,
+            pipe: None,
+            api_export: None,
             receiver: rx,
             tx_file,
             handles: Some(handles),
@@ -480,7 +845,7 @@ mod tests {
 
         let paths: Vec<_> = urls
             .iter()
-            .map(|url| url.path_segments().unwrap().last().unwrap())
+            .map(|url| url.path_segments().unwrap().next_back().unwrap())
             .collect();
 
         assert_eq!(urls.len(), 7);
@@ -503,6 +868,11 @@ mod tests {
         let toh = TermOutHandler {
             config,
             file_task: None,
+            burp_export: None,
+            har_export: None,
+            quiet_stream: None,
+            pipe: None,
+            api_export: None,
             receiver: rx,
             tx_file,
             handles: Some(handles),
@@ -524,7 +894,7 @@ mod tests {
 
         let paths: Vec<_> = urls
             .iter()
-            .map(|url| url.path_segments().unwrap().last().unwrap())
+            .map(|url| url.path_segments().unwrap().next_back().unwrap())
             .collect();
 
         assert_eq!(urls.len(), 6);
@@ -548,6 +918,11 @@ mod tests {
         let toh = TermOutHandler {
             config,
             file_task: None,
+            burp_export: None,
+            har_export: None,
+            quiet_stream: None,
+            pipe: None,
+            api_export: None,
             receiver: rx,
             tx_file,
             handles: Some(handles),
@@ -1,15 +1,22 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use anyhow::{bail, Result};
+use reqwest::header::{HeaderMap, SET_COOKIE};
 use tokio::sync::{mpsc, Semaphore};
 
 use crate::{
+    favicon,
     response::FeroxResponse,
-    scan_manager::{FeroxScan, FeroxScans, ScanOrder},
+    scan_manager::{FeroxScan, FeroxScans, ScanOrder, ScanType, SeenUrls},
     scanner::{FeroxScanner, RESPONSES},
+    shuffle,
     statistics::StatField::TotalScans,
+    tls,
     url::FeroxUrl,
-    utils::should_deny_url,
+    utils::{should_deny_url, watch_canary},
     CommandReceiver, CommandSender, FeroxChannel, Joiner, SLEEP_DURATION,
 };
 
@@ -110,7 +117,7 @@ impl ScanHandler {
     fn wordlist(&self, wordlist: Arc<Vec<String>>) {
         if let Ok(mut guard) = self.wordlist.lock() {
             if guard.is_none() {
-                let _ = std::mem::replace(&mut *guard, Some(wordlist));
+                let _ = guard.replace(wordlist);
             }
         }
     }
@@ -145,7 +152,8 @@ impl ScanHandler {
         while let Some(command) = self.receiver.recv().await {
             match command {
                 Command::ScanInitialUrls(targets) => {
-                    self.ordered_scan_url(targets, ScanOrder::Initial).await?;
+                    self.ordered_scan_url(targets, ScanOrder::Initial, &[])
+                        .await?;
                 }
                 Command::ScanNewUrl(target) => {
                     // added as part of interactive menu ability (2.4.1) to add a new scan.
@@ -153,9 +161,12 @@ impl ScanHandler {
                     // new base url), or simply adding a new sub-directory found some other way.
                     // Since we can't know, we'll start a scan as though we received the scan
                     // from -u | --stdin
-                    self.ordered_scan_url(vec![target], ScanOrder::Initial)
+                    self.ordered_scan_url(vec![target], ScanOrder::Initial, &[])
                         .await?;
                 }
+                Command::RequeueScan(target) => {
+                    self.requeue_scan(&target).await?;
+                }
                 Command::UpdateWordlist(wordlist) => {
                     self.wordlist(wordlist);
                 }
@@ -182,11 +193,11 @@ impl ScanHandler {
                     // the --dont-collect list AND it's also not in the --extensions list, AND
                     // we actually added a new extension (i.e. wasn't previously known), add
                     // it to FeroxScans.collected_extensions
-                    if self.handles.config.collect_extensions
+                    let should_collect = self.handles.config.collect_extensions
                         && !self.handles.config.dont_collect.contains(&new_extension)
-                        && !self.handles.config.extensions.contains(&new_extension)
-                        && self.data.add_discovered_extension(new_extension)
-                    {
+                        && !self.handles.config.extensions.contains(&new_extension);
+
+                    if should_collect && self.data.add_discovered_extension(new_extension) {
                         self.update_all_bar_lengths()?;
                         self.handles
                             .stats
@@ -206,28 +217,34 @@ impl ScanHandler {
     ///
     /// updating all bar lengths correctly requires a few different actions on our part.
     /// - get the current number of requests expected per scan (dynamic when --collect-extensions
-    ///     is used)
+    ///   is used)
     /// - update the overall progress bar via the statistics handler (total expected)
     /// - update the expected per scan value tracked in the statistics handler
     /// - update progress bars on each FeroxScan (type::directory) that are running/not-started
     /// - update progress bar length on FeroxScans (this is used when creating new a FeroxScan and
-    ///     determines the new scan's progress bar length)
+    ///   determines the new scan's progress bar length)
     fn update_all_bar_lengths(&self) -> Result<()> {
         log::trace!("enter: update_all_bar_lengths");
 
         // current number of requests expected per scan
-        // ExpectedPerScan and TotalExpected are a += action, so we need the wordlist length to
-        // update them while the other updates use expected_num_requests_per_dir
+        // ExpectedPerScan and TotalExpected are a += action, so we need the number of requests
+        // the new extension adds per word (one per method) to update them while the other
+        // updates use expected_num_requests_per_dir
         let num_words = self.get_wordlist(0)?.len();
         let current_expectation = self.handles.expected_num_requests_per_dir() as u64;
 
-        // used in the calculation of bar width down below, see explanation there
-        let divisor = (self.handles.expected_num_requests_multiplier() as u64 - 1).max(1);
+        let added_per_word = self.handles.config.methods.len().max(1) as u64;
+        let multiplier = self.handles.expected_num_requests_multiplier() as u64;
+
+        // requests made per word before the new extension was collected
+        let previous_multiplier = multiplier.saturating_sub(added_per_word).max(1);
 
-        // add another `wordlist.len` to the expected per scan tracker in the statistics handler
-        self.handles
-            .stats
-            .send(AddToUsizeField(StatField::ExpectedPerScan, num_words))?;
+        // add the new extension's requests to the expected per scan tracker in the statistics
+        // handler
+        self.handles.stats.send(AddToUsizeField(
+            StatField::ExpectedPerScan,
+            num_words * added_per_word as usize,
+        ))?;
 
         // since we're adding extensions in the middle of scans (potentially), we need to take
         // current number of requests into account, new_total will be used as an accumulator
@@ -243,7 +260,9 @@ impl ScanHandler {
                 // update progress bars on each FeroxScan where its scan type is directory and
                 // scan status is either running or not-started
                 for scan in scans_guard.iter() {
-                    if scan.is_active() {
+                    // a crawl's bar counts the links it found, not words, extensions don't
+                    // change it
+                    if scan.is_active() && !matches!(scan.scan_type, ScanType::Crawl) {
                         // current number of words left in the 'to-scan' bin, for example:
                         //
                         // say we have a 2000 word wordlist, have `-x js` on the command line, and
@@ -251,28 +270,29 @@ impl ScanHandler {
                         //
                         // that puts our state at:
                         // - wordlist length: 2000
-                        // - total expected: 4000 (original length * 2 for -x js)
+                        // - total expected: 4000 (word and word.js for each word)
                         //
                         // let's assume the current scan has sent 3000 requests so far
                         // that means to get the number of `words` left to send, we need to take
-                        // the difference of 4000 and 3000 and then divide that by the current
+                        // the difference of 4000 and 3000 and then divide that by the previous
                         // multiplier (2 in the example)
                         //
                         // (4000 - 3000) / 2 => 500 words left to send
                         //
-                        // the remaining 500 words will be sent as 3 variations (word, word.js,
-                        // word.php). So, we would then need to increment the bar by 500 to
-                        // reflect the dynamism of adding extensions mid-scan.
+                        // the remaining 500 words will each be sent one more time (word.php). So,
+                        // we would then need to increment the bar by 500 to reflect the dynamism
+                        // of adding extensions mid-scan.
                         let bar = scan.progress_bar();
 
-                        // (4000 - 3000) / 2 => 500 words left to send
-                        let length = bar.length().unwrap_or(1);
-                        let num_words_left = (length - bar.position()) / divisor;
+                        let length = bar.length().unwrap_or(0);
+                        let num_words_left =
+                            length.saturating_sub(bar.position()) / previous_multiplier;
+                        let num_new_requests = num_words_left * added_per_word;
 
                         // accumulate each bar's increment value for incrementing the total bar
-                        new_total += num_words_left;
+                        new_total += num_new_requests;
 
-                        bar.inc_length(num_words_left);
+                        bar.inc_length(num_new_requests);
                     }
                 }
             }
@@ -304,13 +324,109 @@ impl ScanHandler {
         bail!("Could not get underlying wordlist")
     }
 
+    /// the part of the wordlist a scan has yet to get through
+    fn remaining_wordlist(&self, scan: &FeroxScan) -> Result<Arc<Vec<String>>> {
+        let divisor = self.handles.expected_num_requests_multiplier();
+
+        let offset = if divisor > 1 && scan.requests() > 0 {
+            // if there were extensions provided and/or more than a single method used, and some
+            // number of requests have already been sent, we need to adjust the offset into the
+            // wordlist to ensure we don't index out of bounds
+            (scan.requests_made_so_far() as f64 / divisor as f64) as usize
+        } else {
+            scan.requests_made_so_far() as usize
+        };
+
+        if !self.handles.config.shuffle {
+            return self.get_wordlist(offset);
+        }
+
+        // --shuffle; the scan's order is the same every time, so the words it already
+        // requested are still the first ones
+        let mut words = shuffle::shuffled(
+            &self.get_wordlist(0)?,
+            self.handles.config.shuffle_seed,
+            scan.url(),
+        );
+        words.drain(..offset.min(words.len()));
+
+        Ok(Arc::new(words))
+    }
+
+    /// restart a scan set aside by --auto-requeue, once its cooldown is over
+    ///
+    /// the scan isn't counted as a new one, and its requeued rate limit is picked up by the
+    /// new scanner's Requester from the scan's tuning state
+    async fn requeue_scan(&mut self, target: &str) -> Result<()> {
+        log::trace!("enter: requeue_scan({})", target);
+
+        let Some(scan) = self.data.get_scan_by_url(target) else {
+            log::warn!("Could not find requeued scan for {}", target);
+            return Ok(());
+        };
+
+        if !scan.is_requeued() {
+            // cancelled from the interactive menu while cooling down
+            log::trace!("exit: requeue_scan -> no longer requeued");
+            return Ok(());
+        }
+
+        let list = self.remaining_wordlist(&scan)?;
+
+        log::info!(
+            "scan handler received requeued {} (attempt {}) - resuming scan",
+            target,
+            scan.requeues()
+        );
+
+        let scanner = FeroxScanner::new(
+            target,
+            scan.scan_order,
+            list,
+            self.limiter.clone(),
+            self.handles.clone(),
+        );
+
+        let task = tokio::spawn(async move {
+            if let Err(e) = scanner.scan_url().await {
+                log::warn!("{}", e);
+            }
+        });
+
+        scan.set_task(task).await?;
+
+        log::trace!("exit: requeue_scan");
+        Ok(())
+    }
+
     /// wrapper around scanning a url to stay DRY
-    async fn ordered_scan_url(&mut self, targets: Vec<String>, order: ScanOrder) -> Result<()> {
-        log::trace!("enter: ordered_scan_url({:?}, {:?})", targets, order);
+    ///
+    /// `headers` are sent with the new scans' requests on top of the configured ones
+    async fn ordered_scan_url(
+        &mut self,
+        targets: Vec<String>,
+        order: ScanOrder,
+        headers: &[(String, String)],
+    ) -> Result<()> {
+        log::trace!(
+            "enter: ordered_scan_url({:?}, {:?}, {:?})",
+            targets,
+            order,
+            headers
+        );
         let should_test_deny = !self.handles.config.url_denylist.is_empty()
             || !self.handles.config.regex_denylist.is_empty();
 
+        // seeded targets that only differ in case, a default port, or a trailing slash are
+        // scanned once
+        let mut batch = HashSet::new();
+
         for target in targets {
+            if !batch.insert(SeenUrls::normalize(&target)) {
+                log::debug!("skipping duplicate target {}", target);
+                continue;
+            }
+
             if self.data.contains(&target) && matches!(order, ScanOrder::Latest) {
                 // FeroxScans knows about this url and scan isn't an Initial scan
                 // initial scans are skipped because when resuming from a .state file, the scans
@@ -321,12 +437,14 @@ impl ScanHandler {
 
             let scan = if let Some(ferox_scan) = self.data.get_scan_by_url(&target) {
                 ferox_scan // scan already known
+            } else if self.handles.config.crawl {
+                self.data.add_crawl_scan(&target, order).1 // --crawl; follow links, no wordlist
             } else {
                 self.data.add_directory_scan(&target, order).1 // add the new target; return FeroxScan
             };
 
             if should_test_deny
-                && should_deny_url(&parse_url_with_raw_path(&target)?, self.handles.clone())?
+                && should_deny_url(&parse_url_with_raw_path(&target)?, &self.handles)?
             {
                 // response was caught by a user-provided deny list
                 // checking this last, since it's most susceptible to longer runtimes due to what
@@ -334,18 +452,11 @@ impl ScanHandler {
                 continue;
             }
 
-            let divisor = self.handles.expected_num_requests_multiplier();
-
-            let list = if divisor > 1 && scan.requests() > 0 {
-                // if there were extensions provided and/or more than a single method used, and some
-                // number of requests have already been sent, we need to adjust the offset into the
-                // wordlist to ensure we don't index out of bounds
+            let list = self.remaining_wordlist(&scan)?;
 
-                let adjusted = scan.requests_made_so_far() as f64 / (divisor as f64 - 1.0).max(1.0);
-                self.get_wordlist(adjusted as usize)?
-            } else {
-                self.get_wordlist(scan.requests_made_so_far() as usize)?
-            };
+            if !headers.is_empty() {
+                scan.set_headers(headers.to_vec());
+            }
 
             log::info!("scan handler received {} - beginning scan", target);
 
@@ -355,6 +466,22 @@ impl ScanHandler {
                 let url = FeroxUrl::from_string(&target, self.handles.clone());
                 let depth = url.depth().unwrap_or(0);
                 self.depths.push((target.clone(), depth));
+
+                // --canary; a single canary watches each host the user asked to scan
+                if let Some(canary) = self.handles.canary.canary_url(&target) {
+                    if self.handles.canary.watch(&canary) {
+                        tokio::spawn(watch_canary(canary, self.handles.clone()));
+                    }
+                }
+
+                // certificate problems are findings of their own, each https host is inspected
+                tokio::spawn(tls::inspect_target(target.clone(), self.handles.clone()));
+
+                // --favicon; each host's favicon is fingerprinted once
+                tokio::spawn(favicon::fingerprint_target(
+                    target.clone(),
+                    self.handles.clone(),
+                ));
             }
 
             let scanner = FeroxScanner::new(
@@ -455,8 +582,27 @@ impl ScanHandler {
             }
         }
 
+        // --inherit-headers; the new scan starts off with what the directory handed out
+        let headers = if self.handles.config.inherit_headers.is_empty() {
+            Vec::new()
+        } else {
+            let parent = self
+                .data
+                .get_base_scan_by_url(response.url().as_str())
+                .map(|scan| scan.headers())
+                .unwrap_or_default();
+
+            inherited_headers(
+                &parent,
+                response.headers(),
+                &self.handles.config.inherit_headers,
+                &self.handles.config.headers,
+            )
+        };
+
         let targets = vec![response.url().to_string()];
-        self.ordered_scan_url(targets, ScanOrder::Latest).await?;
+        self.ordered_scan_url(targets, ScanOrder::Latest, &headers)
+            .await?;
 
         log::info!("Added new directory to recursive scan: {}", response.url());
 
@@ -464,3 +610,177 @@ impl ScanHandler {
         Ok(())
     }
 }
+
+/// --inherit-headers; headers for a scan spawned by recursing into a directory: the parent
+/// scan's own headers, plus each of `names` taken from the directory's `response` headers
+///
+/// cookies handed out via Set-Cookie are added to whatever cookies are already being sent,
+/// including the configured ones
+fn inherited_headers(
+    parent: &[(String, String)],
+    response: &HeaderMap,
+    names: &[String],
+    configured: &HashMap<String, String>,
+) -> Vec<(String, String)> {
+    let mut headers = parent.to_vec();
+
+    for name in names {
+        if name.eq_ignore_ascii_case(SET_COOKIE.as_str()) {
+            let cookies: Vec<&str> = response
+                .get_all(SET_COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .filter_map(|value| value.split(';').next())
+                .map(str::trim)
+                .filter(|cookie| cookie.contains('='))
+                .collect();
+
+            if cookies.is_empty() {
+                continue;
+            }
+
+            let existing = find_header(&headers, "cookie")
+                .or_else(|| {
+                    configured
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case("cookie"))
+                        .map(|(_, value)| value.to_string())
+                })
+                .unwrap_or_default();
+
+            set_header(&mut headers, "Cookie", &merge_cookies(&existing, &cookies));
+        } else if let Some(value) = response
+            .get(name.as_str())
+            .and_then(|value| value.to_str().ok())
+        {
+            set_header(&mut headers, name, value);
+        }
+    }
+
+    headers
+}
+
+/// value of the first header with the given name (case-insensitive)
+fn find_header(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.to_string())
+}
+
+/// replace the value of the header with the given name (case-insensitive), or add it
+fn set_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match headers
+        .iter_mut()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+    {
+        Some((_, existing)) => *existing = value.to_string(),
+        None => headers.push((name.to_string(), value.to_string())),
+    }
+}
+
+/// add `cookies` (name=value pairs) to a Cookie header's value, replacing cookies of the same name
+fn merge_cookies(existing: &str, cookies: &[&str]) -> String {
+    let mut merged: Vec<&str> = existing
+        .split(';')
+        .map(str::trim)
+        .filter(|cookie| !cookie.is_empty())
+        .collect();
+
+    for cookie in cookies {
+        let name = cookie.split('=').next().unwrap_or_default();
+
+        match merged
+            .iter_mut()
+            .find(|known| known.split('=').next() == Some(name))
+        {
+            Some(known) => *known = cookie,
+            None => merged.push(cookie),
+        }
+    }
+
+    merged.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    /// build a header map with the given headers
+    fn response_with_headers(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+
+        for (name, value) in headers {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+
+        map
+    }
+
+    #[test]
+    /// named headers should be taken from the response on top of the parent's, replacing any
+    /// the parent already had
+    fn inherited_headers_extend_the_parent() {
+        let parent = vec![
+            (String::from("X-CSRF-Token"), String::from("old")),
+            (String::from("X-Keep"), String::from("me")),
+        ];
+        let response = response_with_headers(&[("x-csrf-token", "new"), ("x-ignored", "nope")]);
+
+        let headers = inherited_headers(
+            &parent,
+            &response,
+            &[String::from("X-CSRF-Token"), String::from("X-Missing")],
+            &HashMap::new(),
+        );
+
+        assert_eq!(
+            headers,
+            vec![
+                (String::from("X-CSRF-Token"), String::from("new")),
+                (String::from("X-Keep"), String::from("me")),
+            ]
+        );
+    }
+
+    #[test]
+    /// cookies handed out by the directory should be added to the configured cookies, replacing
+    /// those of the same name
+    fn inherited_headers_merge_set_cookie() {
+        let response = response_with_headers(&[
+            ("set-cookie", "session=abc; Path=/; HttpOnly"),
+            ("set-cookie", "theme=dark"),
+        ]);
+
+        let mut configured = HashMap::new();
+        configured.insert(String::from("Cookie"), String::from("theme=light; user=me"));
+
+        let headers = inherited_headers(&[], &response, &[String::from("set-cookie")], &configured);
+
+        assert_eq!(
+            headers,
+            vec![(
+                String::from("Cookie"),
+                String::from("theme=dark; user=me; session=abc")
+            )]
+        );
+
+        // the child's own child builds on the child's cookies
+        let response = response_with_headers(&[("set-cookie", "session=xyz")]);
+        let headers = inherited_headers(
+            &headers,
+            &response,
+            &[String::from("Set-Cookie")],
+            &configured,
+        );
+
+        assert_eq!(
+            headers,
+            vec![(
+                String::from("Cookie"),
+                String::from("theme=dark; user=me; session=xyz")
+            )]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use super::*;
 use crate::{
     config::Configuration,
-    progress::{add_bar, BarType},
-    statistics::{StatField, Stats},
+    progress::add_total_bar,
+    statistics::{StatError, StatField, Stats},
     CommandSender, FeroxChannel, Joiner,
 };
 use anyhow::Result;
@@ -85,7 +85,11 @@ impl StatsHandler {
             match command as Command {
                 Command::AddError(err) => {
                     self.stats.add_error(err);
-                    self.increment_bar();
+
+                    if !matches!(err, StatError::BodyRead | StatError::ReadTimeout) {
+                        // the response was already counted when it came in
+                        self.increment_bar();
+                    }
                 }
                 Command::AddStatus(status) => {
                     self.stats.add_status_code(status);
@@ -104,20 +108,22 @@ impl StatsHandler {
                     self.stats.update_usize_field(field, value);
 
                     if matches!(field, StatField::TotalScans | StatField::TotalExpected) {
-                        self.bar.set_length(self.stats.total_expected() as u64);
+                        self.update_bar_length();
                     }
                 }
                 Command::SubtractFromUsizeField(field, value) => {
                     self.stats.subtract_from_usize_field(field, value);
 
                     if matches!(field, StatField::TotalExpected) {
-                        self.bar.set_length(self.stats.total_expected() as u64);
+                        self.update_bar_length();
                     }
                 }
                 Command::AddToF64Field(field, value) => self.stats.update_f64_field(field, value),
-                Command::CreateBar(offset) => {
-                    self.bar = add_bar("", self.stats.total_expected() as u64, BarType::Total);
+                Command::AddFilterHit(filter) => self.stats.add_filter_hits(&filter, 1),
+                Command::CreateBar(offset, limits) => {
+                    self.bar = add_total_bar(self.stats.total_expected() as u64, limits);
                     self.bar.set_position(offset);
+                    self.update_bar_length();
                 }
                 Command::LoadStats(filename) => {
                     self.stats.merge_from(&filename)?;
@@ -149,13 +155,22 @@ impl StatsHandler {
 
         self.bar.set_message(msg);
 
-        if self.bar.position() < self.stats.total_expected() as u64 {
-            // don't run off the end when we're a few requests over the expected total
-            // due to the heuristics tests
-            self.bar.inc(1);
+        self.bar.inc(1);
+
+        if self.bar.position() > self.bar.length().unwrap_or(0) {
+            // a few requests over the expected total (i.e. the heuristics tests); grow the bar
+            // rather than dropping the increment, which would leave it short once the total
+            // catches up
+            self.update_bar_length();
         }
     }
 
+    /// Set the overall scan's progress bar length from the expected number of requests
+    fn update_bar_length(&self) {
+        let length = self.stats.expected_bar_length(self.bar.position());
+        self.bar.set_length(length);
+    }
+
     /// Initialize new `Stats` object and the sc side of an mpsc channel that is responsible for
     /// updates to the aforementioned object.
     pub fn initialize(config: Arc<Configuration>) -> (Joiner, StatsHandle) {
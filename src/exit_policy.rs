@@ -0,0 +1,204 @@
+//! exit code policy (--exit-code, --fail-on-rule)
+//!
+//! a scan that runs to completion exits with 0. scripts and CI jobs can ask for another code
+//! when the scan went a certain way, without having to parse its output:
+//! - `rule`: a forbidden rule (--rules) matched a finding; --fail-on-rule is `rule=2`
+//! - `errors`: errors made up at least --exit-error-ratio of the requests sent
+//! - `bail`: --auto-bail (or --auto-requeue, once out of attempts) cancelled a scan
+//!
+//! conditions are checked in that order once every handler has drained; the first one that
+//! holds decides the exit code
+use std::{fmt, str::FromStr, sync::atomic::Ordering};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    atomic_load, config::Configuration, event_handlers::Handles, rules::FORBIDDEN_RULE_EXIT_CODE,
+    utils::fmt_err,
+};
+
+/// every condition, in the order they're checked
+const ALL: [ExitCondition; 3] = [
+    ExitCondition::Rule,
+    ExitCondition::Errors,
+    ExitCondition::Bail,
+];
+
+/// a way the scan can go that may warrant a non-zero exit code
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExitCondition {
+    /// a forbidden rule matched a finding
+    Rule,
+
+    /// errors made up at least --exit-error-ratio of the requests sent
+    Errors,
+
+    /// a scan was bailed on
+    Bail,
+}
+
+/// implementation of ExitCondition
+impl ExitCondition {
+    /// name of the condition, as given to --exit-code
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rule => "rule",
+            Self::Errors => "errors",
+            Self::Bail => "bail",
+        }
+    }
+
+    /// why the scan meets this condition, `None` when it doesn't
+    fn check(&self, handles: &Handles) -> Option<String> {
+        match self {
+            Self::Rule => {
+                let matched = handles.features.rules.forbidden_matches();
+                (matched > 0).then(|| format!("{matched} forbidden rule(s) matched"))
+            }
+            Self::Errors => {
+                let requests = atomic_load!(handles.stats.data.requests);
+                let errors = handles.stats.data.errors();
+
+                if requests == 0 {
+                    return None;
+                }
+
+                let ratio = errors as f64 / requests as f64;
+
+                (ratio >= handles.config.exit_error_ratio).then(|| {
+                    format!(
+                        "{errors} of {requests} requests failed ({:.0}%)",
+                        ratio * 100.0
+                    )
+                })
+            }
+            Self::Bail => {
+                let bailed = handles
+                    .ferox_scans()
+                    .map(|scans| scans.bailed_scans())
+                    .unwrap_or_default();
+
+                (bailed > 0).then(|| format!("{bailed} scan(s) were bailed on"))
+            }
+        }
+    }
+}
+
+/// FromStr implementation for ExitCondition
+impl FromStr for ExitCondition {
+    type Err = anyhow::Error;
+
+    /// look a condition up by its name (case-insensitive)
+    fn from_str(name: &str) -> Result<Self> {
+        let lowered = name.trim().to_lowercase();
+
+        ALL.into_iter()
+            .find(|condition| condition.name() == lowered)
+            .ok_or_else(|| {
+                anyhow::anyhow!(fmt_err(&format!(
+                    "Unknown --exit-code condition {name}; expected one of rule, errors, bail"
+                )))
+            })
+    }
+}
+
+/// Display implementation for ExitCondition
+impl fmt::Display for ExitCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// validate the given --exit-code/--fail-on-rule settings, ordered by when they're checked
+fn parse(config: &Configuration) -> Result<Vec<(ExitCondition, i32)>> {
+    let mut policy = Vec::new();
+
+    for (name, code) in &config.exit_codes {
+        let condition = name.parse::<ExitCondition>()?;
+
+        if !(1..=255).contains(code) {
+            bail!(fmt_err(&format!(
+                "Exit code for {condition} must be between 1 and 255; received {code}"
+            )));
+        }
+
+        policy.push((condition, *code));
+    }
+
+    if config.fail_on_rule && !policy.iter().any(|(c, _)| *c == ExitCondition::Rule) {
+        policy.push((ExitCondition::Rule, FORBIDDEN_RULE_EXIT_CODE));
+    }
+
+    if policy.iter().any(|(c, _)| *c == ExitCondition::Rule) && config.rules.is_empty() {
+        bail!(fmt_err("An exit code for rule needs --rules"));
+    }
+
+    policy.sort_by_key(|(condition, _)| ALL.iter().position(|c| c == condition));
+
+    Ok(policy)
+}
+
+/// load the --exit-code/--fail-on-rule settings: the exit code of each condition given, in the
+/// order they're checked; a bad one is reported before the scan starts
+pub fn initialize(config: &Configuration) -> Result<Vec<(ExitCondition, i32)>> {
+    parse(config)
+}
+
+/// exit code the finished scan calls for, along with the reason; `None` means exit with 0
+///
+/// meant to be called after every handler has drained, so the counts it looks at are final
+pub fn evaluate(handles: &Handles) -> Option<(i32, String)> {
+    handles
+        .features
+        .exit_policy
+        .iter()
+        .find_map(|(condition, code)| condition.check(handles).map(|reason| (*code, reason)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// configuration with the given --exit-code settings
+    fn config(codes: &[(&str, i32)]) -> Configuration {
+        Configuration {
+            exit_codes: codes
+                .iter()
+                .map(|(name, code)| (name.to_string(), *code))
+                .collect::<HashMap<_, _>>(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// conditions are ordered by when they're checked, whatever order they were given in
+    fn policy_is_ordered_by_condition() {
+        let mut config = config(&[("BAIL", 4), ("errors", 3)]);
+        config.rules = String::from("rules.toml");
+        config.fail_on_rule = true;
+
+        assert_eq!(
+            parse(&config).unwrap(),
+            [
+                (ExitCondition::Rule, FORBIDDEN_RULE_EXIT_CODE),
+                (ExitCondition::Errors, 3),
+                (ExitCondition::Bail, 4)
+            ]
+        );
+
+        // an explicit rule code wins over --fail-on-rule's
+        config.exit_codes.insert(String::from("rule"), 9);
+        assert_eq!(parse(&config).unwrap()[0], (ExitCondition::Rule, 9));
+    }
+
+    #[test]
+    /// unknown conditions, codes outside of 1-255, and rule codes without --rules are errors
+    fn invalid_policies_are_errors() {
+        assert!(parse(&config(&[("nope", 2)])).is_err());
+        assert!(parse(&config(&[("errors", 0)])).is_err());
+        assert!(parse(&config(&[("bail", 256)])).is_err());
+        assert!(parse(&config(&[("rule", 2)])).is_err());
+        assert!(parse(&config(&[])).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,329 @@
+use std::{env, fs, sync::Mutex, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use reqwest::{header::CONTENT_TYPE, Client, Url};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    config::Configuration, response::FeroxResponse, traits::FeroxSerialize, utils::fmt_err, VERSION,
+};
+
+/// environment variable holding the token used to authenticate against the api
+const API_TOKEN_ENV: &str = "FEROX_API_TOKEN";
+
+/// DefectDojo's parser for the findings built by `dojo_finding`
+const DOJO_SCAN_TYPE: &str = "Generic Findings Import";
+
+/// Sends findings to a vulnerability management api once the scan ends (--api-export)
+///
+/// findings are collected as they're reported and sent in batches of --api-batch-size. the
+/// default (generic) format POSTs each batch as a json array, one entry per finding; each entry
+/// is the finding's json or, with --api-template, the template filled in with the finding's
+/// values. the dojo format uploads each batch to DefectDojo's import-scan endpoint as a
+/// Generic Findings Import into --dojo-engagement
+///
+/// the token in FEROX_API_TOKEN, if any, is sent as `Authorization: Bearer TOKEN`
+/// (`Authorization: Token TOKEN` for DefectDojo)
+#[derive(Debug)]
+pub(crate) struct ApiExport {
+    /// url the batches are sent to
+    url: Url,
+
+    /// whether the api is DefectDojo's
+    dojo: bool,
+
+    /// engagement DefectDojo imports go into
+    engagement: String,
+
+    /// --api-template contents, if given
+    template: Option<String>,
+
+    /// number of findings sent per request
+    batch_size: usize,
+
+    /// token from FEROX_API_TOKEN, if set
+    token: Option<String>,
+
+    /// client used to reach the api; scan headers and cookies aren't sent to it
+    client: Client,
+
+    /// findings collected so far, already in the shape the api expects
+    findings: Mutex<Vec<Value>>,
+}
+
+impl ApiExport {
+    /// validate the --api-export settings and read the template, if any
+    pub(crate) fn new(config: &Configuration) -> Result<Self> {
+        log::trace!("enter: ApiExport::new({})", config.api_export);
+
+        let url = Url::parse(&config.api_export)
+            .with_context(|| fmt_err(&format!("Could not parse {}", config.api_export)))?;
+
+        let dojo = config.api_export_format == "dojo";
+
+        if dojo && config.dojo_engagement.is_empty() {
+            bail!(fmt_err("--api-export-format dojo needs --dojo-engagement"));
+        }
+
+        let template = if config.api_template.is_empty() {
+            None
+        } else {
+            Some(
+                fs::read_to_string(&config.api_template)
+                    .with_context(|| fmt_err(&format!("Could not read {}", config.api_template)))?,
+            )
+        };
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout.max(30)))
+            .danger_accept_invalid_certs(config.insecure)
+            .build()?;
+
+        let export = Self {
+            url,
+            dojo,
+            engagement: config.dojo_engagement.clone(),
+            template,
+            batch_size: config.api_batch_size.max(1),
+            token: env::var(API_TOKEN_ENV)
+                .ok()
+                .filter(|token| !token.is_empty()),
+            client,
+            findings: Mutex::new(Vec::new()),
+        };
+
+        log::trace!("exit: ApiExport::new -> {:?}", export);
+        Ok(export)
+    }
+
+    /// collect a single finding
+    pub(crate) fn add(&self, response: &FeroxResponse) -> Result<()> {
+        let finding = if self.dojo {
+            dojo_finding(response)
+        } else if let Some(template) = &self.template {
+            render(template, response)?
+        } else {
+            serde_json::from_str(&response.as_json()?)?
+        };
+
+        if let Ok(mut findings) = self.findings.lock() {
+            findings.push(finding);
+        }
+
+        Ok(())
+    }
+
+    /// send everything collected, one batch at a time; a failed batch doesn't stop the rest
+    pub(crate) async fn finish(self) -> Result<()> {
+        log::trace!("enter: ApiExport::finish");
+
+        let findings = self.findings.into_inner().unwrap_or_default();
+        let mut failed = 0;
+
+        for batch in findings.chunks(self.batch_size) {
+            let request = if self.dojo {
+                let boundary = format!("ferox{}", Uuid::new_v4().simple());
+
+                self.client
+                    .post(self.url.clone())
+                    .header(
+                        CONTENT_TYPE,
+                        format!("multipart/form-data; boundary={boundary}"),
+                    )
+                    .body(dojo_form(&boundary, &self.engagement, batch)?)
+            } else {
+                self.client.post(self.url.clone()).json(&batch)
+            };
+
+            let request = match &self.token {
+                Some(token) if self.dojo => {
+                    request.header("Authorization", format!("Token {token}"))
+                }
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            };
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => {
+                    failed += batch.len();
+                    log::warn!(
+                        "{} rejected {} findings: {}",
+                        self.url,
+                        batch.len(),
+                        response.status()
+                    );
+                }
+                Err(e) => {
+                    failed += batch.len();
+                    log::warn!(
+                        "Could not send {} findings to {}: {}",
+                        batch.len(),
+                        self.url,
+                        e
+                    );
+                }
+            }
+        }
+
+        log::info!(
+            "sent {} of {} findings to {}",
+            findings.len() - failed,
+            findings.len(),
+            self.url
+        );
+
+        log::trace!("exit: ApiExport::finish");
+        Ok(())
+    }
+}
+
+/// fill in the given --api-template with the values of the given finding
+///
+/// placeholders are replaced with json values, i.e. `{{url}}` becomes `"http://..."` with the
+/// quotes, so a template only needs to decide where values go, not how they're escaped
+fn render(template: &str, response: &FeroxResponse) -> Result<Value> {
+    let title = response
+        .page_meta()
+        .and_then(|meta| meta.title.clone())
+        .map_or(Value::Null, Value::from);
+
+    let values = [
+        ("url", json!(response.url().as_str())),
+        ("path", json!(response.url().path())),
+        ("method", json!(response.method().as_str())),
+        ("status", json!(response.status().as_u16())),
+        ("content_length", json!(response.content_length())),
+        ("line_count", json!(response.line_count())),
+        ("word_count", json!(response.word_count())),
+        ("tags", json!(response.tags())),
+        ("severity", json!(response.severity())),
+        ("title", title),
+    ];
+
+    let mut rendered = template.to_string();
+
+    for (name, value) in values {
+        rendered = rendered.replace(&format!("{{{{{name}}}}}"), &value.to_string());
+    }
+
+    serde_json::from_str(&rendered)
+        .with_context(|| fmt_err("--api-template isn't valid json once filled in"))
+}
+
+/// the given finding as an entry of a DefectDojo Generic Findings Import
+fn dojo_finding(response: &FeroxResponse) -> Value {
+    let url = response.url();
+
+    // dojo's severities are capitalized, and every finding needs one
+    let severity = response
+        .severity()
+        .map(|severity| {
+            let severity = severity.to_string();
+            severity[..1].to_uppercase() + &severity[1..]
+        })
+        .unwrap_or_else(|| String::from("Info"));
+
+    json!({
+        "title": format!("{} {}", response.status().as_u16(), url.path()),
+        "description": format!(
+            "{} {} returned {} ({} bytes, {} lines, {} words)",
+            response.method(),
+            url,
+            response.status(),
+            response.content_length(),
+            response.line_count(),
+            response.word_count()
+        ),
+        "severity": severity,
+        "unique_id_from_tool": format!("{} {}", response.method(), url),
+        "tags": response.tags(),
+        "endpoints": [{
+            "protocol": url.scheme(),
+            "host": url.host_str().unwrap_or_default(),
+            "port": url.port_or_known_default(),
+            "path": url.path(),
+        }],
+    })
+}
+
+/// multipart body of a DefectDojo import-scan request carrying the given findings
+fn dojo_form(boundary: &str, engagement: &str, findings: &[Value]) -> Result<Vec<u8>> {
+    let report = serde_json::to_string(&json!({ "findings": findings }))?;
+
+    let fields = [
+        ("scan_type", DOJO_SCAN_TYPE),
+        ("engagement", engagement),
+        ("active", "true"),
+        ("verified", "false"),
+        ("version", VERSION),
+    ];
+
+    let mut form = String::new();
+
+    for (name, value) in fields {
+        form.push_str(&format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+        ));
+    }
+
+    form.push_str(&format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"feroxbuster.json\"\r\nContent-Type: application/json\r\n\r\n{report}\r\n--{boundary}--\r\n"
+    ));
+
+    Ok(form.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Severity;
+
+    /// finding used by the tests below
+    fn finding() -> FeroxResponse {
+        let mut response = FeroxResponse::default();
+        response.set_url("https://localhost:8443/admin/");
+        response.set_text("hello there");
+        response.add_tag(String::from("admin-panel"));
+        response.raise_severity(Severity::High);
+        response
+    }
+
+    #[test]
+    /// placeholders become json values; a template that isn't json once filled in is an error
+    fn template_is_rendered_as_json() {
+        let rendered = render(
+            r#"{"target": {{url}}, "code": {{status}}, "labels": {{tags}}, "sev": {{severity}}, "title": {{title}}}"#,
+            &finding(),
+        )
+        .unwrap();
+
+        assert_eq!(rendered["target"], "https://localhost:8443/admin/");
+        assert_eq!(rendered["code"], 200);
+        assert_eq!(rendered["labels"], json!(["admin-panel"]));
+        assert_eq!(rendered["sev"], "high");
+        assert_eq!(rendered["title"], Value::Null);
+
+        assert!(render(r#"{"broken": {{nope}}}"#, &finding()).is_err());
+    }
+
+    #[test]
+    /// dojo findings carry a capitalized severity, the tags, and an endpoint for the url
+    fn dojo_findings_and_form() {
+        let dojo = dojo_finding(&finding());
+
+        assert_eq!(dojo["severity"], "High");
+        assert_eq!(dojo["tags"], json!(["admin-panel"]));
+        assert_eq!(dojo["endpoints"][0]["port"], 8443);
+        assert_eq!(dojo["endpoints"][0]["path"], "/admin/");
+
+        let form = String::from_utf8(dojo_form("XYZ", "7", &[dojo]).unwrap()).unwrap();
+
+        assert!(form.starts_with("--XYZ\r\n"));
+        assert!(form.contains("name=\"engagement\"\r\n\r\n7\r\n"));
+        assert!(form.contains(DOJO_SCAN_TYPE));
+        assert!(form.contains(r#"{"findings":[{"#));
+        assert!(form.ends_with("--XYZ--\r\n"));
+    }
+}
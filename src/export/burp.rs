@@ -0,0 +1,312 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::Url;
+
+use super::{request_headers, UtcTime};
+use crate::{config::Configuration, response::FeroxResponse, utils::fmt_err, VERSION};
+
+/// day names as used by burp's `<time>` element, indexed from sunday
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// month names as used by burp's `<time>` element
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Writes discovered responses to disk using the same XML layout as Burp Suite's
+/// `Save items` feature; each item carries the base64 encoded request/response pair
+#[derive(Debug)]
+pub(crate) struct BurpExport {
+    /// buffered writer for the export file, locked as items are added from the output handler
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl BurpExport {
+    /// create the export file and write the opening `<items>` tag
+    pub(crate) fn new(filename: &str) -> Result<Self> {
+        log::trace!("enter: BurpExport::new({})", filename);
+
+        let file = File::create(filename)
+            .with_context(|| fmt_err(&format!("Could not open {filename}")))?;
+
+        let mut writer = BufWriter::new(file);
+
+        write!(
+            writer,
+            "<?xml version=\"1.0\"?>\n<items burpVersion=\"feroxbuster-{}\" exportTime=\"{}\">\n",
+            VERSION,
+            timestamp(SystemTime::now())
+        )?;
+        writer.flush()?;
+
+        let export = Self {
+            writer: Mutex::new(writer),
+        };
+
+        log::trace!("exit: BurpExport::new -> {:?}", export);
+        Ok(export)
+    }
+
+    /// write a single `<item>` to the export file
+    ///
+    /// the response body is only present when it wasn't dropped before reaching the output
+    /// handler, in which case the item contains headers only
+    pub(crate) fn add(&self, response: &FeroxResponse, config: &Configuration) -> Result<()> {
+        let item = item(response, config, SystemTime::now());
+
+        if let Ok(mut writer) = self.writer.lock() {
+            writer.write_all(item.as_bytes())?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// write the closing `</items>` tag; no further items should be added afterwards
+    pub(crate) fn finish(&self) -> Result<()> {
+        log::trace!("enter: BurpExport::finish");
+
+        if let Ok(mut writer) = self.writer.lock() {
+            writer.write_all(b"</items>\n")?;
+            writer.flush()?;
+        }
+
+        log::trace!("exit: BurpExport::finish");
+        Ok(())
+    }
+}
+
+/// build the `<item>` element representing the given response
+fn item(response: &FeroxResponse, config: &Configuration, time: SystemTime) -> String {
+    let url = response.url();
+    let host = url.host_str().unwrap_or_default();
+    let port = url.port_or_known_default().unwrap_or_default();
+
+    let extension = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|filename| filename.rsplit_once('.'))
+        .map(|(_, ext)| ext)
+        .filter(|ext| !ext.is_empty())
+        .unwrap_or("null");
+
+    let request = STANDARD.encode(raw_request(response, config));
+    let response_bytes = raw_response(response);
+
+    format!(
+        "  <item>\n    <time>{}</time>\n    <url>{}</url>\n    <host ip=\"\">{}</host>\n    \
+         <port>{}</port>\n    <protocol>{}</protocol>\n    <method>{}</method>\n    \
+         <path>{}</path>\n    <extension>{}</extension>\n    \
+         <request base64=\"true\">{}</request>\n    <status>{}</status>\n    \
+         <responselength>{}</responselength>\n    <mimetype>{}</mimetype>\n    \
+         <response base64=\"true\">{}</response>\n    <comment></comment>\n  </item>\n",
+        timestamp(time),
+        cdata(url.as_str()),
+        escape(host),
+        port,
+        url.scheme(),
+        cdata(response.method().as_str()),
+        cdata(&request_target(url)),
+        escape(extension),
+        cdata(&request),
+        response.status().as_u16(),
+        response_bytes.len(),
+        mimetype(response),
+        cdata(&STANDARD.encode(&response_bytes)),
+    )
+}
+
+/// path and query of the given url, i.e. what's sent on the request line
+fn request_target(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// reconstruct the request that produced the given response from the scan's configuration
+fn raw_request(response: &FeroxResponse, config: &Configuration) -> Vec<u8> {
+    let url = response.url();
+
+    let mut raw = format!("{} {} HTTP/1.1\r\n", response.method(), request_target(url));
+
+    for (key, value) in request_headers(url, config) {
+        raw.push_str(&format!("{key}: {value}\r\n"));
+    }
+
+    raw.push_str("\r\n");
+
+    let mut raw = raw.into_bytes();
+    raw.extend_from_slice(&config.data);
+    raw
+}
+
+/// serialize the given response's status line, headers, and body (if still present)
+fn raw_response(response: &FeroxResponse) -> Vec<u8> {
+    let status = response.status();
+
+    let mut raw = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    )
+    .into_bytes();
+
+    for (key, value) in response.headers() {
+        raw.extend_from_slice(key.as_str().as_bytes());
+        raw.extend_from_slice(b": ");
+        raw.extend_from_slice(value.as_bytes());
+        raw.extend_from_slice(b"\r\n");
+    }
+
+    raw.extend_from_slice(b"\r\n");
+    raw.extend_from_slice(response.text().as_bytes());
+    raw
+}
+
+/// map the response's content-type onto the coarse categories burp uses for `<mimetype>`
+fn mimetype(response: &FeroxResponse) -> &'static str {
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    if content_type.is_empty() {
+        ""
+    } else if content_type.contains("html") {
+        "HTML"
+    } else if content_type.contains("json") {
+        "JSON"
+    } else if content_type.contains("javascript") {
+        "script"
+    } else if content_type.contains("css") {
+        "CSS"
+    } else if content_type.contains("xml") {
+        "XML"
+    } else if content_type.starts_with("image/") {
+        "image"
+    } else if content_type.starts_with("text/") {
+        "text"
+    } else {
+        "app"
+    }
+}
+
+/// wrap the given string in a CDATA section, splitting any embedded terminators
+fn cdata(value: &str) -> String {
+    format!("<![CDATA[{}]]>", value.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// escape the characters that aren't allowed in xml text nodes
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// format the given time the way burp does, i.e. `Thu Jan 01 00:00:00 UTC 1970`
+fn timestamp(time: SystemTime) -> String {
+    let utc = UtcTime::from(time);
+
+    format!(
+        "{} {} {:02} {:02}:{:02}:{:02} UTC {}",
+        DAYS[utc.weekday],
+        MONTHS[utc.month - 1],
+        utc.day,
+        utc.hour,
+        utc.minute,
+        utc.second,
+        utc.year
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+    use tempfile::NamedTempFile;
+
+    /// helper to build a response with the given url/body
+    fn response(url: &str, text: &str) -> FeroxResponse {
+        let mut response = FeroxResponse::default();
+        response.set_url(url);
+        response.set_text(text);
+        response
+    }
+
+    #[test]
+    /// timestamp should match burp's formatting for known points in time
+    fn burp_timestamp_formats_known_dates() {
+        assert_eq!(timestamp(UNIX_EPOCH), "Thu Jan 01 00:00:00 UTC 1970");
+
+        let leap_day = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        assert_eq!(timestamp(leap_day), "Thu Feb 29 12:34:56 UTC 2024");
+    }
+
+    #[test]
+    /// cdata should neutralize embedded section terminators
+    fn burp_cdata_splits_terminators() {
+        assert_eq!(cdata("a]]>b"), "<![CDATA[a]]]]><![CDATA[>b]]>");
+        assert_eq!(escape("<a&b>"), "&lt;a&amp;b&gt;");
+    }
+
+    #[test]
+    /// raw request should include host, configured headers, and the request body
+    fn burp_raw_request_uses_configuration() {
+        let mut config = Configuration::new().unwrap();
+        config.data = b"stuff=things".to_vec();
+        config
+            .headers
+            .insert("X-Test".to_string(), "yes".to_string());
+
+        let resp = response("http://localhost:8080/admin?id=1", "");
+        let raw = String::from_utf8(raw_request(&resp, &config)).unwrap();
+
+        assert!(raw.starts_with("GET /admin?id=1 HTTP/1.1\r\nHost: localhost:8080\r\n"));
+        assert!(raw.contains(&format!("User-Agent: {}\r\n", config.user_agent)));
+        assert!(raw.contains("X-Test: yes\r\n"));
+        assert!(raw.contains("Content-Length: 12\r\n"));
+        assert!(raw.ends_with("\r\n\r\nstuff=things"));
+    }
+
+    #[test]
+    /// an exported file should contain a complete, base64 encoded item per response
+    fn burp_export_writes_items() {
+        let config = Configuration::new().unwrap();
+        let tmp = NamedTempFile::new().unwrap();
+        let filename = tmp.path().to_str().unwrap();
+
+        let mut resp: FeroxResponse = serde_json::from_str(
+            r#"{"url":"https://localhost/index.php","status":200,"headers":{"content-type":"text/html"}}"#,
+        )
+        .unwrap();
+        resp.set_text("<html></html>");
+
+        let export = BurpExport::new(filename).unwrap();
+        export.add(&resp, &config).unwrap();
+        export.finish().unwrap();
+
+        let contents = std::fs::read_to_string(filename).unwrap();
+        let encoded = STANDARD.encode(raw_response(&resp));
+
+        assert!(contents.starts_with("<?xml version=\"1.0\"?>\n<items burpVersion="));
+        assert!(contents.contains("<url><![CDATA[https://localhost/index.php]]></url>"));
+        assert!(contents.contains("<port>443</port>"));
+        assert!(contents.contains("<protocol>https</protocol>"));
+        assert!(contents.contains("<extension>php</extension>"));
+        assert!(contents.contains("<mimetype>HTML</mimetype>"));
+        assert!(contents.contains(&format!("<![CDATA[{encoded}]]>")));
+        assert!(contents.ends_with("</items>\n"));
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use super::{request_headers, UtcTime};
+use crate::{config::Configuration, response::FeroxResponse, utils::fmt_err, VERSION};
+
+/// Writes discovered responses to disk as an HTTP Archive (HAR 1.2); entries are streamed to
+/// the file as they arrive instead of being held in memory until the scan ends
+#[derive(Debug)]
+pub(crate) struct HarExport {
+    /// buffered writer for the export file along with whether an entry has been written yet,
+    /// which determines if a separating comma is needed
+    writer: Mutex<(BufWriter<File>, bool)>,
+}
+
+impl HarExport {
+    /// create the export file and write everything up to the opening of the `entries` array
+    pub(crate) fn new(filename: &str) -> Result<Self> {
+        log::trace!("enter: HarExport::new({})", filename);
+
+        let file = File::create(filename)
+            .with_context(|| fmt_err(&format!("Could not open {filename}")))?;
+
+        let mut writer = BufWriter::new(file);
+
+        write!(
+            writer,
+            "{{\"log\":{{\"version\":\"1.2\",\"creator\":{},\"entries\":[",
+            json!({"name": "feroxbuster", "version": VERSION})
+        )?;
+        writer.flush()?;
+
+        let export = Self {
+            writer: Mutex::new((writer, false)),
+        };
+
+        log::trace!("exit: HarExport::new -> {:?}", export);
+        Ok(export)
+    }
+
+    /// write a single entry to the export file
+    ///
+    /// the response body is only present when it wasn't dropped before reaching the output
+    /// handler, in which case the entry's content is empty
+    pub(crate) fn add(&self, response: &FeroxResponse, config: &Configuration) -> Result<()> {
+        let entry = entry(response, config, SystemTime::now());
+
+        if let Ok(mut guard) = self.writer.lock() {
+            let (writer, started) = &mut *guard;
+
+            if *started {
+                writer.write_all(b",")?;
+            }
+
+            serde_json::to_writer(&mut *writer, &entry)?;
+            writer.flush()?;
+            *started = true;
+        }
+
+        Ok(())
+    }
+
+    /// close the `entries` array and the top-level object; no further entries should be added
+    pub(crate) fn finish(&self) -> Result<()> {
+        log::trace!("enter: HarExport::finish");
+
+        if let Ok(mut guard) = self.writer.lock() {
+            guard.0.write_all(b"]}}\n")?;
+            guard.0.flush()?;
+        }
+
+        log::trace!("exit: HarExport::finish");
+        Ok(())
+    }
+}
+
+/// build the HAR entry representing the given response
+fn entry(response: &FeroxResponse, config: &Configuration, time: SystemTime) -> Value {
+    let url = response.url();
+    let status = response.status();
+
+    let request_headers: Vec<_> = request_headers(url, config)
+        .into_iter()
+        .map(|(name, value)| json!({"name": name, "value": value}))
+        .collect();
+
+    let query_string: Vec<_> = url
+        .query_pairs()
+        .map(|(name, value)| json!({"name": name, "value": value}))
+        .collect();
+
+    let mut request = json!({
+        "method": response.method().as_str(),
+        "url": url.as_str(),
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": request_headers,
+        "queryString": query_string,
+        "headersSize": -1,
+        "bodySize": config.data.len(),
+    });
+
+    if !config.data.is_empty() {
+        let mime_type = config
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default();
+
+        request["postData"] = json!({
+            "mimeType": mime_type,
+            "text": String::from_utf8_lossy(&config.data),
+        });
+    }
+
+    let header_value = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let response_headers: Vec<_> = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            json!({"name": name.as_str(), "value": String::from_utf8_lossy(value.as_bytes())})
+        })
+        .collect();
+
+    let text = response.text();
+
+    json!({
+        "startedDateTime": UtcTime::from(time).to_iso8601(),
+        "time": 0,
+        "request": request,
+        "response": {
+            "status": status.as_u16(),
+            "statusText": status.canonical_reason().unwrap_or_default(),
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": response_headers,
+            "content": {
+                "size": text.len(),
+                "mimeType": header_value("content-type"),
+                "text": text,
+            },
+            "redirectURL": header_value("location"),
+            "headersSize": -1,
+            "bodySize": text.len(),
+        },
+        "cache": {},
+        "timings": {"send": 0, "wait": 0, "receive": 0},
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    /// an entry should carry the reconstructed request and the response's details
+    fn har_entry_contains_request_and_response() {
+        let mut config = Configuration::new().unwrap();
+        config.data = b"stuff=things".to_vec();
+
+        let mut resp: FeroxResponse = serde_json::from_str(
+            r#"{"url":"http://localhost:8080/admin?id=1","status":301,"headers":{"location":"/login","content-type":"text/html"}}"#,
+        )
+        .unwrap();
+        resp.set_text("moved");
+
+        let entry = entry(&resp, &config, UNIX_EPOCH);
+
+        assert_eq!(entry["startedDateTime"], "1970-01-01T00:00:00.000Z");
+        assert_eq!(entry["request"]["url"], "http://localhost:8080/admin?id=1");
+        assert_eq!(entry["request"]["headers"][0]["value"], "localhost:8080");
+        assert_eq!(entry["request"]["queryString"][0]["name"], "id");
+        assert_eq!(entry["request"]["postData"]["text"], "stuff=things");
+        assert_eq!(entry["response"]["status"], 301);
+        assert_eq!(entry["response"]["statusText"], "Moved Permanently");
+        assert_eq!(entry["response"]["redirectURL"], "/login");
+        assert_eq!(entry["response"]["content"]["mimeType"], "text/html");
+        assert_eq!(entry["response"]["content"]["text"], "moved");
+    }
+
+    #[test]
+    /// an exported file should be valid json containing one entry per response
+    fn har_export_writes_valid_json() {
+        let config = Configuration::new().unwrap();
+        let tmp = NamedTempFile::new().unwrap();
+        let filename = tmp.path().to_str().unwrap();
+
+        let mut first = FeroxResponse::default();
+        first.set_url("https://localhost/index.php");
+        let mut second = FeroxResponse::default();
+        second.set_url("https://localhost/admin");
+
+        let export = HarExport::new(filename).unwrap();
+        export.add(&first, &config).unwrap();
+        export.add(&second, &config).unwrap();
+        export.finish().unwrap();
+
+        let contents = std::fs::read_to_string(filename).unwrap();
+        let har: Value = serde_json::from_str(&contents).unwrap();
+
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(har["log"]["creator"]["name"], "feroxbuster");
+        assert_eq!(har["log"]["entries"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            har["log"]["entries"][1]["request"]["url"],
+            "https://localhost/admin"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! exporters that write discovered resources in formats consumable by other tools
+mod api;
+mod burp;
+mod har;
+mod pipe;
+mod stream;
+
+pub(crate) use self::api::ApiExport;
+pub(crate) use self::burp::BurpExport;
+pub(crate) use self::har::HarExport;
+pub(crate) use self::pipe::PipeSink;
+pub(crate) use self::stream::QuietStream;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::Url;
+
+use crate::{
+    client::{self, IPV6_LITERAL_SUFFIX},
+    config::Configuration,
+};
+
+/// broken down UTC representation of a point in time, used by exporters that need to write
+/// timestamps without pulling in a date/time crate
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct UtcTime {
+    /// four digit year
+    year: i64,
+
+    /// month of the year, 1-12
+    month: usize,
+
+    /// day of the month, 1-31
+    day: i64,
+
+    /// hour of the day, 0-23
+    hour: i64,
+
+    /// minute of the hour, 0-59
+    minute: i64,
+
+    /// second of the minute, 0-59
+    second: i64,
+
+    /// milliseconds within the second, 0-999
+    millis: u32,
+
+    /// day of the week, 0-6 where 0 is sunday
+    weekday: usize,
+}
+
+impl From<SystemTime> for UtcTime {
+    /// civil-from-days conversion; see http://howardhinnant.github.io/date_algorithms.html
+    fn from(time: SystemTime) -> Self {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let secs = since_epoch.as_secs() as i64;
+
+        let days = secs.div_euclid(86_400);
+        let secs_of_day = secs.rem_euclid(86_400);
+
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+        Self {
+            year: yoe + era * 400 + i64::from(month <= 2),
+            month: month as usize,
+            day: doy - (153 * mp + 2) / 5 + 1,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day % 3600 / 60,
+            second: secs_of_day % 60,
+            millis: since_epoch.subsec_millis(),
+            weekday: (days + 4).rem_euclid(7) as usize, // 1970-01-01 was a thursday
+        }
+    }
+}
+
+impl UtcTime {
+    /// ISO 8601 representation, i.e. `1970-01-01T00:00:00.000Z`
+    pub(crate) fn to_iso8601(self) -> String {
+        format!(
+            "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second, self.millis
+        )
+    }
+}
+
+/// reconstruct the headers sent along with a request to the given url from the scan's
+/// configuration; the client adds these same values to every request it makes
+pub(crate) fn request_headers(url: &Url, config: &Configuration) -> Vec<(String, String)> {
+    let host = url.host_str().unwrap_or_default();
+
+    let mut headers = vec![(
+        String::from("Host"),
+        match url.port() {
+            _ if !config.host_header.is_empty() => config.host_header.clone(),
+            _ if host.ends_with(IPV6_LITERAL_SUFFIX) => {
+                client::host_header(&config.target_url, "").unwrap_or_else(|| host.to_string())
+            }
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        },
+    )];
+
+    if !config
+        .headers
+        .keys()
+        .any(|key| key.eq_ignore_ascii_case("user-agent"))
+    {
+        headers.push((String::from("User-Agent"), config.user_agent.clone()));
+    }
+
+    for (key, value) in &config.headers {
+        headers.push((key.to_owned(), value.to_owned()));
+    }
+
+    if !config.data.is_empty() {
+        headers.push((
+            String::from("Content-Length"),
+            config.data.len().to_string(),
+        ));
+    }
+
+    headers
+}
@@ -0,0 +1,217 @@
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use tokio::{
+    io::AsyncWriteExt,
+    process::{Child, ChildStdin, Command},
+    sync::mpsc,
+    task::JoinHandle,
+};
+
+use crate::{traits::FeroxSerialize, utils::fmt_err};
+
+/// number of entries that may be queued for the command before the output handler waits for it
+/// to catch up
+const PIPE_CAPACITY: usize = 1024;
+
+/// number of times the command is restarted after exiting early before the pipe gives up
+const MAX_RESTARTS: usize = 5;
+
+/// External command that receives discovered resources as NDJSON on its stdin (--pipe-to)
+///
+/// entries are handed off to a separate task through a bounded channel, so a slow command
+/// applies backpressure to the output handler instead of growing memory without limit
+#[derive(Debug)]
+pub(crate) struct PipeSink {
+    /// transmitter that queues entries for the task that owns the command
+    tx: mpsc::Sender<String>,
+
+    /// task that feeds the command's stdin
+    task: JoinHandle<()>,
+}
+
+impl PipeSink {
+    /// spawn the given command and the task that feeds it
+    pub(crate) fn new(command: &str) -> Result<Self> {
+        log::trace!("enter: PipeSink::new({})", command);
+
+        let process = PipeProcess::spawn(command)?;
+        let (tx, rx) = mpsc::channel::<String>(PIPE_CAPACITY);
+        let task = tokio::spawn(process.feed(rx));
+
+        let sink = Self { tx, task };
+
+        log::trace!("exit: PipeSink::new -> {:?}", sink);
+        Ok(sink)
+    }
+
+    /// queue a single entry for the command; waits when the queue is full
+    pub(crate) async fn add<T: FeroxSerialize>(&self, entry: &T) -> Result<()> {
+        self.tx.send(entry.as_json()?).await?;
+        Ok(())
+    }
+
+    /// close the command's stdin once everything queued has been written, then wait for it
+    /// to exit
+    pub(crate) async fn finish(self) -> Result<()> {
+        drop(self.tx);
+        self.task.await?;
+        Ok(())
+    }
+}
+
+/// the running command, along with everything needed to restart it
+#[derive(Debug)]
+struct PipeProcess {
+    /// command line given to --pipe-to
+    command: String,
+
+    /// the running child process
+    child: Child,
+
+    /// child's stdin
+    stdin: ChildStdin,
+
+    /// number of times the command has been restarted
+    restarts: usize,
+}
+
+impl PipeProcess {
+    /// start the given command via the system shell, with stdin piped to us
+    fn spawn(command: &str) -> Result<Self> {
+        #[cfg(windows)]
+        let mut shell = {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C");
+            shell
+        };
+
+        #[cfg(not(windows))]
+        let mut shell = {
+            let mut shell = Command::new("sh");
+            shell.arg("-c");
+            shell
+        };
+
+        let mut child = shell
+            .arg(command)
+            .stdin(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| fmt_err(&format!("Could not start {command}")))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .with_context(|| fmt_err(&format!("Could not open stdin of {command}")))?;
+
+        Ok(Self {
+            command: command.to_string(),
+            child,
+            stdin,
+            restarts: 0,
+        })
+    }
+
+    /// replace a command that exited early with a fresh instance
+    async fn restart(&mut self) -> Result<()> {
+        self.child.kill().await.unwrap_or_default();
+
+        let restarts = self.restarts + 1;
+        *self = Self::spawn(&self.command)?;
+        self.restarts = restarts;
+
+        Ok(())
+    }
+
+    /// write a single entry, restarting the command as needed; returns false once the command
+    /// has exited more times than we're willing to restart it
+    async fn write(&mut self, line: &str) -> bool {
+        loop {
+            let result = self.stdin.write_all(line.as_bytes()).await;
+
+            let error = match result {
+                Ok(_) => match self.stdin.flush().await {
+                    Ok(_) => return true,
+                    Err(e) => e,
+                },
+                Err(e) => e,
+            };
+
+            if self.restarts >= MAX_RESTARTS {
+                log::error!(
+                    "{} exited {} times, no longer sending it results: {}",
+                    self.command,
+                    self.restarts + 1,
+                    error
+                );
+                return false;
+            }
+
+            log::warn!("{} exited early, restarting it: {}", self.command, error);
+
+            if let Err(e) = self.restart().await {
+                log::error!("{}", e);
+                return false;
+            }
+        }
+    }
+
+    /// write everything received to the command's stdin until the channel closes
+    async fn feed(mut self, mut rx: mpsc::Receiver<String>) {
+        log::trace!("enter: PipeProcess::feed({:?})", self);
+
+        while let Some(line) = rx.recv().await {
+            if !self.write(&line).await {
+                // the command is gone for good; keep draining so the output handler never
+                // blocks on a full queue
+                while rx.recv().await.is_some() {}
+                break;
+            }
+        }
+
+        // closing stdin signals eof, giving the command a chance to finish up
+        drop(self.stdin);
+
+        if let Err(e) = self.child.wait().await {
+            log::warn!("Could not wait for {} to exit: {}", self.command, e);
+        }
+
+        log::trace!("exit: PipeProcess::feed");
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+    use crate::response::FeroxResponse;
+    use tempfile::TempDir;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// a command that exits after each line should be restarted without losing entries
+    async fn pipe_sink_restarts_command_that_exits_early() {
+        let tmp = TempDir::new().unwrap();
+        let outfile = tmp.path().join("piped.txt");
+
+        let sink = PipeSink::new(&format!("head -n 1 >> {}", outfile.display())).unwrap();
+
+        for url in ["https://localhost/one", "https://localhost/two"] {
+            let mut resp = FeroxResponse::default();
+            resp.set_url(url);
+            sink.add(&resp).await.unwrap();
+
+            // give the command time to exit so the next write has to restart it
+            tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        }
+
+        sink.finish().await.unwrap();
+
+        let contents = std::fs::read_to_string(outfile).unwrap();
+        let urls: Vec<_> = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["url"].clone())
+            .collect();
+
+        assert_eq!(urls, ["https://localhost/one", "https://localhost/two"]);
+    }
+}
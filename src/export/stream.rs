@@ -0,0 +1,104 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Mutex,
+};
+
+use anyhow::{Context, Result};
+use console::strip_ansi_codes;
+
+use crate::{response::FeroxResponse, traits::FeroxSerialize, utils::fmt_err};
+
+/// Secondary output that receives every response seen by the output handler (--quiet-stream),
+/// including those that never made it to the terminal because a filter removed them
+#[derive(Debug)]
+pub(crate) struct QuietStream {
+    /// buffered writer for the stream file
+    writer: Mutex<BufWriter<File>>,
+
+    /// whether entries should be written as NDJSON (--json)
+    json: bool,
+}
+
+impl QuietStream {
+    /// create (or truncate) the stream file
+    pub(crate) fn new(filename: &str, json: bool) -> Result<Self> {
+        log::trace!("enter: QuietStream::new({}, {})", filename, json);
+
+        let file = File::create(filename)
+            .with_context(|| fmt_err(&format!("Could not open {filename}")))?;
+
+        let stream = Self {
+            writer: Mutex::new(BufWriter::new(file)),
+            json,
+        };
+
+        log::trace!("exit: QuietStream::new -> {:?}", stream);
+        Ok(stream)
+    }
+
+    /// write a single response to the stream; `filtered_by` describes the filter that removed
+    /// the response, and is `None` for responses that were reported as findings
+    pub(crate) fn add(&self, response: &FeroxResponse, filtered_by: Option<&str>) -> Result<()> {
+        let line = entry(response, filtered_by, self.json)?;
+
+        if let Ok(mut guard) = self.writer.lock() {
+            guard.write_all(line.as_bytes())?;
+            guard.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// build the line representing the given response; filtered responses are prefixed with the
+/// filter responsible, or carry a `filtered_by` field when writing json
+fn entry(response: &FeroxResponse, filtered_by: Option<&str>, json: bool) -> Result<String> {
+    if json {
+        let mut value: serde_json::Value = serde_json::from_str(&response.as_json()?)?;
+
+        if let Some(filter) = filtered_by {
+            value["filtered_by"] = filter.into();
+        }
+
+        let mut line = value.to_string();
+        line.push('\n');
+        return Ok(line);
+    }
+
+    let line = strip_ansi_codes(&response.as_str()).to_string();
+
+    Ok(match filtered_by {
+        Some(filter) => format!("[filtered: {filter}] {line}"),
+        None => line,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// filtered responses should be tagged with their filter, findings should be left alone
+    fn quiet_stream_entry_tags_filtered_responses() {
+        let mut resp = FeroxResponse::default();
+        resp.set_url("https://localhost/admin");
+
+        let finding = entry(&resp, None, false).unwrap();
+        assert!(finding.contains("https://localhost/admin"));
+        assert!(!finding.starts_with("[filtered"));
+
+        let filtered = entry(&resp, Some("Status code: 404"), false).unwrap();
+        assert!(filtered.starts_with("[filtered: Status code: 404] "));
+        assert!(filtered.ends_with(&finding));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&entry(&resp, Some("Word count: 12"), true).unwrap()).unwrap();
+        assert_eq!(json["filtered_by"], "Word count: 12");
+        assert_eq!(json["url"], "https://localhost/admin");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&entry(&resp, None, true).unwrap()).unwrap();
+        assert!(json.get("filtered_by").is_none());
+    }
+}
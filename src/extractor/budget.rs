@@ -0,0 +1,155 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use crate::{config::Configuration, scan_manager::SeenUrls};
+
+/// --extract-page-limit, --extract-scan-limit, --extract-limit, and --extract-depth; shared by
+/// every scan
+#[derive(Debug, Default)]
+pub struct LinkBudget {
+    /// what's been spent so far
+    budget: Mutex<Budget>,
+}
+
+/// Links queued by the extractor so far, and how deep into extraction each directory is
+#[derive(Debug, Default)]
+struct Budget {
+    /// links queued, keyed by the normalized url of the scan that found them
+    per_scan: HashMap<String, usize>,
+
+    /// links queued across all scans
+    total: usize,
+
+    /// number of extraction hops it took to reach a directory, keyed by normalized url; a
+    /// directory found through the wordlist or given as a target isn't in here, and is at 0
+    depths: HashMap<String, usize>,
+}
+
+/// implementation of Budget
+impl Budget {
+    /// take up to `wanted` links out of the budget for a single page of the given scan, returns
+    /// the number that may be queued
+    fn take(&mut self, config: &Configuration, scan_url: &str, wanted: usize) -> usize {
+        // a limit of 0 means no limit
+        let remaining = |limit: usize, used: usize| match limit {
+            0 => usize::MAX,
+            limit => limit.saturating_sub(used),
+        };
+
+        let scan_used = self
+            .per_scan
+            .entry(SeenUrls::normalize(scan_url))
+            .or_default();
+
+        let allowed = wanted
+            .min(remaining(config.extract_page_limit, 0))
+            .min(remaining(config.extract_scan_limit, *scan_used))
+            .min(remaining(config.extract_limit, self.total));
+
+        *scan_used += allowed;
+        self.total += allowed;
+
+        allowed
+    }
+
+    /// extraction depth of the given directory
+    fn depth(&self, url: &str) -> usize {
+        self.depths
+            .get(&SeenUrls::normalize(url))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// implementation of LinkBudget
+impl LinkBudget {
+    /// number of links a page of the given scan may queue, out of the `wanted` links it found;
+    /// the returned number is counted against the scan's and the overall budget
+    pub(super) fn take(&self, config: &Configuration, scan_url: &str, wanted: usize) -> usize {
+        match self.budget.lock() {
+            Ok(mut budget) => budget.take(config, scan_url, wanted),
+            Err(_) => wanted,
+        }
+    }
+
+    /// extraction depth of the given scan; the number of extraction hops it took to reach it
+    pub(super) fn depth(&self, scan_url: &str) -> usize {
+        self.budget
+            .lock()
+            .map(|budget| budget.depth(scan_url))
+            .unwrap_or(0)
+    }
+
+    /// whether pages of the given scan are too deep into extraction to have their links
+    /// extracted
+    pub(super) fn too_deep(&self, config: &Configuration, scan_url: &str) -> bool {
+        config.extract_depth > 0 && self.depth(scan_url) >= config.extract_depth
+    }
+
+    /// record that the given directory was reached by extracting links from a scan at
+    /// `depth - 1`; a directory reached more than one way keeps the shallowest depth
+    pub(super) fn record_depth(&self, url: &str, depth: usize) {
+        if let Ok(mut budget) = self.budget.lock() {
+            budget
+                .depths
+                .entry(SeenUrls::normalize(url))
+                .and_modify(|known| *known = (*known).min(depth))
+                .or_insert(depth);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// the page, scan, and total limits all cap what a page may queue
+    fn budget_take_respects_every_limit() {
+        let config = Configuration {
+            extract_page_limit: 5,
+            extract_scan_limit: 8,
+            extract_limit: 10,
+            ..Default::default()
+        };
+
+        let mut budget = Budget::default();
+
+        assert_eq!(budget.take(&config, "http://localhost/", 20), 5);
+        assert_eq!(budget.take(&config, "http://localhost", 20), 3);
+        assert_eq!(budget.take(&config, "http://localhost/", 20), 0);
+
+        // another scan has its own allowance, but the total is almost spent
+        assert_eq!(budget.take(&config, "http://localhost/admin/", 20), 2);
+        assert_eq!(budget.take(&config, "http://localhost/other/", 20), 0);
+    }
+
+    #[test]
+    /// no limits means every link is queued
+    fn budget_take_without_limits() {
+        let mut budget = Budget::default();
+        assert_eq!(
+            budget.take(&Configuration::default(), "http://localhost/", 500),
+            500
+        );
+    }
+
+    #[test]
+    /// directories keep the shallowest depth they were reached at
+    fn record_depth_keeps_shallowest() {
+        let budget = LinkBudget::default();
+        let url = "http://budget-depth.test/extracted";
+
+        assert_eq!(budget.depth(url), 0);
+        budget.record_depth(url, 3);
+        budget.record_depth(&format!("{url}/"), 2);
+        budget.record_depth(url, 4);
+        assert_eq!(budget.depth(url), 2);
+
+        let config = Configuration {
+            extract_depth: 2,
+            ..Default::default()
+        };
+        assert!(budget.too_deep(&config, url));
+        assert!(!budget.too_deep(&Configuration::default(), url));
+    }
+}
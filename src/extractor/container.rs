@@ -1,20 +1,20 @@
 use super::*;
 use crate::{
-    client,
+    buckets,
+    client::{self, HostOverride, IpFamily},
     event_handlers::{
         Command::{AddError, AddToUsizeField},
         Handles,
     },
+    plugins,
     scan_manager::ScanOrder,
+    search,
     statistics::{
         StatError::Other,
         StatField::{LinksExtracted, TotalExpected},
     },
     url::FeroxUrl,
-    utils::{
-        logged_request, make_request, parse_url_with_raw_path, send_try_recursion_command,
-        should_deny_url,
-    },
+    utils::{logged_request, make_request, parse_url_with_raw_path, send_try_recursion_command},
     ExtractionResult, DEFAULT_METHOD,
 };
 use anyhow::{bail, Context, Result};
@@ -25,7 +25,7 @@ use std::{borrow::Cow, collections::HashSet};
 
 /// Wrapper around link extraction logic
 ///   - create a new Url object based on cli options/args
-///   - check if the new Url has already been seen/scanned -> None
+///   - check if the new Url has already been seen/scanned (this run or a resumed one) -> None
 ///   - make a request to the new Url ? -> Some(response) : None
 pub(super) async fn request_link(url: &str, handles: Arc<Handles>) -> Result<Response> {
     log::trace!("enter: request_link({})", url);
@@ -37,24 +37,14 @@ pub(super) async fn request_link(url: &str, handles: Arc<Handles>) -> Result<Res
 
     let scanned_urls = handles.ferox_scans()?;
 
-    if scanned_urls.get_scan_by_url(new_url.as_ref()).is_some() {
+    if scanned_urls.get_scan_by_url(new_url.as_ref()).is_some()
+        || !scanned_urls.seen.insert(new_url.as_ref())
+    {
         //we've seen the url before and don't need to scan again
         log::trace!("exit: request_link -> None");
         bail!("previously seen url");
     }
 
-    if (!handles.config.url_denylist.is_empty() || !handles.config.regex_denylist.is_empty())
-        && should_deny_url(&new_url, handles.clone())?
-    {
-        // can't allow a denied url to be requested
-        bail!(
-            "prevented request to {} due to {:?} || {:?}",
-            url,
-            handles.config.url_denylist,
-            handles.config.regex_denylist,
-        );
-    }
-
     // make the request and store the response
     let new_response = logged_request(&new_url, DEFAULT_METHOD, None, handles.clone()).await?;
 
@@ -110,6 +100,7 @@ impl<'a> Extractor<'a> {
             ExtractionTarget::ResponseBody => Ok(self.extract_from_body().await?),
             ExtractionTarget::RobotsTxt => Ok(self.extract_from_robots().await?),
             ExtractionTarget::DirectoryListing => Ok(self.extract_from_dir_listing().await?),
+            ExtractionTarget::WellKnown => Ok(self.extract_from_well_known().await?),
         }
     }
 
@@ -131,7 +122,8 @@ impl<'a> Extractor<'a> {
                     || absolute.host() != original_url.host()
                 {
                     // domains/ips are not the same, don't scan things that aren't part of the original
-                    // target url
+                    // target url, but do report links that point at cloud storage
+                    buckets::observe(&absolute, original_url.as_str(), &self.handles);
                     bail!("parsed url does not belong to original domain/host");
                 }
 
@@ -163,6 +155,32 @@ impl<'a> Extractor<'a> {
         Ok(())
     }
 
+    /// the given links that fit in what's left of the extraction budget (--extract-page-limit,
+    /// --extract-scan-limit, --extract-limit); links are taken in sorted order, so the same links
+    /// make the cut every time
+    pub fn within_budget(&self, links: HashSet<String>) -> HashSet<String> {
+        let allowed =
+            self.handles
+                .features
+                .link_budget
+                .take(&self.handles.config, &self.url, links.len());
+
+        if allowed >= links.len() {
+            return links;
+        }
+
+        log::debug!(
+            "extraction budget allows {} of {} links from {}",
+            allowed,
+            links.len(),
+            self.url
+        );
+
+        let mut sorted: Vec<_> = links.into_iter().collect();
+        sorted.sort();
+        sorted.into_iter().take(allowed).collect()
+    }
+
     /// given a set of links from a normal http body response, task the request handler to make
     /// the requests
     pub async fn request_links(
@@ -175,6 +193,30 @@ impl<'a> Extractor<'a> {
             return Ok(None);
         }
 
+        // --extract-depth; pages reached through too many extractions don't lead any further
+        if self
+            .handles
+            .features
+            .link_budget
+            .too_deep(&self.handles.config, &self.url)
+        {
+            log::debug!(
+                "{} is at --extract-depth, not requesting its {} links",
+                self.url,
+                links.len()
+            );
+            return Ok(None);
+        }
+
+        let links = self.within_budget(links);
+
+        if links.is_empty() {
+            return Ok(None);
+        }
+
+        // directories reached through these links are one extraction deeper than this scan
+        let next_depth = self.handles.features.link_budget.depth(&self.url) + 1;
+
         self.update_stats(links.len())?;
 
         // create clones/remove use of self of/from everything the async move block will need to function
@@ -189,7 +231,7 @@ impl<'a> Extractor<'a> {
         };
 
         let link_request_task = tokio::spawn(async move {
-            let producers = futures::stream::iter(links.into_iter())
+            let producers = futures::stream::iter(links)
                 .map(|link| {
                     // another clone to satisfy the async move block
                     let inner_clone = cloned_handles.clone();
@@ -211,16 +253,35 @@ impl<'a> Extractor<'a> {
                                     reqwest_response,
                                     &og_url,
                                     DEFAULT_METHOD,
-                                    c_handles.config.output_level,
+                                    &c_handles,
                                 )
                                 .await;
 
+                                search::report(&mut resp, &c_handles);
+                                if let Some(recorder) = &c_handles.features.session {
+                                    recorder.record(&resp);
+                                }
+
                                 // filter if necessary
-                                if c_handles
+                                if let Some(filter) = c_handles
                                     .filters
                                     .data
-                                    .should_filter_response(&resp, c_handles.stats.tx.clone())
+                                    .matching_filter(&resp, c_handles.stats.tx.clone())
+                                    .or_else(|| {
+                                        plugins::on_response(
+                                            &c_handles.features.plugins,
+                                            &mut resp,
+                                            c_handles.stats.tx.clone(),
+                                        )
+                                    })
                                 {
+                                    if !c_handles.config.quiet_stream.is_empty() {
+                                        resp.send_filtered_report(
+                                            c_handles.output.tx.clone(),
+                                            filter,
+                                        )
+                                        .unwrap_or_default();
+                                    }
                                     return;
                                 }
 
@@ -236,7 +297,10 @@ impl<'a> Extractor<'a> {
                                         resp.parse_extension(c_handles.clone()).unwrap();
                                     }
 
-                                    if let Err(e) = resp.send_report(c_handles.output.tx.clone()) {
+                                    if let Err(e) = resp.send_report(
+                                        c_handles.output.tx.clone(),
+                                        c_handles.config.exports_bodies(),
+                                    ) {
                                         log::warn!(
                                             "Could not send FeroxResponse to output handler: {}",
                                             e
@@ -263,6 +327,11 @@ impl<'a> Extractor<'a> {
                                         resp.set_url(&format!("{}/", resp.url()));
                                     }
 
+                                    c_handles
+                                        .features
+                                        .link_budget
+                                        .record_depth(resp.url().as_str(), next_depth);
+
                                     if c_handles.config.filter_status.is_empty() {
                                         // -C wasn't used, so -s is the only 'filter' left to account for
                                         if c_handles
@@ -363,7 +432,11 @@ impl<'a> Extractor<'a> {
     ///   - homepage/assets/img/
     ///   - homepage/assets/
     ///   - homepage/
-    fn add_all_sub_paths(&self, url_path: &str, links: &mut HashSet<String>) -> Result<()> {
+    pub(super) fn add_all_sub_paths(
+        &self,
+        url_path: &str,
+        links: &mut HashSet<String>,
+    ) -> Result<()> {
         log::trace!("enter: add_all_sub_paths({}, {:?})", url_path, links);
 
         for sub_path in self.get_sub_paths_from_path(url_path) {
@@ -478,12 +551,14 @@ impl<'a> Extractor<'a> {
             ExtractionTarget::ResponseBody | ExtractionTarget::DirectoryListing => {
                 self.response.unwrap().url().clone()
             }
-            ExtractionTarget::RobotsTxt => match parse_url_with_raw_path(&self.url) {
-                Ok(u) => u,
-                Err(e) => {
-                    bail!("Could not parse {}: {}", self.url, e);
+            ExtractionTarget::RobotsTxt | ExtractionTarget::WellKnown => {
+                match parse_url_with_raw_path(&self.url) {
+                    Ok(u) => u,
+                    Err(e) => {
+                        bail!("Could not parse {}: {}", self.url, e);
+                    }
                 }
-            },
+            }
         };
 
         let new_url = old_url
@@ -492,7 +567,8 @@ impl<'a> Extractor<'a> {
 
         if old_url.domain() != new_url.domain() || old_url.host() != new_url.host() {
             // domains/ips are not the same, don't scan things that aren't part of the original
-            // target url
+            // target url, but do report links that point at cloud storage
+            buckets::observe(&new_url, old_url.as_str(), &self.handles);
             log::debug!(
                 "Skipping {} because it's not part of the original target",
                 new_url
@@ -521,8 +597,16 @@ impl<'a> Extractor<'a> {
 
         let mut result: HashSet<_> = ExtractionResult::new();
 
-        // request
-        let response = self.make_extract_request("/robots.txt").await?;
+        // request; robots.txt is only a bonus, not being able to get it (i.e. --dont-scan denies
+        // the root of the host) shouldn't keep the scan itself from running
+        let response = match self.make_extract_request("/robots.txt").await {
+            Ok(response) => response,
+            Err(e) => {
+                log::warn!("Could not request robots.txt from {}: {}", self.url, e);
+                log::trace!("exit: extract_robots_txt -> {:?}", result);
+                return Ok(result);
+            }
+        };
         let body = response.text();
 
         for capture in self.robots_regex.captures_iter(body) {
@@ -547,6 +631,7 @@ impl<'a> Extractor<'a> {
     /// - checks the page to see if directory listing is enabled and sucks up all the links, if so
     /// - uses the linkfinder regex to grab links from embedded javascript/javascript files
     /// - extracts many different types of link sources from the html itself
+    /// - follows meta refresh/javascript redirects, as long as they stay on the same host
     pub(super) async fn extract_from_body(&self) -> Result<ExtractionResult> {
         log::trace!("enter: extract_from_body");
 
@@ -561,6 +646,15 @@ impl<'a> Extractor<'a> {
         self.extract_all_links_from_html_tags(resp_url, &mut result, &html);
         self.extract_all_links_from_javascript(body, resp_url, &mut result);
 
+        if let Some(destination) = response.soft_redirect() {
+            if self
+                .parse_url_and_add_subpaths(destination, resp_url, &mut result)
+                .is_err()
+            {
+                log::debug!("redirect didn't belong to the target domain/host: {destination}");
+            }
+        }
+
         log::trace!("exit: extract_from_body -> {:?}", result);
         Ok(result)
     }
@@ -635,11 +729,7 @@ impl<'a> Extractor<'a> {
             // all other user specified settings
             let follow_redirects = true;
 
-            let proxy = if self.handles.config.proxy.is_empty() {
-                None
-            } else {
-                Some(self.handles.config.proxy.as_str())
-            };
+            let proxy = self.handles.proxies.proxy();
 
             let server_certs = &self.handles.config.server_certs;
 
@@ -655,21 +745,38 @@ impl<'a> Extractor<'a> {
                 Some(self.handles.config.client_key.as_str())
             };
 
+            let ca_bundle = if self.handles.config.ca_bundle.is_empty() {
+                None
+            } else {
+                Some(self.handles.config.ca_bundle.as_str())
+            };
+
+            let host_override = HostOverride::new(
+                &self.handles.config.target_url,
+                &self.handles.config.host_header,
+                &self.handles.config.sni,
+            )?;
+
             client = client::initialize(
                 self.handles.config.timeout,
+                self.handles.config.connect_timeout,
                 &self.handles.config.user_agent,
                 follow_redirects,
                 self.handles.config.insecure,
                 &self.handles.config.headers,
                 proxy,
                 server_certs,
+                ca_bundle,
                 client_cert,
                 client_key,
+                host_override.as_ref(),
+                self.handles.features.doh.as_ref(),
+                IpFamily::from_flags(self.handles.config.ipv4, self.handles.config.ipv6),
             )?;
         }
 
         let client = if location != "/robots.txt" {
-            &self.handles.config.client
+            self.handles.proxies.client().1
         } else {
             &client
         };
@@ -678,24 +785,13 @@ impl<'a> Extractor<'a> {
         url.set_path(location); // overwrite existing path
 
         // purposefully not using logged_request here due to using the special client
-        let response = make_request(
-            client,
-            &url,
-            DEFAULT_METHOD,
-            None,
-            self.handles.config.output_level,
-            &self.handles.config,
-            self.handles.stats.tx.clone(),
-        )
-        .await?;
-
-        let ferox_response = FeroxResponse::from(
-            response,
-            &self.url,
-            DEFAULT_METHOD,
-            self.handles.config.output_level,
-        )
-        .await;
+        let response = make_request(client, &url, DEFAULT_METHOD, None, &[], &self.handles).await?;
+
+        let mut ferox_response =
+            FeroxResponse::from(response, &self.url, DEFAULT_METHOD, &self.handles).await;
+
+        search::report(&mut ferox_response, &self.handles);
+
         // note: don't call parse_extension here. If we call it here, it gets called on robots.txt
 
         log::trace!("exit: make_extract_request -> {}", ferox_response);
@@ -1,12 +1,16 @@
 //! extract links from html source and robots.txt
+mod budget;
 mod builder;
 mod container;
+mod well_known;
 #[cfg(test)]
 mod tests;
 
+pub use self::budget::LinkBudget;
 pub use self::builder::ExtractionTarget;
 pub use self::builder::ExtractorBuilder;
 pub use self::container::Extractor;
+pub use self::well_known::WellKnownProbes;
 
 use crate::response::FeroxResponse;
 use regex::Regex;
@@ -1,18 +1,16 @@
 use super::builder::{LINKFINDER_REGEX, ROBOTS_TXT_REGEX, URL_CHARS_REGEX};
 use super::container::request_link;
 use super::*;
-use crate::config::{Configuration, OutputLevel};
+use crate::config::Configuration;
 use crate::scan_manager::ScanOrder;
 use crate::{
-    event_handlers::Handles, scan_manager::FeroxScans, utils::make_request, Command, FeroxChannel,
-    DEFAULT_METHOD,
+    event_handlers::Handles, scan_manager::FeroxScans, utils::make_request, DEFAULT_METHOD,
 };
 use anyhow::Result;
 use httpmock::{Method::GET, MockServer};
 use lazy_static::lazy_static;
 use reqwest::{Client, StatusCode, Url};
 use std::collections::HashSet;
-use tokio::sync::mpsc;
 
 lazy_static! {
     /// Extractor for testing robots.txt
@@ -49,6 +47,9 @@ fn setup_extractor(target: ExtractionTarget, scanned_urls: Arc<FeroxScans>) -> E
         ExtractionTarget::DirectoryListing => builder
             .url("http://localhost")
             .target(ExtractionTarget::DirectoryListing),
+        ExtractionTarget::WellKnown => builder
+            .url("http://localhost")
+            .target(ExtractionTarget::WellKnown),
     };
 
     let config = Arc::new(Configuration::new().unwrap());
@@ -239,8 +240,6 @@ fn normalize_url_path_filters_queries_and_fragments() {
 /// the response will contain an absolute path to a domain that is not part of the scanned
 /// domain; expect an empty set returned
 async fn extractor_get_links_with_absolute_url_that_differs_from_target_domain() -> Result<()> {
-    let (tx_stats, _): FeroxChannel<Command> = mpsc::unbounded_channel();
-
     let srv = MockServer::start();
 
     let mock = srv.mock(|when, then| {
@@ -252,24 +251,14 @@ async fn extractor_get_links_with_absolute_url_that_differs_from_target_domain()
 
     let client = Client::new();
     let url = Url::parse(&srv.url("/some-path")).unwrap();
-    let config = Configuration::new().unwrap();
-
-    let response = make_request(
-        &client,
-        &url,
-        DEFAULT_METHOD,
-        None,
-        OutputLevel::Default,
-        &config,
-        tx_stats.clone(),
-    )
-    .await
-    .unwrap();
     let (handles, _rx) = Handles::for_testing(None, None);
 
     let handles = Arc::new(handles);
+    let response = make_request(&client, &url, DEFAULT_METHOD, None, &[], &handles)
+        .await
+        .unwrap();
     let ferox_response =
-        FeroxResponse::from(response, &srv.url(""), DEFAULT_METHOD, OutputLevel::Default).await;
+        FeroxResponse::from(response, &srv.url(""), DEFAULT_METHOD, &handles).await;
 
     let extractor = Extractor {
         links_regex: Regex::new(LINKFINDER_REGEX).unwrap(),
@@ -399,3 +388,42 @@ async fn request_link_bails_on_seen_url() -> Result<()> {
     assert_eq!(mock.hits(), 0); // function exits before requests can happen
     Ok(())
 }
+
+#[test]
+/// links to internationalized domains should be found, and compared against the target (and
+/// requested) in their punycode form
+fn extractor_handles_internationalized_domains() {
+    let regex = Regex::new(LINKFINDER_REGEX).unwrap();
+    let body = r#"<a href="https://пример.рф/login">"#;
+    let link = regex.captures(body).unwrap().get(1).unwrap().as_str();
+    assert_eq!(link, "https://пример.рф/login");
+
+    let mut response = FeroxResponse::default();
+    response.set_url("https://münchen.de/");
+
+    let extractor = Extractor {
+        links_regex: regex,
+        robots_regex: Regex::new(ROBOTS_TXT_REGEX).unwrap(),
+        url_regex: Regex::new(URL_CHARS_REGEX).unwrap(),
+        response: Some(&response),
+        url: String::new(),
+        target: ExtractionTarget::ResponseBody,
+        handles: Arc::new(Handles::for_testing(None, None).0),
+    };
+
+    let mut links = HashSet::<String>::new();
+
+    for link in [
+        "https://MÜNCHEN.de/admin",
+        "//xn--mnchen-3ya.de/login",
+        "https://пример.рф/login",
+    ] {
+        extractor
+            .add_link_to_set_of_links(link, &mut links)
+            .unwrap();
+    }
+
+    assert_eq!(links.len(), 2);
+    assert!(links.contains("https://xn--mnchen-3ya.de/admin"));
+    assert!(links.contains("https://xn--mnchen-3ya.de/login"));
+}
@@ -0,0 +1,221 @@
+//! RFC 8615 /.well-known/ resources (--well-known)
+//!
+//! each host's well-known resources are requested once. the ones that exist are reported like any
+//! other response, and the urls inside of them (endpoints from openid-configuration, policy pages
+//! from security.txt, app paths from apple-app-site-association, ...) are handed back as links,
+//! as long as they belong to the target's host
+use super::*;
+use crate::{
+    event_handlers::Command::AddToUsizeField, plugins, statistics::StatField::TotalExpected,
+    utils::parse_url_with_raw_path, ExtractionResult,
+};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::{collections::HashSet, sync::Mutex};
+
+lazy_static! {
+    /// absolute urls inside of resources that aren't json
+    static ref URL_REGEX: Regex = Regex::new(r#"https?://[^\s<>"']+"#).unwrap();
+}
+
+/// Origins (scheme://host:port) whose well-known resources were already requested
+/// (--well-known)
+#[derive(Debug, Default)]
+pub struct WellKnownProbes {
+    /// origins probed so far
+    probed: Mutex<HashSet<String>>,
+}
+
+/// resources requested under /.well-known/
+pub(super) const WELL_KNOWN: [&str; 16] = [
+    "security.txt",
+    "openid-configuration",
+    "oauth-authorization-server",
+    "oauth-protected-resource",
+    "jwks.json",
+    "apple-app-site-association",
+    "assetlinks.json",
+    "change-password",
+    "host-meta",
+    "host-meta.json",
+    "nodeinfo",
+    "mta-sts.txt",
+    "gpc.json",
+    "ai-plugin.json",
+    "matrix/server",
+    "matrix/client",
+];
+
+/// every string value in the given json document
+fn json_strings(value: &Value, strings: &mut Vec<String>) {
+    match value {
+        Value::String(string) => strings.push(string.to_owned()),
+        Value::Array(values) => values.iter().for_each(|value| json_strings(value, strings)),
+        Value::Object(map) => map.values().for_each(|value| json_strings(value, strings)),
+        _ => {}
+    }
+}
+
+/// urls and absolute paths found in a well-known resource; json documents are walked, anything
+/// else is searched for absolute urls
+///
+/// apple-app-site-association style patterns (`NOT /help/*`) are cut down to the part before
+/// the first wildcard
+pub(super) fn candidates(body: &str) -> Vec<String> {
+    let strings = match serde_json::from_str::<Value>(body) {
+        Ok(json) => {
+            let mut strings = Vec::new();
+            json_strings(&json, &mut strings);
+            strings
+        }
+        Err(_) => URL_REGEX
+            .find_iter(body)
+            .map(|found| found.as_str().to_string())
+            .collect(),
+    };
+
+    strings
+        .into_iter()
+        .map(|string| {
+            let string = string.trim().trim_start_matches("NOT ");
+            let end = string.find(['*', '?', ' ']).unwrap_or(string.len());
+            string[..end].to_string()
+        })
+        .filter(|string| {
+            string.starts_with("http://")
+                || string.starts_with("https://")
+                || (string.starts_with('/') && !string.starts_with("//") && string.len() > 1)
+        })
+        .collect()
+}
+
+/// Extractor implementation, continued
+impl<'a> Extractor<'a> {
+    /// Entry point to request the /.well-known/ resources of the target's host, once per host
+    ///
+    /// resources that exist are reported, unless filtered; the links returned are the urls and
+    /// paths inside of them that belong to the target's host
+    pub(super) async fn extract_from_well_known(&self) -> Result<ExtractionResult> {
+        log::trace!("enter: extract_from_well_known");
+
+        let mut result = ExtractionResult::new();
+
+        let target = parse_url_with_raw_path(&self.url)?;
+        let origin = target.origin().ascii_serialization();
+
+        let first_visit = self
+            .handles
+            .features
+            .well_known
+            .probed
+            .lock()
+            .map(|mut probed| probed.insert(origin))
+            .unwrap_or_default();
+
+        if !first_visit {
+            log::trace!("exit: extract_from_well_known -> already probed");
+            return Ok(result);
+        }
+
+        self.handles
+            .stats
+            .send(AddToUsizeField(TotalExpected, WELL_KNOWN.len()))?;
+
+        let locations: Vec<_> = WELL_KNOWN
+            .iter()
+            .map(|name| format!("/.well-known/{name}"))
+            .collect();
+
+        let responses = futures::future::join_all(
+            locations
+                .iter()
+                .map(|location| self.make_extract_request(location)),
+        )
+        .await;
+
+        for mut response in responses.into_iter().flatten() {
+            if response.status() == &StatusCode::NOT_FOUND {
+                continue;
+            }
+
+            if response.status().is_success() {
+                for candidate in candidates(response.text()) {
+                    let path = match parse_url_with_raw_path(&candidate) {
+                        // an absolute url, only those on the target's host are followed
+                        Ok(url) if url.host() == target.host() && url.port() == target.port() => {
+                            url.path().to_string()
+                        }
+                        Ok(_) => continue,
+                        // an absolute path
+                        Err(_) => candidate,
+                    };
+
+                    if self.add_all_sub_paths(&path, &mut result).is_err() {
+                        log::warn!("could not add sub-paths from {} to {:?}", path, result);
+                    }
+                }
+            }
+
+            if let Some(recorder) = &self.handles.features.session {
+                recorder.record(&response);
+            }
+
+            let tx_stats = self.handles.stats.tx.clone();
+
+            if self
+                .handles
+                .filters
+                .data
+                .matching_filter(&response, tx_stats.clone())
+                .or_else(|| {
+                    plugins::on_response(&self.handles.features.plugins, &mut response, tx_stats)
+                })
+                .is_some()
+            {
+                continue;
+            }
+
+            if let Err(e) = response.send_report(
+                self.handles.output.tx.clone(),
+                self.handles.config.exports_bodies(),
+            ) {
+                log::warn!("Could not send FeroxResponse to output handler: {}", e);
+            }
+        }
+
+        log::trace!("exit: extract_from_well_known -> {:?}", result);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// json documents are walked for urls and paths, anything else is searched for urls
+    fn candidates_from_json_and_text() {
+        let openid = r#"{"issuer":"https://localhost","token_endpoint":"https://localhost/oauth/token","scopes_supported":["openid"],"nested":{"paths":["/app/*","NOT /help/*"]}}"#;
+
+        let mut found = candidates(openid);
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                "/app/",
+                "/help/",
+                "https://localhost",
+                "https://localhost/oauth/token"
+            ]
+        );
+
+        let security =
+            "Contact: mailto:security@localhost\nPolicy: https://localhost/security/policy.html\n";
+        assert_eq!(
+            candidates(security),
+            ["https://localhost/security/policy.html"]
+        );
+    }
+}
@@ -0,0 +1,509 @@
+//! spreading a scan across every address of the target (--fan-out-ips)
+//!
+//! CDNs and load balanced origins hand out several addresses for the same name, and the servers
+//! behind them don't always agree with one another (a stale node, an origin that's reachable
+//! directly, a pool member that missed a deploy). each target's host is resolved once before
+//! scanning starts, and the requests addressed to it take turns on its addresses; every
+//! connection is pinned to one address while the Host header and TLS SNI stay the same
+//!
+//! each finding is then requested from every one of the addresses, and a finding whose status
+//! or size isn't the same everywhere is reported as a delta, printed alongside the responses and
+//! written to --output (as `{"type":"ip-delta",...}` with --json). no more than MAX_COMPARISONS
+//! findings are compared per run
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use anyhow::{Context, Result};
+use console::style;
+use futures::future::join_all;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::{
+    client::IpFamily,
+    config::OutputLevel,
+    event_handlers::{
+        Command::{self, AddToUsizeField},
+        Handles,
+    },
+    response::FeroxResponse,
+    send_command,
+    statistics::StatField::TotalExpected,
+    traits::FeroxSerialize,
+    utils::{fmt_err, make_request},
+};
+
+/// number of findings compared across addresses per run
+const MAX_COMPARISONS: usize = 250;
+
+/// Fanned out hosts and the comparisons made so far (--fan-out-ips)
+#[derive(Debug, Default)]
+pub struct FanOut {
+    /// addresses of each fanned out host, by `host:port`
+    pools: RwLock<HashMap<String, Arc<Pool>>>,
+
+    /// findings that were already compared, by method and url
+    checked: Mutex<HashSet<String>>,
+
+    /// comparisons that are still running
+    pending: Mutex<Vec<JoinHandle<()>>>,
+}
+
+/// implementation of FanOut
+impl FanOut {
+    /// the pool of the given url's host, if it was fanned out
+    fn pool_for(&self, url: &Url) -> Option<Arc<Pool>> {
+        let key = pool_key(url)?;
+        self.pools.read().ok()?.get(&key).cloned()
+    }
+
+    /// the client the next request to the given url should go through, when its host was
+    /// fanned out; the addresses take turns
+    pub(crate) fn client_for(&self, url: &Url) -> Option<Client> {
+        self.pool_for(url).map(|pool| pool.next_client().clone())
+    }
+}
+
+/// Every address of a single host, each with a client pinned to it
+#[derive(Debug)]
+struct Pool {
+    /// addresses in the order they were resolved, along with their clients
+    members: Vec<(IpAddr, Client)>,
+
+    /// member the next request goes to
+    next: AtomicUsize,
+}
+
+/// implementation of Pool
+impl Pool {
+    /// the client of the member whose turn it is
+    fn next_client(&self) -> &Client {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.members.len();
+        &self.members[index].1
+    }
+}
+
+/// What a single address answered when a finding was requested from it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpResult {
+    /// address the request was sent to
+    pub ip: IpAddr,
+
+    /// status code it answered with, `None` when the request failed
+    pub status: Option<u16>,
+
+    /// size of its response body, in bytes
+    pub content_length: usize,
+}
+
+/// A finding that isn't answered the same way by every address of its host
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpDelta {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"ip-delta"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// url of the finding
+    pub url: String,
+
+    /// http method used
+    pub method: String,
+
+    /// what each address answered, in the order the host resolved to them
+    pub results: Vec<IpResult>,
+}
+
+/// FeroxSerialize implementation for IpDelta
+impl FeroxSerialize for IpDelta {
+    /// one line summary, i.e.
+    /// `IPD GET http://localhost/login [127.0.0.1: 200 12c, 127.0.0.2: 404 9c]`
+    fn as_str(&self) -> String {
+        let results: Vec<_> = self
+            .results
+            .iter()
+            .map(|result| match result.status {
+                Some(status) => format!("{}: {} {}c", result.ip, status, result.content_length),
+                None => format!("{}: error", result.ip),
+            })
+            .collect();
+
+        format!(
+            "{} {:>5} {} {}\n",
+            style("IPD").bright().cyan(),
+            self.method,
+            style(&self.url).yellow(),
+            style(format!("[{}]", results.join(", "))).dim()
+        )
+    }
+
+    /// Create an NDJSON representation of the delta
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the per-ip results of {} to JSON",
+                self.url
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// `host:port` of the given url, which is what pools are kept by
+fn pool_key(url: &Url) -> Option<String> {
+    Some(format!(
+        "{}:{}",
+        url.host_str()?,
+        url.port_or_known_default()?
+    ))
+}
+
+/// every address of the given host, of the family allowed by -4/-6, without repeats; looked up
+/// through --doh when it's used
+pub(crate) async fn resolve(host: &str, port: u16, handles: &Handles) -> Result<Vec<IpAddr>> {
+    let family = IpFamily::from_flags(handles.config.ipv4, handles.config.ipv6);
+
+    let found = match &handles.features.doh {
+        None => tokio::net::lookup_host((host, port))
+            .await
+            .with_context(|| format!("Could not resolve {host}"))?
+            .map(|addr| addr.ip())
+            .collect::<Vec<_>>(),
+        // the resolver the clients use, so the answer is cached for them too
+        Some(resolver) => resolver
+            .lookup(host)
+            .await
+            .with_context(|| format!("Could not resolve {host} through --doh"))?,
+    };
+
+    let mut addrs = Vec::new();
+
+    for ip in found {
+        if family.is_none_or(|family| family.contains(&ip)) && !addrs.contains(&ip) {
+            addrs.push(ip);
+        }
+    }
+
+    Ok(addrs)
+}
+
+/// resolve the host of each target and set up a client for every one of its addresses; does
+/// nothing when --fan-out-ips wasn't used. a host with a single address is scanned the usual way
+pub async fn initialize(targets: &[String], handles: Arc<Handles>) -> Result<()> {
+    let config = &handles.config;
+
+    if !config.fan_out_ips {
+        return Ok(());
+    }
+
+    if !config.proxy.is_empty() {
+        // the proxy picks the address it connects to, there's nothing to pin
+        log::warn!("--fan-out-ips has no effect when requests go through --proxy");
+        return Ok(());
+    }
+
+    for target in targets {
+        let Ok(url) = Url::parse(target) else {
+            continue;
+        };
+
+        let (Some(host), Some(port), Some(key)) =
+            (url.host_str(), url.port_or_known_default(), pool_key(&url))
+        else {
+            continue;
+        };
+
+        if handles
+            .features
+            .fanout
+            .pools
+            .read()
+            .is_ok_and(|pools| pools.contains_key(&key))
+        {
+            continue;
+        }
+
+        if host.trim_matches(['[', ']']).parse::<IpAddr>().is_ok() {
+            log::warn!("{} is an address already, --fan-out-ips leaves it be", host);
+            continue;
+        }
+
+        let addrs = resolve(host, port, &handles)
+            .await
+            .with_context(|| fmt_err(&format!("Could not fan out {target}")))?;
+
+        if addrs.len() < 2 {
+            log::warn!(
+                "{} only resolves to {:?}, --fan-out-ips has nothing to spread it across",
+                host,
+                addrs
+            );
+            continue;
+        }
+
+        log::info!("fanning {} out across {:?}", host, addrs);
+
+        let members = addrs
+            .into_iter()
+            .map(|ip| Ok((ip, config.client_pinned_to(&url, ip)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let pool = Pool {
+            members,
+            next: AtomicUsize::new(0),
+        };
+
+        if let Ok(mut pools) = handles.features.fanout.pools.write() {
+            pools.insert(key, Arc::new(pool));
+        }
+    }
+
+    Ok(())
+}
+
+/// whether the given results don't all agree on status and size
+fn differ(results: &[IpResult]) -> bool {
+    results.windows(2).any(|pair| {
+        pair[0].status != pair[1].status || pair[0].content_length != pair[1].content_length
+    })
+}
+
+/// request the finding from every address of its host, reporting it when they don't agree
+async fn compare(url: Url, method: String, pool: Arc<Pool>, handles: Arc<Handles>) {
+    log::trace!("enter: compare({}, {}, {:?})", url, method, handles);
+
+    // need to manually adjust stats
+    send_command!(
+        handles.stats.tx,
+        AddToUsizeField(TotalExpected, pool.members.len())
+    );
+
+    let data = if handles.config.data.is_empty() {
+        None
+    } else {
+        Some(handles.config.data.as_slice())
+    };
+
+    let requests = pool.members.iter().map(|(ip, client)| {
+        let url = &url;
+        let method = &method;
+        let handles = &handles;
+
+        async move {
+            let response = make_request(client, url, method, data, &[], handles).await;
+
+            match response {
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    let content_length = response
+                        .bytes()
+                        .await
+                        .map(|body| body.len())
+                        .unwrap_or_default();
+
+                    IpResult {
+                        ip: *ip,
+                        status: Some(status),
+                        content_length,
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Could not request {} from {}: {}", url, ip, e);
+
+                    IpResult {
+                        ip: *ip,
+                        status: None,
+                        content_length: 0,
+                    }
+                }
+            }
+        }
+    });
+
+    let results = join_all(requests).await;
+
+    if differ(&results) {
+        let delta = IpDelta {
+            kind: String::from("ip-delta"),
+            url: url.to_string(),
+            method,
+            results,
+        };
+
+        log::info!("{:?}", delta);
+
+        handles
+            .output
+            .send(Command::ReportIpDelta(Box::new(delta)))
+            .unwrap_or_default();
+    }
+
+    log::trace!("exit: compare");
+}
+
+/// compare the given finding across every address of its host in the background; does nothing
+/// when --fan-out-ips wasn't used, or the finding's host wasn't fanned out
+pub fn on_finding(response: &FeroxResponse, handles: Arc<Handles>) {
+    if !handles.config.fan_out_ips {
+        return;
+    }
+
+    let Some(pool) = handles.features.fanout.pool_for(response.url()) else {
+        return;
+    };
+
+    let Ok(mut checked) = handles.features.fanout.checked.lock() else {
+        return;
+    };
+
+    let key = format!("{} {}", response.method(), response.url());
+
+    if checked.contains(&key) {
+        return;
+    }
+
+    if checked.len() >= MAX_COMPARISONS {
+        log::debug!("comparison limit reached, not comparing {}", response.url());
+        return;
+    }
+
+    checked.insert(key);
+    drop(checked);
+
+    let task = tokio::spawn(compare(
+        response.url().clone(),
+        response.method().to_string(),
+        pool,
+        handles.clone(),
+    ));
+
+    if let Ok(mut pending) = handles.features.fanout.pending.lock() {
+        pending.push(task);
+    }
+}
+
+/// wait for every queued comparison to finish, so their findings make it out before the output
+/// handler is closed
+pub async fn finish(handles: &Handles) {
+    loop {
+        let tasks: Vec<_> = match handles.features.fanout.pending.lock() {
+            Ok(mut pending) => pending.drain(..).collect(),
+            Err(_) => return,
+        };
+
+        if tasks.is_empty() {
+            return;
+        }
+
+        for task in tasks {
+            task.await.unwrap_or_default();
+        }
+    }
+}
+
+/// whether per-ip deltas are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// result of a request to the given address
+    fn result(ip: &str, status: Option<u16>, content_length: usize) -> IpResult {
+        IpResult {
+            ip: ip.parse().unwrap(),
+            status,
+            content_length,
+        }
+    }
+
+    #[test]
+    /// pools are kept by host and port, with the scheme's port filled in
+    fn pool_key_includes_known_port() {
+        let key = |url: &str| pool_key(&Url::parse(url).unwrap());
+
+        assert_eq!(
+            key("https://example.com/admin"),
+            Some("example.com:443".into())
+        );
+        assert_eq!(
+            key("http://example.com:8080/"),
+            Some("example.com:8080".into())
+        );
+        assert_eq!(key("http://Example.COM/"), Some("example.com:80".into()));
+    }
+
+    #[test]
+    /// the members of a pool take turns, starting over once each one had one
+    fn pool_hands_out_clients_in_turn() {
+        let members: Vec<_> = ["127.0.0.1", "127.0.0.2"]
+            .iter()
+            .map(|ip| (ip.parse().unwrap(), Client::new()))
+            .collect();
+
+        let pool = Pool {
+            members,
+            next: AtomicUsize::new(0),
+        };
+
+        for _ in 0..5 {
+            pool.next_client();
+        }
+
+        assert_eq!(pool.next.load(Ordering::Relaxed) % pool.members.len(), 1);
+    }
+
+    #[test]
+    /// only a difference in status or size makes a delta, and a failed request is a difference
+    fn differ_compares_status_and_size() {
+        let same = [
+            result("127.0.0.1", Some(200), 12),
+            result("127.0.0.2", Some(200), 12),
+        ];
+        assert!(!differ(&same));
+
+        let status = [
+            result("127.0.0.1", Some(200), 12),
+            result("127.0.0.2", Some(403), 12),
+        ];
+        assert!(differ(&status));
+
+        let size = [
+            result("127.0.0.1", Some(200), 12),
+            result("127.0.0.2", Some(200), 11),
+        ];
+        assert!(differ(&size));
+
+        let failed = [
+            result("127.0.0.1", Some(200), 0),
+            result("127.0.0.2", None, 0),
+        ];
+        assert!(differ(&failed));
+    }
+
+    #[test]
+    /// a delta is written as a single json line with each address's result
+    fn ip_delta_as_json() {
+        let delta = IpDelta {
+            kind: String::from("ip-delta"),
+            url: String::from("http://localhost/login"),
+            method: String::from("GET"),
+            results: vec![result("127.0.0.1", Some(200), 12), result("::1", None, 0)],
+        };
+
+        assert_eq!(
+            delta.as_json().unwrap(),
+            "{\"type\":\"ip-delta\",\"url\":\"http://localhost/login\",\"method\":\"GET\",\"results\":[{\"ip\":\"127.0.0.1\",\"status\":200,\"content_length\":12},{\"ip\":\"::1\",\"status\":null,\"content_length\":0}]}\n"
+        );
+        assert!(delta.as_str().contains("::1: error"));
+    }
+}
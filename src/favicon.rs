@@ -0,0 +1,351 @@
+//! favicon fingerprints of scanned hosts (--favicon)
+//!
+//! each host's /favicon.ico is fetched once and hashed the way Shodan does it (mmh3 of the
+//! base64 encoded icon, `http.favicon.hash:` in a Shodan query). hashes are looked up in a small
+//! bundled table of well known technologies, along with any given via --favicon-db. the result is
+//! printed alongside the responses and written to --output (as `{"type":"favicon",...}` with
+//! --json)
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use console::style;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Configuration, OutputLevel},
+    event_handlers::{Command, Handles},
+    traits::FeroxSerialize,
+    utils::{fmt_err, logged_request},
+    DEFAULT_METHOD,
+};
+
+/// favicon hashes of well known technologies
+const KNOWN_FAVICONS: [(i32, &str); 9] = [
+    (81586312, "Jenkins"),
+    (116323821, "Spring Boot"),
+    (-297069493, "Apache Tomcat"),
+    (-335242539, "F5 BIG-IP"),
+    (1278323681, "GitLab"),
+    (-305179312, "Atlassian Confluence"),
+    (1485257654, "SonarQube"),
+    (999357577, "Hikvision"),
+    (2123863676, "Grafana"),
+];
+
+/// number of base64 characters per line, as written by python's base64.encodebytes
+const BASE64_LINE_LENGTH: usize = 76;
+
+/// Hosts fingerprinted so far, along with the hashes given via --favicon-db
+#[derive(Debug, Default)]
+pub struct Favicons {
+    /// origins (scheme://host:port) whose favicon was already fetched
+    fetched: Mutex<HashSet<String>>,
+
+    /// hashes from --favicon-db, checked before the bundled ones
+    custom: HashMap<i32, String>,
+}
+
+/// Favicon fingerprint of a single host
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct FaviconInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"favicon"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// url the favicon was fetched from
+    pub url: String,
+
+    /// Shodan style mmh3 hash of the favicon
+    pub hash: i32,
+
+    /// size of the favicon, in bytes
+    pub size: usize,
+
+    /// technology the hash belongs to, when it's a known one
+    pub technology: Option<String>,
+}
+
+/// FeroxSerialize implementation for FaviconInfo
+impl FeroxSerialize for FaviconInfo {
+    /// one line summary, i.e. `FAV 81586312 http://localhost/favicon.ico [Jenkins]`
+    fn as_str(&self) -> String {
+        let technology = self
+            .technology
+            .as_ref()
+            .map(|name| format!(" {}", style(format!("[{name}]")).bright().yellow()))
+            .unwrap_or_default();
+
+        format!(
+            "{} {} {}{}\n",
+            style("FAV").bright().cyan(),
+            self.hash,
+            self.url,
+            technology
+        )
+    }
+
+    /// Create an NDJSON representation of the favicon fingerprint
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the favicon of {} to JSON",
+                self.url
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// 32 bit murmur3 (x86 variant), the hash behind python's mmh3.hash
+fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut hash = seed;
+    let chunks = data.chunks_exact(4);
+    let tail = chunks.remainder();
+
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+        hash ^= k;
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .enumerate()
+            .fold(0u32, |k, (i, byte)| k ^ (u32::from(*byte) << (8 * i)));
+
+        hash ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+
+    hash
+}
+
+/// Shodan's favicon hash: mmh3 of the icon, base64 encoded with a newline after every 76
+/// characters and at the end (python's base64.encodebytes)
+pub fn favicon_hash(icon: &[u8]) -> i32 {
+    let encoded = STANDARD.encode(icon);
+
+    let mut wrapped = String::with_capacity(encoded.len() + encoded.len() / BASE64_LINE_LENGTH + 1);
+
+    for line in encoded.as_bytes().chunks(BASE64_LINE_LENGTH) {
+        // base64 output is always ascii
+        wrapped.push_str(std::str::from_utf8(line).unwrap_or_default());
+        wrapped.push('\n');
+    }
+
+    murmur3_32(wrapped.as_bytes(), 0) as i32
+}
+
+/// implementation of Favicons
+impl Favicons {
+    /// technology the given hash belongs to, if it's known
+    pub fn lookup(&self, hash: i32) -> Option<String> {
+        if let Some(name) = self.custom.get(&hash) {
+            return Some(name.clone());
+        }
+
+        KNOWN_FAVICONS
+            .iter()
+            .find(|(known, _)| *known == hash)
+            .map(|(_, name)| name.to_string())
+    }
+}
+
+/// parse a --favicon-db file; one `HASH,TECHNOLOGY` per line, blank lines and lines starting
+/// with # are ignored
+fn parse_favicon_db(contents: &str) -> Result<HashMap<i32, String>> {
+    let mut favicons = HashMap::new();
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (hash, name) = line
+            .split_once(',')
+            .with_context(|| fmt_err(&format!("line {}: expected HASH,TECHNOLOGY", number + 1)))?;
+
+        let hash = hash.trim().parse::<i32>().with_context(|| {
+            fmt_err(&format!(
+                "line {}: {} is not a favicon hash",
+                number + 1,
+                hash
+            ))
+        })?;
+
+        favicons.insert(hash, name.trim().to_string());
+    }
+
+    Ok(favicons)
+}
+
+/// load the hashes given via --favicon-db
+pub fn initialize(config: &Configuration) -> Result<Favicons> {
+    if config.favicon_db.is_empty() {
+        return Ok(Favicons::default());
+    }
+
+    let contents = fs::read_to_string(&config.favicon_db)
+        .with_context(|| fmt_err(&format!("Could not read {}", config.favicon_db)))?;
+
+    let favicons = parse_favicon_db(&contents)
+        .with_context(|| fmt_err(&format!("Could not parse {}", config.favicon_db)))?;
+
+    log::info!(
+        "loaded {} favicon hashes from {}",
+        favicons.len(),
+        config.favicon_db
+    );
+
+    Ok(Favicons {
+        custom: favicons,
+        ..Default::default()
+    })
+}
+
+/// fetch and fingerprint the favicon of the given target's host, once per host; does nothing
+/// unless --favicon is used
+pub async fn fingerprint_target(target: String, handles: Arc<Handles>) {
+    if !handles.config.favicon {
+        return;
+    }
+
+    let Ok(url) = Url::parse(&target) else {
+        return;
+    };
+
+    let origin = url.origin().ascii_serialization();
+
+    let favicons = &handles.features.favicons;
+
+    let first_visit = favicons
+        .fetched
+        .lock()
+        .map(|mut fetched| fetched.insert(origin.clone()))
+        .unwrap_or_default();
+
+    if !first_visit {
+        return;
+    }
+
+    let Ok(favicon_url) = Url::parse(&format!("{origin}/favicon.ico")) else {
+        return;
+    };
+
+    let response = match logged_request(&favicon_url, DEFAULT_METHOD, None, handles.clone()).await {
+        Ok(response) if response.status() == StatusCode::OK => response,
+        Ok(response) => {
+            log::debug!("{} responded with {}", favicon_url, response.status());
+            return;
+        }
+        Err(e) => {
+            log::warn!("Could not fetch {}: {}", favicon_url, e);
+            return;
+        }
+    };
+
+    let icon = match response.bytes().await {
+        Ok(icon) if !icon.is_empty() => icon,
+        _ => return,
+    };
+
+    let hash = favicon_hash(&icon);
+
+    let info = FaviconInfo {
+        kind: String::from("favicon"),
+        url: favicon_url.to_string(),
+        hash,
+        size: icon.len(),
+        technology: favicons.lookup(hash),
+    };
+
+    log::info!("{:?}", info);
+
+    handles
+        .output
+        .send(Command::ReportFavicon(Box::new(info)))
+        .unwrap_or_default();
+}
+
+/// whether favicon fingerprints are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// known murmur3 values, as returned by python's mmh3.hash
+    fn murmur3_matches_mmh3() {
+        assert_eq!(murmur3_32(b"", 0) as i32, 0);
+        assert_eq!(murmur3_32(b"foo", 0) as i32, -156908512);
+    }
+
+    #[test]
+    /// the icon should be base64 encoded in 76 character lines, each ending with a newline
+    fn favicon_hash_wraps_base64_lines() {
+        let icon = vec![0u8; 100];
+
+        let encoded = STANDARD.encode(&icon);
+        let expected = format!("{}\n{}\n", &encoded[..76], &encoded[76..]);
+
+        assert_eq!(
+            favicon_hash(&icon),
+            murmur3_32(expected.as_bytes(), 0) as i32
+        );
+    }
+
+    #[test]
+    /// --favicon-db entries are parsed, comments and blank lines skipped, bad lines reported
+    fn parse_favicon_db_reads_hashes() {
+        let favicons =
+            parse_favicon_db("# hash,name\n\n-1234, Some Appliance\n81586312,Jenkins\n").unwrap();
+
+        assert_eq!(favicons[&-1234], "Some Appliance");
+        assert_eq!(favicons[&81586312], "Jenkins");
+
+        assert!(parse_favicon_db("not a hash,thing").is_err());
+        assert!(parse_favicon_db("12345").is_err());
+    }
+
+    #[test]
+    /// bundled hashes are found, unknown ones aren't; --favicon-db hashes come first
+    fn lookup_finds_bundled_hashes() {
+        let favicons = Favicons::default();
+        assert_eq!(favicons.lookup(81586312).unwrap(), "Jenkins");
+        assert!(favicons.lookup(42).is_none());
+
+        let favicons = Favicons {
+            custom: parse_favicon_db("81586312,Not Jenkins\n42,Answers").unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(favicons.lookup(81586312).unwrap(), "Not Jenkins");
+        assert_eq!(favicons.lookup(42).unwrap(), "Answers");
+    }
+}
@@ -0,0 +1,219 @@
+//! state kept by the optional features (--traffic-log, --plugin, --baseline, etc...), set up
+//! from the Configuration before anything is requested and shared through Handles; nothing here
+//! outlives the Handles it belongs to
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::{
+    archives::ArchivePeeks,
+    bandwidth::{self, Bandwidth},
+    baseline::{self, Baseline},
+    buckets::BucketReports,
+    bypass::{self, BypassAttempts},
+    categories::{self, Categories},
+    client::RequestIds,
+    config::Configuration,
+    diff::BaselineWords,
+    doh::DohResolver,
+    evasion::{self, Transform},
+    exit_policy::{self, ExitCondition},
+    extractor::{LinkBudget, WellKnownProbes},
+    fanout::FanOut,
+    favicon::{self, Favicons},
+    pacing::{self, Pacing},
+    plugins::{self, Plugin},
+    policy_audit::{self, PolicyAudit},
+    progress::RateLimits,
+    pruning::Pruning,
+    raw::Destinations,
+    rules::{self, Rules},
+    scanner::GlobalRateLimit,
+    scheme::SchemeDetections,
+    screenshots::{self, Screenshotter},
+    search::{self, Search},
+    server_watch::ServerWatch,
+    session::{self, Recorder},
+    signatures::{self, Signatures},
+    stability::StabilityChecks,
+    statistics::ErrorWeights,
+    summary::Summary,
+    template::Templating,
+    tls::TlsInspections,
+    traffic::{self, TrafficLog},
+    variables::HeaderVariables,
+    vcs::VcsChecks,
+};
+
+/// Container for the state of every optional feature; anything that's `None`/empty is turned off
+#[derive(Debug, Default)]
+pub struct Features {
+    /// --traffic-log; every request and its response's metadata
+    pub traffic: Option<TrafficLog>,
+
+    /// --policy-audit; every auto-tune/bail/requeue decision
+    pub policy_audit: Option<PolicyAudit>,
+
+    /// --record; every response, right before the filters see it
+    pub session: Option<Recorder>,
+
+    /// --plugin; loaded scripts, in the order given
+    pub plugins: Vec<Plugin>,
+
+    /// --baseline; responses from the previous scan that carried a validator
+    pub baseline: Baseline,
+
+    /// 404 signatures learned by the heuristics, including those reused from a previous run
+    /// (--reuse-signatures, --resume-from)
+    pub signatures: Signatures,
+
+    /// --categorize/--category-rule; categories responses are tagged with
+    pub categories: Categories,
+
+    /// --rules; tags, severities, and alerts for findings
+    pub rules: Rules,
+
+    /// --exit-code/--fail-on-rule; exit code of each condition, in the order they're checked
+    pub exit_policy: Vec<(ExitCondition, i32)>,
+
+    /// --screenshots; queue of findings handed to the browser workers
+    pub screenshots: Option<Screenshotter>,
+
+    /// --favicon; hosts fingerprinted so far and the hashes given via --favicon-db
+    pub favicons: Favicons,
+
+    /// --evasion; transforms applied to each word while its url is formatted
+    pub evasion: Vec<Transform>,
+
+    /// --bypass-403; urls attempted so far and the attempts still running
+    pub bypass: BypassAttempts,
+
+    /// FUZZ keywords in use; filled in once the wordlist is expanded into their combinations
+    pub template: Templating,
+
+    /// --server-alerts; last seen Server/X-Powered-By values of every host
+    pub server_watch: ServerWatch,
+
+    /// --search-regex/--entropy-secrets; patterns bodies are searched for and the secrets
+    /// reported so far
+    pub search: Search,
+
+    /// --policy-error-weight; how much each category of error counts toward
+    /// --auto-tune/--auto-bail
+    pub error_weights: ErrorWeights,
+
+    /// --rate-limit-global; limiter shared by every scan, on top of their own
+    pub global_rate_limit: GlobalRateLimit,
+
+    /// rate limit of every scan and --rate-limit-global, shown on the overall progress bar
+    pub rate_limits: Arc<RateLimits>,
+
+    /// --bandwidth-limit; bytes received across every scan
+    pub bandwidth: Bandwidth,
+
+    /// --pacing; burst/quiet cycle shared by every scan
+    pub pacing: Pacing,
+
+    /// --extract-limit, etc...; links queued by the extractor and how deep each directory is
+    pub link_budget: LinkBudget,
+
+    /// --well-known; origins whose /.well-known/ resources were requested
+    pub well_known: WellKnownProbes,
+
+    /// cloud storage buckets found in extracted links and reported so far
+    pub buckets: BucketReports,
+
+    /// --baseline-url; words of its body, once it's been fetched
+    pub baseline_words: BaselineWords,
+
+    /// --summary; best findings reported so far
+    pub summary: Summary,
+
+    /// --request-id; id of this run and the number of requests tagged with it
+    pub request_ids: RequestIds,
+
+    /// https hosts whose TLS details were reported so far
+    pub tls: TlsInspections,
+
+    /// schemes detected for targets given without one
+    pub scheme: SchemeDetections,
+
+    /// --doh; resolver every client looks host names up with, and the answers it cached
+    pub doh: Option<DohResolver>,
+
+    /// --fan-out-ips; addresses of each fanned out host and the findings compared so far
+    pub fanout: FanOut,
+
+    /// --stability-check; findings kept around to be rechecked
+    pub stability: StabilityChecks,
+
+    /// --prune-extensions; requests and findings of each extension on every host
+    pub pruning: Pruning,
+
+    /// --vcs-check; repositories checked so far
+    pub vcs: VcsChecks,
+
+    /// --archive-peek; archives listed so far
+    pub archives: ArchivePeeks,
+
+    /// {{counter}} and friends, expanded in header values for every request
+    pub variables: HeaderVariables,
+
+    /// --raw-requests; where each host's raw requests connect
+    pub raw: Destinations,
+}
+
+/// implementation of Features
+impl Features {
+    /// set up every feature from the values given on the command line/config file; invalid
+    /// values are reported here, before any requests are made
+    pub fn new(config: &Configuration) -> Result<Self> {
+        log::trace!("enter: Features::new");
+
+        let features = Self {
+            // --traffic-log is independent of logging, it's written regardless of output level
+            traffic: traffic::initialize(config)?,
+            policy_audit: policy_audit::initialize(config)?,
+            session: session::initialize(config)?,
+            plugins: plugins::initialize(config)?,
+            baseline: baseline::initialize(config)?,
+            signatures: signatures::initialize(config)?,
+            categories: categories::initialize(config)?,
+            rules: rules::initialize(config)?,
+            exit_policy: exit_policy::initialize(config)?,
+            // --screenshots workers run on threads of their own, next to the scan
+            screenshots: screenshots::initialize(config)?,
+            favicons: favicon::initialize(config)?,
+            evasion: evasion::initialize(config)?,
+            bypass: bypass::initialize(config),
+            template: Templating::default(),
+            server_watch: ServerWatch::default(),
+            search: search::initialize(config)?,
+            error_weights: ErrorWeights::new(&config.policy_error_weights)?,
+            global_rate_limit: GlobalRateLimit::new(config.rate_limit_global)?,
+            rate_limits: Arc::new(RateLimits::new(config.rate_limit_global)),
+            bandwidth: bandwidth::initialize(config)?,
+            pacing: pacing::initialize(config)?,
+            link_budget: LinkBudget::default(),
+            well_known: WellKnownProbes::default(),
+            buckets: BucketReports::default(),
+            baseline_words: BaselineWords::default(),
+            summary: Summary::default(),
+            request_ids: RequestIds::default(),
+            tls: TlsInspections::default(),
+            scheme: SchemeDetections::default(),
+            // the clients built along with the config already use it; later ones share its cache
+            doh: config.doh_resolver.clone(),
+            fanout: FanOut::default(),
+            stability: StabilityChecks::default(),
+            pruning: Pruning::default(),
+            vcs: VcsChecks::default(),
+            archives: ArchivePeeks::default(),
+            variables: HeaderVariables::default(),
+            raw: Destinations::new(config)?,
+        };
+
+        log::trace!("exit: Features::new");
+        Ok(features)
+    }
+}
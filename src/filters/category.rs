@@ -0,0 +1,33 @@
+use super::*;
+
+/// Simple implementor of FeroxFilter; used to filter out responses tagged with a category
+/// specified using --filter-category
+#[derive(Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryFilter {
+    /// Category whose responses should not be displayed to the user
+    pub category: String,
+}
+
+/// implementation of FeroxFilter for CategoryFilter
+impl FeroxFilter for CategoryFilter {
+    /// Check the response's categories against what was passed in via --filter-category
+    fn should_filter_response(&self, response: &FeroxResponse) -> bool {
+        log::trace!("enter: should_filter_response({:?} {})", self, response);
+
+        let result = response.tags().contains(&self.category);
+
+        log::trace!("exit: should_filter_response -> {}", result);
+
+        result
+    }
+
+    /// Compare one CategoryFilter to another
+    fn box_eq(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>() == Some(self)
+    }
+
+    /// Return self as Any for dynamic dispatch purposes
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -1,16 +1,18 @@
 use std::sync::RwLock;
 
 use anyhow::Result;
+use console::strip_ansi_codes;
 use serde::{ser::SerializeSeq, Serialize, Serializer};
 
 use crate::response::FeroxResponse;
 
 use super::{
-    FeroxFilter, LinesFilter, RegexFilter, SimilarityFilter, SizeFilter, StatusCodeFilter,
-    WildcardFilter, WordsFilter,
+    CategoryFilter, FeroxFilter, LinesFilter, RegexFilter, SimilarityFilter, SizeFilter,
+    StatusCodeFilter, WildcardFilter, WordsFilter,
 };
 use crate::{
-    event_handlers::Command::AddToUsizeField, statistics::StatField::WildcardsFiltered,
+    event_handlers::Command::{AddFilterHit, AddToUsizeField},
+    statistics::StatField::{ResponsesFiltered, WildcardsFiltered},
     CommandSender,
 };
 /// Container around a collection of `FeroxFilters`s
@@ -72,20 +74,55 @@ impl FeroxFilters {
         response: &FeroxResponse,
         tx_stats: CommandSender,
     ) -> bool {
+        self.matching_filter(response, tx_stats).is_some()
+    }
+
+    /// same as `should_filter_response`, but returns a description of the first filter that
+    /// matched the given `FeroxResponse`, if any
+    pub fn matching_filter(
+        &self,
+        response: &FeroxResponse,
+        tx_stats: CommandSender,
+    ) -> Option<String> {
         if let Ok(filters) = self.filters.read() {
             for filter in filters.iter() {
                 // wildcard.should_filter goes here
                 if filter.should_filter_response(response) {
                     log::debug!("filtering response due to: {:?}", filter);
-                    if filter.as_any().downcast_ref::<WildcardFilter>().is_some() {
-                        tx_stats
-                            .send(AddToUsizeField(WildcardsFiltered, 1))
-                            .unwrap_or_default();
-                    }
-                    return true;
+
+                    // wildcards_filtered implies responses_filtered, everything else only
+                    // counts toward the latter
+                    let field = if filter.as_any().downcast_ref::<WildcardFilter>().is_some() {
+                        WildcardsFiltered
+                    } else {
+                        ResponsesFiltered
+                    };
+
+                    let description = strip_ansi_codes(&filter.to_string()).to_string();
+
+                    tx_stats.send(AddToUsizeField(field, 1)).unwrap_or_default();
+                    tx_stats
+                        .send(AddFilterHit(description.clone()))
+                        .unwrap_or_default();
+
+                    return Some(description);
                 }
             }
         }
+        None
+    }
+
+    /// whether the given `FeroxResponse` would be filtered based on its status code or size
+    /// alone, i.e. without needing its body (--range-probe); statistics aren't updated
+    pub fn filters_without_body(&self, response: &FeroxResponse) -> bool {
+        if let Ok(filters) = self.filters.read() {
+            return filters
+                .iter()
+                .filter(|filter| {
+                    filter.as_any().is::<StatusCodeFilter>() || filter.as_any().is::<SizeFilter>()
+                })
+                .any(|filter| filter.should_filter_response(response));
+        }
         false
     }
 }
@@ -119,6 +156,10 @@ impl Serialize for FeroxFilters {
                     filter.as_any().downcast_ref::<SimilarityFilter>()
                 {
                     seq.serialize_element(similarity_filter).unwrap_or_default();
+                } else if let Some(category_filter) =
+                    filter.as_any().downcast_ref::<CategoryFilter>()
+                {
+                    seq.serialize_element(category_filter).unwrap_or_default();
                 }
             }
             seq.end()
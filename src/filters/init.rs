@@ -1,6 +1,6 @@
 use super::{
-    utils::create_similarity_filter, LinesFilter, RegexFilter, SizeFilter, StatusCodeFilter,
-    WordsFilter,
+    utils::create_similarity_filter, CategoryFilter, LinesFilter, RegexFilter, SizeFilter,
+    StatusCodeFilter, WordsFilter,
 };
 use crate::{event_handlers::Handles, skip_fail, utils::fmt_err, Command::AddFilter};
 use anyhow::Result;
@@ -66,6 +66,15 @@ pub async fn initialize(handles: Arc<Handles>) -> Result<()> {
         skip_fail!(handles.filters.send(AddFilter(boxed_filter)));
     }
 
+    // add any category filters to filters handler's FeroxFilters  (--filter-category)
+    for category_filter in &handles.config.filter_category {
+        let filter = CategoryFilter {
+            category: category_filter.to_owned(),
+        };
+        let boxed_filter = Box::new(filter);
+        skip_fail!(handles.filters.send(AddFilter(boxed_filter)));
+    }
+
     handles.filters.sync().await?;
     Ok(())
 }
@@ -6,6 +6,7 @@ use std::fmt::Debug;
 use crate::response::FeroxResponse;
 use crate::traits::FeroxFilter;
 
+pub use self::category::CategoryFilter;
 pub use self::container::FeroxFilters;
 pub(crate) use self::empty::EmptyFilter;
 pub use self::init::initialize;
@@ -31,3 +32,4 @@ mod init;
 mod utils;
 mod wildcard;
 mod empty;
+mod category;
@@ -17,7 +17,7 @@ const MAX_HAMMING_DISTANCE: usize = 3;
 
 /// Simple implementor of FeroxFilter; used to filter out responses based on the similarity of a
 /// Response body with a known response; specified using --filter-similar-to
-#[derive(Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SimilarityFilter {
     /// Hash of Response's body to be used during similarity comparison
     pub hash: u64,
@@ -39,7 +39,7 @@ impl FeroxFilter for SimilarityFilter {
     fn box_eq(&self, other: &dyn Any) -> bool {
         other
             .downcast_ref::<Self>()
-            .map_or(false, |a| self.hash == a.hash)
+            .is_some_and(|a| self.hash == a.hash)
     }
 
     /// Return self as Any for dynamic dispatch purposes
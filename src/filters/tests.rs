@@ -249,6 +249,25 @@ fn similarity_filter_as_any() {
     );
 }
 
+#[test]
+/// a category filter should only filter responses tagged with its category
+fn categoryfilter_should_filter_when_response_has_category() {
+    let mut resp = FeroxResponse::default();
+    resp.set_url("http://localhost/index.php.bak");
+
+    let filter = CategoryFilter {
+        category: String::from("backup-file"),
+    };
+
+    assert!(!filter.should_filter_response(&resp));
+
+    resp.add_tag(String::from("config-file"));
+    assert!(!filter.should_filter_response(&resp));
+
+    resp.add_tag(String::from("backup-file"));
+    assert!(filter.should_filter_response(&resp));
+}
+
 #[test]
 /// test correctness of FeroxFilters::remove
 fn remove_function_works_as_expected() {
@@ -271,7 +290,7 @@ fn remove_function_works_as_expected() {
 
     assert_eq!(data.filters.read().unwrap().len(), 5);
 
-    let expected = vec![
+    let expected = [
         WordsFilter { word_count: 1 },
         WordsFilter { word_count: 3 },
         WordsFilter { word_count: 5 },
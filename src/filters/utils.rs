@@ -28,13 +28,7 @@ pub(crate) async fn create_similarity_filter(
     let resp = logged_request(&url, DEFAULT_METHOD, None, handles.clone()).await?;
 
     // if successful, create a filter based on the response's body
-    let mut fr = FeroxResponse::from(
-        resp,
-        similarity_filter,
-        DEFAULT_METHOD,
-        handles.config.output_level,
-    )
-    .await;
+    let mut fr = FeroxResponse::from(resp, similarity_filter, DEFAULT_METHOD, &handles).await;
 
     if handles.config.collect_extensions {
         fr.parse_extension(handles.clone())?;
@@ -91,6 +85,11 @@ pub(crate) fn filter_lookup(filter_type: &str, filter_value: &str) -> Option<Box
                 }));
             }
         }
+        "category" => {
+            return Some(Box::new(super::CategoryFilter {
+                category: filter_value.to_string(),
+            }));
+        }
         "similarity" => {
             return Some(Box::new(SimilarityFilter {
                 hash: 0,
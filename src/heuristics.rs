@@ -1,8 +1,13 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Result};
 use futures::future;
+use reqwest::StatusCode;
 use scraper::{Html, Selector};
 use uuid::Uuid;
 
@@ -10,6 +15,8 @@ use crate::filters::{SimilarityFilter, WildcardFilter, SIM_HASHER};
 use crate::message::FeroxMessage;
 use crate::nlp::preprocess;
 use crate::scanner::RESPONSES;
+use crate::signatures::Signature;
+use crate::tls;
 use crate::{
     config::OutputLevel,
     event_handlers::{Command, Handles},
@@ -21,6 +28,42 @@ use crate::{
     DEFAULT_METHOD,
 };
 
+/// resources commonly found on windows and *nix servers alike; `case_insensitivity` requests
+/// them in mixed case to see whether the server cares
+const CASE_CANARIES: [&str; 6] = [
+    "favicon.ico",
+    "robots.txt",
+    "index.html",
+    "default.aspx",
+    "iisstart.htm",
+    "sitemap.xml",
+];
+
+/// api paths requested ahead of the wordlist when a target turns out to be a single-page app;
+/// the app's routes are all answered with its index page, but the backend it talks to isn't
+pub const SPA_API_WORDS: [&str; 20] = [
+    "api",
+    "api/v1",
+    "api/v2",
+    "api/v3",
+    "api/graphql",
+    "api/health",
+    "api/swagger.json",
+    "api-docs",
+    "graphql",
+    "graphiql",
+    "openapi.json",
+    "swagger.json",
+    "swagger-ui",
+    "rest",
+    "v1",
+    "v2",
+    "health",
+    "config.json",
+    "asset-manifest.json",
+    "socket.io",
+];
+
 /// enum representing the different servers that `parse_html` can detect when directory listing is
 /// enabled
 #[derive(Copy, Debug, Clone)]
@@ -65,13 +108,32 @@ pub enum WildcardResult {
 pub struct HeuristicTests {
     /// Handles object for event handler interaction
     handles: Arc<Handles>,
+
+    /// response times of the 404 detection probes
+    latencies: Mutex<Vec<Duration>>,
+
+    /// whether any of the 404 detection probes got a 2xx page that loads scripts, which is
+    /// what a single-page app answers with
+    app_shell_seen: AtomicBool,
 }
 
 /// HeuristicTests implementation
 impl HeuristicTests {
     /// create a new HeuristicTests struct
     pub fn new(handles: Arc<Handles>) -> Self {
-        Self { handles }
+        Self {
+            handles,
+            latencies: Mutex::new(Vec::new()),
+            app_shell_seen: AtomicBool::new(false),
+        }
+    }
+
+    /// response times of the 404 detection probes that got a response
+    pub fn latencies(&self) -> Vec<Duration> {
+        self.latencies
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
     }
 
     /// Simple helper to return a uuid, formatted as lowercase without hyphens
@@ -107,6 +169,26 @@ impl HeuristicTests {
             let url = FeroxUrl::from_string(target_url, self.handles.clone());
             let request = skip_fail!(url.format("", None));
 
+            if let Err(e) = tls::check_target_pins(
+                target_url,
+                &self.handles.config.pin_sha256,
+                Duration::from_secs(self.handles.config.timeout),
+            )
+            .await
+            {
+                if matches!(
+                    self.handles.config.output_level,
+                    OutputLevel::Default | OutputLevel::Quiet
+                ) {
+                    ferox_print(
+                        &format!("Could not verify the certificate of {target_url}, skipping...\n  => {e}\n"),
+                        &PROGRESS_PRINTER,
+                    );
+                }
+                log::warn!("{}", e);
+                continue;
+            }
+
             let result = logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await;
 
             match result {
@@ -146,6 +228,75 @@ impl HeuristicTests {
         Ok(good_urls)
     }
 
+    /// request the given word relative to the given target
+    async fn probe(&self, target_url: &str, word: &str) -> Result<FeroxResponse> {
+        let url = FeroxUrl::from_string(target_url, self.handles.clone());
+        let request = url.format(word, None)?;
+
+        let response = logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await?;
+
+        Ok(FeroxResponse::from(response, &url.target, DEFAULT_METHOD, &self.handles).await)
+    }
+
+    /// heuristic designed to detect targets that treat paths case-insensitively (i.e. IIS), in
+    /// which case Admin, ADMIN, and admin are all the same request
+    ///
+    /// the first canary that exists is requested again in mixed case; the target is
+    /// case-insensitive when both requests get the same response. targets that respond to
+    /// anything (catch-alls), or where no canary exists, are reported as case-sensitive, since
+    /// there's no telling otherwise
+    ///
+    /// returns true only when every given target is case-insensitive
+    pub async fn case_insensitivity(&self, target_urls: &[String]) -> Result<bool> {
+        log::trace!("enter: case_insensitivity({:?})", target_urls);
+
+        for target_url in target_urls {
+            let catch_all = self.probe(target_url, &self.unique_string(1)).await?;
+
+            if catch_all.status() != &StatusCode::NOT_FOUND {
+                log::debug!(
+                    "{} has a catch-all response, assuming it's case-sensitive",
+                    target_url
+                );
+                log::trace!("exit: case_insensitivity -> false");
+                return Ok(false);
+            }
+
+            let mut insensitive = false;
+
+            for canary in CASE_CANARIES {
+                let lower = self.probe(target_url, canary).await?;
+
+                if !lower.status().is_success() {
+                    continue;
+                }
+
+                let mixed = self.probe(target_url, &mixed_case(canary)).await?;
+
+                insensitive = mixed.status() == lower.status()
+                    && mixed.content_length() == lower.content_length();
+
+                log::debug!(
+                    "{} responded to {} with {} and {} with {}",
+                    target_url,
+                    lower.url(),
+                    lower.status(),
+                    mixed.url(),
+                    mixed.status()
+                );
+                break;
+            }
+
+            if !insensitive {
+                log::trace!("exit: case_insensitivity -> false");
+                return Ok(false);
+            }
+        }
+
+        log::trace!("exit: case_insensitivity -> {}", !target_urls.is_empty());
+        Ok(!target_urls.is_empty())
+    }
+
     /// heuristic designed to detect when a server has directory listing enabled
     pub async fn directory_listing(&self, target_url: &str) -> Result<Option<DirListingResult>> {
         log::trace!("enter: directory_listing({})", target_url);
@@ -166,27 +317,21 @@ impl HeuristicTests {
 
         let result = logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await?;
 
-        let ferox_response = FeroxResponse::from(
-            result,
-            &url.target,
-            DEFAULT_METHOD,
-            self.handles.config.output_level,
-        )
-        .await;
+        let ferox_response =
+            FeroxResponse::from(result, &url.target, DEFAULT_METHOD, &self.handles).await;
 
         let body = ferox_response.text();
         let html = Html::parse_document(body);
 
         let dirlist_type = self.detect_directory_listing(&html);
 
-        if dirlist_type.is_some() {
+        if let Some(dirlist_type) = dirlist_type {
             // folks that run things and step away/rely on logs need to be notified of directory
             // listing, since they won't see the message on the bar; bastardizing FeroxMessage
             // for ease of implementation. This could use a bit of polish at some point.
             let msg = format!(
                 "detected directory listing: {} ({:?})",
-                target_url,
-                dirlist_type.unwrap()
+                target_url, dirlist_type
             );
             let ferox_msg = FeroxMessage {
                 kind: "log".to_string(),
@@ -204,7 +349,7 @@ impl HeuristicTests {
             log::info!("{}", msg);
 
             let result = DirListingResult {
-                dir_list_type: dirlist_type,
+                dir_list_type: Some(dirlist_type),
                 response: ferox_response,
             };
 
@@ -266,8 +411,18 @@ impl HeuristicTests {
             return Ok(None);
         }
 
+        // a signature learned earlier (this run, or a previous one via the state file) stands in
+        // for the probes
+        if let Some(signature) = self.handles.features.signatures.lookup(target_url) {
+            log::trace!("exit: detect_404_like_responses -> reused signature");
+            return self.apply_signature(signature).map(Some);
+        }
+
         let mut req_counter = 0;
 
+        // everything learned about this directory, saved for later runs
+        let mut signature = Signature::default();
+
         let data = if self.handles.config.data.is_empty() {
             None
         } else {
@@ -331,11 +486,17 @@ impl HeuristicTests {
                     // - http://localhost/.htaccess92969beae6bf4beb855d1622406d87e395c87387a9ad432e8a11245002b709b03cf609d471004154b83bcc1c6ec49f6f
                     // - http://localhost/adminf1d2541e73c44dcb9d1fb7d93334b280
                     // - http://localhost/admin92969beae6bf4beb855d1622406d87e395c87387a9ad432e8a11245002b709b03cf609d471004154b83bcc1c6ec49f6f
+                    let started = Instant::now();
+
                     let Ok(response) =
-                        logged_request(&nonexistent_url, method, data, self.handles.clone())
-                            .await else {
-                                return None;
-                            };
+                        logged_request(&nonexistent_url, method, data, self.handles.clone()).await
+                    else {
+                        return None;
+                    };
+
+                    if let Ok(mut latencies) = self.latencies.lock() {
+                        latencies.push(started.elapsed());
+                    }
 
                     if !self
                         .handles
@@ -350,15 +511,15 @@ impl HeuristicTests {
                         return None;
                     }
 
-                    Some(
-                        FeroxResponse::from(
-                            response,
-                            &ferox_url.target,
-                            method,
-                            self.handles.config.output_level,
-                        )
-                        .await,
-                    )
+                    let response =
+                        FeroxResponse::from(response, &ferox_url.target, method, &self.handles)
+                            .await;
+
+                    if is_app_shell(&response) {
+                        self.app_shell_seen.store(true, Ordering::Relaxed);
+                    }
+
+                    Some(response)
                 }))
                 .await // await gives vector of options containing feroxresponses
                 .into_iter()
@@ -372,49 +533,20 @@ impl HeuristicTests {
                 }
 
                 // check the responses for similarities on which we can filter, multiple may be returned
-                let Some((wildcard_filters, wildcard_responses)) = self.examine_404_like_responses(&responses) else {
+                let Some((wildcard_filters, wildcard_responses)) =
+                    self.examine_404_like_responses(&responses)
+                else {
                     // no match was found during analysis of responses
                     log::warn!("no match found for 404 responses");
                     continue;
                 };
 
                 // report to the user, if appropriate
-                if matches!(
-                    self.handles.config.output_level,
-                    OutputLevel::Default | OutputLevel::Quiet
-                ) {
-                    // sentry value to control whether or not to print the filter
-                    // used because we only want to print the same filter once
-                    let mut print_sentry;
-
-                    if let Ok(filters) = self.handles.filters.data.filters.read() {
-                        for new_wildcard in &wildcard_filters {
-                            // reset the sentry for every new wildcard produced by examine_404_like_responses
-                            print_sentry = true;
-
-                            for other in filters.iter() {
-                                if let Some(other_wildcard) =
-                                    other.as_any().downcast_ref::<WildcardFilter>()
-                                {
-                                    // check the new wildcard against all existing wildcards, if it was added
-                                    // on the cli or by a previous directory, don't print it
-                                    if new_wildcard.as_ref() == other_wildcard {
-                                        print_sentry = false;
-                                        break;
-                                    }
-                                }
-                            }
-
-                            // if we're here, we've found a new wildcard that we didn't previously display, print it
-                            if print_sentry {
-                                ferox_print(&format!("{}", new_wildcard), &PROGRESS_PRINTER);
-                            }
-                        }
-                    }
-                }
+                self.report_wildcards(&wildcard_filters);
 
                 // create the new filter
                 for wildcard in wildcard_filters {
+                    signature.wildcards.push(wildcard.as_ref().clone());
                     self.handles.filters.send(Command::AddFilter(wildcard))?;
                 }
 
@@ -429,6 +561,8 @@ impl HeuristicTests {
                         original_url: resp.url().to_string(),
                     };
 
+                    signature.similarities.push(sim_filter.clone());
+
                     self.handles
                         .filters
                         .send(Command::AddFilter(Box::new(sim_filter)))?;
@@ -447,6 +581,8 @@ impl HeuristicTests {
 
                         cloned_resp.set_wildcard(true);
 
+                        signature.directories.push(cloned_resp.clone());
+
                         // add the response to the global list of responses
                         RESPONSES.insert(cloned_resp);
 
@@ -457,6 +593,11 @@ impl HeuristicTests {
             }
         }
 
+        self.handles
+            .features
+            .signatures
+            .record(target_url, signature);
+
         log::trace!("exit: detect_404_like_responses");
 
         let retval = if req_counter >= 100 {
@@ -468,6 +609,113 @@ impl HeuristicTests {
         Ok(Some(retval))
     }
 
+    /// heuristic designed to detect single-page apps, where every path is answered with the app's
+    /// index page and routing happens client-side
+    ///
+    /// two paths that can't exist, one of them nested, are requested along with the target;
+    /// it's an spa when all three get a 2xx page that loads scripts, and the pages have the
+    /// same number of words and lines (so that a nonce or two in the page doesn't throw it off)
+    ///
+    /// nothing is requested unless one of `detect_404_like_responses`' probes already got such
+    /// a page; returns the target's response, which is the app's index page
+    pub async fn single_page_app(&self, target_url: &str) -> Result<Option<FeroxResponse>> {
+        log::trace!("enter: single_page_app({})", target_url);
+
+        if !self.app_shell_seen.load(Ordering::Relaxed) {
+            log::trace!("exit: single_page_app -> None");
+            return Ok(None);
+        }
+
+        let nested = format!("{}/{}", self.unique_string(1), self.unique_string(1));
+        let mut probes = Vec::new();
+
+        for word in [self.unique_string(1), nested, String::new()] {
+            let probe = self.probe(target_url, &word).await?;
+
+            let matches_first = probes.first().is_none_or(|first: &FeroxResponse| {
+                probe.word_count() == first.word_count() && probe.line_count() == first.line_count()
+            });
+
+            if !is_app_shell(&probe) || !matches_first {
+                log::trace!("exit: single_page_app -> None");
+                return Ok(None);
+            }
+
+            probes.push(probe);
+        }
+
+        let index = probes.pop().unwrap_or_default();
+
+        log::trace!("exit: single_page_app -> {}", index);
+        Ok(Some(index))
+    }
+
+    /// print the given wildcard filters, skipping those that are already known (added on the
+    /// cli or by a previous directory); nothing is printed with --silent
+    fn report_wildcards(&self, wildcard_filters: &[Box<WildcardFilter>]) {
+        if !matches!(
+            self.handles.config.output_level,
+            OutputLevel::Default | OutputLevel::Quiet
+        ) {
+            return;
+        }
+
+        let Ok(filters) = self.handles.filters.data.filters.read() else {
+            return;
+        };
+
+        for new_wildcard in wildcard_filters {
+            let known = filters.iter().any(|other| {
+                other
+                    .as_any()
+                    .downcast_ref::<WildcardFilter>()
+                    .is_some_and(|other_wildcard| new_wildcard.as_ref() == other_wildcard)
+            });
+
+            if !known {
+                ferox_print(&format!("{}", new_wildcard), &PROGRESS_PRINTER);
+            }
+        }
+    }
+
+    /// set up the filters from a previously learned signature, the same way
+    /// `detect_404_like_responses` would have after probing; no requests are made
+    fn apply_signature(&self, signature: Signature) -> Result<WildcardResult> {
+        let wildcard_filters = signature
+            .wildcards
+            .into_iter()
+            .map(Box::new)
+            .collect::<Vec<_>>();
+
+        self.report_wildcards(&wildcard_filters);
+
+        for wildcard in wildcard_filters {
+            self.handles.filters.send(Command::AddFilter(wildcard))?;
+        }
+
+        for sim_filter in signature.similarities {
+            self.handles
+                .filters
+                .send(Command::AddFilter(Box::new(sim_filter)))?;
+        }
+
+        let wildcard_directory = !signature.directories.is_empty();
+
+        for mut directory in signature.directories {
+            // on resume, the state file's responses already hold these
+            if !RESPONSES.contains(&directory) {
+                directory.set_wildcard(true);
+                RESPONSES.insert(directory);
+            }
+        }
+
+        if wildcard_directory {
+            Ok(WildcardResult::WildcardDirectory(0))
+        } else {
+            Ok(WildcardResult::FourOhFourLike(0))
+        }
+    }
+
     /// for all responses, group them by status code, then examine chars/words/lines.
     /// if all responses' respective lengths within a status code grouping match
     /// each other, we can assume that will remain true for subsequent non-existent urls
@@ -603,6 +851,33 @@ impl HeuristicTests {
     }
 }
 
+/// whether the given response is a 2xx page that loads scripts, i.e. a single-page app's index
+fn is_app_shell(response: &FeroxResponse) -> bool {
+    response.status().is_success() && response.text().contains("<script")
+}
+
+/// alternate the case of each letter in the given word, i.e. favicon.ico -> FaViCoN.iCo
+fn mixed_case(word: &str) -> String {
+    let mut upper = true;
+
+    word.chars()
+        .map(|c| {
+            if !c.is_alphabetic() {
+                return c.to_string();
+            }
+
+            let mixed = if upper {
+                c.to_uppercase().to_string()
+            } else {
+                c.to_lowercase().to_string()
+            };
+
+            upper = !upper;
+            mixed
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -663,4 +938,11 @@ mod tests {
         let dirlist_type = heuristics.detect_directory_listing(&parsed);
         assert!(dirlist_type.is_none());
     }
+
+    #[test]
+    /// canaries should alternate case one letter at a time, skipping everything else
+    fn heuristics_mixed_case_alternates_letters() {
+        assert_eq!(mixed_case("favicon.ico"), "FaViCoN.iCo");
+        assert_eq!(mixed_case("a1b-c"), "A1b-C");
+    }
 }
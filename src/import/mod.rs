@@ -0,0 +1,4 @@
+//! importers that turn the output of other tools into scan targets
+mod nmap;
+
+pub use self::nmap::targets_from_nmap;
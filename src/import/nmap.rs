@@ -0,0 +1,249 @@
+use std::{collections::HashMap, fs::read_to_string};
+
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::utils::fmt_err;
+
+lazy_static! {
+    /// matches a single `<host>...</host>` block, shared by nmap and masscan
+    static ref HOST_REGEX: Regex =
+        Regex::new(r"(?s)<host[\s>].*?</host>").expect("Could not compile regex");
+
+    /// matches a single `<port ...>...</port>` block within a host
+    static ref PORT_REGEX: Regex =
+        Regex::new(r"(?s)<port\s([^>]*)>(.*?)</port>").expect("Could not compile regex");
+
+    /// matches an opening tag along with its attributes, i.e. `<address addr="..." .../>`
+    static ref TAG_REGEX: Regex =
+        Regex::new(r"<(address|hostname|state|service)\s([^>]*)>").expect("Could not compile regex");
+
+    /// matches a single `key="value"` attribute pair
+    static ref ATTR_REGEX: Regex =
+        Regex::new(r#"([\w-]+)="([^"]*)""#).expect("Could not compile regex");
+}
+
+/// ports that are assumed to speak https when no service information is available
+const HTTPS_PORTS: [u16; 4] = [443, 4443, 8443, 9443];
+
+/// ports that are assumed to speak http when no service information is available
+const HTTP_PORTS: [u16; 10] = [80, 81, 3000, 5000, 8000, 8008, 8080, 8081, 8888, 9000];
+
+/// Read the given nmap (`-oX`) or masscan (`-oX`) xml file and return a target url for each open
+/// port that looks like it's serving http/https
+pub fn targets_from_nmap(filename: &str) -> Result<Vec<String>> {
+    log::trace!("enter: targets_from_nmap({})", filename);
+
+    let contents =
+        read_to_string(filename).with_context(|| fmt_err(&format!("Could not open {filename}")))?;
+
+    let targets = parse_targets(&contents);
+
+    log::trace!("exit: targets_from_nmap -> {:?}", targets);
+    Ok(targets)
+}
+
+/// parse xml contents into a de-duplicated list of target urls, in the order they were found
+fn parse_targets(contents: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+
+    for host in HOST_REGEX.find_iter(contents) {
+        let host = host.as_str();
+
+        let mut address = None;
+        let mut hostname = None;
+
+        for tag in TAG_REGEX.captures_iter(host) {
+            let attrs = attributes(&tag[2]);
+
+            match &tag[1] {
+                "address" if address.is_none() => {
+                    // mac addresses show up as an address too, only ip addresses are useful
+                    match attrs.get("addrtype").map(String::as_str) {
+                        Some("ipv4") => address = attrs.get("addr").cloned(),
+                        Some("ipv6") => address = attrs.get("addr").map(|ip| format!("[{ip}]")),
+                        _ => {}
+                    }
+                }
+                // prefer the name the user handed to nmap over the ip, since it's likely to
+                // matter for virtual hosting; reverse dns names aren't guaranteed to resolve back
+                "hostname" if attrs.get("type").map(String::as_str) == Some("user") => {
+                    hostname = attrs.get("name").cloned();
+                }
+                _ => {}
+            }
+        }
+
+        let Some(host_str) = hostname.or(address) else {
+            continue;
+        };
+
+        for port in PORT_REGEX.captures_iter(host) {
+            let port_attrs = attributes(&port[1]);
+
+            if port_attrs.get("protocol").map(String::as_str) != Some("tcp") {
+                continue;
+            }
+
+            let Some(portid) = port_attrs
+                .get("portid")
+                .and_then(|id| id.parse::<u16>().ok())
+            else {
+                continue;
+            };
+
+            let mut open = false;
+            let mut service = None;
+
+            for tag in TAG_REGEX.captures_iter(&port[2]) {
+                let attrs = attributes(&tag[2]);
+
+                match &tag[1] {
+                    "state" => open = attrs.get("state").map(String::as_str) == Some("open"),
+                    "service" => service = Some(attrs),
+                    _ => {}
+                }
+            }
+
+            if !open {
+                continue;
+            }
+
+            let Some(scheme) = guess_scheme(portid, service.as_ref()) else {
+                continue;
+            };
+
+            let target = match (scheme, portid) {
+                ("http", 80) | ("https", 443) => format!("{scheme}://{host_str}"),
+                _ => format!("{scheme}://{host_str}:{portid}"),
+            };
+
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+
+    targets
+}
+
+/// collect all `key="value"` pairs from the given tag body
+fn attributes(tag: &str) -> HashMap<String, String> {
+    ATTR_REGEX
+        .captures_iter(tag)
+        .map(|attr| (attr[1].to_string(), attr[2].to_string()))
+        .collect()
+}
+
+/// determine whether the given port serves http or https, returning None for non-web ports
+///
+/// service detection results (nmap -sV / masscan --banners) take precedence; without them, the
+/// decision is made based on commonly used port numbers
+fn guess_scheme(port: u16, service: Option<&HashMap<String, String>>) -> Option<&'static str> {
+    match service.and_then(|attrs| attrs.get("name")) {
+        Some(name) => {
+            let tunneled = service
+                .and_then(|attrs| attrs.get("tunnel"))
+                .is_some_and(|tunnel| tunnel == "ssl");
+
+            if name.starts_with("https") || (name.starts_with("ssl") && name.contains("http")) {
+                Some("https")
+            } else if name.starts_with("http") {
+                if tunneled || HTTPS_PORTS.contains(&port) {
+                    Some("https")
+                } else {
+                    Some("http")
+                }
+            } else {
+                None
+            }
+        }
+        None if HTTPS_PORTS.contains(&port) => Some("https"),
+        None if HTTP_PORTS.contains(&port) => Some("http"),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::write;
+    use tempfile::NamedTempFile;
+
+    /// trimmed down nmap -sV -oX output
+    const NMAP_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<nmaprun scanner="nmap" args="nmap -sV -oX scan.xml example.com 10.0.0.2">
+<host starttime="1" endtime="2"><status state="up" reason="syn-ack" reason_ttl="0"/>
+<address addr="10.0.0.1" addrtype="ipv4"/>
+<hostnames>
+<hostname name="example.com" type="user"/>
+<hostname name="www.example.com" type="PTR"/>
+</hostnames>
+<ports>
+<port protocol="tcp" portid="22"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="ssh" method="probed" conf="10"/></port>
+<port protocol="tcp" portid="80"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="http" product="nginx" method="probed" conf="10"/></port>
+<port protocol="tcp" portid="443"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="http" tunnel="ssl" method="probed" conf="10"/></port>
+<port protocol="tcp" portid="8080"><state state="closed" reason="reset" reason_ttl="0"/><service name="http-proxy" method="table" conf="3"/></port>
+<port protocol="tcp" portid="8443"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="https-alt" method="table" conf="3"/></port>
+</ports>
+</host>
+<host starttime="1" endtime="2"><status state="up" reason="syn-ack" reason_ttl="0"/>
+<address addr="10.0.0.2" addrtype="ipv4"/>
+<address addr="00:11:22:33:44:55" addrtype="mac"/>
+<ports>
+<port protocol="tcp" portid="8000"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="http-alt" method="probed" conf="10"/></port>
+<port protocol="udp" portid="80"><state state="open" reason="udp-response" reason_ttl="0"/><service name="http" method="table" conf="3"/></port>
+</ports>
+</host>
+</nmaprun>"#;
+
+    /// trimmed down masscan -oX output
+    const MASSCAN_XML: &str = r#"<?xml version="1.0"?>
+<nmaprun scanner="masscan" start="1" version="1.0-BETA"  xmloutputversion="1.03">
+<host endtime="1"><address addr="192.168.1.5" addrtype="ipv4"/><ports><port protocol="tcp" portid="8443"><state state="open" reason="syn-ack" reason_ttl="64"/></port></ports></host>
+<host endtime="1"><address addr="192.168.1.5" addrtype="ipv4"/><ports><port protocol="tcp" portid="80"><state state="open" reason="syn-ack" reason_ttl="64"/></port></ports></host>
+<host endtime="1"><address addr="192.168.1.5" addrtype="ipv4"/><ports><port protocol="tcp" portid="3389"><state state="open" reason="syn-ack" reason_ttl="64"/></port></ports></host>
+<host endtime="1"><address addr="fe80::1" addrtype="ipv6"/><ports><port protocol="tcp" portid="8080"><state state="open" reason="syn-ack" reason_ttl="64"/></port></ports></host>
+<runstats><finished time="1" timestr="2023-06-01 00:00:00" elapsed="1" /></runstats>
+</nmaprun>"#;
+
+    #[test]
+    /// nmap output should produce urls for open web ports only, using the user supplied hostname
+    fn parse_targets_handles_nmap_output() {
+        assert_eq!(
+            parse_targets(NMAP_XML),
+            vec![
+                "http://example.com",
+                "https://example.com",
+                "https://example.com:8443",
+                "http://10.0.0.2:8000",
+            ]
+        );
+    }
+
+    #[test]
+    /// masscan output has no service info, scheme should be guessed based on port
+    fn parse_targets_handles_masscan_output() {
+        assert_eq!(
+            parse_targets(MASSCAN_XML),
+            vec![
+                "https://192.168.1.5:8443",
+                "http://192.168.1.5",
+                "http://[fe80::1]:8080",
+            ]
+        );
+    }
+
+    #[test]
+    /// targets_from_nmap should read from disk and error on missing files
+    fn targets_from_nmap_reads_file() {
+        let tmp = NamedTempFile::new().unwrap();
+        write(&tmp, MASSCAN_XML).unwrap();
+
+        let targets = targets_from_nmap(tmp.path().to_str().unwrap()).unwrap();
+        assert_eq!(targets.len(), 3);
+
+        assert!(targets_from_nmap("/definitely/doesnt/exist/scan.xml").is_err());
+    }
+}
@@ -10,20 +10,58 @@ use tokio::{
 
 use crate::event_handlers::Command;
 
+pub mod archives;
+pub mod bandwidth;
 pub mod banner;
+pub mod baseline;
+pub mod buckets;
+pub mod categories;
+pub mod bypass;
 pub mod config;
-mod client;
+pub mod client;
+pub mod diff;
+pub mod doh;
+pub mod evasion;
 pub mod event_handlers;
+pub mod exit_policy;
+mod export;
+pub mod fanout;
+pub mod favicon;
+pub mod features;
 pub mod filters;
 pub mod heuristics;
+pub mod import;
 pub mod logger;
+pub mod pacing;
 mod parser;
+pub mod plugins;
+pub mod policy_audit;
 pub mod progress;
+pub mod pruning;
+pub mod raw;
+pub mod rules;
+pub mod safety;
 pub mod scan_manager;
 pub mod scanner;
+pub mod scheme;
+pub mod screenshots;
+pub mod search;
+pub mod selftest;
+pub mod server_watch;
+pub mod session;
+pub mod shuffle;
+pub mod signatures;
+pub mod stability;
 pub mod statistics;
+pub mod summary;
+pub mod template;
+pub mod tls;
+pub mod traffic;
 mod traits;
 pub mod utils;
+pub mod variables;
+pub mod vcs;
+pub mod wordlist;
 mod extractor;
 mod macros;
 mod url;
@@ -52,6 +90,10 @@ pub(crate) const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Maximum number of file descriptors that can be opened during a scan
 pub const DEFAULT_OPEN_FILE_LIMIT: u64 = 8192;
 
+/// Number of file descriptors set aside for wordlists, output files, logs, etc... when deciding
+/// how many sockets a scan may have open at once
+pub const RESERVED_OPEN_FILES: u64 = 64;
+
 /// Default set of extensions to Ignore when auto-collecting extensions during scans
 pub(crate) const DEFAULT_IGNORED_EXTENSIONS: [&str; 38] = [
     "tif", "tiff", "ico", "cur", "bmp", "webp", "svg", "png", "jpg", "jpeg", "jfif", "gif", "avif",
@@ -82,6 +124,16 @@ pub(crate) const SLEEP_DURATION: u64 = 500;
 /// The percentage of requests as errors it takes to be deemed too high
 pub const HIGH_ERROR_RATIO: f64 = 0.90;
 
+/// The percentage of requests as 429s it takes to be deemed too high
+pub const HIGH_429_RATIO: f64 = HIGH_ERROR_RATIO / 3.0;
+
+/// The minimum number of errors it takes to be deemed too high
+pub const MIN_ERROR_THRESHOLD: usize = 25;
+
+/// Number of seconds a scan bailed on by --auto-requeue waits before restarting, multiplied by
+/// the number of times it's been requeued
+pub const REQUEUE_COOLDOWN: u64 = 30;
+
 /// Default list of status codes to report (all of them)
 pub const DEFAULT_STATUS_CODES: [StatusCode; 60] = [
     // all 1XX response codes
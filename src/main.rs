@@ -1,15 +1,17 @@
 use std::io::stdin;
 use std::{
+    collections::HashSet,
     env::{
         args,
         consts::{ARCH, OS},
     },
-    fs::{create_dir, remove_file, File},
-    io::{stderr, BufRead, BufReader},
+    fs::{create_dir, remove_file},
+    io::{stderr, BufRead},
     ops::Index,
     path::Path,
     process::{exit, Command},
     sync::{atomic::Ordering, Arc},
+    time::Instant,
 };
 
 use anyhow::{bail, Context, Result};
@@ -21,21 +23,31 @@ use tokio::{
 use tokio_util::codec::{FramedRead, LinesCodec};
 
 use feroxbuster::{
+    archives,
     banner::{Banner, UPDATE_URL},
+    bypass,
+    client::HostOverride,
     config::{Configuration, OutputLevel},
+    diff,
     event_handlers::{
         Command::{
-            AddHandles, CreateBar, Exit, JoinTasks, LoadStats, ScanInitialUrls, UpdateWordlist,
+            AddHandles, AddToUsizeField, CreateBar, Exit, JoinTasks, LoadStats, ScanInitialUrls,
+            UpdateWordlist,
         },
         FiltersHandler, Handles, ScanHandler, StatsHandler, Tasks, TermInputHandler,
         TermOutHandler, SCAN_COMPLETE,
     },
-    filters, heuristics, logger,
+    exit_policy, fanout,
+    features::Features,
+    filters, heuristics, import, logger,
     progress::PROGRESS_PRINTER,
+    safety,
     scan_manager::{self, ScanType},
-    scanner,
-    utils::{fmt_err, slugify_filename},
-    SECONDARY_WORDLIST,
+    scanner, scheme, selftest, session, stability,
+    statistics::StatField::WordlistWords,
+    summary, template,
+    utils::{clamp_threads_to_open_files, ferox_eprint, fmt_err, slugify_filename},
+    vcs, wordlist,
 };
 #[cfg(not(target_os = "windows"))]
 use feroxbuster::{utils::set_open_file_limit, DEFAULT_OPEN_FILE_LIMIT};
@@ -48,45 +60,18 @@ lazy_static! {
     static ref PARALLEL_LIMITER: Semaphore = Semaphore::new(0);
 }
 
-/// Create a Vec of Strings from the given wordlist then stores it inside an Arc
-fn get_unique_words_from_wordlist(path: &str) -> Result<Arc<Vec<String>>> {
-    log::trace!("enter: get_unique_words_from_wordlist({})", path);
-    let mut trimmed_word = false;
-
-    let file = File::open(path).with_context(|| format!("Could not open {path}"))?;
-
-    let reader = BufReader::new(file);
-
-    // this empty string ensures that we call Requester::request with the base url, i.e.
-    // `http://localhost/` instead of going straight into `http://localhost/WORD.EXT`.
-    // for vanilla scans, it doesn't matter all that much, but it can be a significant difference
-    // when `-e` is used, depending on the content at the base url.
-    let mut words = vec![String::from("")];
-
-    for line in reader.lines() {
-        line.map(|result| {
-            if !result.starts_with('#') && !result.is_empty() {
-                if result.starts_with('/') {
-                    words.push(result.trim_start_matches('/').to_string());
-                    trimmed_word = true;
-                } else {
-                    words.push(result);
-                }
-            }
-        })
-        .ok();
-    }
-
-    if trimmed_word {
-        log::warn!("Some words in the wordlist started with a leading forward-slash; those words were trimmed (i.e. /word -> word)");
-    }
-
-    log::trace!(
-        "exit: get_unique_words_from_wordlist -> Arc<wordlist[{} words...]>",
-        words.len()
-    );
-
-    Ok(Arc::new(words))
+/// lowercase every word in the given wordlist, dropping the duplicates that result; used when
+/// every target is case-insensitive (--detect-case)
+fn lowercase_words(words: &[String]) -> Arc<Vec<String>> {
+    let mut seen = HashSet::new();
+
+    Arc::new(
+        words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .filter(|word| seen.insert(word.clone()))
+            .collect(),
+    )
 }
 
 /// Determine whether it's a single url scan or urls are coming from stdin, then scan as needed
@@ -95,9 +80,9 @@ async fn scan(targets: Vec<String>, handles: Arc<Handles>) -> Result<()> {
 
     let scanned_urls = handles.ferox_scans()?;
 
-    handles.send_scan_command(UpdateWordlist(handles.wordlist.clone()))?;
+    handles.send_scan_command(UpdateWordlist(handles.wordlist()))?;
 
-    scanner::initialize(handles.wordlist.len(), handles.clone()).await?;
+    scanner::initialize(handles.wordlist().len(), handles.clone()).await?;
 
     // at this point, the stat thread's progress bar can be created; things that needed to happen
     // first:
@@ -119,7 +104,10 @@ async fn scan(targets: Vec<String>, handles: Arc<Handles>) -> Result<()> {
         }
 
         // only create the bar if no --silent|--quiet
-        handles.stats.send(CreateBar(total_offset))?;
+        handles.stats.send(CreateBar(
+            total_offset,
+            handles.features.rate_limits.clone(),
+        ))?;
 
         // blocks until the bar is created / avoids race condition in first two bars
         handles.stats.sync().await?;
@@ -128,7 +116,7 @@ async fn scan(targets: Vec<String>, handles: Arc<Handles>) -> Result<()> {
     if handles.config.resumed {
         // display what has already been completed
         scanned_urls.print_known_responses();
-        scanned_urls.print_completed_bars(handles.wordlist.len())?;
+        scanned_urls.print_completed_bars(handles.wordlist().len())?;
     }
 
     log::debug!("sending {:?} to be scanned as initial targets", targets);
@@ -139,7 +127,8 @@ async fn scan(targets: Vec<String>, handles: Arc<Handles>) -> Result<()> {
     Ok(())
 }
 
-/// Get targets from either commandline or stdin, pass them back to the caller as a Result<Vec>
+/// Get targets from either commandline, stdin, or an nmap/masscan xml file, pass them back to the
+/// caller as a Result<Vec>
 async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
     log::trace!("enter: get_targets({:?})", handles);
 
@@ -171,12 +160,30 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
                 targets.push(scan.url().to_owned());
             }
         };
+    } else if !handles.config.import_nmap.is_empty() {
+        // --import-nmap scan.xml; every open port that looks like http(s) becomes a target
+        targets = import::targets_from_nmap(&handles.config.import_nmap)?;
+
+        if targets.is_empty() {
+            bail!(
+                "No open http(s) ports found in {}",
+                handles.config.import_nmap
+            );
+        }
+    } else if let Some(host_override) = HostOverride::new(
+        &handles.config.target_url,
+        &handles.config.host_header,
+        &handles.config.sni,
+    )? {
+        // --host-header used, or --url is a zone scoped ipv6 address; the scan is addressed to
+        // the SNI name, while the client connects to the target given via --url
+        targets.push(host_override.target(&handles.config.target_url)?);
     } else {
         targets.push(handles.config.target_url.clone());
     }
 
     // remove footgun that arises if a --dont-scan value matches on a base url
-    for target in targets.iter_mut() {
+    for target in &targets {
         for denier in &handles.config.regex_denylist {
             if denier.is_match(target) {
                 bail!(
@@ -195,13 +202,22 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
                 );
             }
         }
+    }
 
-        if !target.starts_with("http") && !target.starts_with("https") {
-            // --url hackerone.com
-            *target = format!("https://{target}");
-        }
+    if !handles.config.ports.is_empty() && !handles.config.resumed {
+        // --ports 80,443; one target per host and port
+        targets = scheme::expand_ports(&targets, &handles.config.ports);
     }
 
+    // urls beneath the targets are requested even when --dont-scan denies a parent, which
+    // includes the probes made while detecting their schemes
+    handles.set_targets(&targets);
+
+    // --url hackerone.com; figure out whether it's https or http
+    scheme::detect_all(&mut targets, handles.clone()).await?;
+
+    handles.set_targets(&targets);
+
     log::trace!("exit: get_targets -> {:?}", targets);
 
     Ok(targets)
@@ -209,7 +225,9 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
 
 /// async main called from real main, broken out in this way to allow for some synchronous code
 /// to be executed before bringing the tokio runtime online
-async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
+async fn wrapped_main(config: Arc<Configuration>, features: Features) -> Result<()> {
+    let started = Instant::now();
+
     // join can only be called once, otherwise it causes the thread to panic
     tokio::task::spawn_blocking(move || {
         // ok, lazy_static! uses (unsurprisingly in retrospect) a lazy loading model where the
@@ -236,61 +254,26 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         exit(0);
     }
 
-    let words = if config.wordlist.starts_with("http") {
-        // found a url scheme, attempt to download the wordlist
-        let response = config.client.get(&config.wordlist).send().await?;
-
-        if !response.status().is_success() {
-            // status code isn't a 200, bail
-            bail!(
-                "[{}] Unable to download wordlist from url: {}",
-                response.status().as_str(),
-                config.wordlist
-            );
-        }
-
-        // attempt to get the filename from the url's path
-        let Some(path_segments) = response
-            .url()
-            .path_segments() else {
-                bail!("Unable to parse path from url: {}", response.url());
-            };
-
-        let Some(filename) = path_segments.last() else {
-            bail!("Unable to parse filename from url's path: {}", response.url().path());
-        };
-
-        let filename = filename.to_string();
-
-        // read the body and write it to disk, then use existing code to read the wordlist
-        let body = response.text().await?;
-
-        std::fs::write(&filename, body)?;
-
-        get_unique_words_from_wordlist(&filename)?
+    let words = if !config.replay.is_empty() {
+        // `replay` makes no requests, there's nothing to read a wordlist for
+        Arc::new(Vec::new())
+    } else if config.selftest {
+        // `selftest` brings its own wordlist, matching what its server holds
+        selftest::wordlist()
     } else {
-        match get_unique_words_from_wordlist(&config.wordlist) {
-            Ok(w) => w,
-            Err(err) => {
-                let secondary = Path::new(SECONDARY_WORDLIST);
-
-                if secondary.exists() {
-                    eprintln!("Found wordlist in secondary location");
-                    get_unique_words_from_wordlist(SECONDARY_WORDLIST)?
-                } else {
-                    return Err(err);
-                }
-            }
-        }
+        wordlist::load(&config).await?
     };
 
-    if words.len() <= 1 {
+    if words.len() <= 1 && config.replay.is_empty() {
         // the check is now <= 1 due to the initial empty string added in 2.6.0
         // 1 -> empty wordlist
         // 0 -> error
         bail!("Did not find any words in {}", config.wordlist);
     }
 
+    // not counting the empty string added above
+    let num_words = words.len().saturating_sub(1);
+
     // spawn all event handlers, expect back a JoinHandle and a *Handle to the specific event
     let (stats_task, stats_handle) = StatsHandler::initialize(config.clone());
     let (filters_task, filters_handle) = FiltersHandler::initialize();
@@ -304,18 +287,30 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         out_handle,
         config.clone(),
         words,
+        features,
     ));
 
     let (scan_task, scan_handle) = ScanHandler::initialize(handles.clone());
 
     handles.set_scan_handle(scan_handle); // must be done after Handles initialization
     handles.output.send(AddHandles(handles.clone()))?;
+    handles
+        .stats
+        .send(AddToUsizeField(WordlistWords, num_words))?;
 
     filters::initialize(handles.clone()).await?; // send user-supplied filters to the handler
 
     // create new Tasks object, each of these handles is one that will be joined on later
     let tasks = Tasks::new(out_task, stats_task, filters_task, scan_task);
 
+    if !config.replay.is_empty() {
+        // `replay`; the recorded responses go through the filters/output set up above, no
+        // requests are made
+        session::replay(handles.clone()).await?;
+        clean_up(handles, tasks).await?;
+        return Ok(());
+    }
+
     if !config.time_limit.is_empty() {
         // --time-limit value not an empty string, need to kick off the thread that enforces
         // the limit
@@ -343,18 +338,35 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         handles.stats.send(LoadStats(from_here))?;
     }
 
+    // `selftest`; the built-in server stands in for the targets
+    let selftest_server = if config.selftest {
+        match selftest::TestServer::start().await {
+            Ok(server) => Some(server),
+            Err(e) => {
+                clean_up(handles, tasks).await?;
+                bail!(e);
+            }
+        }
+    } else {
+        None
+    };
+
     // get targets from command line or stdin
-    let targets = match get_targets(handles.clone()).await {
-        Ok(t) => t,
-        Err(e) => {
-            // should only happen in the event that there was an error reading from stdin
-            clean_up(handles, tasks).await?;
-            bail!("Could not determine initial targets: {}", e);
+    let targets = if let Some(server) = &selftest_server {
+        server.targets()
+    } else {
+        match get_targets(handles.clone()).await {
+            Ok(t) => t,
+            Err(e) => {
+                // should only happen in the event that there was an error reading from stdin
+                clean_up(handles, tasks).await?;
+                bail!("Could not determine initial targets: {}", e);
+            }
         }
     };
 
-    // --parallel branch
-    if config.parallel > 0 {
+    // --parallel branch; `selftest` has a single server to scan, no children are needed
+    if config.parallel > 0 && !config.selftest {
         log::trace!("enter: parallel branch");
 
         PARALLEL_LIMITER.add_permits(config.parallel);
@@ -386,6 +398,21 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         // from removing --parallel)
         original.remove(parallel_index);
 
+        // same goes for --import-nmap; only the original process reads the xml file, each child
+        // gets one of the resulting targets via -u below
+        while let Some(import_index) = original.iter().position(|s| *s == "--import-nmap") {
+            original.remove(import_index); // --import-nmap
+            original.remove(import_index); // XML_FILE
+        }
+        original.retain(|s| !s.starts_with("--import-nmap="));
+
+        // --ports was already applied above, each child gets a single host and port via -u
+        while let Some(ports_index) = original.iter().position(|s| *s == "--ports") {
+            original.remove(ports_index); // --ports
+            original.remove(ports_index); // PORTS
+        }
+        original.retain(|s| !s.starts_with("--ports="));
+
         // to log unique files to a shared folder, we need to first check for the presence
         // of -o|--output.
         let out_dir = if !config.output.is_empty() {
@@ -479,11 +506,29 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         return Ok(());
     }
 
+    // FUZZ keywords in the targets, headers, or data; the wordlist becomes every combination of
+    // the keywords' words
+    match template::initialize(
+        &targets,
+        &config,
+        &handles.wordlist(),
+        &handles.features.template,
+    ) {
+        Ok(Some(combinations)) => handles.set_wordlist(Arc::new(combinations)),
+        Ok(None) => {}
+        Err(e) => {
+            clean_up(handles, tasks).await?;
+            bail!(fmt_err(&e.to_string()));
+        }
+    }
+
     if matches!(config.output_level, OutputLevel::Default) {
         // only print banner if output level is default (no banner on --quiet|--silent)
         let std_stderr = stderr(); // std::io::stderr
 
         let mut banner = Banner::new(&targets, &config);
+        banner.add_scheme_detections(&handles.features.scheme.detections());
+        banner.add_scan_id(&config.request_id, handles.features.request_ids.scan_id());
 
         // only interested in the side-effect that sets banner.update_status
         let _ = banner.check_for_updates(UPDATE_URL, handles.clone()).await;
@@ -494,6 +539,26 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         }
     }
 
+    if config.safe_methods_only {
+        safety::warn_about_words(&handles.wordlist(), config.output_level);
+    }
+
+    // --preview; a resumed scan picks up where it left off, the start of it isn't worth showing
+    if config.preview > 0 && !config.resumed {
+        match scanner::preview::preview(&targets, handles.clone()) {
+            Ok(true) => {}
+            Ok(false) => {
+                clean_up(handles, tasks).await?;
+                eprintln!("Scan not started, no requests were sent");
+                return Ok(());
+            }
+            Err(e) => {
+                clean_up(handles, tasks).await?;
+                bail!(fmt_err(&format!("Could not preview requests: {e}")));
+            }
+        }
+    }
+
     {
         let send_to_file = !config.output.is_empty();
 
@@ -511,12 +576,13 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
     // discard non-responsive targets
     let live_targets = {
         let test = heuristics::HeuristicTests::new(handles.clone());
-        let result = test.connectivity(&targets).await;
-        if result.is_err() {
-            clean_up(handles, tasks).await?;
-            bail!(fmt_err(&result.unwrap_err().to_string()));
+        match test.connectivity(&targets).await {
+            Ok(live) => live,
+            Err(e) => {
+                clean_up(handles, tasks).await?;
+                bail!(fmt_err(&e.to_string()));
+            }
         }
-        result?
     };
 
     if live_targets.is_empty() {
@@ -524,6 +590,53 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         bail!(fmt_err("Could not find any live targets to scan"));
     }
 
+    // --baseline-url; fetched once, every response after this is compared against it
+    if let Err(e) = diff::initialize(handles.clone()).await {
+        clean_up(handles, tasks).await?;
+        return Err(e);
+    }
+
+    // --fan-out-ips; each live target's host is resolved once, requests take turns on its
+    // addresses from here on
+    if let Err(e) = fanout::initialize(&live_targets, handles.clone()).await {
+        clean_up(handles, tasks).await?;
+        return Err(e);
+    }
+
+    // --stability-check; earlier findings get requested again every so often
+    stability::start(handles.clone());
+
+    if config.detect_case && !handles.features.template.is_active() {
+        // --detect-case; the wordlist is shared by every target, so it's only normalized when
+        // all of them ignore case
+        let test = heuristics::HeuristicTests::new(handles.clone());
+
+        match test.case_insensitivity(&live_targets).await {
+            Ok(true) => {
+                let original = handles.wordlist();
+                let lowered = lowercase_words(&original);
+
+                if matches!(
+                    config.output_level,
+                    OutputLevel::Default | OutputLevel::Quiet
+                ) {
+                    ferox_eprint(
+                        &format!(
+                            "Target ignores case, lowercased the wordlist ({} => {} words)",
+                            original.len() - 1,
+                            lowered.len() - 1
+                        ),
+                        &PROGRESS_PRINTER,
+                    );
+                }
+
+                handles.set_wordlist(lowered);
+            }
+            Ok(false) => {}
+            Err(e) => log::warn!("Could not determine whether the target ignores case: {}", e),
+        }
+    }
+
     // kick off a scan against any targets determined to be responsive
     match scan(live_targets, handles.clone()).await {
         Ok(_) => {}
@@ -533,7 +646,36 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         }
     }
 
-    clean_up(handles, tasks).await?;
+    clean_up(handles.clone(), tasks).await?;
+
+    // --record; the filters learned along the way close out the recording
+    if let Some(recorder) = &handles.features.session {
+        recorder.finish(&handles.filters.data)?;
+    }
+
+    // --summary; written after the handlers drain, so every count is final
+    if !config.summary.is_empty() {
+        summary::write(&config.summary, &handles, started.elapsed().as_secs_f64())?;
+    }
+
+    // --junit; every finding has been through the rules by now
+    if !config.junit.is_empty() {
+        handles.features.rules.write_junit(&config.junit)?;
+    }
+
+    // `selftest`; the findings and counts are final, compare them with what the server holds
+    if selftest_server.is_some() && !selftest::verify(&handles) {
+        exit(1);
+    }
+
+    // --exit-code/--fail-on-rule; every handler has drained, so the counts are final
+    if let Some((code, reason)) = exit_policy::evaluate(&handles) {
+        if !matches!(config.output_level, OutputLevel::Silent) {
+            eprintln!("{reason}, exiting with {code}");
+        }
+
+        exit(code);
+    }
 
     log::trace!("exit: wrapped_main");
     Ok(())
@@ -550,6 +692,26 @@ async fn clean_up(handles: Arc<Handles>, tasks: Tasks) -> Result<()> {
 
     log::info!("All scans complete!");
 
+    // --bypass-403; attempts report through the terminal handler, which needs to stay up for them
+    bypass::finish(&handles).await;
+    log::trace!("bypass attempts finished");
+
+    // --vcs-check; same as the bypass attempts
+    vcs::finish(&handles).await;
+    log::trace!("repository checks finished");
+
+    // --archive-peek; same as the bypass attempts
+    archives::finish(&handles).await;
+    log::trace!("archive listings finished");
+
+    // --fan-out-ips; same as the bypass attempts
+    fanout::finish(&handles).await;
+    log::trace!("per-ip comparisons finished");
+
+    // --stability-check; the last round reports through the terminal handler too
+    stability::finish(handles.clone()).await;
+    log::trace!("stability checks finished");
+
     // terminal handler closes file handler if one is in use
     handles.output.send(Exit)?;
     tasks.terminal.await??;
@@ -563,6 +725,51 @@ async fn clean_up(handles: Arc<Handles>, tasks: Tasks) -> Result<()> {
     tasks.stats.await??;
     log::trace!("stats handler closed");
 
+    // --screenshots; the output handler is gone, nothing else gets queued up
+    let screenshot_handles = handles.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Some(screenshotter) = &screenshot_handles.features.screenshots {
+            screenshotter.finish();
+        }
+    })
+    .await?;
+    log::trace!("screenshot workers finished");
+
+    if matches!(handles.config.output_level, OutputLevel::Default) {
+        // let the user know where responses came from and how much each filter removed, in case
+        // one is hiding real findings
+        if let Some(summary) = handles
+            .ferox_scans()
+            .ok()
+            .and_then(|scans| scans.status_code_summary())
+        {
+            ferox_eprint(&summary, &PROGRESS_PRINTER);
+        }
+
+        if let Some(summary) = handles
+            .ferox_scans()
+            .ok()
+            .and_then(|scans| scans.error_summary())
+        {
+            ferox_eprint(&summary, &PROGRESS_PRINTER);
+        }
+
+        if !handles.config.ports.is_empty() {
+            // --ports spreads each host over several targets, put them back together
+            if let Some(summary) = handles
+                .ferox_scans()
+                .ok()
+                .and_then(|scans| scans.host_summary())
+            {
+                ferox_eprint(&summary, &PROGRESS_PRINTER);
+            }
+        }
+
+        if let Some(summary) = handles.stats.data.filter_summary() {
+            ferox_eprint(&summary, &PROGRESS_PRINTER);
+        }
+    }
+
     // mark all scans complete so the terminal input handler will exit cleanly
     SCAN_COMPLETE.store(true, Ordering::Relaxed);
 
@@ -593,7 +800,16 @@ async fn update_app() -> Result<self_update::Status, Box<dyn ::std::error::Error
 }
 
 fn main() -> Result<()> {
-    let config = Arc::new(Configuration::new().with_context(|| "Could not create Configuration")?);
+    // this function uses rlimit, which is not supported on windows; it's called before clients
+    // are built so that their connection pools are sized from the raised limit
+    #[cfg(not(target_os = "windows"))]
+    set_open_file_limit(DEFAULT_OPEN_FILE_LIMIT);
+
+    let mut config = Configuration::new().with_context(|| "Could not create Configuration")?;
+
+    let requested_threads = clamp_threads_to_open_files(&mut config);
+
+    let config = Arc::new(config);
 
     // setup logging based on the number of -v's used
     if matches!(
@@ -604,15 +820,22 @@ fn main() -> Result<()> {
         logger::initialize(config.clone())?;
     }
 
-    // this function uses rlimit, which is not supported on windows
-    #[cfg(not(target_os = "windows"))]
-    set_open_file_limit(DEFAULT_OPEN_FILE_LIMIT);
+    // --traffic-log, etc...; a bad value for any of them is reported before any requests are made
+    let features = Features::new(&config)?;
+
+    if let Some(requested) = requested_threads {
+        log::warn!(
+            "--threads {} would exceed the open file limit, using {} instead",
+            requested,
+            config.threads
+        );
+    }
 
     if let Ok(runtime) = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()
     {
-        let future = wrapped_main(config.clone());
+        let future = wrapped_main(config.clone(), features);
         if let Err(e) = runtime.block_on(future) {
             eprintln!("{e}");
 
@@ -0,0 +1,184 @@
+//! burst/quiet request cycles (--pacing)
+//!
+//! every scan draws from the same cycle: once the burst's worth of requests has been sent, all
+//! scans go quiet until the pause is over, then the next burst starts. pacing sits on top of
+//! --rate-limit and friends, which still decide how quickly a burst is sent
+//!
+//! the cycle isn't saved in the state file; a resumed scan starts with a fresh burst
+use std::{sync::Mutex, time::Instant};
+
+use anyhow::{Context, Result};
+use tokio::time::{sleep, Duration};
+
+use crate::{
+    config::Configuration,
+    parser::{PACINGSPEC_REGEX, PACING_PROFILES},
+    scan_manager::{time_spec_in_secs, FeroxScan},
+    utils::fmt_err,
+};
+
+/// --pacing; shared by every scan
+#[derive(Debug, Default)]
+pub struct Pacing {
+    /// `None` when --pacing wasn't used
+    pacer: Option<Mutex<Pacer>>,
+}
+
+/// Burst/quiet cycle shared by all scans
+#[derive(Debug)]
+struct Pacer {
+    /// number of requests sent per burst
+    burst: usize,
+
+    /// how long to stay quiet between bursts
+    pause: Duration,
+
+    /// requests sent so far in the current burst
+    sent: usize,
+
+    /// end of the current quiet period, `None` while a burst is under way
+    quiet_until: Option<Instant>,
+}
+
+/// implementation of Pacer
+impl Pacer {
+    /// new pacer, starting with a full burst
+    fn new(burst: usize, pause: Duration) -> Self {
+        Self {
+            burst,
+            pause,
+            sent: 0,
+            quiet_until: None,
+        }
+    }
+
+    /// take a request from the current burst; when the burst has run out, or is yet to start,
+    /// returns how long is left of the quiet period instead
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(until) = self.quiet_until {
+            if now < until {
+                return Some(until - now);
+            }
+
+            self.quiet_until = None;
+            self.sent = 0;
+        }
+
+        if self.sent < self.burst {
+            self.sent += 1;
+            return None;
+        }
+
+        log::info!(
+            "sent {} requests, pacing quiet for {:?}",
+            self.sent,
+            self.pause
+        );
+
+        self.quiet_until = Some(now + self.pause);
+        Some(self.pause)
+    }
+}
+
+/// parse a --pacing value, either the name of a profile or N/TIME_SPEC, into the number of
+/// requests per burst and the pause that follows
+fn parse_pacing(pacing: &str) -> Option<(usize, Duration)> {
+    let spec = PACING_PROFILES
+        .iter()
+        .find(|(name, _)| *name == pacing)
+        .map_or(pacing, |(_, spec)| *spec);
+
+    let captures = PACINGSPEC_REGEX.captures(spec)?;
+    let burst = captures.name("n")?.as_str().parse().ok()?;
+    let pause = time_spec_in_secs(captures.name("t")?.as_str())?;
+
+    (burst > 0).then(|| (burst, Duration::from_secs(pause)))
+}
+
+/// set up the --pacing cycle; nothing is paced when it wasn't used
+pub fn initialize(config: &Configuration) -> Result<Pacing> {
+    if config.pacing.is_empty() {
+        return Ok(Pacing::default());
+    }
+
+    let (burst, pause) = parse_pacing(&config.pacing)
+        .with_context(|| fmt_err(&format!("Invalid pacing profile given: {}", config.pacing)))?;
+
+    Ok(Pacing {
+        pacer: Some(Mutex::new(Pacer::new(burst, pause))),
+    })
+}
+
+/// implementation of Pacing
+impl Pacing {
+    /// wait for the given scan's turn in the current burst, sitting out any quiet period first
+    pub async fn pace(&self, scan: &FeroxScan) {
+        let Some(pacer) = &self.pacer else {
+            return;
+        };
+
+        let mut waited = false;
+
+        loop {
+            let wait = match pacer.lock() {
+                Ok(mut pacer) => pacer.take(Instant::now()),
+                Err(_) => None,
+            };
+
+            let Some(wait) = wait else {
+                break;
+            };
+
+            if !waited {
+                scan.progress_bar()
+                    .set_message(format!("=> 💤 pacing, quiet for {}s", wait.as_secs()));
+                waited = true;
+            }
+
+            // other scans may have taken the start of the next burst while waiting, so check
+            // again
+            sleep(wait).await;
+        }
+
+        if waited {
+            scan.progress_bar().set_message("");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// a burst is handed out one request at a time, then everything waits out the pause
+    fn pacer_cycles_bursts_and_pauses() {
+        let start = Instant::now();
+        let mut pacer = Pacer::new(2, Duration::from_secs(60));
+
+        assert_eq!(pacer.take(start), None);
+        assert_eq!(pacer.take(start), None);
+        assert_eq!(pacer.take(start), Some(Duration::from_secs(60)));
+
+        let later = start + Duration::from_secs(45);
+        assert_eq!(pacer.take(later), Some(Duration::from_secs(15)));
+
+        // the pause is over, the next burst starts
+        let done = start + Duration::from_secs(60);
+        assert_eq!(pacer.take(done), None);
+        assert_eq!(pacer.take(done), None);
+        assert!(pacer.take(done).is_some());
+    }
+
+    #[test]
+    /// profiles are looked up by name, anything else is parsed as N/TIME_SPEC
+    fn parse_pacing_profiles_and_specs() {
+        assert_eq!(
+            parse_pacing("low-and-slow"),
+            Some((20, Duration::from_secs(600)))
+        );
+        assert_eq!(parse_pacing("50/2h"), Some((50, Duration::from_secs(7200))));
+        assert_eq!(parse_pacing("0/5m"), None);
+        assert_eq!(parse_pacing("sneaky"), None);
+    }
+}
@@ -18,6 +18,34 @@ lazy_static! {
     pub static ref TIMESPEC_REGEX: Regex =
         Regex::new(r"^(?i)(?P<n>\d+)(?P<m>[smdh])$").expect("Could not compile regex");
 
+    /// Regex used to validate values passed to --traffic-log-max
+    ///
+    /// Examples of expected values that will this regex will match:
+    /// - 4096
+    /// - 500K
+    /// - 100MB
+    /// - 1G
+    pub static ref SIZESPEC_REGEX: Regex =
+        Regex::new(r"^(?i)(?P<n>\d+)(?P<u>[kmg])?b?$").expect("Could not compile regex");
+
+    /// Regex used to validate values passed to --tune-on-latency
+    ///
+    /// Examples of expected values that will this regex will match:
+    /// - 2x-baseline
+    /// - 1.5x-baseline
+    /// - 3x
+    pub static ref LATENCYSPEC_REGEX: Regex =
+        Regex::new(r"^(?i)(?P<n>\d+(\.\d+)?)x(-baseline)?$").expect("Could not compile regex");
+
+    /// Regex used to validate custom values passed to --pacing; a number of requests, followed by
+    /// how long to stay quiet after sending them
+    ///
+    /// Examples of expected values that will this regex will match:
+    /// - 100/5m
+    /// - 20/1h
+    pub static ref PACINGSPEC_REGEX: Regex =
+        Regex::new(r"^(?i)(?P<n>\d+)/(?P<t>\d+[smdh])$").expect("Could not compile regex");
+
     /// help string for user agent, your guess is as good as mine as to why this is required...
     static ref DEFAULT_USER_AGENT: String = format!(
         "Sets the User-Agent (default: feroxbuster/{})",
@@ -40,12 +68,12 @@ pub fn initialize() -> Command {
             Arg::new("url")
                 .short('u')
                 .long("url")
-                .required_unless_present_any(["stdin", "resume_from", "update_app"])
+                .required_unless_present_any(["stdin", "resume_from", "import_nmap", "update_app"])
                 .help_heading("Target selection")
                 .value_name("URL")
                 .use_value_delimiter(true)
                 .value_hint(ValueHint::Url)
-                .help("The target URL (required, unless [--stdin || --resume-from] used)"),
+                .help("The target URL (required, unless [--stdin || --resume-from || --import-nmap] used)"),
         )
         .arg(
             Arg::new("stdin")
@@ -64,6 +92,36 @@ pub fn initialize() -> Command {
                 .help("State file from which to resume a partially complete scan (ex. --resume-from ferox-1606586780.state)")
                 .conflicts_with("url")
                 .num_args(1),
+        )
+        .arg(
+            Arg::new("import_nmap")
+                .long("import-nmap")
+                .value_hint(ValueHint::FilePath)
+                .value_name("XML_FILE")
+                .help_heading("Target selection")
+                .help("Read http(s) targets from the open ports in Nmap/Masscan XML output (ex. --import-nmap scan.xml)")
+                .conflicts_with_all(["url", "stdin", "resume_from"])
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("ports")
+                .long("ports")
+                .value_name("PORTS")
+                .num_args(1)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Target selection")
+                .help("Scan each target host on every given port, guessing the scheme from the port (ex. --ports 80,443,8080,8443)"),
+        )
+        .arg(
+            Arg::new("scheme_probes")
+                .long("scheme-probes")
+                .value_name("SCHEME:PORT")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Target selection")
+                .help("Schemes and ports tried, in order, for targets given without a scheme (default: https:443 http:80)"),
         );
 
     /////////////////////////////////////////////////////////////////////
@@ -91,7 +149,7 @@ pub fn initialize() -> Command {
                 .long("smart")
                 .num_args(0)
                 .help_heading("Composite settings")
-                .conflicts_with_all(["rate_limit", "auto_bail"])
+                .conflicts_with_all(["rate_limit", "auto_bail", "auto_requeue"])
                 .help("Set --auto-tune, --collect-words, and --collect-backups to true"),
         )
         .arg(
@@ -99,7 +157,7 @@ pub fn initialize() -> Command {
                 .long("thorough")
                 .num_args(0)
                 .help_heading("Composite settings")
-                .conflicts_with_all(["rate_limit", "auto_bail"])
+                .conflicts_with_all(["rate_limit", "auto_bail", "auto_requeue"])
                 .help("Use the same settings as --smart and set --collect-extensions to true"),
         );
 
@@ -119,6 +177,26 @@ pub fn initialize() -> Command {
                     "Proxy to use for requests (ex: http(s)://host:port, socks5(h)://host:port)",
                 ),
         )
+        .arg(
+            Arg::new("proxy_failover")
+                .long("proxy-failover")
+                .value_name("PROXY")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .value_hint(ValueHint::Url)
+                .requires("proxy")
+                .help_heading("Proxy settings")
+                .help("Proxies to fail over to, in order, when the current one keeps refusing connections"),
+        )
+        .arg(
+            Arg::new("proxy_failover_direct")
+                .long("proxy-failover-direct")
+                .num_args(0)
+                .requires("proxy")
+                .help_heading("Proxy settings")
+                .help("Connect directly once every proxy has failed, instead of turning requests into errors"),
+        )
         .arg(
             Arg::new("replay_proxy")
                 .short('P')
@@ -144,6 +222,38 @@ pub fn initialize() -> Command {
                 .help(
                     "Status Codes to send through a Replay Proxy when found (default: --status-codes value)",
                 ),
+        )
+        .arg(
+            Arg::new("replay_headers")
+                .long("replay-headers")
+                .value_name("HEADER")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Proxy settings")
+                .help(
+                    "HTTP headers added to requests sent through a Replay Proxy (ex: --replay-headers X-Tag:ferox)",
+                ),
+        )
+        .arg(
+            Arg::new("replay_client_cert")
+                .long("replay-client-cert")
+                .value_name("PEM")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1)
+                .requires("replay_client_key")
+                .help_heading("Proxy settings")
+                .help("PEM encoded certificate for mTLS on requests sent through a Replay Proxy (default: --client-cert)"),
+        )
+        .arg(
+            Arg::new("replay_client_key")
+                .long("replay-client-key")
+                .value_name("PEM")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1)
+                .requires("replay_client_cert")
+                .help_heading("Proxy settings")
+                .help("PEM encoded private key that goes with --replay-client-cert"),
         );
 
     /////////////////////////////////////////////////////////////////////
@@ -180,6 +290,15 @@ pub fn initialize() -> Command {
                     "File extension(s) to search for (ex: -x php -x pdf js)",
                 ),
         )
+        .arg(
+            Arg::new("extension_order")
+                .long("extension-order")
+                .value_name("ORDER")
+                .num_args(1)
+                .value_parser(["sequential", "interleaved", "shuffled"])
+                .help_heading("Request settings")
+                .help("Order in which each word's extensions are requested; interleaved/shuffled spread them across words (default: sequential)"),
+        )
         .arg(
             Arg::new("methods")
                 .short('m')
@@ -193,6 +312,21 @@ pub fn initialize() -> Command {
                     "Which HTTP request method(s) should be sent (default: GET)",
                 ),
         )
+        .arg(
+            Arg::new("safe_methods_only")
+                .long("safe-methods-only")
+                .num_args(0)
+                .help_heading("Request settings")
+                .help("Refuse to send anything but GET/HEAD and warn about words that look like state-changing actions (logout, delete, ...)"),
+        )
+        .arg(
+            Arg::new("raw_requests")
+                .long("raw-requests")
+                .num_args(0)
+                .conflicts_with("proxy")
+                .help_heading("Request settings")
+                .help("Send words the url parser would rewrite (spaces, control characters, \\, #, ../) byte for byte over a minimal HTTP/1.1 client"),
+        )
         .arg(
             Arg::new("data")
                 .long("data")
@@ -213,7 +347,17 @@ pub fn initialize() -> Command {
                 .help_heading("Request settings")
                 .use_value_delimiter(true)
                 .help(
-                    "Specify HTTP headers to be used in each request (ex: -H Header:val -H 'stuff: things')",
+                    "Specify HTTP headers to be used in each request (ex: -H Header:val -H 'stuff: things'); {{uuid}}, {{timestamp}}, and {{counter}} in a value are expanded per request",
+                ),
+        )
+        .arg(
+            Arg::new("request_id")
+                .long("request-id")
+                .value_name("HEADER")
+                .num_args(1)
+                .help_heading("Request settings")
+                .help(
+                    "Tag each request with a unique id in the given header, for correlation in the target's logs (ex: --request-id X-Ferox-Scan-Id)",
                 ),
         )
         .arg(
@@ -249,6 +393,51 @@ pub fn initialize() -> Command {
                 .help_heading("Request settings")
                 .num_args(0)
                 .help("Append / to each request's URL")
+        )
+        .arg(
+            Arg::new("dual_slash")
+                .long("dual-slash")
+                .num_args(0)
+                .help_heading("Request settings")
+                .help("Request each word both with and without a trailing /, only recursing into word/ when its response differs from word's")
+        )
+        .arg(
+            Arg::new("encode")
+                .long("encode")
+                .value_name("ENCODING")
+                .num_args(1)
+                .value_parser(["none", "url", "double-url", "unicode"])
+                .help_heading("Request settings")
+                .help("Encode each word before it's added to the URL; slashes are left alone (default: none)")
+        ).arg(
+            Arg::new("evasion")
+                .long("evasion")
+                .value_name("PROFILE")
+                .num_args(1)
+                .help_heading("Request settings")
+                .help("Mutate each request's path to get past WAFs: light, moderate, aggressive, a single transform (dup-slash, dot-segment, path-params, trailing-junk), or a profile from [evasion_profiles] in ferox-config.toml")
+        )
+        .arg(
+            Arg::new("range_probe")
+                .long("range-probe")
+                .num_args(0)
+                .help_heading("Request settings")
+                .help("Send GETs with a Range: bytes=0-0 header first, only fetching full bodies for responses that aren't filtered by status code or size")
+        )
+        .arg(
+            Arg::new("accept_encoding")
+                .long("accept-encoding")
+                .value_name("ENCODINGS")
+                .num_args(1)
+                .help_heading("Request settings")
+                .help("Value of the Accept-Encoding header, i.e. identity or 'gzip, deflate' (default: not sent; -H takes precedence)")
+        )
+        .arg(
+            Arg::new("decompress")
+                .long("decompress")
+                .num_args(0)
+                .help_heading("Request settings")
+                .help("Decompress gzip/deflate bodies before filtering and extraction; sizes are those of the decompressed bodies")
         );
 
     /////////////////////////////////////////////////////////////////////
@@ -263,6 +452,15 @@ pub fn initialize() -> Command {
             .use_value_delimiter(true)
             .help_heading("Request filters")
             .help("URL(s) or Regex Pattern(s) to exclude from recursion/scans"),
+    )
+    .arg(
+        Arg::new("dont_scan_regex")
+            .long("dont-scan-regex")
+            .value_name("REGEX")
+            .num_args(1..)
+            .action(ArgAction::Append)
+            .help_heading("Request filters")
+            .help("Regex Pattern(s) matched against every url before it's requested or recursed into; matches are never touched (ex: --dont-scan-regex '/(logout|delete)')"),
     );
 
     /////////////////////////////////////////////////////////////////////
@@ -348,6 +546,18 @@ pub fn initialize() -> Command {
                     "Filter out pages that are similar to the given page (ex. --filter-similar-to http://site.xyz/soft404)",
                 ),
         )
+        .arg(
+            Arg::new("filter_category")
+                .long("filter-category")
+                .value_name("CATEGORY")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Response filters")
+                .help(
+                    "Filter out responses tagged with the given categories (ex. --filter-category backup-file,api-spec)",
+                ),
+        )
         .arg(
             Arg::new("status_codes")
                 .short('s')
@@ -375,6 +585,22 @@ pub fn initialize() -> Command {
                 .help_heading("Client settings")
                 .help("Number of seconds before a client's request times out (default: 7)"),
         )
+        .arg(
+            Arg::new("connect_timeout")
+                .long("connect-timeout")
+                .value_name("SECONDS")
+                .num_args(1)
+                .help_heading("Client settings")
+                .help("Number of seconds a client may spend connecting, counted as connect-timeout errors (default: --timeout)"),
+        )
+        .arg(
+            Arg::new("read_timeout")
+                .long("read-timeout")
+                .value_name("SECONDS")
+                .num_args(1)
+                .help_heading("Client settings")
+                .help("Number of seconds a response body may stall, counted as read-timeout errors (default: --timeout)"),
+        )
         .arg(
             Arg::new("redirects")
                 .short('r')
@@ -391,6 +617,58 @@ pub fn initialize() -> Command {
                 .help_heading("Client settings")
                 .help("Disables TLS certificate validation in the client"),
         )
+        .arg(
+            Arg::new("host_header")
+                .long("host-header")
+                .value_name("HOST")
+                .num_args(1)
+                .requires("url")
+                .help_heading("Client settings")
+                .help("Send HOST as the Host header (and TLS SNI) while connecting to the --url target, i.e. an origin IP behind a CDN"),
+        )
+        .arg(
+            Arg::new("sni")
+                .long("sni")
+                .value_name("NAME")
+                .num_args(1)
+                .requires("host_header")
+                .help_heading("Client settings")
+                .help("Present NAME via TLS SNI instead of the --host-header value"),
+        )
+        .arg(
+            Arg::new("doh")
+                .long("doh")
+                .value_name("URL")
+                .num_args(1)
+                .value_hint(ValueHint::Url)
+                .help_heading("Client settings")
+                .help("Resolve target host names through a DNS-over-HTTPS endpoint (ex: --doh https://1.1.1.1/dns-query)"),
+        )
+        .arg(
+            Arg::new("ipv4")
+                .short('4')
+                .long("ipv4")
+                .num_args(0)
+                .conflicts_with("ipv6")
+                .help_heading("Client settings")
+                .help("Only connect to targets over IPv4"),
+        )
+        .arg(
+            Arg::new("ipv6")
+                .short('6')
+                .long("ipv6")
+                .num_args(0)
+                .help_heading("Client settings")
+                .help("Only connect to targets over IPv6"),
+        )
+        .arg(
+            Arg::new("fan_out_ips")
+                .long("fan-out-ips")
+                .num_args(0)
+                .conflicts_with_all(["proxy", "host_header"])
+                .help_heading("Client settings")
+                .help("Spread requests across every address the target resolves to, and report findings that differ between them"),
+        )
         .arg(
             Arg::new("server_certs")
                 .long("server-certs")
@@ -419,6 +697,24 @@ pub fn initialize() -> Command {
                 .requires("client_cert")
                 .help_heading("Client settings")
                 .help("Add a PEM encoded private key for mutual authentication (mTLS)"),
+        )
+        .arg(
+            Arg::new("ca_bundle")
+                .long("ca-bundle")
+                .value_name("PEM")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1)
+                .help_heading("Client settings")
+                .help("Trust every certificate in a PEM encoded CA bundle (i.e. a TLS intercepting proxy's CA)"),
+        )
+        .arg(
+            Arg::new("pin_sha256")
+                .long("pin-sha256")
+                .value_name("FINGERPRINT")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .help_heading("Client settings")
+                .help("Only scan https targets whose certificate chain includes a certificate with the given SHA-256 fingerprint (hex, colons optional)"),
         );
 
     /////////////////////////////////////////////////////////////////////
@@ -457,6 +753,21 @@ pub fn initialize() -> Command {
                 .conflicts_with("no_recursion")
                 .help_heading("Scan settings")
                 .help("Force recursion attempts on all 'found' endpoints (still respects recursion depth)"),
+        ).arg(
+            Arg::new("directories_first")
+                .long("directories-first")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Request every word without extensions, across all recursion levels, before coming back for the extensions"),
+        ).arg(
+            Arg::new("inherit_headers")
+                .long("inherit-headers")
+                .value_name("HEADER")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Scan settings")
+                .help("Response headers from a found directory to send with requests made by its recursive scan; Set-Cookie is sent as cookies (ex: --inherit-headers Set-Cookie,X-CSRF-Token)"),
         ).arg(
             Arg::new("extract_links")
                 .short('e')
@@ -473,6 +784,46 @@ pub fn initialize() -> Command {
                 .help_heading("Scan settings")
                 .help("Don't extract links from response body (html, javascript, etc...)")
         )
+        .arg(
+            Arg::new("extract_page_limit")
+                .long("extract-page-limit")
+                .value_name("NUM_LINKS")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Limit number of extracted links requested per page (default: 0, i.e. no limit)")
+        )
+        .arg(
+            Arg::new("extract_scan_limit")
+                .long("extract-scan-limit")
+                .value_name("NUM_LINKS")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Limit number of extracted links requested per scan (default: 0, i.e. no limit)")
+        )
+        .arg(
+            Arg::new("extract_limit")
+                .long("extract-limit")
+                .value_name("NUM_LINKS")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Limit number of extracted links requested across all scans (default: 0, i.e. no limit)")
+        )
+        .arg(
+            Arg::new("extract_depth")
+                .long("extract-depth")
+                .value_name("DEPTH")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Stop extracting links from directories reached through this many extractions (default: 0, i.e. no limit)")
+        )
+        .arg(
+            Arg::new("crawl")
+                .long("crawl")
+                .num_args(0)
+                .conflicts_with("dont_extract_links")
+                .help_heading("Scan settings")
+                .help("Don't use the wordlist; only follow links extracted from each target, up to --depth links deep")
+        )
         .arg(
             Arg::new("scan_limit")
                 .short('L')
@@ -480,7 +831,34 @@ pub fn initialize() -> Command {
                 .value_name("SCAN_LIMIT")
                 .num_args(1)
                 .help_heading("Scan settings")
-                .help("Limit total number of concurrent scans (default: 0, i.e. no limit)")
+                .help("Limit total number of concurrent scans, others wait as pending (default: 0, i.e. no limit)")
+        )
+        .arg(
+            Arg::new("recursion_strategy")
+                .long("recursion-strategy")
+                .value_name("STRATEGY")
+                .num_args(1)
+                .value_parser(["bfs", "dfs"])
+                .requires("scan_limit")
+                .help_heading("Scan settings")
+                .help("Order in which pending scans found by recursion start under --scan-limit; breadth or depth first (default: bfs)")
+        )
+        .arg(
+            Arg::new("shuffle")
+                .long("shuffle")
+                .value_name("SEED")
+                .num_args(0..=1)
+                .help_heading("Scan settings")
+                .help("Request the wordlist in a random order per scan, reproducible from SEED (default: random seed, saved for --resume-from)")
+        )
+        .arg(
+            Arg::new("preview")
+                .long("preview")
+                .value_name("N")
+                .num_args(1)
+                .conflicts_with_all(["crawl", "stdin"])
+                .help_heading("Scan settings")
+                .help("Print the first N fully-built requests without sending them, then ask whether to start the scan")
         )
         .arg(
             Arg::new("parallel")
@@ -500,6 +878,32 @@ pub fn initialize() -> Command {
                 .help_heading("Scan settings")
                 .help("Limit number of requests per second (per directory) (default: 0, i.e. no limit)")
         )
+        .arg(
+            Arg::new("rate_limit_global")
+                .long("rate-limit-global")
+                .value_name("RATE_LIMIT")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Limit number of requests per second across all scans combined (default: 0, i.e. no limit)")
+        )
+        .arg(
+            Arg::new("bandwidth_limit")
+                .long("bandwidth-limit")
+                .value_name("SIZE_SPEC")
+                .value_parser(valid_size_spec)
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Limit the bytes per second received across all scans, measured from response sizes (ex: --bandwidth-limit 512K)")
+        )
+        .arg(
+            Arg::new("pacing")
+                .long("pacing")
+                .value_name("PROFILE")
+                .value_parser(valid_pacing_spec)
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Send requests in bursts with quiet periods between them, across all scans: low-and-slow (20/10m), quiet (100/2m), burst (1000/30s), or N/TIME_SPEC (ex: --pacing 50/5m)")
+        )
         .arg(
             Arg::new("time_limit")
                 .long("time-limit")
@@ -515,55 +919,419 @@ pub fn initialize() -> Command {
                 .long("wordlist")
                 .value_hint(ValueHint::FilePath)
                 .value_name("FILE")
-                .help("Path or URL of the wordlist")
+                .help("Path or URL of the wordlist; - reads it from STDIN, plugin:FILE has a lua script's wordlist() generate it, builtin:common|api use one built into feroxbuster")
                 .help_heading("Scan settings")
                 .num_args(1),
+        ).arg(
+            Arg::new("fuzz_wordlists")
+                .long("fuzz-wordlist")
+                .value_name("KEYWORD=FILE")
+                .num_args(1)
+                .action(ArgAction::Append)
+                .help_heading("Scan settings")
+                .help("Wordlist for a keyword other than FUZZ (ex: --fuzz-wordlist FUZZ2=users.txt); FUZZ markers in the url, headers, or --data are filled in from the wordlists instead of adding words to the url")
+        ).arg(
+            Arg::new("fuzz_mode")
+                .long("fuzz-mode")
+                .value_name("MODE")
+                .num_args(1)
+                .value_parser(["clusterbomb", "pitchfork"])
+                .help_heading("Scan settings")
+                .help("How FUZZ keyword wordlists are combined: every combination, or line by line (default: clusterbomb)")
         ).arg(
             Arg::new("auto_tune")
                 .long("auto-tune")
                 .num_args(0)
-                .conflicts_with("auto_bail")
+                .conflicts_with_all(["auto_bail", "auto_requeue"])
                 .help_heading("Scan settings")
                 .help("Automatically lower scan rate when an excessive amount of errors are encountered")
         )
         .arg(
-            Arg::new("auto_bail")
-                .long("auto-bail")
-                .num_args(0)
+            Arg::new("tune_strategy")
+                .long("tune-strategy")
+                .value_name("STRATEGY")
+                .num_args(1)
+                .value_parser(["heap", "gradient", "aimd"])
                 .help_heading("Scan settings")
-                .help("Automatically stop scanning when an excessive amount of errors are encountered")
-        ).arg(
-            Arg::new("dont_filter")
-                .short('D')
-                .long("dont-filter")
-                .num_args(0)
+                .help("Algorithm --auto-tune uses to pick a new scan rate (default: heap)")
+        )
+        .arg(
+            Arg::new("tune_on_latency")
+                .long("tune-on-latency")
+                .value_name("MULTIPLE")
+                .num_args(1)
+                .value_parser(valid_latency_spec)
                 .help_heading("Scan settings")
-                .help("Don't auto-filter wildcard responses")
-        ).arg(
-            Arg::new("collect_extensions")
-                .short('E')
-                .long("collect-extensions")
-                .num_args(0)
-                .help_heading("Dynamic collection settings")
-                .help("Automatically discover extensions and add them to --extensions (unless they're in --dont-collect)")
-        ).arg(
-            Arg::new("collect_backups")
-                .short('B')
-                .long("collect-backups")
-                .num_args(0)
-                .help_heading("Dynamic collection settings")
-                .help("Automatically request likely backup extensions for \"found\" urls")
+                .help("Also trigger --auto-tune/--auto-bail when the p95 response time rises past a multiple of the target's baseline (ex: --tune-on-latency 2x-baseline)")
         )
         .arg(
-            Arg::new("collect_words")
-                .short('g')
-                .long("collect-words")
-                .num_args(0)
-                .help_heading("Dynamic collection settings")
-                .help("Automatically discover important words from within responses and add them to the wordlist")
-        ).arg(
-            Arg::new("dont_collect")
-                .short('I')
+            Arg::new("policy_403_ratio")
+                .long("policy-403-ratio")
+                .value_name("RATIO")
+                .num_args(1)
+                .value_parser(valid_ratio)
+                .help_heading("Scan settings")
+                .help("Ratio of a scan's requests that must be 403s to trigger --auto-tune/--auto-bail (default: 0.9)")
+        )
+        .arg(
+            Arg::new("policy_429_ratio")
+                .long("policy-429-ratio")
+                .value_name("RATIO")
+                .num_args(1)
+                .value_parser(valid_ratio)
+                .help_heading("Scan settings")
+                .help("Ratio of a scan's requests that must be 429s to trigger --auto-tune/--auto-bail (default: 0.3)")
+        )
+        .arg(
+            Arg::new("policy_status")
+                .long("policy-status")
+                .value_name("STATUS_CODE")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Scan settings")
+                .help("Status codes that trigger --auto-tune/--auto-bail the same way 429s do, using --policy-429-ratio (ex: --policy-status 503,520)")
+        )
+        .arg(
+            Arg::new("policy_error_threshold")
+                .long("policy-error-threshold")
+                .value_name("ERRORS")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Minimum number of errors a scan must see to trigger --auto-tune/--auto-bail; half of --threads is used when higher (default: 25)")
+        )
+        .arg(
+            Arg::new("policy_error_weights")
+                .long("policy-error-weight")
+                .value_name("CATEGORY=WEIGHT")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Scan settings")
+                .help("How much each error of a category (dns, connect, tls, connect-timeout, timeout, read-timeout, proxy, body-read, ...) counts toward --policy-error-threshold (ex: --policy-error-weight timeout=2,tls=0)")
+        )
+        .arg(
+            Arg::new("tune_step")
+                .long("tune-step")
+                .value_name("REQS_PER_SEC")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Requests per second added after each error-free interval by --tune-strategy aimd (default: 1/20th of the scan rate)")
+        )
+        .arg(
+            Arg::new("tune_floor")
+                .long("tune-floor")
+                .value_name("REQS_PER_SEC")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Lowest requests per second --tune-strategy aimd will drop to (default: 1)")
+        )
+        .arg(
+            Arg::new("tune_ceiling")
+                .long("tune-ceiling")
+                .value_name("REQS_PER_SEC")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Highest requests per second --tune-strategy aimd will climb to (default: the scan rate when tuning started)")
+        )
+        .arg(
+            Arg::new("auto_bail")
+                .long("auto-bail")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Automatically stop scanning when an excessive amount of errors are encountered")
+        ).arg(
+            Arg::new("auto_requeue")
+                .long("auto-requeue")
+                .value_name("ATTEMPTS")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Like --auto-bail, but restart a bailed scan at half its rate after a cooldown, up to ATTEMPTS times")
+        ).arg(
+            Arg::new("auto_resume")
+                .long("auto-resume")
+                .value_name("SECONDS")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Pause all scans after SECONDS of nothing but connection errors (i.e. VPN drop), resuming once the target responds again (default: 0, i.e. disabled)")
+        ).arg(
+            Arg::new("canary")
+                .long("canary")
+                .value_name("PATH")
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("Periodically request a known-good PATH (or url) on each target's host, pausing the host's scans while it looks banned (ex: --canary /)")
+        ).arg(
+            Arg::new("canary_interval")
+                .long("canary-interval")
+                .value_name("SECONDS")
+                .num_args(1)
+                .requires("canary")
+                .help_heading("Scan settings")
+                .help("Number of seconds between --canary requests (default: 30)")
+        ).arg(
+            Arg::new("stability_check")
+                .long("stability-check")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Periodically re-request a sample of earlier findings, flagging the ones that come back differently (i.e. A/B backends)")
+        ).arg(
+            Arg::new("stability_interval")
+                .long("stability-interval")
+                .value_name("SECONDS")
+                .num_args(1)
+                .requires("stability_check")
+                .help_heading("Scan settings")
+                .help("Number of seconds between --stability-check rounds (default: 60)")
+        ).arg(
+            Arg::new("server_alerts")
+                .long("server-alerts")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Alert when a host's Server/X-Powered-By header changes mid-scan; counts toward --auto-tune/--auto-bail")
+        ).arg(
+            Arg::new("search_regex")
+                .long("search-regex")
+                .value_name("REGEX")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .help_heading("Scan settings")
+                .help("Report matches of REGEX in every response body, filtered ones included (ex: --search-regex 'api[_-]?key|BEGIN RSA')")
+        ).arg(
+            Arg::new("entropy_secrets")
+                .long("entropy-secrets")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Report high entropy base64/hex strings (likely keys and tokens) found in text responses, filtered ones included")
+        ).arg(
+            Arg::new("entropy_threshold")
+                .long("entropy-threshold")
+                .value_name("BITS")
+                .num_args(1)
+                .value_parser(valid_entropy)
+                .requires("entropy_secrets")
+                .help_heading("Scan settings")
+                .help("Bits of entropy per character a base64 string needs for --entropy-secrets to report it; hex strings are scaled to match (default: 4.5)")
+        ).arg(
+            Arg::new("plugins")
+                .long("plugin")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .help_heading("Scan settings")
+                .help("Lua script(s) defining before_request, on_response, and/or on_finding hooks")
+        ).arg(
+            Arg::new("plugin_timeout")
+                .long("plugin-timeout")
+                .value_name("MILLIS")
+                .num_args(1)
+                .requires("plugins")
+                .help_heading("Scan settings")
+                .help("Milliseconds a single plugin hook may run before it's aborted (default: 100)")
+        ).arg(
+            Arg::new("categorize")
+                .long("categorize")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Tag responses with built-in categories (backup-file, config-file, admin-panel, api-spec, directory-listing)")
+        ).arg(
+            Arg::new("category_rules")
+                .long("category-rule")
+                .value_name("CATEGORY:REGEX")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .help_heading("Scan settings")
+                .help("Tag responses whose url matches REGEX with CATEGORY (ex: --category-rule 'login-page:(?i)/(login|signin)')")
+        ).arg(
+            Arg::new("rules")
+                .long("rules")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1)
+                .help_heading("Scan settings")
+                .help("TOML file of rules matching url/header/body regexes that tag findings, set their severity, and raise alerts")
+        ).arg(
+            Arg::new("junit")
+                .long("junit")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1)
+                .requires("rules")
+                .help_heading("Output settings")
+                .help("Write a JUnit XML report with a test case per forbidden rule (--rules), failed when the rule matched")
+        ).arg(
+            Arg::new("fail_on_rule")
+                .long("fail-on-rule")
+                .num_args(0)
+                .requires("rules")
+                .help_heading("Scan settings")
+                .help("Exit with status 2 (or --exit-code rule=CODE) when any forbidden rule (--rules) matched a finding")
+        ).arg(
+            Arg::new("exit_codes")
+                .long("exit-code")
+                .value_name("CONDITION=CODE")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .help_heading("Scan settings")
+                .help("Exit with CODE when the scan ends a certain way; conditions: rule (a forbidden rule matched), errors (see --exit-error-ratio), bail (a scan was bailed on) (ex: --exit-code errors=3,bail=4)")
+        ).arg(
+            Arg::new("exit_error_ratio")
+                .long("exit-error-ratio")
+                .value_name("RATIO")
+                .num_args(1)
+                .value_parser(valid_ratio)
+                .help_heading("Scan settings")
+                .help("Share of requests that must fail for --exit-code errors=CODE to apply (default: 0.1)")
+        ).arg(
+            Arg::new("dont_filter")
+                .short('D')
+                .long("dont-filter")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Don't auto-filter wildcard responses")
+        ).arg(
+            Arg::new("detect_case")
+                .long("detect-case")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Detect targets that ignore case (i.e. IIS) and lowercase/dedupe the wordlist when found")
+        ).arg(
+            Arg::new("favicon")
+                .long("favicon")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Fetch each host's /favicon.ico and report its Shodan style hash (http.favicon.hash), along with the technology it belongs to when known")
+        ).arg(
+            Arg::new("favicon_db")
+                .long("favicon-db")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .num_args(1)
+                .requires("favicon")
+                .help_heading("Scan settings")
+                .help("Extra HASH,TECHNOLOGY pairs (one per line) to check --favicon hashes against")
+        ).arg(
+            Arg::new("well_known")
+                .long("well-known")
+                .num_args(0)
+                .help_heading("Scan settings")
+                .help("Request each host's /.well-known/ resources (security.txt, openid-configuration, ...) and follow the urls inside of them")
+        ).arg(
+            Arg::new("bucket_listing")
+                .long("bucket-listing")
+                .num_args(0)
+                .conflicts_with("dont_extract_links")
+                .help_heading("Scan settings")
+                .help("Check whether S3/GCS/Azure buckets found in extracted links publicly list their contents")
+        ).arg(
+            Arg::new("baseline")
+                .long("baseline")
+                .value_name("FILE")
+                .num_args(1)
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Scan settings")
+                .help("A previous scan's --json output or .state file; known urls are requested with If-None-Match/If-Modified-Since and 304s are reported as unchanged")
+        ).arg(
+            Arg::new("baseline_url")
+                .long("baseline-url")
+                .value_name("URL")
+                .num_args(1)
+                .value_hint(ValueHint::Url)
+                .help_heading("Scan settings")
+                .help("A page (e.g. the app's default page) fetched once; every response's body is given a rough diff ratio against it (0.0 same, 1.0 nothing in common)")
+        ).arg(
+            Arg::new("reuse_signatures")
+                .long("reuse-signatures")
+                .value_name("STATE_FILE")
+                .num_args(1)
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with("dont_filter")
+                .help_heading("Scan settings")
+                .help("A previous scan's .state file; directories it already learned the 404 responses of skip the wildcard heuristics")
+        ).arg(
+            Arg::new("collect_extensions")
+                .short('E')
+                .long("collect-extensions")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Automatically discover extensions and add them to --extensions (unless they're in --dont-collect)")
+        ).arg(
+            Arg::new("prune_extensions")
+                .long("prune-extensions")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Stop requesting an extension on a host once it's been requested --prune-after times without a single finding")
+        ).arg(
+            Arg::new("prune_after")
+                .long("prune-after")
+                .value_name("REQUESTS")
+                .num_args(1)
+                .requires("prune_extensions")
+                .help_heading("Dynamic collection settings")
+                .help("Number of fruitless requests after which --prune-extensions drops an extension (default: 250)")
+        ).arg(
+            Arg::new("collect_backups")
+                .short('B')
+                .long("collect-backups")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Automatically request likely backup extensions for \"found\" urls")
+        ).arg(
+            Arg::new("bypass_403")
+                .long("bypass-403")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Request 403 bypass variants (case tricks, %2e segments, trailing %20, X-Original-URL, etc...) of forbidden urls and report the ones that get a different status")
+        ).arg(
+            Arg::new("bypass_limit")
+                .long("bypass-limit")
+                .value_name("NUM_URLS")
+                .num_args(1)
+                .requires("bypass_403")
+                .help_heading("Dynamic collection settings")
+                .help("Maximum number of forbidden urls to attempt bypasses against (default: 50)")
+        ).arg(
+            Arg::new("bypass_rate")
+                .long("bypass-rate")
+                .value_name("RATE_LIMIT")
+                .num_args(1)
+                .requires("bypass_403")
+                .help_heading("Dynamic collection settings")
+                .help("Maximum number of 403 bypass requests per second, on top of the scan's own (default: 10, 0 disables)")
+        ).arg(
+            Arg::new("vcs_check")
+                .long("vcs-check")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Check .git/.svn/.hg findings for a usable repository (HEAD, config, refs; at most 3 requests each) and report exposed ones as high severity")
+        ).arg(
+            Arg::new("archive_peek")
+                .long("archive-peek")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Download archives that were found (zip, jar, war, tar, tar.gz, etc...) and list the names of the files inside, without extracting them")
+        ).arg(
+            Arg::new("archive_max_size")
+                .long("archive-max-size")
+                .value_name("SIZE_SPEC")
+                .value_parser(valid_size_spec)
+                .num_args(1)
+                .requires("archive_peek")
+                .help_heading("Dynamic collection settings")
+                .help("Largest archive --archive-peek downloads (default: 10M)")
+        )
+        .arg(
+            Arg::new("collect_words")
+                .short('g')
+                .long("collect-words")
+                .num_args(0)
+                .help_heading("Dynamic collection settings")
+                .help("Automatically discover important words from within responses and add them to the wordlist")
+        ).arg(
+            Arg::new("dont_collect")
+                .short('I')
                 .long("dont-collect")
                 .value_name("FILE_EXTENSION")
                 .num_args(1..)
@@ -612,6 +1380,24 @@ pub fn initialize() -> Command {
                 .requires("output_files")
                 .help_heading("Output settings")
                 .help("Emit JSON logs to --output and --debug-log instead of normal text")
+        ).arg(
+            Arg::new("punycode")
+                .long("punycode")
+                .num_args(0)
+                .help_heading("Output settings")
+                .help("Display internationalized domain names as punycode instead of unicode (requests always use punycode)")
+        ).arg(
+            Arg::new("titles")
+                .long("titles")
+                .num_args(0)
+                .help_heading("Output settings")
+                .help("Show the <title> and meta generator of HTML responses alongside the results (--json output also gets their charset)")
+        ).arg(
+            Arg::new("fuzzy_hash")
+                .long("fuzzy-hash")
+                .num_args(0)
+                .help_heading("Output settings")
+                .help("Add a simhash of each response's body to --json output (next to its SHA-256), the same fuzzy hash --filter-similar-to compares")
         ).arg(
             Arg::new("output")
                 .short('o')
@@ -631,6 +1417,184 @@ pub fn initialize() -> Command {
                 .help("Output file to write log entries (use w/ --json for JSON entries)")
                 .num_args(1),
         )
+        .arg(
+            Arg::new("burp_export")
+                .long("burp-export")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Output settings")
+                .help("Export discovered resources, w/ full request/response pairs, as Burp Suite XML")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("har_export")
+                .long("har-export")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Output settings")
+                .help("Export discovered resources, w/ full request/response pairs, as an HTTP Archive (HAR)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("quiet_stream")
+                .long("quiet-stream")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Output settings")
+                .help("Write every response to FILE, including filtered ones (tagged with the filter that removed them); the terminal still shows only findings")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("traffic_log")
+                .long("traffic-log")
+                .value_name("DIRECTORY")
+                .value_hint(ValueHint::DirPath)
+                .help_heading("Output settings")
+                .help("Log every request sent (w/ sensitive headers redacted) and the response's metadata to files in DIRECTORY")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("traffic_log_max")
+                .long("traffic-log-max")
+                .value_name("SIZE_SPEC")
+                .value_parser(valid_size_spec)
+                .requires("traffic_log")
+                .help_heading("Output settings")
+                .help("Start a new --traffic-log file once the current one reaches this size (ex: --traffic-log-max 1G)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("traffic_log_bodies")
+                .long("traffic-log-bodies")
+                .num_args(0)
+                .requires("traffic_log")
+                .help_heading("Output settings")
+                .help("Include full response bodies in the --traffic-log")
+        )
+        .arg(
+            Arg::new("policy_audit")
+                .long("policy-audit")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Output settings")
+                .help("Write every auto-tune/bail/requeue policy decision, w/ the counters that triggered it, to FILE as NDJSON")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("screenshots")
+                .long("screenshots")
+                .value_name("DIRECTORY")
+                .value_hint(ValueHint::DirPath)
+                .help_heading("Output settings")
+                .help("Screenshot findings with headless Chrome/Chromium, saving the images and an index.html that links them to DIRECTORY")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("chrome")
+                .long("chrome")
+                .value_name("PATH")
+                .value_hint(ValueHint::ExecutablePath)
+                .requires("screenshots")
+                .help_heading("Output settings")
+                .help("Chrome/Chromium executable used for --screenshots (default: first one found on the PATH)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("screenshot_codes")
+                .long("screenshot-codes")
+                .value_name("STATUS_CODE")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .requires("screenshots")
+                .help_heading("Output settings")
+                .help("Status codes of findings worth a screenshot (default: 200)"),
+        )
+        .arg(
+            Arg::new("screenshot_types")
+                .long("screenshot-types")
+                .value_name("CONTENT_TYPE")
+                .num_args(1..)
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .requires("screenshots")
+                .help_heading("Output settings")
+                .help("Content types of findings worth a screenshot, matched against the Content-Type header (default: text/html)"),
+        )
+        .arg(
+            Arg::new("pipe_to")
+                .long("pipe-to")
+                .value_name("COMMAND")
+                .value_hint(ValueHint::CommandString)
+                .help_heading("Output settings")
+                .help("Spawn COMMAND and write discovered resources to its stdin as JSON lines; COMMAND is restarted if it exits early (ex: --pipe-to \"jq -r .url\")")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("api_export")
+                .long("api-export")
+                .value_name("URL")
+                .value_hint(ValueHint::Url)
+                .help_heading("Output settings")
+                .help("POST findings to a vulnerability management api once the scan ends; a token in FEROX_API_TOKEN is sent along")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("api_export_format")
+                .long("api-export-format")
+                .value_name("FORMAT")
+                .value_parser(["generic", "dojo"])
+                .requires("api_export")
+                .help_heading("Output settings")
+                .help("generic: a json array of findings per request; dojo: a DefectDojo import-scan upload (default: generic)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("api_template")
+                .long("api-template")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .requires("api_export")
+                .help_heading("Output settings")
+                .help("JSON template sent for each finding, with {{url}}, {{status}}, {{tags}}, {{severity}}, ... filled in (generic format)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("api_batch_size")
+                .long("api-batch-size")
+                .value_name("FINDINGS")
+                .requires("api_export")
+                .help_heading("Output settings")
+                .help("Number of findings sent per --api-export request (default: 100)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("dojo_engagement")
+                .long("dojo-engagement")
+                .value_name("ID")
+                .requires("api_export")
+                .help_heading("Output settings")
+                .help("DefectDojo engagement that findings are imported into (--api-export-format dojo)")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Output settings")
+                .help("Write a JSON summary of the scan (targets, durations, request counts, status codes, bails, filters, top findings) once it ends")
+                .num_args(1),
+        )
+        .arg(
+            Arg::new("record")
+                .long("record")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help_heading("Output settings")
+                .help("Record every response, in the order received, for `feroxbuster replay FILE`")
+                .num_args(1),
+        )
         .arg(
             Arg::new("no_state")
                 .long("no-state")
@@ -657,6 +1621,23 @@ pub fn initialize() -> Command {
                 .help_heading("Update settings")
                 .help("Update feroxbuster to the latest version"),
         )
+        .subcommand(
+            Command::new("replay")
+                .about("Run the responses of a --record session through the filters and output given before `replay`, without making any requests")
+                .arg(
+                    Arg::new("session")
+                        .value_name("SESSION")
+                        .required(true)
+                        .value_hint(ValueHint::FilePath)
+                        .help("File written by --record"),
+                ),
+        )
+        .subcommand(
+            Command::new("selftest")
+                .about("Scan a built-in test server (404s, redirects, 429s, wildcard pages) with the settings given before `selftest`, and check that the results come out as expected"),
+        )
+        .subcommand_negates_reqs(true)
+        .subcommand_precedence_over_arg(true)
         .after_long_help(EPILOGUE);
 
     /////////////////////////////////////////////////////////////////////
@@ -694,6 +1675,88 @@ fn valid_time_spec(time_spec: &str) -> Result<String, String> {
     }
 }
 
+/// Validate that a string is formatted as a multiple of the baseline greater than 1 (2x-baseline,
+/// 1.5x, etc...)
+fn valid_latency_spec(latency_spec: &str) -> Result<String, String> {
+    let multiplier = LATENCYSPEC_REGEX
+        .captures(latency_spec)
+        .and_then(|captures| captures.name("n")?.as_str().parse::<f64>().ok());
+
+    match multiplier {
+        Some(multiplier) if multiplier > 1.0 => Ok(latency_spec.to_string()),
+        _ => {
+            let msg = format!(
+                "Expected a number greater than 1 followed by x or x-baseline (case insensitive); received {latency_spec}"
+            );
+            Err(msg)
+        }
+    }
+}
+
+/// Validate that a string is a ratio greater than 0 and no more than 1 (0.5, 1, etc...)
+fn valid_ratio(ratio: &str) -> Result<f64, String> {
+    match ratio.parse::<f64>() {
+        Ok(parsed) if parsed > 0.0 && parsed <= 1.0 => Ok(parsed),
+        _ => Err(format!(
+            "Expected a number greater than 0 and no more than 1; received {ratio}"
+        )),
+    }
+}
+
+/// Validate that a string is a number of bits of entropy per base64 character (0 < n <= 6)
+fn valid_entropy(bits: &str) -> Result<f64, String> {
+    match bits.parse::<f64>() {
+        Ok(parsed) if parsed > 0.0 && parsed <= 6.0 => Ok(parsed),
+        _ => Err(format!(
+            "Expected a number greater than 0 and no more than 6; received {bits}"
+        )),
+    }
+}
+
+/// Validate that a string is formatted as a number optionally followed by k, m, or g (100M, 1G, etc...)
+fn valid_size_spec(size_spec: &str) -> Result<String, String> {
+    match SIZESPEC_REGEX.is_match(size_spec) {
+        true => Ok(size_spec.to_string()),
+        false => {
+            let msg = format!(
+                "Expected a non-negative, whole number optionally followed by k, m, or g (case insensitive); received {size_spec}"
+            );
+            Err(msg)
+        }
+    }
+}
+
+/// named --pacing profiles, and the burst/quiet cycle each one stands for
+pub const PACING_PROFILES: [(&str, &str); 3] = [
+    ("low-and-slow", "20/10m"),
+    ("quiet", "100/2m"),
+    ("burst", "1000/30s"),
+];
+
+/// Validate that a string is either a named pacing profile or formatted as N/TIME_SPEC, with N
+/// greater than 0
+fn valid_pacing_spec(pacing_spec: &str) -> Result<String, String> {
+    if PACING_PROFILES.iter().any(|(name, _)| *name == pacing_spec) {
+        return Ok(pacing_spec.to_string());
+    }
+
+    let burst = PACINGSPEC_REGEX
+        .captures(pacing_spec)
+        .and_then(|captures| captures.name("n")?.as_str().parse::<usize>().ok());
+
+    match burst {
+        Some(burst) if burst > 0 => Ok(pacing_spec.to_string()),
+        _ => {
+            let names: Vec<_> = PACING_PROFILES.iter().map(|(name, _)| *name).collect();
+            let msg = format!(
+                "Expected one of {} or a number of requests followed by how long to pause (ex: 100/5m); received {pacing_spec}",
+                names.join(", ")
+            );
+            Err(msg)
+        }
+    }
+}
+
 const EPILOGUE: &str = r#"NOTE:
     Options that take multiple values are very flexible.  Consider the following ways of specifying
     extensions:
@@ -783,4 +1846,27 @@ mod tests {
         let space_between_rejected = "1 4m";
         assert!(valid_time_spec(space_between_rejected).is_err());
     }
+
+    #[test]
+    /// sanity checks that valid_latency_spec correctly checks and rejects a given string
+    fn validate_valid_latency_spec_validation() {
+        for accepted in &["2x-baseline", "2X-BASELINE", "1.5x", "10x"] {
+            assert!(valid_latency_spec(accepted).is_ok());
+        }
+
+        for rejected in &["2", "x-baseline", "-2x", "1x", "0.5x", " 2x", "2x-base"] {
+            assert!(valid_latency_spec(rejected).is_err());
+        }
+    }
+
+    #[test]
+    /// sanity checks that valid_ratio correctly checks and rejects a given string
+    fn validate_valid_ratio_validation() {
+        assert_eq!(valid_ratio("0.5"), Ok(0.5));
+        assert_eq!(valid_ratio("1"), Ok(1.0));
+
+        for rejected in &["0", "1.1", "-0.5", "half", ""] {
+            assert!(valid_ratio(rejected).is_err());
+        }
+    }
 }
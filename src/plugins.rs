@@ -0,0 +1,465 @@
+//! lua plugins that hook into request and response processing (--plugin)
+//!
+//! a plugin is a lua script that defines any of the following global functions
+//!
+//! - `before_request(request)`: called before every request is sent; `request` is a table with
+//!   `url`, `method`, and `headers` fields. `url` and `headers` may be modified in place (or a
+//!   modified copy returned) to change what's sent
+//! - `on_response(response)`: called for every response that made it past the normal filters;
+//!   return `false` to filter the response, or a string (or list of strings) to tag it
+//! - `on_finding(response)`: called for every response reported as a finding; the return value
+//!   is ignored
+//! - `wordlist()`: only called when the script is given as the wordlist (`-w plugin:FILE`);
+//!   returns the list of words to scan with
+//!
+//! response tables have `url`, `method`, `status`, `content_length`, `line_count`,
+//! `word_count`, `wildcard`, `headers`, and `body` fields
+//!
+//! hooks called at the same time run in lua states of their own, each of which loaded the
+//! script separately; global variables aren't shared between them
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use mlua::{Function, HookTriggers, Lua, Table, Value};
+use reqwest::Url;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::{
+    config::Configuration,
+    event_handlers::Command::{AddFilterHit, AddToUsizeField},
+    response::FeroxResponse,
+    statistics::StatField::ResponsesFiltered,
+    utils::fmt_err,
+    CommandSender,
+};
+
+/// name of the hook called before each request
+const BEFORE_REQUEST: &str = "before_request";
+
+/// name of the hook called for each response that passed the normal filters
+const ON_RESPONSE: &str = "on_response";
+
+/// name of the hook called for each finding
+const ON_FINDING: &str = "on_finding";
+
+/// name of the hook that generates a wordlist
+const WORDLIST: &str = "wordlist";
+
+/// number of lua instructions executed between checks of a hook's time budget
+const BUDGET_CHECK_INTERVAL: u32 = 1000;
+
+/// A single lua script along with the hooks it defines
+#[derive(Debug)]
+pub struct Plugin {
+    /// name shown in logs and filter descriptions; the script's file name
+    name: String,
+
+    /// the script's source, loaded into a new lua state whenever every existing one is busy
+    source: String,
+
+    /// lua states that aren't running a hook; a hook takes one for as long as it runs
+    idle: Mutex<Vec<Lua>>,
+
+    /// maximum amount of time a single hook may run before it's aborted; zero means no limit
+    budget: Duration,
+
+    /// hooks defined by the script
+    hooks: Vec<&'static str>,
+}
+
+impl Plugin {
+    /// load the script at the given path, running its top level code (subject to the same time
+    /// budget as its hooks)
+    pub fn new(path: &str, budget: Duration) -> Result<Self> {
+        log::trace!("enter: Plugin::new({}, {:?})", path, budget);
+
+        let source = fs::read_to_string(path)
+            .with_context(|| fmt_err(&format!("Could not read plugin {path}")))?;
+
+        let name = Path::new(path).file_name().map_or_else(
+            || path.to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+
+        let lua = load(&name, &source, budget)?;
+
+        let hooks = [BEFORE_REQUEST, ON_RESPONSE, ON_FINDING, WORDLIST]
+            .into_iter()
+            .filter(|hook| {
+                lua.globals()
+                    .get::<_, Option<Function>>(*hook)
+                    .is_ok_and(|function| function.is_some())
+            })
+            .collect();
+
+        let plugin = Self {
+            name,
+            source,
+            idle: Mutex::new(vec![lua]),
+            budget,
+            hooks,
+        };
+
+        log::trace!("exit: Plugin::new -> {:?}", plugin);
+        Ok(plugin)
+    }
+
+    /// whether the script defines the given hook
+    fn has_hook(&self, hook: &str) -> bool {
+        self.hooks.contains(&hook)
+    }
+
+    /// call the script's wordlist hook, returning the words it generated
+    pub fn words(&self) -> Result<Vec<String>> {
+        if !self.has_hook(WORDLIST) {
+            bail!(fmt_err(&format!(
+                "Plugin {} doesn't define {WORDLIST}()",
+                self.name
+            )));
+        }
+
+        self.run(WORDLIST, |_, function| function.call::<_, Vec<String>>(()))
+            .with_context(|| fmt_err(&format!("Plugin {} didn't generate a wordlist", self.name)))
+    }
+
+    /// call the given hook, handing its function to `call`; errors (including running out of
+    /// time) are logged and result in `None`
+    fn run<T, F>(&self, hook: &str, call: F) -> Option<T>
+    where
+        F: FnOnce(&Lua, Function) -> mlua::Result<T>,
+    {
+        // the lock is only held to take a state, never while the hook runs
+        let idle = self.idle.lock().ok()?.pop();
+
+        let lua = match idle {
+            Some(lua) => lua,
+            None => match load(&self.name, &self.source, self.budget) {
+                Ok(lua) => lua,
+                Err(e) => {
+                    log::warn!("Plugin {} failed during {}: {}", self.name, hook, e);
+                    return None;
+                }
+            },
+        };
+
+        let result = blocking(|| {
+            let function = lua.globals().get::<_, Function>(hook)?;
+
+            set_budget(&lua, self.budget);
+            let result = call(&lua, function);
+            lua.remove_hook();
+
+            result
+        });
+
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.push(lua);
+        }
+
+        result
+            .map_err(|e| log::warn!("Plugin {} failed during {}: {}", self.name, hook, e))
+            .ok()
+    }
+}
+
+/// a new lua state with the given script loaded, its top level code subject to the budget
+fn load(name: &str, source: &str, budget: Duration) -> Result<Lua> {
+    let lua = Lua::new();
+
+    set_budget(&lua, budget);
+    let loaded = lua.load(source).set_name(name).exec();
+    lua.remove_hook();
+
+    loaded.with_context(|| fmt_err(&format!("Could not load plugin {name}")))?;
+
+    Ok(lua)
+}
+
+/// run lua code, which may take up to its whole budget; on a multi-threaded runtime, the
+/// worker's other tasks are handed off to the rest of the workers in the meantime
+fn blocking<T>(run: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(runtime) if runtime.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(run)
+        }
+        _ => run(),
+    }
+}
+
+/// abort whatever lua code runs next once the given budget has been used up
+fn set_budget(lua: &Lua, budget: Duration) {
+    if budget.is_zero() {
+        return;
+    }
+
+    let deadline = Instant::now() + budget;
+
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(BUDGET_CHECK_INTERVAL),
+        move |_, _| {
+            if Instant::now() > deadline {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "exceeded time budget of {}ms",
+                    budget.as_millis()
+                )));
+            }
+            Ok(())
+        },
+    );
+}
+
+/// load every plugin given on the command line/config file, in the order given; empty when
+/// --plugin wasn't used
+pub fn initialize(config: &Configuration) -> Result<Vec<Plugin>> {
+    // hooks run for every request and response, one that never returns would stall the scan
+    if !config.plugins.is_empty() && config.plugin_timeout == 0 {
+        bail!(fmt_err("--plugin-timeout must be greater than 0"));
+    }
+
+    let budget = Duration::from_millis(config.plugin_timeout);
+
+    config
+        .plugins
+        .iter()
+        .map(|path| Plugin::new(path, budget))
+        .collect()
+}
+
+/// build the table handed to on_response/on_finding
+fn response_table<'lua>(lua: &'lua Lua, response: &FeroxResponse) -> mlua::Result<Table<'lua>> {
+    let headers: BTreeMap<String, String> = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect();
+
+    let table = lua.create_table()?;
+    table.set("url", response.url().as_str())?;
+    table.set("method", response.method().as_str())?;
+    table.set("status", response.status().as_u16())?;
+    table.set("content_length", response.content_length())?;
+    table.set("line_count", response.line_count())?;
+    table.set("word_count", response.word_count())?;
+    table.set("wildcard", response.wildcard())?;
+    table.set("headers", headers)?;
+    table.set("body", response.text())?;
+
+    Ok(table)
+}
+
+/// run every before_request hook against the given request; returns the (possibly rewritten)
+/// url along with any headers the hooks want sent, or `None` when no plugin defines the hook
+pub(crate) fn before_request(
+    plugins: &[Plugin],
+    url: &Url,
+    method: &str,
+) -> Option<(Url, Vec<(String, String)>)> {
+    if !plugins.iter().any(|plugin| plugin.has_hook(BEFORE_REQUEST)) {
+        return None;
+    }
+
+    let mut url = url.clone();
+    let mut headers = BTreeMap::<String, String>::new();
+
+    for plugin in plugins
+        .iter()
+        .filter(|plugin| plugin.has_hook(BEFORE_REQUEST))
+    {
+        let result = plugin.run(BEFORE_REQUEST, |lua, function| {
+            let request = lua.create_table()?;
+            request.set("url", url.as_str())?;
+            request.set("method", method)?;
+            request.set("headers", headers.clone())?;
+
+            // hooks may modify the table in place or hand back a new one
+            let request = function
+                .call::<_, Option<Table>>(request.clone())?
+                .unwrap_or(request);
+
+            Ok((
+                request.get::<_, String>("url")?,
+                request
+                    .get::<_, Option<BTreeMap<String, String>>>("headers")?
+                    .unwrap_or_default(),
+            ))
+        });
+
+        if let Some((new_url, new_headers)) = result {
+            match Url::parse(&new_url) {
+                Ok(parsed) => url = parsed,
+                Err(e) => log::warn!(
+                    "Plugin {} returned an invalid url ({}): {}",
+                    plugin.name,
+                    new_url,
+                    e
+                ),
+            }
+
+            headers = new_headers;
+        }
+    }
+
+    Some((url, headers.into_iter().collect()))
+}
+
+/// run every on_response hook against the given response, tagging it as requested; returns a
+/// description of the plugin that filtered the response, if any
+pub(crate) fn on_response(
+    plugins: &[Plugin],
+    response: &mut FeroxResponse,
+    tx_stats: CommandSender,
+) -> Option<String> {
+    for plugin in plugins.iter().filter(|plugin| plugin.has_hook(ON_RESPONSE)) {
+        let tags = plugin.run(ON_RESPONSE, |lua, function| {
+            match function.call::<_, Value>(response_table(lua, response)?)? {
+                Value::Boolean(false) => Ok(None),
+                Value::String(tag) => Ok(Some(vec![tag.to_str()?.to_string()])),
+                Value::Table(tags) => Ok(Some(
+                    tags.sequence_values::<String>()
+                        .collect::<mlua::Result<Vec<_>>>()?,
+                )),
+                _ => Ok(Some(Vec::new())),
+            }
+        });
+
+        match tags {
+            Some(Some(tags)) => tags.into_iter().for_each(|tag| response.add_tag(tag)),
+            Some(None) => {
+                let description = format!("Plugin: {}", plugin.name);
+
+                tx_stats
+                    .send(AddToUsizeField(ResponsesFiltered, 1))
+                    .unwrap_or_default();
+                tx_stats
+                    .send(AddFilterHit(description.clone()))
+                    .unwrap_or_default();
+
+                return Some(description);
+            }
+            None => {} // hook failed, leave the response alone
+        }
+    }
+
+    None
+}
+
+/// run every on_finding hook against the given response
+pub(crate) fn on_finding(plugins: &[Plugin], response: &FeroxResponse) {
+    for plugin in plugins.iter().filter(|plugin| plugin.has_hook(ON_FINDING)) {
+        plugin.run(ON_FINDING, |lua, function| {
+            function.call::<_, ()>(response_table(lua, response)?)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// write the given source to a temporary file and load it as a plugin
+    fn plugin(source: &str, budget: Duration) -> Plugin {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{source}").unwrap();
+        Plugin::new(file.path().to_str().unwrap(), budget).unwrap()
+    }
+
+    #[test]
+    /// only the hooks a script actually defines should be recorded
+    fn plugin_new_records_defined_hooks() {
+        let loaded = plugin(
+            "function on_response(r) return true end\nlocal function helper() end",
+            Duration::ZERO,
+        );
+
+        assert!(loaded.has_hook(ON_RESPONSE));
+        assert!(!loaded.has_hook(BEFORE_REQUEST));
+        assert!(!loaded.has_hook(ON_FINDING));
+    }
+
+    #[test]
+    /// hooks that run past their budget should be aborted rather than hang the scan
+    fn plugin_run_aborts_hooks_that_exceed_budget() {
+        let loaded = plugin(
+            "function on_finding(r) while true do end end",
+            Duration::from_millis(50),
+        );
+
+        let start = Instant::now();
+        let result = loaded.run(ON_FINDING, |lua, function| {
+            function.call::<_, ()>(response_table(lua, &FeroxResponse::default())?)
+        });
+
+        assert!(result.is_none());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    /// a hook that's already running doesn't keep another from starting; it gets a state of its
+    /// own instead of waiting on the busy one
+    fn plugin_run_loads_another_state_while_one_is_busy() {
+        let loaded = plugin("function on_finding(r) return r.url end", Duration::ZERO);
+
+        let nested = loaded.run(ON_FINDING, |_, _| {
+            Ok(loaded.run(ON_FINDING, |lua, function| {
+                function.call::<_, String>(response_table(lua, &FeroxResponse::default())?)
+            }))
+        });
+
+        assert!(nested.unwrap().is_some());
+        assert_eq!(loaded.idle.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    /// hooks always run under a time budget
+    fn initialize_rejects_zero_plugin_timeout() {
+        let config = Configuration {
+            plugins: vec![String::from("unused.lua")],
+            plugin_timeout: 0,
+            ..Default::default()
+        };
+
+        assert!(initialize(&config).is_err());
+    }
+
+    #[test]
+    /// top level code is subject to the same budget as hooks
+    fn plugin_new_aborts_scripts_that_exceed_budget() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "while true do end").unwrap();
+
+        let result = Plugin::new(file.path().to_str().unwrap(), Duration::from_millis(50));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    /// response tables should expose the response's details to lua
+    fn plugin_response_table_exposes_response() {
+        let loaded = plugin(
+            "function on_finding(r) return r.url .. ' ' .. r.status .. ' ' .. r.body end",
+            Duration::ZERO,
+        );
+
+        let mut response = FeroxResponse::default();
+        response.set_url("http://localhost/admin");
+        response.set_text("hello");
+
+        let result = loaded.run(ON_FINDING, |lua, function| {
+            function.call::<_, String>(response_table(lua, &response)?)
+        });
+
+        assert_eq!(result.unwrap(), "http://localhost/admin 200 hello");
+    }
+}
@@ -0,0 +1,193 @@
+//! record of every auto-tune/bail/requeue policy decision, along with the counters that
+//! justified it, for review after the fact (--policy-audit)
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{config::Configuration, export::UtcTime, utils::fmt_err};
+
+/// The counters a Requester looked at when it decided a policy needed to be enforced
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct PolicyDecision {
+    /// url of the scan the policy was enforced on
+    pub url: String,
+
+    /// policy in effect, i.e. `AutoTune`
+    pub policy: String,
+
+    /// what fired, i.e. `Status429`
+    pub trigger: String,
+
+    /// requests made across every scan, checked against the minimum needed before any policy
+    /// is enforced
+    pub requests: usize,
+
+    /// requests made by the scan since it was (re)started
+    pub scan_requests: u64,
+
+    /// errors/responses counted against the trigger (weighted, for general errors)
+    pub count: usize,
+
+    /// share of the scan's requests taken up by `count`; only set for status code triggers
+    pub ratio: Option<f64>,
+
+    /// what `count` (or `ratio`) had to reach for the trigger to fire; for latency, the
+    /// multiple of the baseline the p95 had to pass
+    pub threshold: f64,
+
+    /// p95 response time, in milliseconds; only set for latency triggers
+    pub p95_ms: Option<u128>,
+
+    /// baseline response time, in milliseconds; only set for latency triggers
+    pub baseline_ms: Option<u128>,
+
+    /// requests per second the scan was held to when the decision was made, 0 means unlimited
+    pub rate_limit: usize,
+
+    /// number of times the scan was requeued before the decision was made
+    pub requeues: usize,
+}
+
+/// Writes one NDJSON entry per policy decision to a single file, independent of any other output
+#[derive(Debug)]
+pub struct PolicyAudit {
+    /// buffered writer for the audit file
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl PolicyAudit {
+    /// create (or truncate) the audit file
+    pub fn new(filename: &str) -> Result<Self> {
+        log::trace!("enter: PolicyAudit::new({})", filename);
+
+        let file = File::create(filename)
+            .with_context(|| fmt_err(&format!("Could not open {filename}")))?;
+
+        let audit = Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        };
+
+        log::trace!("exit: PolicyAudit::new -> {:?}", audit);
+        Ok(audit)
+    }
+
+    /// record a single policy decision
+    pub fn record(&self, decision: &PolicyDecision) {
+        self.write(decision, SystemTime::now())
+            .unwrap_or_else(|e| log::warn!("Could not write to the policy audit: {}", e));
+    }
+
+    /// write a single decision, flushed right away so nothing is lost if the scan is killed
+    fn write(&self, decision: &PolicyDecision, time: SystemTime) -> Result<()> {
+        let mut line = entry(decision, time).to_string();
+        line.push('\n');
+
+        if let Ok(mut guard) = self.writer.lock() {
+            guard.write_all(line.as_bytes())?;
+            guard.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// build the entry describing a single decision
+fn entry(decision: &PolicyDecision, time: SystemTime) -> serde_json::Value {
+    let mut entry = json!({
+        "type": "policy_decision",
+        "time": UtcTime::from(time).to_iso8601(),
+    });
+
+    if let (Some(entry), Ok(serde_json::Value::Object(fields))) =
+        (entry.as_object_mut(), serde_json::to_value(decision))
+    {
+        entry.extend(fields);
+    }
+
+    entry
+}
+
+/// set up the audit log from the value given on the command line/config file; `None` when
+/// --policy-audit wasn't used
+pub fn initialize(config: &Configuration) -> Result<Option<PolicyAudit>> {
+    if config.policy_audit.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(PolicyAudit::new(&config.policy_audit)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    /// entries should carry their type and time alongside every counter, with counters that
+    /// don't apply to the trigger left as null
+    fn policy_audit_entry_includes_counters() {
+        let decision = PolicyDecision {
+            url: "http://localhost/api".to_string(),
+            policy: "AutoBail".to_string(),
+            trigger: "Status403".to_string(),
+            requests: 120,
+            scan_requests: 100,
+            count: 95,
+            ratio: Some(0.95),
+            threshold: 0.9,
+            rate_limit: 50,
+            ..Default::default()
+        };
+
+        let time = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let entry = entry(&decision, time);
+
+        assert_eq!(entry["type"], "policy_decision");
+        assert_eq!(entry["time"], UtcTime::from(time).to_iso8601());
+        assert_eq!(entry["url"], "http://localhost/api");
+        assert_eq!(entry["policy"], "AutoBail");
+        assert_eq!(entry["trigger"], "Status403");
+        assert_eq!(entry["requests"], 120);
+        assert_eq!(entry["scan_requests"], 100);
+        assert_eq!(entry["count"], 95);
+        assert_eq!(entry["ratio"], 0.95);
+        assert_eq!(entry["threshold"], 0.9);
+        assert_eq!(entry["rate_limit"], 50);
+        assert_eq!(entry["requeues"], 0);
+        assert!(entry["p95_ms"].is_null());
+        assert!(entry["baseline_ms"].is_null());
+    }
+
+    #[test]
+    /// decisions should be written one per line and readable as json
+    fn policy_audit_writes_one_line_per_decision() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("audit.jsonl");
+        let audit = PolicyAudit::new(path.to_str().unwrap()).unwrap();
+
+        let decision = PolicyDecision {
+            trigger: "Errors".to_string(),
+            count: 25,
+            threshold: 25.0,
+            ..Default::default()
+        };
+
+        audit.write(&decision, SystemTime::now()).unwrap();
+        audit.write(&decision, SystemTime::now()).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let parsed: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed["trigger"], "Errors");
+        assert_eq!(parsed["count"], 25);
+    }
+}
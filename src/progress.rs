@@ -1,8 +1,22 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::{Duration, Instant},
+};
 
+use console::style;
 use indicatif::{HumanDuration, MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use lazy_static::lazy_static;
 
+/// how quickly older samples fade from `RequestRate`; a sample this old carries ~37% of the
+/// weight it had when it was recorded
+const RATE_SMOOTHING_WINDOW: Duration = Duration::from_secs(5);
+
+/// samples closer together than this are dropped, anything shorter is mostly noise
+const RATE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
 lazy_static! {
     /// Global progress bar that houses other progress bars
     pub static ref PROGRESS_BAR: MultiProgress = MultiProgress::with_draw_target(ProgressDrawTarget::stdout());
@@ -30,62 +44,380 @@ pub enum BarType {
     Quiet,
 }
 
+/// Exponentially weighted moving average of a progress bar's position per second
+///
+/// The raw average (position / elapsed) swings wildly for the first few seconds of a scan;
+/// this settles much faster and follows changes in throughput (i.e. rate limiting) over time
+#[derive(Debug, Default)]
+pub struct RequestRate {
+    /// last sample taken, and the smoothed rate as of that sample
+    state: Mutex<Option<RateSample>>,
+}
+
+/// A single observation folded into `RequestRate`
+#[derive(Debug, Copy, Clone)]
+struct RateSample {
+    /// progress bar position when the sample was taken
+    position: u64,
+
+    /// when the sample was taken
+    taken_at: Instant,
+
+    /// smoothed rate as of this sample; `None` until there are two samples to compare
+    per_sec: Option<f64>,
+}
+
+impl RequestRate {
+    /// fold the given position into the moving average
+    pub fn record(&self, position: u64, now: Instant) {
+        let Ok(mut guard) = self.state.lock() else {
+            return;
+        };
+
+        let Some(last) = *guard else {
+            *guard = Some(RateSample {
+                position,
+                taken_at: now,
+                per_sec: None,
+            });
+            return;
+        };
+
+        let elapsed = now.saturating_duration_since(last.taken_at);
+
+        if elapsed < RATE_SAMPLE_INTERVAL {
+            return;
+        }
+
+        let seconds = elapsed.as_secs_f64();
+        let current = position.saturating_sub(last.position) as f64 / seconds;
+
+        // weight the newest sample by how much time it covers, so that irregular sampling
+        // doesn't skew the average
+        let weight = 1.0 - (-seconds / RATE_SMOOTHING_WINDOW.as_secs_f64()).exp();
+
+        let per_sec = match last.per_sec {
+            Some(previous) => previous + weight * (current - previous),
+            None => current,
+        };
+
+        *guard = Some(RateSample {
+            position,
+            taken_at: now,
+            per_sec: Some(per_sec),
+        });
+    }
+
+    /// current smoothed rate, if enough samples have been recorded
+    pub fn per_sec(&self) -> Option<f64> {
+        self.state.lock().ok()?.as_ref()?.per_sec
+    }
+}
+
+/// Which way a rate limit was last moved
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TuningDirection {
+    /// not moved since it was put in place
+    Steady,
+
+    /// lowered, or put in place mid-scan
+    Down,
+
+    /// raised
+    Up,
+}
+
+/// implementation of TuningDirection
+impl TuningDirection {
+    /// direction stored in an atomic
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Down,
+            2 => Self::Up,
+            _ => Self::Steady,
+        }
+    }
+
+    /// arrow shown next to a limit, colored by direction
+    fn arrow(&self) -> String {
+        match self {
+            Self::Steady => String::new(),
+            Self::Down => style("↓").red().to_string(),
+            Self::Up => style("↑").green().to_string(),
+        }
+    }
+}
+
+/// Requests per second a scan is held to, and which way it was last moved; shown next to the
+/// scan's reqs/sec so throttling can be seen as it happens
+#[derive(Debug)]
+pub struct RateLimitGauge {
+    /// current limit, 0 means the scan isn't limited
+    limit: AtomicUsize,
+
+    /// last TuningDirection, as a u8
+    direction: AtomicU8,
+}
+
+/// Default implementation for RateLimitGauge
+impl Default for RateLimitGauge {
+    fn default() -> Self {
+        Self {
+            limit: AtomicUsize::new(0),
+            direction: AtomicU8::new(TuningDirection::Steady as u8),
+        }
+    }
+}
+
+/// implementation of RateLimitGauge
+impl RateLimitGauge {
+    /// the limit the scan starts out with (i.e. --rate-limit, or one picked up from a resumed
+    /// scan); it hasn't moved yet
+    pub fn start_at(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+        self.direction
+            .store(TuningDirection::Steady as u8, Ordering::Relaxed);
+    }
+
+    /// the limit was changed mid-scan; putting a limit in place counts as lowering it, removing
+    /// one as raising it. returns the direction it moved in, `None` when it didn't move
+    pub fn set(&self, limit: usize) -> Option<TuningDirection> {
+        let previous = self.limit.swap(limit, Ordering::Relaxed);
+
+        let direction = match (previous, limit) {
+            (0, 0) => return None,
+            (0, _) => TuningDirection::Down,
+            (_, 0) => TuningDirection::Up,
+            (previous, limit) if limit < previous => TuningDirection::Down,
+            (previous, limit) if limit > previous => TuningDirection::Up,
+            _ => return None,
+        };
+
+        self.direction.store(direction as u8, Ordering::Relaxed);
+        Some(direction)
+    }
+
+    /// the scan is over, its limit no longer counts
+    pub fn clear(&self) {
+        self.start_at(0);
+    }
+
+    /// current limit, 0 means the scan isn't limited
+    pub fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    /// which way the limit was last moved
+    pub fn direction(&self) -> TuningDirection {
+        TuningDirection::from_u8(self.direction.load(Ordering::Relaxed))
+    }
+
+    /// i.e. `≤150/s↓ `; nothing at all when the scan isn't limited
+    fn render(&self) -> String {
+        match self.limit() {
+            0 => String::new(),
+            limit => format!("≤{}/s{} ", limit, self.direction().arrow()),
+        }
+    }
+}
+
+/// Rate limits of every scan, added up on the overall bar
+#[derive(Debug)]
+pub struct RateLimits {
+    /// gauge of every scan, for as long as the scan is around
+    gauges: Mutex<Vec<Weak<RateLimitGauge>>>,
+
+    /// --rate-limit-global, shown as a cap on the scans' own limits; 0 when not used
+    global: usize,
+
+    /// direction of the latest change made to any scan's limit, as a u8
+    latest: AtomicU8,
+}
+
+/// Default implementation for RateLimits
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// implementation of RateLimits
+impl RateLimits {
+    /// nothing tracked yet, capped by the given --rate-limit-global (0 when not used)
+    pub fn new(global: usize) -> Self {
+        Self {
+            gauges: Mutex::new(Vec::new()),
+            global,
+            latest: AtomicU8::new(TuningDirection::Steady as u8),
+        }
+    }
+
+    /// keep track of the given scan's gauge for the overall bar
+    pub fn register(&self, gauge: &Arc<RateLimitGauge>) {
+        if let Ok(mut gauges) = self.gauges.lock() {
+            gauges.retain(|known| known.strong_count() > 0);
+
+            if !gauges
+                .iter()
+                .any(|known| known.as_ptr() == Arc::as_ptr(gauge))
+            {
+                gauges.push(Arc::downgrade(gauge));
+            }
+        }
+    }
+
+    /// change the limit shown on the given scan's gauge mid-scan, see `RateLimitGauge::set`
+    pub fn set(&self, gauge: &RateLimitGauge, limit: usize) {
+        if let Some(direction) = gauge.set(limit) {
+            self.latest.store(direction as u8, Ordering::Relaxed);
+        }
+    }
+
+    /// the overall bar's take on rate limiting, i.e. `limited:2 (≤350/s↓)`; the scans' limits
+    /// are added up, and capped by --rate-limit-global. nothing at all when nothing is limited
+    fn render(&self) -> String {
+        let limits: Vec<_> = self
+            .gauges
+            .lock()
+            .map(|gauges| {
+                gauges
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    .map(|gauge| gauge.limit())
+                    .filter(|limit| *limit > 0)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let total = match (limits.iter().sum::<usize>(), self.global) {
+            (0, 0) => return String::new(),
+            (0, global) => global,
+            (sum, 0) => sum,
+            (sum, global) => sum.min(global),
+        };
+
+        format!(
+            "{}:{:<3} (≤{}/s{})",
+            style("limited").yellow(),
+            limits.len(),
+            total,
+            TuningDirection::from_u8(self.latest.load(Ordering::Relaxed)).arrow()
+        )
+    }
+}
+
 /// Add an [indicatif::ProgressBar](https://docs.rs/indicatif/latest/indicatif/struct.ProgressBar.html)
 /// to the global [PROGRESS_BAR](../config/struct.PROGRESS_BAR.html)
 pub fn add_bar(prefix: &str, length: u64, bar_type: BarType) -> ProgressBar {
+    add_bar_with_rate(
+        prefix,
+        length,
+        bar_type,
+        Arc::new(RequestRate::default()),
+        Arc::new(RateLimitGauge::default()),
+    )
+}
+
+/// Same as `add_bar`, except that the bar's reqs/sec and ETA are read from (and sampled into)
+/// the given `RequestRate`, so that others can make use of the same estimate, and the given
+/// `RateLimitGauge` is shown next to them
+pub fn add_bar_with_rate(
+    prefix: &str,
+    length: u64,
+    bar_type: BarType,
+    rate: Arc<RequestRate>,
+    gauge: Arc<RateLimitGauge>,
+) -> ProgressBar {
+    build_bar(prefix, length, bar_type, rate, gauge, Arc::default())
+}
+
+/// Same as `add_bar` with `BarType::Total`, except that the given `RateLimits` are shown on it
+pub fn add_total_bar(length: u64, limits: Arc<RateLimits>) -> ProgressBar {
+    build_bar(
+        "",
+        length,
+        BarType::Total,
+        Arc::new(RequestRate::default()),
+        Arc::new(RateLimitGauge::default()),
+        limits,
+    )
+}
+
+/// the bar behind each of the add_bar functions
+fn build_bar(
+    prefix: &str,
+    length: u64,
+    bar_type: BarType,
+    rate: Arc<RequestRate>,
+    gauge: Arc<RateLimitGauge>,
+    limits: Arc<RateLimits>,
+) -> ProgressBar {
+    let eta_rate = rate.clone();
+
     let mut style = ProgressStyle::default_bar()
         .progress_chars("#>-")
         .with_key(
             "smoothed_per_sec",
-            |state: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| match (
-                state.pos(),
-                state.elapsed().as_millis(),
-            ) {
+            move |state: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| {
                 // https://github.com/console-rs/indicatif/issues/394#issuecomment-1309971049
                 //
                 // indicatif released a change to how they reported eta/per_sec
                 // and the results looked really weird based on how we use the progress
                 // bars. this fixes that
-                (pos, elapsed_ms) if elapsed_ms > 0 => {
-                    write!(w, "{:.0}/s", pos as f64 * 1000_f64 / elapsed_ms as f64).unwrap()
+                rate.record(state.pos(), Instant::now());
+
+                match rate.per_sec() {
+                    Some(per_sec) => write!(w, "{per_sec:.0}/s").unwrap(),
+                    None => write!(w, "-").unwrap(),
                 }
-                _ => write!(w, "-").unwrap(),
             },
         )
         .with_key(
             "smoothed_eta",
-            |state: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| match (
-                state.pos(),
-                state.len(),
-            ) {
-                (pos, Some(len)) => write!(
-                    w,
-                    "{:#}",
-                    HumanDuration(Duration::from_millis(
-                        (state.elapsed().as_millis()
-                            * ((len as u128).checked_sub(pos as u128).unwrap_or(1))
-                                .checked_div(pos as u128)
-                                .unwrap_or(1)) as u64
-                    ))
-                )
-                .unwrap(),
-                _ => write!(w, "-").unwrap(),
+            move |state: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| {
+                eta_rate.record(state.pos(), Instant::now());
+
+                match (state.len(), eta_rate.per_sec()) {
+                    (Some(len), Some(per_sec)) if per_sec > 0.0 => {
+                        let remaining = len.saturating_sub(state.pos()) as f64;
+
+                        write!(
+                            w,
+                            "{:#}",
+                            HumanDuration(Duration::from_secs_f64(remaining / per_sec))
+                        )
+                        .unwrap()
+                    }
+                    _ => write!(w, "-").unwrap(),
+                }
+            },
+        )
+        .with_key(
+            "rate_limit",
+            move |_: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| {
+                write!(w, "{}", gauge.render()).unwrap()
+            },
+        )
+        .with_key(
+            "overall_limit",
+            move |_: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| {
+                write!(w, "{}", limits.render()).unwrap()
             },
         );
 
     style = match bar_type {
         BarType::Hidden => style.template("").unwrap(),
         BarType::Default => style
-            .template("[{bar:.cyan/blue}] - {elapsed:<4} {pos:>7}/{len:7} {smoothed_per_sec:7} {prefix} {msg}")
+            .template("[{bar:.cyan/blue}] - {elapsed:<4} {pos:>7}/{len:7} {smoothed_per_sec:7} {rate_limit}{prefix} {msg}")
             .unwrap(),
         BarType::Message => style
             .template(&format!(
-            "[{{bar:.cyan/blue}}] - {{elapsed:<4}} {{pos:>7}}/{{len:7}} {:7} {{prefix}} {{msg}}",
+            "[{{bar:.cyan/blue}}] - {{elapsed:<4}} {{pos:>7}}/{{len:7}} {:7} {{rate_limit}}{{prefix}} {{msg}}",
             "-"
         ))
             .unwrap(),
         BarType::Total => style
-            .template("[{bar:.yellow/blue}] - {elapsed:<4} {pos:>7}/{len:7} {smoothed_eta:7} {msg}")
+            .template("[{bar:.yellow/blue}] - {elapsed:<4} {pos:>7}/{len:7} {smoothed_eta:7} {msg} {overall_limit}")
             .unwrap(),
         BarType::Quiet => style.template("Scanning: {prefix}").unwrap(),
     };
@@ -119,4 +451,89 @@ mod tests {
         assert!(p3.is_finished());
         assert!(p4.is_finished());
     }
+
+    #[test]
+    /// RequestRate should need two samples before producing a rate, ignore samples that are
+    /// too close together, and move toward newer rates without jumping straight to them
+    fn request_rate_smooths_samples() {
+        let rate = RequestRate::default();
+        let start = Instant::now();
+
+        rate.record(0, start);
+        assert!(rate.per_sec().is_none());
+
+        rate.record(100, start + Duration::from_millis(100));
+        assert!(rate.per_sec().is_none());
+
+        rate.record(100, start + Duration::from_secs(1));
+        assert_eq!(rate.per_sec(), Some(100.0));
+
+        rate.record(300, start + Duration::from_secs(2));
+        let per_sec = rate.per_sec().unwrap();
+        assert!(per_sec > 100.0 && per_sec < 200.0);
+    }
+
+    #[test]
+    /// RateLimitGauge should start out steady, point down when a limit is put in place or
+    /// lowered, point up when it's raised or removed, and show nothing once unlimited
+    fn rate_limit_gauge_tracks_direction() {
+        let gauge = RateLimitGauge::default();
+        assert!(gauge.render().is_empty());
+
+        gauge.start_at(100);
+        assert_eq!(gauge.limit(), 100);
+        assert_eq!(gauge.direction(), TuningDirection::Steady);
+
+        gauge.set(50);
+        assert_eq!(gauge.direction(), TuningDirection::Down);
+        assert!(gauge.render().starts_with("≤50/s"));
+
+        gauge.set(50);
+        assert_eq!(gauge.direction(), TuningDirection::Down);
+
+        gauge.set(75);
+        assert_eq!(gauge.direction(), TuningDirection::Up);
+
+        gauge.set(0);
+        assert_eq!(gauge.direction(), TuningDirection::Up);
+        assert!(gauge.render().is_empty());
+
+        gauge.set(25);
+        assert_eq!(gauge.direction(), TuningDirection::Down);
+
+        gauge.clear();
+        assert_eq!(gauge.limit(), 0);
+        assert_eq!(gauge.direction(), TuningDirection::Steady);
+    }
+
+    #[test]
+    /// RateLimits should add up the limits of the scans still around, cap them with
+    /// --rate-limit-global, and point in the direction of the latest change
+    fn rate_limits_add_up_scans() {
+        assert!(RateLimits::default().render().is_empty());
+
+        let limits = RateLimits::new(120);
+        let one = Arc::new(RateLimitGauge::default());
+        let two = Arc::new(RateLimitGauge::default());
+        one.start_at(100);
+        two.start_at(50);
+
+        limits.register(&one);
+        limits.register(&one);
+        limits.register(&two);
+
+        let rendered = limits.render();
+        assert!(rendered.contains(":2"));
+        assert!(rendered.contains("≤120/s"));
+
+        limits.set(&two, 10);
+        let rendered = limits.render();
+        assert!(rendered.contains("≤110/s"));
+        assert!(rendered.contains('↓'));
+
+        drop(two);
+        let rendered = limits.render();
+        assert!(rendered.contains(":1"));
+        assert!(rendered.contains("≤100/s"));
+    }
 }
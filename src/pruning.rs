@@ -0,0 +1,151 @@
+//! dead extension pruning (--prune-extensions)
+//!
+//! every response to a url built from -x/--extensions (or a collected extension) is tallied
+//! against its host. once an extension has been requested --prune-after times on a host without
+//! a single response making it past the filters, the host clearly doesn't serve it, and the
+//! extension is dropped from the expansion set for the rest of the scan. an extension that's
+//! produced even one finding on a host is never dropped there
+use std::{collections::HashMap, sync::Mutex};
+
+use reqwest::Url;
+
+use crate::url::FeroxUrl;
+
+/// Extension tallies of every host (--prune-extensions)
+#[derive(Debug, Default)]
+pub struct Pruning {
+    /// per-extension tallies, keyed by host:port and then by extension
+    hosts: Mutex<HashMap<String, HashMap<String, Tally>>>,
+}
+
+/// What's been seen for a single extension on a single host
+#[derive(Debug, Default)]
+struct Tally {
+    /// number of responses to urls ending in the extension
+    requests: usize,
+
+    /// number of those responses that made it past the filters
+    hits: usize,
+
+    /// whether the extension has been dropped on the host
+    dropped: bool,
+}
+
+/// extension the given url was expanded with, if any
+///
+/// a word from the wordlist that already ends in one of the extensions (i.e. `index.php`) is
+/// requested as-is, and isn't attributed to the extension
+pub fn extension_of<'a, I>(url: &Url, word: &str, extensions: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let path = url.path().trim_end_matches('/');
+
+    extensions
+        .into_iter()
+        .map(|ext| ext.trim_start_matches('.'))
+        .find(|ext| {
+            let suffix = format!(".{ext}");
+            !ext.is_empty() && path.ends_with(&suffix) && !word.ends_with(&suffix)
+        })
+        .map(String::from)
+}
+
+/// implementation of Pruning
+impl Pruning {
+    /// whether the given extension has been dropped on the url's host
+    pub fn is_pruned(&self, url: &Url, extension: &str) -> bool {
+        let Ok(hosts) = self.hosts.lock() else {
+            return false;
+        };
+
+        hosts
+            .get(&FeroxUrl::host_key(url))
+            .and_then(|extensions| extensions.get(extension))
+            .is_some_and(|tally| tally.dropped)
+    }
+
+    /// tally a response to a url expanded with the given extension; `hit` is whether the response
+    /// made it past the filters
+    ///
+    /// returns true when this response is the one that got the extension dropped on its host
+    pub fn record(&self, url: &Url, extension: &str, hit: bool, prune_after: usize) -> bool {
+        let Ok(mut hosts) = self.hosts.lock() else {
+            return false;
+        };
+
+        let tally = hosts
+            .entry(FeroxUrl::host_key(url))
+            .or_default()
+            .entry(extension.to_string())
+            .or_default();
+
+        tally.requests += 1;
+
+        if hit {
+            tally.hits += 1;
+        }
+
+        if !tally.dropped && tally.hits == 0 && tally.requests >= prune_after {
+            tally.dropped = true;
+            log::info!(
+                "dropped .{} on {} after {} requests without a finding",
+                extension,
+                FeroxUrl::host_key(url),
+                tally.requests
+            );
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// only the extension the url was expanded with is found, not one that was part of the word
+    fn extension_of_ignores_words_that_carry_the_extension() {
+        let extensions = vec![String::from("php"), String::from(".asp")];
+
+        let url = Url::parse("http://pruning-one.test/admin.php").unwrap();
+        assert_eq!(
+            extension_of(&url, "admin", &extensions).as_deref(),
+            Some("php")
+        );
+        assert_eq!(extension_of(&url, "admin.php", &extensions), None);
+
+        let url = Url::parse("http://pruning-one.test/default.asp/").unwrap();
+        assert_eq!(
+            extension_of(&url, "default", &extensions).as_deref(),
+            Some("asp")
+        );
+
+        let url = Url::parse("http://pruning-one.test/admin").unwrap();
+        assert_eq!(extension_of(&url, "admin", &extensions), None);
+    }
+
+    #[test]
+    /// an extension is dropped once it reaches the limit without a hit, one with a hit never is
+    fn record_drops_extensions_without_hits() {
+        let url = Url::parse("http://pruning-two.test/index.php").unwrap();
+        let other = Url::parse("http://pruning-two.test:8080/index.php").unwrap();
+        let pruning = Pruning::default();
+
+        assert!(!pruning.record(&url, "php", false, 3));
+        assert!(!pruning.record(&url, "php", false, 3));
+        assert!(!pruning.is_pruned(&url, "php"));
+        assert!(pruning.record(&url, "php", false, 3));
+        assert!(pruning.is_pruned(&url, "php"));
+
+        // only reported as dropped once, and only on the host it was seen on
+        assert!(!pruning.record(&url, "php", false, 3));
+        assert!(!pruning.is_pruned(&other, "php"));
+
+        assert!(!pruning.record(&url, "asp", true, 1));
+        assert!(!pruning.record(&url, "asp", false, 1));
+        assert!(!pruning.is_pruned(&url, "asp"));
+    }
+}
@@ -0,0 +1,741 @@
+//! raw byte mode (--raw-requests)
+//!
+//! every url reqwest sends has been through the url parser first, which normalizes it: spaces,
+//! quotes, and anything outside of ascii are percent-encoded, `\` becomes `/`, `#` starts a
+//! fragment that's never sent, and `.`/`..` segments are resolved. words that the parser would
+//! change are sent as-is instead, over a minimal HTTP/1.1 client of their own:
+//!
+//! - a fresh connection for every request (`Connection: close`)
+//! - the request target is written byte for byte, with the word appended to the scan's path
+//! - the response is read until it's complete or the server closes the connection, up to 32MiB;
+//!   a body that's closed before its length or last chunk was read is an error
+//!
+//! connections go where the client's would: to --url when the scan is addressed to the
+//! --host-header/--sni name, otherwise to the host's address as resolved with --doh and -4/-6.
+//! none of the client's other features apply to raw requests: proxies, redirects, cookies,
+//! compression, --encode/--evasion/--queries, and certificate validation are all skipped.
+//! they're counted on their own in the statistics (`raw_requests`, `raw_errors`), and their
+//! findings are tagged `raw`
+use std::{
+    collections::{HashMap, HashSet},
+    io::{ErrorKind, Read, Write},
+    net::{IpAddr, SocketAddr, TcpStream},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use hyper::http;
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    ResponseBuilderExt, Url,
+};
+use url::Host;
+
+use crate::{
+    client::HostOverride,
+    config::Configuration,
+    event_handlers::Handles,
+    fanout, safety, tls,
+    url::FeroxUrl,
+    utils::fmt_err,
+    variables::{self, HeaderVariables},
+};
+
+/// segments the url parser resolves, compared case insensitively
+const DOT_SEGMENTS: [&str; 6] = [".", "..", "%2e", ".%2e", "%2e.", "%2e%2e"];
+
+/// characters the url parser percent-encodes in a path, or otherwise treats specially
+const NORMALIZED: [char; 8] = ['"', '<', '>', '`', '{', '}', '\\', '#'];
+
+/// most bytes read for a single response, head included; anything bigger is given up on
+const MAX_RESPONSE_SIZE: usize = 32 * 1024 * 1024;
+
+/// address and TLS name of a single `host:port`, once it's been worked out; locked while it's
+/// looked up, so that requests sent at the same time wait for the first one's answer
+type Destination = Arc<tokio::sync::Mutex<Option<(SocketAddr, String)>>>;
+
+/// Where raw requests connect; worked out once for each `host:port` rather than for every
+/// request
+#[derive(Debug, Default)]
+pub struct Destinations {
+    /// --host-header/zone scoped ipv6 target, `None` when neither was used
+    host_override: Option<HostOverride>,
+
+    /// destinations looked up so far, keyed by `host:port`
+    resolved: Mutex<HashMap<String, Destination>>,
+}
+
+/// implementation of Destinations
+impl Destinations {
+    /// set up the --host-header/zone scoped ipv6 target raw requests share with the client;
+    /// nothing to do without --raw-requests
+    pub fn new(config: &Configuration) -> Result<Self> {
+        if !config.raw_requests {
+            return Ok(Self::default());
+        }
+
+        Ok(Self {
+            host_override: HostOverride::new(&config.target_url, &config.host_header, &config.sni)?,
+            ..Default::default()
+        })
+    }
+
+    /// the --host-header/zone scoped ipv6 target, when the url is addressed to its SNI name;
+    /// other urls connect to their own host, the same as with the client
+    fn host_override(&self, url: &Url) -> Option<&HostOverride> {
+        self.host_override
+            .as_ref()
+            .filter(|host_override| url.host_str() == Some(host_override.sni.as_str()))
+    }
+
+    /// address the url connects to, and the name its TLS handshake presents
+    async fn lookup(&self, url: &Url, handles: &Handles) -> Result<(SocketAddr, String)> {
+        let destination = match self.resolved.lock() {
+            Ok(mut resolved) => resolved.entry(FeroxUrl::host_key(url)).or_default().clone(),
+            // nothing to share with, the lookup still goes through
+            Err(_) => Destination::default(),
+        };
+
+        let mut destination = destination.lock().await;
+
+        if let Some(found) = destination.as_ref() {
+            return Ok(found.clone());
+        }
+
+        let found = self::destination(url, self.host_override(url), handles).await?;
+        *destination = Some(found.clone());
+
+        Ok(found)
+    }
+}
+
+/// whether the url parser would send something other than the word itself
+pub fn needs_raw(word: &str) -> bool {
+    word.chars()
+        .any(|c| c.is_ascii_control() || c == ' ' || !c.is_ascii() || NORMALIZED.contains(&c))
+        || word
+            .split('/')
+            .any(|segment| DOT_SEGMENTS.contains(&segment.to_ascii_lowercase().as_str()))
+}
+
+/// request targets (path and query) the word is sent as, grouped by variant the same way as
+/// `FeroxUrl::formatted_url_groups`: the word itself (word/ with --add-slash, both with
+/// --dual-slash), then the word with each extension, passed in first and collected after
+pub fn request_targets(
+    target_url: &str,
+    word: &str,
+    collected_extensions: HashSet<String>,
+    config: &Configuration,
+) -> Result<Vec<Vec<String>>> {
+    let url =
+        Url::parse(target_url).with_context(|| fmt_err(&format!("Invalid target {target_url}")))?;
+
+    let base = format!("{}/", url.path().trim_end_matches('/'));
+    let word = format!("{base}{}", word.trim_start_matches('/'));
+
+    let words = if config.dual_slash {
+        vec![word.clone(), format!("{word}/")]
+    } else if config.add_slash {
+        vec![format!("{word}/")]
+    } else {
+        vec![word.clone()]
+    };
+
+    let mut collected: Vec<_> = collected_extensions.into_iter().collect();
+    collected.sort();
+
+    let mut groups = vec![words];
+
+    for ext in config.extensions.iter().chain(collected.iter()) {
+        let target = if ext == "/" {
+            format!("{word}/")
+        } else {
+            format!("{word}.{ext}")
+        };
+
+        groups.push(vec![target]);
+    }
+
+    Ok(groups)
+}
+
+/// headers sent with a raw request: Host, the configured ones (with their variables expanded),
+/// then the ones given, which replace configured headers of the same name
+fn headers(
+    url: &Url,
+    given: &[(String, String)],
+    config: &Configuration,
+    host_override: Option<&HostOverride>,
+    variables: &HeaderVariables,
+) -> Vec<(String, String)> {
+    let host = match (host_override, url.host_str(), url.port()) {
+        (Some(host_override), ..) => host_override.host.clone(),
+        (None, Some(host), Some(port)) => format!("{host}:{port}"),
+        (None, Some(host), None) => host.to_string(),
+        _ => String::new(),
+    };
+
+    let mut headers = vec![
+        (String::from("Host"), host),
+        (String::from("User-Agent"), config.user_agent.clone()),
+    ];
+
+    let mut configured: Vec<_> = config.headers.iter().collect();
+    configured.sort();
+
+    for (name, value) in configured
+        .into_iter()
+        .chain(given.iter().map(|(n, v)| (n, v)))
+    {
+        let value = if variables::is_dynamic(value) {
+            variables.expand(value)
+        } else {
+            value.trim().to_string()
+        };
+
+        match headers
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+        {
+            Some(header) => header.1 = value,
+            None => headers.push((name.clone(), value)),
+        }
+    }
+
+    headers.push((String::from("Connection"), String::from("close")));
+    headers
+}
+
+/// the request as it's written to the socket
+fn serialize(method: &str, target: &str, headers: &[(String, String)], body: &[u8]) -> Vec<u8> {
+    let mut request = format!("{method} {target} HTTP/1.1\r\n").into_bytes();
+
+    for (name, value) in headers {
+        request.extend_from_slice(format!("{name}: {value}\r\n").as_bytes());
+    }
+
+    if !body.is_empty() {
+        request.extend_from_slice(format!("Content-Length: {}\r\n", body.len()).as_bytes());
+    }
+
+    request.extend_from_slice(b"\r\n");
+    request.extend_from_slice(body);
+    request
+}
+
+/// status line and headers of a response
+struct Head {
+    /// response status code
+    status: u16,
+
+    /// headers, in the order they were sent
+    headers: Vec<(String, Vec<u8>)>,
+
+    /// offset of the body in the data read
+    body_start: usize,
+}
+
+/// the head of a response; `None` until all of it has been read
+fn parse_head(data: &[u8]) -> Result<Option<Head>> {
+    let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") else {
+        return Ok(None);
+    };
+
+    let head = String::from_utf8_lossy(&data[..end]);
+    let mut lines = head.split("\r\n");
+
+    let status_line = lines.next().unwrap_or_default();
+
+    let status = match status_line.split(' ').collect::<Vec<_>>()[..] {
+        [version, code, ..] if version.starts_with("HTTP/") => code
+            .parse::<u16>()
+            .with_context(|| format!("Invalid status line: {status_line}"))?,
+        _ => bail!("Invalid status line: {status_line}"),
+    };
+
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().as_bytes().to_vec()))
+        .collect();
+
+    Ok(Some(Head {
+        status,
+        headers,
+        body_start: end + 4,
+    }))
+}
+
+/// value of the given header, compared case insensitively
+fn header<'a>(headers: &'a [(String, Vec<u8>)], name: &str) -> Option<&'a [u8]> {
+    headers
+        .iter()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_slice())
+}
+
+/// whether the response is chunked
+fn is_chunked(headers: &[(String, Vec<u8>)]) -> bool {
+    header(headers, "transfer-encoding").is_some_and(|value| {
+        String::from_utf8_lossy(value)
+            .to_ascii_lowercase()
+            .contains("chunked")
+    })
+}
+
+/// value of the Content-Length header, when there's a valid one
+fn content_length(headers: &[(String, Vec<u8>)]) -> Option<usize> {
+    header(headers, "content-length")
+        .and_then(|value| String::from_utf8_lossy(value).parse::<usize>().ok())
+}
+
+/// the body of a chunked response, put back together; `None` until the last chunk was read
+fn dechunk(mut data: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+
+    loop {
+        let line_end = data.windows(2).position(|window| window == b"\r\n")?;
+        let size_line = String::from_utf8_lossy(&data[..line_end]);
+        let size = usize::from_str_radix(size_line.split(';').next()?.trim(), 16).ok()?;
+
+        data = &data[line_end + 2..];
+
+        if size == 0 {
+            return Some(body);
+        }
+
+        if data.len() < size + 2 {
+            return None;
+        }
+
+        body.extend_from_slice(&data[..size]);
+        data = &data[size + 2..];
+    }
+}
+
+/// the body of the response, once all of it has been read; without a length or chunking, the
+/// body only ends when the connection does
+fn complete_body(data: &[u8], head_request: bool) -> Result<Option<Vec<u8>>> {
+    let Some(Head {
+        status,
+        headers,
+        body_start: start,
+    }) = parse_head(data)?
+    else {
+        return Ok(None);
+    };
+
+    let body = &data[start..];
+
+    if head_request || status == 204 || status == 304 || (100..200).contains(&status) {
+        return Ok(Some(Vec::new()));
+    }
+
+    if is_chunked(&headers) {
+        return Ok(dechunk(body));
+    }
+
+    Ok(match content_length(&headers) {
+        Some(length) if body.len() >= length => Some(body[..length].to_vec()),
+        _ => None,
+    })
+}
+
+/// read until the response is complete, or the other side is done sending; a body with a
+/// length or chunking that the other side stopped sending partway through is an error
+fn read_response<S: Read>(stream: &mut S, head_request: bool) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut buffer = [0; 8192];
+
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => {
+                data.extend_from_slice(&buffer[..read]);
+
+                if complete_body(&data, head_request)?.is_some() {
+                    return Ok(data);
+                }
+
+                if data.len() > MAX_RESPONSE_SIZE {
+                    bail!("Response is larger than {MAX_RESPONSE_SIZE} bytes");
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            // servers regularly skip the TLS close_notify once they've sent everything
+            Err(_) if !data.is_empty() => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if let Some(head) = parse_head(&data)? {
+        if is_chunked(&head.headers) || content_length(&head.headers).is_some() {
+            bail!("Connection closed before the whole body was read");
+        }
+    }
+
+    Ok(data)
+}
+
+/// address a raw request to the given url connects to, and the name its TLS handshake
+/// presents; the same one the client would pick
+///
+/// - --host-header/zone scoped ipv6 targets: the target given via --url, presenting the SNI name
+///   (zone scoped targets present nothing, like any other ip address)
+/// - ip addresses: the address itself
+/// - anything else: the host's first address of the family allowed by -4/-6, looked up through
+///   --doh when it's used
+async fn destination(
+    url: &Url,
+    host_override: Option<&HostOverride>,
+    handles: &Handles,
+) -> Result<(SocketAddr, String)> {
+    let (Some(host), Some(port)) = (url.host(), url.port_or_known_default()) else {
+        bail!("{url} doesn't have a host to send raw requests to");
+    };
+
+    if let Some(host_override) = host_override {
+        let mut address = host_override.addr;
+        address.set_port(port);
+
+        // a zone scoped ipv6 target's made up name stays out of the handshake, the same as the
+        // client leaves it out
+        let name = if host_override.presents_sni {
+            host_override.sni.clone()
+        } else {
+            address.ip().to_string()
+        };
+
+        return Ok((address, name));
+    }
+
+    let ip = match host {
+        Host::Ipv4(ip) => IpAddr::V4(ip),
+        Host::Ipv6(ip) => IpAddr::V6(ip),
+        Host::Domain(domain) => fanout::resolve(domain, port, handles)
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| fmt_err(&format!("Could not resolve {domain}")))?,
+    };
+
+    Ok((SocketAddr::new(ip, port), host.to_string()))
+}
+
+/// send the given bytes to the address, and read back the response; https urls complete a TLS
+/// handshake presenting `name` first. blocking
+fn exchange(
+    url: &Url,
+    address: SocketAddr,
+    name: &str,
+    request: &[u8],
+    head_request: bool,
+    timeout: Duration,
+) -> Result<Vec<u8>> {
+    if url.scheme() == "https" {
+        let mut stream = tls::handshake_with(address, name, timeout)?;
+        stream.write_all(request)?;
+        return read_response(&mut stream, head_request);
+    }
+
+    let mut stream = TcpStream::connect_timeout(&address, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    stream.write_all(request)?;
+    read_response(&mut stream, head_request)
+}
+
+/// turn the bytes read off the wire into a response the rest of the scan can handle; `url` is
+/// what's reported for it
+fn into_response(data: &[u8], url: Url, head_request: bool) -> Result<reqwest::Response> {
+    let Some(Head {
+        status,
+        headers,
+        body_start: start,
+    }) = parse_head(data)?
+    else {
+        bail!("Connection closed before a complete response was read");
+    };
+
+    // a body without a length or chunking is everything the server sent before closing
+    let body = complete_body(data, head_request)?.unwrap_or_else(|| data[start..].to_vec());
+
+    let mut builder = http::Response::builder().status(status).url(url);
+
+    for (name, value) in &headers {
+        if name.eq_ignore_ascii_case("transfer-encoding") {
+            // already put back together
+            continue;
+        }
+
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_bytes(value),
+        ) {
+            builder = builder.header(name, value);
+        }
+    }
+
+    Ok(builder.body(body)?.into())
+}
+
+/// send a single raw request for `target` (path and query, sent byte for byte) to the host of
+/// `target_url`; the response is reported under `display_url`
+pub async fn send(
+    target_url: &str,
+    target: &str,
+    display_url: Url,
+    method: &str,
+    headers: &[(String, String)],
+    body: &[u8],
+    handles: &Handles,
+) -> Result<reqwest::Response> {
+    let config = &handles.config;
+
+    // --safe-methods-only; the same backstop as in make_request, which raw requests never reach
+    if config.safe_methods_only && !safety::is_safe_method(method) {
+        bail!(
+            "--safe-methods-only refused to send {} {}",
+            method,
+            display_url
+        );
+    }
+
+    let url =
+        Url::parse(target_url).with_context(|| fmt_err(&format!("Invalid target {target_url}")))?;
+
+    let destinations = &handles.features.raw;
+
+    let headers = self::headers(
+        &url,
+        headers,
+        config,
+        destinations.host_override(&url),
+        &handles.features.variables,
+    );
+    let request = serialize(method, target, &headers, body);
+    let head_request = method.eq_ignore_ascii_case("HEAD");
+    let timeout = Duration::from_secs(config.timeout);
+
+    let (address, name) = destinations.lookup(&url, handles).await?;
+
+    let data = tokio::task::spawn_blocking(move || {
+        exchange(&url, address, &name, &request, head_request, timeout)
+    })
+    .await??;
+
+    into_response(&data, display_url, head_request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::TcpListener, sync::Arc};
+
+    #[test]
+    /// words the url parser would rewrite are sent raw, everything else isn't
+    fn needs_raw_flags_words_the_url_parser_changes() {
+        for word in [
+            "a b",
+            "admin%00\0",
+            "back\\slash",
+            "frag#ment",
+            "../etc/passwd",
+            "a/%2E%2e/b",
+            "quote\"",
+            "caf\u{e9}",
+            "{{x}}",
+        ] {
+            assert!(needs_raw(word), "{word}");
+        }
+
+        for word in ["admin", "admin%00", "a..b", ".htaccess", "a?b=c", "a|b^c"] {
+            assert!(!needs_raw(word), "{word}");
+        }
+    }
+
+    #[test]
+    /// the word is appended to the scan's path as-is, each extension gets a group of its own
+    fn request_targets_groups_variants() {
+        let config = Configuration {
+            extensions: vec![String::from("php")],
+            ..Default::default()
+        };
+
+        let groups = request_targets(
+            "http://localhost/api",
+            "a b",
+            HashSet::from([String::from("bak")]),
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(
+            groups,
+            vec![
+                vec![String::from("/api/a b")],
+                vec![String::from("/api/a b.php")],
+                vec![String::from("/api/a b.bak")],
+            ]
+        );
+    }
+
+    #[test]
+    /// chunked bodies are put back together, content-length bodies are cut to length
+    fn complete_body_handles_lengths_and_chunks() {
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        assert_eq!(
+            complete_body(chunked, false).unwrap().unwrap(),
+            b"Wikipedia"
+        );
+        assert!(complete_body(&chunked[..chunked.len() - 5], false)
+            .unwrap()
+            .is_none());
+
+        let sized = b"HTTP/1.1 404 Not Found\r\nContent-Length: 3\r\n\r\nabcdef";
+        assert_eq!(complete_body(sized, false).unwrap().unwrap(), b"abc");
+        assert_eq!(complete_body(sized, true).unwrap().unwrap(), b"");
+        assert!(complete_body(b"HTTP/1.1 200 OK\r\n", false)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    /// bodies with a length or chunking have to be read in full, others end with the connection;
+    /// either way, reading stops once the response gets too big
+    fn read_response_requires_complete_bodies() {
+        let read = |data: &[u8]| read_response(&mut &data[..], false);
+
+        assert!(read(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhel").is_err());
+        assert!(read(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhel").is_err());
+        assert_eq!(
+            read(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello").unwrap(),
+            b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello"
+        );
+        assert_eq!(
+            read(b"HTTP/1.1 200 OK\r\n\r\nuntil close").unwrap(),
+            b"HTTP/1.1 200 OK\r\n\r\nuntil close"
+        );
+        assert!(read_response(&mut &b"HTTP/1.1 204 No Content\r\n\r\n"[..], true).is_ok());
+
+        let mut endless = (&b"HTTP/1.1 200 OK\r\n\r\n"[..]).chain(std::io::repeat(b'a'));
+        assert!(read_response(&mut endless, false).is_err());
+    }
+
+    /// answer a single request on the given listener, handing back the request that was read
+    fn serve_once(listener: TcpListener) -> std::thread::JoinHandle<String> {
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nX-Raw: yes\r\n\r\nhello")
+                .unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// the request target reaches the server untouched, and the response comes back whole
+    async fn send_writes_the_target_byte_for_byte() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = serve_once(listener);
+
+        let target_url = format!("http://127.0.0.1:{port}/");
+        let display = Url::parse(&format!("{target_url}a%20b")).unwrap();
+        let (handles, _rx) = Handles::for_testing(None, Some(Arc::new(Configuration::default())));
+
+        let response = send(
+            &target_url,
+            "/a b/../x",
+            display.clone(),
+            "GET",
+            &[],
+            &[],
+            &handles,
+        )
+        .await
+        .unwrap();
+
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("GET /a b/../x HTTP/1.1\r\n"));
+        assert!(request.contains(&format!("Host: 127.0.0.1:{port}\r\n")));
+        assert!(request.contains("Connection: close\r\n"));
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.url(), &display);
+        assert_eq!(response.headers()["x-raw"], "yes");
+        assert_eq!(response.text().await.unwrap(), "hello");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// requests to the same host and port share a single lookup, other ports get their own
+    async fn destinations_are_looked_up_once_per_host() {
+        let (handles, _rx) = Handles::for_testing(None, None);
+        let destinations = Destinations::default();
+
+        for url in [
+            "http://127.0.0.1:8080/a",
+            "http://127.0.0.1:8080/b%20c",
+            "https://127.0.0.1/",
+        ] {
+            destinations
+                .lookup(&Url::parse(url).unwrap(), &handles)
+                .await
+                .unwrap();
+        }
+
+        let resolved = destinations.resolved.lock().unwrap();
+        assert_eq!(resolved.len(), 2);
+
+        let destination = resolved["127.0.0.1:8080"].try_lock().unwrap().clone();
+        assert_eq!(
+            destination,
+            Some(("127.0.0.1:8080".parse().unwrap(), String::from("127.0.0.1")))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// with --host-header, the scan is addressed to the vhost (which doesn't resolve), but raw
+    /// requests still connect to --url and carry the vhost in their Host header
+    async fn send_connects_to_url_with_host_header() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = serve_once(listener);
+
+        let config = Configuration {
+            target_url: format!("http://127.0.0.1:{port}/"),
+            host_header: String::from("vhost.invalid"),
+            raw_requests: true,
+            ..Default::default()
+        };
+
+        let host_override = HostOverride::new(&config.target_url, &config.host_header, "")
+            .unwrap()
+            .unwrap();
+        let target_url = host_override.target(&config.target_url).unwrap();
+        assert_eq!(target_url, format!("http://vhost.invalid:{port}/"));
+
+        let display = Url::parse(&format!("{target_url}a%20b")).unwrap();
+        let (handles, _rx) = Handles::for_testing(None, Some(Arc::new(config)));
+
+        let response = send(&target_url, "/a b", display, "GET", &[], &[], &handles)
+            .await
+            .unwrap();
+
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("GET /a b HTTP/1.1\r\n"));
+        assert!(request.contains("Host: vhost.invalid\r\n"));
+        assert_eq!(response.status(), 200);
+    }
+}
@@ -2,29 +2,425 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     fmt,
+    io::Read,
+    net::IpAddr,
     str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Context, Result};
 use console::style;
+use encoding_rs::{Encoding, UTF_8};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use lazy_static::lazy_static;
+use openssl::sha::sha256;
+use regex::Regex;
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue},
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_ENCODING, CONTENT_RANGE, CONTENT_TYPE},
     Method, Response, StatusCode, Url,
 };
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::{
+    baseline,
+    client::{RedirectHop, RemoteIp, RequestId},
     config::OutputLevel,
     event_handlers::{Command, Handles},
+    filters::SIM_HASHER,
+    nlp::preprocess,
+    rules::Severity,
+    search::{BodySearch, Search, SearchMatch},
+    statistics::StatError,
     traits::FeroxSerialize,
     url::FeroxUrl,
-    utils::{self, fmt_err, parse_url_with_raw_path, status_colorizer},
+    utils::{self, display_url, fmt_err, parse_url_with_raw_path, status_colorizer},
     CommandSender,
 };
 
+lazy_static! {
+    /// `<meta http-equiv="refresh" ...>` tags, attributes in any order
+    static ref META_REFRESH_REGEX: Regex =
+        Regex::new(r#"(?is)<meta\b[^>]*http-equiv\s*=\s*["']?refresh[^>]*>"#).unwrap();
+
+    /// the destination within a meta refresh's `content="0; url=/somewhere"`
+    static ref META_REFRESH_URL_REGEX: Regex =
+        Regex::new(r#"(?i)\burl\s*=\s*['"]?([^'">\s;]+)"#).unwrap();
+
+    /// trivial javascript redirects, i.e. `window.location.href = "/somewhere"` or
+    /// `location.replace('/somewhere')`
+    static ref JS_REDIRECT_REGEX: Regex = Regex::new(
+        r#"(?:\b(?:window|document|top|self)\.)?\blocation(?:\.href)?\s*=\s*["']([^"']+)["']|\blocation\.(?:replace|assign)\(\s*["']([^"']+)["']\s*\)"#
+    )
+    .unwrap();
+
+    /// the page's `<title>`
+    static ref TITLE_REGEX: Regex = Regex::new(r"(?is)<title\b[^>]*>(.*?)</title>").unwrap();
+
+    /// every `<meta ...>` tag
+    static ref META_TAG_REGEX: Regex = Regex::new(r"(?is)<meta\b[^>]*>").unwrap();
+
+    /// a single attribute within a tag, quoted or not
+    static ref ATTRIBUTE_REGEX: Regex =
+        Regex::new(r#"(?is)\b([a-z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap();
+}
+
+/// how much of an html body is searched for its title and meta tags; they belong in the head
+const PAGE_META_MAX_SIZE: usize = 65536;
+
+/// titles longer than this are shortened in terminal output, json gets the whole thing
+const TITLE_DISPLAY_LENGTH: usize = 80;
+
+/// title, generator, and charset of an html page (--titles)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PageMeta {
+    /// text of the `<title>`, whitespace collapsed
+    pub title: Option<String>,
+
+    /// content of `<meta name="generator">`, i.e. `WordPress 6.2`
+    pub generator: Option<String>,
+
+    /// charset from the Content-Type header, or the one the page declares itself
+    pub charset: Option<String>,
+}
+
+/// charset parameter of a Content-Type value, i.e. `text/html; charset=utf-8` => `utf-8`
+fn content_type_charset(value: &str) -> Option<&str> {
+    value.split(';').skip(1).find_map(|param| {
+        let (name, label) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| label.trim().trim_matches('"'))
+    })
+}
+
+/// whether the response is an html page, going by its Content-Type or, without one, its body
+fn is_html(headers: &HeaderMap, text: &str) -> bool {
+    match headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(content_type) => content_type.to_ascii_lowercase().contains("html"),
+        None => {
+            let start = text.trim_start().as_bytes();
+
+            [b"<!doctype html".as_slice(), b"<html"]
+                .iter()
+                .any(|prefix| {
+                    start.len() >= prefix.len()
+                        && start[..prefix.len()].eq_ignore_ascii_case(prefix)
+                })
+        }
+    }
+}
+
+/// replace the html entities likely to show up in a title with the characters they stand for
+fn unescape_html(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .get(1..rest.find(';').unwrap_or(0))
+            .filter(|entity| entity.len() <= 8);
+
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        });
+
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                unescaped.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// attributes of the given tag, names lowercased
+fn tag_attributes(tag: &str) -> HashMap<String, String> {
+    ATTRIBUTE_REGEX
+        .captures_iter(tag)
+        .filter_map(|captures| {
+            let name = captures.get(1)?.as_str().to_ascii_lowercase();
+            let value = captures.iter().skip(2).flatten().next()?.as_str();
+            Some((name, unescape_html(value.trim())))
+        })
+        .collect()
+}
+
+/// title, meta generator, and charset of the given response; `None` when it isn't html
+fn find_page_meta(headers: &HeaderMap, text: &str) -> Option<PageMeta> {
+    if !is_html(headers, text) {
+        return None;
+    }
+
+    let mut end = text.len().min(PAGE_META_MAX_SIZE);
+
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let head = &text[..end];
+
+    let title = TITLE_REGEX
+        .captures(head)
+        .map(|captures| {
+            unescape_html(&captures[1])
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|title| !title.is_empty());
+
+    let mut generator = None;
+    let mut declared_charset = None;
+
+    for tag in META_TAG_REGEX.find_iter(head) {
+        let attributes = tag_attributes(tag.as_str());
+
+        if generator.is_none()
+            && attributes
+                .get("name")
+                .is_some_and(|name| name.eq_ignore_ascii_case("generator"))
+        {
+            generator = attributes
+                .get("content")
+                .filter(|content| !content.is_empty())
+                .cloned();
+        }
+
+        if declared_charset.is_none() {
+            // <meta charset="utf-8"> or <meta http-equiv="Content-Type" content="text/html; charset=utf-8">
+            declared_charset = attributes.get("charset").cloned().or_else(|| {
+                attributes
+                    .get("http-equiv")
+                    .filter(|equiv| equiv.eq_ignore_ascii_case("content-type"))
+                    .and(attributes.get("content"))
+                    .and_then(|content| content_type_charset(content))
+                    .map(String::from)
+            });
+        }
+    }
+
+    let charset = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(content_type_charset)
+        .map(String::from)
+        .or(declared_charset)
+        .filter(|charset| !charset.is_empty());
+
+    Some(PageMeta {
+        title,
+        generator,
+        charset,
+    })
+}
+
+/// bodies larger than this aren't checked for meta refresh/javascript redirects; redirect stubs
+/// are tiny, while larger pages that merely contain navigation code aren't redirects
+const SOFT_REDIRECT_MAX_SIZE: usize = 4096;
+
+/// look for a meta refresh tag or trivial javascript redirect in the given body, returning its
+/// destination made absolute against `url`; redirects back to `url` itself are ignored
+fn find_soft_redirect(url: &Url, body: &str) -> Option<String> {
+    if body.len() > SOFT_REDIRECT_MAX_SIZE {
+        return None;
+    }
+
+    let location = META_REFRESH_REGEX
+        .find(body)
+        .and_then(|tag| META_REFRESH_URL_REGEX.captures(tag.as_str()))
+        .or_else(|| JS_REDIRECT_REGEX.captures(body))
+        .and_then(|captures| captures.iter().skip(1).flatten().next())?
+        .as_str();
+
+    let destination = url.join(location).ok()?;
+
+    if &destination == url {
+        return None;
+    }
+
+    Some(destination.to_string())
+}
+
+/// number of bytes at the start of a body examined when deciding whether it's binary
+const BINARY_SNIFF_SIZE: usize = 1024;
+
+/// signatures of common binary formats that can start out looking like text
+const BINARY_MAGIC: [&[u8]; 5] = [
+    b"\x1f\x8b",          // gzip
+    b"PK\x03\x04",        // zip (docx, jar, ...)
+    b"\x89PNG\r\n\x1a\n", // png
+    b"\xff\xd8\xff",      // jpeg
+    b"%PDF-",             // pdf
+];
+
+/// whether the given body is binary data (images, archives, compressed bodies that weren't
+/// decoded, ...) rather than text; only the start of the body is examined
+///
+/// valid utf-8 is always text; anything else is binary when it has a NUL byte or is more than
+/// 10% control characters, so that text in legacy single-byte charsets still counts as text
+pub(crate) fn is_binary(body: &[u8]) -> bool {
+    let sample = &body[..body.len().min(BINARY_SNIFF_SIZE)];
+
+    if BINARY_MAGIC.iter().any(|magic| sample.starts_with(magic)) || sample.contains(&0) {
+        return true;
+    }
+
+    match std::str::from_utf8(sample) {
+        Ok(_) => return false,
+        // the sample cut a multibyte character in half, the rest of it was fine
+        Err(e) if e.error_len().is_none() => return false,
+        Err(_) => {}
+    }
+
+    let control = sample
+        .iter()
+        .filter(|&&byte| (byte < 0x20 && !b"\t\n\r\x0c".contains(&byte)) || byte == 0x7f)
+        .count();
+
+    control * 10 > sample.len()
+}
+
+/// decode a body using the charset from its Content-Type, utf-8 when there isn't one (or it's
+/// unknown); invalid sequences are replaced instead of failing the whole body
+fn decode_body(body: &[u8], headers: &HeaderMap) -> String {
+    let encoding = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(content_type_charset)
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+
+    let (text, _, _) = encoding.decode(body);
+    text.into_owned()
+}
+
+/// read the whole body of the given response, along with why it was cut short, if it was, and
+/// any --search-regex matches found in it; with --read-timeout, a body that goes that long
+/// without sending anything is given up on
+async fn read_body(
+    mut response: Response,
+    url: &Url,
+    read_timeout: u64,
+    search: &Search,
+) -> (Vec<u8>, Option<StatError>, Vec<SearchMatch>) {
+    // --read-timeout; zero means there's no limit
+    let read_timeout = (read_timeout > 0).then(|| Duration::from_secs(read_timeout));
+    let mut body = Vec::new();
+
+    // searched a chunk at a time, as it comes in
+    let mut searching = search.start(url.as_str());
+    let finish =
+        |searching: Option<BodySearch>| searching.map(BodySearch::finish).unwrap_or_default();
+
+    loop {
+        let chunk = match read_timeout {
+            Some(limit) => match tokio::time::timeout(limit, response.chunk()).await {
+                Ok(chunk) => chunk,
+                Err(_) => {
+                    log::warn!(
+                        "{} sent nothing for {}s, giving up on its body",
+                        url,
+                        limit.as_secs()
+                    );
+                    return (body, Some(StatError::ReadTimeout), finish(searching));
+                }
+            },
+            None => response.chunk().await,
+        };
+
+        match chunk {
+            Ok(Some(chunk)) => {
+                if let Some(searching) = searching.as_mut() {
+                    searching.feed(&chunk);
+                }
+
+                body.extend_from_slice(&chunk);
+            }
+            Ok(None) => return (body, None, finish(searching)),
+            Err(e) => {
+                log::warn!("Could not read body from {}: {:?}", url, e);
+                return (body, Some(StatError::BodyRead), finish(searching));
+            }
+        }
+    }
+}
+
+/// content-coding the body was sent with, lowercased; `None` when there wasn't one (or it was
+/// identity)
+fn content_encoding(headers: &HeaderMap) -> Option<String> {
+    let encoding = headers
+        .get(CONTENT_ENCODING)?
+        .to_str()
+        .ok()?
+        .trim()
+        .to_ascii_lowercase();
+
+    (!encoding.is_empty() && encoding != "identity").then_some(encoding)
+}
+
+/// undo the given content-coding; `None` for codings that aren't supported (br, stacked codings)
+/// or bodies that don't decode, which are then left as they are
+fn decompress(body: &[u8], encoding: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+
+    match encoding {
+        "gzip" | "x-gzip" => GzDecoder::new(body).read_to_end(&mut decoded).ok()?,
+        // deflate is supposed to be zlib wrapped, but plenty of servers send it raw
+        "deflate" => ZlibDecoder::new(body)
+            .read_to_end(&mut decoded)
+            .or_else(|_| {
+                decoded.clear();
+                DeflateDecoder::new(body).read_to_end(&mut decoded)
+            })
+            .ok()?,
+        _ => return None,
+    };
+
+    Some(decoded)
+}
+
+/// number of lines and words in a body's text; words are separated by any unicode whitespace
+///
+/// binary bodies don't have either, their size is all that's meaningful
+fn count_lines_and_words(text: &str, binary: bool) -> (usize, usize) {
+    if binary {
+        return (0, 0);
+    }
+
+    let line_count = text.lines().count();
+    let word_count = text.lines().map(|s| s.split_whitespace().count()).sum();
+
+    (line_count, word_count)
+}
+
 /// A `FeroxResponse`, derived from a `Response` to a submitted `Request`
 #[derive(Debug, Clone)]
 pub struct FeroxResponse {
@@ -61,8 +457,53 @@ pub struct FeroxResponse {
     /// whether the user passed --quiet|--silent on the command line
     pub(crate) output_level: OutputLevel,
 
+    /// whether the user passed --punycode on the command line
+    punycode: bool,
+
     /// Url's file extension, if one exists
     pub(crate) extension: Option<String>,
+
+    /// tags attached to the response by --plugin on_response hooks and --baseline
+    tags: Vec<String>,
+
+    /// absolute destination of a meta refresh/javascript redirect found in the body, if any
+    soft_redirect: Option<String>,
+
+    /// redirects followed on the way to this response (--redirects), in order
+    redirect_chain: Vec<RedirectHop>,
+
+    /// value of the --request-id header sent with the request, if any
+    request_id: Option<String>,
+
+    /// address the response was served from, unless it came through a proxy
+    remote_ip: Option<IpAddr>,
+
+    /// content-coding the body was sent with (Content-Encoding), if any
+    content_encoding: Option<String>,
+
+    /// whether the body was decompressed before being measured (--decompress)
+    decompressed: bool,
+
+    /// lowercase hex SHA-256 of the body, as measured
+    sha256: String,
+
+    /// simhash signature of the body, the same one --filter-similar-to compares (--fuzzy-hash)
+    simhash: Option<u64>,
+
+    /// title, generator, and charset of an html body (--titles)
+    page_meta: Option<PageMeta>,
+
+    /// why the body couldn't be read in full, if it couldn't; what was read is still measured
+    body_read_error: Option<StatError>,
+
+    /// how different the body is from the --baseline-url body, from 0.0 to 1.0
+    baseline_diff: Option<f64>,
+
+    /// --search-regex matches found in the body, until they're reported
+    search_matches: Vec<SearchMatch>,
+
+    /// highest severity given to the response by a --rules rule, if any
+    severity: Option<Severity>,
 }
 
 /// implement Default trait for FeroxResponse
@@ -81,7 +522,22 @@ impl Default for FeroxResponse {
             headers: Default::default(),
             wildcard: false,
             output_level: Default::default(),
+            punycode: false,
             extension: None,
+            tags: Vec::new(),
+            soft_redirect: None,
+            redirect_chain: Vec::new(),
+            request_id: None,
+            remote_ip: None,
+            content_encoding: None,
+            decompressed: false,
+            sha256: String::new(),
+            simhash: None,
+            page_meta: None,
+            body_read_error: None,
+            baseline_diff: None,
+            search_matches: Vec::new(),
+            severity: None,
         }
     }
 }
@@ -138,6 +594,71 @@ impl FeroxResponse {
         self.content_length
     }
 
+    /// Get the content-coding the body was sent with, if any
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Get whether the body was decompressed before being measured (--decompress)
+    pub fn decompressed(&self) -> bool {
+        self.decompressed
+    }
+
+    /// Get the lowercase hex SHA-256 of the body
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// Get the address the response was served from, unless it came through a proxy
+    pub fn remote_ip(&self) -> Option<IpAddr> {
+        self.remote_ip
+    }
+
+    /// Get the simhash signature of the body, only computed with --fuzzy-hash
+    pub fn simhash(&self) -> Option<u64> {
+        self.simhash
+    }
+
+    /// Get how different the body is from the --baseline-url body, if that was used
+    pub fn baseline_diff(&self) -> Option<f64> {
+        self.baseline_diff
+    }
+
+    /// Get the title, generator, and charset of an html body, only extracted with --titles
+    pub fn page_meta(&self) -> Option<&PageMeta> {
+        self.page_meta.as_ref()
+    }
+
+    /// Get why the body couldn't be read in full, if it couldn't (body-read or read-timeout)
+    pub fn body_read_error(&self) -> Option<StatError> {
+        self.body_read_error
+    }
+
+    /// add a match to be reported along with the ones --search-regex found in the body
+    pub(crate) fn add_search_match(&mut self, found: SearchMatch) {
+        self.search_matches.push(found);
+    }
+
+    /// hand over the --search-regex matches found in the body; they're only reported once
+    pub(crate) fn take_search_matches(&mut self) -> Vec<SearchMatch> {
+        std::mem::take(&mut self.search_matches)
+    }
+
+    /// Get the redirects followed on the way to this response
+    pub fn redirect_chain(&self) -> &[RedirectHop] {
+        &self.redirect_chain
+    }
+
+    /// value of the --request-id header sent with the request, if any
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Get the destination of a meta refresh/javascript redirect found in this response's body
+    pub fn soft_redirect(&self) -> Option<&str> {
+        self.soft_redirect.as_deref()
+    }
+
     /// Set `FeroxResponse`'s `url` attribute, has no affect if an error occurs
     pub fn set_url(&mut self, url: &str) {
         match parse_url_with_raw_path(url) {
@@ -155,17 +676,91 @@ impl FeroxResponse {
         self.wildcard = is_wildcard;
     }
 
+    /// Get the tags attached to this `FeroxResponse` by plugins/--baseline
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// attach a tag to this `FeroxResponse`, ignoring duplicates
+    pub(crate) fn add_tag(&mut self, tag: String) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Get the severity given to this `FeroxResponse` by --rules, if any
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// --rules; keep the given severity if it's higher than the current one
+    pub(crate) fn raise_severity(&mut self, severity: Severity) {
+        self.severity = self.severity.max(Some(severity));
+    }
+
+    /// --baseline; a 304 came back for a resource the baseline scan already saw, report it with
+    /// the status and size seen back then, tagged as unchanged
+    pub(crate) fn mark_unchanged(&mut self, known: &FeroxResponse) {
+        self.status = known.status;
+        self.content_length = known.content_length;
+        self.line_count = known.line_count;
+        self.word_count = known.word_count;
+        self.add_tag(baseline::UNCHANGED.to_string());
+    }
+
+    /// --range-probe; turn a 206 received in response to `Range: bytes=0-0` into the 200 the full
+    /// request would get, using the size from its Content-Range header. returns the full size,
+    /// or `None` when this isn't a partial response with a known size
+    ///
+    /// note: line/word counts still reflect the partial body
+    pub(crate) fn resolve_range_probe(&mut self) -> Option<u64> {
+        if self.status != StatusCode::PARTIAL_CONTENT {
+            return None;
+        }
+
+        // Content-Range: bytes 0-0/1234, where the size may be * when unknown
+        let size = self
+            .headers
+            .get(CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .rsplit_once('/')?
+            .1
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+
+        self.status = StatusCode::OK;
+        self.content_length = size;
+
+        Some(size)
+    }
+
+    /// set `status` attribute
+    #[cfg(test)]
+    pub fn set_status(&mut self, status: u16) {
+        self.status = StatusCode::from_u16(status).unwrap();
+    }
+
+    /// set `headers` attribute
+    #[cfg(test)]
+    pub fn set_headers(&mut self, headers: HeaderMap) {
+        self.headers = headers;
+    }
+
     /// set `text` attribute; update words/lines/content_length
     #[cfg(test)]
     pub fn set_text(&mut self, text: &str) {
         self.text = String::from(text);
         self.content_length = self.text.len() as u64;
-        self.line_count = self.text.lines().count();
-        self.word_count = self
-            .text
-            .lines()
-            .map(|s| s.split_whitespace().count())
-            .sum();
+        (self.line_count, self.word_count) =
+            count_lines_and_words(&self.text, is_binary(text.as_bytes()));
+    }
+
+    /// put back the body of a response read from a --record session; the counts recorded
+    /// along with it are left alone
+    pub(crate) fn restore_text(&mut self, text: &str) {
+        self.text = String::from(text);
     }
 
     /// free the `text` data, reducing memory usage
@@ -182,8 +777,8 @@ impl FeroxResponse {
     /// Additionally, inspects query parameters, as they're also often indicative of a file
     pub fn is_file(&self) -> bool {
         let has_extension = match self.url.path_segments() {
-            Some(path) => {
-                if let Some(last) = path.last() {
+            Some(mut path) => {
+                if let Some(last) = path.next_back() {
                     last.contains('.') // last segment has some sort of extension, probably
                 } else {
                     false
@@ -210,32 +805,106 @@ impl FeroxResponse {
         response: Response,
         original_url: &str,
         method: &str,
-        output_level: OutputLevel,
+        handles: &Handles,
     ) -> Self {
         let url = response.url().clone();
         let status = response.status();
         let headers = response.headers().clone();
         let content_length = response.content_length().unwrap_or(0);
 
-        // .text() consumes the response, must be called last
-        let text = response
-            .text()
-            .await
-            .with_context(|| "Could not parse body from response")
+        // recorded by make_request when redirects were followed
+        let redirect_chain = response
+            .extensions()
+            .get::<Vec<RedirectHop>>()
+            .cloned()
             .unwrap_or_default();
 
+        // recorded by make_request when --request-id was used
+        let request_id = response
+            .extensions()
+            .get::<RequestId>()
+            .map(|RequestId(id)| id.clone());
+
+        // recorded by logged_request when connecting directly
+        let remote_ip = response
+            .extensions()
+            .get::<RemoteIp>()
+            .map(|RemoteIp(ip)| *ip);
+
+        // reading the body consumes the response, must be called last
+        let (body, body_read_error, search_matches) = read_body(
+            response,
+            &url,
+            handles.config.read_timeout,
+            &handles.features.search,
+        )
+        .await;
+
+        let content_encoding = content_encoding(&headers);
+
+        // --decompress; the body is measured and filtered as the server meant it to be read
+        let decoded = content_encoding
+            .as_deref()
+            .filter(|_| handles.config.decompress)
+            .and_then(|encoding| decompress(&body, encoding));
+
+        let decompressed = decoded.is_some();
+        let body = decoded.unwrap_or(body);
+
+        let binary = is_binary(&body);
+        let text = decode_body(&body, &headers);
+
+        // hashes let --json consumers dedupe and cluster findings without keeping the bodies
+        let sha256 = sha256(&body)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        let simhash = handles
+            .config
+            .fuzzy_hash
+            .then(|| SIM_HASHER.create_signature(preprocess(&text).iter()));
+
+        let page_meta = handles
+            .config
+            .titles
+            .then(|| find_page_meta(&headers, &text))
+            .flatten();
+
+        // --baseline-url used; None otherwise
+        let baseline_diff = handles.features.baseline_words.diff(&text);
+
+        if let Some(traffic_log) = &handles.features.traffic {
+            // --traffic-log used; no-op unless --traffic-log-bodies was used too
+            traffic_log.record_body(&url, method, status, &text);
+        }
+
         // in the event that the content_length was 0, we can try to get the length
-        // of the body we just parsed. At worst, it's still 0; at best we've accounted
+        // of the body we just read. At worst, it's still 0; at best we've accounted
         // for sites that reply without a content-length header and yet still have
         // contents in the body.
         //
+        // the raw body is measured rather than the decoded text, which can be longer or
+        // shorter than what was actually sent (multibyte charsets, replaced binary data)
+        //
         // thanks to twitter use @f3rn0s for pointing out the possibility
-        let content_length = content_length.max(text.len() as u64);
+        //
+        // a decompressed body's size is all that counts, Content-Length is the compressed size
+        let content_length = if decompressed {
+            body.len() as u64
+        } else {
+            content_length.max(body.len() as u64)
+        };
 
-        let line_count = text.lines().count();
-        let word_count = text.lines().map(|s| s.split_whitespace().count()).sum();
+        let (line_count, word_count) = count_lines_and_words(&text, binary);
+
+        let soft_redirect = if status.is_success() {
+            find_soft_redirect(&url, &text)
+        } else {
+            None
+        };
 
-        FeroxResponse {
+        let mut response = FeroxResponse {
             url,
             original_url: original_url.to_string(),
             status,
@@ -245,10 +914,38 @@ impl FeroxResponse {
             headers,
             line_count,
             word_count,
-            output_level,
+            output_level: handles.config.output_level,
+            punycode: handles.config.punycode,
             wildcard: false,
             extension: None,
+            tags: Vec::new(),
+            soft_redirect,
+            redirect_chain,
+            request_id,
+            remote_ip,
+            content_encoding,
+            decompressed,
+            sha256,
+            simhash,
+            page_meta,
+            body_read_error,
+            baseline_diff,
+            search_matches,
+            severity: None,
+        };
+
+        // --baseline used; no-op otherwise
+        handles.features.baseline.resolve(&mut response);
+
+        // --categorize/--category-rule used; no-op otherwise
+        handles.features.categories.categorize(&mut response);
+
+        // --entropy-secrets used; no-op otherwise. binary bodies are full of noise
+        if !binary {
+            handles.features.search.detect_secrets(&mut response);
         }
+
+        response
     }
 
     /// if --collect-extensions is used, examine the response's url and grab the file's extension
@@ -268,7 +965,7 @@ impl FeroxResponse {
         //     (which may be empty).
         //
         // meaning: the two unwraps here are fine, the worst outcome is an empty string
-        let filename = self.url.path_segments().unwrap().last().unwrap();
+        let filename = self.url.path_segments().unwrap().next_back().unwrap();
 
         if !filename.is_empty() {
             // non-empty string, try to get extension
@@ -392,21 +1089,46 @@ impl FeroxResponse {
     }
 
     /// Simple helper to send a `FeroxResponse` over the tx side of an `mpsc::unbounded_channel`
-    pub fn send_report(self, report_sender: CommandSender) -> Result<()> {
-        log::trace!("enter: send_report({:?}", report_sender);
+    ///
+    /// `keep_text` should only be set when the body is needed by the output handler, i.e. when
+    /// exporting full request/response pairs
+    pub fn send_report(self, report_sender: CommandSender, keep_text: bool) -> Result<()> {
+        log::trace!("enter: send_report({:?}, {})", report_sender, keep_text);
 
         // there's no reason to send the response body across the mpsc
         //
-        // the only possible reason is for filtering on the body, but both `send_report`
-        // calls are gated behind checks for `should_filter_response`
+        // the only possible reasons are for filtering on the body, but both `send_report`
+        // calls are gated behind checks for `should_filter_response`, and for exports that
+        // include the body, which the output handler drops once it's finished with it
         let mut me = self;
-        me.drop_text();
+
+        if !keep_text {
+            me.drop_text();
+        }
 
         report_sender.send(Command::Report(Box::new(me)))?;
 
         log::trace!("exit: send_report");
         Ok(())
     }
+
+    /// Simple helper to send a `FeroxResponse` that was removed by the given filter over the tx
+    /// side of an `mpsc::unbounded_channel`; the body is never needed for these
+    pub fn send_filtered_report(self, report_sender: CommandSender, filter: String) -> Result<()> {
+        log::trace!(
+            "enter: send_filtered_report({:?}, {})",
+            report_sender,
+            filter
+        );
+
+        let mut me = self;
+        me.drop_text();
+
+        report_sender.send(Command::ReportFiltered(Box::new(me), filter))?;
+
+        log::trace!("exit: send_filtered_report");
+        Ok(())
+    }
 }
 
 /// Implement FeroxSerialize for FeroxResponse
@@ -420,41 +1142,100 @@ impl FeroxSerialize for FeroxResponse {
         let method = self.method().as_str();
         let wild_status = status_colorizer("WLD");
 
-        let mut url_with_redirect = match (
+        let location = match (
             self.status().is_redirection(),
-            self.headers().get("Location").is_some(),
+            self.headers().get("Location"),
         ) {
-            (true, true) => {
-                // redirect with Location header, show where it goes if possible
-                let loc = self
-                    .headers()
-                    .get("Location")
-                    .unwrap() // known Some() already
-                    .to_str()
-                    .unwrap_or("Unknown")
-                    .to_string();
+            // redirect with Location header, show where it goes if possible
+            (true, Some(location)) => Some(location.to_str().unwrap_or("Unknown").to_string()),
+            // meta refresh/javascript redirect, already made absolute
+            _ => self.soft_redirect.clone(),
+        };
 
+        let mut url_with_redirect = match location {
+            Some(loc) => {
                 let loc = if loc.starts_with('/') {
                     if let Ok(joined) = self.url().join(&loc) {
-                        joined.to_string()
+                        display_url(&joined, self.punycode)
                     } else {
                         loc
                     }
                 } else {
-                    loc
+                    // absolute redirects are shown as given, unless the host needs converting
+                    match Url::parse(&loc) {
+                        Ok(absolute)
+                            if display_url(&absolute, self.punycode) != absolute.as_str() =>
+                        {
+                            display_url(&absolute, self.punycode)
+                        }
+                        _ => loc,
+                    }
                 };
 
                 // prettify the redirect target
                 let loc = style(loc).yellow();
 
-                format!("{} => {loc}", self.url())
+                format!("{} => {loc}", display_url(self.url(), self.punycode))
             }
-            _ => {
+            None => {
                 // no redirect, just use the normal url
-                self.url().to_string()
+                display_url(self.url(), self.punycode)
             }
         };
 
+        if let Some(page_meta) = self
+            .page_meta
+            .as_ref()
+            .filter(|_| !matches!(self.output_level, OutputLevel::Silent))
+        {
+            // --titles; the quickest way to tell what a page is without opening it
+            if let Some(title) = &page_meta.title {
+                let title = if title.chars().count() > TITLE_DISPLAY_LENGTH {
+                    let shortened: String = title.chars().take(TITLE_DISPLAY_LENGTH - 1).collect();
+                    format!("{shortened}…")
+                } else {
+                    title.clone()
+                };
+
+                url_with_redirect = format!(
+                    "{url_with_redirect} {}",
+                    style(format!("\"{title}\"")).bright().white()
+                );
+            }
+
+            if let Some(generator) = &page_meta.generator {
+                url_with_redirect = format!(
+                    "{url_with_redirect} {}",
+                    style(format!("<{generator}>")).magenta()
+                );
+            }
+        }
+
+        if !self.tags.is_empty() && !matches!(self.output_level, OutputLevel::Silent) {
+            // --plugin/--baseline tagged this response; --silent output stays a bare list of urls
+            url_with_redirect = format!(
+                "{url_with_redirect} {}",
+                style(format!("[{}]", self.tags.join(", "))).cyan()
+            );
+        }
+
+        if let Some(severity) = self.severity {
+            if !matches!(self.output_level, OutputLevel::Silent) {
+                // --rules gave this response a severity
+                url_with_redirect = format!("{url_with_redirect} {{{}}}", severity.styled());
+            }
+        }
+
+        if let Some(encoding) = &self.content_encoding {
+            if !matches!(self.output_level, OutputLevel::Silent) {
+                // sizes of compressed bodies aren't comparable to uncompressed ones, say which
+                url_with_redirect = format!(
+                    "{url_with_redirect} {}",
+                    style(format!("({encoding})")).dim()
+                );
+            }
+        }
+
         if self.wildcard && matches!(self.output_level, OutputLevel::Default | OutputLevel::Quiet) {
             // --silent was not used and response is a wildcard, special messages abound when
             // this is the case...
@@ -506,9 +1287,15 @@ impl FeroxSerialize for FeroxResponse {
     ///    "url":"https://localhost.com/images",
     ///    "path":"/images",
     ///    "status":301,
+    ///    "remote_ip":"93.184.216.34", (unless --proxy was used)
     ///    "content_length":179,
     ///    "line_count":10,
     ///    "word_count":16,
+    ///    "sha256":"9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+    ///    "simhash":"01a2b3c4d5e6f708", (--fuzzy-hash only)
+    ///    "baseline_diff":0.42, (--baseline-url only)
+    ///    "title":"301 Moved Permanently", (--titles only, as are generator and charset)
+    ///    "charset":"UTF-8",
     ///    "headers":{
     ///       "x-content-type-options":"nosniff",
     ///       "strict-transport-security":"max-age=31536000; includeSubDomains",
@@ -558,15 +1345,78 @@ impl Serialize for FeroxResponse {
         state.serialize_field("wildcard", &self.wildcard)?;
         state.serialize_field("status", &self.status.as_u16())?;
         state.serialize_field("method", &self.method.as_str())?;
+
+        if let Some(remote_ip) = self.remote_ip {
+            state.serialize_field("remote_ip", &remote_ip)?;
+        }
+
         state.serialize_field("content_length", &self.content_length)?;
         state.serialize_field("line_count", &self.line_count)?;
         state.serialize_field("word_count", &self.word_count)?;
+
+        if !self.sha256.is_empty() {
+            state.serialize_field("sha256", &self.sha256)?;
+        }
+
+        if let Some(simhash) = self.simhash {
+            state.serialize_field("simhash", &format!("{simhash:016x}"))?;
+        }
+
+        if let Some(baseline_diff) = self.baseline_diff {
+            state.serialize_field("baseline_diff", &baseline_diff)?;
+        }
+
+        if let Some(page_meta) = &self.page_meta {
+            if let Some(title) = &page_meta.title {
+                state.serialize_field("title", title)?;
+            }
+
+            if let Some(generator) = &page_meta.generator {
+                state.serialize_field("generator", generator)?;
+            }
+
+            if let Some(charset) = &page_meta.charset {
+                state.serialize_field("charset", charset)?;
+            }
+        }
+
         state.serialize_field("headers", &headers)?;
         state.serialize_field(
             "extension",
             self.extension.as_ref().unwrap_or(&String::new()),
         )?;
 
+        if !self.tags.is_empty() {
+            state.serialize_field("tags", &self.tags)?;
+        }
+
+        if let Some(severity) = &self.severity {
+            state.serialize_field("severity", severity)?;
+        }
+
+        if let Some(soft_redirect) = &self.soft_redirect {
+            state.serialize_field("soft_redirect", soft_redirect)?;
+        }
+
+        if let Some(content_encoding) = &self.content_encoding {
+            state.serialize_field("content_encoding", content_encoding)?;
+            state.serialize_field("decompressed", &self.decompressed)?;
+        }
+
+        if !self.redirect_chain.is_empty() {
+            let chain: Vec<_> = self
+                .redirect_chain
+                .iter()
+                .map(|hop| json!({"url": hop.url, "status": hop.status.as_u16()}))
+                .collect();
+
+            state.serialize_field("redirect_chain", &chain)?;
+        }
+
+        if let Some(request_id) = &self.request_id {
+            state.serialize_field("request_id", request_id)?;
+        }
+
         state.end()
     }
 }
@@ -588,9 +1438,24 @@ impl<'de> Deserialize<'de> for FeroxResponse {
             headers: HeaderMap::new(),
             wildcard: false,
             output_level: Default::default(),
+            punycode: false,
             line_count: 0,
             word_count: 0,
             extension: None,
+            tags: Vec::new(),
+            soft_redirect: None,
+            redirect_chain: Vec::new(),
+            request_id: None,
+            remote_ip: None,
+            content_encoding: None,
+            decompressed: false,
+            sha256: String::new(),
+            simhash: None,
+            page_meta: None,
+            body_read_error: None,
+            baseline_diff: None,
+            search_matches: Vec::new(),
+            severity: None,
         };
 
         let map: HashMap<String, Value> = HashMap::deserialize(deserializer)?;
@@ -623,6 +1488,11 @@ impl<'de> Deserialize<'de> for FeroxResponse {
                         response.method = Method::from_bytes(method.as_bytes()).unwrap_or_default();
                     }
                 }
+                "remote_ip" => {
+                    if let Some(remote_ip) = value.as_str() {
+                        response.remote_ip = remote_ip.parse().ok();
+                    }
+                }
                 "content_length" => {
                     if let Some(num) = value.as_u64() {
                         response.content_length = num;
@@ -664,6 +1534,76 @@ impl<'de> Deserialize<'de> for FeroxResponse {
                         response.extension = Some(result.to_string());
                     }
                 }
+                "tags" => {
+                    if let Some(tags) = value.as_array() {
+                        response.tags = tags
+                            .iter()
+                            .filter_map(|tag| tag.as_str().map(String::from))
+                            .collect();
+                    }
+                }
+                "severity" => {
+                    response.severity = serde_json::from_value(value.clone()).ok();
+                }
+                "soft_redirect" => {
+                    if let Some(result) = value.as_str() {
+                        response.soft_redirect = Some(result.to_string());
+                    }
+                }
+                "content_encoding" => {
+                    if let Some(result) = value.as_str() {
+                        response.content_encoding = Some(result.to_string());
+                    }
+                }
+                "decompressed" => {
+                    if let Some(result) = value.as_bool() {
+                        response.decompressed = result;
+                    }
+                }
+                "sha256" => {
+                    if let Some(result) = value.as_str() {
+                        response.sha256 = result.to_string();
+                    }
+                }
+                "simhash" => {
+                    if let Some(result) = value.as_str() {
+                        response.simhash = u64::from_str_radix(result, 16).ok();
+                    }
+                }
+                "baseline_diff" => {
+                    response.baseline_diff = value.as_f64();
+                }
+                "title" | "generator" | "charset" => {
+                    if let Some(result) = value.as_str() {
+                        let page_meta = response.page_meta.get_or_insert_with(Default::default);
+                        let result = Some(result.to_string());
+
+                        match key.as_str() {
+                            "title" => page_meta.title = result,
+                            "generator" => page_meta.generator = result,
+                            _ => page_meta.charset = result,
+                        }
+                    }
+                }
+                "request_id" => {
+                    response.request_id = value.as_str().map(String::from);
+                }
+                "redirect_chain" => {
+                    if let Some(hops) = value.as_array() {
+                        response.redirect_chain = hops
+                            .iter()
+                            .filter_map(|hop| {
+                                Some(RedirectHop {
+                                    url: hop["url"].as_str()?.to_string(),
+                                    status: StatusCode::from_u16(
+                                        hop["status"].as_u64()?.try_into().ok()?,
+                                    )
+                                    .ok()?,
+                                })
+                            })
+                            .collect();
+                    }
+                }
                 _ => {}
             }
         }
@@ -678,6 +1618,112 @@ mod tests {
     use crate::config::Configuration;
     use std::default::Default;
 
+    #[test]
+    /// meta refresh tags and trivial javascript redirects should be found and made absolute,
+    /// anything else should be ignored
+    fn find_soft_redirect_handles_meta_refresh_and_javascript() {
+        let url = Url::parse("http://localhost/portal/").unwrap();
+
+        let meta = r#"<meta content="0;URL='login.php'" http-equiv="Refresh">"#;
+        assert_eq!(
+            find_soft_redirect(&url, meta).unwrap(),
+            "http://localhost/portal/login.php"
+        );
+
+        let js = r#"<script>window.location.href = "/app/";</script>"#;
+        assert_eq!(
+            find_soft_redirect(&url, js).unwrap(),
+            "http://localhost/app/"
+        );
+
+        let replace = "<script>location.replace('https://localhost/sso')</script>";
+        assert_eq!(
+            find_soft_redirect(&url, replace).unwrap(),
+            "https://localhost/sso"
+        );
+
+        let comparison = r#"if (location.href == "/portal/") { }"#;
+        assert!(find_soft_redirect(&url, comparison).is_none());
+
+        let refresh = r#"<meta http-equiv="refresh" content="30">"#;
+        assert!(find_soft_redirect(&url, refresh).is_none());
+
+        let itself = r#"<meta http-equiv="refresh" content="5; url=/portal/">"#;
+        assert!(find_soft_redirect(&url, itself).is_none());
+
+        let large = format!("{js}{}", " ".repeat(SOFT_REDIRECT_MAX_SIZE));
+        assert!(find_soft_redirect(&url, &large).is_none());
+    }
+
+    #[test]
+    /// titles are unescaped and tidied, generators and charsets are found in any attribute
+    /// order, and the Content-Type charset wins over the declared one
+    fn find_page_meta_reads_title_generator_and_charset() {
+        let body = r#"<!DOCTYPE html><html><head>
+            <meta content="WordPress 6.2" name='Generator'>
+            <meta http-equiv="Content-Type" content="text/html; charset=ISO-8859-1">
+            <TITLE>
+                Login &amp; Dashboard &#8211; Acme
+            </TITLE></head></html>"#;
+
+        let mut headers = HeaderMap::new();
+        let meta = find_page_meta(&headers, body).unwrap();
+
+        assert_eq!(meta.title.unwrap(), "Login & Dashboard \u{2013} Acme");
+        assert_eq!(meta.generator.unwrap(), "WordPress 6.2");
+        assert_eq!(meta.charset.unwrap(), "ISO-8859-1");
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+        let meta = find_page_meta(&headers, r#"<meta charset="windows-1252">"#).unwrap();
+        assert_eq!(meta.charset.unwrap(), "utf-8");
+        assert!(meta.title.is_none() && meta.generator.is_none());
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        assert!(find_page_meta(&headers, "<title>nope</title>").is_none());
+    }
+
+    #[test]
+    /// known entities are replaced, anything else is left as it was
+    fn unescape_html_replaces_entities() {
+        assert_eq!(unescape_html("a &lt;b&gt; &#x41;&#66;"), "a <b> AB");
+        assert_eq!(unescape_html("R&D; &bogus; & &;"), "R&D; &bogus; & &;");
+    }
+
+    #[test]
+    /// partial responses should take on the full size from Content-Range, anything else should
+    /// be left alone
+    fn resolve_range_probe_uses_content_range_size() {
+        let mut response = FeroxResponse {
+            status: StatusCode::PARTIAL_CONTENT,
+            content_length: 1,
+            ..Default::default()
+        };
+        response
+            .headers
+            .insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-0/4134"));
+
+        assert_eq!(response.resolve_range_probe(), Some(4134));
+        assert_eq!(response.status(), &StatusCode::OK);
+        assert_eq!(response.content_length(), 4134);
+
+        // already resolved
+        assert_eq!(response.resolve_range_probe(), None);
+
+        let mut unknown = FeroxResponse {
+            status: StatusCode::PARTIAL_CONTENT,
+            ..Default::default()
+        };
+        unknown
+            .headers
+            .insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-0/*"));
+
+        assert_eq!(unknown.resolve_range_probe(), None);
+        assert_eq!(unknown.status(), &StatusCode::PARTIAL_CONTENT);
+    }
+
     #[test]
     /// call reached_max_depth with max depth of zero, which is infinite recursion, expect false
     fn reached_max_depth_returns_early_on_zero() {
@@ -808,4 +1854,243 @@ mod tests {
 
         assert_eq!(response.extension, None);
     }
+
+    #[test]
+    /// tags should be deduplicated, shown alongside the url, and round-trip through json, while
+    /// --silent output and untagged json are left alone
+    fn tags_are_displayed_and_serialized() {
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("tags"));
+
+        response.add_tag(String::from("admin"));
+        response.add_tag(String::from("login"));
+        response.add_tag(String::from("admin"));
+        assert_eq!(response.tags(), ["admin", "login"]);
+
+        assert!(console::strip_ansi_codes(&response.as_str()).contains("[admin, login]"));
+
+        let json = response.as_json().unwrap();
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.tags(), ["admin", "login"]);
+
+        response.output_level = OutputLevel::Silent;
+        assert!(!response.as_str().contains("admin"));
+    }
+
+    #[test]
+    /// only the highest severity is kept, and it round-trips through json
+    fn severity_is_raised_and_serialized() {
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("severity"));
+
+        response.raise_severity(Severity::Medium);
+        response.raise_severity(Severity::Low);
+        assert_eq!(response.severity(), Some(Severity::Medium));
+
+        response.raise_severity(Severity::Critical);
+        assert!(console::strip_ansi_codes(&response.as_str()).contains("{critical}"));
+
+        let json = response.as_json().unwrap();
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.severity(), Some(Severity::Critical));
+    }
+
+    #[test]
+    /// redirect chains should round-trip through json, and be left out when nothing was followed
+    fn redirect_chain_is_serialized() {
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("redirect_chain"));
+
+        response.redirect_chain = vec![RedirectHop {
+            url: String::from("http://localhost/old"),
+            status: StatusCode::MOVED_PERMANENTLY,
+        }];
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""redirect_chain":[{"status":301,"url":"http://localhost/old"}]"#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.redirect_chain(), response.redirect_chain());
+    }
+
+    #[test]
+    /// request ids should round-trip through json, and be left out when --request-id wasn't used
+    fn request_id_is_serialized() {
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("request_id"));
+
+        response.request_id = Some(String::from("4f1e0c8a-5b7d-4e8f-9a43-2d6c1b0e7f35-7"));
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""request_id":"4f1e0c8a-5b7d-4e8f-9a43-2d6c1b0e7f35-7""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.request_id(), response.request_id());
+    }
+
+    #[test]
+    /// body hashes should round-trip through json, and be left out when they weren't computed
+    fn body_hashes_are_serialized() {
+        let mut response = FeroxResponse::default();
+        let json = response.as_json().unwrap();
+        assert!(!json.contains("sha256") && !json.contains("simhash"));
+
+        response.sha256 =
+            String::from("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08");
+        response.simhash = Some(0x1a2b);
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""simhash":"0000000000001a2b""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.sha256(), response.sha256());
+        assert_eq!(deserialized.simhash(), Some(0x1a2b));
+    }
+
+    #[test]
+    /// the --baseline-url diff ratio should round-trip through json, and be left out when it
+    /// wasn't computed
+    fn baseline_diff_is_serialized() {
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("baseline_diff"));
+
+        response.baseline_diff = Some(0.25);
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""baseline_diff":0.25"#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.baseline_diff(), Some(0.25));
+    }
+
+    #[test]
+    /// the address a response came from should round-trip through json, and be left out when
+    /// it isn't known
+    fn remote_ip_is_serialized() {
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("remote_ip"));
+
+        response.remote_ip = Some("2001:db8::1".parse().unwrap());
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""remote_ip":"2001:db8::1""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.remote_ip(), response.remote_ip());
+    }
+
+    #[test]
+    /// text in any charset is text; nul bytes, control characters, and known binary formats are
+    /// binary
+    fn is_binary_tells_text_from_binary() {
+        assert!(!is_binary(b""));
+        assert!(!is_binary(b"<html>hello\tworld\r\n</html>"));
+        assert!(!is_binary("日本語のページ ünïcödé".as_bytes()));
+        // latin-1 text isn't valid utf-8, but it's still text
+        assert!(!is_binary(b"caf\xe9 cr\xe8me br\xfbl\xe9e"));
+
+        // multibyte character cut in half by the sample size
+        let mut long = "a".repeat(BINARY_SNIFF_SIZE - 1).into_bytes();
+        long.extend_from_slice("é".as_bytes());
+        assert!(!is_binary(&long));
+
+        assert!(is_binary(b"\x1f\x8b\x08\x00compressed"));
+        assert!(is_binary(b"\x89PNG\r\n\x1a\nimage"));
+        assert!(is_binary(b"text with a \x00 in it"));
+        assert!(is_binary(b"\x01\x02\x03\x04\xfe\xff\x05\x06"));
+    }
+
+    #[test]
+    /// bodies should be decoded with the charset from their content-type, defaulting to utf-8
+    fn decode_body_uses_content_type_charset() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(decode_body("ünï".as_bytes(), &headers), "ünï");
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=\"ISO-8859-1\""),
+        );
+        assert_eq!(decode_body(b"caf\xe9", &headers), "café");
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=bogus"),
+        );
+        assert_eq!(decode_body(b"caf\xe9", &headers), "caf\u{fffd}");
+    }
+
+    #[test]
+    /// words are split on unicode whitespace; binary bodies have no lines or words
+    fn count_lines_and_words_handles_unicode_and_binary() {
+        assert_eq!(
+            count_lines_and_words("こんにちは 世界\u{3000}です\nbye", false),
+            (2, 4)
+        );
+        assert_eq!(count_lines_and_words("lots of\nwords\n", true), (0, 0));
+
+        let mut response = FeroxResponse::default();
+        response.set_text("\u{0}\u{1}\nbinary stuff");
+        assert_eq!(response.content_length(), 15);
+        assert_eq!((response.line_count(), response.word_count()), (0, 0));
+    }
+
+    #[test]
+    /// gzip and deflate (zlib wrapped or raw) bodies should decompress; unsupported codings and
+    /// corrupt bodies are left alone
+    fn decompress_handles_gzip_and_deflate() {
+        use flate2::{
+            write::{DeflateEncoder, GzEncoder, ZlibEncoder},
+            Compression,
+        };
+        use std::io::Write;
+
+        let body = b"<html>compressed body</html>";
+
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(body).unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(body).unwrap();
+        let zlib = zlib.finish().unwrap();
+
+        let mut raw = DeflateEncoder::new(Vec::new(), Compression::default());
+        raw.write_all(body).unwrap();
+        let raw = raw.finish().unwrap();
+
+        assert_eq!(decompress(&gzip, "gzip").unwrap(), body);
+        assert_eq!(decompress(&gzip, "x-gzip").unwrap(), body);
+        assert_eq!(decompress(&zlib, "deflate").unwrap(), body);
+        assert_eq!(decompress(&raw, "deflate").unwrap(), body);
+
+        assert!(decompress(&gzip, "br").is_none());
+        assert!(decompress(&gzip, "gzip, br").is_none());
+        assert!(decompress(body, "gzip").is_none());
+    }
+
+    #[test]
+    /// identity isn't a content-coding worth reporting; the rest are lowercased, shown alongside
+    /// the url, and round-trip through json
+    fn content_encoding_is_displayed_and_serialized() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_encoding(&headers), None);
+
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+        assert_eq!(content_encoding(&headers), None);
+
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(" GZIP "));
+        assert_eq!(content_encoding(&headers).as_deref(), Some("gzip"));
+
+        let mut response = FeroxResponse::default();
+        assert!(!response.as_json().unwrap().contains("content_encoding"));
+
+        response.content_encoding = Some(String::from("gzip"));
+        response.decompressed = true;
+        assert!(console::strip_ansi_codes(&response.as_str()).contains("(gzip)"));
+
+        let json = response.as_json().unwrap();
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.content_encoding(), Some("gzip"));
+        assert!(deserialized.decompressed());
+    }
 }
@@ -0,0 +1,128 @@
+//! guard rails for scans of production apps behind a login (--safe-methods-only)
+//!
+//! only GET and HEAD are ever sent; a configuration that asks for any other method (-m, a config
+//! file, or a resumed scan's state) is refused before the scan starts, and `make_request` refuses
+//! anything else that would slip through
+//!
+//! words in the wordlist that look like state-changing actions (logout, delete, ...) are pointed
+//! out before the scan starts; even a GET to /logout ends an authenticated session
+use console::style;
+
+use crate::config::OutputLevel;
+
+/// methods that are allowed with --safe-methods-only
+pub const SAFE_METHODS: [&str; 2] = ["GET", "HEAD"];
+
+/// pieces of words that usually mean the path does something, rather than shows something
+const RISKY_ACTIONS: [&str; 19] = [
+    "logout",
+    "logoff",
+    "signout",
+    "sign-out",
+    "sign_out",
+    "delete",
+    "remove",
+    "destroy",
+    "purge",
+    "wipe",
+    "truncate",
+    "reset",
+    "revoke",
+    "disable",
+    "deactivate",
+    "unsubscribe",
+    "shutdown",
+    "reboot",
+    "restart",
+];
+
+/// number of risky words listed in the warning, the rest are only counted
+const MAX_LISTED: usize = 10;
+
+/// whether the given method is allowed with --safe-methods-only, compared case insensitively
+pub fn is_safe_method(method: &str) -> bool {
+    SAFE_METHODS
+        .iter()
+        .any(|safe| safe.eq_ignore_ascii_case(method))
+}
+
+/// the given methods that aren't allowed with --safe-methods-only
+pub fn unsafe_methods(methods: &[String]) -> Vec<&str> {
+    methods
+        .iter()
+        .map(String::as_str)
+        .filter(|method| !is_safe_method(method))
+        .collect()
+}
+
+/// words that look like state-changing actions, in wordlist order
+pub fn risky_words(words: &[String]) -> Vec<&str> {
+    words
+        .iter()
+        .filter(|word| {
+            let word = word.to_ascii_lowercase();
+            RISKY_ACTIONS.iter().any(|action| word.contains(action))
+        })
+        .map(String::as_str)
+        .collect()
+}
+
+/// print a warning listing the words that look like state-changing actions, if there are any
+pub fn warn_about_words(words: &[String], output_level: OutputLevel) {
+    if !matches!(output_level, OutputLevel::Default | OutputLevel::Quiet) {
+        return;
+    }
+
+    let risky = risky_words(words);
+
+    if risky.is_empty() {
+        return;
+    }
+
+    let mut listed = risky
+        .iter()
+        .take(MAX_LISTED)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if risky.len() > MAX_LISTED {
+        listed.push_str(&format!(", ... ({} more)", risky.len() - MAX_LISTED));
+    }
+
+    eprintln!(
+        "{} {} words look like state-changing actions and will be requested: {}\n    skip them with {} (i.e. --dont-scan-regex '/(logout|delete)')",
+        style("WRN").yellow().bright(),
+        risky.len(),
+        listed,
+        style("--dont-scan-regex").bright().yellow()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// only GET and HEAD are safe, in any case
+    fn unsafe_methods_leaves_out_get_and_head() {
+        let methods: Vec<_> = ["GET", "head", "POST", "delete", "OPTIONS"]
+            .iter()
+            .map(|method| method.to_string())
+            .collect();
+
+        assert_eq!(unsafe_methods(&methods), ["POST", "delete", "OPTIONS"]);
+        assert!(is_safe_method("Get"));
+    }
+
+    #[test]
+    /// words containing an action are flagged, case insensitively, in wordlist order
+    fn risky_words_flags_state_changing_actions() {
+        let words: Vec<_> = ["admin", "Logout", "user_delete", "images", "sign-out", ""]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        assert_eq!(risky_words(&words), ["Logout", "user_delete", "sign-out"]);
+    }
+}
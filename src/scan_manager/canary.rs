@@ -0,0 +1,233 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use reqwest::Url;
+use tokio::time::sleep;
+
+use crate::{url::FeroxUrl, SLEEP_DURATION};
+
+/// number of canary responses in a row that must look wrong before a host is considered to have
+/// banned the scan; keeps a single hiccup from pausing everything
+const CANARY_FAILURES: usize = 2;
+
+/// Tracks a known-good url per host (--canary), used to tell a host that has banned the scan
+/// apart from paths that are simply forbidden
+///
+/// the first response to a host's canary is taken as what the canary should look like; once the
+/// canary stops answering or answers with a different status code, the host is banned and
+/// requests to it wait until the canary looks right again
+#[derive(Debug)]
+pub struct CanaryMonitor {
+    /// path (or full url) of the canary, relative to each target; empty disables the canary
+    path: String,
+
+    /// time between canary requests
+    interval: Duration,
+
+    /// canary state, keyed by host
+    hosts: Mutex<HashMap<String, CanaryHost>>,
+}
+
+/// state of a single host's canary
+#[derive(Debug, Default)]
+struct CanaryHost {
+    /// status code of the first canary response
+    baseline: Option<u16>,
+
+    /// number of canary responses in a row that didn't match the baseline
+    failures: usize,
+
+    /// whether the host is considered to have banned the scan
+    banned: bool,
+}
+
+/// change in a host's ban status, as seen by its canary
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CanaryEvent {
+    /// canary started failing, the host's scans are paused
+    Banned,
+
+    /// canary looks right again, the host's scans are resumed
+    Cleared,
+}
+
+/// implementation of CanaryMonitor
+impl CanaryMonitor {
+    /// create a new CanaryMonitor, given the canary path and the number of seconds between
+    /// canary requests
+    pub fn new(path: &str, interval_secs: u64) -> Self {
+        Self {
+            path: path.to_string(),
+            interval: Duration::from_secs(interval_secs.max(1)),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// whether --canary was used
+    pub fn is_enabled(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    /// time between canary requests
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// canary url for the given target; `None` when --canary wasn't used or the target isn't a
+    /// valid url
+    pub fn canary_url(&self, target: &str) -> Option<Url> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        Url::parse(target).ok()?.join(&self.path).ok()
+    }
+
+    /// start tracking the canary's host; only returns true the first time a host is seen, so
+    /// that each host gets a single canary
+    pub fn watch(&self, canary: &Url) -> bool {
+        let Ok(mut hosts) = self.hosts.lock() else {
+            return false;
+        };
+
+        let key = FeroxUrl::host_key(canary);
+
+        if hosts.contains_key(&key) {
+            return false;
+        }
+
+        hosts.insert(key, CanaryHost::default());
+        true
+    }
+
+    /// record the status code of a canary response, `None` meaning the canary didn't respond
+    /// at all; returns the change in the host's ban status, if any
+    pub fn record(&self, canary: &Url, status: Option<u16>) -> Option<CanaryEvent> {
+        let mut hosts = self.hosts.lock().ok()?;
+        let host = hosts.entry(FeroxUrl::host_key(canary)).or_default();
+
+        let Some(baseline) = host.baseline else {
+            // nothing to compare against until the canary has answered once
+            host.baseline = status;
+            return None;
+        };
+
+        if status == Some(baseline) {
+            host.failures = 0;
+
+            if host.banned {
+                host.banned = false;
+                return Some(CanaryEvent::Cleared);
+            }
+
+            return None;
+        }
+
+        host.failures += 1;
+
+        if !host.banned && host.failures >= CANARY_FAILURES {
+            host.banned = true;
+            return Some(CanaryEvent::Banned);
+        }
+
+        None
+    }
+
+    /// whether the given url's host is considered to have banned the scan
+    pub fn is_banned(&self, url: &Url) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        self.hosts
+            .lock()
+            .map(|hosts| {
+                hosts
+                    .get(&FeroxUrl::host_key(url))
+                    .is_some_and(|host| host.banned)
+            })
+            .unwrap_or_default()
+    }
+
+    /// wait for the given url's host to lift its ban (if any)
+    pub async fn wait_while_banned(&self, url: &Url) {
+        while self.is_banned(url) {
+            sleep(Duration::from_millis(SLEEP_DURATION)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// without --canary, nothing is watched and nothing is ever banned
+    fn canary_monitor_disabled_never_bans() {
+        let monitor = CanaryMonitor::new("", 30);
+        assert!(!monitor.is_enabled());
+        assert_eq!(monitor.canary_url("http://localhost/api"), None);
+        assert!(!monitor.is_banned(&Url::parse("http://localhost").unwrap()));
+    }
+
+    #[test]
+    /// canary paths are resolved against the target; one canary per host
+    fn canary_monitor_resolves_and_watches_hosts_once() {
+        let monitor = CanaryMonitor::new("/", 30);
+
+        let canary = monitor.canary_url("http://localhost/api/v1").unwrap();
+        assert_eq!(canary.as_str(), "http://localhost/");
+
+        assert!(monitor.watch(&canary));
+        assert!(!monitor.watch(&monitor.canary_url("http://localhost/other").unwrap()));
+        assert!(monitor.watch(&monitor.canary_url("http://localhost:8080/").unwrap()));
+
+        let full = CanaryMonitor::new("http://localhost/health", 30);
+        assert_eq!(
+            full.canary_url("http://localhost/api").unwrap().as_str(),
+            "http://localhost/health"
+        );
+    }
+
+    #[test]
+    /// a canary that keeps failing bans its host, only its host, until it looks right again
+    fn canary_monitor_bans_and_clears_host() {
+        let monitor = CanaryMonitor::new("/", 30);
+        let canary = Url::parse("http://localhost/").unwrap();
+        let other = Url::parse("http://127.0.0.1/").unwrap();
+
+        assert_eq!(monitor.record(&canary, Some(200)), None);
+        assert_eq!(monitor.record(&canary, Some(200)), None);
+
+        // a single failure isn't enough
+        assert_eq!(monitor.record(&canary, Some(403)), None);
+        assert!(!monitor.is_banned(&canary));
+        assert_eq!(monitor.record(&canary, Some(200)), None);
+
+        assert_eq!(monitor.record(&canary, Some(403)), None);
+        assert_eq!(monitor.record(&canary, None), Some(CanaryEvent::Banned));
+        assert!(monitor.is_banned(&Url::parse("http://localhost/admin").unwrap()));
+        assert!(!monitor.is_banned(&other));
+
+        assert_eq!(monitor.record(&canary, Some(403)), None);
+        assert_eq!(
+            monitor.record(&canary, Some(200)),
+            Some(CanaryEvent::Cleared)
+        );
+        assert!(!monitor.is_banned(&canary));
+    }
+
+    #[test]
+    /// a canary that has never answered can't ban its host
+    fn canary_monitor_needs_a_baseline() {
+        let monitor = CanaryMonitor::new("/", 30);
+        let canary = Url::parse("http://localhost/").unwrap();
+
+        for _ in 0..CANARY_FAILURES * 2 {
+            assert_eq!(monitor.record(&canary, None), None);
+        }
+        assert!(!monitor.is_banned(&canary));
+
+        assert_eq!(monitor.record(&canary, Some(200)), None);
+        assert_eq!(monitor.record(&canary, Some(200)), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use reqwest::Client;
+
+use crate::{atomic_load, atomic_store, config::Configuration};
+
+/// number of proxy errors in a row after which the proxy in use is given up on
+const FAILOVER_THRESHOLD: usize = 3;
+
+/// Tracks which proxy requests are sent through when --proxy-failover and/or
+/// --proxy-failover-direct are used
+///
+/// requests start out going through --proxy; once it has failed too many requests in a row,
+/// requests move on to the next proxy in the list, ending with a direct connection when that's
+/// allowed. as long as there's somewhere left to go, a request that fails because of its proxy
+/// is retried instead of being counted as an error
+#[derive(Debug)]
+pub struct ProxyFailover {
+    /// every proxy in the order they're tried, along with the client that connects through it;
+    /// an empty name stands for a direct connection
+    chain: Vec<(String, Client)>,
+
+    /// index into `chain` of the proxy in use
+    current: AtomicUsize,
+
+    /// number of proxy errors in a row seen on the proxy in use
+    failures: AtomicUsize,
+}
+
+/// implementation of ProxyFailover
+impl ProxyFailover {
+    /// create a new ProxyFailover from the proxy settings in the given configuration; proxies
+    /// that a client can't be built for are left out of the chain
+    pub fn new(config: &Configuration) -> Self {
+        let mut chain = vec![(config.proxy.clone(), config.client.clone())];
+
+        if !config.proxy.is_empty() {
+            for proxy in &config.proxy_failover {
+                match config.client_with_proxy(Some(proxy)) {
+                    Ok(client) => chain.push((proxy.clone(), client)),
+                    Err(e) => log::warn!("Could not use {} as a failover proxy: {}", proxy, e),
+                }
+            }
+
+            if config.proxy_failover_direct {
+                match config.client_with_proxy(None) {
+                    Ok(client) => chain.push((String::new(), client)),
+                    Err(e) => log::warn!("Could not build a direct failover client: {}", e),
+                }
+            }
+        }
+
+        Self {
+            chain,
+            current: AtomicUsize::new(0),
+            failures: AtomicUsize::new(0),
+        }
+    }
+
+    /// whether there's anything to fail over to
+    pub fn is_enabled(&self) -> bool {
+        self.chain.len() > 1
+    }
+
+    /// the client requests should be sent with, along with its place in the chain; the latter
+    /// is handed back to `record_success`/`record_failure` once the request is done
+    pub fn client(&self) -> (usize, &Client) {
+        let index = atomic_load!(self.current);
+        (index, &self.chain[index].1)
+    }
+
+    /// the proxy in use, if any
+    pub fn proxy(&self) -> Option<&str> {
+        let (name, _) = &self.chain[atomic_load!(self.current)];
+        (!name.is_empty()).then_some(name.as_str())
+    }
+
+    /// whether the client at the given place in the chain connects to targets directly
+    pub fn is_direct(&self, index: usize) -> bool {
+        self.chain
+            .get(index)
+            .is_some_and(|(name, _)| name.is_empty())
+    }
+
+    /// human-readable name of the proxy at the given place in the chain
+    pub fn name(&self, index: usize) -> &str {
+        match self.chain.get(index) {
+            Some((name, _)) if !name.is_empty() => name,
+            _ => "a direct connection",
+        }
+    }
+
+    /// note that a request sent through the proxy at the given place in the chain got a response
+    pub fn record_success(&self, index: usize) {
+        if index == atomic_load!(self.current) {
+            atomic_store!(self.failures, 0);
+        }
+    }
+
+    /// note that the proxy at the given place in the chain failed a request; returns whether the
+    /// request should be retried, along with the place the chain moved on to if this failure
+    /// was the one that caused it to; failures on the last proxy in the chain aren't retried
+    pub fn record_failure(&self, index: usize) -> (bool, Option<usize>) {
+        if !self.is_enabled() {
+            return (false, None);
+        }
+
+        let current = atomic_load!(self.current);
+
+        if index < current {
+            // already given up on, the request just needs to go through the one in use
+            return (true, None);
+        }
+
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if current + 1 >= self.chain.len() {
+            // nowhere left to go
+            return (false, None);
+        }
+
+        if failures < FAILOVER_THRESHOLD {
+            return (true, None);
+        }
+
+        match self.current.compare_exchange(
+            current,
+            current + 1,
+            Ordering::AcqRel,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => {
+                atomic_store!(self.failures, 0);
+                (true, Some(current + 1))
+            }
+            // someone else moved the chain along in the meantime
+            Err(_) => (true, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a ProxyFailover with the given proxies in its chain
+    fn failover(names: &[&str]) -> ProxyFailover {
+        ProxyFailover {
+            chain: names
+                .iter()
+                .map(|name| (name.to_string(), Client::new()))
+                .collect(),
+            current: AtomicUsize::new(0),
+            failures: AtomicUsize::new(0),
+        }
+    }
+
+    #[test]
+    /// without anything to fail over to, failures are never retried
+    fn single_proxy_never_fails_over() {
+        let failover = failover(&["http://127.0.0.1:8080"]);
+
+        for _ in 0..FAILOVER_THRESHOLD * 2 {
+            assert_eq!(failover.record_failure(0), (false, None));
+        }
+
+        assert_eq!(failover.client().0, 0);
+    }
+
+    #[test]
+    /// enough failures in a row move the chain along, and every request that failed on the way
+    /// is retried
+    fn failures_in_a_row_move_to_the_next_proxy() {
+        let failover = failover(&["http://127.0.0.1:8080", ""]);
+
+        for _ in 1..FAILOVER_THRESHOLD {
+            assert_eq!(failover.record_failure(0), (true, None));
+        }
+
+        assert_eq!(failover.record_failure(0), (true, Some(1)));
+        assert_eq!(failover.client().0, 1);
+        assert_eq!(failover.name(1), "a direct connection");
+
+        // in flight while the switch happened
+        assert_eq!(failover.record_failure(0), (true, None));
+
+        // nowhere left to go
+        for _ in 0..FAILOVER_THRESHOLD * 2 {
+            assert_eq!(failover.record_failure(1), (false, None));
+        }
+    }
+
+    #[test]
+    /// a response in between failures starts the count over
+    fn success_resets_the_count() {
+        let failover = failover(&["http://127.0.0.1:8080", "http://127.0.0.1:8081"]);
+
+        for _ in 1..FAILOVER_THRESHOLD {
+            failover.record_failure(0);
+        }
+
+        failover.record_success(0);
+
+        assert_eq!(failover.record_failure(0), (true, None));
+        assert_eq!(failover.client().0, 0);
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use crate::{atomic_increment, atomic_load, parser::LATENCYSPEC_REGEX};
+
+/// number of most recent response times kept per scan
+const LATENCY_WINDOW: usize = 100;
+
+/// number of response times needed before a baseline is taken from the scan's own traffic, or
+/// before the p95 is trusted
+const MIN_LATENCY_SAMPLES: usize = 20;
+
+/// Tracks response times for a single scan, used by --tune-on-latency to notice a target
+/// slowing down before it starts erroring
+///
+/// the baseline comes from the heuristics phase's probes when there were any, otherwise from the
+/// scan's first responses
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    /// typical response time of the target before the scan got going
+    baseline: Mutex<Option<Duration>>,
+
+    /// most recent response times, oldest first
+    recent: Mutex<VecDeque<Duration>>,
+
+    /// number of responses that took longer than the allowed multiple of the baseline
+    slow: AtomicUsize,
+}
+
+/// convert a latency spec (i.e. 2x-baseline, 1.5x) into a multiple of the baseline; `None` when
+/// the spec is malformed or wouldn't allow responses to be any slower than the baseline
+pub fn latency_multiplier(latency_spec: &str) -> Option<f64> {
+    let captures = LATENCYSPEC_REGEX.captures(latency_spec)?;
+
+    let multiplier: f64 = captures.name("n")?.as_str().parse().ok()?;
+
+    (multiplier > 1.0).then_some(multiplier)
+}
+
+/// median of the given response times
+fn median(samples: &[Duration]) -> Option<Duration> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    sorted.get(sorted.len() / 2).copied()
+}
+
+/// implementation of LatencyTracker
+impl LatencyTracker {
+    /// set the baseline to the median of the given response times; does nothing if empty
+    pub fn set_baseline(&self, samples: &[Duration]) {
+        if let (Some(baseline), Ok(mut guard)) = (median(samples), self.baseline.lock()) {
+            *guard = Some(baseline);
+        }
+    }
+
+    /// getter for the baseline
+    pub fn baseline(&self) -> Option<Duration> {
+        self.baseline.lock().map(|guard| *guard).unwrap_or_default()
+    }
+
+    /// record a response time; `multiplier` is how many times the baseline a response may take
+    /// before it's counted as slow
+    pub fn record(&self, elapsed: Duration, multiplier: f64) {
+        let Ok(mut recent) = self.recent.lock() else {
+            return;
+        };
+
+        if recent.len() == LATENCY_WINDOW {
+            recent.pop_front();
+        }
+        recent.push_back(elapsed);
+
+        let Ok(mut baseline) = self.baseline.lock() else {
+            return;
+        };
+
+        match *baseline {
+            Some(typical) if elapsed > typical.mul_f64(multiplier) => {
+                atomic_increment!(self.slow);
+            }
+            None if recent.len() >= MIN_LATENCY_SAMPLES => {
+                // no heuristics probes to go on, the scan's first responses will have to do
+                *baseline = median(recent.make_contiguous());
+            }
+            _ => {}
+        }
+    }
+
+    /// 95th percentile of the most recent response times, None until enough have been seen
+    pub fn p95(&self) -> Option<Duration> {
+        let recent = self.recent.lock().ok()?;
+
+        if recent.len() < MIN_LATENCY_SAMPLES {
+            return None;
+        }
+
+        let mut sorted = recent.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let index = (sorted.len() * 95).div_ceil(100) - 1;
+        sorted.get(index).copied()
+    }
+
+    /// whether the recent p95 has climbed past `multiplier` times the baseline
+    pub fn is_overloaded(&self, multiplier: f64) -> bool {
+        match (self.baseline(), self.p95()) {
+            (Some(baseline), Some(p95)) => p95 > baseline.mul_f64(multiplier),
+            _ => false,
+        }
+    }
+
+    /// forget the most recent response times, keeping the baseline
+    pub fn clear_recent(&self) {
+        if let Ok(mut recent) = self.recent.lock() {
+            recent.clear();
+        }
+    }
+
+    /// number of responses that took longer than the allowed multiple of the baseline
+    pub fn slow_responses(&self) -> usize {
+        atomic_load!(self.slow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// a baseline from heuristics should be used as-is; p95 shouldn't be reported until enough
+    /// samples are in
+    fn latency_tracker_detects_rising_p95() {
+        let tracker = LatencyTracker::default();
+        tracker.set_baseline(&[
+            Duration::from_millis(90),
+            Duration::from_millis(100),
+            Duration::from_millis(500),
+        ]);
+        assert_eq!(tracker.baseline(), Some(Duration::from_millis(100)));
+
+        for _ in 0..MIN_LATENCY_SAMPLES - 1 {
+            tracker.record(Duration::from_millis(110), 2.0);
+        }
+        assert_eq!(tracker.p95(), None);
+        assert!(!tracker.is_overloaded(2.0));
+
+        tracker.record(Duration::from_millis(110), 2.0);
+        assert_eq!(tracker.p95(), Some(Duration::from_millis(110)));
+        assert!(!tracker.is_overloaded(2.0));
+        assert_eq!(tracker.slow_responses(), 0);
+
+        // 5 slow responses out of 25 pushes the p95 over 2x the baseline
+        for _ in 0..5 {
+            tracker.record(Duration::from_millis(300), 2.0);
+        }
+        assert_eq!(tracker.p95(), Some(Duration::from_millis(300)));
+        assert!(tracker.is_overloaded(2.0));
+        assert!(!tracker.is_overloaded(4.0));
+        assert_eq!(tracker.slow_responses(), 5);
+    }
+
+    #[test]
+    /// latency specs should parse into multipliers greater than 1
+    fn latency_multiplier_parses_specs() {
+        assert_eq!(latency_multiplier("2x-baseline"), Some(2.0));
+        assert_eq!(latency_multiplier("1.5X"), Some(1.5));
+        assert_eq!(latency_multiplier("3x"), Some(3.0));
+        assert_eq!(latency_multiplier("1x-baseline"), None);
+        assert_eq!(latency_multiplier("x-baseline"), None);
+        assert_eq!(latency_multiplier(""), None);
+    }
+
+    #[test]
+    /// without a baseline from heuristics, the scan's first responses should become the baseline
+    fn latency_tracker_takes_baseline_from_first_responses() {
+        let tracker = LatencyTracker::default();
+        tracker.set_baseline(&[]);
+        assert_eq!(tracker.baseline(), None);
+
+        for _ in 0..MIN_LATENCY_SAMPLES {
+            tracker.record(Duration::from_millis(50), 2.0);
+        }
+        assert_eq!(tracker.baseline(), Some(Duration::from_millis(50)));
+
+        // older samples fall out of the window
+        for _ in 0..LATENCY_WINDOW {
+            tracker.record(Duration::from_millis(150), 2.0);
+        }
+        assert_eq!(tracker.p95(), Some(Duration::from_millis(150)));
+        assert_eq!(tracker.slow_responses(), LATENCY_WINDOW);
+        assert_eq!(tracker.recent.lock().unwrap().len(), LATENCY_WINDOW);
+    }
+}
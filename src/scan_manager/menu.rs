@@ -19,6 +19,20 @@ pub enum MenuCmd {
 
     /// user wants to remove one or more active filters
     RemoveFilter(Vec<usize>),
+
+    /// user wants to change the requests per second allowed, 0 removes the limit; applies to
+    /// the given scans only, or to every scan when no scans are given
+    RateLimit(usize, Vec<usize>),
+
+    /// user wants to turn auto-tune on/off, None toggles the current state; applies to the
+    /// given scans only, or to every scan when no scans are given
+    AutoTune(Option<bool>, Vec<usize>),
+
+    /// user wants to move one or more queued scans to the front of the queue (true) or the back
+    MoveQueued(Vec<usize>, bool),
+
+    /// user wants to drop one or more queued scans before they start
+    DropQueued(Vec<usize>),
 }
 
 /// Data container for a command result to be used internally by the ferox_scanner
@@ -86,29 +100,57 @@ impl Menu {
         );
 
         let valid_filters = format!(
-            "    FILTER_TYPEs: {}, {}, {}, {}, {}, {}\n",
+            "    FILTER_TYPEs: {}, {}, {}, {}, {}, {}, {}\n",
             style("status").yellow(),
             style("lines").yellow(),
             style("size").yellow(),
             style("words").yellow(),
             style("regex").yellow(),
-            style("similarity").yellow()
+            style("similarity").yellow(),
+            style("category").yellow()
         );
 
         let rm_filter_cmd = format!(
-            "  {}[{}] FILTER_ID[-FILTER_ID[,...]] (ex: {} 1-4,8,9-13 or {} 3)",
+            "  {}[{}] FILTER_ID[-FILTER_ID[,...]] (ex: {} 1-4,8,9-13 or {} 3)\n",
             style("r").red(),
             style("m-filter").red(),
             style("rm-filter").red(),
             style("r").red(),
         );
 
+        let limit_cmd = format!(
+            "  {}[{}] REQS_PER_SEC [SCAN_ID[,...]] (ex: {} 100, {} 50 3 or l 0 to remove the limit)\n",
+            style("l").yellow(),
+            style("imit").yellow(),
+            style("limit").yellow(),
+            style("l").yellow(),
+        );
+
+        let tune_cmd = format!(
+            "  {}[{}] [on|off] [SCAN_ID[,...]] (ex: {} off, {} off 3 or t to toggle auto-tune)\n",
+            style("t").yellow(),
+            style("une").yellow(),
+            style("tune").yellow(),
+            style("t").yellow(),
+        );
+
+        let queue_cmd = format!(
+            "  {}[{}] first|last|drop QUEUE_ID[-QUEUE_ID[,...]] (ex: {} first 3 or {} drop 1-4)",
+            style("q").yellow(),
+            style("ueue").yellow(),
+            style("queue").yellow(),
+            style("q").yellow(),
+        );
+
         let mut commands = format!("{}:\n", style("Commands").bright().blue());
         commands.push_str(&add_cmd);
         commands.push_str(&canx_cmd);
         commands.push_str(&new_filter_cmd);
         commands.push_str(&valid_filters);
         commands.push_str(&rm_filter_cmd);
+        commands.push_str(&limit_cmd);
+        commands.push_str(&tune_cmd);
+        commands.push_str(&queue_cmd);
 
         let longest = measure_text_width(&canx_cmd).max(measure_text_width(&name));
 
@@ -284,6 +326,64 @@ impl Menu {
 
                 Some(MenuCmd::RemoveFilter(indices))
             }
+            'l' => {
+                // rate limit command
+                let mut line = line.split_whitespace();
+                line.next(); // 'l' or 'limit'
+
+                let value = line.next()?;
+
+                // anything after the limit is a list of scans to limit, none means all of them
+                let scans = self.split_to_nums(&line.collect::<Vec<_>>().join(","));
+
+                match value.parse::<usize>() {
+                    Ok(limit) => Some(MenuCmd::RateLimit(limit, scans)),
+                    Err(e) => {
+                        self.println(&format!("Found non-numeric input: {e}: {value:?}"));
+                        None
+                    }
+                }
+            }
+            't' => {
+                // auto-tune command; no on/off means toggle, and anything after that is a list
+                // of scans to change, none means all of them
+                let mut line = line.split_whitespace().peekable();
+                line.next(); // 't' or 'tune'
+
+                let enabled = match line.peek().map(|arg| arg.to_ascii_lowercase()).as_deref() {
+                    Some("on") => Some(true),
+                    Some("off") => Some(false),
+                    Some(arg) if !arg.starts_with(|c: char| c.is_ascii_digit()) => return None,
+                    _ => None,
+                };
+
+                if enabled.is_some() {
+                    line.next();
+                }
+
+                let scans = self.split_to_nums(&line.collect::<Vec<_>>().join(","));
+
+                Some(MenuCmd::AutoTune(enabled, scans))
+            }
+            'q' => {
+                // queue command; an action followed by the positions of the queued scans
+                let mut line = line.split_whitespace();
+                line.next(); // 'q' or 'queue'
+
+                let action = line.next()?.to_ascii_lowercase();
+                let indices = self.split_to_nums(&line.collect::<Vec<_>>().join(","));
+
+                if indices.is_empty() {
+                    return None;
+                }
+
+                match action.as_str() {
+                    "first" => Some(MenuCmd::MoveQueued(indices, true)),
+                    "last" => Some(MenuCmd::MoveQueued(indices, false)),
+                    "drop" => Some(MenuCmd::DropQueued(indices)),
+                    _ => None,
+                }
+            }
             _ => {
                 // invalid input
                 None
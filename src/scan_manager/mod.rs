@@ -5,14 +5,30 @@ mod menu;
 mod utils;
 mod order;
 mod state;
+mod outage;
+mod failover;
+mod latency;
+mod canary;
+mod queue;
+mod phase;
+mod seen;
+mod tuning;
 #[cfg(test)]
 mod tests;
 
-pub(self) use menu::Menu;
+pub use canary::{CanaryEvent, CanaryMonitor};
+pub use failover::ProxyFailover;
+pub use latency::{latency_multiplier, LatencyTracker};
+use menu::Menu;
 pub use menu::{MenuCmd, MenuCmdResult};
 pub use order::ScanOrder;
+pub use outage::OutageMonitor;
+pub use phase::ScanPhase;
+pub use queue::{QueuedScan, RecursionStrategy, ScanQueue};
 pub use response_container::FeroxResponses;
-pub use scan::{FeroxScan, ScanStatus, ScanType};
+pub use scan::{FeroxScan, ScanOverrides, ScanStatus, ScanType, TuningState};
 pub use scan_container::{FeroxScans, PAUSE_SCAN};
+pub use seen::SeenUrls;
 pub use state::FeroxState;
-pub use utils::{resume_scan, start_max_time_thread};
+pub use tuning::HostTuning;
+pub use utils::{resume_scan, start_max_time_thread, time_spec_in_secs};
@@ -0,0 +1,166 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::time::sleep;
+
+use crate::{atomic_load, atomic_store, SLEEP_DURATION};
+
+/// Tracks whether the target(s) appear to be unreachable, i.e. every request across all scans
+/// has failed to connect for longer than the configured threshold (--auto-resume)
+///
+/// while an outage is in progress, requests wait instead of failing, and a single probe checks
+/// for connectivity periodically
+#[derive(Debug)]
+pub struct OutageMonitor {
+    /// how long requests must fail without a single success before scans are paused, zero
+    /// disables outage detection entirely
+    threshold: Duration,
+
+    /// time at which the last response (of any kind) was received
+    last_success: Mutex<Instant>,
+
+    /// whether an outage is in progress
+    down: AtomicBool,
+
+    /// whether something is already probing for connectivity during the current outage
+    probing: AtomicBool,
+}
+
+/// implementation of OutageMonitor
+impl OutageMonitor {
+    /// create a new OutageMonitor, given the number of seconds of consecutive connection failures
+    /// that constitute an outage
+    pub fn new(threshold_secs: u64) -> Self {
+        Self {
+            threshold: Duration::from_secs(threshold_secs),
+            last_success: Mutex::new(Instant::now()),
+            down: AtomicBool::new(false),
+            probing: AtomicBool::new(false),
+        }
+    }
+
+    /// whether --auto-resume was used
+    pub fn is_enabled(&self) -> bool {
+        !self.threshold.is_zero()
+    }
+
+    /// whether an outage is in progress
+    pub fn is_down(&self) -> bool {
+        atomic_load!(self.down, Ordering::Acquire)
+    }
+
+    /// note that a response was received
+    pub fn record_success(&self) {
+        if let Ok(mut guard) = self.last_success.lock() {
+            *guard = Instant::now();
+        }
+    }
+
+    /// note that a request failed to connect; returns true when the failure is part of an
+    /// outage, meaning the request should be retried once connectivity is restored
+    pub fn record_failure(&self) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        if self.is_down() {
+            return true;
+        }
+
+        let elapsed = self
+            .last_success
+            .lock()
+            .map(|guard| guard.elapsed())
+            .unwrap_or_default();
+
+        if elapsed >= self.threshold {
+            atomic_store!(self.down, true, Ordering::Release);
+            return true;
+        }
+
+        false
+    }
+
+    /// claim the responsibility of probing for connectivity; only the first caller during an
+    /// outage gets true
+    pub fn claim_probe(&self) -> bool {
+        self.probing
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// end the current outage
+    pub fn mark_up(&self) {
+        self.record_success();
+        atomic_store!(self.probing, false, Ordering::Release);
+        atomic_store!(self.down, false, Ordering::Release);
+    }
+
+    /// wait for the current outage (if any) to end
+    pub async fn wait_until_up(&self) {
+        while self.is_down() {
+            sleep(Duration::from_millis(SLEEP_DURATION)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// a threshold of 0 should never report an outage
+    fn outage_monitor_disabled_never_goes_down() {
+        let monitor = OutageMonitor::new(0);
+        assert!(!monitor.is_enabled());
+        assert!(!monitor.record_failure());
+        assert!(!monitor.is_down());
+    }
+
+    #[test]
+    /// failures should only count as an outage once the threshold has passed without a success
+    fn outage_monitor_goes_down_after_threshold() {
+        let monitor = OutageMonitor::new(1);
+
+        assert!(!monitor.record_failure());
+        assert!(!monitor.is_down());
+
+        *monitor.last_success.lock().unwrap() = Instant::now() - Duration::from_secs(2);
+
+        assert!(monitor.record_failure());
+        assert!(monitor.is_down());
+
+        // only one probe per outage
+        assert!(monitor.claim_probe());
+        assert!(!monitor.claim_probe());
+
+        monitor.mark_up();
+        assert!(!monitor.is_down());
+        assert!(!monitor.record_failure());
+        assert!(monitor.claim_probe());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// wait_until_up should return once the outage ends
+    async fn outage_monitor_wait_until_up_returns_on_recovery() {
+        let monitor = std::sync::Arc::new(OutageMonitor::new(1));
+        *monitor.last_success.lock().unwrap() = Instant::now() - Duration::from_secs(2);
+        assert!(monitor.record_failure());
+
+        let clone = monitor.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(SLEEP_DURATION)).await;
+            clone.mark_up();
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), monitor.wait_until_up())
+            .await
+            .unwrap();
+        assert!(!monitor.is_down());
+    }
+}
@@ -0,0 +1,144 @@
+use std::{ops::Range, time::Duration};
+
+use super::{FeroxScans, ScanType};
+use crate::{config::Configuration, SLEEP_DURATION};
+
+/// Part of the wordlist a directory scan requests at a time
+///
+/// with --directories-first, every directory scan (including the ones found by recursion)
+/// requests its words without extensions first, mapping the site's structure breadth first;
+/// only once every known directory scan is through that pass do they come back for the
+/// extensions
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ScanPhase {
+    /// every variant of each word, the default
+    #[default]
+    All,
+
+    /// only the word itself (word and word/ with --dual-slash)
+    Directories,
+
+    /// only the word with each of the extensions
+    Files,
+}
+
+/// implementation of ScanPhase
+impl ScanPhase {
+    /// phase a directory scan starts in; with --directories-first, that's the directory pass,
+    /// unless there are no extensions to come back for or requests are `templated` (FUZZ
+    /// keywords in use)
+    pub fn first(config: &Configuration, templated: bool) -> Self {
+        if config.directories_first
+            && !templated
+            && (!config.extensions.is_empty() || config.collect_extensions)
+        {
+            ScanPhase::Directories
+        } else {
+            ScanPhase::All
+        }
+    }
+
+    /// variants (as in `FeroxUrl::formatted_url_groups`) requested during the phase, given the
+    /// number of variants each word has; `None` when there's no need to pick them apart
+    pub fn variants(self, variants: usize) -> Option<Range<usize>> {
+        match self {
+            ScanPhase::All => None,
+            ScanPhase::Directories => Some(0..variants.min(1)),
+            ScanPhase::Files => Some(variants.min(1)..variants),
+        }
+    }
+}
+
+/// --directories-first; scheduling between the two phases
+impl FeroxScans {
+    /// number of directory scans that still have words to request without extensions
+    ///
+    /// a scan whose task errored out never finishes its pass, it doesn't hold up the others
+    pub async fn num_in_directory_pass(&self) -> usize {
+        let scans = match self.scans.read() {
+            Ok(guard) => guard.clone(),
+            Err(_) => return 0,
+        };
+
+        let mut num_scans = 0;
+
+        for scan in scans {
+            if matches!(scan.scan_type, ScanType::Directory)
+                && scan.is_active()
+                && !scan.directory_pass_done()
+                && !scan.task_finished().await
+            {
+                num_scans += 1;
+            }
+        }
+
+        num_scans
+    }
+
+    /// wait until every directory scan, including the ones still waiting for a slot under
+    /// --scan-limit, has requested its words without extensions
+    pub async fn wait_for_directory_pass(&self) {
+        while self.num_in_directory_pass().await > 0 {
+            tokio::time::sleep(Duration::from_millis(SLEEP_DURATION)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan_manager::{FeroxScan, ScanStatus};
+    use std::sync::Arc;
+
+    #[test]
+    /// directories get the first variant, files the rest; all doesn't pick them apart
+    fn scan_phase_splits_variants() {
+        assert_eq!(ScanPhase::All.variants(4), None);
+        assert_eq!(ScanPhase::Directories.variants(4), Some(0..1));
+        assert_eq!(ScanPhase::Files.variants(4), Some(1..4));
+        assert_eq!(ScanPhase::Files.variants(1), Some(1..1));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// only active directory scans that haven't finished their pass are waited on
+    async fn num_in_directory_pass_counts_unfinished_directory_scans() {
+        let scans = FeroxScans::default();
+        let mut added = Vec::new();
+
+        for (url, scan_type, status) in [
+            (
+                "http://localhost/a",
+                ScanType::Directory,
+                ScanStatus::Running,
+            ),
+            (
+                "http://localhost/b",
+                ScanType::Directory,
+                ScanStatus::Pending,
+            ),
+            (
+                "http://localhost/c",
+                ScanType::Directory,
+                ScanStatus::Complete,
+            ),
+            ("http://localhost/d", ScanType::File, ScanStatus::Running),
+        ] {
+            let scan = Arc::new(FeroxScan {
+                url: url.to_string(),
+                scan_type,
+                ..Default::default()
+            });
+            scan.set_status(status).unwrap();
+            scans.insert(scan.clone());
+            added.push(scan);
+        }
+
+        assert_eq!(scans.num_in_directory_pass().await, 2);
+
+        added[0].finish_directory_pass();
+        assert_eq!(scans.num_in_directory_pass().await, 1);
+
+        added[1].finish_directory_pass();
+        scans.wait_for_directory_pass().await;
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::{AcquireError, Semaphore, SemaphorePermit};
+
+use super::FeroxScan;
+use crate::{utils::parse_url_with_raw_path, SLEEP_DURATION};
+
+/// Order in which directory scans found by recursion take their turn under --scan-limit
+/// (--recursion-strategy)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum RecursionStrategy {
+    /// shallower scans go first; scans at the same depth go in the order they were found
+    #[default]
+    BreadthFirst,
+
+    /// deeper scans go first; scans at the same depth go newest first, so the branch that's
+    /// being explored is followed all the way down before moving on
+    DepthFirst,
+}
+
+/// implementation of RecursionStrategy
+impl RecursionStrategy {
+    /// parse the value given to --recursion-strategy, anything unknown is breadth first
+    pub fn from_config(strategy: &str) -> Self {
+        match strategy {
+            "dfs" => Self::DepthFirst,
+            _ => Self::BreadthFirst,
+        }
+    }
+}
+
+/// number of non-empty path segments in the scan's url; 0 when it can't be parsed
+fn depth(scan: &FeroxScan) -> usize {
+    parse_url_with_raw_path(scan.url())
+        .ok()
+        .and_then(|url| {
+            url.path_segments()
+                .map(|segments| segments.filter(|segment| !segment.is_empty()).count())
+        })
+        .unwrap_or_default()
+}
+
+/// A directory scan waiting for a free slot under --scan-limit
+#[derive(Debug, Clone)]
+pub struct QueuedScan {
+    /// the waiting scan
+    pub scan: Arc<FeroxScan>,
+
+    /// url of the directory scan that found it, `None` for targets given by the user
+    pub origin: Option<String>,
+}
+
+/// Directory scans waiting for a free slot under --scan-limit, in the order they'll be started
+///
+/// targets given by the user take their turn in the order they were queued; scans found by
+/// recursion are slotted in according to the --recursion-strategy. either can be reordered from
+/// the interactive menu afterwards
+#[derive(Debug, Default)]
+pub struct ScanQueue {
+    /// scans waiting for their turn, next in line first
+    pending: Mutex<Vec<QueuedScan>>,
+}
+
+/// implementation of ScanQueue
+impl ScanQueue {
+    /// wait for both a slot from the limiter and the scan's turn in the queue
+    ///
+    /// the limiter hands out slots first come first served; a scan that gets one out of turn
+    /// gives it back and checks again a little later, which leaves the slot to whoever is next
+    pub async fn acquire<'a>(
+        &self,
+        scan: &Arc<FeroxScan>,
+        origin: Option<String>,
+        strategy: RecursionStrategy,
+        limiter: &'a Semaphore,
+    ) -> Result<SemaphorePermit<'a>, AcquireError> {
+        self.enqueue(
+            QueuedScan {
+                scan: scan.clone(),
+                origin,
+            },
+            strategy,
+        );
+
+        loop {
+            let permit = match limiter.acquire().await {
+                Ok(permit) => permit,
+                Err(e) => {
+                    self.remove(scan);
+                    return Err(e);
+                }
+            };
+
+            if self.is_next(scan) {
+                self.remove(scan);
+                return Ok(permit);
+            }
+
+            drop(permit);
+            tokio::time::sleep(Duration::from_millis(SLEEP_DURATION)).await;
+        }
+    }
+
+    /// add a scan to the queue; one found by recursion goes ahead of the first scan the given
+    /// strategy would have it run before, everything else goes to the back
+    fn enqueue(&self, queued: QueuedScan, strategy: RecursionStrategy) {
+        let Ok(mut guard) = self.pending.lock() else {
+            return;
+        };
+
+        if queued.origin.is_none() {
+            guard.push(queued);
+            return;
+        }
+
+        let new_depth = depth(&queued.scan);
+
+        let position = guard.iter().position(|pending| {
+            let pending_depth = depth(&pending.scan);
+
+            match strategy {
+                RecursionStrategy::BreadthFirst => pending_depth > new_depth,
+                RecursionStrategy::DepthFirst => pending_depth <= new_depth,
+            }
+        });
+
+        match position {
+            Some(position) => guard.insert(position, queued),
+            None => guard.push(queued),
+        }
+    }
+
+    /// scans still waiting for their turn, next in line first; scans cancelled while they
+    /// were waiting are dropped from the queue
+    pub fn pending(&self) -> Vec<QueuedScan> {
+        match self.pending.lock() {
+            Ok(mut guard) => {
+                guard.retain(|queued| !queued.scan.is_cancelled());
+                guard.clone()
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// whether the given scan is next in line
+    fn is_next(&self, scan: &FeroxScan) -> bool {
+        self.pending()
+            .first()
+            .is_none_or(|queued| *queued.scan == *scan)
+    }
+
+    /// take the given scan out of the queue
+    fn remove(&self, scan: &FeroxScan) {
+        if let Ok(mut guard) = self.pending.lock() {
+            guard.retain(|queued| *queued.scan != *scan);
+        }
+    }
+
+    /// take the scans at the given positions (1-based, as shown by the menu) out of the queue;
+    /// positions that don't exist are ignored
+    pub fn take(&self, positions: &[usize]) -> Vec<Arc<FeroxScan>> {
+        let selected = self.select(positions);
+
+        if let Ok(mut guard) = self.pending.lock() {
+            guard.retain(|queued| !selected.iter().any(|s| *s.scan == *queued.scan));
+        }
+
+        selected.into_iter().map(|queued| queued.scan).collect()
+    }
+
+    /// move the scans at the given positions (1-based, as shown by the menu) to the front of
+    /// the queue, or to the back, keeping the order they were given in; positions that don't
+    /// exist are ignored
+    pub fn reorder(&self, positions: &[usize], to_front: bool) {
+        let selected = self.select(positions);
+
+        if let Ok(mut guard) = self.pending.lock() {
+            guard.retain(|queued| !selected.iter().any(|s| *s.scan == *queued.scan));
+
+            if to_front {
+                guard.splice(0..0, selected);
+            } else {
+                guard.extend(selected);
+            }
+        }
+    }
+
+    /// the queued scans at the given positions (1-based), in the order given
+    fn select(&self, positions: &[usize]) -> Vec<QueuedScan> {
+        let pending = self.pending();
+
+        positions
+            .iter()
+            .filter_map(|position| pending.get(position.checked_sub(1)?).cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan_manager::ScanStatus;
+
+    /// queue holding a scan for each of the given urls, in order
+    fn queue_of(urls: &[&str]) -> (ScanQueue, Vec<Arc<FeroxScan>>) {
+        let scans: Vec<_> = urls
+            .iter()
+            .map(|url| {
+                Arc::new(FeroxScan {
+                    url: url.to_string(),
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        let queue = ScanQueue::default();
+
+        if let Ok(mut guard) = queue.pending.lock() {
+            for scan in &scans {
+                guard.push(QueuedScan {
+                    scan: scan.clone(),
+                    origin: None,
+                });
+            }
+        }
+
+        (queue, scans)
+    }
+
+    /// urls of the queued scans, next in line first
+    fn urls(queue: &ScanQueue) -> Vec<String> {
+        queue
+            .pending()
+            .iter()
+            .map(|queued| queued.scan.url().to_string())
+            .collect()
+    }
+
+    #[test]
+    /// selected scans move to the front or back in the order given, bad positions are ignored
+    fn scan_queue_reorders_scans() {
+        let (queue, scans) = queue_of(&["a", "b", "c", "d"]);
+
+        queue.reorder(&[4, 3, 0, 9], true);
+        assert_eq!(urls(&queue), ["d", "c", "a", "b"]);
+        assert!(queue.is_next(&scans[3]));
+
+        queue.reorder(&[1], false);
+        assert_eq!(urls(&queue), ["c", "a", "b", "d"]);
+    }
+
+    #[test]
+    /// taken scans leave the queue, cancelled scans are skipped over
+    fn scan_queue_takes_and_prunes_scans() {
+        let (queue, scans) = queue_of(&["a", "b", "c"]);
+
+        let taken = queue.take(&[2]);
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].url(), "b");
+
+        scans[0].set_status(ScanStatus::Cancelled).unwrap();
+        assert_eq!(urls(&queue), ["c"]);
+        assert!(queue.is_next(&scans[2]));
+    }
+
+    /// queue the given urls, in order, as if found by recursion
+    fn enqueue_all(queue: &ScanQueue, urls: &[&str], strategy: RecursionStrategy) {
+        for url in urls {
+            let scan = Arc::new(FeroxScan {
+                url: url.to_string(),
+                ..Default::default()
+            });
+
+            queue.enqueue(
+                QueuedScan {
+                    scan,
+                    origin: Some(String::from("http://localhost/")),
+                },
+                strategy,
+            );
+        }
+    }
+
+    #[test]
+    /// breadth first runs shallower scans first, depth first runs the newest, deepest ones first
+    fn scan_queue_orders_scans_by_strategy() {
+        let found = [
+            "http://localhost/a/",
+            "http://localhost/a/b/",
+            "http://localhost/c/",
+            "http://localhost/a/b/d/",
+            "http://localhost/c/e/",
+        ];
+
+        let queue = ScanQueue::default();
+        enqueue_all(&queue, &found, RecursionStrategy::BreadthFirst);
+        assert_eq!(
+            urls(&queue),
+            [
+                "http://localhost/a/",
+                "http://localhost/c/",
+                "http://localhost/a/b/",
+                "http://localhost/c/e/",
+                "http://localhost/a/b/d/",
+            ]
+        );
+
+        let queue = ScanQueue::default();
+        enqueue_all(&queue, &found, RecursionStrategy::DepthFirst);
+        assert_eq!(
+            urls(&queue),
+            [
+                "http://localhost/a/b/d/",
+                "http://localhost/c/e/",
+                "http://localhost/a/b/",
+                "http://localhost/c/",
+                "http://localhost/a/",
+            ]
+        );
+
+        assert_eq!(
+            RecursionStrategy::from_config("dfs"),
+            RecursionStrategy::DepthFirst
+        );
+        assert_eq!(
+            RecursionStrategy::from_config(""),
+            RecursionStrategy::BreadthFirst
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    /// a free slot goes to the scan that's next in line, not the one that asked first
+    async fn scan_queue_acquire_waits_for_turn() {
+        let (queue, scans) = queue_of(&["first"]);
+        let queue = Arc::new(queue);
+        let limiter = Arc::new(Semaphore::new(1));
+
+        let waiting = {
+            let (queue, limiter) = (queue.clone(), limiter.clone());
+            let scan = Arc::new(FeroxScan {
+                url: String::from("second"),
+                ..Default::default()
+            });
+
+            tokio::spawn(async move {
+                let _permit = queue
+                    .acquire(&scan, None, RecursionStrategy::default(), &limiter)
+                    .await
+                    .unwrap();
+            })
+        };
+
+        tokio::time::sleep(Duration::from_millis(SLEEP_DURATION * 2)).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(urls(&queue), ["first", "second"]);
+
+        // the scan that was next in line is gone, so the waiting scan gets its turn
+        queue.remove(&scans[0]);
+        waiting.await.unwrap();
+        assert!(queue.pending().is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 use super::*;
 use crate::{
+    atomic_increment, atomic_load, atomic_store,
     config::OutputLevel,
-    progress::{add_bar, BarType},
+    progress::{add_bar_with_rate, BarType, RateLimitGauge, RequestRate},
     scanner::PolicyTrigger,
+    statistics::{ErrorWeights, StatError},
+    url::FeroxUrl,
 };
 use anyhow::Result;
 use console::style;
@@ -10,13 +13,14 @@ use indicatif::ProgressBar;
 use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt,
+    net::IpAddr,
     sync::{Arc, Mutex},
     time::Instant,
 };
 
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use tokio::{sync, task::JoinHandle};
 use uuid::Uuid;
 
@@ -39,7 +43,6 @@ pub struct FeroxScan {
     pub scan_type: ScanType,
 
     /// The order in which the scan was received
-    #[allow(dead_code)] // not entirely sure this isn't used somewhere
     pub(crate) scan_order: ScanOrder,
 
     /// Number of requests to populate the progress bar with
@@ -49,7 +52,9 @@ pub struct FeroxScan {
     ///
     /// serialization: saves self.requests() to this field
     /// deserialization: sets self.requests_made_so_far to this field
-    pub(super) requests_made_so_far: u64,
+    ///
+    /// also updated when the scan is requeued, so it picks up where it left off
+    pub(super) requests_made_so_far: AtomicU64,
 
     /// Status of this scan
     pub status: Mutex<ScanStatus>,
@@ -63,17 +68,60 @@ pub struct FeroxScan {
     /// whether or not the user passed --silent|--quiet on the command line
     pub(super) output_level: OutputLevel,
 
-    /// tracker for overall number of 403s seen by the FeroxScan instance
-    pub(super) status_403s: AtomicUsize,
+    /// number of errors encountered by the FeroxScan instance, keyed by category
+    pub(super) error_kinds: Mutex<BTreeMap<StatError, usize>>,
+
+    /// number of responses seen by the FeroxScan instance, keyed by status code
+    pub(super) status_codes: Mutex<BTreeMap<u16, usize>>,
 
-    /// tracker for overall number of 429s seen by the FeroxScan instance
-    pub(super) status_429s: AtomicUsize,
+    /// number of times a host's server banner changed under this scan (--server-alerts)
+    pub(super) server_changes: AtomicUsize,
 
-    /// tracker for total number of errors encountered by the FeroxScan instance
-    pub(super) errors: AtomicUsize,
+    /// every address that served a response to this scan, when connecting directly
+    pub(super) remote_ips: Mutex<BTreeSet<IpAddr>>,
 
     /// tracker for the time at which this scan was started
     pub(super) start_time: Instant,
+
+    /// smoothed requests per second, shared with (and sampled by) the progress bar
+    pub(super) rate: Arc<RequestRate>,
+
+    /// requests per second this scan is held to, shown on its progress bar
+    pub(super) limit_gauge: Arc<RateLimitGauge>,
+
+    /// recent response times, used by --tune-on-latency
+    pub(super) latency: LatencyTracker,
+
+    /// snapshot of the rate limiting/auto-tune state reached by this scan's Requester, saved
+    /// so that a resumed scan picks up where it left off
+    pub(super) tuning: Mutex<Option<TuningState>>,
+
+    /// rate limit/auto-tune settings the user picked for this scan alone from the interactive
+    /// menu, in place of the ones every other scan uses
+    pub(super) overrides: Mutex<ScanOverrides>,
+
+    /// number of times the scan was requeued by --auto-requeue
+    pub(super) requeues: AtomicUsize,
+
+    /// request/error counts as of the last requeue; policies only judge what came after
+    pub(super) requeue_mark: Mutex<RequeueMark>,
+
+    /// whether the scan was cancelled by --auto-bail (or --auto-requeue, once out of attempts)
+    pub(super) bailed: AtomicBool,
+
+    /// seconds the scan took, set once it completes
+    pub(super) runtime: Mutex<Option<f64>>,
+
+    /// every change made to the scan's rate limit, as (seconds into the scan, new limit); a
+    /// limit of 0 means the limiter was removed
+    pub(super) tuning_events: Mutex<Vec<(f64, usize)>>,
+
+    /// headers sent with this scan's requests on top of the configured ones, inherited from the
+    /// scan that found it (--inherit-headers)
+    pub(super) headers: Mutex<Vec<(String, String)>>,
+
+    /// whether the scan has requested every word without extensions yet (--directories-first)
+    pub(super) directory_pass: AtomicBool,
 }
 
 /// Default implementation for FeroxScan
@@ -87,17 +135,30 @@ impl Default for FeroxScan {
             task: sync::Mutex::new(None), // tokio mutex
             status: Mutex::new(ScanStatus::default()),
             num_requests: 0,
-            requests_made_so_far: 0,
+            requests_made_so_far: AtomicU64::new(0),
             scan_order: ScanOrder::Latest,
             url: String::new(),
             normalized_url: String::new(),
             progress_bar: Mutex::new(None),
             scan_type: ScanType::File,
             output_level: Default::default(),
-            errors: Default::default(),
-            status_429s: Default::default(),
-            status_403s: Default::default(),
+            error_kinds: Default::default(),
+            status_codes: Default::default(),
+            server_changes: Default::default(),
+            remote_ips: Default::default(),
             start_time: Instant::now(),
+            rate: Default::default(),
+            limit_gauge: Default::default(),
+            latency: Default::default(),
+            tuning: Mutex::new(None),
+            overrides: Default::default(),
+            requeues: Default::default(),
+            requeue_mark: Default::default(),
+            bailed: Default::default(),
+            runtime: Default::default(),
+            tuning_events: Default::default(),
+            headers: Default::default(),
+            directory_pass: Default::default(),
         }
     }
 }
@@ -110,11 +171,14 @@ impl FeroxScan {
 
         match self.task.try_lock() {
             Ok(mut guard) => {
-                if let Some(task) = std::mem::replace(&mut *guard, None) {
+                if let Some(task) = guard.take() {
                     log::trace!("aborting {:?}", self);
                     task.abort();
                     self.set_status(ScanStatus::Cancelled)?;
                     self.stop_progress_bar();
+                } else if self.is_requeued() {
+                    // nothing running, but the requeue still needs to be called off
+                    self.set_status(ScanStatus::Cancelled)?;
                 }
             }
             Err(e) => {
@@ -132,16 +196,137 @@ impl FeroxScan {
 
     /// getter for number of requests made during previously saved scans (i.e. --resume-from used)
     pub fn requests_made_so_far(&self) -> u64 {
-        self.requests_made_so_far
+        atomic_load!(self.requests_made_so_far)
+    }
+
+    /// getter for the last saved rate limiting/auto-tune state
+    pub fn tuning_state(&self) -> Option<TuningState> {
+        self.tuning.lock().ok().and_then(|guard| *guard)
+    }
+
+    /// seconds the scan took, `None` until it completes
+    pub fn runtime(&self) -> Option<f64> {
+        self.runtime.lock().ok().and_then(|guard| *guard)
+    }
+
+    /// record a change to the scan's rate limit; 0 means the limiter was removed
+    pub fn add_tuning_event(&self, rate_limit: usize) {
+        if let Ok(mut guard) = self.tuning_events.lock() {
+            guard.push((self.start_time.elapsed().as_secs_f64(), rate_limit));
+        }
+    }
+
+    /// getter for the requests per second this scan is held to, as shown on its progress bar
+    pub fn limit_gauge(&self) -> &Arc<RateLimitGauge> {
+        &self.limit_gauge
+    }
+
+    /// every change made to the scan's rate limit, as (seconds into the scan, new limit)
+    pub fn tuning_events(&self) -> Vec<(f64, usize)> {
+        self.tuning_events
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// small wrapper to save the current rate limiting/auto-tune state
+    pub fn set_tuning_state(&self, state: TuningState) {
+        if let Ok(mut guard) = self.tuning.lock() {
+            let _ = guard.replace(state);
+        }
+    }
+
+    /// getter for the rate limit/auto-tune settings picked for this scan alone
+    pub fn overrides(&self) -> ScanOverrides {
+        self.overrides
+            .lock()
+            .map(|guard| *guard)
+            .unwrap_or_default()
+    }
+
+    /// pin this scan's rate limit, leaving the rest of the scans as they are; a pinned limit
+    /// also turns auto-tune off for the scan, so that it stays put
+    ///
+    /// 0 unpins the scan, it goes back to the limit and auto-tune setting every other scan uses
+    pub fn set_rate_limit(&self, limit: usize) {
+        if let Ok(mut guard) = self.overrides.lock() {
+            if limit > 0 {
+                guard.rate_limit = Some(limit);
+                guard.auto_tune = Some(false);
+            } else {
+                guard.rate_limit = None;
+                guard.auto_tune = None;
+            }
+
+            guard.generation += 1;
+        }
+    }
+
+    /// turn auto-tune on or off for this scan, leaving the rest of the scans as they are
+    pub fn set_auto_tune(&self, enabled: bool) {
+        if let Ok(mut guard) = self.overrides.lock() {
+            guard.auto_tune = Some(enabled);
+            guard.generation += 1;
+        }
+    }
+
+    /// short, human readable version of `overrides`, i.e. `limit: 50/s, tune: off`
+    pub fn override_summary(&self) -> String {
+        let overrides = self.overrides();
+        let mut summary = Vec::new();
+
+        if let Some(limit) = overrides.rate_limit {
+            summary.push(format!("limit: {limit}/s"));
+        }
+
+        if let Some(enabled) = overrides.auto_tune {
+            summary.push(format!("tune: {}", if enabled { "on" } else { "off" }));
+        }
+
+        summary.join(", ")
+    }
+
+    /// getter for the headers sent with this scan's requests on top of the configured ones
+    pub fn headers(&self) -> Vec<(String, String)> {
+        self.headers
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// small wrapper to set the scan's own headers
+    pub fn set_headers(&self, headers: Vec<(String, String)>) {
+        if let Ok(mut guard) = self.headers.lock() {
+            *guard = headers;
+        }
     }
 
     /// small wrapper to set the JoinHandle
     pub async fn set_task(&self, task: JoinHandle<()>) -> Result<()> {
         let mut guard = self.task.lock().await;
-        let _ = std::mem::replace(&mut *guard, Some(task));
+        let _ = guard.replace(task);
         Ok(())
     }
 
+    /// whether the scan's task has run to completion, or errored out along the way
+    pub async fn task_finished(&self) -> bool {
+        self.task
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+    }
+
+    /// whether every word has been requested without extensions (--directories-first)
+    pub fn directory_pass_done(&self) -> bool {
+        self.directory_pass.load(Ordering::Relaxed)
+    }
+
+    /// mark the pass over the wordlist without extensions as done (--directories-first)
+    pub fn finish_directory_pass(&self) {
+        self.directory_pass.store(true, Ordering::Relaxed);
+    }
+
     /// small wrapper to set ScanStatus
     pub fn set_status(&self, status: ScanStatus) -> Result<()> {
         if let Ok(mut guard) = self.status.lock() {
@@ -152,6 +337,8 @@ impl FeroxScan {
 
     /// Simple helper to call .finish on the scan's progress bar
     pub(super) fn stop_progress_bar(&self) {
+        self.limit_gauge.clear();
+
         if let Ok(guard) = self.progress_bar.lock() {
             if guard.is_some() {
                 let pb = (*guard).as_ref().unwrap();
@@ -178,12 +365,18 @@ impl FeroxScan {
                         OutputLevel::Silent => BarType::Hidden,
                     };
 
-                    let pb = add_bar(&self.url, self.num_requests, bar_type);
+                    let pb = add_bar_with_rate(
+                        &self.url,
+                        self.num_requests,
+                        bar_type,
+                        self.rate.clone(),
+                        self.limit_gauge.clone(),
+                    );
                     pb.reset_elapsed();
 
-                    pb.set_position(self.requests_made_so_far);
+                    pb.set_position(self.requests_made_so_far());
 
-                    let _ = std::mem::replace(&mut *guard, Some(pb.clone()));
+                    let _ = guard.replace(pb.clone());
 
                     pb
                 }
@@ -197,7 +390,13 @@ impl FeroxScan {
                     OutputLevel::Silent => BarType::Hidden,
                 };
 
-                let pb = add_bar(&self.url, self.num_requests, bar_type);
+                let pb = add_bar_with_rate(
+                    &self.url,
+                    self.num_requests,
+                    bar_type,
+                    self.rate.clone(),
+                    self.limit_gauge.clone(),
+                );
                 pb.reset_elapsed();
 
                 pb
@@ -216,7 +415,7 @@ impl FeroxScan {
     ) -> Arc<Self> {
         Arc::new(Self {
             url: url.to_string(),
-            normalized_url: format!("{}/", url.trim_end_matches('/')),
+            normalized_url: FeroxUrl::canonicalize(url),
             scan_type,
             scan_order,
             num_requests,
@@ -228,19 +427,28 @@ impl FeroxScan {
 
     /// Mark the scan as complete and stop the scan's progress bar
     pub fn finish(&self) -> Result<()> {
+        if let Ok(mut guard) = self.runtime.lock() {
+            *guard = Some(self.start_time.elapsed().as_secs_f64());
+        }
+
         self.set_status(ScanStatus::Complete)?;
         self.stop_progress_bar();
         Ok(())
     }
 
-    /// small wrapper to inspect ScanType and ScanStatus to see if a Directory scan is running or
-    /// in the queue to be run
+    /// small wrapper to inspect ScanType and ScanStatus to see if a Directory (or Crawl) scan is
+    /// running or in the queue to be run (including waiting to be requeued)
     pub fn is_active(&self) -> bool {
         if let Ok(guard) = self.status.lock() {
             return matches!(
                 (self.scan_type, *guard),
-                (ScanType::Directory, ScanStatus::Running)
-                    | (ScanType::Directory, ScanStatus::NotStarted)
+                (
+                    ScanType::Directory | ScanType::Crawl,
+                    ScanStatus::Running
+                        | ScanStatus::NotStarted
+                        | ScanStatus::Pending
+                        | ScanStatus::Requeued
+                )
             );
         }
         false
@@ -262,13 +470,96 @@ impl FeroxScan {
         false
     }
 
+    /// small wrapper to inspect ScanStatus and see if it's Pending
+    pub fn is_pending(&self) -> bool {
+        if let Ok(guard) = self.status.lock() {
+            return matches!(*guard, ScanStatus::Pending);
+        }
+        false
+    }
+
+    /// small wrapper to inspect ScanStatus and see if it's Requeued
+    pub fn is_requeued(&self) -> bool {
+        if let Ok(guard) = self.status.lock() {
+            return matches!(*guard, ScanStatus::Requeued);
+        }
+        false
+    }
+
+    /// number of times the scan was requeued by --auto-requeue
+    pub fn requeues(&self) -> usize {
+        atomic_load!(self.requeues)
+    }
+
+    /// record that the scan was cancelled by --auto-bail/--auto-requeue
+    pub fn mark_bailed(&self) {
+        atomic_store!(self.bailed, true);
+    }
+
+    /// whether the scan was cancelled by --auto-bail/--auto-requeue
+    pub fn is_bailed(&self) -> bool {
+        atomic_load!(self.bailed)
+    }
+
+    /// stop a running scan and set it aside so it can be restarted later (--auto-requeue)
+    ///
+    /// the scan remembers how far it got, so the restarted scan picks up from there with a
+    /// fresh progress bar (the old one is left as-is), and the errors seen so far are no longer
+    /// held against it
+    pub fn requeue(&self) -> Result<()> {
+        // set first, so in-flight requests don't try to bail on the scan a second time
+        self.set_status(ScanStatus::Requeued)?;
+
+        if let Ok(mut guard) = self.task.try_lock() {
+            if let Some(task) = guard.take() {
+                log::trace!("requeueing {:?}", self);
+                task.abort();
+            }
+        }
+
+        let requests = self.requests();
+
+        if let Ok(mut guard) = self.progress_bar.lock() {
+            if let Some(pb) = guard.take() {
+                pb.abandon();
+            }
+        }
+
+        atomic_store!(self.requests_made_so_far, requests);
+        atomic_increment!(self.requeues);
+
+        if let Ok(mut guard) = self.requeue_mark.lock() {
+            *guard = RequeueMark {
+                requests,
+                error_kinds: self.errors_by_kind(),
+                status_codes: self.status_codes(),
+                server_changes: self.server_changes.load(Ordering::Relaxed),
+            };
+        }
+
+        self.latency.clear_recent();
+
+        Ok(())
+    }
+
+    /// number of requests made since the scan was last requeued (or started)
+    pub fn requests_since_requeue(&self) -> u64 {
+        let mark = self
+            .requeue_mark
+            .lock()
+            .map(|guard| guard.requests)
+            .unwrap_or_default();
+
+        self.requests().saturating_sub(mark)
+    }
+
     /// await a task's completion, similar to a thread's join; perform necessary bookkeeping
     pub async fn join(&self) {
         log::trace!("enter join({:?})", self);
         let mut guard = self.task.lock().await;
 
         if guard.is_some() {
-            if let Some(task) = std::mem::replace(&mut *guard, None) {
+            if let Some(task) = guard.take() {
                 task.await.unwrap();
                 self.set_status(ScanStatus::Complete)
                     .unwrap_or_else(|e| log::warn!("Could not mark scan complete: {}", e))
@@ -277,58 +568,152 @@ impl FeroxScan {
 
         log::trace!("exit join({:?})", self);
     }
-    /// increment the value in question by 1
-    pub(crate) fn add_403(&self) {
-        self.status_403s.fetch_add(1, Ordering::Relaxed);
+    /// add one to the number of responses seen with the given status code
+    pub(crate) fn add_status_code(&self, code: u16) {
+        if let Ok(mut guard) = self.status_codes.lock() {
+            *guard.entry(code).or_default() += 1;
+        }
     }
 
-    /// increment the value in question by 1
-    pub(crate) fn add_429(&self) {
-        self.status_429s.fetch_add(1, Ordering::Relaxed);
+    /// return the number of responses seen by this scan, keyed by status code
+    pub fn status_codes(&self) -> BTreeMap<u16, usize> {
+        self.status_codes
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
     }
 
-    /// increment the value in question by 1
-    pub(crate) fn add_error(&self) {
-        self.errors.fetch_add(1, Ordering::Relaxed);
+    /// return the number of responses seen by this scan with the given status code
+    pub fn num_status_code(&self, code: u16) -> usize {
+        self.status_codes
+            .lock()
+            .map(|guard| guard.get(&code).copied().unwrap_or_default())
+            .unwrap_or_default()
     }
 
-    /// simple wrapper to call the appropriate getter based on the given PolicyTrigger
-    pub fn num_errors(&self, trigger: PolicyTrigger) -> usize {
-        match trigger {
-            PolicyTrigger::Status403 => self.status_403s(),
-            PolicyTrigger::Status429 => self.status_429s(),
-            PolicyTrigger::Errors => self.errors(),
-            PolicyTrigger::TryAdjustUp => 0,
+    /// short, human readable version of `status_codes`, i.e. `200: 5, 404: 1000`
+    pub fn status_code_summary(&self) -> String {
+        self.status_codes()
+            .iter()
+            .map(|(code, count)| format!("{code}: {count}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// increment the number of errors of the given category by 1
+    pub(crate) fn add_error(&self, error: StatError) {
+        if let Ok(mut guard) = self.error_kinds.lock() {
+            *guard.entry(error).or_default() += 1;
+        }
+    }
+
+    /// return the errors seen by this scan, keyed by category
+    pub fn errors_by_kind(&self) -> BTreeMap<StatError, usize> {
+        self.error_kinds
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// human readable breakdown of the errors seen by this scan, i.e. `dns: 3, timeout: 1`
+    pub fn error_summary(&self) -> String {
+        self.errors_by_kind()
+            .iter()
+            .map(|(error, count)| format!("{}: {count}", error.name()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// errors seen since the given mark, each counted by the weight of its category
+    /// (--policy-error-weight)
+    fn weighted_errors_since(&self, mark: &RequeueMark, weights: &ErrorWeights) -> usize {
+        self.errors_by_kind()
+            .iter()
+            .map(|(error, count)| {
+                let since_mark =
+                    count.saturating_sub(mark.error_kinds.get(error).copied().unwrap_or_default());
+                since_mark * weights.weight(*error)
+            })
+            .sum()
+    }
+
+    /// note that a response to this scan was served from the given address
+    pub(crate) fn add_remote_ip(&self, ip: IpAddr) {
+        if let Ok(mut guard) = self.remote_ips.lock() {
+            guard.insert(ip);
         }
     }
 
-    /// return the number of errors seen by this scan
-    fn errors(&self) -> usize {
-        self.errors.load(Ordering::Relaxed)
+    /// every address that served a response to this scan, when connecting directly
+    pub fn remote_ips(&self) -> BTreeSet<IpAddr> {
+        self.remote_ips
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
     }
 
-    /// return the number of 403s seen by this scan
-    fn status_403s(&self) -> usize {
-        self.status_403s.load(Ordering::Relaxed)
+    /// increment the number of server banner changes seen by this scan
+    pub(crate) fn add_server_change(&self) {
+        self.server_changes.fetch_add(1, Ordering::Relaxed);
     }
 
-    /// return the number of 429s seen by this scan
-    fn status_429s(&self) -> usize {
-        self.status_429s.load(Ordering::Relaxed)
+    /// simple wrapper to call the appropriate getter based on the given PolicyTrigger
+    ///
+    /// only counts what was seen since the scan was last requeued
+    pub fn num_errors(&self, trigger: PolicyTrigger, weights: &ErrorWeights) -> usize {
+        let Ok(mark) = self.requeue_mark.lock() else {
+            return 0;
+        };
+
+        let since_mark = |code: u16| {
+            self.num_status_code(code)
+                .saturating_sub(mark.status_codes.get(&code).copied().unwrap_or_default())
+        };
+
+        match trigger {
+            PolicyTrigger::Status403 => since_mark(403),
+            PolicyTrigger::Status429 => since_mark(429),
+            PolicyTrigger::Status(code) => since_mark(code),
+            PolicyTrigger::Errors => self.weighted_errors_since(&mark, weights),
+            PolicyTrigger::Latency => self.latency.slow_responses(),
+            PolicyTrigger::ServerChange => self
+                .server_changes
+                .load(Ordering::Relaxed)
+                .saturating_sub(mark.server_changes),
+            PolicyTrigger::TryAdjustUp => 0,
+        }
+    }
+
+    /// getter for the scan's response time tracker
+    pub fn latency(&self) -> &LatencyTracker {
+        &self.latency
     }
 
     /// return the number of requests per second performed by this scan's scanner
+    ///
+    /// prefers the smoothed rate, falling back to the overall average until enough samples
+    /// have been taken
     pub fn requests_per_second(&self) -> u64 {
         if !self.is_active() {
             return 0;
         }
 
+        if let Some(per_sec) = self.rate.per_sec() {
+            return per_sec.round() as u64;
+        }
+
         let reqs = self.requests();
         let seconds = self.start_time.elapsed().as_secs();
 
         reqs.checked_div(seconds).unwrap_or(0)
     }
 
+    /// sample the number of requests made so far into the smoothed requests per second; the
+    /// progress bar does the same whenever it's drawn, this covers bars that are never drawn
+    pub fn sample_rate(&self) {
+        self.rate.record(self.requests(), Instant::now());
+    }
+
     /// return the number of requests performed by this scan's scanner
     pub fn requests(&self) -> u64 {
         self.progress_bar().position()
@@ -341,9 +726,11 @@ impl fmt::Display for FeroxScan {
         let status = if let Ok(guard) = self.status.lock() {
             match *guard {
                 ScanStatus::NotStarted => style("not started").bright().blue(),
+                ScanStatus::Pending => style("pending").cyan(),
                 ScanStatus::Complete => style("complete").green(),
                 ScanStatus::Cancelled => style("cancelled").red(),
                 ScanStatus::Running => style("running").bright().yellow(),
+                ScanStatus::Requeued => style("requeued").magenta(),
             }
         } else {
             style("unknown").red()
@@ -367,7 +754,7 @@ impl Serialize for FeroxScan {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("FeroxScan", 6)?;
+        let mut state = serializer.serialize_struct("FeroxScan", 11)?;
 
         state.serialize_field("id", &self.id)?;
         state.serialize_field("url", &self.url)?;
@@ -377,6 +764,43 @@ impl Serialize for FeroxScan {
         state.serialize_field("num_requests", &self.num_requests)?;
         state.serialize_field("requests_made_so_far", &self.requests())?;
 
+        let status_codes = self.status_codes();
+        if status_codes.is_empty() {
+            state.skip_field("status_codes")?;
+        } else {
+            state.serialize_field("status_codes", &status_codes)?;
+        }
+
+        let remote_ips = self.remote_ips();
+        if remote_ips.is_empty() {
+            state.skip_field("remote_ips")?;
+        } else {
+            state.serialize_field("remote_ips", &remote_ips)?;
+        }
+
+        let errors = self
+            .errors_by_kind()
+            .into_iter()
+            .map(|(error, count)| (error.name(), count))
+            .collect::<BTreeMap<_, _>>();
+        if errors.is_empty() {
+            state.skip_field("errors")?;
+        } else {
+            state.serialize_field("errors", &errors)?;
+        }
+
+        match self.tuning_state() {
+            Some(tuning) => state.serialize_field("tuning", &tuning)?,
+            None => state.skip_field("tuning")?,
+        }
+
+        let headers = self.headers();
+        if headers.is_empty() {
+            state.skip_field("headers")?;
+        } else {
+            state.serialize_field("headers", &headers)?;
+        }
+
         state.end()
     }
 }
@@ -404,6 +828,7 @@ impl<'de> Deserialize<'de> for FeroxScan {
                         scan.scan_type = match scan_type {
                             "File" => ScanType::File,
                             "Directory" => ScanType::Directory,
+                            "Crawl" => ScanType::Crawl,
                             _ => ScanType::File,
                         }
                     }
@@ -412,9 +837,11 @@ impl<'de> Deserialize<'de> for FeroxScan {
                     if let Some(status) = value.as_str() {
                         scan.status = Mutex::new(match status {
                             "NotStarted" => ScanStatus::NotStarted,
+                            "Pending" => ScanStatus::Pending,
                             "Running" => ScanStatus::Running,
                             "Complete" => ScanStatus::Complete,
                             "Cancelled" => ScanStatus::Cancelled,
+                            "Requeued" => ScanStatus::Requeued,
                             _ => ScanStatus::default(),
                         })
                     }
@@ -426,7 +853,8 @@ impl<'de> Deserialize<'de> for FeroxScan {
                 }
                 "normalized_url" => {
                     if let Some(normalized_url) = value.as_str() {
-                        scan.normalized_url = normalized_url.to_string();
+                        // canonicalized again in case it was saved by an older version
+                        scan.normalized_url = FeroxUrl::canonicalize(normalized_url);
                     }
                 }
                 "num_requests" => {
@@ -436,7 +864,40 @@ impl<'de> Deserialize<'de> for FeroxScan {
                 }
                 "requests_made_so_far" => {
                     if let Some(requests_made_so_far) = value.as_u64() {
-                        scan.requests_made_so_far = requests_made_so_far;
+                        scan.requests_made_so_far = AtomicU64::new(requests_made_so_far);
+                    }
+                }
+                "status_codes" => {
+                    if let Ok(status_codes) = serde_json::from_value(value.clone()) {
+                        scan.status_codes = Mutex::new(status_codes);
+                    }
+                }
+                "remote_ips" => {
+                    if let Ok(remote_ips) = serde_json::from_value(value.clone()) {
+                        scan.remote_ips = Mutex::new(remote_ips);
+                    }
+                }
+                "errors" => {
+                    if let Ok(errors) =
+                        serde_json::from_value::<BTreeMap<String, usize>>(value.clone())
+                    {
+                        let error_kinds = errors
+                            .into_iter()
+                            .filter_map(|(name, count)| Some((name.parse().ok()?, count)))
+                            .collect::<BTreeMap<StatError, _>>();
+
+                        scan.error_kinds = Mutex::new(error_kinds);
+                    }
+                }
+                "tuning" => {
+                    // only present in state files written by v2 and later
+                    if let Ok(Some(tuning)) = serde_json::from_value(value.clone()) {
+                        scan.tuning = Mutex::new(Some(tuning));
+                    }
+                }
+                "headers" => {
+                    if let Ok(headers) = serde_json::from_value(value.clone()) {
+                        scan.headers = Mutex::new(headers);
                     }
                 }
                 _ => {}
@@ -447,6 +908,56 @@ impl<'de> Deserialize<'de> for FeroxScan {
     }
 }
 
+/// A scan's counters as of its last requeue
+#[derive(Debug, Default)]
+pub(super) struct RequeueMark {
+    /// number of requests made
+    requests: u64,
+
+    /// number of errors seen, keyed by category
+    error_kinds: BTreeMap<StatError, usize>,
+
+    /// number of responses seen, keyed by status code
+    status_codes: BTreeMap<u16, usize>,
+
+    /// number of server banner changes seen
+    server_changes: usize,
+}
+
+/// Rate limiting/auto-tune state of a single scan, as reached by its Requester
+#[derive(Debug, Default, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+pub struct TuningState {
+    /// requests per second enforced by the scan's rate limiter, 0 means no limiter
+    pub rate_limit: usize,
+
+    /// requests per second seen when auto-tune first kicked in, used to rebuild the limit heap
+    pub original_rate: i32,
+
+    /// position within the limit heap
+    pub heap_position: usize,
+
+    /// number of errors seen when the limit was last adjusted
+    pub errors: usize,
+
+    /// whether auto-tune has been triggered at least once
+    pub policy_triggered: bool,
+}
+
+/// Rate limiting/auto-tune settings picked for a single scan from the interactive menu; unset
+/// values fall back to the ones shared by every scan
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ScanOverrides {
+    /// requests per second allowed for the scan
+    pub rate_limit: Option<usize>,
+
+    /// whether auto-tune is on for the scan
+    pub auto_tune: Option<bool>,
+
+    /// incremented each time an override changes, the scan's Requester compares it against the
+    /// last one it applied
+    pub generation: usize,
+}
+
 /// Simple enum used to flag a `FeroxScan` as likely a directory or file
 #[derive(Debug, Serialize, Deserialize, Copy, Clone)]
 pub enum ScanType {
@@ -455,6 +966,9 @@ pub enum ScanType {
 
     /// A an entire directory that might be scanned
     Directory,
+
+    /// A target whose extracted links are followed instead of requesting the wordlist (--crawl)
+    Crawl,
 }
 
 /// Default implementation for ScanType
@@ -471,6 +985,9 @@ pub enum ScanStatus {
     /// Scan hasn't started yet
     NotStarted,
 
+    /// Scan is waiting for another scan to finish, due to --scan-limit
+    Pending,
+
     /// Scan finished normally
     Complete,
 
@@ -479,6 +996,9 @@ pub enum ScanStatus {
 
     /// Scan has started, but hasn't finished, nor been cancelled
     Running,
+
+    /// Scan was bailed on by --auto-requeue and is waiting out its cooldown before resuming
+    Requeued,
 }
 
 /// Default implementation for ScanStatus
@@ -509,16 +1029,39 @@ mod tests {
             None,
         );
 
-        scan.add_error();
-        scan.add_403();
-        scan.add_403();
-        scan.add_429();
-        scan.add_429();
-        scan.add_429();
-
-        assert_eq!(scan.num_errors(PolicyTrigger::Errors), 1);
-        assert_eq!(scan.num_errors(PolicyTrigger::Status403), 2);
-        assert_eq!(scan.num_errors(PolicyTrigger::Status429), 3);
+        scan.add_error(StatError::Timeout);
+        scan.add_status_code(403);
+        scan.add_status_code(403);
+        scan.add_status_code(429);
+        scan.add_status_code(429);
+        scan.add_status_code(429);
+        scan.add_status_code(503);
+        scan.add_server_change();
+
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::Errors, &ErrorWeights::default()),
+            1
+        );
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::Status403, &ErrorWeights::default()),
+            2
+        );
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::Status429, &ErrorWeights::default()),
+            3
+        );
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::Status(503), &ErrorWeights::default()),
+            1
+        );
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::Status(520), &ErrorWeights::default()),
+            0
+        );
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::ServerChange, &ErrorWeights::default()),
+            1
+        );
     }
 
     #[test]
@@ -531,15 +1074,28 @@ mod tests {
             scan_type: ScanType::Directory,
             scan_order: ScanOrder::Initial,
             num_requests: 0,
-            requests_made_so_far: 0,
+            requests_made_so_far: AtomicU64::new(0),
             status: Mutex::new(ScanStatus::Running),
             task: Default::default(),
             progress_bar: Mutex::new(None),
             output_level: Default::default(),
-            status_403s: Default::default(),
-            status_429s: Default::default(),
-            errors: Default::default(),
             start_time: Instant::now(),
+            rate: Default::default(),
+            limit_gauge: Default::default(),
+            latency: Default::default(),
+            status_codes: Default::default(),
+            error_kinds: Default::default(),
+            server_changes: Default::default(),
+            remote_ips: Default::default(),
+            tuning: Default::default(),
+            overrides: Default::default(),
+            requeues: Default::default(),
+            requeue_mark: Default::default(),
+            bailed: Default::default(),
+            runtime: Default::default(),
+            tuning_events: Default::default(),
+            headers: Default::default(),
+            directory_pass: Default::default(),
         };
 
         let pb = scan.progress_bar();
@@ -9,23 +9,26 @@ use crate::traits::FeroxFilter;
 use crate::Command::AddFilter;
 use crate::{
     banner::Banner,
-    config::OutputLevel,
+    config::{OutputLevel, RequesterPolicy},
     progress::PROGRESS_PRINTER,
     progress::{add_bar, BarType},
     scan_manager::{MenuCmd, MenuCmdResult},
     scanner::RESPONSES,
+    statistics::StatError,
     traits::FeroxSerialize,
+    url::FeroxUrl,
     Command, SLEEP_DURATION,
 };
 use anyhow::Result;
 use console::style;
-use reqwest::StatusCode;
+use reqwest::{StatusCode, Url};
 use serde::{ser::SerializeSeq, Serialize, Serializer};
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     convert::TryInto,
     fs::File,
     io::BufReader,
+    net::IpAddr,
     ops::Index,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -60,6 +63,15 @@ pub struct FeroxScans {
 
     /// vector of extensions discovered and collected during scans
     pub(crate) collected_extensions: RwLock<HashSet<String>>,
+
+    /// directory scans waiting for a free slot under --scan-limit
+    pub(crate) queue: ScanQueue,
+
+    /// urls already queued or requested outside of the wordlist, persisted across resumes
+    pub(crate) seen: SeenUrls,
+
+    /// latest auto-tune state reached on each host, handed down to scans started after it
+    pub(crate) host_tuning: HostTuning,
 }
 
 /// Serialize implementation for FeroxScans
@@ -107,6 +119,9 @@ impl FeroxScans {
         // If the container did not contain the scan, set sentry to true
         let sentry = !self.contains(&scan.url);
 
+        // scans added by recursion, by the extractor, and given as targets all count as seen
+        self.seen.insert(&scan.url);
+
         if sentry {
             // can't update the internal container while the scan itself is locked, so first
             // lock the scan and check the container for the scan's presence, then add if
@@ -133,6 +148,13 @@ impl FeroxScans {
         let reader = BufReader::new(file);
         let state: serde_json::Value = serde_json::from_reader(reader)?;
 
+        // files written before versioning was introduced are v1
+        let version = state
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(1);
+        log::debug!("loading state file {} (version {})", filename, version);
+
         if let Some(scans) = state.get("scans") {
             if let Some(arr_scans) = scans.as_array() {
                 for scan in arr_scans {
@@ -172,6 +194,12 @@ impl FeroxScans {
             }
         }
 
+        if let Some(seen) = state.get("seen_urls") {
+            if let Ok(urls) = serde_json::from_value::<Vec<String>>(seen.clone()) {
+                self.seen.extend(urls);
+            }
+        }
+
         if let Some(filters) = state.get("filters") {
             if let Some(arr_filters) = filters.as_array() {
                 for filter in arr_filters {
@@ -223,7 +251,7 @@ impl FeroxScans {
     /// on the given URL
     pub fn contains(&self, url: &str) -> bool {
         if let Ok(scans) = self.scans.read() {
-            let normalized = format!("{}/", url.trim_end_matches('/'));
+            let normalized = FeroxUrl::canonicalize(url);
 
             for scan in scans.iter() {
                 if scan.normalized_url == normalized {
@@ -237,7 +265,7 @@ impl FeroxScans {
     /// Find and return a `FeroxScan` based on the given URL
     pub fn get_scan_by_url(&self, url: &str) -> Option<Arc<FeroxScan>> {
         if let Ok(guard) = self.scans.read() {
-            let normalized = format!("{}/", url.trim_end_matches('/'));
+            let normalized = FeroxUrl::canonicalize(url);
 
             for scan in guard.iter() {
                 if scan.normalized_url == normalized {
@@ -283,25 +311,25 @@ impl FeroxScans {
         log::trace!("enter: get_base_scan_by_url -> None");
         None
     }
-    /// add one to either 403 or 429 tracker in the scan related to the given url
+    /// add one to the status code histogram in the scan related to the given url, which also
+    /// feeds the 403/429/--policy-status policy triggers
     pub fn increment_status_code(&self, url: &str, code: StatusCode) {
         if let Some(scan) = self.get_base_scan_by_url(url) {
-            match code {
-                StatusCode::TOO_MANY_REQUESTS => {
-                    scan.add_429();
-                }
-                StatusCode::FORBIDDEN => {
-                    scan.add_403();
-                }
-                _ => {}
-            }
+            scan.add_status_code(code.as_u16());
+        }
+    }
+
+    /// note that the scan related to the given url got a response from the given address
+    pub fn record_remote_ip(&self, url: &str, ip: IpAddr) {
+        if let Some(scan) = self.get_base_scan_by_url(url) {
+            scan.add_remote_ip(ip);
         }
     }
 
-    /// add one to either 403 or 429 tracker in the scan related to the given url
-    pub fn increment_error(&self, url: &str) {
+    /// add one to the error tracker in the scan related to the given url
+    pub fn increment_error(&self, url: &str, error: StatError) {
         if let Some(scan) = self.get_base_scan_by_url(url) {
-            scan.add_error();
+            scan.add_error(error);
         }
     }
 
@@ -325,14 +353,25 @@ impl FeroxScans {
         let mut printed = 0;
 
         for (i, scan) in scans.iter().enumerate() {
-            if matches!(scan.scan_type, ScanType::Directory) {
+            if matches!(scan.scan_type, ScanType::Directory | ScanType::Crawl) {
                 if printed == 0 {
                     self.menu
                         .println(&format!("{}:", style("Scans").bright().blue()));
                 }
                 // we're only interested in displaying directory scans, as those are
                 // the only ones that make sense to be stopped
-                let scan_msg = format!("{i:3}: {scan}");
+                let mut scan_msg = format!("{i:3}: {scan}");
+
+                let status_codes = scan.status_code_summary();
+                if !status_codes.is_empty() {
+                    scan_msg.push_str(&format!(" [{status_codes}]"));
+                }
+
+                let overrides = scan.override_summary();
+                if !overrides.is_empty() {
+                    scan_msg.push_str(&format!(" ({overrides})"));
+                }
+
                 self.menu.println(&scan_msg);
                 printed += 1;
             }
@@ -343,6 +382,29 @@ impl FeroxScans {
         }
     }
 
+    /// Given a list of indexes (as shown by `display_scans`), return their associated
+    /// FeroxScans; invalid indexes are reported and skipped
+    fn select_scans(&self, indexes: &[usize]) -> Vec<Arc<FeroxScan>> {
+        let Ok(scans) = self.scans.read() else {
+            return Vec::new();
+        };
+
+        let mut selected = Vec::new();
+
+        for num in indexes {
+            match scans.get(*num) {
+                Some(scan) => selected.push(scan.clone()),
+                None => {
+                    self.menu
+                        .println(&format!("The number {num} is not a valid choice."));
+                    sleep(Duration::from_millis(SLEEP_DURATION));
+                }
+            }
+        }
+
+        selected
+    }
+
     /// Given a list of indexes, cancel their associated FeroxScans
     async fn cancel_scans(&self, indexes: Vec<usize>, force: bool) -> usize {
         let menu_pause_duration = Duration::from_millis(SLEEP_DURATION);
@@ -411,12 +473,60 @@ impl FeroxScans {
         }
     }
 
+    /// print all directory scans waiting for a free slot under --scan-limit, in the order
+    /// they'll be started
+    fn display_queue(&self) {
+        let queued = self.queue.pending();
+
+        if queued.is_empty() {
+            return;
+        }
+
+        self.menu
+            .println(&format!("{}:", style("Queued").bright().blue()));
+
+        for (i, queued) in queued.iter().enumerate() {
+            let origin = match &queued.origin {
+                Some(origin) => format!("found under {origin}"),
+                None => String::from("user supplied"),
+            };
+
+            self.menu.println(&format!(
+                "{:3}: {} {}",
+                i + 1,
+                queued.scan.url(),
+                style(format!("({origin})")).dim()
+            ));
+        }
+
+        self.menu.print_border();
+    }
+
+    /// Given a list of queue positions, cancel the associated scans before they start
+    async fn drop_queued(&self, positions: &[usize]) -> usize {
+        let mut num_cancelled = 0_usize;
+
+        for scan in self.queue.take(positions) {
+            self.menu.println(&format!("Dropping {}...", scan.url));
+
+            scan.abort()
+                .await
+                .unwrap_or_else(|e| log::warn!("Could not cancel task: {}", e));
+
+            let pb = scan.progress_bar();
+            num_cancelled += pb.length().unwrap_or(0) as usize - pb.position() as usize;
+        }
+
+        num_cancelled
+    }
+
     /// CLI menu that allows for interactive cancellation of recursed-into directories
     async fn interactive_menu(&self, handles: Arc<Handles>) -> Option<MenuCmdResult> {
         self.menu.hide_progress_bars();
         self.menu.clear_screen();
         self.menu.print_header();
         self.display_scans().await;
+        self.display_queue();
         self.display_filters(handles.clone());
         self.menu.print_footer();
 
@@ -441,12 +551,52 @@ impl FeroxScans {
                     .unwrap_or_default();
                 None
             }
+            Some(MenuCmd::RateLimit(limit, indices)) => {
+                // picked up by each Requester before its next request
+                if indices.is_empty() {
+                    handles.runtime.set_rate_limit(limit);
+                } else {
+                    for scan in self.select_scans(&indices) {
+                        scan.set_rate_limit(limit);
+                    }
+                }
+                None
+            }
+            Some(MenuCmd::MoveQueued(indices, to_front)) => {
+                self.queue.reorder(&indices, to_front);
+                None
+            }
+            Some(MenuCmd::DropQueued(indices)) => {
+                let num_cancelled = self.drop_queued(&indices).await;
+                Some(MenuCmdResult::NumCancelled(num_cancelled))
+            }
+            Some(MenuCmd::AutoTune(enabled, indices)) => {
+                if indices.is_empty() {
+                    handles.runtime.set_auto_tune(enabled);
+                } else {
+                    let tuning = handles.runtime.policy() == RequesterPolicy::AutoTune;
+
+                    for scan in self.select_scans(&indices) {
+                        // toggling flips whatever the scan is currently doing
+                        let current = scan.overrides().auto_tune.unwrap_or(tuning);
+                        scan.set_auto_tune(enabled.unwrap_or(!current));
+                    }
+                }
+                None
+            }
             None => None,
         };
 
         self.menu.clear_screen();
 
-        let banner = Banner::new(&[handles.config.target_url.clone()], &handles.config);
+        let mut banner = Banner::new(
+            std::slice::from_ref(&handles.config.target_url),
+            &handles.config,
+        );
+        banner.add_scan_id(
+            &handles.config.request_id,
+            handles.features.request_ids.scan_id(),
+        );
         banner
             .print_to(&self.menu.term, handles.config.clone())
             .unwrap_or_default();
@@ -591,7 +741,7 @@ impl FeroxScans {
         };
 
         let bar = match scan_type {
-            ScanType::Directory => {
+            ScanType::Directory | ScanType::Crawl => {
                 let bar_type = match self.output_level {
                     OutputLevel::Default => BarType::Default,
                     OutputLevel::Quiet => BarType::Quiet,
@@ -633,6 +783,16 @@ impl FeroxScans {
         self.add_scan(&normalized, ScanType::Directory, scan_order)
     }
 
+    /// Given a url, create a new `FeroxScan` and add it to `FeroxScans` as a Crawl Scan (--crawl)
+    ///
+    /// If `FeroxScans` did not already contain the scan, return true; otherwise return false
+    ///
+    /// Also return a reference to the new `FeroxScan`
+    pub fn add_crawl_scan(&self, url: &str, scan_order: ScanOrder) -> (bool, Arc<FeroxScan>) {
+        let normalized = format!("{}/", url.trim_end_matches('/'));
+        self.add_scan(&normalized, ScanType::Crawl, scan_order)
+    }
+
     /// Given a url, create a new `FeroxScan` and add it to `FeroxScans` as a File Scan
     ///
     /// If `FeroxScans` did not already contain the scan, return true; otherwise return false
@@ -669,6 +829,142 @@ impl FeroxScans {
         scans
     }
 
+    /// human readable breakdown of the status codes seen by each directory scan, `None` when no
+    /// directory scan has seen a response
+    pub fn status_code_summary(&self) -> Option<String> {
+        let mut lines = vec![];
+
+        if let Ok(guard) = self.scans.read() {
+            for scan in guard.iter() {
+                if !matches!(scan.scan_type, ScanType::Directory | ScanType::Crawl) {
+                    continue;
+                }
+
+                let status_codes = scan.status_code_summary();
+
+                if status_codes.is_empty() {
+                    continue;
+                }
+
+                let remote_ips = scan.remote_ips();
+
+                if remote_ips.is_empty() {
+                    lines.push(format!("   {}  {status_codes}", scan.url()));
+                } else {
+                    // a load balanced target may answer from different addresses per family
+                    let remote_ips = remote_ips
+                        .iter()
+                        .map(IpAddr::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    lines.push(format!(
+                        "   {}  {status_codes}  (via {remote_ips})",
+                        scan.url()
+                    ));
+                }
+            }
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "📊 Status codes by directory\n{}",
+            lines.join("\n")
+        ))
+    }
+
+    /// number of scans cancelled by --auto-bail/--auto-requeue
+    pub fn bailed_scans(&self) -> usize {
+        self.scans
+            .read()
+            .map(|scans| scans.iter().filter(|scan| scan.is_bailed()).count())
+            .unwrap_or_default()
+    }
+
+    /// human readable breakdown of the errors seen by each directory scan, by category; `None`
+    /// when no directory scan has seen an error
+    pub fn error_summary(&self) -> Option<String> {
+        let mut lines = vec![];
+
+        if let Ok(guard) = self.scans.read() {
+            for scan in guard.iter() {
+                if !matches!(scan.scan_type, ScanType::Directory | ScanType::Crawl) {
+                    continue;
+                }
+
+                let errors = scan.error_summary();
+
+                if !errors.is_empty() {
+                    lines.push(format!("   {}  {errors}", scan.url()));
+                }
+            }
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        Some(format!("💢 Errors by directory\n{}", lines.join("\n")))
+    }
+
+    /// human readable breakdown of the status codes seen on each host, across all of its ports
+    /// and directories (--ports), `None` when no directory scan has seen a response
+    pub fn host_summary(&self) -> Option<String> {
+        // host -> (origins scanned, status code counts)
+        let mut hosts: BTreeMap<String, (Vec<String>, BTreeMap<u16, usize>)> = BTreeMap::new();
+
+        if let Ok(guard) = self.scans.read() {
+            for scan in guard.iter() {
+                if !matches!(scan.scan_type, ScanType::Directory | ScanType::Crawl) {
+                    continue;
+                }
+
+                let Ok(url) = Url::parse(scan.url()) else {
+                    continue;
+                };
+
+                let Some(host) = url.host_str() else {
+                    continue;
+                };
+
+                let (origins, status_codes) = hosts.entry(host.to_string()).or_default();
+
+                let origin = url.origin().ascii_serialization();
+
+                if !origins.contains(&origin) {
+                    origins.push(origin);
+                }
+
+                for (code, count) in scan.status_codes() {
+                    *status_codes.entry(code).or_default() += count;
+                }
+            }
+        }
+
+        let lines = hosts
+            .iter()
+            .filter(|(_, (_, status_codes))| !status_codes.is_empty())
+            .map(|(host, (origins, status_codes))| {
+                let status_codes = status_codes
+                    .iter()
+                    .map(|(code, count)| format!("{code}: {count}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("   {host} [{}]  {status_codes}", origins.join(", "))
+            })
+            .collect::<Vec<_>>();
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        Some(format!("🖥  Status codes by host\n{}", lines.join("\n")))
+    }
+
     /// given an extension, add it to `collected_extensions` if all constraints are met
     /// returns `true` if an extension was added, `false` otherwise
     pub fn add_discovered_extension(&self, extension: String) -> bool {
@@ -0,0 +1,91 @@
+use std::{collections::HashSet, sync::RwLock};
+
+use crate::url::FeroxUrl;
+
+/// Normalized urls that have already been queued or requested outside of the wordlist; saved in
+/// the state file so that a resumed scan doesn't queue the same work twice
+///
+/// every scan that's added to `FeroxScans` is recorded, as is every link the extractor requests;
+/// the extractor finds the same links on many pages, and only the first one is requested
+#[derive(Debug, Default)]
+pub struct SeenUrls {
+    /// normalized urls, as produced by `SeenUrls::normalize`
+    urls: RwLock<HashSet<String>>,
+}
+
+/// implementation of SeenUrls
+impl SeenUrls {
+    /// normalize a url for comparison, the same as `FeroxScan.normalized_url`; see
+    /// `FeroxUrl::canonicalize`
+    pub fn normalize(url: &str) -> String {
+        FeroxUrl::canonicalize(url)
+    }
+
+    /// record the given url; true when it wasn't seen before
+    pub fn insert(&self, url: &str) -> bool {
+        match self.urls.write() {
+            Ok(mut urls) => urls.insert(Self::normalize(url)),
+            Err(_) => true,
+        }
+    }
+
+    /// whether the given url was seen before
+    pub fn contains(&self, url: &str) -> bool {
+        self.urls
+            .read()
+            .is_ok_and(|urls| urls.contains(&Self::normalize(url)))
+    }
+
+    /// every url seen so far, sorted so that state files are stable
+    pub fn snapshot(&self) -> Vec<String> {
+        let mut urls: Vec<_> = match self.urls.read() {
+            Ok(urls) => urls.iter().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+
+        urls.sort();
+        urls
+    }
+
+    /// add urls loaded from a state file; they're normalized again in case they were saved by an
+    /// older version
+    pub fn extend<I: IntoIterator<Item = String>>(&self, urls: I) {
+        if let Ok(mut guard) = self.urls.write() {
+            guard.extend(urls.into_iter().map(|url| Self::normalize(&url)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// urls that only differ in case, default port, fragment, or trailing slash are the same
+    fn seen_urls_normalizes_before_comparing() {
+        let seen = SeenUrls::default();
+
+        assert!(seen.insert("http://LOCALHOST:80/js/app.js#main"));
+        assert!(!seen.insert("http://localhost/js/app.js/"));
+        assert!(seen.contains("HTTP://localhost/js/app.js"));
+
+        // the path is left as is, as is a different port
+        assert!(seen.insert("http://localhost/JS/app.js"));
+        assert!(seen.insert("http://localhost:8080/js/app.js"));
+    }
+
+    #[test]
+    /// the snapshot is sorted, and loading it back gives the same set
+    fn seen_urls_round_trip() {
+        let seen = SeenUrls::default();
+        seen.insert("http://localhost/b");
+        seen.insert("http://localhost/a");
+
+        let snapshot = seen.snapshot();
+        assert_eq!(snapshot, ["http://localhost/a/", "http://localhost/b/"]);
+
+        let loaded = SeenUrls::default();
+        loaded.extend(snapshot.clone());
+        assert_eq!(loaded.snapshot(), snapshot);
+    }
+}
@@ -1,14 +1,29 @@
 use super::*;
 use crate::filters::FeroxFilters;
-use crate::{config::Configuration, statistics::Stats, traits::FeroxSerialize, utils::fmt_err};
+use crate::{
+    config::Configuration, signatures::Signature, statistics::Stats, traits::FeroxSerialize,
+    utils::fmt_err,
+};
 use anyhow::{Context, Result};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// version of the state file format; bumped when fields are added that older versions of
+/// feroxbuster don't know about
+///
+/// - v1: scans, config, responses, statistics, collected_extensions, filters
+/// - v2: adds per-scan rate limiting/auto-tune state (FeroxScan.tuning)
+/// - v3: adds learned 404 signatures, keyed by directory (signatures)
+/// - v4: adds the normalized urls already queued or requested (seen_urls)
+const STATE_VERSION: usize = 4;
+
 /// Data container for (de)?serialization of multiple items
 #[derive(Serialize, Debug)]
 pub struct FeroxState {
+    /// state file format version
+    version: usize,
+
     /// Known scans
     scans: Arc<FeroxScans>,
 
@@ -26,6 +41,12 @@ pub struct FeroxState {
 
     /// runtime filters, as they may differ from original config
     filters: Arc<FeroxFilters>,
+
+    /// 404 signatures learned by the heuristics, reused on resume/--reuse-signatures
+    signatures: HashMap<String, Signature>,
+
+    /// urls already queued or requested outside of the wordlist, so they aren't queued again
+    seen_urls: Vec<String>,
 }
 
 /// implementation of FeroxState
@@ -37,19 +58,25 @@ impl FeroxState {
         responses: &'static FeroxResponses,
         statistics: Arc<Stats>,
         filters: Arc<FeroxFilters>,
+        signatures: HashMap<String, Signature>,
     ) -> Self {
         let collected_extensions = match scans.collected_extensions.read() {
             Ok(extensions) => extensions.clone(),
             Err(_) => HashSet::new(),
         };
 
+        let seen_urls = scans.seen.snapshot();
+
         Self {
+            version: STATE_VERSION,
             scans,
             config,
             responses,
             statistics,
             collected_extensions,
             filters,
+            signatures,
+            seen_urls,
         }
     }
 }
@@ -7,14 +7,16 @@ use crate::{
     config::{Configuration, OutputLevel},
     event_handlers::Handles,
     response::FeroxResponse,
-    scanner::RESPONSES,
-    statistics::Stats,
+    scanner::{PolicyTrigger, RESPONSES},
+    statistics::{ErrorWeights, StatError, Stats},
     traits::FeroxSerialize,
     SLEEP_DURATION, VERSION,
 };
 use indicatif::ProgressBar;
 use predicates::prelude::*;
 use regex::Regex;
+use reqwest::StatusCode;
+use std::collections::HashMap;
 use std::sync::{atomic::Ordering, Arc};
 use std::thread::sleep;
 use std::time::Instant;
@@ -25,7 +27,7 @@ use tokio::time::{self, Duration};
 fn default_scantype_is_file() {
     match ScanType::default() {
         ScanType::File => {}
-        ScanType::Directory => panic!(),
+        ScanType::Directory | ScanType::Crawl => panic!(),
     }
 }
 
@@ -140,6 +142,31 @@ fn add_url_to_list_of_scanned_urls_with_known_url_without_slash() {
     assert!(!result);
 }
 
+#[test]
+/// directory scans that differ only by default port, trailing slash, or case of the host are
+/// the same scan, and can be looked up by any of those forms
+fn add_directory_scan_suppresses_equivalent_urls() {
+    let urls = FeroxScans::default();
+
+    let (added, scan) = urls.add_directory_scan("http://LocalHost:80/js", ScanOrder::Latest);
+    assert!(added);
+
+    for url in [
+        "http://localhost/js",
+        "http://localhost/js/",
+        "HTTP://LOCALHOST/js//",
+    ] {
+        let (added, _) = urls.add_directory_scan(url, ScanOrder::Latest);
+        assert!(!added);
+        assert_eq!(urls.get_scan_by_url(url).unwrap(), scan);
+    }
+
+    assert!(urls.contains("http://localhost:80/js/#top"));
+    assert!(!urls.contains("http://localhost:8080/js/"));
+    assert!(!urls.contains("http://localhost/JS/"));
+    assert_eq!(urls.scans.read().unwrap().len(), 1);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 /// just increasing coverage, no real expectations
 async fn call_display_scans() {
@@ -220,6 +247,24 @@ fn ferox_scan_get_progress_bar_when_none_is_set() {
     assert!(!pb.is_finished()) // not finished
 }
 
+#[test]
+/// crawl scans survive a round trip through the state file, and count as active like directories
+fn ferox_scan_crawl_deserialize_and_is_active() {
+    let json = r#"{"id":"057016a14769414aac9a7a62707598cb","url":"https://spiritanimal.com/","scan_type":"Crawl","status":"NotStarted"}"#;
+
+    let scan: FeroxScan = serde_json::from_str(json).unwrap();
+    assert!(matches!(scan.scan_type, ScanType::Crawl));
+    assert!(scan.is_active());
+
+    let scans = FeroxScans::default();
+    let (added, scan) = scans.add_crawl_scan("https://spiritanimal.com", ScanOrder::Initial);
+    assert!(added);
+    assert_eq!(scan.url(), "https://spiritanimal.com/");
+    assert!(serde_json::to_string(&*scan)
+        .unwrap()
+        .contains(r#""scan_type":"Crawl""#));
+}
+
 #[test]
 /// given a JSON entry representing a FeroxScan, test that it deserializes into the proper type
 /// with the right attributes
@@ -235,12 +280,12 @@ fn ferox_scan_deserialize() {
 
     match fs.scan_type {
         ScanType::Directory => {}
-        ScanType::File => {
+        ScanType::File | ScanType::Crawl => {
             panic!();
         }
     }
     match fs_two.scan_type {
-        ScanType::Directory => {
+        ScanType::Directory | ScanType::Crawl => {
             panic!();
         }
         ScanType::File => {}
@@ -287,6 +332,237 @@ fn ferox_scan_serialize() {
     assert_eq!(fs_json, serde_json::to_string(&*fs).unwrap());
 }
 
+#[test]
+/// tuning state should survive a round trip through the state file, and be omitted when the
+/// scan was never rate limited
+fn ferox_scan_tuning_state_serialize_and_deserialize() {
+    let fs = FeroxScan::new(
+        "https://spiritanimal.com",
+        ScanType::Directory,
+        ScanOrder::Latest,
+        0,
+        OutputLevel::Default,
+        None,
+    );
+
+    assert!(!serde_json::to_string(&*fs).unwrap().contains("tuning"));
+
+    let tuning = TuningState {
+        rate_limit: 150,
+        original_rate: 400,
+        heap_position: 6,
+        errors: 12,
+        policy_triggered: true,
+    };
+    fs.set_tuning_state(tuning);
+
+    let json = serde_json::to_string(&*fs).unwrap();
+    assert!(json.contains(r#""tuning":{"rate_limit":150,"original_rate":400,"heap_position":6,"errors":12,"policy_triggered":true}"#));
+
+    let deserialized: FeroxScan = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.tuning_state(), Some(tuning));
+
+    // v1 state files have no tuning info
+    let v1: FeroxScan =
+        serde_json::from_str(r#"{"id":"1","url":"https://spiritanimal.com"}"#).unwrap();
+    assert_eq!(v1.tuning_state(), None);
+}
+
+#[test]
+/// inherited headers should survive a round trip through the state file, and be omitted when
+/// the scan has none
+fn ferox_scan_headers_serialize_and_deserialize() {
+    let fs = FeroxScan::new(
+        "https://spiritanimal.com",
+        ScanType::Directory,
+        ScanOrder::Latest,
+        0,
+        OutputLevel::Default,
+        None,
+    );
+
+    assert!(!serde_json::to_string(&*fs).unwrap().contains("headers"));
+
+    let headers = vec![
+        (String::from("Cookie"), String::from("session=abc")),
+        (String::from("X-CSRF-Token"), String::from("123")),
+    ];
+    fs.set_headers(headers.clone());
+
+    let json = serde_json::to_string(&*fs).unwrap();
+    assert!(json.contains(r#""headers":[["Cookie","session=abc"],["X-CSRF-Token","123"]]"#));
+
+    let deserialized: FeroxScan = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.headers(), headers);
+}
+
+#[test]
+/// the status code histogram should survive a round trip through the state file
+fn ferox_scan_status_codes_serialize_and_deserialize() {
+    let fs = FeroxScan::new(
+        "https://spiritanimal.com",
+        ScanType::Directory,
+        ScanOrder::Latest,
+        0,
+        OutputLevel::Default,
+        None,
+    );
+
+    fs.add_status_code(200);
+    fs.add_status_code(500);
+    fs.add_status_code(500);
+
+    let json = serde_json::to_string(&*fs).unwrap();
+    assert!(json.contains(r#""status_codes":{"200":1,"500":2}"#));
+
+    let deserialized: FeroxScan = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.status_codes(), fs.status_codes());
+    assert_eq!(deserialized.status_code_summary(), "200: 1, 500: 2");
+}
+
+#[test]
+/// status codes should be attributed to the directory scan the url belongs to, and only
+/// directory scans that have seen responses show up in the summary
+fn ferox_scans_increment_status_code_builds_histogram() {
+    let scans = FeroxScans::default();
+
+    assert!(scans.status_code_summary().is_none());
+
+    scans.add_directory_scan("http://localhost/", ScanOrder::Initial);
+    scans.add_directory_scan("http://localhost/api/", ScanOrder::Latest);
+    scans.add_directory_scan("http://localhost/empty/", ScanOrder::Latest);
+
+    scans.increment_status_code("http://localhost/index.html", StatusCode::OK);
+    scans.increment_status_code(
+        "http://localhost/api/users",
+        StatusCode::INTERNAL_SERVER_ERROR,
+    );
+    scans.increment_status_code(
+        "http://localhost/api/login",
+        StatusCode::INTERNAL_SERVER_ERROR,
+    );
+    scans.increment_status_code("http://localhost/api/admin", StatusCode::FORBIDDEN);
+
+    let api = scans.get_scan_by_url("http://localhost/api/").unwrap();
+    assert_eq!(api.status_code_summary(), "403: 1, 500: 2");
+    assert_eq!(
+        api.num_errors(PolicyTrigger::Status403, &ErrorWeights::default()),
+        1
+    );
+
+    let summary = scans.status_code_summary().unwrap();
+    assert!(summary.contains("http://localhost/  200: 1"));
+    assert!(summary.contains("http://localhost/api/  403: 1, 500: 2"));
+    assert!(!summary.contains("empty"));
+}
+
+#[test]
+/// addresses that served a scan should show up next to its status codes, and survive a trip
+/// through the state file
+fn ferox_scans_record_remote_ips() {
+    let scans = FeroxScans::default();
+    scans.add_directory_scan("http://localhost/", ScanOrder::Initial);
+
+    scans.increment_status_code("http://localhost/index.html", StatusCode::OK);
+    scans.record_remote_ip("http://localhost/index.html", "::1".parse().unwrap());
+    scans.record_remote_ip("http://localhost/login", "127.0.0.1".parse().unwrap());
+    scans.record_remote_ip("http://localhost/admin", "127.0.0.1".parse().unwrap());
+
+    let summary = scans.status_code_summary().unwrap();
+    assert!(summary.contains("http://localhost/  200: 1  (via 127.0.0.1, ::1)"));
+
+    let scan = scans.get_scan_by_url("http://localhost/").unwrap();
+    let json = serde_json::to_string(&*scan).unwrap();
+    assert!(json.contains(r#""remote_ips":["127.0.0.1","::1"]"#));
+
+    let deserialized: FeroxScan = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.remote_ips(), scan.remote_ips());
+}
+
+#[test]
+/// pinning a scan's limit turns its auto-tune off, unpinning clears both, and every change
+/// bumps the generation
+fn ferox_scan_overrides_pin_and_unpin() {
+    let scan = FeroxScan::default();
+    assert_eq!(scan.overrides(), ScanOverrides::default());
+    assert!(scan.override_summary().is_empty());
+
+    scan.set_rate_limit(50);
+    assert_eq!(scan.overrides().rate_limit, Some(50));
+    assert_eq!(scan.overrides().auto_tune, Some(false));
+    assert_eq!(scan.override_summary(), "limit: 50/s, tune: off");
+
+    scan.set_auto_tune(true);
+    assert_eq!(scan.override_summary(), "limit: 50/s, tune: on");
+
+    scan.set_rate_limit(0);
+    assert_eq!(scan.overrides().rate_limit, None);
+    assert_eq!(scan.overrides().auto_tune, None);
+    assert_eq!(scan.overrides().generation, 3);
+}
+
+#[test]
+/// errors should be broken down by category for each directory scan, survive a round trip
+/// through the state file, and only count toward the policy by their weight
+fn ferox_scans_error_summary_groups_by_category() {
+    let scans = FeroxScans::default();
+
+    assert!(scans.error_summary().is_none());
+
+    scans.add_directory_scan("http://localhost/", ScanOrder::Initial);
+    scans.add_directory_scan("http://localhost/api/", ScanOrder::Latest);
+
+    scans.increment_error("http://localhost/api/users", StatError::Dns);
+    scans.increment_error("http://localhost/api/login", StatError::Timeout);
+    scans.increment_error("http://localhost/api/admin", StatError::Timeout);
+    scans.increment_error("http://localhost/api/proxied", StatError::Proxy);
+
+    let api = scans.get_scan_by_url("http://localhost/api/").unwrap();
+    assert_eq!(api.error_summary(), "timeout: 2, dns: 1, proxy: 1");
+    // proxy errors don't count toward the policy by default
+    assert_eq!(
+        api.num_errors(PolicyTrigger::Errors, &ErrorWeights::default()),
+        3
+    );
+
+    let summary = scans.error_summary().unwrap();
+    assert!(summary.contains("http://localhost/api/  timeout: 2, dns: 1, proxy: 1"));
+    assert!(!summary.contains("http://localhost/  "));
+
+    let json = serde_json::to_string(&*api).unwrap();
+    assert!(json.contains(r#""errors":{"dns":1,"proxy":1,"timeout":2}"#));
+
+    let resumed: FeroxScan = serde_json::from_str(&json).unwrap();
+    assert_eq!(resumed.errors_by_kind(), api.errors_by_kind());
+    assert_eq!(
+        resumed.num_errors(PolicyTrigger::Errors, &ErrorWeights::default()),
+        3
+    );
+}
+
+#[test]
+/// status codes of every port and directory of a host should be added up under that host
+fn ferox_scans_host_summary_aggregates_ports() {
+    let scans = FeroxScans::default();
+
+    assert!(scans.host_summary().is_none());
+
+    scans.add_directory_scan("http://localhost/", ScanOrder::Initial);
+    scans.add_directory_scan("https://localhost:8443/", ScanOrder::Initial);
+    scans.add_directory_scan("https://localhost:8443/api/", ScanOrder::Latest);
+    scans.add_directory_scan("http://127.0.0.1/", ScanOrder::Initial);
+
+    scans.increment_status_code("http://localhost/index.html", StatusCode::OK);
+    scans.increment_status_code("https://localhost:8443/login", StatusCode::OK);
+    scans.increment_status_code("https://localhost:8443/api/users", StatusCode::FORBIDDEN);
+
+    let summary = scans.host_summary().unwrap();
+    assert!(
+        summary.contains("localhost [http://localhost, https://localhost:8443]  200: 2, 403: 1")
+    );
+    assert!(!summary.contains("127.0.0.1"));
+}
+
 #[test]
 /// given a FeroxScans, test that it serializes into the proper JSON entry
 fn ferox_scans_serialize() {
@@ -348,6 +624,27 @@ fn ferox_response_serialize_and_deserialize() {
     assert_eq!(json_response, new_json);
 }
 
+#[test]
+/// urls seen before the scan was saved are seen again once it's resumed
+fn add_serialized_scans_loads_seen_urls() {
+    let state = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(
+        state.path(),
+        r#"{"version":4,"seen_urls":["http://localhost/js/app.js/"]}"#,
+    )
+    .unwrap();
+
+    let handles = Arc::new(Handles::for_testing(None, None).0);
+    let ferox_scans = FeroxScans::default();
+
+    ferox_scans
+        .add_serialized_scans(state.path().to_str().unwrap(), handles)
+        .unwrap();
+
+    assert!(ferox_scans.seen.contains("http://localhost/js/app.js"));
+    assert!(!ferox_scans.seen.insert("http://LOCALHOST/js/app.js"));
+}
+
 #[test]
 /// test FeroxSerialize implementation of FeroxState
 fn feroxstates_feroxserialize_implementation() {
@@ -414,6 +711,7 @@ fn feroxstates_feroxserialize_implementation() {
         &RESPONSES,
         stats,
         Arc::new(filters),
+        HashMap::new(),
     );
 
     let expected_strs = predicates::str::contains("scans: FeroxScans").and(
@@ -432,6 +730,7 @@ fn feroxstates_feroxserialize_implementation() {
     println!("echo '{json_state}'|jq"); // for debugging, if the test fails, can see what's going on
 
     for expected in [
+        r#""version":4"#,
         r#""scans""#,
         &format!(r#""id":"{saved_id}""#),
         r#""url":"https://spiritanimal.com""#,
@@ -507,6 +806,8 @@ fn feroxstates_feroxserialize_implementation() {
         r#""collect_words":false"#,
         r#""filters":[{"filter_code":100},{"word_count":200},{"content_length":300},{"line_count":400},{"compiled":".*","raw_string":".*"},{"hash":1,"original_url":"http://localhost:12345/"}]"#,
         r#""collected_extensions":["php"]"#,
+        r#""signatures":{"#,
+        r#""seen_urls":["https://spiritanimal.com/"]"#,
         r#""dont_collect":["tif","tiff","ico","cur","bmp","webp","svg","png","jpg","jpeg","jfif","gif","avif","apng","pjpeg","pjp","mov","wav","mpg","mpeg","mp3","mp4","m4a","m4p","m4v","ogg","webm","ogv","oga","flac","aac","3gp","css","zip","xls","xml","gz","tgz"]"#,
     ]
     .iter()
@@ -517,6 +818,17 @@ fn feroxstates_feroxserialize_implementation() {
     }
 }
 
+#[test]
+/// time specs convert to seconds, malformed or overflowing ones don't convert at all
+fn time_spec_in_secs_converts_units() {
+    assert_eq!(time_spec_in_secs("30s"), Some(30));
+    assert_eq!(time_spec_in_secs("10M"), Some(600));
+    assert_eq!(time_spec_in_secs("2h"), Some(7200));
+    assert_eq!(time_spec_in_secs("1d"), Some(86400));
+    assert_eq!(time_spec_in_secs("10"), None);
+    assert_eq!(time_spec_in_secs("18446744073709551615m"), None);
+}
+
 #[should_panic]
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 /// call start_max_time_thread with a valid timespec, expect a panic, but only after a certain
@@ -566,15 +878,28 @@ fn feroxscan_display() {
         scan_order: ScanOrder::Latest,
         scan_type: Default::default(),
         num_requests: 0,
-        requests_made_so_far: 0,
+        requests_made_so_far: Default::default(),
         start_time: Instant::now(),
+        rate: Default::default(),
+        limit_gauge: Default::default(),
+        latency: Default::default(),
         output_level: OutputLevel::Default,
-        status_403s: Default::default(),
-        status_429s: Default::default(),
         status: Default::default(),
         task: tokio::sync::Mutex::new(None),
         progress_bar: std::sync::Mutex::new(None),
-        errors: Default::default(),
+        status_codes: Default::default(),
+        error_kinds: Default::default(),
+        server_changes: Default::default(),
+        remote_ips: Default::default(),
+        tuning: Default::default(),
+        overrides: Default::default(),
+        requeues: Default::default(),
+        requeue_mark: Default::default(),
+        bailed: Default::default(),
+        runtime: Default::default(),
+        tuning_events: Default::default(),
+        headers: Default::default(),
+        directory_pass: Default::default(),
     };
 
     let not_started = format!("{scan}");
@@ -600,6 +925,12 @@ fn feroxscan_display() {
     assert!(predicate::str::contains("running")
         .and(predicate::str::contains("localhost"))
         .eval(&running));
+
+    scan.set_status(ScanStatus::Requeued).unwrap();
+    let requeued = format!("{scan}");
+    assert!(predicate::str::contains("requeued")
+        .and(predicate::str::contains("localhost"))
+        .eval(&requeued));
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -612,17 +943,30 @@ async fn ferox_scan_abort() {
         scan_order: ScanOrder::Latest,
         scan_type: Default::default(),
         num_requests: 0,
-        requests_made_so_far: 0,
+        requests_made_so_far: Default::default(),
         start_time: Instant::now(),
+        rate: Default::default(),
+        limit_gauge: Default::default(),
+        latency: Default::default(),
         output_level: OutputLevel::Default,
-        status_403s: Default::default(),
-        status_429s: Default::default(),
         status: std::sync::Mutex::new(ScanStatus::Running),
         task: tokio::sync::Mutex::new(Some(tokio::spawn(async move {
             sleep(Duration::from_millis(SLEEP_DURATION * 2));
         }))),
         progress_bar: std::sync::Mutex::new(None),
-        errors: Default::default(),
+        status_codes: Default::default(),
+        error_kinds: Default::default(),
+        server_changes: Default::default(),
+        remote_ips: Default::default(),
+        tuning: Default::default(),
+        overrides: Default::default(),
+        requeues: Default::default(),
+        requeue_mark: Default::default(),
+        bailed: Default::default(),
+        runtime: Default::default(),
+        tuning_events: Default::default(),
+        headers: Default::default(),
+        directory_pass: Default::default(),
     };
 
     scan.abort().await.unwrap();
@@ -633,6 +977,92 @@ async fn ferox_scan_abort() {
     ));
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// requeueing a scan should keep it active, remember its position, and stop holding earlier
+/// errors against it; aborting a requeued scan should cancel it
+async fn ferox_scan_requeue_and_abort() {
+    let scan = FeroxScan::new(
+        "http://localhost",
+        ScanType::Directory,
+        ScanOrder::Latest,
+        1000,
+        OutputLevel::Default,
+        None,
+    );
+    scan.set_status(ScanStatus::Running).unwrap();
+    scan.progress_bar().set_position(100);
+
+    for _ in 0..40 {
+        scan.add_status_code(429);
+    }
+    scan.add_error(StatError::Timeout);
+    scan.add_server_change();
+
+    scan.requeue().unwrap();
+
+    assert!(scan.is_requeued());
+    assert!(scan.is_active());
+    assert_eq!(scan.requeues(), 1);
+    assert_eq!(scan.requests_made_so_far(), 100);
+    assert_eq!(scan.requests_since_requeue(), 0);
+    assert_eq!(
+        scan.num_errors(PolicyTrigger::Status429, &ErrorWeights::default()),
+        0
+    );
+    assert_eq!(
+        scan.num_errors(PolicyTrigger::Errors, &ErrorWeights::default()),
+        0
+    );
+    assert_eq!(
+        scan.num_errors(PolicyTrigger::ServerChange, &ErrorWeights::default()),
+        0
+    );
+
+    // totals are kept for the status code summary
+    assert_eq!(scan.num_status_code(429), 40);
+
+    // the restarted scan's bar picks up where the old one left off
+    let pb = scan.progress_bar();
+    assert_eq!(pb.position(), 100);
+    pb.inc(10);
+    scan.add_status_code(429);
+
+    assert_eq!(scan.requests_since_requeue(), 10);
+    assert_eq!(
+        scan.num_errors(PolicyTrigger::Status429, &ErrorWeights::default()),
+        1
+    );
+
+    scan.abort().await.unwrap();
+    assert!(scan.is_cancelled());
+    assert!(!scan.is_active());
+}
+
+#[test]
+/// a scan waiting on --scan-limit is still active, and comes back pending from a state file
+fn ferox_scan_pending_is_active_and_round_trips() {
+    let scan = FeroxScan::new(
+        "http://localhost/pending/",
+        ScanType::Directory,
+        ScanOrder::Latest,
+        1000,
+        OutputLevel::Default,
+        None,
+    );
+    scan.set_status(ScanStatus::Pending).unwrap();
+
+    assert!(scan.is_pending());
+    assert!(scan.is_active());
+    assert!(scan.to_string().contains("pending"));
+
+    let json = serde_json::to_string(&*scan).unwrap();
+    let deserialized: FeroxScan = serde_json::from_str(&json).unwrap();
+    assert!(deserialized.is_pending());
+
+    scan.set_status(ScanStatus::Running).unwrap();
+    assert!(!scan.is_pending());
+}
+
 #[test]
 /// call a few menu functions for coverage's sake
 ///
@@ -699,6 +1129,80 @@ fn menu_get_command_input_from_user_returns_add() {
     }
 }
 
+/// ensure command parsing from user input results int he correct MenuCmd returned
+#[test]
+fn menu_get_command_input_from_user_returns_rate_limit() {
+    let menu = Menu::new();
+
+    for cmd in ["limit", "Limit", "l", "L"] {
+        let result = menu.get_command_input_from_user(&format!("{cmd} 100\n"));
+        assert!(matches!(result, Some(MenuCmd::RateLimit(100, scans)) if scans.is_empty()));
+    }
+
+    assert!(matches!(
+        menu.get_command_input_from_user("l 0"),
+        Some(MenuCmd::RateLimit(0, _))
+    ));
+    assert!(matches!(
+        menu.get_command_input_from_user("limit 50 3,5"),
+        Some(MenuCmd::RateLimit(50, scans)) if scans == vec![3, 5]
+    ));
+    assert!(menu.get_command_input_from_user("limit").is_none());
+    assert!(menu.get_command_input_from_user("limit fast").is_none());
+}
+
+/// ensure command parsing from user input results int he correct MenuCmd returned
+#[test]
+fn menu_get_command_input_from_user_returns_auto_tune() {
+    let menu = Menu::new();
+
+    for cmd in ["tune", "Tune", "t", "T"] {
+        let result = menu.get_command_input_from_user(&format!("{cmd}\n"));
+        assert!(matches!(result, Some(MenuCmd::AutoTune(None, scans)) if scans.is_empty()));
+    }
+
+    assert!(matches!(
+        menu.get_command_input_from_user("tune ON"),
+        Some(MenuCmd::AutoTune(Some(true), _))
+    ));
+    assert!(matches!(
+        menu.get_command_input_from_user("t off"),
+        Some(MenuCmd::AutoTune(Some(false), _))
+    ));
+    assert!(matches!(
+        menu.get_command_input_from_user("t off 2 4"),
+        Some(MenuCmd::AutoTune(Some(false), scans)) if scans == vec![2, 4]
+    ));
+    assert!(matches!(
+        menu.get_command_input_from_user("tune 1-3"),
+        Some(MenuCmd::AutoTune(None, scans)) if scans == vec![1, 2, 3]
+    ));
+    assert!(menu.get_command_input_from_user("tune maybe").is_none());
+}
+
+/// ensure command parsing from user input results int he correct MenuCmd returned
+#[test]
+fn menu_get_command_input_from_user_returns_queue_commands() {
+    let menu = Menu::new();
+
+    for cmd in ["queue", "Queue", "q", "Q"] {
+        let result = menu.get_command_input_from_user(&format!("{cmd} first 3\n"));
+        assert!(matches!(result, Some(MenuCmd::MoveQueued(ids, true)) if ids == vec![3]));
+    }
+
+    assert!(matches!(
+        menu.get_command_input_from_user("q LAST 1,2"),
+        Some(MenuCmd::MoveQueued(ids, false)) if ids == vec![1, 2]
+    ));
+    assert!(matches!(
+        menu.get_command_input_from_user("q drop 1-3"),
+        Some(MenuCmd::DropQueued(ids)) if ids == vec![1, 2, 3]
+    ));
+    assert!(menu.get_command_input_from_user("q").is_none());
+    assert!(menu.get_command_input_from_user("q drop").is_none());
+    assert!(menu.get_command_input_from_user("q shuffle 1").is_none());
+}
+
 #[test]
 /// ensure spaces are trimmed and numbers are returned from split_to_nums
 fn split_to_nums_is_correct() {
@@ -779,3 +1283,36 @@ fn get_base_scan_by_url_finds_correct_scan_with_trailing_slash() {
         scan.id
     );
 }
+
+#[test]
+/// host tuning is kept per host and port, and only for states auto-tune had a hand in
+fn host_tuning_records_and_inherits_per_host() {
+    let host_tuning = HostTuning::default();
+
+    let tuned = TuningState {
+        rate_limit: 150,
+        original_rate: 400,
+        heap_position: 6,
+        errors: 12,
+        policy_triggered: true,
+    };
+
+    // set by hand (i.e. the interactive menu), not auto-tune
+    host_tuning.record(
+        "http://localhost/",
+        TuningState {
+            rate_limit: 10,
+            ..Default::default()
+        },
+    );
+    assert_eq!(host_tuning.inherit("http://localhost/admin/"), None);
+
+    host_tuning.record("http://localhost/", tuned);
+    assert_eq!(
+        host_tuning.inherit("http://LOCALHOST:80/admin/"),
+        Some(tuned)
+    );
+    assert_eq!(host_tuning.inherit("http://localhost:8080/admin/"), None);
+    assert_eq!(host_tuning.inherit("https://localhost/admin/"), None);
+    assert_eq!(host_tuning.inherit("not a url"), None);
+}
@@ -0,0 +1,49 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use reqwest::Url;
+
+use super::TuningState;
+use crate::url::FeroxUrl;
+
+/// Latest auto-tune state reached on each host, so that a scan started later on the same host
+/// (i.e. by recursion) starts out at the rate its parent was tuned down to, instead of starting
+/// unlimited and running into the same wall of 429s all over again
+///
+/// only kept for the current run; a resumed scan gets its own state back from the state file
+#[derive(Debug, Default)]
+pub struct HostTuning {
+    /// latest tuning state seen on each host, keyed by host:port
+    hosts: Mutex<HashMap<String, TuningState>>,
+}
+
+/// implementation of HostTuning
+impl HostTuning {
+    /// record the tuning state a scan of the given url just reached; only states that auto-tune
+    /// had a hand in are kept, including the one where it lifted its limit again
+    pub fn record(&self, url: &str, state: TuningState) {
+        if !state.policy_triggered {
+            return;
+        }
+
+        let Ok(url) = Url::parse(url) else {
+            return;
+        };
+        let key = FeroxUrl::host_key(&url);
+
+        if let Ok(mut hosts) = self.hosts.lock() {
+            hosts.insert(key, state);
+        }
+    }
+
+    /// the tuning state a new scan of the given url should start out with; `None` when its host
+    /// was never tuned, or auto-tune has since lifted the limit
+    pub fn inherit(&self, url: &str) -> Option<TuningState> {
+        let key = FeroxUrl::host_key(&Url::parse(url).ok()?);
+        let hosts = self.hosts.lock().ok()?;
+
+        hosts
+            .get(&key)
+            .copied()
+            .filter(|state| state.rate_limit > 0)
+    }
+}
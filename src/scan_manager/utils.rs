@@ -15,21 +15,9 @@ pub async fn start_max_time_thread(handles: Arc<Handles>) {
     log::trace!("enter: start_max_time_thread({:?})", handles);
 
     // as this function has already made it through the parser, which calls is_match on
-    // the value passed to --time-limit using TIMESPEC_REGEX; we can safely assume that
-    // the capture groups are populated; can expect something like 10m, 30s, 1h, etc...
-    let captures = TIMESPEC_REGEX.captures(&handles.config.time_limit).unwrap();
-    let length_match = captures.get(1).unwrap();
-    let measurement_match = captures.get(2).unwrap();
-
-    if let Ok(length) = length_match.as_str().parse::<u64>() {
-        let length_in_secs = match measurement_match.as_str().to_ascii_lowercase().as_str() {
-            "s" => length,
-            "m" => length * 60,           // minutes
-            "h" => length * 60 * 60,      // hours
-            "d" => length * 60 * 60 * 24, // days
-            _ => length,
-        };
-
+    // the value passed to --time-limit using TIMESPEC_REGEX; can expect something like 10m,
+    // 30s, 1h, etc...
+    if let Some(length_in_secs) = time_spec_in_secs(&handles.config.time_limit) {
         log::debug!(
             "max time limit as string: {} and as seconds: {}",
             handles.config.time_limit,
@@ -52,6 +40,22 @@ pub async fn start_max_time_thread(handles: Arc<Handles>) {
     );
 }
 
+/// Given a string representing some number of seconds, minutes, hours, or days (i.e. 30s, 10m),
+/// convert that representation to seconds; `None` when the spec is malformed
+pub fn time_spec_in_secs(time_spec: &str) -> Option<u64> {
+    let captures = TIMESPEC_REGEX.captures(time_spec)?;
+    let length: u64 = captures.name("n")?.as_str().parse().ok()?;
+
+    let multiplier = match captures.name("m")?.as_str().to_ascii_lowercase().as_str() {
+        "m" => 60,           // minutes
+        "h" => 60 * 60,      // hours
+        "d" => 60 * 60 * 24, // days
+        _ => 1,
+    };
+
+    length.checked_mul(multiplier)
+}
+
 /// Primary logic used to load a Configuration from disk and populate the appropriate data
 /// structures
 pub fn resume_scan(filename: &str) -> Configuration {
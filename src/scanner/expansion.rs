@@ -0,0 +1,185 @@
+use uuid::Uuid;
+
+use crate::scan_manager::ScanPhase;
+
+/// Order in which a word's variants (the word itself, then each extension) are requested
+/// (--extension-order)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ExtensionOrder {
+    /// word, word.ext1 ... word.extN, then the next word
+    #[default]
+    Sequential,
+
+    /// each word of a chunk, then each word of the chunk with ext1, and so on
+    Interleaved,
+
+    /// every word/variant combination of a chunk, in random order
+    Shuffled,
+}
+
+/// implementation of ExtensionOrder
+impl ExtensionOrder {
+    /// parse the value given to --extension-order, anything unknown is sequential
+    pub fn from_config(order: &str) -> Self {
+        match order {
+            "interleaved" => Self::Interleaved,
+            "shuffled" => Self::Shuffled,
+            _ => Self::Sequential,
+        }
+    }
+}
+
+/// order in which a chunk of words is requested, given the number of variants each word has
+///
+/// sequential requests keep every variant of a word together (`None`); otherwise each variant
+/// is requested on its own, so that a word's variants are spread over the whole chunk instead
+/// of hitting the same path back to back
+pub(super) fn order_chunk(
+    words: &[String],
+    variants: usize,
+    order: ExtensionOrder,
+) -> Vec<(String, Option<usize>)> {
+    if order == ExtensionOrder::Sequential {
+        return words.iter().map(|word| (word.clone(), None)).collect();
+    }
+
+    let mut ordered: Vec<_> = (0..variants)
+        .flat_map(|variant| words.iter().map(move |word| (word.clone(), Some(variant))))
+        .collect();
+
+    if order == ExtensionOrder::Shuffled {
+        ordered.sort_by_cached_key(|_| Uuid::new_v4().as_u128());
+    }
+
+    ordered
+}
+
+/// same as `order_chunk`, limited to the variants requested during the given phase
+/// (--directories-first)
+///
+/// sequential order still keeps the phase's variants of a word together, but they're each
+/// requested on their own, the same as the other orders
+pub(super) fn order_phase(
+    words: &[String],
+    variants: usize,
+    order: ExtensionOrder,
+    phase: ScanPhase,
+) -> Vec<(String, Option<usize>)> {
+    let Some(range) = phase.variants(variants) else {
+        return order_chunk(words, variants, order);
+    };
+
+    if order == ExtensionOrder::Sequential {
+        return words
+            .iter()
+            .flat_map(|word| {
+                range
+                    .clone()
+                    .map(move |variant| (word.clone(), Some(variant)))
+            })
+            .collect();
+    }
+
+    order_chunk(words, variants, order)
+        .into_iter()
+        .filter(|(_, variant)| variant.is_some_and(|variant| range.contains(&variant)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// words used by the tests below
+    fn words() -> Vec<String> {
+        vec![String::from("one"), String::from("two")]
+    }
+
+    #[test]
+    /// sequential leaves each word whole, interleaved walks the chunk once per variant
+    fn order_chunk_sequential_and_interleaved() {
+        assert_eq!(
+            order_chunk(&words(), 3, ExtensionOrder::Sequential),
+            [(String::from("one"), None), (String::from("two"), None)]
+        );
+
+        let interleaved: Vec<_> = order_chunk(&words(), 3, ExtensionOrder::Interleaved)
+            .into_iter()
+            .map(|(word, variant)| format!("{word}:{}", variant.unwrap()))
+            .collect();
+
+        assert_eq!(
+            interleaved,
+            ["one:0", "two:0", "one:1", "two:1", "one:2", "two:2"]
+        );
+    }
+
+    #[test]
+    /// shuffled requests every combination exactly once
+    fn order_chunk_shuffled_covers_every_combination() {
+        let mut shuffled = order_chunk(&words(), 3, ExtensionOrder::Shuffled);
+        shuffled.sort();
+
+        let mut interleaved = order_chunk(&words(), 3, ExtensionOrder::Interleaved);
+        interleaved.sort();
+
+        assert_eq!(shuffled, interleaved);
+    }
+
+    #[test]
+    /// unknown values fall back to sequential
+    fn extension_order_from_config() {
+        assert_eq!(
+            ExtensionOrder::from_config("interleaved"),
+            ExtensionOrder::Interleaved
+        );
+        assert_eq!(
+            ExtensionOrder::from_config("shuffled"),
+            ExtensionOrder::Shuffled
+        );
+        assert_eq!(ExtensionOrder::from_config(""), ExtensionOrder::Sequential);
+    }
+
+    #[test]
+    /// each phase only gets its own variants, in the requested order
+    fn order_phase_limits_variants() {
+        let render = |ordered: Vec<(String, Option<usize>)>| -> Vec<String> {
+            ordered
+                .into_iter()
+                .map(|(word, variant)| format!("{word}:{}", variant.unwrap()))
+                .collect()
+        };
+
+        assert_eq!(
+            render(order_phase(
+                &words(),
+                3,
+                ExtensionOrder::Sequential,
+                ScanPhase::Directories
+            )),
+            ["one:0", "two:0"]
+        );
+        assert_eq!(
+            render(order_phase(
+                &words(),
+                3,
+                ExtensionOrder::Sequential,
+                ScanPhase::Files
+            )),
+            ["one:1", "one:2", "two:1", "two:2"]
+        );
+        assert_eq!(
+            render(order_phase(
+                &words(),
+                3,
+                ExtensionOrder::Interleaved,
+                ScanPhase::Files
+            )),
+            ["one:1", "two:1", "one:2", "two:2"]
+        );
+        assert_eq!(
+            order_phase(&words(), 3, ExtensionOrder::Sequential, ScanPhase::All),
+            order_chunk(&words(), 3, ExtensionOrder::Sequential)
+        );
+    }
+}
@@ -1,34 +1,46 @@
+use std::collections::HashSet;
 use std::fmt::Write as _;
 use std::sync::atomic::AtomicBool;
-use std::{ops::Deref, sync::atomic::Ordering, sync::Arc, time::Instant};
+use std::{sync::atomic::Ordering, sync::Arc, time::Instant};
 
 use anyhow::{bail, Result};
 use console::style;
 use futures::{stream, StreamExt};
 use indicatif::ProgressBar;
 use lazy_static::lazy_static;
-use tokio::sync::Semaphore;
+use tokio::{
+    sync::{AcquireError, Semaphore, SemaphorePermit},
+    task::JoinHandle,
+};
 
-use crate::filters::{create_similarity_filter, EmptyFilter, SimilarityFilter};
-use crate::heuristics::WildcardResult;
+use crate::filters::{create_similarity_filter, EmptyFilter, SimilarityFilter, SIM_HASHER};
+use crate::heuristics::{WildcardResult, SPA_API_WORDS};
 use crate::Command::AddFilter;
 use crate::{
+    config::OutputLevel,
     event_handlers::{
         Command::{AddError, AddToF64Field, AddToUsizeField, SubtractFromUsizeField},
         Handles,
     },
     extractor::{ExtractionTarget, ExtractorBuilder},
     heuristics,
-    scan_manager::{FeroxResponses, FeroxScans, MenuCmdResult, ScanOrder, ScanStatus, PAUSE_SCAN},
+    nlp::preprocess,
+    progress::PROGRESS_PRINTER,
+    response::FeroxResponse,
+    scan_manager::{
+        FeroxResponses, FeroxScan, FeroxScans, MenuCmdResult, RecursionStrategy, ScanOrder,
+        ScanPhase, ScanStatus, ScanType, SeenUrls, PAUSE_SCAN,
+    },
     scanner::requester::TF_IDF,
     statistics::{
         StatError::Other,
         StatField::{DirScanTimes, TotalExpected},
     },
-    utils::fmt_err,
+    utils::{ferox_print, fmt_err, parse_url_with_raw_path},
     Command,
 };
 
+use super::expansion::{order_phase, ExtensionOrder};
 use super::requester::Requester;
 
 lazy_static! {
@@ -59,13 +71,11 @@ async fn check_for_user_input(
                     .send_scan_command(Command::ScanNewUrl(url))
                     .unwrap_or_else(|e| log::warn!("Could not add scan to scan queue: {}", e))
             }
-            Some(MenuCmdResult::NumCancelled(num_canx)) => {
-                if num_canx > 0 {
-                    handles
-                        .stats
-                        .send(SubtractFromUsizeField(TotalExpected, num_canx))
-                        .unwrap_or_else(|e| log::warn!("Could not update overall scan bar: {}", e));
-                }
+            Some(MenuCmdResult::NumCancelled(num_canx)) if num_canx > 0 => {
+                handles
+                    .stats
+                    .send(SubtractFromUsizeField(TotalExpected, num_canx))
+                    .unwrap_or_else(|e| log::warn!("Could not update overall scan bar: {}", e));
             }
             Some(MenuCmdResult::Filter(mut filter)) => {
                 let url = if let Some(SimilarityFilter { original_url, .. }) =
@@ -150,11 +160,33 @@ impl FeroxScanner {
         progress_bar: ProgressBar,
         scanned_urls: Arc<FeroxScans>,
         requester: Arc<Requester>,
+        phase: ScanPhase,
     ) {
         log::trace!("enter: stream_requests(params too verbose to print)");
 
-        let producers = stream::iter(looping_words.deref().to_owned())
-            .map(|word| {
+        // --extension-order; templated scans only have a single variant per word
+        let order = if self.handles.features.template.is_active() {
+            ExtensionOrder::Sequential
+        } else {
+            ExtensionOrder::from_config(&self.handles.config.extension_order)
+        };
+
+        // a chunk as large as the number of threads means a word's variants are never in flight
+        // at the same time
+        let chunk_size = self.handles.config.threads.max(1);
+        let handles = self.handles.clone();
+
+        let items = looping_words.chunks(chunk_size).flat_map(move |chunk| {
+            // extensions collected along the way are picked up chunk by chunk, the same as
+            // they're picked up word by word when requested sequentially
+            let variants = 1 + handles.config.extensions.len() + handles.num_collected_extensions();
+            order_phase(chunk, variants, order, phase)
+        });
+
+        let methods = self.handles.config.methods.len().max(1) as u64;
+
+        let producers = stream::iter(items)
+            .map(|(word, variant)| {
                 let pb = progress_bar.clone(); // progress bar is an Arc around internal state
                 let scanned_urls_clone = scanned_urls.clone();
                 let requester_clone = requester.clone();
@@ -167,26 +199,38 @@ impl FeroxScanner {
 
                         // after checking for user input, send the request
                         requester_clone
-                            .request(&word)
+                            .request(&word, variant)
                             .await
-                            .unwrap_or_else(|e| log::warn!("Requester encountered an error: {}", e))
+                            .map_err(|e| log::warn!("Requester encountered an error: {}", e))
+                            .ok()
                     }),
                     pb,
+                    variant,
                 )
             })
-            .for_each_concurrent(self.handles.config.threads, |(resp, bar)| async move {
-                match resp.await {
-                    Ok(_) => {
-                        let increment_len = self.handles.expected_num_requests_multiplier() as u64;
-                        bar.inc(increment_len);
-                    }
-                    Err(e) => {
-                        log::warn!("error awaiting a response: {}", e);
-                        self.handles.stats.send(AddError(Other)).unwrap_or_default();
-                        std::process::exit(1);
+            .for_each_concurrent(
+                self.handles.config.threads,
+                |(resp, bar, variant)| async move {
+                    match resp.await {
+                        Ok(num_urls) => {
+                            let increment_len = match (num_urls, variant) {
+                                (Some(num_urls), _) => num_urls as u64 * methods,
+                                // the requester bailed, count what it was expected to request
+                                (None, None) => {
+                                    self.handles.expected_num_requests_multiplier() as u64
+                                }
+                                (None, Some(_)) => methods,
+                            };
+                            bar.inc(increment_len);
+                        }
+                        Err(e) => {
+                            log::warn!("error awaiting a response: {}", e);
+                            self.handles.stats.send(AddError(Other)).unwrap_or_default();
+                            std::process::exit(1);
+                        }
                     }
-                }
-            });
+                },
+            );
 
         // await tx tasks
         log::trace!("awaiting scan producers");
@@ -195,6 +239,201 @@ impl FeroxScanner {
         log::trace!("exit: stream_requests");
     }
 
+    /// --crawl; request the target, then every in-scope link found along the way, one level of
+    /// links at a time, instead of the wordlist
+    ///
+    /// links are followed up to --depth levels away from the target (0 is no limit); what's
+    /// listed in robots.txt is requested alongside the target of an initial scan
+    async fn crawl(&self, ferox_scan: Arc<FeroxScan>, scanned_urls: Arc<FeroxScans>) -> Result<()> {
+        log::trace!("enter: crawl({})", self.target_url);
+
+        let progress_bar = ferox_scan.progress_bar();
+
+        // the bar was sized for the wordlist, a crawl only expects the pages found so far; a
+        // resumed crawl starts over from the target
+        self.handles.stats.send(SubtractFromUsizeField(
+            TotalExpected,
+            progress_bar.length().unwrap_or(0) as usize,
+        ))?;
+        progress_bar.reset();
+        progress_bar.set_length(0);
+
+        let mut frontier = vec![self.target_url.clone()];
+
+        if matches!(self.order, ScanOrder::Initial) {
+            let extractor = ExtractorBuilder::default()
+                .target(ExtractionTarget::RobotsTxt)
+                .url(&self.target_url)
+                .handles(self.handles.clone())
+                .build()?;
+
+            let mut robots: Vec<_> = extractor.extract().await?.into_iter().collect();
+            robots.sort();
+            frontier.extend(robots);
+        }
+
+        let mut visited = HashSet::new();
+        frontier.retain(|url| visited.insert(SeenUrls::normalize(url)));
+
+        let requester = Arc::new(Requester::from(self, ferox_scan.clone())?);
+        let max_depth = self.handles.config.depth;
+        let mut depth = 0;
+
+        while !frontier.is_empty() {
+            // links found on the last level allowed by --depth aren't requested, so they aren't
+            // extracted at all
+            let follow = max_depth == 0 || depth < max_depth;
+
+            progress_bar.inc_length(frontier.len() as u64);
+            self.handles
+                .stats
+                .send(AddToUsizeField(TotalExpected, frontier.len()))?;
+
+            let found: Vec<HashSet<String>> = stream::iter(frontier)
+                .map(|url| {
+                    let requester = requester.clone();
+                    let scanned_urls = scanned_urls.clone();
+                    let handles = self.handles.clone();
+                    let bar = progress_bar.clone();
+
+                    async move {
+                        check_for_user_input(&PAUSE_SCAN, scanned_urls, handles).await;
+
+                        let links = requester.crawl(&url, follow).await.unwrap_or_else(|e| {
+                            log::warn!("Could not crawl {}: {}", url, e);
+                            HashSet::new()
+                        });
+
+                        bar.inc(1);
+                        links
+                    }
+                })
+                .buffer_unordered(self.handles.config.threads.max(1))
+                .collect()
+                .await;
+
+            // sorted, so that the same pages are requested in the same order every time
+            let mut next: Vec<_> = found.into_iter().flatten().collect();
+            next.sort();
+            next.retain(|url| visited.insert(SeenUrls::normalize(url)));
+
+            frontier = next;
+            depth += 1;
+        }
+
+        log::trace!("exit: crawl -> {} pages", visited.len());
+        Ok(())
+    }
+
+    /// a single-page app answers every path with its index page; switch strategies for it:
+    /// - the index page is filtered, so that the catch-all responses aren't reported
+    /// - the scripts the index page loads are requested (and extracted from) right away, that's
+    ///   where an spa's routes and api endpoints live
+    /// - common api paths are requested ahead of the wordlist
+    ///
+    /// returns the task requesting the scripts, and the api paths that aren't in the wordlist
+    async fn single_page_app(
+        &self,
+        index: FeroxResponse,
+    ) -> Result<(Option<JoinHandle<()>>, Vec<String>)> {
+        log::trace!("enter: single_page_app({})", index);
+
+        let hash = SIM_HASHER.create_signature(preprocess(index.text()).iter());
+
+        self.handles
+            .filters
+            .send(AddFilter(Box::new(SimilarityFilter {
+                hash,
+                original_url: index.url().to_string(),
+            })))?;
+
+        let mut num_scripts = 0;
+
+        let task = if self.handles.config.extract_links {
+            let mut extractor = ExtractorBuilder::default()
+                .target(ExtractionTarget::ResponseBody)
+                .response(&index)
+                .handles(self.handles.clone())
+                .url(&self.target_url)
+                .build()?;
+
+            let scripts: HashSet<_> = extractor
+                .extract()
+                .await?
+                .into_iter()
+                .filter(|link| {
+                    parse_url_with_raw_path(link).is_ok_and(|url| url.path().ends_with(".js"))
+                })
+                .collect();
+
+            num_scripts = scripts.len();
+            extractor.request_links(scripts).await?
+        } else {
+            None
+        };
+
+        let words: Vec<_> = SPA_API_WORDS
+            .iter()
+            .filter(|word| !self.wordlist.iter().any(|known| known == *word))
+            .map(|word| word.to_string())
+            .collect();
+
+        if matches!(
+            self.handles.config.output_level,
+            OutputLevel::Default | OutputLevel::Quiet
+        ) {
+            ferox_print(
+                &format!(
+                    "🧩 {} detected at {}: filtering its catch-all page, requesting {} scripts and {} api paths first",
+                    style("Single-page app").bright().green(),
+                    self.target_url,
+                    num_scripts,
+                    words.len()
+                ),
+                &PROGRESS_PRINTER,
+            );
+        }
+
+        log::trace!("exit: single_page_app -> {} api words", words.len());
+        Ok((task, words))
+    }
+
+    /// wait for a slot to run the scan in
+    ///
+    /// When acquire is called and the semaphore has remaining permits, the function immediately
+    /// returns a permit. However, if no remaining permits are available, acquire (asynchronously)
+    /// waits until an outstanding permit is dropped, at which point, the freed permit is assigned
+    /// to the caller.
+    ///
+    /// with --scan-limit, waiting scans line up in FeroxScans' queue instead, where they can
+    /// be reordered or dropped from the interactive menu before they start
+    async fn acquire_slot(
+        &self,
+        ferox_scan: &Arc<FeroxScan>,
+        scanned_urls: &FeroxScans,
+    ) -> Result<SemaphorePermit<'_>, AcquireError> {
+        if self.handles.config.scan_limit == 0 {
+            return self.scan_limiter.acquire().await;
+        }
+
+        let origin = match self.order {
+            ScanOrder::Initial => None,
+            ScanOrder::Latest => scanned_urls
+                .get_base_scan_by_url(self.target_url.trim_end_matches('/'))
+                .map(|scan| scan.url().to_string()),
+        };
+
+        scanned_urls
+            .queue
+            .acquire(
+                ferox_scan,
+                origin,
+                RecursionStrategy::from_config(&self.handles.config.recursion_strategy),
+                &self.scan_limiter,
+            )
+            .await
+    }
+
     /// Scan a given url using a given wordlist
     ///
     /// This is the primary entrypoint for the scanner
@@ -207,8 +446,12 @@ impl FeroxScanner {
         // it completes before the scan ends
         let mut extraction_tasks = Vec::new();
 
-        if self.handles.config.extract_links && matches!(self.order, ScanOrder::Initial) {
-            // check for robots.txt (cannot be in sub-directories, so limited to Initial)
+        if self.handles.config.extract_links
+            && !self.handles.config.crawl
+            && matches!(self.order, ScanOrder::Initial)
+        {
+            // check for robots.txt (cannot be in sub-directories, so limited to Initial); a
+            // crawl requests what's in there itself
             let mut extractor = ExtractorBuilder::default()
                 .target(ExtractionTarget::RobotsTxt)
                 .url(&self.target_url)
@@ -219,12 +462,21 @@ impl FeroxScanner {
             extraction_tasks.push(extractor.request_links(result).await?)
         }
 
+        if self.handles.config.well_known && matches!(self.order, ScanOrder::Initial) {
+            // --well-known; /.well-known/ only lives at the root of a host, so limited to Initial
+            let mut extractor = ExtractorBuilder::default()
+                .target(ExtractionTarget::WellKnown)
+                .url(&self.target_url)
+                .handles(self.handles.clone())
+                .build()?;
+
+            let result = extractor.extract().await?;
+            extraction_tasks.push(extractor.request_links(result).await?)
+        }
+
         let scanned_urls = self.handles.ferox_scans()?;
         let ferox_scan = match scanned_urls.get_scan_by_url(&self.target_url) {
-            Some(scan) => {
-                scan.set_status(ScanStatus::Running)?;
-                scan
-            }
+            Some(scan) => scan,
             None => {
                 let msg = format!(
                     "Could not find FeroxScan associated with {}; this shouldn't happen... exiting",
@@ -234,77 +486,102 @@ impl FeroxScanner {
             }
         };
 
+        // with --scan-limit, the scan may have to wait for a free slot
+        let waiting = self.handles.config.scan_limit > 0;
+
         let progress_bar = ferox_scan.progress_bar();
 
-        // When acquire is called and the semaphore has remaining permits, the function immediately
-        // returns a permit. However, if no remaining permits are available, acquire (asynchronously)
-        // waits until an outstanding permit is dropped, at which point, the freed permit is assigned
-        // to the caller.
-        let _permit = self.scan_limiter.acquire().await;
+        if waiting {
+            ferox_scan.set_status(ScanStatus::Pending)?;
+            progress_bar.set_message("=> pending");
+        } else {
+            ferox_scan.set_status(ScanStatus::Running)?;
+        }
 
-        if self.handles.config.scan_limit > 0 {
+        let mut permit = self.acquire_slot(&ferox_scan, &scanned_urls).await;
+
+        if waiting {
+            ferox_scan.set_status(ScanStatus::Running)?;
             scan_timer = Instant::now();
+            progress_bar.set_message("");
             progress_bar.reset();
         }
 
-        {
+        if matches!(ferox_scan.scan_type, ScanType::Crawl) {
+            // --crawl; none of the wordlist heuristics apply when following links
+            self.crawl(ferox_scan.clone(), scanned_urls).await?;
+
+            self.handles.stats.send(AddToF64Field(
+                DirScanTimes,
+                scan_timer.elapsed().as_secs_f64(),
+            ))?;
+
+            ferox_scan.finish()?;
+
+            log::trace!("exit: scan_url");
+            return Ok(());
+        }
+
+        // api paths requested ahead of the wordlist, when the target is a single-page app
+        let mut spa_words = Vec::new();
+
+        // templated scans (FUZZ keywords) don't request directories of the target, the
+        // heuristics would only test the template itself
+        if !self.handles.features.template.is_active() {
             // heuristics test block:
             let test = heuristics::HeuristicTests::new(self.handles.clone());
 
-            if let Ok(dirlist_result) = test.directory_listing(&self.target_url).await {
-                if dirlist_result.is_some() {
-                    let dirlist_result = dirlist_result.unwrap();
-                    // at this point, we have a DirListingType, and it's not the None variant
-                    // which means we found directory listing based on the heuristic; now we need
-                    // to process the links that are available if --extract-links was used
+            if let Ok(Some(dirlist_result)) = test.directory_listing(&self.target_url).await {
+                // at this point, we have a DirListingType, and it's not the None variant
+                // which means we found directory listing based on the heuristic; now we need
+                // to process the links that are available if --extract-links was used
 
-                    if self.handles.config.extract_links {
-                        let mut extractor = ExtractorBuilder::default()
-                            .response(&dirlist_result.response)
-                            .target(ExtractionTarget::DirectoryListing)
-                            .url(&self.target_url)
-                            .handles(self.handles.clone())
-                            .build()?;
+                if self.handles.config.extract_links {
+                    let mut extractor = ExtractorBuilder::default()
+                        .response(&dirlist_result.response)
+                        .target(ExtractionTarget::DirectoryListing)
+                        .url(&self.target_url)
+                        .handles(self.handles.clone())
+                        .build()?;
 
-                        let result = extractor.extract_from_dir_listing().await?;
+                    let result = extractor.extract_from_dir_listing().await?;
 
-                        extraction_tasks.push(extractor.request_links(result).await?);
+                    extraction_tasks.push(extractor.request_links(result).await?);
 
-                        log::trace!("exit: scan_url -> Directory listing heuristic");
+                    log::trace!("exit: scan_url -> Directory listing heuristic");
 
-                        self.handles.stats.send(AddToF64Field(
-                            DirScanTimes,
-                            scan_timer.elapsed().as_secs_f64(),
-                        ))?;
+                    self.handles.stats.send(AddToF64Field(
+                        DirScanTimes,
+                        scan_timer.elapsed().as_secs_f64(),
+                    ))?;
 
-                        self.handles.stats.send(SubtractFromUsizeField(
-                            TotalExpected,
-                            progress_bar.length().unwrap_or(0) as usize,
-                        ))?;
-                    }
+                    self.handles.stats.send(SubtractFromUsizeField(
+                        TotalExpected,
+                        progress_bar.length().unwrap_or(0) as usize,
+                    ))?;
+                }
 
-                    let mut message = format!("=> {}", style("Directory listing").blue().bright());
+                let mut message = format!("=> {}", style("Directory listing").blue().bright());
 
-                    if !self.handles.config.extract_links {
-                        write!(
-                            message,
-                            " (remove {} to scan)",
-                            style("--dont-extract-links").bright().yellow()
-                        )?;
-                    }
+                if !self.handles.config.extract_links {
+                    write!(
+                        message,
+                        " (remove {} to scan)",
+                        style("--dont-extract-links").bright().yellow()
+                    )?;
+                }
 
-                    if !self.handles.config.force_recursion {
-                        for handle in extraction_tasks.into_iter().flatten() {
-                            _ = handle.await;
-                        }
+                if !self.handles.config.force_recursion {
+                    for handle in extraction_tasks.into_iter().flatten() {
+                        _ = handle.await;
+                    }
 
-                        progress_bar.reset_eta();
-                        progress_bar.finish_with_message(message);
+                    progress_bar.reset_eta();
+                    progress_bar.finish_with_message(message);
 
-                        ferox_scan.finish()?;
+                    ferox_scan.finish()?;
 
-                        return Ok(()); // nothing left to do if we found a dir listing
-                    }
+                    return Ok(()); // nothing left to do if we found a dir listing
                 }
             }
 
@@ -317,7 +594,7 @@ impl FeroxScanner {
             // wildcard test
             let num_reqs_made = test.detect_404_like_responses(&self.target_url).await?;
 
-            match num_reqs_made {
+            let num_probes = match num_reqs_made {
                 Some(WildcardResult::WildcardDirectory(num_reqs)) => {
                     let message = format!(
                         "=> {} dir! {} recursion",
@@ -326,11 +603,32 @@ impl FeroxScanner {
                     );
                     progress_bar.set_message(message);
                     progress_bar.inc(num_reqs as u64);
+                    num_reqs
                 }
                 Some(WildcardResult::FourOhFourLike(num_reqs)) => {
                     progress_bar.inc(num_reqs as u64);
+                    num_reqs
+                }
+                _ => 0,
+            };
+
+            // the probes were sent on top of the wordlist, the overall bar needs to expect them
+            self.handles
+                .stats
+                .send(AddToUsizeField(TotalExpected, num_probes))
+                .unwrap_or_default();
+
+            // how quickly the target answered the probes is the baseline for --tune-on-latency
+            ferox_scan.latency().set_baseline(&test.latencies());
+
+            // single-page apps answer every path with the same index page; anything below an
+            // spa is part of it, so only initial targets are tested
+            if matches!(self.order, ScanOrder::Initial) && !self.handles.config.dont_filter {
+                if let Ok(Some(index)) = test.single_page_app(&self.target_url).await {
+                    let (task, words) = self.single_page_app(index).await?;
+                    extraction_tasks.push(task);
+                    spa_words = words;
                 }
-                _ => {}
             }
         }
 
@@ -339,13 +637,74 @@ impl FeroxScanner {
 
         let requester = Arc::new(Requester::from(self, ferox_scan.clone())?);
 
-        self.stream_requests(
-            looping_words.clone(),
-            progress_bar.clone(),
-            scanned_urls.clone(),
-            requester.clone(),
-        )
-        .await;
+        if !spa_words.is_empty() {
+            let expected = spa_words.len() * self.handles.expected_num_requests_multiplier();
+
+            progress_bar.inc_length(expected as u64);
+
+            self.handles
+                .stats
+                .send(AddToUsizeField(TotalExpected, expected))
+                .unwrap_or_default();
+
+            self.stream_requests(
+                Arc::new(spa_words),
+                progress_bar.clone(),
+                scanned_urls.clone(),
+                requester.clone(),
+                ScanPhase::All,
+            )
+            .await;
+        }
+
+        // --directories-first; nothing to come back for when no extensions can be requested
+        if ScanPhase::first(
+            &self.handles.config,
+            self.handles.features.template.is_active(),
+        ) == ScanPhase::Directories
+        {
+            // a requeued scan may have been through its directory pass already
+            if !ferox_scan.directory_pass_done() {
+                self.stream_requests(
+                    looping_words.clone(),
+                    progress_bar.clone(),
+                    scanned_urls.clone(),
+                    requester.clone(),
+                    ScanPhase::Directories,
+                )
+                .await;
+
+                ferox_scan.finish_directory_pass();
+            }
+
+            // the slot is given up while waiting; under --scan-limit, the scans found during
+            // the pass need one to do theirs
+            drop(permit);
+
+            progress_bar.set_message("=> waiting on the directory pass");
+            scanned_urls.wait_for_directory_pass().await;
+
+            permit = self.acquire_slot(&ferox_scan, &scanned_urls).await;
+            progress_bar.set_message("");
+
+            self.stream_requests(
+                looping_words.clone(),
+                progress_bar.clone(),
+                scanned_urls.clone(),
+                requester.clone(),
+                ScanPhase::Files,
+            )
+            .await;
+        } else {
+            self.stream_requests(
+                looping_words.clone(),
+                progress_bar.clone(),
+                scanned_urls.clone(),
+                requester.clone(),
+                ScanPhase::All,
+            )
+            .await;
+        }
 
         if self.handles.config.collect_words {
             let new_words = TF_IDF.read().unwrap().all_words();
@@ -372,6 +731,7 @@ impl FeroxScanner {
                 progress_bar.clone(),
                 scanned_urls.clone(),
                 requester.clone(),
+                ScanPhase::All,
             )
             .await;
         }
@@ -386,6 +746,7 @@ impl FeroxScanner {
         }
 
         ferox_scan.finish()?;
+        drop(permit);
 
         log::trace!("exit: scan_url");
 
@@ -1,5 +1,7 @@
 use std::fmt::{Debug, Formatter, Result};
 
+use super::rate_strategy::RateStrategy;
+
 /// bespoke variation on an array-backed max-heap
 ///
 /// 255 possible values generated from the initial requests/second
@@ -169,3 +171,116 @@ impl LimitHeap {
         self.move_to(0); // reset current index to the root of the tree
     }
 }
+
+/// the original tuning algorithm: walk the tree, left (faster) while errors hold steady and
+/// right (slower) when they increase
+impl RateStrategy for LimitHeap {
+    fn seed(&mut self, reqs_sec: usize) -> usize {
+        self.original = reqs_sec as i32;
+        self.build();
+        self.value() as usize // 1/2 of the current request rate
+    }
+
+    fn decrease(&mut self) -> usize {
+        if self.has_children() {
+            self.move_right();
+        }
+        self.value() as usize
+    }
+
+    fn increase(&mut self, streak: usize) -> usize {
+        if streak > 2 {
+            // streak of 3 upward moves in a row, traverse the tree upward instead of to a
+            // higher-valued branch lower in the tree
+            let current = self.value();
+            self.move_up();
+            self.move_up();
+            if current > self.value() {
+                // the tree's structure makes it so that sometimes 2 moves up results in a
+                // value greater than the current node's and other times we need to move 3 up
+                // to arrive at a greater value
+                if self.has_parent() && self.parent_value() > current {
+                    // all nodes except 0th node (root)
+                    self.move_up();
+                }
+            }
+        } else if self.has_children() {
+            // streak not at 3, just check that we can move down, and do so
+            self.move_left();
+        } else {
+            // tree bottomed out, need to move back up the tree a bit
+            let current = self.value();
+            self.move_up();
+            self.move_up();
+
+            if current > self.value() {
+                self.move_up();
+            }
+        }
+
+        self.value() as usize
+    }
+
+    fn recovered(&self) -> bool {
+        // been here enough that we can try resuming the scan to its original speed
+        !self.has_parent()
+    }
+
+    fn state(&self) -> (i32, usize) {
+        (self.original, self.current)
+    }
+
+    fn restore(&mut self, original: i32, position: usize) -> usize {
+        self.original = original;
+        self.build();
+        let last = self.inner.len() - 1;
+        self.move_to(position.min(last));
+        self.value() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// seeding builds the heap and starts at 1/2 of the original rate
+    fn limit_heap_seed_builds_heap() {
+        let mut heap = LimitHeap::default();
+        assert_eq!(heap.seed(400), 200);
+        assert_eq!(heap.original, 400);
+        assert_eq!(heap.current, 0);
+        assert_eq!(heap.inner[0], 200);
+        assert_eq!(heap.inner[1], 300);
+        assert_eq!(heap.inner[2], 100);
+    }
+
+    #[test]
+    /// decrease stays put when no child nodes are present
+    fn limit_heap_decrease_no_children() {
+        let mut heap = LimitHeap::default();
+        heap.seed(400);
+        heap.move_to(250);
+        heap.set_value(27);
+
+        assert_eq!(heap.decrease(), 27);
+        assert_eq!(heap.current, 250);
+    }
+
+    #[test]
+    /// hit some of the out of the way corners of limitheap for coverage
+    fn increase_limit_heap_coverage_by_hitting_edge_cases() {
+        let mut heap = LimitHeap::default();
+        heap.seed(400);
+
+        println!("{heap:?}"); // debug derivation
+
+        heap.move_to(240);
+        assert_eq!(heap.move_right(), 240);
+        assert_eq!(heap.move_left(), 240);
+
+        heap.move_to(0);
+        assert_eq!(heap.move_up(), 0);
+        assert_eq!(heap.parent_value(), 400);
+    }
+}
@@ -1,12 +1,18 @@
+mod expansion;
 mod ferox_scanner;
 mod utils;
 mod init;
 #[cfg(test)]
 mod tests;
 mod limit_heap;
+pub mod preview;
 mod policy_data;
+mod rate_strategy;
 mod requester;
+mod runtime;
 
 pub use self::ferox_scanner::{FeroxScanner, RESPONSES};
 pub use self::init::initialize;
+pub use self::requester::GlobalRateLimit;
+pub use self::runtime::RuntimeSettings;
 pub use self::utils::PolicyTrigger;
@@ -1,14 +1,17 @@
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use crate::{atomic_load, atomic_store, config::RequesterPolicy};
+use crate::{
+    atomic_load, atomic_store, config::RequesterPolicy, HIGH_429_RATIO, HIGH_ERROR_RATIO,
+    MIN_ERROR_THRESHOLD,
+};
 
-use super::limit_heap::LimitHeap;
+use super::{limit_heap::LimitHeap, rate_strategy::RateStrategy};
 
 /// data regarding policy and metadata about last enforced trigger etc...
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct PolicyData {
     /// how to handle exceptional cases such as too many errors / 403s / 429s etc
-    pub(super) policy: RequesterPolicy,
+    policy: std::sync::RwLock<RequesterPolicy>,
 
     /// whether or not we're in the middle of a cooldown period
     pub(super) cooling_down: AtomicBool,
@@ -26,8 +29,35 @@ pub struct PolicyData {
     /// has been limited and moves back up to the point of its original scan speed
     pub(super) remove_limit: AtomicBool,
 
-    /// heap of values used for adjusting # of requests/second
-    pub(super) heap: std::sync::RwLock<LimitHeap>,
+    /// algorithm used for adjusting # of requests/second
+    strategy: std::sync::RwLock<Box<dyn RateStrategy>>,
+
+    /// ratio of requests that must be 403s before the policy is enforced
+    pub(super) ratio_403: f64,
+
+    /// ratio of requests that must be 429s before the policy is enforced
+    pub(super) ratio_429: f64,
+
+    /// minimum number of errors before the policy is enforced
+    pub(super) error_threshold: usize,
+}
+
+/// default implementation of PolicyData, tunes using the limit heap
+impl Default for PolicyData {
+    fn default() -> Self {
+        Self {
+            policy: Default::default(),
+            cooling_down: Default::default(),
+            wait_time: 0,
+            limit: Default::default(),
+            errors: Default::default(),
+            remove_limit: Default::default(),
+            strategy: std::sync::RwLock::new(Box::<LimitHeap>::default()),
+            ratio_403: HIGH_ERROR_RATIO,
+            ratio_429: HIGH_429_RATIO,
+            error_threshold: MIN_ERROR_THRESHOLD,
+        }
+    }
 }
 
 /// implementation of PolicyData
@@ -38,21 +68,66 @@ impl PolicyData {
         let wait_time = ((timeout as f64 / 2.0) * 1000.0) as u64;
 
         Self {
-            policy,
+            policy: std::sync::RwLock::new(policy),
             wait_time,
             ..Default::default()
         }
     }
 
-    /// setter for requests / second; populates the underlying heap with values from req/sec seed
+    /// use the given algorithm to pick requests/second limits, instead of the limit heap
+    pub(super) fn with_strategy(mut self, strategy: Box<dyn RateStrategy>) -> Self {
+        self.strategy = std::sync::RwLock::new(strategy);
+        self
+    }
+
+    /// use the given trigger thresholds instead of the defaults
+    pub(super) fn with_thresholds(
+        mut self,
+        ratio_403: f64,
+        ratio_429: f64,
+        error_threshold: usize,
+    ) -> Self {
+        self.ratio_403 = ratio_403;
+        self.ratio_429 = ratio_429;
+        self.error_threshold = error_threshold;
+        self
+    }
+
+    /// setter for requests / second; seeds the tuning strategy with the current req/sec
     pub(super) fn set_reqs_sec(&self, reqs_sec: usize) {
-        if let Ok(mut guard) = self.heap.write() {
-            guard.original = reqs_sec as i32;
-            guard.build();
-            self.set_limit(guard.inner[0] as usize); // set limit to 1/2 of current request rate
+        if let Ok(mut guard) = self.strategy.write() {
+            self.set_limit(guard.seed(reqs_sec));
+        }
+    }
+
+    /// getter for policy
+    pub(super) fn policy(&self) -> RequesterPolicy {
+        self.policy.read().map(|guard| *guard).unwrap_or_default()
+    }
+
+    /// setter for policy, used when the policy is changed mid-scan
+    pub(super) fn set_policy(&self, policy: RequesterPolicy) {
+        if let Ok(mut guard) = self.policy.write() {
+            *guard = policy;
         }
     }
 
+    /// pick the tuning strategy back up from a saved state
+    pub(super) fn restore(&self, original_rate: i32, heap_position: usize, errors: usize) {
+        if let Ok(mut guard) = self.strategy.write() {
+            self.set_limit(guard.restore(original_rate, heap_position));
+        }
+        self.set_errors(errors);
+    }
+
+    /// getter for the tuning strategy's original requests/second and current position
+    pub(super) fn heap_state(&self) -> (i32, usize) {
+        self.strategy
+            .read()
+            .map(|guard| guard.state())
+            .unwrap_or_default()
+    }
+
     /// setter for errors
     pub(super) fn set_errors(&self, errors: usize) {
         atomic_store!(self.errors, errors);
@@ -70,52 +145,22 @@ impl PolicyData {
 
     /// adjust the rate of requests per second up (increase rate)
     pub(super) fn adjust_up(&self, streak_counter: &usize) {
-        if let Ok(mut heap) = self.heap.try_write() {
-            if *streak_counter > 2 {
-                // streak of 3 upward moves in a row, traverse the tree upward instead of to a
-                // higher-valued branch lower in the tree
-                let current = heap.value();
-                heap.move_up();
-                heap.move_up();
-                if current > heap.value() {
-                    // the tree's structure makes it so that sometimes 2 moves up results in a
-                    // value greater than the current node's and other times we need to move 3 up
-                    // to arrive at a greater value
-                    if heap.has_parent() && heap.parent_value() > current {
-                        // all nodes except 0th node (root)
-                        heap.move_up();
-                    }
-                }
-            } else if heap.has_children() {
-                // streak not at 3, just check that we can move down, and do so
-                heap.move_left();
-            } else {
-                // tree bottomed out, need to move back up the tree a bit
-                let current = heap.value();
-                heap.move_up();
-                heap.move_up();
-
-                if current > heap.value() {
-                    heap.move_up();
-                }
-            }
+        if let Ok(mut strategy) = self.strategy.try_write() {
+            let limit = strategy.increase(*streak_counter);
 
-            if !heap.has_parent() {
+            if strategy.recovered() {
                 // been here enough that we can try resuming the scan to its original
                 // speed (no limiting at all)
                 atomic_store!(self.remove_limit, true);
             }
-            self.set_limit(heap.value() as usize);
+            self.set_limit(limit);
         }
     }
 
     /// adjust the rate of requests per second down (decrease rate)
     pub(super) fn adjust_down(&self) {
-        if let Ok(mut heap) = self.heap.try_write() {
-            if heap.has_children() {
-                heap.move_right();
-                self.set_limit(heap.value() as usize);
-            }
+        if let Ok(mut strategy) = self.strategy.try_write() {
+            self.set_limit(strategy.decrease());
         }
     }
 }
@@ -123,6 +168,7 @@ impl PolicyData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Configuration;
 
     #[test]
     /// PolicyData builds and sets correct values for the inner heap when set_reqs_sec is called
@@ -131,11 +177,7 @@ mod tests {
         assert_eq!(pd.wait_time, 3500);
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
-        assert_eq!(pd.heap.read().unwrap().original, 400);
-        assert_eq!(pd.heap.read().unwrap().current, 0);
-        assert_eq!(pd.heap.read().unwrap().inner[0], 200);
-        assert_eq!(pd.heap.read().unwrap().inner[1], 300);
-        assert_eq!(pd.heap.read().unwrap().inner[2], 100);
+        assert_eq!(pd.heap_state(), (400, 0));
     }
 
     #[test]
@@ -148,6 +190,25 @@ mod tests {
         assert_eq!(pd.get_limit(), 200);
     }
 
+    #[test]
+    /// PolicyData restore rebuilds the heap and picks up at the saved position
+    fn policy_data_restore_from_heap_state() {
+        let pd = PolicyData::new(RequesterPolicy::AutoTune, 7);
+        pd.set_reqs_sec(400);
+        pd.adjust_down();
+        assert_eq!(pd.heap_state(), (400, 2));
+
+        let restored = PolicyData::new(RequesterPolicy::AutoTune, 7);
+        restored.restore(400, 2, 3);
+        assert_eq!(restored.get_limit(), pd.get_limit());
+        assert_eq!(restored.heap_state(), (400, 2));
+        assert_eq!(restored.errors.load(Ordering::Relaxed), 3);
+
+        // bogus positions shouldn't panic
+        restored.restore(400, 1000, 0);
+        assert_eq!(restored.heap_state(), (400, 254));
+    }
+
     #[test]
     /// PolicyData adjust_down sets the limit to the correct value
     fn policy_data_adjust_down_simple() {
@@ -159,19 +220,19 @@ mod tests {
     }
 
     #[test]
-    /// PolicyData adjust_down sets the limit to the correct value when no child nodes are present
+    /// PolicyData adjust_down leaves the limit where it is when no child nodes are present
     fn policy_data_adjust_down_no_children() {
         let pd = PolicyData::new(RequesterPolicy::AutoBail, 7);
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
-        let mut guard = pd.heap.write().unwrap();
-        guard.move_to(250);
-        guard.set_value(27);
-        pd.set_limit(guard.value() as usize);
-        drop(guard);
+
+        // 250 is on the bottom row of the heap built for 400
+        pd.restore(400, 250, 0);
+        let limit = pd.get_limit();
 
         pd.adjust_down();
-        assert_eq!(pd.get_limit(), 27);
+        assert_eq!(pd.get_limit(), limit);
+        assert_eq!(pd.heap_state(), (400, 250));
     }
 
     #[test]
@@ -194,10 +255,10 @@ mod tests {
         assert_eq!(pd.get_limit(), 200);
 
         // 2 moves
-        pd.heap.write().unwrap().move_to(9);
-        assert_eq!(pd.heap.read().unwrap().value(), 275);
+        pd.restore(400, 9, 0);
+        assert_eq!(pd.get_limit(), 275);
         pd.adjust_up(&3);
-        assert_eq!(pd.heap.read().unwrap().value(), 300);
+        assert_eq!(pd.get_limit(), 300);
         assert_eq!(pd.limit.load(Ordering::Relaxed), 300);
         assert!(!pd.remove_limit.load(Ordering::Relaxed));
     }
@@ -211,10 +272,10 @@ mod tests {
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
 
-        pd.heap.write().unwrap().move_to(4);
-        assert_eq!(pd.heap.read().unwrap().value(), 250);
+        pd.restore(400, 4, 0);
+        assert_eq!(pd.get_limit(), 250);
         pd.adjust_up(&3);
-        assert_eq!(pd.heap.read().unwrap().value(), 200);
+        assert_eq!(pd.get_limit(), 200);
         assert_eq!(pd.limit.load(Ordering::Relaxed), 200);
         assert!(pd.remove_limit.load(Ordering::Relaxed));
     }
@@ -228,10 +289,10 @@ mod tests {
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
 
-        pd.heap.write().unwrap().move_to(15);
-        assert_eq!(pd.heap.read().unwrap().value(), 387);
+        pd.restore(400, 15, 0);
+        assert_eq!(pd.get_limit(), 387);
         pd.adjust_up(&3);
-        assert_eq!(pd.heap.read().unwrap().value(), 350);
+        assert_eq!(pd.get_limit(), 350);
         assert_eq!(pd.limit.load(Ordering::Relaxed), 350);
         assert!(!pd.remove_limit.load(Ordering::Relaxed));
     }
@@ -245,10 +306,10 @@ mod tests {
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
 
-        pd.heap.write().unwrap().move_to(19);
-        assert_eq!(pd.heap.read().unwrap().value(), 287);
+        pd.restore(400, 19, 0);
+        assert_eq!(pd.get_limit(), 287);
         pd.adjust_up(&3);
-        assert_eq!(pd.heap.read().unwrap().value(), 300);
+        assert_eq!(pd.get_limit(), 300);
         assert_eq!(pd.limit.load(Ordering::Relaxed), 300);
         assert!(!pd.remove_limit.load(Ordering::Relaxed));
     }
@@ -262,11 +323,11 @@ mod tests {
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
 
-        pd.heap.write().unwrap().move_to(241);
+        pd.restore(400, 241, 0);
 
-        assert_eq!(pd.heap.read().unwrap().value(), 41);
+        assert_eq!(pd.get_limit(), 41);
         pd.adjust_up(&0);
-        assert_eq!(pd.heap.read().unwrap().value(), 43);
+        assert_eq!(pd.get_limit(), 43);
         assert_eq!(pd.limit.load(Ordering::Relaxed), 43);
         assert!(!pd.remove_limit.load(Ordering::Relaxed));
     }
@@ -280,29 +341,32 @@ mod tests {
         pd.set_reqs_sec(400);
         assert_eq!(pd.get_limit(), 200);
 
-        pd.heap.write().unwrap().move_to(240);
+        pd.restore(400, 240, 0);
 
-        assert_eq!(pd.heap.read().unwrap().value(), 45);
+        assert_eq!(pd.get_limit(), 45);
         pd.adjust_up(&0);
-        assert_eq!(pd.heap.read().unwrap().value(), 37);
+        assert_eq!(pd.get_limit(), 37);
         assert_eq!(pd.limit.load(Ordering::Relaxed), 37);
         assert!(!pd.remove_limit.load(Ordering::Relaxed));
     }
 
     #[test]
-    /// hit some of the out of the way corners of limitheap for coverage
-    fn increase_limit_heap_coverage_by_hitting_edge_cases() {
-        let pd = PolicyData::new(RequesterPolicy::AutoBail, 7);
+    /// PolicyData should defer to the strategy it was given
+    fn policy_data_uses_given_strategy() {
+        let pd = PolicyData::new(RequesterPolicy::AutoTune, 7).with_strategy(
+            crate::scanner::rate_strategy::build(&Configuration {
+                tune_strategy: "gradient".to_string(),
+                ..Default::default()
+            }),
+        );
         pd.set_reqs_sec(400);
+        assert_eq!(pd.get_limit(), 200);
 
-        println!("{:?}", pd.heap.read().unwrap()); // debug derivation
-
-        pd.heap.write().unwrap().move_to(240);
-        assert_eq!(pd.heap.write().unwrap().move_right(), 240);
-        assert_eq!(pd.heap.write().unwrap().move_left(), 240);
-
-        pd.heap.write().unwrap().move_to(0);
-        assert_eq!(pd.heap.write().unwrap().move_up(), 0);
-        assert_eq!(pd.heap.write().unwrap().parent_value(), 400);
+        // gradient steps are a quarter of the seed, growing by half while the direction holds
+        pd.adjust_down();
+        assert_eq!(pd.get_limit(), 50);
+        pd.adjust_up(&1);
+        assert_eq!(pd.get_limit(), 125);
+        assert!(!pd.remove_limit.load(Ordering::Relaxed));
     }
 }
@@ -0,0 +1,207 @@
+//! --preview; the first few requests of the scan, shown before anything is sent
+//!
+//! requests are built the same way the scan builds them (templates, --evasion, --extension-order,
+//! --directories-first, plugin hooks, --request-id, ...), which makes this a sanity check for
+//! how all of those interact. the user is asked whether to go ahead with the scan afterwards
+use std::{collections::HashSet, io, sync::Arc};
+
+use anyhow::Result;
+use console::style;
+use reqwest::Request;
+
+use super::{
+    expansion::{order_phase, ExtensionOrder},
+    requester::WordRequests,
+};
+use crate::{
+    config::Configuration,
+    event_handlers::Handles,
+    scan_manager::ScanPhase,
+    shuffle,
+    utils::{prepare_request, should_deny_url},
+};
+
+/// the first `limit` requests of the scan, in the order they'd be sent
+///
+/// the targets are taken in order; scans found by recursion can't be known ahead of time, so
+/// only the initial targets are covered
+pub fn sample(targets: &[String], limit: usize, handles: Arc<Handles>) -> Result<Vec<Request>> {
+    log::trace!("enter: sample({:?}, {})", targets, limit);
+
+    let config = &handles.config;
+
+    let templated = handles.features.template.is_active();
+
+    let order = if templated {
+        ExtensionOrder::Sequential
+    } else {
+        ExtensionOrder::from_config(&config.extension_order)
+    };
+
+    let phases = match ScanPhase::first(config, templated) {
+        ScanPhase::Directories => vec![ScanPhase::Directories, ScanPhase::Files],
+        phase => vec![phase],
+    };
+
+    // nothing has been collected yet, each word only has the configured extensions
+    let variants = 1 + config.extensions.len();
+
+    let should_test_deny = !config.url_denylist.is_empty() || !config.regex_denylist.is_empty();
+
+    let (_, client) = handles.proxies.client();
+    let mut requests = Vec::new();
+
+    for target in targets {
+        let words = if config.shuffle {
+            Arc::new(shuffle::shuffled(
+                &handles.wordlist(),
+                config.shuffle_seed,
+                target,
+            ))
+        } else {
+            handles.wordlist()
+        };
+
+        for phase in &phases {
+            let items = words
+                .chunks(config.threads.max(1))
+                .flat_map(|chunk| order_phase(chunk, variants, order, *phase));
+
+            for (word, variant) in items {
+                let built = WordRequests::new(
+                    target,
+                    &word,
+                    variant,
+                    HashSet::new(),
+                    Vec::new(),
+                    &handles,
+                )?;
+
+                let data = (!built.data.is_empty()).then_some(built.data.as_slice());
+
+                for url in &built.urls {
+                    if should_test_deny && should_deny_url(url, &handles)? {
+                        continue;
+                    }
+
+                    for method in &config.methods {
+                        let prepared =
+                            prepare_request(client, url, method, data, &built.headers, &handles)?;
+
+                        requests.push(prepared.builder.build()?);
+
+                        if requests.len() >= limit {
+                            log::trace!("exit: sample -> {} requests", requests.len());
+                            return Ok(requests);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    log::trace!("exit: sample -> {} requests", requests.len());
+    Ok(requests)
+}
+
+/// the request as it goes out on the wire: request line, headers (the client's defaults
+/// included), and body
+pub fn render(request: &Request, config: &Configuration) -> String {
+    let mut headers = vec![(String::from("User-Agent"), config.user_agent.clone())];
+
+    if !config.host_header.is_empty() {
+        headers.push((String::from("Host"), config.host_header.clone()));
+    }
+
+    let mut configured: Vec<_> = config.headers.iter().collect();
+    configured.sort();
+
+    for (name, value) in configured {
+        set_header(&mut headers, name, value);
+    }
+
+    for (name, value) in request.headers() {
+        set_header(
+            &mut headers,
+            name.as_str(),
+            &String::from_utf8_lossy(value.as_bytes()),
+        );
+    }
+
+    let mut rendered = format!("{} {}\n", request.method(), request.url());
+
+    for (name, value) in headers {
+        rendered.push_str(&format!("{name}: {value}\n"));
+    }
+
+    if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+        rendered.push_str(&format!("\n{}\n", String::from_utf8_lossy(body)));
+    }
+
+    rendered
+}
+
+/// set the header, replacing one of the same name (compared case insensitively) in place
+fn set_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match headers
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+    {
+        Some(header) => *header = (name.to_string(), value.trim().to_string()),
+        None => headers.push((name.to_string(), value.trim().to_string())),
+    }
+}
+
+/// print the first --preview requests of the scan, then ask whether to start it; anything but
+/// y/yes (including no answer at all) means no
+pub fn preview(targets: &[String], handles: Arc<Handles>) -> Result<bool> {
+    let limit = handles.config.preview;
+    let requests = sample(targets, limit, handles.clone())?;
+
+    for (index, request) in requests.iter().enumerate() {
+        eprintln!(
+            "{} {}\n{}",
+            style("Preview").cyan().bright(),
+            style(format!("[{}/{}]", index + 1, requests.len())).bright(),
+            render(request, &handles.config)
+        );
+    }
+
+    eprint!("Send these requests and start the scan? [y/N] ");
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    /// configured headers replace the client's defaults in place, the request's own come last
+    fn render_merges_default_and_request_headers() {
+        let config = Configuration {
+            user_agent: String::from("ferox"),
+            headers: HashMap::from([(String::from("user-agent"), String::from("mine"))]),
+            ..Default::default()
+        };
+
+        let request = reqwest::Client::new()
+            .post("http://localhost/admin.php")
+            .header("X-Request-Id", "abc-1")
+            .body("user=FUZZ")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            render(&request, &config),
+            "POST http://localhost/admin.php\nuser-agent: mine\nx-request-id: abc-1\n\nuser=FUZZ\n"
+        );
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt::Debug;
+
+use super::limit_heap::LimitHeap;
+use crate::config::Configuration;
+
+/// Algorithm used by --auto-tune to pick a scan's requests/second limit
+///
+/// A strategy is seeded with the scan's speed when tuning first kicks in and is then told,
+/// after each cooldown, whether errors went up (`decrease`) or not (`increase`)
+pub(super) trait RateStrategy: Debug + Send + Sync {
+    /// start tuning from the given requests/second, returns the first limit to enforce
+    fn seed(&mut self, reqs_sec: usize) -> usize;
+
+    /// errors increased since the last adjustment, returns the new limit
+    fn decrease(&mut self) -> usize;
+
+    /// no new errors since the last adjustment, returns the new limit; `streak` is the number
+    /// of increases in a row, including this one
+    fn increase(&mut self, streak: usize) -> usize;
+
+    /// whether the limit is back to the speed tuning started from, i.e. it can be removed
+    fn recovered(&self) -> bool;
+
+    /// the seed and a strategy specific position, saved to the state file
+    fn state(&self) -> (i32, usize);
+
+    /// pick up from a saved seed and position, returns the limit to enforce
+    fn restore(&mut self, original: i32, position: usize) -> usize;
+}
+
+/// Create the strategy named by --tune-strategy; anything unrecognized walks the limit heap
+pub(super) fn build(config: &Configuration) -> Box<dyn RateStrategy> {
+    match config.tune_strategy.as_str() {
+        "gradient" => Box::<GradientStep>::default(),
+        "aimd" => Box::new(Aimd::new(
+            config.tune_step,
+            config.tune_floor,
+            config.tune_ceiling,
+        )),
+        _ => Box::<LimitHeap>::default(),
+    }
+}
+
+/// Moves the limit in whichever direction errors push it; the step grows while the direction
+/// holds and is halved whenever it reverses, so the limit settles down instead of bouncing
+/// between the same two values
+#[derive(Debug, Default)]
+pub(super) struct GradientStep {
+    /// requests/second seen when tuning started
+    original: usize,
+
+    /// current limit
+    limit: usize,
+
+    /// amount the limit moves on the next adjustment
+    step: usize,
+
+    /// whether the last adjustment was an increase
+    rising: bool,
+}
+
+impl GradientStep {
+    /// step taken after a reversal, or when starting out
+    fn initial_step(&self) -> usize {
+        (self.original / 4).max(1)
+    }
+
+    /// grow the step when continuing in the same direction, halve it otherwise
+    fn next_step(&mut self, rising: bool) -> usize {
+        self.step = if rising == self.rising {
+            self.step + self.step / 2
+        } else {
+            self.step / 2
+        }
+        .max(1);
+
+        self.rising = rising;
+        self.step
+    }
+}
+
+impl RateStrategy for GradientStep {
+    fn seed(&mut self, reqs_sec: usize) -> usize {
+        self.original = reqs_sec;
+        self.limit = (reqs_sec / 2).max(1);
+        self.step = self.initial_step();
+        self.rising = false;
+        self.limit
+    }
+
+    fn decrease(&mut self) -> usize {
+        let step = self.next_step(false);
+        self.limit = self.limit.saturating_sub(step).max(1);
+        self.limit
+    }
+
+    fn increase(&mut self, _streak: usize) -> usize {
+        let step = self.next_step(true);
+        self.limit = (self.limit + step).min(self.original.max(1));
+        self.limit
+    }
+
+    fn recovered(&self) -> bool {
+        self.limit >= self.original
+    }
+
+    fn state(&self) -> (i32, usize) {
+        (self.original as i32, self.limit)
+    }
+
+    fn restore(&mut self, original: i32, position: usize) -> usize {
+        self.seed(original.max(0) as usize);
+        self.limit = position.clamp(1, self.original.max(1));
+        self.limit
+    }
+}
+
+/// Additive increase, multiplicative decrease: the limit climbs by a fixed step after each clean
+/// interval and is halved whenever errors go up
+#[derive(Debug, Default)]
+pub(super) struct Aimd {
+    /// requests/second seen when tuning started
+    original: usize,
+
+    /// current limit
+    limit: usize,
+
+    /// requests/second added per clean interval; 0 means 1/20th of the seed rate
+    step: usize,
+
+    /// lowest limit allowed; 0 means 1
+    floor: usize,
+
+    /// highest limit allowed; 0 means the seed rate
+    ceiling: usize,
+}
+
+impl Aimd {
+    /// create a new AIMD strategy, 0 values are filled in from the seed rate
+    pub(super) fn new(step: usize, floor: usize, ceiling: usize) -> Self {
+        Self {
+            step,
+            floor,
+            ceiling,
+            ..Default::default()
+        }
+    }
+
+    /// amount added to the limit per clean interval
+    fn step(&self) -> usize {
+        match self.step {
+            0 => (self.original / 20).max(1),
+            step => step,
+        }
+    }
+
+    /// lowest and highest limits allowed; never above the seed rate, at which point the limit
+    /// is removed entirely
+    fn bounds(&self) -> (usize, usize) {
+        let upper = match self.ceiling {
+            0 => self.original,
+            ceiling => ceiling.min(self.original),
+        }
+        .max(1);
+
+        (self.floor.clamp(1, upper), upper)
+    }
+
+    /// keep the given limit within the floor and ceiling
+    fn bounded(&self, limit: usize) -> usize {
+        let (lower, upper) = self.bounds();
+        limit.clamp(lower, upper)
+    }
+}
+
+impl RateStrategy for Aimd {
+    fn seed(&mut self, reqs_sec: usize) -> usize {
+        self.original = reqs_sec;
+        self.limit = self.bounded(reqs_sec / 2);
+        self.limit
+    }
+
+    fn decrease(&mut self) -> usize {
+        self.limit = self.bounded(self.limit / 2);
+        self.limit
+    }
+
+    fn increase(&mut self, _streak: usize) -> usize {
+        self.limit = self.bounded(self.limit + self.step());
+        self.limit
+    }
+
+    fn recovered(&self) -> bool {
+        self.limit >= self.original
+    }
+
+    fn state(&self) -> (i32, usize) {
+        (self.original as i32, self.limit)
+    }
+
+    fn restore(&mut self, original: i32, position: usize) -> usize {
+        self.seed(original.max(0) as usize);
+        self.limit = self.bounded(position);
+        self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// drive the given strategy against a simulated target that starts erroring above
+    /// `capacity` requests/second; returns every limit chosen along the way
+    fn simulate(strategy: &mut dyn RateStrategy, seed: usize, capacity: usize) -> Vec<usize> {
+        let mut limit = strategy.seed(seed);
+        let mut limits = vec![limit];
+        let mut streak = 0;
+
+        for _ in 0..40 {
+            limit = if limit > capacity {
+                streak = 0;
+                strategy.decrease()
+            } else {
+                streak += 1;
+                strategy.increase(streak)
+            };
+
+            limits.push(limit);
+        }
+
+        limits
+    }
+
+    /// build the strategy with the given name and default settings
+    fn named(name: &str) -> Box<dyn RateStrategy> {
+        build(&Configuration {
+            tune_strategy: name.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// number of times the limit changed direction over the last `window` adjustments
+    fn reversals(limits: &[usize], window: usize) -> usize {
+        let recent = &limits[limits.len() - window..];
+
+        recent
+            .windows(3)
+            .filter(|w| (w[1] > w[0] && w[2] < w[1]) || (w[1] < w[0] && w[2] > w[1]))
+            .count()
+    }
+
+    #[test]
+    /// every strategy should stay within 1 and the seed rate when driven by a simulated target
+    fn rate_strategies_stay_within_bounds() {
+        for name in ["heap", "gradient", "aimd"] {
+            let mut strategy = named(name);
+            let limits = simulate(strategy.as_mut(), 400, 130);
+
+            assert!(
+                limits.iter().all(|limit| (1..=400).contains(limit)),
+                "{name}: {limits:?}"
+            );
+        }
+    }
+
+    #[test]
+    /// the gradient strategy should settle near the simulated target's capacity, with ever
+    /// smaller swings around it
+    fn gradient_strategy_converges_on_capacity() {
+        let mut strategy = named("gradient");
+        let limits = simulate(strategy.as_mut(), 400, 130);
+
+        let tail = &limits[limits.len() - 10..];
+        assert!(
+            tail.iter().all(|limit| (120..=140).contains(limit)),
+            "{limits:?}"
+        );
+    }
+
+    #[test]
+    /// against a target with room to spare, the gradient strategy should climb back to the
+    /// seed rate and report that the limit can be removed
+    fn gradient_strategy_recovers_without_errors() {
+        let mut strategy = GradientStep::default();
+        let limits = simulate(&mut strategy, 400, 1000);
+
+        assert_eq!(*limits.last().unwrap(), 400);
+        assert!(strategy.recovered());
+        assert_eq!(reversals(&limits, 20), 0);
+    }
+
+    #[test]
+    /// restoring from saved state should pick up at the saved limit
+    fn gradient_strategy_restores_state() {
+        let mut strategy = GradientStep::default();
+        strategy.seed(400);
+        strategy.decrease();
+
+        let (original, position) = strategy.state();
+        assert_eq!((original, position), (400, 50));
+
+        let mut restored = GradientStep::default();
+        assert_eq!(restored.restore(original, position), 50);
+        assert_eq!(restored.state(), (400, 50));
+
+        // bogus positions shouldn't go past the seed rate
+        assert_eq!(restored.restore(400, 1000), 400);
+    }
+
+    #[test]
+    /// aimd should climb by its step after clean intervals and halve on errors, settling into a
+    /// sawtooth just under the simulated target's capacity
+    fn aimd_strategy_climbs_and_halves() {
+        let mut strategy = Aimd::new(10, 0, 0);
+        assert_eq!(strategy.seed(400), 200);
+        assert_eq!(strategy.decrease(), 100);
+        assert_eq!(strategy.increase(1), 110);
+        assert_eq!(strategy.increase(2), 120);
+
+        let limits = simulate(&mut Aimd::new(10, 0, 0), 400, 130);
+        let tail = &limits[limits.len() - 10..];
+        assert!(
+            tail.iter().all(|limit| (70..=140).contains(limit)),
+            "{limits:?}"
+        );
+    }
+
+    #[test]
+    /// aimd should never leave the configured floor and ceiling
+    fn aimd_strategy_respects_floor_and_ceiling() {
+        let mut strategy = Aimd::new(50, 40, 250);
+        let limits = simulate(&mut strategy, 400, 10);
+        assert_eq!(*limits.last().unwrap(), 40);
+
+        let mut strategy = Aimd::new(50, 40, 250);
+        let limits = simulate(&mut strategy, 400, 1000);
+        assert_eq!(*limits.last().unwrap(), 250);
+
+        // capped below the seed rate, so the limit is never removed
+        assert!(!strategy.recovered());
+
+        // bogus positions are clamped as well
+        assert_eq!(strategy.restore(400, 1000), 250);
+        assert_eq!(strategy.restore(400, 0), 40);
+    }
+
+    #[test]
+    /// with no ceiling, aimd should climb back to the seed rate and report that the limit can be
+    /// removed
+    fn aimd_strategy_recovers_without_errors() {
+        let mut strategy = Aimd::new(0, 0, 0);
+        let limits = simulate(&mut strategy, 400, 1000);
+
+        // default step is 1/20th of the seed rate
+        assert_eq!(limits[1], 220);
+        assert_eq!(*limits.last().unwrap(), 400);
+        assert!(strategy.recovered());
+    }
+}
@@ -1,17 +1,20 @@
 use std::{
     cmp::max,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    fmt,
     sync::{
         self,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex,
     },
+    time::Instant,
 };
 
 use anyhow::Result;
 use console::style;
 use lazy_static::lazy_static;
 use leaky_bucket::RateLimiter;
+use reqwest::{StatusCode, Url};
 use tokio::{
     sync::RwLock,
     time::{sleep, Duration},
@@ -21,26 +24,130 @@ use crate::{
     atomic_load, atomic_store,
     config::RequesterPolicy,
     event_handlers::{
-        Command::{AddError, SubtractFromUsizeField},
+        Command::{self, AddError, AddStatus, AddToUsizeField, SubtractFromUsizeField},
         Handles,
     },
     extractor::{ExtractionTarget, ExtractorBuilder},
     nlp::{Document, TfIdf},
+    plugins,
+    policy_audit::PolicyDecision,
+    pruning, raw,
     response::FeroxResponse,
-    scan_manager::{FeroxScan, ScanStatus},
-    statistics::{StatError::Other, StatField::TotalExpected},
+    scan_manager::{latency_multiplier, FeroxScan, ScanStatus, TuningState},
+    search,
+    statistics::{
+        StatError::Other,
+        StatField::{RawErrors, RawRequests, TotalExpected},
+    },
+    template::Payload,
     url::FeroxUrl,
-    utils::{logged_request, send_try_recursion_command, should_deny_url},
-    HIGH_ERROR_RATIO,
+    utils::{
+        logged_request_for_scan, parse_url_with_raw_path, send_try_recursion_command,
+        should_deny_url,
+    },
+    DEFAULT_METHOD, REQUEUE_COOLDOWN,
 };
 
-use super::{policy_data::PolicyData, FeroxScanner, PolicyTrigger};
+use super::{policy_data::PolicyData, rate_strategy, FeroxScanner, PolicyTrigger};
+
+/// value of the Range header sent by --range-probe; asks for nothing but the first byte
+const RANGE_PROBE: &str = "bytes=0-0";
 
 lazy_static! {
     /// make sure to note that this is a std rwlock and not tokio
     pub(crate) static ref TF_IDF: Arc<sync::RwLock<TfIdf>> = Arc::new(sync::RwLock::new(TfIdf::new()));
 }
 
+/// --rate-limit-global; shared by every Requester, on top of each scan's own limiter
+#[derive(Default)]
+pub struct GlobalRateLimit {
+    /// `None` when --rate-limit-global wasn't used
+    limiter: Option<RateLimiter>,
+}
+
+/// Debug implementation for GlobalRateLimit; the limiter has none of its own
+impl fmt::Debug for GlobalRateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalRateLimit")
+            .field("limiter", &self.limiter.is_some())
+            .finish()
+    }
+}
+
+/// implementation of GlobalRateLimit
+impl GlobalRateLimit {
+    /// set up the --rate-limit-global limiter; 0 means no limit
+    pub fn new(limit: usize) -> Result<Self> {
+        if limit == 0 {
+            return Ok(Self::default());
+        }
+
+        Ok(Self {
+            limiter: Some(Requester::build_a_bucket(limit)?),
+        })
+    }
+}
+
+/// What a word is requested as: its urls, and the headers and body each of them is sent with
+pub(super) struct WordRequests {
+    /// urls the word expands to
+    pub(super) urls: Vec<Url>,
+
+    /// headers sent along with the client's defaults
+    pub(super) headers: Vec<(String, String)>,
+
+    /// body sent with each request
+    pub(super) data: Vec<u8>,
+
+    /// FUZZ keywords in use; the word filled in the target, headers, and data
+    pub(super) templated: bool,
+}
+
+/// WordRequests implementation
+impl WordRequests {
+    /// expand the given word (or only the given variant of it) against the target
+    pub(super) fn new(
+        target_url: &str,
+        word: &str,
+        variant: Option<usize>,
+        collected: HashSet<String>,
+        mut headers: Vec<(String, String)>,
+        handles: &Arc<Handles>,
+    ) -> Result<Self> {
+        // FUZZ keywords in use; the word is a combination that fills in the target, headers,
+        // and data instead of being added to the end of the target
+        let templating = &handles.features.template;
+        let payload = templating
+            .is_active()
+            .then(|| Payload::from_word(word, templating));
+
+        let urls = match (&payload, variant) {
+            (Some(payload), _) => vec![parse_url_with_raw_path(&payload.render(target_url))?],
+            (None, None) => FeroxUrl::from_string(target_url, handles.clone())
+                .formatted_urls(word, collected)?,
+            (None, Some(variant)) => FeroxUrl::from_string(target_url, handles.clone())
+                .formatted_url_groups(word, collected)?
+                .into_iter()
+                .nth(variant)
+                .unwrap_or_default(),
+        };
+
+        let mut data = handles.config.data.clone();
+
+        if let Some(payload) = &payload {
+            headers.extend(payload.headers(&handles.config));
+            data = payload.data(&data);
+        }
+
+        Ok(Self {
+            urls,
+            headers,
+            data,
+            templated: payload.is_some(),
+        })
+    }
+}
+
 /// Makes multiple requests based on the presence of extensions
 pub(super) struct Requester {
     /// handles to handlers and config
@@ -71,13 +178,61 @@ pub(super) struct Requester {
     tuning_lock: Mutex<usize>,
 
     policy_triggered: AtomicBool,
+
+    /// generation of `Handles.runtime` plus the generation of the scan's own overrides that
+    /// were last applied to this Requester's policy_data and rate_limiter; both only ever go up,
+    /// so the sum changes whenever either of them does
+    settings_generation: AtomicUsize,
 }
 
 /// Requester implementation
 impl Requester {
     /// given a FeroxScanner, create a Requester
     pub fn from(scanner: &FeroxScanner, ferox_scan: Arc<FeroxScan>) -> Result<Self> {
-        let limit = scanner.handles.config.rate_limit;
+        let policy_data = PolicyData::new(
+            scanner.handles.runtime.policy(),
+            scanner.handles.config.timeout,
+        )
+        .with_strategy(rate_strategy::build(&scanner.handles.config))
+        .with_thresholds(
+            scanner.handles.config.policy_403_ratio,
+            scanner.handles.config.policy_429_ratio,
+            scanner.handles.config.policy_error_threshold,
+        );
+
+        let mut limit = scanner.handles.runtime.rate_limit();
+        let mut policy_triggered = false;
+
+        if let Some(tuning) = ferox_scan.tuning_state() {
+            // resumed scan, pick up with the limits it had reached
+            if tuning.policy_triggered {
+                policy_data.restore(tuning.original_rate, tuning.heap_position, tuning.errors);
+            }
+
+            limit = tuning.rate_limit;
+            policy_triggered = tuning.policy_triggered;
+        } else if let Some(tuning) = Self::inherited_tuning(scanner, &policy_data) {
+            // another scan already tuned this host down, start out where it got to
+            log::info!(
+                "{} starts at {}/s, inherited from an earlier scan of its host",
+                scanner.target_url,
+                tuning.rate_limit
+            );
+
+            policy_data.restore(tuning.original_rate, tuning.heap_position, tuning.errors);
+            limit = tuning.rate_limit;
+            policy_triggered = true;
+
+            ferox_scan.set_tuning_state(tuning);
+        }
+
+        // shown on the scan's bar, and added up on the overall one
+        ferox_scan.limit_gauge().start_at(limit);
+        scanner
+            .handles
+            .features
+            .rate_limits
+            .register(ferox_scan.limit_gauge());
 
         let rate_limiter = if limit > 0 {
             Some(Self::build_a_bucket(limit)?)
@@ -85,11 +240,6 @@ impl Requester {
             None
         };
 
-        let policy_data = PolicyData::new(
-            scanner.handles.config.requester_policy,
-            scanner.handles.config.timeout,
-        );
-
         Ok(Self {
             ferox_scan,
             policy_data,
@@ -98,10 +248,26 @@ impl Requester {
             handles: scanner.handles.clone(),
             target_url: scanner.target_url.to_owned(),
             tuning_lock: Mutex::new(0),
-            policy_triggered: AtomicBool::new(false),
+            policy_triggered: AtomicBool::new(policy_triggered),
+            settings_generation: AtomicUsize::new(scanner.handles.runtime.generation()),
         })
     }
 
+    /// the tuning state an earlier scan of the same host reached, when auto-tune is in charge
+    /// of this scan's rate
+    fn inherited_tuning(scanner: &FeroxScanner, policy_data: &PolicyData) -> Option<TuningState> {
+        if policy_data.policy() != RequesterPolicy::AutoTune {
+            return None;
+        }
+
+        scanner
+            .handles
+            .ferox_scans()
+            .ok()?
+            .host_tuning
+            .inherit(&scanner.target_url)
+    }
+
     /// build a RateLimiter, given a rate limit (as requests per second)
     fn build_a_bucket(limit: usize) -> Result<RateLimiter> {
         let refill = max((limit as f64 / 10.0).round() as usize, 1); // minimum of 1 per second
@@ -131,6 +297,13 @@ impl Requester {
         atomic_store!(self.policy_data.cooling_down, false, Ordering::SeqCst);
     }
 
+    /// limit the number of requests per second across all scans combined (--rate-limit-global)
+    async fn limit_global(&self) {
+        if let Some(limiter) = &self.handles.features.global_rate_limit.limiter {
+            limiter.acquire_one().await;
+        }
+    }
+
     /// limit the number of requests per second
     pub async fn limit(&self) -> Result<()> {
         let guard = self.rate_limiter.read().await;
@@ -143,36 +316,75 @@ impl Requester {
     }
 
     /// small function to break out different error checking mechanisms
+    ///
+    /// each error counts by the weight of its category (--policy-error-weight)
     fn too_many_errors(&self) -> bool {
-        let total = self.ferox_scan.num_errors(PolicyTrigger::Errors);
+        let total = self
+            .ferox_scan
+            .num_errors(PolicyTrigger::Errors, &self.handles.features.error_weights);
 
-        // at least 25 errors
-        let threshold = max(self.handles.config.threads / 2, 25);
+        total >= self.error_threshold()
+    }
 
-        total >= threshold
+    /// number of (weighted) errors that triggers a policy; at least 25 errors (by default)
+    fn error_threshold(&self) -> usize {
+        max(
+            self.handles.config.threads / 2,
+            self.policy_data.error_threshold,
+        )
     }
 
     /// small function to break out different error checking mechanisms
     fn too_many_status_errors(&self, trigger: PolicyTrigger) -> bool {
-        let total = self.ferox_scan.num_errors(trigger);
-        let requests = self.ferox_scan.requests();
+        match self.status_ratio_threshold(trigger) {
+            Some(threshold) => self.status_ratio(trigger) >= threshold,
+            None => false,
+        }
+    }
 
-        let ratio = total as f64 / requests as f64;
+    /// share of the scan's requests (since it was last requeued) counted against the trigger
+    fn status_ratio(&self, trigger: PolicyTrigger) -> f64 {
+        let total = self
+            .ferox_scan
+            .num_errors(trigger, &self.handles.features.error_weights);
+        let requests = self.ferox_scan.requests_since_requeue();
+
+        total as f64 / requests as f64
+    }
 
+    /// ratio at which the given status code trigger fires; `None` for other triggers
+    fn status_ratio_threshold(&self, trigger: PolicyTrigger) -> Option<f64> {
         match trigger {
-            PolicyTrigger::Status403 => ratio >= HIGH_ERROR_RATIO,
-            PolicyTrigger::Status429 => ratio >= HIGH_ERROR_RATIO / 3.0,
-            _ => false,
+            PolicyTrigger::Status403 => Some(self.policy_data.ratio_403),
+            PolicyTrigger::Status429 | PolicyTrigger::Status(_) => Some(self.policy_data.ratio_429),
+            _ => None,
         }
     }
 
     /// determine whether or not a policy needs to be enforced
     ///
-    /// criteria:
+    /// criteria (thresholds are configurable, defaults shown):
     /// - number of threads (50 default) for general errors (timeouts etc)
-    /// - 90% of requests are 403
-    /// - 30% of requests are 429
+    /// - 90% of requests are 403 (--policy-403-ratio)
+    /// - 30% of requests are 429 (--policy-429-ratio)
+    /// - 30% of requests are any one of the --policy-status codes (--policy-429-ratio)
+    /// - p95 response time past a multiple of the baseline (--tune-on-latency)
+    /// - a host's Server/X-Powered-By header changed since the scan was (re)started
+    ///   (--server-alerts)
+    ///
+    /// every trigger that fires is recorded, along with the counters behind it (--policy-audit)
     fn should_enforce_policy(&self) -> Option<PolicyTrigger> {
+        let trigger = self.policy_trigger()?;
+
+        if let Some(audit) = &self.handles.features.policy_audit {
+            audit.record(&self.policy_decision(trigger));
+        }
+
+        Some(trigger)
+    }
+
+    /// the first of the criteria listed on `should_enforce_policy` that's been met, if any
+    fn policy_trigger(&self) -> Option<PolicyTrigger> {
         if atomic_load!(self.policy_data.cooling_down, Ordering::SeqCst) {
             // prevents a few racy threads making it in here and doubling the wait time erroneously
             return None;
@@ -186,6 +398,14 @@ impl Requester {
             return None;
         }
 
+        if self.ferox_scan.requeues() > 0
+            && self.ferox_scan.requests_since_requeue()
+                < max(self.handles.config.threads, 50) as u64
+        {
+            // same idea, but a requeued scan's ratios start over, so it needs a round of its own
+            return None;
+        }
+
         if self.too_many_errors() {
             return Some(PolicyTrigger::Errors);
         }
@@ -198,12 +418,73 @@ impl Requester {
             return Some(PolicyTrigger::Status429);
         }
 
+        for code in self.handles.config.policy_status.iter() {
+            if self.too_many_status_errors(PolicyTrigger::Status(*code)) {
+                return Some(PolicyTrigger::Status(*code));
+            }
+        }
+
+        if let Some(multiplier) = latency_multiplier(&self.handles.config.tune_on_latency) {
+            if self.ferox_scan.latency().is_overloaded(multiplier) {
+                return Some(PolicyTrigger::Latency);
+            }
+        }
+
+        if self.handles.config.server_alerts
+            && self.ferox_scan.num_errors(
+                PolicyTrigger::ServerChange,
+                &self.handles.features.error_weights,
+            ) > 0
+        {
+            return Some(PolicyTrigger::ServerChange);
+        }
+
         None
     }
 
+    /// the counters that led to the given trigger firing, for --policy-audit
+    fn policy_decision(&self, trigger: PolicyTrigger) -> PolicyDecision {
+        let mut decision = PolicyDecision {
+            url: self.target_url.clone(),
+            policy: format!("{:?}", self.policy_data.policy()),
+            trigger: format!("{trigger:?}"),
+            requests: atomic_load!(self.handles.stats.data.requests),
+            scan_requests: self.ferox_scan.requests_since_requeue(),
+            count: self
+                .ferox_scan
+                .num_errors(trigger, &self.handles.features.error_weights),
+            rate_limit: self.ferox_scan.limit_gauge().limit(),
+            requeues: self.ferox_scan.requeues(),
+            ..Default::default()
+        };
+
+        match trigger {
+            PolicyTrigger::Errors => decision.threshold = self.error_threshold() as f64,
+            PolicyTrigger::Status403 | PolicyTrigger::Status429 | PolicyTrigger::Status(_) => {
+                decision.ratio = Some(self.status_ratio(trigger));
+                decision.threshold = self.status_ratio_threshold(trigger).unwrap_or_default();
+            }
+            PolicyTrigger::Latency => {
+                let latency = self.ferox_scan.latency();
+
+                decision.count = latency.slow_responses();
+                decision.threshold =
+                    latency_multiplier(&self.handles.config.tune_on_latency).unwrap_or_default();
+                decision.p95_ms = latency.p95().map(|p95| p95.as_millis());
+                decision.baseline_ms = latency.baseline().map(|baseline| baseline.as_millis());
+            }
+            PolicyTrigger::ServerChange => decision.threshold = 1.0,
+            PolicyTrigger::TryAdjustUp => {}
+        }
+
+        decision
+    }
+
     /// wrapper for adjust_[up,down] functions, checks error levels to determine adjustment direction
     async fn adjust_limit(&self, trigger: PolicyTrigger, create_limiter: bool) -> Result<()> {
-        let scan_errors = self.ferox_scan.num_errors(trigger);
+        let scan_errors = self
+            .ferox_scan
+            .num_errors(trigger, &self.handles.features.error_weights);
         let policy_errors = atomic_load!(self.policy_data.errors, Ordering::SeqCst);
 
         if let Ok(mut guard) = self.tuning_lock.try_lock() {
@@ -254,20 +535,100 @@ impl Requester {
     async fn set_rate_limiter(&self, new_limit: Option<usize>) -> Result<()> {
         let mut guard = self.rate_limiter.write().await;
 
-        let new_bucket = if new_limit.is_none() {
+        let new_bucket = match new_limit {
             // got None, need to remove the rate_limiter
-            None
-        } else if guard.is_some() && guard.as_ref().unwrap().max() == new_limit.unwrap() {
-            // new_limit is checked for None in first branch, should be fine to unwrap
+            None => None,
+            Some(limit) if guard.as_ref().is_some_and(|bucket| bucket.max() == limit) => {
+                // this function is called more often than i'd prefer due to Send requirements of
+                // mutex/rwlock primitives and awaits, this will minimize the cost of the extra calls
+                return Ok(());
+            }
+            Some(limit) => Some(Self::build_a_bucket(limit)?),
+        };
+
+        let rate_limit = new_bucket.as_ref().map_or(0, |bucket| bucket.max());
 
-            // this function is called more often than i'd prefer due to Send requirements of
-            // mutex/rwlock primitives and awaits, this will minimize the cost of the extra calls
+        if guard.is_some() || new_bucket.is_some() {
+            // removing a limiter that was never there isn't worth mentioning
+            self.ferox_scan.add_tuning_event(rate_limit);
+        }
+
+        self.handles
+            .features
+            .rate_limits
+            .set(self.ferox_scan.limit_gauge(), rate_limit);
+
+        *guard = new_bucket;
+
+        self.save_tuning_state(rate_limit);
+        Ok(())
+    }
+
+    /// record the current rate limiting/auto-tune state on the associated FeroxScan, so it can
+    /// be written to the state file
+    fn save_tuning_state(&self, rate_limit: usize) {
+        let (original_rate, heap_position) = self.policy_data.heap_state();
+
+        let state = TuningState {
+            rate_limit,
+            original_rate,
+            heap_position,
+            errors: atomic_load!(self.policy_data.errors),
+            policy_triggered: atomic_load!(self.policy_triggered),
+        };
+
+        self.ferox_scan.set_tuning_state(state);
+
+        // --auto-tune; scans of the same host started after this one pick up where it got to
+        if let Ok(scans) = self.handles.ferox_scans() {
+            scans.host_tuning.record(&self.target_url, state);
+        }
+    }
+
+    /// requests per second allowed for this scan by the user, 0 means unlimited; the scan's own
+    /// limit takes precedence over the one shared by every scan
+    fn rate_limit(&self) -> usize {
+        self.ferox_scan
+            .overrides()
+            .rate_limit
+            .unwrap_or_else(|| self.handles.runtime.rate_limit())
+    }
+
+    /// pick up any settings changed mid-scan (i.e. via the interactive menu)
+    ///
+    /// a user-supplied rate limit replaces whatever limit is in place, including one set by
+    /// auto-tune; removing the limit also resets auto-tune so it can start over from the scan's
+    /// current speed. settings picked for this scan alone take precedence over the ones shared
+    /// by every scan
+    async fn sync_runtime_settings(&self) -> Result<()> {
+        let overrides = self.ferox_scan.overrides();
+        let generation = self.handles.runtime.generation() + overrides.generation;
+
+        if atomic_load!(self.settings_generation) == generation {
             return Ok(());
-        } else {
-            Some(Self::build_a_bucket(new_limit.unwrap())?)
+        }
+
+        atomic_store!(self.settings_generation, generation);
+
+        let policy = match overrides.auto_tune {
+            Some(enabled) => self.handles.runtime.auto_tune_policy(enabled),
+            None => self.handles.runtime.policy(),
         };
+        let limit = self.rate_limit();
+
+        if policy != self.policy_data.policy() || limit == 0 {
+            // auto-tune's bookkeeping is only meaningful for the policy/limit it was built for
+            self.policy_data.set_policy(policy);
+            self.policy_data.set_errors(0);
+            atomic_store!(self.policy_triggered, false);
+        }
+
+        if limit > 0 {
+            self.set_rate_limiter(Some(limit)).await?;
+        } else {
+            self.set_rate_limiter(None).await?;
+        }
 
-        let _ = std::mem::replace(&mut *guard, new_bucket);
         Ok(())
     }
 
@@ -295,17 +656,77 @@ impl Requester {
         Ok(())
     }
 
+    /// enforce auto-requeue policy; set the scan aside and have the scan handler restart it at
+    /// half its rate once the cooldown has passed
+    ///
+    /// runs within the scan's own task, which is aborted along the way, so nothing here can be
+    /// awaited
+    fn requeue(&self, trigger: PolicyTrigger) {
+        let current = match self.ferox_scan.tuning_state() {
+            Some(tuning) if tuning.rate_limit > 0 => tuning.rate_limit,
+            _ => match self.rate_limit() {
+                0 => self.ferox_scan.requests_per_second() as usize,
+                limit => limit,
+            },
+        };
+
+        let new_limit = max(current / 2, 1);
+
+        // picked up by the restarted scan's Requester
+        self.ferox_scan.set_tuning_state(TuningState {
+            rate_limit: new_limit,
+            ..Default::default()
+        });
+
+        let attempt = self.ferox_scan.requeues() + 1;
+        let cooldown = REQUEUE_COOLDOWN * attempt as u64;
+
+        self.ferox_scan.progress_bar().set_message(format!(
+            "=> ⏳ too many {} ({}), requeued at {new_limit}/s in {cooldown}s ({attempt}/{})",
+            style(format!("{trigger:?}")).red(),
+            self.ferox_scan
+                .num_errors(trigger, &self.handles.features.error_weights),
+            self.handles.config.auto_requeue,
+        ));
+
+        self.ferox_scan
+            .requeue()
+            .unwrap_or_else(|e| log::warn!("Could not requeue scan: {}", e));
+
+        let handles = self.handles.clone();
+        let url = self.ferox_scan.url().to_string();
+
+        // detached from the scan's task, so it survives the abort
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(cooldown)).await;
+
+            handles
+                .send_scan_command(Command::RequeueScan(url))
+                .unwrap_or_else(|e| log::warn!("Could not restart requeued scan: {}", e));
+        });
+    }
+
     /// enforce auto-bail policy
+    ///
+    /// with --auto-requeue, the scan is requeued instead, until it runs out of attempts
     async fn bail(&self, trigger: PolicyTrigger) -> Result<()> {
-        if self.ferox_scan.is_active() {
+        if self.ferox_scan.is_active() && !self.ferox_scan.is_requeued() {
             log::warn!(
                 "too many {:?} ({}) triggered {:?} Policy on {}",
                 trigger,
-                self.ferox_scan.num_errors(trigger),
-                self.handles.config.requester_policy,
+                self.ferox_scan
+                    .num_errors(trigger, &self.handles.features.error_weights),
+                self.policy_data.policy(),
                 self.ferox_scan
             );
 
+            if self.policy_data.policy() == RequesterPolicy::AutoRequeue
+                && self.ferox_scan.requeues() < self.handles.config.auto_requeue
+            {
+                self.requeue(trigger);
+                return Ok(());
+            }
+
             // if allowed to be called within .abort, the inner .await makes it so other
             // in-flight requests don't see the Cancelled status, doing it here ensures a
             // minimum number of requests entering this block
@@ -313,6 +734,8 @@ impl Requester {
                 .set_status(ScanStatus::Cancelled)
                 .unwrap_or_else(|e| log::warn!("Could not set scan status: {}", e));
 
+            self.ferox_scan.mark_bailed();
+
             // kill the scan
             self.ferox_scan
                 .abort()
@@ -328,7 +751,8 @@ impl Requester {
             pb.set_message(format!(
                 "=> 💀 too many {} ({}) 💀 bailing",
                 styled_trigger,
-                self.ferox_scan.num_errors(trigger),
+                self.ferox_scan
+                    .num_errors(trigger, &self.handles.features.error_weights),
             ));
 
             // update the overall scan bar by subtracting the number of skipped requests from
@@ -342,28 +766,185 @@ impl Requester {
         Ok(())
     }
 
+    /// --range-probe; request only the first byte of the given url to learn its status and size,
+    /// fetching the full body only when the response won't be filtered on those alone
+    ///
+    /// servers that ignore Range, as well as non-2xx responses, answer with the full response
+    /// right away, in which case no second request is made
+    async fn range_probe(&self, url: &Url, data: Option<&[u8]>) -> Result<FeroxResponse> {
+        log::trace!("enter: range_probe({}, {:?})", url, data);
+
+        let headers = self.ferox_scan.headers();
+
+        let mut range = headers.clone();
+        range.push((String::from("Range"), String::from(RANGE_PROBE)));
+
+        let response = logged_request_for_scan(
+            url,
+            "GET",
+            data,
+            &range,
+            &self.ferox_scan,
+            self.handles.clone(),
+        )
+        .await?;
+
+        let mut probe = FeroxResponse::from(response, &self.target_url, "GET", &self.handles).await;
+
+        let needs_body = match probe.resolve_range_probe() {
+            // the probe already holds the entire body when there's at most one byte of it
+            Some(size) => size > 1 && !self.handles.filters.data.filters_without_body(&probe),
+            // an empty resource can't satisfy the range, the real status comes with a full GET
+            None => probe.status() == &StatusCode::RANGE_NOT_SATISFIABLE,
+        };
+
+        if !needs_body {
+            log::trace!("exit: range_probe -> {}", probe);
+            return Ok(probe);
+        }
+
+        // only one request per url was expected, the overall bar needs to know about the second
+        self.handles
+            .stats
+            .send(AddToUsizeField(TotalExpected, 1))
+            .unwrap_or_default();
+
+        let response = logged_request_for_scan(
+            url,
+            "GET",
+            data,
+            &headers,
+            &self.ferox_scan,
+            self.handles.clone(),
+        )
+        .await?;
+        let ferox_response =
+            FeroxResponse::from(response, &self.target_url, "GET", &self.handles).await;
+
+        log::trace!("exit: range_probe -> {}", ferox_response);
+        Ok(ferox_response)
+    }
+
+    /// --prune-extensions; tally a response to a url expanded with `extension`, letting the
+    /// user know when that got the extension dropped on the url's host
+    fn record_extension(&self, url: &Url, extension: &str, hit: bool) {
+        if self.handles.features.pruning.record(
+            url,
+            extension,
+            hit,
+            self.handles.config.prune_after,
+        ) {
+            self.ferox_scan
+                .progress_bar()
+                .set_message(format!("=> ✂️ dropped dead extension .{extension}"));
+        }
+    }
+
     /// Wrapper for make_request
     ///
     /// Attempts recursion when appropriate and sends Responses to the output handler for processing
-    pub async fn request(&self, word: &str) -> Result<()> {
-        log::trace!("enter: request({})", word);
+    ///
+    /// `variant` limits the requests to a single group from `FeroxUrl::formatted_url_groups`
+    /// (--extension-order), `None` requests all of them; returns the number of urls requested
+    pub async fn request(&self, word: &str, variant: Option<usize>) -> Result<usize> {
+        log::trace!("enter: request({}, {:?})", word, variant);
+
+        // keeps the smoothed reqs/sec current for tuning, even when the bar isn't drawn
+        self.ferox_scan.sample_rate();
 
         let collected = self.handles.collected_extensions();
 
-        let urls = FeroxUrl::from_string(&self.target_url, self.handles.clone())
-            .formatted_urls(word, collected)?;
+        // --prune-extensions; extensions the urls may have been expanded with
+        let prunable: Vec<String> = if self.handles.config.prune_extensions {
+            self.handles
+                .config
+                .extensions
+                .iter()
+                .chain(collected.iter())
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // --raw-requests; words the url parser would rewrite go out byte for byte instead
+        if self.handles.config.raw_requests
+            && !self.handles.features.template.is_active()
+            && raw::needs_raw(word)
+        {
+            return self.raw_request(word, variant, collected).await;
+        }
+
+        let WordRequests {
+            urls,
+            headers,
+            data,
+            templated,
+        } = WordRequests::new(
+            &self.target_url,
+            word,
+            variant,
+            collected,
+            self.ferox_scan.headers(),
+            &self.handles,
+        )?;
+
+        let num_urls = urls.len();
+
+        // --tune-on-latency; how many times the baseline a response may take
+        let latency_multiplier = latency_multiplier(&self.handles.config.tune_on_latency);
 
         let should_test_deny = !self.handles.config.url_denylist.is_empty()
             || !self.handles.config.regex_denylist.is_empty();
 
+        // --dual-slash; status/size of the responses to the bare word, per method, so that
+        // word/ can be compared against them
+        let mut bare_responses = HashMap::new();
+
         for url in urls {
+            let extension = pruning::extension_of(&url, word, &prunable);
+
+            if let Some(ext) = &extension {
+                if self.handles.features.pruning.is_pruned(&url, ext) {
+                    // dropped on this host; the url was counted on when the scan's expected
+                    // total was calculated, once per method, so those need to come back off
+                    self.handles
+                        .stats
+                        .send(SubtractFromUsizeField(
+                            TotalExpected,
+                            self.handles.config.methods.len(),
+                        ))
+                        .unwrap_or_default();
+                    continue;
+                }
+            }
+
+            if should_test_deny && should_deny_url(&url, &self.handles)? {
+                // can't allow a denied url to be requested; checked ahead of the rate limiters
+                // so it doesn't use up a token. it was counted on when the scan's expected total
+                // was calculated, once per method, so those need to come back off
+                self.handles
+                    .stats
+                    .send(SubtractFromUsizeField(
+                        TotalExpected,
+                        self.handles.config.methods.len(),
+                    ))
+                    .unwrap_or_default();
+                continue;
+            }
+
             for method in self.handles.config.methods.iter() {
+                self.sync_runtime_settings().await?;
+
+                let policy = self.policy_data.policy();
+
                 // auto_tune is true, or rate_limit was set (mutually exclusive to user)
                 // and a rate_limiter has been created
                 // short-circuiting the lock access behind the first boolean check
-                let should_tune =
-                    self.handles.config.auto_tune || self.handles.config.rate_limit > 0;
-                let should_limit = should_tune && self.rate_limiter.read().await.is_some();
+                let should_tune = policy == RequesterPolicy::AutoTune || self.rate_limit() > 0;
+                // a requeued scan comes back with a limiter of its own
+                let should_limit = (should_tune || policy == RequesterPolicy::AutoRequeue)
+                    && self.rate_limiter.read().await.is_some();
 
                 if should_limit {
                     // found a rate limiter, limit that junk!
@@ -373,27 +954,84 @@ impl Requester {
                     }
                 }
 
-                if should_test_deny && should_deny_url(&url, self.handles.clone())? {
-                    // can't allow a denied url to be requested
-                    continue;
-                }
+                // the scan's own limit is waited on first, so a scan held back by it doesn't
+                // sit on a token the other scans could be using
+                self.limit_global().await;
 
-                let data = if self.handles.config.data.is_empty() {
+                // --pacing; waited on after the rate limiters, so a burst is still sent no
+                // faster than they allow
+                self.handles.features.pacing.pace(&self.ferox_scan).await;
+
+                // --bandwidth-limit; the expected size of the response is set aside up front, so
+                // requests already in flight are accounted for
+                let reserved = self.handles.features.bandwidth.throttle().await;
+
+                let data = if data.is_empty() {
                     None
                 } else {
-                    Some(self.handles.config.data.as_slice())
+                    Some(data.as_slice())
+                };
+
+                let started = Instant::now();
+
+                let mut ferox_response = if self.handles.config.range_probe && method == "GET" {
+                    self.range_probe(&url, data).await?
+                } else {
+                    let response = logged_request_for_scan(
+                        &url,
+                        method.as_str(),
+                        data,
+                        &headers,
+                        &self.ferox_scan,
+                        self.handles.clone(),
+                    )
+                    .await?;
+
+                    // response came back without error, convert it to FeroxResponse
+                    FeroxResponse::from(response, &self.target_url, method, &self.handles).await
                 };
 
-                let response =
-                    logged_request(&url, method.as_str(), data, self.handles.clone()).await?;
+                if let Some(multiplier) = latency_multiplier {
+                    self.ferox_scan
+                        .latency()
+                        .record(started.elapsed(), multiplier);
+                }
+
+                self.handles
+                    .features
+                    .bandwidth
+                    .consume(&ferox_response, reserved);
+
+                if let Some(error) = ferox_response.body_read_error() {
+                    // the response itself was already counted, only the error is added
+                    self.handles.stats.send(AddError(error)).unwrap_or_default();
+                    self.ferox_scan.add_error(error);
+                }
+
+                // matches are reported whether or not the response makes it past the filters
+                search::report(&mut ferox_response, &self.handles);
+
+                if self.handles.config.server_alerts {
+                    // a different server answering mid-scan is reported, and counts toward the
+                    // scan's policy the same as a run of 403s would
+                    for change in self.handles.features.server_watch.observe(&ferox_response) {
+                        self.ferox_scan.add_server_change();
+                        self.handles
+                            .output
+                            .send(Command::ReportServerChange(Box::new(change)))
+                            .unwrap_or_default();
+                    }
+                }
 
-                if (should_tune || self.handles.config.auto_bail)
+                if (should_tune
+                    || policy == RequesterPolicy::AutoBail
+                    || policy == RequesterPolicy::AutoRequeue)
                     && !atomic_load!(self.policy_data.cooling_down, Ordering::SeqCst)
                 {
                     // only check for policy enforcement when the trigger isn't on cooldown and tuning
                     // or bailing is in place (should_tune used here because when auto-tune is on, we'll
                     // reach this without a rate_limiter in place)
-                    match self.policy_data.policy {
+                    match policy {
                         RequesterPolicy::AutoTune => {
                             if let Some(trigger) = self.should_enforce_policy() {
                                 self.tune(trigger).await?;
@@ -402,7 +1040,7 @@ impl Requester {
                                 self.cool_down().await;
                             }
                         }
-                        RequesterPolicy::AutoBail => {
+                        RequesterPolicy::AutoBail | RequesterPolicy::AutoRequeue => {
                             if let Some(trigger) = self.should_enforce_policy() {
                                 self.bail(trigger).await?;
                             }
@@ -411,17 +1049,16 @@ impl Requester {
                     }
                 }
 
-                // response came back without error, convert it to FeroxResponse
-                let mut ferox_response = FeroxResponse::from(
-                    response,
-                    &self.target_url,
-                    method,
-                    self.handles.config.output_level,
-                )
-                .await;
+                // templated requests aren't directories of the target, there's nothing to recurse into
+                let recurse = !templated
+                    && (!self.handles.config.dual_slash
+                        || dual_slash_allows_recursion(&mut bare_responses, &ferox_response));
 
                 // do recursion if appropriate
-                if !self.handles.config.no_recursion && !self.handles.config.force_recursion {
+                if recurse
+                    && !self.handles.config.no_recursion
+                    && !self.handles.config.force_recursion
+                {
                     // to support --force-recursion, we want to limit recursive calls to only
                     // 'found' assets. That means we need to either gate or delay the call.
                     //
@@ -431,18 +1068,47 @@ impl Requester {
                         .await?;
                 }
 
+                // --record used; the response is recorded as the filters are about to see it
+                if let Some(recorder) = &self.handles.features.session {
+                    recorder.record(&ferox_response);
+                }
+
                 // purposefully doing recursion before filtering. the thought process is that
                 // even though this particular url is filtered, subsequent urls may not
-                if self
+                if let Some(filter) = self
                     .handles
                     .filters
                     .data
-                    .should_filter_response(&ferox_response, self.handles.stats.tx.clone())
+                    .matching_filter(&ferox_response, self.handles.stats.tx.clone())
+                    .or_else(|| {
+                        // --plugin used; on_response hooks may filter or tag the response
+                        plugins::on_response(
+                            &self.handles.features.plugins,
+                            &mut ferox_response,
+                            self.handles.stats.tx.clone(),
+                        )
+                    })
                 {
+                    if let Some(ext) = &extension {
+                        self.record_extension(&url, ext, false);
+                    }
+
+                    if !self.handles.config.quiet_stream.is_empty() {
+                        // --quiet-stream used, filtered responses still get written there
+                        ferox_response
+                            .send_filtered_report(self.handles.output.tx.clone(), filter)?;
+                    }
                     continue;
                 }
 
-                if !self.handles.config.no_recursion && self.handles.config.force_recursion {
+                if let Some(ext) = &extension {
+                    self.record_extension(&url, ext, true);
+                }
+
+                if recurse
+                    && !self.handles.config.no_recursion
+                    && self.handles.config.force_recursion
+                {
                     // in this branch, we're saying that both recursion AND force recursion
                     // are turned on. It comes after should_filter_response, so those cases
                     // are handled. Now we need to account for -s/-C options.
@@ -523,55 +1189,307 @@ impl Requester {
                 }
 
                 // everything else should be reported
-                if let Err(e) = ferox_response.send_report(self.handles.output.tx.clone()) {
+                if let Err(e) = ferox_response.send_report(
+                    self.handles.output.tx.clone(),
+                    self.handles.config.exports_bodies(),
+                ) {
                     log::warn!("Could not send FeroxResponse to output handler: {}", e);
                 }
             }
         }
 
-        log::trace!("exit: request");
-        Ok(())
+        log::trace!("exit: request -> {}", num_urls);
+        Ok(num_urls)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::time::Instant;
-
-    use reqwest::StatusCode;
 
-    use crate::{
-        config::Configuration,
-        config::OutputLevel,
-        event_handlers::Command::AddStatus,
-        event_handlers::{FiltersHandler, ScanHandler, StatsHandler, Tasks, TermOutHandler},
-        filters,
-        scan_manager::{ScanOrder, ScanType},
-        statistics::StatError,
-    };
+    /// --raw-requests; send the word (or only the given variant of it) as-is, outside of the
+    /// client; responses go through the filters and are reported, tagged raw, but aren't
+    /// recursed into
+    async fn raw_request(
+        &self,
+        word: &str,
+        variant: Option<usize>,
+        collected: HashSet<String>,
+    ) -> Result<usize> {
+        log::trace!("enter: raw_request({}, {:?})", word, variant);
+
+        let groups = raw::request_targets(&self.target_url, word, collected, &self.handles.config)?;
+
+        let targets: Vec<String> = match variant {
+            Some(variant) => groups.into_iter().nth(variant).unwrap_or_default(),
+            None => groups.into_iter().flatten().collect(),
+        };
 
-    use super::*;
+        let base = parse_url_with_raw_path(&self.target_url)?;
+        let headers = self.ferox_scan.headers();
+
+        for target in &targets {
+            // what's reported; the url parser's take on the target, the bytes sent are the
+            // target itself
+            let display_url = base.join(target)?;
+
+            if should_deny_url(&display_url, &self.handles)? {
+                // checked ahead of the rate limiters, so a denied target doesn't use up a token
+                self.handles
+                    .stats
+                    .send(SubtractFromUsizeField(
+                        TotalExpected,
+                        self.handles.config.methods.len(),
+                    ))
+                    .unwrap_or_default();
+                continue;
+            }
 
-    /// helper to setup a realistic requester test
-    async fn setup_requester_test(config: Option<Arc<Configuration>>) -> (Arc<Handles>, Tasks) {
-        // basically C&P from main::wrapped_main, can look there for comments etc if needed
-        let configuration = config.unwrap_or_else(|| Arc::new(Configuration::new().unwrap()));
+            for method in self.handles.config.methods.iter() {
+                self.sync_runtime_settings().await?;
 
-        let (stats_task, stats_handle) = StatsHandler::initialize(configuration.clone());
-        let (filters_task, filters_handle) = FiltersHandler::initialize();
-        let (out_task, out_handle) =
-            TermOutHandler::initialize(configuration.clone(), stats_handle.tx.clone());
-        let wordlist = Arc::new(vec![String::from("this_is_a_test")]);
+                if self.rate_limiter.read().await.is_some() {
+                    if let Err(e) = self.limit().await {
+                        log::warn!("Could not rate limit scan: {}", e);
+                    }
+                }
 
-        let handles = Arc::new(Handles::new(
-            stats_handle,
-            filters_handle,
-            out_handle,
-            configuration.clone(),
-            wordlist,
-        ));
+                self.limit_global().await;
 
-        let (scan_task, scan_handle) = ScanHandler::initialize(handles.clone());
+                self.handles.features.pacing.pace(&self.ferox_scan).await;
+
+                let response = raw::send(
+                    &self.target_url,
+                    target,
+                    display_url.clone(),
+                    method,
+                    &headers,
+                    &self.handles.config.data,
+                    &self.handles,
+                )
+                .await;
+
+                let response = match response {
+                    Ok(response) => response,
+                    Err(e) => {
+                        log::warn!("Raw request to {} failed: {}", display_url, e);
+                        self.handles
+                            .stats
+                            .send(AddToUsizeField(RawErrors, 1))
+                            .unwrap_or_default();
+                        continue;
+                    }
+                };
+
+                self.handles
+                    .stats
+                    .send(AddToUsizeField(RawRequests, 1))
+                    .unwrap_or_default();
+                self.handles
+                    .stats
+                    .send(AddStatus(response.status()))
+                    .unwrap_or_default();
+
+                let mut ferox_response =
+                    FeroxResponse::from(response, &self.target_url, method, &self.handles).await;
+
+                ferox_response.add_tag(String::from("raw"));
+
+                search::report(&mut ferox_response, &self.handles);
+
+                if let Some(filter) = self
+                    .handles
+                    .filters
+                    .data
+                    .matching_filter(&ferox_response, self.handles.stats.tx.clone())
+                {
+                    if !self.handles.config.quiet_stream.is_empty() {
+                        ferox_response
+                            .send_filtered_report(self.handles.output.tx.clone(), filter)?;
+                    }
+                    continue;
+                }
+
+                if let Err(e) = ferox_response.send_report(
+                    self.handles.output.tx.clone(),
+                    self.handles.config.exports_bodies(),
+                ) {
+                    log::warn!("Could not send FeroxResponse to output handler: {}", e);
+                }
+            }
+        }
+
+        log::trace!("exit: raw_request -> {}", targets.len());
+        Ok(targets.len())
+    }
+
+    /// --crawl; request a single page found while crawling and report it unless it's filtered,
+    /// returns the in-scope links found on the page, or none when `follow` is false
+    ///
+    /// limits and --pacing apply the same as they do to the wordlist, but nothing found while
+    /// crawling is considered for recursion
+    pub async fn crawl(&self, url: &str, follow: bool) -> Result<HashSet<String>> {
+        log::trace!("enter: crawl({}, {})", url, follow);
+
+        self.ferox_scan.sample_rate();
+
+        let url = parse_url_with_raw_path(url)?;
+
+        let should_test_deny = !self.handles.config.url_denylist.is_empty()
+            || !self.handles.config.regex_denylist.is_empty();
+
+        if should_test_deny && should_deny_url(&url, &self.handles)? {
+            // the page was counted on when it was added to the crawl, it needs to come back off;
+            // checked ahead of the rate limiters, so it doesn't use up a token
+            self.handles
+                .stats
+                .send(SubtractFromUsizeField(TotalExpected, 1))
+                .unwrap_or_default();
+            log::trace!("exit: crawl -> denied");
+            return Ok(HashSet::new());
+        }
+
+        if self.rate_limiter.read().await.is_some() {
+            self.limit().await?;
+        }
+
+        self.limit_global().await;
+
+        self.handles.features.pacing.pace(&self.ferox_scan).await;
+
+        let reserved = self.handles.features.bandwidth.throttle().await;
+
+        let response = logged_request_for_scan(
+            &url,
+            DEFAULT_METHOD,
+            None,
+            &self.ferox_scan.headers(),
+            &self.ferox_scan,
+            self.handles.clone(),
+        )
+        .await?;
+
+        let mut ferox_response =
+            FeroxResponse::from(response, &self.target_url, DEFAULT_METHOD, &self.handles).await;
+
+        self.handles
+            .features
+            .bandwidth
+            .consume(&ferox_response, reserved);
+
+        // links are followed whether or not the page itself is filtered, a custom 404 page
+        // still links to the rest of the site
+        let links = if follow {
+            let extractor = ExtractorBuilder::default()
+                .target(ExtractionTarget::ResponseBody)
+                .response(&ferox_response)
+                .handles(self.handles.clone())
+                .url(self.ferox_scan.url())
+                .build()?;
+
+            let links = extractor.extract().await?;
+            extractor.within_budget(links)
+        } else {
+            HashSet::new()
+        };
+
+        if let Some(recorder) = &self.handles.features.session {
+            recorder.record(&ferox_response);
+        }
+
+        if let Some(filter) = self
+            .handles
+            .filters
+            .data
+            .matching_filter(&ferox_response, self.handles.stats.tx.clone())
+            .or_else(|| {
+                plugins::on_response(
+                    &self.handles.features.plugins,
+                    &mut ferox_response,
+                    self.handles.stats.tx.clone(),
+                )
+            })
+        {
+            if !self.handles.config.quiet_stream.is_empty() {
+                ferox_response.send_filtered_report(self.handles.output.tx.clone(), filter)?;
+            }
+        } else if let Err(e) = ferox_response.send_report(
+            self.handles.output.tx.clone(),
+            self.handles.config.exports_bodies(),
+        ) {
+            log::warn!("Could not send FeroxResponse to output handler: {}", e);
+        }
+
+        log::trace!("exit: crawl -> {} links", links.len());
+        Ok(links)
+    }
+}
+
+/// --dual-slash classification; records the responses to bare words and decides whether word/
+/// should be considered for recursion at all
+///
+/// servers that ignore trailing slashes (or serve the same catch-all page for everything)
+/// respond to word and word/ identically, so word/ only counts as a directory when its status
+/// code or size differs from word's. a word that redirects to word/ is still picked up by the
+/// usual 3xx check on the bare word's response
+fn dual_slash_allows_recursion(
+    bare_responses: &mut HashMap<String, (StatusCode, u64)>,
+    response: &FeroxResponse,
+) -> bool {
+    let path = response.url().path();
+    let method = response.method().as_str();
+
+    let Some(bare) = path.strip_suffix('/') else {
+        bare_responses.insert(
+            format!("{method} {path}"),
+            (*response.status(), response.content_length()),
+        );
+        return true;
+    };
+
+    match bare_responses.get(&format!("{method} {bare}")) {
+        Some((status, length)) => {
+            status != response.status() || *length != response.content_length()
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use reqwest::StatusCode;
+
+    use crate::{
+        config::Configuration,
+        config::OutputLevel,
+        event_handlers::Command::AddStatus,
+        event_handlers::{FiltersHandler, ScanHandler, StatsHandler, Tasks, TermOutHandler},
+        features::Features,
+        filters,
+        scan_manager::{ScanOrder, ScanType},
+        statistics::{ErrorWeights, StatError},
+    };
+
+    use super::*;
+
+    /// helper to setup a realistic requester test
+    async fn setup_requester_test(config: Option<Arc<Configuration>>) -> (Arc<Handles>, Tasks) {
+        // basically C&P from main::wrapped_main, can look there for comments etc if needed
+        let configuration = config.unwrap_or_else(|| Arc::new(Configuration::new().unwrap()));
+
+        let (stats_task, stats_handle) = StatsHandler::initialize(configuration.clone());
+        let (filters_task, filters_handle) = FiltersHandler::initialize();
+        let (out_task, out_handle) =
+            TermOutHandler::initialize(configuration.clone(), stats_handle.tx.clone());
+        let wordlist = Arc::new(vec![String::from("this_is_a_test")]);
+
+        let handles = Arc::new(Handles::new(
+            stats_handle,
+            filters_handle,
+            out_handle,
+            configuration.clone(),
+            wordlist,
+            Features::new(&configuration).unwrap(),
+        ));
+
+        let (scan_task, scan_handle) = ScanHandler::initialize(handles.clone());
 
         handles.set_scan_handle(scan_handle);
         filters::initialize(handles.clone()).await.unwrap();
@@ -585,7 +1503,7 @@ mod tests {
     async fn increment_errors(handles: Arc<Handles>, scan: Arc<FeroxScan>, num_errors: usize) {
         for _ in 0..num_errors {
             handles.stats.send(AddError(StatError::Other)).unwrap();
-            scan.add_error();
+            scan.add_error(StatError::Timeout);
         }
 
         handles.stats.sync().await.unwrap();
@@ -596,7 +1514,7 @@ mod tests {
         let scans = handles.ferox_scans().unwrap();
 
         for _ in 0..num_errors {
-            scans.increment_error(format!("{url}/").as_str());
+            scans.increment_error(format!("{url}/").as_str(), StatError::Timeout);
         }
     }
 
@@ -622,11 +1540,7 @@ mod tests {
     ) {
         for _ in 0..num_codes {
             handles.stats.send(AddStatus(code)).unwrap();
-            if code == StatusCode::FORBIDDEN {
-                scan.add_403();
-            } else {
-                scan.add_429();
-            }
+            scan.add_status_code(code.as_u16());
         }
 
         handles.stats.sync().await.unwrap();
@@ -678,7 +1592,10 @@ mod tests {
             _ => {}
         }
 
-        assert_eq!(scan.num_errors(trigger), num_errors);
+        assert_eq!(
+            scan.num_errors(trigger, &ErrorWeights::default()),
+            num_errors
+        );
 
         scan
     }
@@ -697,6 +1614,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         let ferox_scan = Arc::new(FeroxScan::default());
@@ -726,6 +1644,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         increment_errors(requester.handles.clone(), ferox_scan.clone(), 25).await;
@@ -737,6 +1656,45 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// errors of a category that's weighted 0 (proxy, by default) should never trigger a policy
+    async fn should_enforce_policy_ignores_proxy_errors() {
+        let mut config = Configuration::new().unwrap_or_default();
+        config.threads = 50;
+
+        let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+
+        let ferox_scan = Arc::new(FeroxScan::default());
+
+        let requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: ferox_scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        for _ in 0..100 {
+            requester
+                .handles
+                .stats
+                .send(AddError(StatError::Proxy))
+                .unwrap();
+            ferox_scan.add_error(StatError::Proxy);
+        }
+        requester.handles.stats.sync().await.unwrap();
+
+        assert_eq!(
+            ferox_scan.num_errors(PolicyTrigger::Errors, &ErrorWeights::default()),
+            0
+        );
+        assert_eq!(requester.should_enforce_policy(), None);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// should_enforce_policy should return true when # of requests is >= 50 and 403s >= 45 (90%)
     async fn should_enforce_policy_returns_true_on_excessive_403s() {
@@ -752,6 +1710,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         increment_status_codes(
@@ -775,6 +1734,54 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// policy_decision should carry the counts and ratio that led to a 403 trigger firing
+    async fn policy_decision_records_counters_behind_403s() {
+        let (handles, _) = setup_requester_test(None).await;
+        let ferox_scan = Arc::new(FeroxScan::default());
+
+        let requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: ferox_scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        increment_status_codes(
+            requester.handles.clone(),
+            ferox_scan.clone(),
+            45,
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+        increment_status_codes(
+            requester.handles.clone(),
+            ferox_scan.clone(),
+            5,
+            StatusCode::OK,
+        )
+        .await;
+        ferox_scan.progress_bar().set_position(50);
+        ferox_scan.limit_gauge().start_at(100);
+
+        let decision = requester.policy_decision(PolicyTrigger::Status403);
+
+        assert_eq!(decision.url, "http://localhost");
+        assert_eq!(decision.trigger, "Status403");
+        assert_eq!(decision.requests, 50);
+        assert_eq!(decision.scan_requests, 50);
+        assert_eq!(decision.count, 45);
+        assert_eq!(decision.ratio, Some(0.9));
+        assert_eq!(decision.threshold, requester.policy_data.ratio_403);
+        assert_eq!(decision.rate_limit, 100);
+        assert!(decision.p95_ms.is_none());
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// should_enforce_policy should return true when # of requests is >= 50 and errors >= 45 (90%)
     async fn should_enforce_policy_returns_true_on_excessive_429s() {
@@ -793,6 +1800,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         increment_status_codes(
@@ -816,6 +1824,212 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// should_enforce_policy should use the thresholds carried in PolicyData instead of the
+    /// defaults
+    async fn should_enforce_policy_honors_configured_thresholds() {
+        let mut config = Configuration::new().unwrap_or_default();
+        config.threads = 50;
+
+        let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+        let ferox_scan = Arc::new(FeroxScan::default());
+        ferox_scan.progress_bar().set_position(100);
+
+        let mut requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: ferox_scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        // 60% of requests being 403s is fine by default
+        increment_status_codes(
+            requester.handles.clone(),
+            ferox_scan.clone(),
+            60,
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+        assert_eq!(requester.should_enforce_policy(), None);
+
+        requester.policy_data = PolicyData::default().with_thresholds(0.5, 0.3, 25);
+        assert_eq!(
+            requester.should_enforce_policy(),
+            Some(PolicyTrigger::Status403)
+        );
+
+        // 40 errors is under the configured threshold, even though it's over the default
+        requester.policy_data = PolicyData::default().with_thresholds(0.9, 0.3, 50);
+        increment_errors(requester.handles.clone(), ferox_scan.clone(), 40).await;
+        assert_eq!(requester.should_enforce_policy(), None);
+
+        requester.policy_data = PolicyData::default().with_thresholds(0.9, 0.3, 25);
+        assert_eq!(
+            requester.should_enforce_policy(),
+            Some(PolicyTrigger::Errors)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// should_enforce_policy should treat --policy-status codes like 429s
+    async fn should_enforce_policy_returns_status_on_excessive_policy_status() {
+        let mut config = Configuration::new().unwrap_or_default();
+        config.threads = 50;
+        config.policy_status = vec![503, 520];
+
+        let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+        let ferox_scan = Arc::new(FeroxScan::default());
+        ferox_scan.progress_bar().set_position(100);
+
+        let requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: ferox_scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        increment_status_codes(
+            requester.handles.clone(),
+            ferox_scan.clone(),
+            25,
+            StatusCode::from_u16(520).unwrap(),
+        )
+        .await;
+        increment_status_codes(
+            requester.handles.clone(),
+            ferox_scan.clone(),
+            25,
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .await;
+        assert_eq!(requester.should_enforce_policy(), None);
+
+        increment_status_codes(
+            requester.handles.clone(),
+            ferox_scan.clone(),
+            5,
+            StatusCode::from_u16(520).unwrap(),
+        )
+        .await;
+        assert_eq!(
+            requester.should_enforce_policy(),
+            Some(PolicyTrigger::Status(520))
+        );
+        assert_eq!(
+            ferox_scan.num_errors(PolicyTrigger::Status(520), &ErrorWeights::default()),
+            30
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// should_enforce_policy should return Latency when the p95 response time passes the
+    /// --tune-on-latency multiple of the baseline
+    async fn should_enforce_policy_returns_latency_on_slow_responses() {
+        let mut config = Configuration::new().unwrap_or_default();
+        config.threads = 50;
+        config.tune_on_latency = String::from("2x-baseline");
+
+        let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+        let ferox_scan = Arc::new(FeroxScan::default());
+
+        let requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: ferox_scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        for _ in 0..50 {
+            requester
+                .handles
+                .stats
+                .send(AddStatus(StatusCode::OK))
+                .unwrap();
+        }
+        requester.handles.stats.sync().await.unwrap();
+
+        let latency = ferox_scan.latency();
+        latency.set_baseline(&[Duration::from_millis(100)]);
+
+        for _ in 0..30 {
+            latency.record(Duration::from_millis(150), 2.0);
+        }
+        assert_eq!(requester.should_enforce_policy(), None);
+
+        for _ in 0..10 {
+            latency.record(Duration::from_millis(400), 2.0);
+        }
+        assert_eq!(
+            requester.should_enforce_policy(),
+            Some(PolicyTrigger::Latency)
+        );
+        assert_eq!(
+            ferox_scan.num_errors(PolicyTrigger::Latency, &ErrorWeights::default()),
+            10
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// should_enforce_policy should return ServerChange once a host's server banner changed,
+    /// but only when --server-alerts is used
+    async fn should_enforce_policy_returns_server_change_on_banner_change() {
+        let mut config = Configuration::new().unwrap_or_default();
+        config.threads = 50;
+        config.server_alerts = true;
+
+        let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+        let ferox_scan = Arc::new(FeroxScan::default());
+
+        let requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: ferox_scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        for _ in 0..50 {
+            requester
+                .handles
+                .stats
+                .send(AddStatus(StatusCode::OK))
+                .unwrap();
+        }
+        requester.handles.stats.sync().await.unwrap();
+
+        assert_eq!(requester.should_enforce_policy(), None);
+
+        ferox_scan.add_server_change();
+
+        assert_eq!(
+            requester.should_enforce_policy(),
+            Some(PolicyTrigger::ServerChange)
+        );
+        assert_eq!(
+            ferox_scan.num_errors(PolicyTrigger::ServerChange, &ErrorWeights::default()),
+            1
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// bail should call abort on the scan with the most errors
     async fn bail_calls_abort_on_highest_errored_feroxscan() {
@@ -849,6 +2063,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         requester.bail(PolicyTrigger::Errors).await.unwrap();
@@ -857,6 +2072,8 @@ mod tests {
         assert!(scan_three.is_active());
         assert!(scan_four.is_active());
         assert!(!scan_two.is_active());
+        assert!(scan_two.is_bailed() && !scan_one.is_bailed());
+        assert_eq!(scans.bailed_scans(), 1);
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -884,12 +2101,72 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         let result = requester.bail(PolicyTrigger::Status403).await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// with --auto-requeue, bail should requeue the scan at half its rate until it runs out of
+    /// attempts, at which point the scan is cancelled as usual
+    async fn bail_requeues_scan_until_out_of_attempts() {
+        let mut config = Configuration::new().unwrap_or_default();
+        config.auto_requeue = 2;
+
+        let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+
+        let scan = create_scan(handles.clone(), "http://one", 20, PolicyTrigger::Status429).await;
+        scan.set_tuning_state(TuningState {
+            rate_limit: 100,
+            ..Default::default()
+        });
+
+        let requester = Requester {
+            handles,
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: scan.clone(),
+            target_url: "http://one/one/stuff.php".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: PolicyData::new(RequesterPolicy::AutoRequeue, 7),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        requester.bail(PolicyTrigger::Status429).await.unwrap();
+        assert!(scan.is_requeued());
+        assert!(scan.is_active());
+        assert_eq!(scan.requeues(), 1);
+        assert_eq!(scan.tuning_state().unwrap().rate_limit, 50);
+        assert_eq!(
+            scan.num_errors(PolicyTrigger::Status429, &ErrorWeights::default()),
+            0
+        );
+
+        // in-flight requests bailing on a scan that's already requeued change nothing
+        requester.bail(PolicyTrigger::Status429).await.unwrap();
+        assert_eq!(scan.requeues(), 1);
+
+        // restarted by the scan handler
+        scan.set_status(ScanStatus::Running).unwrap();
+        requester.bail(PolicyTrigger::Status429).await.unwrap();
+        assert!(scan.is_requeued());
+        assert_eq!(scan.requeues(), 2);
+        assert_eq!(scan.tuning_state().unwrap().rate_limit, 25);
+
+        scan.set_status(ScanStatus::Running).unwrap();
+        scan.set_task(tokio::spawn(async move {
+            tokio::time::sleep(Duration::new(15, 0)).await
+        }))
+        .await
+        .unwrap();
+        requester.bail(PolicyTrigger::Status429).await.unwrap();
+        assert!(scan.is_cancelled());
+        assert_eq!(scan.requeues(), 2);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// should_enforce should early exit when cooldown flag is set
     async fn should_enforce_policy_returns_none_on_cooldown() {
@@ -907,6 +2184,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         requester
@@ -931,6 +2209,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: PolicyData::new(RequesterPolicy::AutoBail, 7),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         });
 
         let start = Instant::now();
@@ -962,6 +2241,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: PolicyData::new(RequesterPolicy::AutoBail, 7),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         requester.policy_data.set_reqs_sec(400);
@@ -989,8 +2269,8 @@ mod tests {
             .build();
 
         let scan = FeroxScan::default();
-        scan.add_error();
-        scan.add_error();
+        scan.add_error(StatError::Timeout);
+        scan.add_error(StatError::Timeout);
 
         let requester = Requester {
             handles,
@@ -1001,6 +2281,7 @@ mod tests {
             rate_limiter: RwLock::new(Some(limiter)),
             policy_data: PolicyData::new(RequesterPolicy::AutoBail, 7),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         requester.policy_data.set_reqs_sec(400);
@@ -1027,8 +2308,8 @@ mod tests {
         let (handles, _) = setup_requester_test(None).await;
 
         let scan = FeroxScan::default();
-        scan.add_error();
-        scan.add_error();
+        scan.add_error(StatError::Timeout);
+        scan.add_error(StatError::Timeout);
 
         let requester = Requester {
             handles,
@@ -1039,6 +2320,7 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: PolicyData::new(RequesterPolicy::AutoBail, 7),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         requester.policy_data.set_reqs_sec(400);
@@ -1068,29 +2350,30 @@ mod tests {
             rate_limiter: RwLock::new(None),
             policy_data: PolicyData::new(RequesterPolicy::AutoBail, 7),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         assert!(!requester.too_many_status_errors(PolicyTrigger::Errors));
 
         assert!(!requester.too_many_status_errors(PolicyTrigger::Status429));
         requester.ferox_scan.progress_bar().set_position(10);
-        requester.ferox_scan.add_429();
-        requester.ferox_scan.add_429();
-        requester.ferox_scan.add_429();
+        requester.ferox_scan.add_status_code(429);
+        requester.ferox_scan.add_status_code(429);
+        requester.ferox_scan.add_status_code(429);
         assert!(requester.too_many_status_errors(PolicyTrigger::Status429));
 
         assert!(!requester.too_many_status_errors(PolicyTrigger::Status403));
         requester.ferox_scan = Arc::new(FeroxScan::default());
         requester.ferox_scan.progress_bar().set_position(10);
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
-        requester.ferox_scan.add_403();
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
+        requester.ferox_scan.add_status_code(403);
         assert!(requester.too_many_status_errors(PolicyTrigger::Status403));
     }
 
@@ -1112,6 +2395,7 @@ mod tests {
             rate_limiter: RwLock::new(Some(limiter)),
             policy_data: PolicyData::new(RequesterPolicy::AutoBail, 7),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         requester.set_rate_limiter(Some(200)).await.unwrap();
@@ -1145,7 +2429,7 @@ mod tests {
             None,
         );
         scan.set_status(ScanStatus::Running).unwrap();
-        scan.add_429();
+        scan.add_status_code(429);
 
         let requester = Requester {
             handles,
@@ -1156,6 +2440,7 @@ mod tests {
             rate_limiter: RwLock::new(Some(limiter)),
             policy_data: PolicyData::new(RequesterPolicy::AutoTune, 4),
             policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
         };
 
         let start = Instant::now();
@@ -1169,7 +2454,7 @@ mod tests {
 
         requester.tune(PolicyTrigger::Status429).await.unwrap();
 
-        assert_eq!(requester.policy_data.heap.read().unwrap().original, 400);
+        assert_eq!(requester.policy_data.heap_state().0, 400);
         assert_eq!(requester.policy_data.get_limit(), 200);
         assert_eq!(
             requester.rate_limiter.read().await.as_ref().unwrap().max(),
@@ -1179,4 +2464,229 @@ mod tests {
         scan.finish().unwrap();
         assert!(start.elapsed().as_millis() >= 2000);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// a new scan of a host that auto-tune already slowed down starts out at that rate, but only
+    /// while auto-tune is the policy
+    async fn requester_inherits_tuning_of_its_host() {
+        let tuning = TuningState {
+            rate_limit: 150,
+            original_rate: 400,
+            heap_position: 6,
+            errors: 12,
+            policy_triggered: true,
+        };
+
+        for (policy, expected) in [
+            (RequesterPolicy::AutoTune, Some(150)),
+            (RequesterPolicy::Default, None),
+        ] {
+            let config = Configuration {
+                requester_policy: policy,
+                ..Configuration::new().unwrap()
+            };
+            let (handles, _) = setup_requester_test(Some(Arc::new(config))).await;
+
+            handles
+                .ferox_scans()
+                .unwrap()
+                .host_tuning
+                .record("http://localhost/", tuning);
+
+            let scanner = FeroxScanner::new(
+                "http://localhost/admin/",
+                ScanOrder::Latest,
+                Arc::new(vec![]),
+                Arc::new(tokio::sync::Semaphore::new(1)),
+                handles.clone(),
+            );
+            let scan = Arc::new(FeroxScan::default());
+            let requester = Requester::from(&scanner, scan.clone()).unwrap();
+
+            assert_eq!(
+                requester
+                    .rate_limiter
+                    .read()
+                    .await
+                    .as_ref()
+                    .map(|bucket| bucket.max()),
+                expected
+            );
+            assert_eq!(scan.tuning_state().map(|state| state.rate_limit), expected);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// changes made to the runtime settings should be applied to the requester's rate limiter
+    /// and policy the next time it syncs
+    async fn sync_runtime_settings_applies_changes() {
+        let (handles, _) = setup_requester_test(None).await;
+
+        let requester = Requester {
+            handles: handles.clone(),
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: Arc::new(FeroxScan::default()),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: PolicyData::new(RequesterPolicy::Default, 7),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        handles.runtime.set_rate_limit(50);
+        requester.sync_runtime_settings().await.unwrap();
+        assert_eq!(
+            requester.rate_limiter.read().await.as_ref().unwrap().max(),
+            50
+        );
+
+        handles.runtime.set_auto_tune(Some(true));
+        handles.runtime.set_rate_limit(0);
+        requester.sync_runtime_settings().await.unwrap();
+        assert!(requester.rate_limiter.read().await.is_none());
+        assert_eq!(requester.policy_data.policy(), RequesterPolicy::AutoTune);
+        assert_eq!(
+            requester.settings_generation.load(Ordering::Relaxed),
+            handles.runtime.generation()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// settings picked for a single scan should win over the shared ones, and unpinning the scan
+    /// should hand it back to the shared ones
+    async fn sync_runtime_settings_applies_scan_overrides() {
+        let (handles, _) = setup_requester_test(None).await;
+        let scan = Arc::new(FeroxScan::default());
+
+        let requester = Requester {
+            handles: handles.clone(),
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: PolicyData::new(RequesterPolicy::AutoTune, 7),
+            policy_triggered: AtomicBool::new(false),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        handles.runtime.set_auto_tune(Some(true));
+        scan.set_rate_limit(20);
+        requester.sync_runtime_settings().await.unwrap();
+        assert_eq!(
+            requester.rate_limiter.read().await.as_ref().unwrap().max(),
+            20
+        );
+        assert_eq!(requester.policy_data.policy(), RequesterPolicy::Default);
+
+        // the shared limit doesn't move a pinned scan
+        handles.runtime.set_rate_limit(100);
+        requester.sync_runtime_settings().await.unwrap();
+        assert_eq!(
+            requester.rate_limiter.read().await.as_ref().unwrap().max(),
+            20
+        );
+
+        scan.set_auto_tune(true);
+        requester.sync_runtime_settings().await.unwrap();
+        assert_eq!(requester.policy_data.policy(), RequesterPolicy::AutoTune);
+
+        scan.set_rate_limit(0);
+        requester.sync_runtime_settings().await.unwrap();
+        assert_eq!(
+            requester.rate_limiter.read().await.as_ref().unwrap().max(),
+            100
+        );
+        assert_eq!(requester.policy_data.policy(), RequesterPolicy::AutoTune);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// changing the rate limiter should record the tuning state on the associated scan, and for
+    /// later scans of the same host
+    async fn set_rate_limiter_saves_tuning_state() {
+        let (handles, _) = setup_requester_test(None).await;
+        let scan = Arc::new(FeroxScan::default());
+
+        let requester = Requester {
+            handles: handles.clone(),
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            ferox_scan: scan.clone(),
+            target_url: "http://localhost".to_string(),
+            rate_limiter: RwLock::new(None),
+            policy_data: PolicyData::new(RequesterPolicy::AutoTune, 7),
+            policy_triggered: AtomicBool::new(true),
+            settings_generation: AtomicUsize::new(0),
+        };
+
+        requester.policy_data.set_reqs_sec(400);
+        requester.policy_data.adjust_down();
+        requester.policy_data.set_errors(5);
+        requester.set_rate_limiter(Some(100)).await.unwrap();
+
+        assert_eq!(
+            scan.tuning_state(),
+            Some(TuningState {
+                rate_limit: 100,
+                original_rate: 400,
+                heap_position: 2,
+                errors: 5,
+                policy_triggered: true,
+            })
+        );
+
+        let host_tuning = &handles.ferox_scans().unwrap().host_tuning;
+        assert_eq!(
+            host_tuning.inherit("http://localhost/admin/"),
+            scan.tuning_state()
+        );
+
+        requester.set_rate_limiter(None).await.unwrap();
+        assert_eq!(scan.tuning_state().unwrap().rate_limit, 0);
+
+        // auto-tune lifted its limit, later scans start out unlimited again
+        assert_eq!(host_tuning.inherit("http://localhost/admin/"), None);
+    }
+
+    #[test]
+    /// word/ should only be considered for recursion when it differs from the bare word
+    fn dual_slash_allows_recursion_compares_against_bare_word() {
+        let mut bare_responses = HashMap::new();
+
+        let response = |url: &str, text: &str| {
+            let mut response = FeroxResponse::default();
+            response.set_url(url);
+            response.set_text(text);
+            response
+        };
+
+        // never seen the bare word, nothing to compare against
+        assert!(dual_slash_allows_recursion(
+            &mut bare_responses,
+            &response("http://localhost/orphan/", "stuff")
+        ));
+
+        // bare words are recorded and always allowed
+        assert!(dual_slash_allows_recursion(
+            &mut bare_responses,
+            &response("http://localhost/login", "login page")
+        ));
+        assert!(dual_slash_allows_recursion(
+            &mut bare_responses,
+            &response("http://localhost/admin", "admin")
+        ));
+
+        // same response with and without the slash; not a directory
+        assert!(!dual_slash_allows_recursion(
+            &mut bare_responses,
+            &response("http://localhost/login/", "login page")
+        ));
+
+        // different response; directory
+        assert!(dual_slash_allows_recursion(
+            &mut bare_responses,
+            &response("http://localhost/admin/", "index of /admin")
+        ));
+    }
 }
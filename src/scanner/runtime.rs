@@ -0,0 +1,129 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    RwLock,
+};
+
+use crate::{
+    atomic_load, atomic_store,
+    config::{determine_requester_policy, Configuration, RequesterPolicy},
+};
+
+/// scan settings that can be changed while a scan is running (i.e. from the interactive menu)
+///
+/// every `Requester` keeps track of the last generation it applied; bumping the generation is
+/// how a change gets broadcast to all of them
+#[derive(Debug, Default)]
+pub struct RuntimeSettings {
+    /// incremented each time a setting changes
+    generation: AtomicUsize,
+
+    /// requests per second allowed for each scan, 0 means unlimited
+    rate_limit: AtomicUsize,
+
+    /// policy each `Requester` should enforce
+    policy: RwLock<RequesterPolicy>,
+
+    /// whether --auto-bail was used; needed to know what to fall back to when auto-tune is
+    /// toggled off
+    auto_bail: bool,
+
+    /// whether --auto-requeue was used; same reason as auto_bail
+    auto_requeue: bool,
+}
+
+/// implementation of RuntimeSettings
+impl RuntimeSettings {
+    /// seed the runtime settings from the values given on the command line/config file
+    pub fn new(config: &Configuration) -> Self {
+        Self {
+            rate_limit: AtomicUsize::new(config.rate_limit),
+            policy: RwLock::new(config.requester_policy),
+            auto_bail: config.auto_bail,
+            auto_requeue: config.auto_requeue > 0,
+            ..Default::default()
+        }
+    }
+
+    /// getter for the current generation
+    pub fn generation(&self) -> usize {
+        atomic_load!(self.generation, Ordering::Acquire)
+    }
+
+    /// getter for the current rate limit
+    pub fn rate_limit(&self) -> usize {
+        atomic_load!(self.rate_limit)
+    }
+
+    /// getter for the current requester policy
+    pub fn policy(&self) -> RequesterPolicy {
+        self.policy.read().map(|guard| *guard).unwrap_or_default()
+    }
+
+    /// change the rate limit for all scans, 0 removes the limit
+    pub fn set_rate_limit(&self, limit: usize) {
+        atomic_store!(self.rate_limit, limit);
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// turn auto-tune on or off for all scans; `None` toggles the current state
+    ///
+    /// turning auto-tune off falls back to auto-bail/auto-requeue, if requested at startup
+    pub fn set_auto_tune(&self, enabled: Option<bool>) {
+        if let Ok(mut guard) = self.policy.write() {
+            let enabled = enabled.unwrap_or(*guard != RequesterPolicy::AutoTune);
+            *guard = self.auto_tune_policy(enabled);
+        }
+
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// policy to enforce with auto-tune on or off; off falls back to auto-bail/auto-requeue, if
+    /// requested at startup
+    pub fn auto_tune_policy(&self, enabled: bool) -> RequesterPolicy {
+        if enabled {
+            RequesterPolicy::AutoTune
+        } else {
+            determine_requester_policy(false, self.auto_bail, self.auto_requeue)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// changing a setting should bump the generation and store the new value
+    fn runtime_settings_setters_bump_generation() {
+        let settings = RuntimeSettings::new(&Configuration::new().unwrap());
+        assert_eq!(settings.generation(), 0);
+        assert_eq!(settings.rate_limit(), 0);
+
+        settings.set_rate_limit(25);
+        assert_eq!(settings.rate_limit(), 25);
+        assert_eq!(settings.generation(), 1);
+
+        settings.set_auto_tune(Some(true));
+        assert_eq!(settings.policy(), RequesterPolicy::AutoTune);
+        assert_eq!(settings.generation(), 2);
+    }
+
+    #[test]
+    /// toggling auto-tune off should fall back to auto-bail when it was used at startup
+    fn runtime_settings_toggle_auto_tune() {
+        let mut config = Configuration::new().unwrap();
+        config.auto_bail = true;
+        config.requester_policy = RequesterPolicy::AutoBail;
+
+        let settings = RuntimeSettings::new(&config);
+
+        settings.set_auto_tune(None);
+        assert_eq!(settings.policy(), RequesterPolicy::AutoTune);
+
+        settings.set_auto_tune(None);
+        assert_eq!(settings.policy(), RequesterPolicy::AutoBail);
+
+        settings.set_auto_tune(Some(false));
+        assert_eq!(settings.policy(), RequesterPolicy::AutoBail);
+    }
+}
@@ -7,9 +7,18 @@ pub enum PolicyTrigger {
     /// excessive 429 trigger
     Status429,
 
+    /// excessive responses with one of the --policy-status codes
+    Status(u16),
+
     /// excessive general errors
     Errors,
 
+    /// p95 response time past the allowed multiple of the baseline
+    Latency,
+
+    /// a host's Server/X-Powered-By header changed mid-scan (--server-alerts)
+    ServerChange,
+
     /// dummy error for upward rate adjustment
     TryAdjustUp,
 }
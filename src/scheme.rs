@@ -0,0 +1,284 @@
+//! scheme detection for targets given without one, i.e. `--url example.com` or a list of hosts
+//! from a recon tool piped into --stdin
+//!
+//! each of the --scheme-probes (https:443, then http:80 by default) is tried in order and the
+//! first one that gets any response at all wins. a target that already has a port keeps it and
+//! only the schemes are tried. decisions are shown in the banner
+//!
+//! --ports expands each target host into one target per port before any of that happens; well
+//! known ports get their scheme guessed, the rest are left schemeless and probed like any other
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Context, Result};
+use futures::future;
+use reqwest::Url;
+
+use crate::{event_handlers::Handles, utils::fmt_err};
+
+/// Schemes detected for schemeless targets
+#[derive(Debug, Default)]
+pub struct SchemeDetections {
+    /// (target as given, target that was settled on) for every probed target
+    detected: Mutex<Vec<(String, String)>>,
+}
+
+/// implementation of SchemeDetections
+impl SchemeDetections {
+    /// (target as given, target that was settled on) for every target whose scheme was
+    /// detected
+    pub fn detections(&self) -> Vec<(String, String)> {
+        self.detected
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// whether the given target needs its scheme detected
+pub fn is_schemeless(target: &str) -> bool {
+    !target.starts_with("http://") && !target.starts_with("https://")
+}
+
+/// parse a single --scheme-probes value, i.e. `https:8443`
+fn parse_probe(probe: &str) -> Result<(&str, u16)> {
+    let (scheme, port) = probe
+        .split_once(':')
+        .with_context(|| fmt_err(&format!("{probe} is not a SCHEME:PORT pair")))?;
+
+    if !matches!(scheme, "http" | "https") {
+        bail!("{probe} should use either http or https");
+    }
+
+    let port = port
+        .parse::<u16>()
+        .with_context(|| fmt_err(&format!("{probe} doesn't have a valid port")))?;
+
+    Ok((scheme, port))
+}
+
+/// split a schemeless target into its host and the rest (path, query, etc...), along with its
+/// port, if it has one; ipv6 literals keep their brackets
+fn split_target(target: &str) -> (&str, Option<u16>, &str) {
+    let end = target.find(['/', '?', '#']).unwrap_or(target.len());
+    let (authority, rest) = target.split_at(end);
+
+    match authority.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && !port.contains(']') => {
+            match port.parse::<u16>() {
+                Ok(port) if host.ends_with(']') || !host.contains(':') => (host, Some(port), rest),
+                _ => (authority, None, rest),
+            }
+        }
+        _ => (authority, None, rest),
+    }
+}
+
+/// scheme conventionally served on the given port, if there is one
+fn guess_scheme(port: u16) -> Option<&'static str> {
+    match port {
+        443 | 4443 | 8443 | 9443 | 10443 => Some("https"),
+        80 | 3000 | 5000 | 8000 | 8008 | 8080 | 8888 | 9000 => Some("http"),
+        _ => None,
+    }
+}
+
+/// --ports; turn each target into one target per port. the scheme follows the port (a given
+/// scheme is dropped), and targets that already have a port are left alone
+pub fn expand_ports(targets: &[String], ports: &[u16]) -> Vec<String> {
+    let mut expanded = Vec::new();
+
+    for target in targets {
+        let schemeless = target
+            .strip_prefix("https://")
+            .or_else(|| target.strip_prefix("http://"))
+            .unwrap_or(target);
+
+        let (host, port, rest) = split_target(schemeless);
+
+        if port.is_some() {
+            if !expanded.contains(target) {
+                expanded.push(target.to_owned());
+            }
+            continue;
+        }
+
+        for port in ports {
+            let candidate = match guess_scheme(*port) {
+                Some(scheme @ "https") if *port == 443 => format!("{scheme}://{host}{rest}"),
+                Some(scheme @ "http") if *port == 80 => format!("{scheme}://{host}{rest}"),
+                Some(scheme) => format!("{scheme}://{host}:{port}{rest}"),
+                None => format!("{host}:{port}{rest}"),
+            };
+
+            if !expanded.contains(&candidate) {
+                expanded.push(candidate);
+            }
+        }
+    }
+
+    expanded
+}
+
+/// urls to try for the given schemeless target, in the order they're tried
+fn candidates(target: &str, probes: &[String]) -> Result<Vec<String>> {
+    let (host, port, rest) = split_target(target);
+    let mut candidates = Vec::new();
+
+    for probe in probes {
+        let (scheme, probe_port) = parse_probe(probe)?;
+
+        let candidate = match port {
+            // the target's own port wins, only the scheme is up for grabs
+            Some(port) => format!("{scheme}://{host}:{port}{rest}"),
+            None if matches!((scheme, probe_port), ("https", 443) | ("http", 80)) => {
+                format!("{scheme}://{host}{rest}")
+            }
+            None => format!("{scheme}://{host}:{probe_port}{rest}"),
+        };
+
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+
+    if candidates.is_empty() {
+        bail!("--scheme-probes needs at least one SCHEME:PORT pair");
+    }
+
+    Ok(candidates)
+}
+
+/// figure out which scheme (and port) the given schemeless target answers on; when none of the
+/// probes get a response, the first one is used so that the connectivity check can report why
+pub async fn detect(target: &str, handles: Arc<Handles>) -> Result<String> {
+    log::trace!("enter: detect({}, {:?})", target, handles);
+
+    let candidates = candidates(target, &handles.config.scheme_probes)?;
+
+    let mut detected = None;
+
+    for candidate in &candidates {
+        let Ok(url) = Url::parse(candidate) else {
+            continue;
+        };
+
+        match handles.proxies.client().1.get(url).send().await {
+            Ok(_) => {
+                detected = Some(candidate.clone());
+                break;
+            }
+            Err(e) => log::debug!("{} didn't answer: {}", candidate, e),
+        }
+    }
+
+    let result = match detected {
+        Some(found) => {
+            if let Ok(mut guard) = handles.features.scheme.detected.lock() {
+                guard.push((target.to_string(), found.clone()));
+            }
+            found
+        }
+        None => {
+            log::warn!("none of the schemes probed for {} answered", target);
+            candidates[0].clone()
+        }
+    };
+
+    log::trace!("exit: detect -> {}", result);
+    Ok(result)
+}
+
+/// detect the scheme of every schemeless target, all at once; targets with a scheme are left
+/// alone
+pub async fn detect_all(targets: &mut [String], handles: Arc<Handles>) -> Result<()> {
+    let probes = targets
+        .iter()
+        .filter(|target| is_schemeless(target))
+        .map(|target| detect(target, handles.clone()));
+
+    let mut detected = future::try_join_all(probes).await?.into_iter();
+
+    for target in targets.iter_mut().filter(|target| is_schemeless(target)) {
+        if let Some(found) = detected.next() {
+            *target = found;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the default probes
+    fn probes() -> Vec<String> {
+        vec![String::from("https:443"), String::from("http:80")]
+    }
+
+    #[test]
+    /// default ports are left out of the url, others are spelled out
+    fn candidates_follow_probe_order() {
+        assert_eq!(
+            candidates("example.com/app", &probes()).unwrap(),
+            ["https://example.com/app", "http://example.com/app"]
+        );
+
+        let probes = vec![String::from("http:8080"), String::from("https:8443")];
+        assert_eq!(
+            candidates("example.com", &probes).unwrap(),
+            ["http://example.com:8080", "https://example.com:8443"]
+        );
+    }
+
+    #[test]
+    /// a target's own port is kept, ipv6 literals aren't mistaken for ports
+    fn candidates_keep_explicit_ports() {
+        assert_eq!(
+            candidates("10.0.0.1:8000/?q=1", &probes()).unwrap(),
+            ["https://10.0.0.1:8000/?q=1", "http://10.0.0.1:8000/?q=1"]
+        );
+        assert_eq!(
+            candidates("[::1]:8000", &probes()).unwrap(),
+            ["https://[::1]:8000", "http://[::1]:8000"]
+        );
+        assert_eq!(
+            candidates("[::1]", &probes()).unwrap(),
+            ["https://[::1]", "http://[::1]"]
+        );
+    }
+
+    #[test]
+    /// each host gets one target per port, schemes are guessed where possible
+    fn expand_ports_guesses_schemes() {
+        let targets = vec![
+            String::from("https://example.com/app"),
+            String::from("10.0.0.1"),
+            String::from("http://example.com:8081"),
+        ];
+
+        assert_eq!(
+            expand_ports(&targets, &[80, 443, 8443, 8081]),
+            [
+                "http://example.com/app",
+                "https://example.com/app",
+                "https://example.com:8443/app",
+                "example.com:8081/app",
+                "http://10.0.0.1",
+                "https://10.0.0.1",
+                "https://10.0.0.1:8443",
+                "10.0.0.1:8081",
+                "http://example.com:8081",
+            ]
+        );
+    }
+
+    #[test]
+    /// malformed probes are reported
+    fn candidates_reject_bad_probes() {
+        assert!(candidates("example.com", &[String::from("ftp:21")]).is_err());
+        assert!(candidates("example.com", &[String::from("https")]).is_err());
+        assert!(candidates("example.com", &[String::from("https:99999")]).is_err());
+        assert!(candidates("example.com", &[]).is_err());
+    }
+}
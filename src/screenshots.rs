@@ -0,0 +1,470 @@
+//! screenshots of interesting findings, taken by an external Chrome/Chromium (--screenshots)
+//!
+//! findings that match --screenshot-codes and --screenshot-types are queued up for a small pool
+//! of worker threads, each of which runs the browser in headless mode against the finding. the
+//! queue is bounded and never waited on; findings that show up while it's full are skipped (and
+//! logged) rather than slowing the scan down. every screenshot taken is linked from an
+//! index.html in the same directory
+use std::{
+    env,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use reqwest::header::CONTENT_TYPE;
+
+use crate::{config::Configuration, response::FeroxResponse, utils::fmt_err};
+
+/// number of browsers allowed to run at once
+const SCREENSHOT_WORKERS: usize = 2;
+
+/// number of findings that may wait for a browser before new ones are skipped
+const SCREENSHOT_QUEUE_SIZE: usize = 64;
+
+/// browser viewport used for every screenshot
+const WINDOW_SIZE: &str = "1280,800";
+
+/// executables looked for on the PATH when --chrome isn't used
+const BROWSERS: [&str; 5] = [
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+];
+
+/// a finding waiting for its screenshot
+#[derive(Debug, Clone)]
+struct Job {
+    /// url of the finding
+    url: String,
+
+    /// status code of the finding
+    status: u16,
+}
+
+/// a screenshot that was taken
+#[derive(Debug, Clone)]
+struct Shot {
+    /// finding that was screenshotted
+    job: Job,
+
+    /// image filename, relative to the screenshot directory
+    filename: String,
+}
+
+/// everything a worker needs to take a screenshot
+#[derive(Debug)]
+struct Browser {
+    /// path to the Chrome/Chromium executable
+    executable: PathBuf,
+
+    /// directory to which screenshots (and index.html) are written
+    directory: PathBuf,
+
+    /// extra command line arguments, i.e. --proxy-server
+    args: Vec<String>,
+
+    /// how long a single screenshot may take before the browser is killed
+    timeout: Duration,
+
+    /// number of screenshots attempted so far, used to number the images
+    attempts: AtomicUsize,
+
+    /// screenshots taken so far, in the order they were taken
+    shots: Mutex<Vec<Shot>>,
+}
+
+/// Queue of findings to screenshot, along with the workers draining it
+#[derive(Debug)]
+pub struct Screenshotter {
+    /// status codes worth a screenshot
+    codes: Vec<u16>,
+
+    /// content types worth a screenshot, matched as substrings of the Content-Type header
+    types: Vec<String>,
+
+    /// sending half of the queue; `None` once the queue is closed
+    tx: Mutex<Option<SyncSender<Job>>>,
+
+    /// worker threads
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Screenshotter {
+    /// create the screenshot directory and start the workers
+    fn new(config: &Configuration) -> Result<Self> {
+        let executable = if config.chrome.is_empty() {
+            find_browser().with_context(|| {
+                fmt_err("Could not find Chrome/Chromium on the PATH, use --chrome to point at it")
+            })?
+        } else {
+            PathBuf::from(&config.chrome)
+        };
+
+        let directory = PathBuf::from(&config.screenshots);
+
+        fs::create_dir_all(&directory)
+            .with_context(|| fmt_err(&format!("Could not create {}", directory.display())))?;
+
+        let mut args = Vec::new();
+
+        if !config.proxy.is_empty() {
+            args.push(format!("--proxy-server={}", config.proxy));
+        }
+
+        if config.insecure {
+            args.push(String::from("--ignore-certificate-errors"));
+        }
+
+        let browser = Arc::new(Browser {
+            executable,
+            directory,
+            args,
+            timeout: Duration::from_secs(config.timeout.max(1) * 3),
+            attempts: AtomicUsize::new(0),
+            shots: Mutex::new(Vec::new()),
+        });
+
+        let (tx, rx) = sync_channel::<Job>(SCREENSHOT_QUEUE_SIZE);
+        let rx = Arc::new(Mutex::new(rx));
+
+        let workers = (0..SCREENSHOT_WORKERS)
+            .map(|id| {
+                let browser = browser.clone();
+                let rx = rx.clone();
+                thread::spawn(move || work(id, &browser, &rx))
+            })
+            .collect();
+
+        Ok(Self {
+            codes: config.screenshot_codes.clone(),
+            types: config
+                .screenshot_types
+                .iter()
+                .map(|kind| kind.to_ascii_lowercase())
+                .collect(),
+            tx: Mutex::new(Some(tx)),
+            workers: Mutex::new(workers),
+        })
+    }
+
+    /// whether the given response is worth a screenshot
+    fn matches(&self, response: &FeroxResponse) -> bool {
+        if !self.codes.contains(&response.status().as_u16()) {
+            return false;
+        }
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        self.types.iter().any(|kind| content_type.contains(kind))
+    }
+
+    /// queue the given response for a screenshot, if it's worth one; never blocks
+    pub fn add(&self, response: &FeroxResponse) {
+        if !self.matches(response) {
+            return;
+        }
+
+        let job = Job {
+            url: response.url().to_string(),
+            status: response.status().as_u16(),
+        };
+
+        let Ok(guard) = self.tx.lock() else {
+            return;
+        };
+
+        let Some(tx) = guard.as_ref() else {
+            return;
+        };
+
+        match tx.try_send(job) {
+            Ok(_) => {}
+            Err(TrySendError::Full(job)) => {
+                log::warn!("screenshot queue is full, skipping {}", job.url)
+            }
+            Err(TrySendError::Disconnected(job)) => {
+                log::warn!("screenshot workers are gone, skipping {}", job.url)
+            }
+        }
+    }
+
+    /// close the queue and wait for the screenshots already in it
+    pub fn finish(&self) {
+        if let Ok(mut guard) = self.tx.lock() {
+            guard.take();
+        }
+
+        let workers = self
+            .workers
+            .lock()
+            .map(|mut guard| guard.drain(..).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        for worker in workers {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// first of the known browser executables found on the PATH
+fn find_browser() -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+
+    env::split_paths(&path)
+        .flat_map(|dir| BROWSERS.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// worker loop; take jobs off the queue until it's closed and empty
+fn work(id: usize, browser: &Browser, rx: &Mutex<Receiver<Job>>) {
+    // each browser gets a profile of its own, concurrent instances can't share one
+    let profile = env::temp_dir().join(format!("ferox-chrome-{}-{id}", std::process::id()));
+
+    loop {
+        let job = match rx.lock().map(|rx| rx.recv()) {
+            Ok(Ok(job)) => job,
+            _ => break,
+        };
+
+        let filename = filename(&job, browser);
+
+        match screenshot(
+            browser,
+            &job.url,
+            &browser.directory.join(&filename),
+            &profile,
+        ) {
+            Ok(_) => record(browser, Shot { job, filename }),
+            Err(e) => log::warn!("Could not screenshot {}: {}", job.url, e),
+        }
+    }
+
+    let _ = fs::remove_dir_all(&profile);
+}
+
+/// image filename for the given job, numbered in the order screenshots were attempted
+fn filename(job: &Job, browser: &Browser) -> String {
+    let number = browser.attempts.fetch_add(1, Ordering::Relaxed) + 1;
+
+    let slug = job
+        .url
+        .replace("://", "_")
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "_");
+
+    let slug: String = slug.chars().take(100).collect();
+
+    format!("{number:04}-{}-{slug}.png", job.status)
+}
+
+/// run the browser against the given url, killing it if it takes too long
+fn screenshot(browser: &Browser, url: &str, output: &Path, profile: &Path) -> Result<()> {
+    let mut child = Command::new(&browser.executable)
+        .args([
+            "--headless",
+            "--disable-gpu",
+            "--no-sandbox",
+            "--hide-scrollbars",
+            &format!("--window-size={WINDOW_SIZE}"),
+            &format!("--user-data-dir={}", profile.display()),
+            &format!("--screenshot={}", output.display()),
+        ])
+        .args(&browser.args)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| {
+            fmt_err(&format!(
+                "Could not run {}",
+                browser.executable.to_string_lossy()
+            ))
+        })?;
+
+    let started = Instant::now();
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if started.elapsed() > browser.timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("browser didn't finish within {:?}", browser.timeout);
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    };
+
+    if !status.success() || !output.is_file() {
+        bail!(
+            "browser exited with {status} without writing {}",
+            output.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// remember a screenshot and rewrite index.html to include it
+fn record(browser: &Browser, shot: Shot) {
+    let Ok(mut shots) = browser.shots.lock() else {
+        return;
+    };
+
+    shots.push(shot);
+
+    let index = browser.directory.join("index.html");
+
+    if let Err(e) = write_index(&index, &shots) {
+        log::warn!("Could not write {}: {}", index.display(), e);
+    }
+}
+
+/// minimal html escaping for urls and filenames placed into index.html
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// write an html page linking every screenshot, along with the finding it belongs to
+fn write_index(path: &Path, shots: &[Shot]) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writeln!(
+        writer,
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>feroxbuster screenshots</title></head>\n<body>"
+    )?;
+
+    for shot in shots {
+        let url = escape_html(&shot.job.url);
+        let filename = escape_html(&shot.filename);
+
+        writeln!(
+            writer,
+            "<div><p>{} <a href=\"{url}\">{url}</a></p><a href=\"{filename}\"><img src=\"{filename}\" width=\"640\"></a></div>",
+            shot.job.status
+        )?;
+    }
+
+    writeln!(writer, "</body>\n</html>")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// start the screenshot workers, when --screenshots is used
+pub fn initialize(config: &Configuration) -> Result<Option<Screenshotter>> {
+    if config.screenshots.is_empty() {
+        return Ok(None);
+    }
+
+    Screenshotter::new(config).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// response deserialized from the given json
+    fn create_response(json: &str) -> FeroxResponse {
+        serde_json::from_str(json).unwrap()
+    }
+
+    /// configuration that screenshots 200s with html bodies using the given browser
+    fn config(chrome: &str, screenshots: &Path) -> Configuration {
+        Configuration {
+            chrome: chrome.to_string(),
+            screenshots: screenshots.to_string_lossy().to_string(),
+            screenshot_codes: vec![200],
+            screenshot_types: vec![String::from("text/html")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// only findings with a matching status code and content type are worth a screenshot
+    fn screenshotter_matches_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let screenshotter = Screenshotter::new(&config("/bin/true", dir.path())).unwrap();
+
+        let html = create_response(
+            r#"{"url":"http://localhost/","status":200,"headers":{"content-type":"text/html; charset=utf-8"}}"#,
+        );
+        let json = create_response(
+            r#"{"url":"http://localhost/api","status":200,"headers":{"content-type":"application/json"}}"#,
+        );
+        let missing = create_response(
+            r#"{"url":"http://localhost/gone","status":404,"headers":{"content-type":"text/html"}}"#,
+        );
+
+        assert!(screenshotter.matches(&html));
+        assert!(!screenshotter.matches(&json));
+        assert!(!screenshotter.matches(&missing));
+
+        screenshotter.finish();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    /// queued findings should be handed to the browser and linked from index.html
+    fn screenshotter_runs_browser_and_writes_index() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+
+        // stands in for chrome; writes whatever --screenshot= points at
+        let browser = dir.path().join("fake-chrome");
+        fs::write(
+            &browser,
+            "#!/bin/sh\nfor arg in \"$@\"; do case \"$arg\" in --screenshot=*) printf png > \"${arg#--screenshot=}\";; esac; done\n",
+        )
+        .unwrap();
+        fs::set_permissions(&browser, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let output = dir.path().join("shots");
+        let screenshotter =
+            Screenshotter::new(&config(&browser.to_string_lossy(), &output)).unwrap();
+
+        screenshotter.add(&create_response(
+            r#"{"url":"http://localhost/admin?a=1&b=2","status":200,"headers":{"content-type":"text/html"}}"#,
+        ));
+        screenshotter.finish();
+
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains("http://localhost/admin?a=1&amp;b=2"));
+        assert!(index.contains("0001-200-http_localhost_admin_a_1_b_2.png"));
+        assert!(output
+            .join("0001-200-http_localhost_admin_a_1_b_2.png")
+            .is_file());
+    }
+
+    #[test]
+    /// special characters shouldn't leak into index.html
+    fn escape_html_escapes_markup() {
+        assert_eq!(
+            escape_html(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,526 @@
+//! keyword search in response bodies (--search-regex)
+//!
+//! every body is searched for the given patterns as it's read off the wire, filtered responses
+//! included; a key or password leaking from a page that's otherwise uninteresting is still worth
+//! knowing about. each match is printed alongside the responses and written to --output (as
+//! `{"type":"search_match",...}` with --json), along with where in the body it was found and a
+//! bit of the text around it
+//!
+//! the search only ever holds a small window of the body, so it costs the same no matter how
+//! large the body is
+//!
+//! with --entropy-secrets, text bodies are also looked over for long base64 and hex strings that
+//! are random enough to be keys or tokens. these are reported the same way (as
+//! `{"type":"secret",...}` with --json) and the response is tagged `high-entropy`; each string is
+//! only reported the first time it's seen, so a token that's on every page shows up once
+use std::{collections::HashSet, sync::Mutex};
+
+use anyhow::{Context, Result};
+use console::style;
+use lazy_static::lazy_static;
+use regex::bytes::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Configuration, OutputLevel},
+    event_handlers::{Command, Handles},
+    response::FeroxResponse,
+    traits::FeroxSerialize,
+    utils::fmt_err,
+};
+
+/// bytes of a body kept around between chunks; matches longer than this (less the context) may
+/// be missed when they're split across chunks
+const OVERLAP: usize = 1024;
+
+/// bytes of text shown on either side of a match
+const CONTEXT: usize = 32;
+
+/// number of matches reported per response; a page that's full of matches would otherwise bury
+/// the scan's output
+const MAX_MATCHES: usize = 25;
+
+/// shortest string checked for being a base64 encoded secret
+const MIN_BASE64_LEN: usize = 20;
+
+/// shortest string checked for being a hex encoded secret; shorter ones are mostly cache busters
+/// and commit ids
+const MIN_HEX_LEN: usize = 32;
+
+/// tag given to responses holding a high entropy string
+const SECRET_TAG: &str = "high-entropy";
+
+lazy_static! {
+    /// runs of characters that could make up a base64 (standard or url safe) or hex string
+    static ref SECRET_CANDIDATE: regex::Regex =
+        regex::Regex::new(r"[A-Za-z0-9+/_-]{20,}={0,2}").unwrap();
+}
+
+/// Patterns given to --search-regex and the --entropy-secrets state
+#[derive(Debug, Default)]
+pub struct Search {
+    /// patterns given to --search-regex
+    patterns: Vec<Regex>,
+
+    /// --entropy-threshold, when --entropy-secrets is used
+    entropy_threshold: Option<f64>,
+
+    /// high entropy strings that were already reported
+    reported: Mutex<HashSet<String>>,
+}
+
+/// implementation of Search
+impl Search {
+    /// start searching the body of the given url; `None` when --search-regex wasn't used
+    pub(crate) fn start(&self, url: &str) -> Option<BodySearch> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+
+        Some(BodySearch {
+            url: url.to_string(),
+            next_start: vec![0; self.patterns.len()],
+            patterns: self.patterns.clone(),
+            window: Vec::new(),
+            window_start: 0,
+            matches: Vec::new(),
+        })
+    }
+
+    /// look over the text of the given response for high entropy strings; those that weren't
+    /// reported yet are handed to the response to be reported, and the response is tagged
+    /// `high-entropy`. does nothing when --entropy-secrets wasn't used
+    pub(crate) fn detect_secrets(&self, response: &mut FeroxResponse) {
+        let Some(threshold) = self.entropy_threshold else {
+            return;
+        };
+
+        let secrets = find_secrets(response.url().as_str(), response.text(), threshold);
+
+        if secrets.is_empty() {
+            return;
+        }
+
+        response.add_tag(String::from(SECRET_TAG));
+
+        let Ok(mut reported) = self.reported.lock() else {
+            return;
+        };
+
+        for secret in secrets {
+            if reported.insert(secret.matched.clone()) {
+                response.add_search_match(secret);
+            }
+        }
+    }
+}
+
+/// compile the patterns given to --search-regex and turn on --entropy-secrets; nothing is
+/// searched for when neither was used
+pub fn initialize(config: &Configuration) -> Result<Search> {
+    let patterns = config
+        .search_regex
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .with_context(|| fmt_err(&format!("Invalid --search-regex {pattern}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Search {
+        patterns,
+        entropy_threshold: config.entropy_secrets.then_some(config.entropy_threshold),
+        reported: Mutex::new(HashSet::new()),
+    })
+}
+
+/// A --search-regex match or --entropy-secrets string found in a response body
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"search_match"}` or
+    /// `{"type":"secret"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// url whose body held the match
+    pub url: String,
+
+    /// the pattern that matched; for secrets, how the string is encoded (base64 or hex)
+    pub pattern: String,
+
+    /// entropy of a secret, in bits per character
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<f64>,
+
+    /// byte offset of the match in the body; secrets are found in the decoded text
+    pub offset: usize,
+
+    /// the text that matched
+    pub matched: String,
+
+    /// the match along with the text around it
+    pub snippet: String,
+}
+
+/// FeroxSerialize implementation for SearchMatch
+impl FeroxSerialize for SearchMatch {
+    /// one line summary, i.e.
+    /// `MATCH http://localhost/app.js @1024 [api[_-]?key]: var api_key = "..."` or
+    /// `SECRET http://localhost/app.js @2048 [base64 5.12]: token = "..."`
+    fn as_str(&self) -> String {
+        let (label, pattern) = match self.entropy {
+            Some(entropy) => ("SECRET", format!("[{} {entropy:.2}]", self.pattern)),
+            None => ("MATCH", format!("[{}]", self.pattern)),
+        };
+
+        format!(
+            "{} {} @{} {}: {}\n",
+            style(label).bright().magenta(),
+            self.url,
+            self.offset,
+            style(pattern).dim(),
+            style(&self.snippet).yellow()
+        )
+    }
+
+    /// Create an NDJSON representation of the match
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the search match in {} to JSON",
+                self.url
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// text of the given bytes with anything that would mess up a single line of output replaced
+fn printable(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// Searches a single body, a chunk at a time
+#[derive(Debug)]
+pub(crate) struct BodySearch {
+    /// url the body belongs to
+    url: String,
+
+    /// patterns searched for
+    patterns: Vec<Regex>,
+
+    /// the part of the body that's still being looked at
+    window: Vec<u8>,
+
+    /// offset of the start of `window` in the body
+    window_start: usize,
+
+    /// for each pattern, the offset in the body where its next match may start; keeps matches in
+    /// the part of the window carried over from one chunk to the next from being reported twice
+    next_start: Vec<usize>,
+
+    /// matches found so far
+    matches: Vec<SearchMatch>,
+}
+
+impl BodySearch {
+    /// search the next chunk of the body
+    pub(crate) fn feed(&mut self, chunk: &[u8]) {
+        if self.matches.len() >= MAX_MATCHES {
+            return;
+        }
+
+        self.window.extend_from_slice(chunk);
+        self.search(false);
+    }
+
+    /// search what's left of the body and hand back every match found in it
+    pub(crate) fn finish(mut self) -> Vec<SearchMatch> {
+        if self.matches.len() < MAX_MATCHES {
+            self.search(true);
+        }
+
+        self.matches
+    }
+
+    /// look for matches in the window; until the end of the body, only matches with OVERLAP
+    /// bytes after them are taken, the rest are found again once more of the body is in
+    fn search(&mut self, last: bool) {
+        let settled = if last {
+            self.window.len()
+        } else {
+            self.window.len().saturating_sub(OVERLAP)
+        };
+
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            for found in pattern.find_iter(&self.window) {
+                if found.end() > settled || self.matches.len() >= MAX_MATCHES {
+                    break;
+                }
+
+                let offset = self.window_start + found.start();
+
+                if found.as_bytes().is_empty() || offset < self.next_start[index] {
+                    continue;
+                }
+
+                self.next_start[index] = self.window_start + found.end();
+
+                let before = found.start().saturating_sub(CONTEXT);
+                let after = (found.end() + CONTEXT).min(self.window.len());
+
+                self.matches.push(SearchMatch {
+                    kind: String::from("search_match"),
+                    url: self.url.clone(),
+                    pattern: pattern.to_string(),
+                    entropy: None,
+                    offset,
+                    matched: printable(found.as_bytes()),
+                    snippet: printable(&self.window[before..after]).trim().to_string(),
+                });
+            }
+        }
+
+        if self.matches.len() >= MAX_MATCHES {
+            log::info!(
+                "{} reached the limit of {} search matches, the rest aren't reported",
+                self.url,
+                MAX_MATCHES
+            );
+        }
+
+        // anything that could still be part of a match that isn't settled yet is kept
+        let keep_from = settled.saturating_sub(OVERLAP);
+        self.window.drain(..keep_from);
+        self.window_start += keep_from;
+    }
+}
+
+/// shannon entropy of the given string, in bits per character
+fn entropy(text: &str) -> f64 {
+    let mut counts = [0usize; 256];
+
+    for byte in text.bytes() {
+        counts[byte as usize] += 1;
+    }
+
+    let len = text.len() as f64;
+
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// how the given string is encoded, along with the most entropy a string of that encoding can
+/// have; `None` for strings that don't look encoded at all
+fn encoding(candidate: &str) -> Option<(&'static str, f64)> {
+    if candidate.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return (candidate.len() >= MIN_HEX_LEN).then_some(("hex", 4.0));
+    }
+
+    // long words, paths, and identifiers are made of letters alone
+    let has_digit = candidate.bytes().any(|byte| byte.is_ascii_digit());
+    let has_alpha = candidate.bytes().any(|byte| byte.is_ascii_alphabetic());
+
+    (has_digit && has_alpha && candidate.len() >= MIN_BASE64_LEN).then_some(("base64", 6.0))
+}
+
+/// high entropy strings in the given text, up to MAX_MATCHES of them
+///
+/// the threshold is in bits per character of a base64 string; hex strings, which can't carry
+/// more than 4 bits per character, are held to the same share of their maximum
+fn find_secrets(url: &str, text: &str, threshold: f64) -> Vec<SearchMatch> {
+    let mut secrets = Vec::new();
+
+    for found in SECRET_CANDIDATE.find_iter(text) {
+        if secrets.len() >= MAX_MATCHES {
+            break;
+        }
+
+        let candidate = found.as_str().trim_end_matches('=');
+
+        let Some((encoding, max_bits)) = encoding(candidate) else {
+            continue;
+        };
+
+        let bits = entropy(candidate);
+
+        if bits < threshold * max_bits / 6.0 {
+            continue;
+        }
+
+        let before = found.start().saturating_sub(CONTEXT);
+        let after = (found.end() + CONTEXT).min(text.len());
+
+        secrets.push(SearchMatch {
+            kind: String::from("secret"),
+            url: url.to_string(),
+            pattern: encoding.to_string(),
+            entropy: Some((bits * 100.0).round() / 100.0),
+            offset: found.start(),
+            matched: candidate.to_string(),
+            snippet: printable(&text.as_bytes()[before..after])
+                .trim()
+                .to_string(),
+        });
+    }
+
+    secrets
+}
+
+/// send the matches found in the given response's body to the output handler
+pub fn report(response: &mut FeroxResponse, handles: &Handles) {
+    for found in response.take_search_matches() {
+        handles
+            .output
+            .send(Command::ReportSearchMatch(Box::new(found)))
+            .unwrap_or_default();
+    }
+}
+
+/// whether search matches are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a search for the given patterns, independent of --search-regex
+    fn search(patterns: &[&str]) -> BodySearch {
+        let patterns: Vec<_> = patterns.iter().map(|p| Regex::new(p).unwrap()).collect();
+
+        BodySearch {
+            url: String::from("http://localhost/app.js"),
+            next_start: vec![0; patterns.len()],
+            patterns,
+            window: Vec::new(),
+            window_start: 0,
+            matches: Vec::new(),
+        }
+    }
+
+    #[test]
+    /// a match split across chunks is found once, at its offset in the whole body
+    fn matches_across_chunks_are_found_once() {
+        let mut body = vec![b'a'; OVERLAP * 3];
+        body.extend_from_slice(b"var api_key = 'secret';");
+        body.extend(vec![b'b'; OVERLAP * 2]);
+        body.extend_from_slice(b"BEGIN RSA PRIVATE KEY");
+
+        let offset = OVERLAP * 3 + 4;
+
+        // chunk sizes that split the first match down the middle
+        for size in [7, 100, OVERLAP, OVERLAP * 3 + 6, body.len()] {
+            let mut searching = search(&["api[_-]?key", "BEGIN RSA"]);
+
+            for chunk in body.chunks(size) {
+                searching.feed(chunk);
+            }
+
+            let mut matches = searching.finish();
+            matches.sort_by_key(|found| found.offset);
+
+            assert_eq!(matches.len(), 2, "chunk size {size}");
+            assert_eq!(matches[0].offset, offset);
+            assert_eq!(matches[0].matched, "api_key");
+            assert!(matches[0].snippet.contains("var api_key = 'secret';"));
+            assert_eq!(
+                matches[1].offset,
+                body.len() - "BEGIN RSA PRIVATE KEY".len()
+            );
+            assert_eq!(matches[1].pattern, "BEGIN RSA");
+        }
+    }
+
+    #[test]
+    /// only so many matches are reported per body, and the window doesn't grow with the body
+    fn matches_are_capped_and_window_stays_small() {
+        let mut searching = search(&["key"]);
+
+        for _ in 0..100 {
+            searching.feed(&[b'x'; 4096]);
+            assert!(searching.window.len() <= OVERLAP * 2 + 4096);
+        }
+
+        for _ in 0..MAX_MATCHES * 2 {
+            searching.feed(&[b"key\n".as_slice(), &[b'x'; OVERLAP]].concat());
+        }
+
+        assert_eq!(searching.finish().len(), MAX_MATCHES);
+    }
+
+    #[test]
+    /// random strings have more entropy than repetitive ones
+    fn entropy_measures_randomness() {
+        assert_eq!(entropy("aaaaaaaa"), 0.0);
+        assert_eq!(entropy("abababab"), 1.0);
+        assert_eq!(entropy("0123456789abcdef"), 4.0);
+    }
+
+    #[test]
+    /// keys and tokens are found, words, identifiers, and short hex strings aren't
+    fn find_secrets_finds_encoded_keys() {
+        let text = concat!(
+            "<script>var key = \"AKIAIOSFODNN7EXAMPLEwJalrXUtnFEMI/K7MDENG+bPxRfiCY\";\n",
+            "var sha = \"9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08\";\n",
+            "var name = \"internationalizationConfigurationProvider\";\n",
+            "var commit = \"0123456789abcdef0123\";\n",
+            "var repeated = \"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa111\";</script>"
+        );
+
+        let secrets = find_secrets("http://localhost/", text, 4.5);
+
+        let found: Vec<_> = secrets
+            .iter()
+            .map(|secret| (secret.pattern.as_str(), secret.matched.as_str()))
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                (
+                    "base64",
+                    "AKIAIOSFODNN7EXAMPLEwJalrXUtnFEMI/K7MDENG+bPxRfiCY"
+                ),
+                (
+                    "hex",
+                    "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+                )
+            ]
+        );
+
+        assert_eq!(secrets[0].offset, text.find("AKIA").unwrap());
+        assert!(secrets[0].entropy.unwrap() >= 4.5);
+        assert!(secrets[0]
+            .as_json()
+            .unwrap()
+            .starts_with(r#"{"type":"secret""#));
+        assert!(secrets[0].as_str().contains("SECRET"));
+    }
+
+    #[test]
+    /// the match serializes with its type, and control characters stay out of the snippet
+    fn search_match_serializes_with_type() {
+        let mut searching = search(&["password"]);
+        searching.feed(b"user=admin\r\npassword=hunter2\r\n");
+        let found = searching.finish().remove(0);
+
+        assert_eq!(found.snippet, "user=admin  password=hunter2");
+
+        let json = found.as_json().unwrap();
+        assert!(json.starts_with(r#"{"type":"search_match""#));
+        assert!(json.contains(r#""offset":12"#));
+        assert!(found.as_str().contains("password"));
+    }
+}
@@ -0,0 +1,331 @@
+//! end-to-end check of a build and its settings (`feroxbuster selftest`)
+//!
+//! a small http server is started on localhost and scanned with whatever settings were given
+//! before `selftest` (config files included), using a wordlist of its own. the server holds a
+//! page, a redirect, a throttled (429) endpoint, and a directory that answers every request
+//! with the same page; once the scan ends, the findings and statistics are compared with what
+//! the server is known to hold. nothing leaves the machine, so the check works where the
+//! network is locked down
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use crate::{config::Configuration, event_handlers::Handles, scanner::RESPONSES, utils::fmt_err};
+
+/// words scanned for; the first (empty) word requests the target itself, as with any wordlist
+const WORDLIST: [&str; 6] = ["", "admin", "old", "busy", "missing", "backup"];
+
+/// largest request head the server reads before answering
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+/// the wordlist used by `selftest`
+pub fn wordlist() -> Arc<Vec<String>> {
+    Arc::new(WORDLIST.iter().map(|word| word.to_string()).collect())
+}
+
+/// status code, extra headers, and body served at the given path
+fn route(path: &str) -> (u16, Vec<(&'static str, &'static str)>, String) {
+    let page = |text: &str| format!("<html><body><p>{text}</p></body></html>");
+
+    match path {
+        "/" => (200, vec![], page("feroxbuster selftest")),
+        "/admin" => (200, vec![], page("admin panel")),
+        "/old" => (301, vec![("Location", "/admin")], String::new()),
+        "/busy" => (429, vec![("Retry-After", "1")], page("slow down")),
+        wild if wild.starts_with("/wild/") => (200, vec![], page("this page is everywhere")),
+        _ => (404, vec![], page("not found")),
+    }
+}
+
+/// read a single request off the given connection and answer it
+async fn answer(mut stream: TcpStream) -> Result<()> {
+    let mut head = Vec::new();
+    let mut buffer = [0; 1024];
+
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let read = stream.read(&mut buffer).await?;
+
+        if read == 0 {
+            break;
+        }
+
+        head.extend_from_slice(&buffer[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or("/");
+    let path = target.split(['?', '#']).next().unwrap_or("/");
+
+    let (status, headers, body) = route(path);
+    let status = StatusCode::from_u16(status)?;
+
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default(),
+        body.len()
+    );
+
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+
+    response.push_str("\r\n");
+
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+/// The built-in server scanned by `selftest`; stops when dropped
+#[derive(Debug)]
+pub struct TestServer {
+    /// address the server listens on
+    address: SocketAddr,
+
+    /// task accepting connections
+    task: JoinHandle<()>,
+}
+
+impl TestServer {
+    /// start listening on a free port on localhost
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .with_context(|| fmt_err("Could not start the selftest server"))?;
+
+        let address = listener.local_addr()?;
+
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    answer(stream)
+                        .await
+                        .unwrap_or_else(|e| log::debug!("selftest server: {}", e));
+                });
+            }
+        });
+
+        log::info!("selftest server listening on {}", address);
+        Ok(Self { address, task })
+    }
+
+    /// targets scanned by `selftest`: the server itself and its catch-all directory
+    pub fn targets(&self) -> Vec<String> {
+        vec![
+            format!("http://{}", self.address),
+            format!("http://{}/wild/", self.address),
+        ]
+    }
+}
+
+/// Drop implementation for TestServer
+impl Drop for TestServer {
+    /// stop accepting connections
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// what the finished scan came up with, as far as the checks are concerned
+#[derive(Debug, Default)]
+struct Outcome {
+    /// path and status code of every finding
+    reported: Vec<(String, u16)>,
+
+    /// number of 429 responses counted
+    status_429s: usize,
+
+    /// number of responses removed by wildcard filters
+    wildcards_filtered: usize,
+}
+
+/// the checks made against the given outcome; each one is a description along with what went
+/// wrong, if anything
+fn checks(outcome: &Outcome, config: &Configuration) -> Vec<(&'static str, Result<(), String>)> {
+    let reported = |path: &str| {
+        outcome
+            .reported
+            .iter()
+            .find(|(reported, _)| reported == path)
+            .map(|(_, status)| *status)
+    };
+
+    let findings = match reported("/admin") {
+        Some(200) => Ok(()),
+        Some(status) => Err(format!("/admin was reported with {status} instead of 200")),
+        None => Err(String::from(
+            "/admin (200) wasn't reported; check the status codes and filters in use",
+        )),
+    };
+
+    let redirects = match reported("/old") {
+        Some(_) => Ok(()),
+        None => Err(String::from(
+            "/old (301) wasn't reported; check the status codes and filters in use",
+        )),
+    };
+
+    let not_found = match ["/missing", "/backup"]
+        .into_iter()
+        .find(|path| reported(path).is_some())
+    {
+        Some(path) => Err(format!("{path} (404) was reported")),
+        None => Ok(()),
+    };
+
+    let throttling = if outcome.status_429s > 0 {
+        Ok(())
+    } else {
+        Err(String::from("/busy (429) wasn't counted as throttled"))
+    };
+
+    let leaked: Vec<_> = outcome
+        .reported
+        .iter()
+        .filter(|(path, _)| path.starts_with("/wild/") && path.len() > "/wild/".len())
+        .map(|(path, _)| path.as_str())
+        .collect();
+
+    let wildcards = if config.dont_filter {
+        Err(String::from("--dont-filter turns wildcard filtering off"))
+    } else if !leaked.is_empty() {
+        Err(format!("{} made it through", leaked.join(", ")))
+    } else if outcome.wildcards_filtered == 0 {
+        Err(String::from(
+            "/wild/ wasn't detected as a wildcard directory",
+        ))
+    } else {
+        Ok(())
+    };
+
+    vec![
+        ("findings are reported", findings),
+        ("redirects are reported", redirects),
+        ("404s are left out", not_found),
+        ("429s are counted", throttling),
+        ("wildcard pages are filtered", wildcards),
+    ]
+}
+
+/// compare the finished scan with what the server holds and print the results; returns whether
+/// every check passed
+///
+/// meant to be called after every handler has drained, so the counts it looks at are final
+pub fn verify(handles: &Handles) -> bool {
+    let reported = RESPONSES
+        .responses
+        .read()
+        .map(|responses| {
+            responses
+                .iter()
+                .map(|response| {
+                    (
+                        response.url().path().to_string(),
+                        response.status().as_u16(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let outcome = Outcome {
+        reported,
+        status_429s: handles.stats.data.status_429s(),
+        wildcards_filtered: handles.stats.data.wildcards_filtered(),
+    };
+
+    let results = checks(&outcome, &handles.config);
+    let passed = results.iter().filter(|(_, result)| result.is_ok()).count();
+
+    for (description, result) in &results {
+        match result {
+            Ok(()) => println!("✅ {description}"),
+            Err(reason) => println!("❌ {description}: {reason}"),
+        }
+    }
+
+    if passed == results.len() {
+        println!("selftest passed ({passed} of {} checks)", results.len());
+        true
+    } else {
+        println!(
+            "selftest failed ({passed} of {} checks passed)",
+            results.len()
+        );
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// outcome of a scan where everything went as expected
+    fn expected() -> Outcome {
+        Outcome {
+            reported: vec![
+                (String::from("/"), 200),
+                (String::from("/admin"), 200),
+                (String::from("/old"), 301),
+            ],
+            status_429s: 1,
+            wildcards_filtered: 5,
+        }
+    }
+
+    #[test]
+    /// the server holds what the checks expect of it
+    fn routes_match_the_checks() {
+        assert_eq!(route("/admin").0, 200);
+        assert_eq!(route("/old").1, [("Location", "/admin")]);
+        assert_eq!(route("/busy").0, 429);
+        assert_eq!(route("/missing").0, 404);
+        assert_eq!(route("/wild/anything").2, route("/wild/else").2);
+    }
+
+    #[test]
+    /// every check passes on the expected outcome, and each one catches its own problem
+    fn checks_catch_what_went_wrong() {
+        let config = Configuration::default();
+        assert!(checks(&expected(), &config)
+            .iter()
+            .all(|(_, result)| result.is_ok()));
+
+        let mut outcome = expected();
+        outcome.reported.retain(|(path, _)| path != "/admin");
+        outcome.reported.push((String::from("/missing"), 404));
+        outcome.reported.push((String::from("/wild/admin"), 200));
+        outcome.status_429s = 0;
+
+        let failed: Vec<_> = checks(&outcome, &config)
+            .into_iter()
+            .filter(|(_, result)| result.is_err())
+            .map(|(description, _)| description)
+            .collect();
+
+        assert_eq!(
+            failed,
+            [
+                "findings are reported",
+                "404s are left out",
+                "429s are counted",
+                "wildcard pages are filtered"
+            ]
+        );
+    }
+}
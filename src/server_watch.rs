@@ -0,0 +1,238 @@
+//! server banner delta alerting (--server-alerts)
+//!
+//! the first Server and X-Powered-By values seen on each host are remembered; when a later
+//! response from the same host comes back with a different value, an alert is shown and written
+//! to --output (as `{"type":"server_change",...}` with --json) and the --pipe-to command. a change
+//! mid-scan usually means a WAF took over or a different backend pool answered
+//!
+//! each change also counts against the scan that saw it, which lets --auto-tune slow the scan
+//! down and --auto-bail/--auto-requeue pull it, the same as too many 403s or 429s would
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::OutputLevel, response::FeroxResponse, traits::FeroxSerialize, url::FeroxUrl,
+    utils::fmt_err,
+};
+
+/// headers that are tracked, compared case insensitively
+const WATCHED_HEADERS: [&str; 2] = ["server", "x-powered-by"];
+
+/// number of alerts shown per host; a host that flips back and forth between backends on every
+/// request would otherwise bury the scan's output
+const MAX_ALERTS_PER_HOST: usize = 10;
+
+/// Last seen values of the watched headers of every host (--server-alerts)
+#[derive(Debug, Default)]
+pub struct ServerWatch {
+    /// what's been seen from each host, keyed by host:port
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+/// What's been seen from a single host
+#[derive(Debug, Default)]
+struct HostState {
+    /// last value seen for each of the watched headers
+    values: HashMap<&'static str, String>,
+
+    /// number of alerts raised for the host
+    alerts: usize,
+}
+
+/// A watched header that changed value on a host
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ServerChangeInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"server_change"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// url whose response carried the new value
+    pub url: String,
+
+    /// host:port the header is tracked for
+    pub host: String,
+
+    /// name of the header that changed
+    pub header: String,
+
+    /// value seen before
+    pub previous: String,
+
+    /// value seen now
+    pub current: String,
+}
+
+/// FeroxSerialize implementation for ServerChangeInfo
+impl FeroxSerialize for ServerChangeInfo {
+    /// one line summary, i.e.
+    /// `SRV localhost:80 server: nginx => cloudflare [http://localhost/admin]`
+    fn as_str(&self) -> String {
+        format!(
+            "{} {} {}: {} => {} {}\n",
+            style("SRV").bright().red(),
+            self.host,
+            self.header,
+            self.previous,
+            style(&self.current).yellow(),
+            style(format!("[{}]", self.url)).dim()
+        )
+    }
+
+    /// Create an NDJSON representation of the change
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the server change on {} to JSON",
+                self.host
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// implementation of ServerWatch
+impl ServerWatch {
+    /// compare the watched headers of the given response against what its host sent before
+    ///
+    /// the first value seen for a header becomes the one later responses are compared against; a
+    /// response that doesn't carry the header at all is ignored. each change becomes the new value
+    /// to compare against, so a host that moves from one backend to another alerts once
+    pub fn observe(&self, response: &FeroxResponse) -> Vec<ServerChangeInfo> {
+        let host = FeroxUrl::host_key(response.url());
+
+        let Ok(mut hosts) = self.hosts.lock() else {
+            return Vec::new();
+        };
+
+        let state = hosts.entry(host.clone()).or_default();
+        let mut changes = Vec::new();
+
+        for header in WATCHED_HEADERS {
+            let Some(current) = response
+                .headers()
+                .get(header)
+                .and_then(|value| value.to_str().ok())
+            else {
+                continue;
+            };
+
+            match state.values.get(header) {
+                None => {
+                    state.values.insert(header, current.to_string());
+                }
+                Some(previous) if previous != current => {
+                    changes.push(ServerChangeInfo {
+                        kind: String::from("server_change"),
+                        url: response.url().to_string(),
+                        host: host.clone(),
+                        header: header.to_string(),
+                        previous: previous.clone(),
+                        current: current.to_string(),
+                    });
+                    state.values.insert(header, current.to_string());
+                }
+                Some(_) => {}
+            }
+        }
+
+        if !changes.is_empty() {
+            state.alerts += 1;
+
+            if state.alerts > MAX_ALERTS_PER_HOST {
+                for change in changes.drain(..) {
+                    log::info!(
+                        "{} {} changed from {} to {} (alert limit reached)",
+                        change.host,
+                        change.header,
+                        change.previous,
+                        change.current
+                    );
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+/// whether server change alerts are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// response for the given url with the given Server header
+    fn response(url: &str, server: Option<&str>) -> FeroxResponse {
+        let headers = match server {
+            Some(server) => serde_json::json!({ "server": server }),
+            None => serde_json::json!({}),
+        };
+
+        serde_json::from_value(serde_json::json!({ "url": url, "headers": headers })).unwrap()
+    }
+
+    #[test]
+    /// the first value is the baseline, a different one alerts, and missing headers are ignored
+    fn observe_reports_changed_values() {
+        let url = "http://server-watch-one.test/";
+        let watch = ServerWatch::default();
+
+        assert!(watch.observe(&response(url, Some("nginx"))).is_empty());
+        assert!(watch.observe(&response(url, Some("nginx"))).is_empty());
+        assert!(watch.observe(&response(url, None)).is_empty());
+
+        let changes = watch.observe(&response(&format!("{url}admin"), Some("cloudflare")));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].host, "server-watch-one.test:80");
+        assert_eq!(changes[0].header, "server");
+        assert_eq!(changes[0].previous, "nginx");
+        assert_eq!(changes[0].current, "cloudflare");
+
+        // the new value is what's compared against from now on
+        assert!(watch.observe(&response(url, Some("cloudflare"))).is_empty());
+
+        // other hosts (and ports) are tracked separately
+        assert!(watch
+            .observe(&response("http://server-watch-one.test:8080/", Some("iis")))
+            .is_empty());
+    }
+
+    #[test]
+    /// a host that keeps flipping stops alerting after MAX_ALERTS_PER_HOST
+    fn observe_caps_alerts_per_host() {
+        let url = "http://server-watch-two.test/";
+        let watch = ServerWatch::default();
+        watch.observe(&response(url, Some("a")));
+
+        let alerts: usize = (0..MAX_ALERTS_PER_HOST * 2)
+            .map(|i| {
+                watch
+                    .observe(&response(url, Some(if i % 2 == 0 { "b" } else { "a" })))
+                    .len()
+            })
+            .sum();
+
+        assert_eq!(alerts, MAX_ALERTS_PER_HOST);
+    }
+
+    #[test]
+    /// the alert serializes with its type
+    fn server_change_serializes_with_type() {
+        let url = "http://server-watch-three.test/";
+        let watch = ServerWatch::default();
+        watch.observe(&response(url, Some("apache")));
+        let change = watch.observe(&response(url, Some("varnish"))).remove(0);
+
+        let json = change.as_json().unwrap();
+        assert!(json.starts_with(r#"{"type":"server_change""#));
+        assert!(json.contains(r#""previous":"apache""#));
+        assert!(change.as_str().contains("apache"));
+    }
+}
@@ -0,0 +1,322 @@
+//! session recording (--record) and offline replay (`feroxbuster replay SESSION`)
+//!
+//! a recording is a gzipped NDJSON file: a header describing the scan (target, shuffle seed),
+//! one entry per response in the order they came back, bodies included, and a trailer holding
+//! the filters learned while scanning (wildcard and similarity filters can't be rebuilt
+//! without the target). replaying a recording runs every response back through the filters
+//! and output settings given on the replay command line without making a single request, and
+//! says which filter removed each response that didn't make it through
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Instant, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde_json::{json, Value};
+
+use crate::{
+    config::{Configuration, OutputLevel},
+    event_handlers::Handles,
+    export::UtcTime,
+    filters::{FeroxFilters, SimilarityFilter, WildcardFilter},
+    plugins,
+    progress::PROGRESS_PRINTER,
+    response::FeroxResponse,
+    utils::{ferox_eprint, fmt_err},
+    VERSION,
+};
+
+/// Writes every response that's about to be filtered to a gzipped NDJSON file
+#[derive(Debug)]
+pub struct Recorder {
+    /// the open recording
+    writer: Mutex<GzEncoder<BufWriter<File>>>,
+
+    /// number of responses recorded so far, gives each entry its place in the order
+    recorded: AtomicUsize,
+
+    /// when recording started
+    started: Instant,
+}
+
+impl Recorder {
+    /// write a single entry; the compressor is flushed every time, so a recording cut short by
+    /// ctrl+c can still be replayed up to that point
+    fn write(&self, entry: &Value) -> Result<()> {
+        if let Ok(mut writer) = self.writer.lock() {
+            writer.write_all(entry.to_string().as_bytes())?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// record the given response, just before it's checked against the filters
+    pub fn record(&self, response: &FeroxResponse) {
+        let entry = json!({
+            "type": "recorded",
+            "seq": self.recorded.fetch_add(1, Ordering::Relaxed),
+            "elapsed": self.started.elapsed().as_secs_f64(),
+            "response": response,
+            "body": response.text(),
+        });
+
+        self.write(&entry)
+            .unwrap_or_else(|e| log::warn!("Could not record {}: {}", response.url(), e));
+    }
+
+    /// write the filters learned while scanning and close the recording
+    pub fn finish(&self, filters: &FeroxFilters) -> Result<()> {
+        let mut wildcards = Vec::new();
+        let mut similarities = Vec::new();
+
+        if let Ok(guard) = filters.filters.read() {
+            for filter in guard.iter() {
+                if let Some(wildcard) = filter.as_any().downcast_ref::<WildcardFilter>() {
+                    wildcards.push(wildcard.clone());
+                } else if let Some(similarity) = filter.as_any().downcast_ref::<SimilarityFilter>()
+                {
+                    similarities.push(similarity.clone());
+                }
+            }
+        }
+
+        self.write(&json!({
+            "type": "filters",
+            "wildcard": wildcards,
+            "similarity": similarities,
+        }))?;
+
+        if let Ok(mut writer) = self.writer.lock() {
+            writer.try_finish()?;
+            writer.get_mut().flush()?;
+        }
+
+        log::info!(
+            "recorded {} responses",
+            self.recorded.load(Ordering::Relaxed)
+        );
+        Ok(())
+    }
+}
+
+/// start the recording given to --record; `None` when it wasn't used, or when replaying
+pub fn initialize(config: &Configuration) -> Result<Option<Recorder>> {
+    if config.record.is_empty() || !config.replay.is_empty() {
+        // recording a replay would only copy the recording
+        return Ok(None);
+    }
+
+    let file = File::create(&config.record)
+        .with_context(|| fmt_err(&format!("Could not open {}", config.record)))?;
+
+    let recorder = Recorder {
+        writer: Mutex::new(GzEncoder::new(BufWriter::new(file), Compression::default())),
+        recorded: AtomicUsize::new(0),
+        started: Instant::now(),
+    };
+
+    recorder.write(&json!({
+        "type": "session",
+        "version": VERSION,
+        "time": UtcTime::from(SystemTime::now()).to_iso8601(),
+        "target": config.target_url,
+        "wordlist": config.wordlist,
+        "shuffle_seed": config.shuffle_seed,
+    }))?;
+
+    Ok(Some(recorder))
+}
+
+/// every entry of the given recording, in the order they were written; a recording that was
+/// cut short ends at its last complete entry
+fn entries(path: &str) -> Result<impl Iterator<Item = Value>> {
+    let file = File::open(path).with_context(|| fmt_err(&format!("Could not open {path}")))?;
+    let path = path.to_string();
+
+    let entries = BufReader::new(GzDecoder::new(file))
+        .lines()
+        .map_while(move |line| {
+            line.map_err(|e| log::warn!("{} ends early: {}", path, e))
+                .ok()
+        })
+        .filter_map(|line| serde_json::from_str(&line).ok());
+
+    Ok(entries)
+}
+
+/// description of the status code check the output handler makes (-s/-C), if the given
+/// response fails it
+fn status_code_filter(response: &FeroxResponse, config: &Configuration) -> Option<String> {
+    let status = response.status().as_u16();
+
+    let reported = if !config.filter_status.is_empty() {
+        !config.filter_status.contains(&status)
+    } else {
+        config.status_codes.contains(&status)
+    };
+
+    (!reported).then(|| format!("Status code: {status}"))
+}
+
+/// run every response of the recording given to `replay` through the filters and output, as
+/// if the scan were happening again; responses that are filtered out are listed along with the
+/// filter responsible
+pub async fn replay(handles: Arc<Handles>) -> Result<()> {
+    let path = &handles.config.replay;
+    let explain = matches!(handles.config.output_level, OutputLevel::Default);
+
+    // the trailer comes last, but its filters need to be in place before the first response
+    for entry in entries(path)? {
+        match entry["type"].as_str() {
+            Some("session") => log::info!("replaying {}: {}", path, entry),
+            Some("filters") => {
+                for wildcard in entry["wildcard"].as_array().into_iter().flatten() {
+                    if let Ok(filter) = serde_json::from_value::<WildcardFilter>(wildcard.clone()) {
+                        handles.filters.data.push(Box::new(filter))?;
+                    }
+                }
+
+                for similarity in entry["similarity"].as_array().into_iter().flatten() {
+                    if let Ok(filter) =
+                        serde_json::from_value::<SimilarityFilter>(similarity.clone())
+                    {
+                        handles.filters.data.push(Box::new(filter))?;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut replayed = 0;
+    let mut filtered = 0;
+
+    for entry in entries(path)? {
+        if entry["type"] != "recorded" {
+            continue;
+        }
+
+        let Ok(mut response) = serde_json::from_value::<FeroxResponse>(entry["response"].clone())
+        else {
+            continue;
+        };
+
+        response.restore_text(entry["body"].as_str().unwrap_or_default());
+        response.output_level = handles.config.output_level;
+
+        replayed += 1;
+
+        let tx_stats = handles.stats.tx.clone();
+
+        if let Some(filter) = handles
+            .filters
+            .data
+            .matching_filter(&response, tx_stats.clone())
+            .or_else(|| plugins::on_response(&handles.features.plugins, &mut response, tx_stats))
+        {
+            filtered += 1;
+
+            if explain {
+                let msg = format!(
+                    "FILTERED {} {} ({filter})",
+                    response.status(),
+                    response.url()
+                );
+                ferox_eprint(&msg, &PROGRESS_PRINTER);
+            }
+
+            if !handles.config.quiet_stream.is_empty() {
+                response.send_filtered_report(handles.output.tx.clone(), filter)?;
+            }
+
+            continue;
+        }
+
+        if let Some(filter) = status_code_filter(&response, &handles.config) {
+            // the output handler drops these itself, only the explanation is missing
+            filtered += 1;
+
+            if explain {
+                let msg = format!(
+                    "FILTERED {} {} ({filter})",
+                    response.status(),
+                    response.url()
+                );
+                ferox_eprint(&msg, &PROGRESS_PRINTER);
+            }
+        }
+
+        response.send_report(handles.output.tx.clone(), handles.config.exports_bodies())?;
+    }
+
+    if explain {
+        let msg = format!("Replayed {replayed} responses from {path}, {filtered} filtered");
+        ferox_eprint(&msg, &PROGRESS_PRINTER);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// a recording that was cut short is read up to its last complete entry
+    fn truncated_recordings_are_read_up_to_the_cut() {
+        let path = std::env::temp_dir().join(format!("ferox-session-{}", std::process::id()));
+
+        let mut writer = GzEncoder::new(Vec::new(), Compression::default());
+
+        for seq in 0..3 {
+            writer
+                .write_all(format!("{{\"type\":\"recorded\",\"seq\":{seq}}}\n").as_bytes())
+                .unwrap();
+            writer.flush().unwrap();
+        }
+
+        let complete = writer.finish().unwrap();
+
+        // everything but the gzip footer, as if the process was killed
+        std::fs::write(&path, &complete[..complete.len() - 8]).unwrap();
+
+        let seqs: Vec<_> = entries(path.to_str().unwrap())
+            .unwrap()
+            .map(|entry| entry["seq"].as_u64().unwrap())
+            .collect();
+
+        assert_eq!(seqs, [0, 1, 2]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    /// -C wins over -s, as it does in the output handler
+    fn status_code_filter_follows_output_handler() {
+        let mut response = FeroxResponse::default();
+        response.set_status(404);
+
+        let config = Configuration {
+            status_codes: vec![200],
+            ..Default::default()
+        };
+        assert_eq!(
+            status_code_filter(&response, &config).as_deref(),
+            Some("Status code: 404")
+        );
+
+        let config = Configuration {
+            filter_status: vec![500],
+            ..Default::default()
+        };
+        assert!(status_code_filter(&response, &config).is_none());
+    }
+}
@@ -0,0 +1,93 @@
+//! seeded wordlist shuffling (--shuffle)
+//!
+//! each scan walks the wordlist in its own order, derived from the --shuffle seed and the scan's
+//! url. the same seed always produces the same order, which is what lets a resumed scan skip the
+//! words it already requested; the seed is saved in the state file along with the rest of the
+//! configuration
+use uuid::Uuid;
+
+/// seed used when --shuffle is given without one; never 0, which means "pick one"
+pub fn random_seed() -> u64 {
+    (Uuid::new_v4().as_u128() as u64).max(1)
+}
+
+/// SplitMix64; small, fast, and more than good enough for ordering a wordlist
+struct SplitMix64(u64);
+
+/// implementation of SplitMix64
+impl SplitMix64 {
+    /// next number in the sequence
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// FNV-1a hash of the given key; unlike std's hasher, it's guaranteed not to change between
+/// releases, which would break resuming a scan saved by an older version
+fn stable_hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// the given words in the order the scan of `url` requests them under the given seed
+pub fn shuffled(words: &[String], seed: u64, url: &str) -> Vec<String> {
+    let mut rng = SplitMix64(seed ^ stable_hash(url));
+    let mut words = words.to_vec();
+
+    // fisher-yates; the modulo bias is irrelevant at wordlist sizes
+    for i in (1..words.len()).rev() {
+        let j = (rng.next() % (i as u64 + 1)) as usize;
+        words.swap(i, j);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// words used by the tests below
+    fn words() -> Vec<String> {
+        (0..100).map(|i| format!("word{i}")).collect()
+    }
+
+    #[test]
+    /// the same seed and url always give the same order, and every word is kept
+    fn shuffled_is_deterministic() {
+        let first = shuffled(&words(), 42, "http://localhost/");
+        let second = shuffled(&words(), 42, "http://localhost/");
+
+        assert_eq!(first, second);
+        assert_ne!(first, words());
+
+        let mut sorted = first;
+        sorted.sort();
+        let mut expected = words();
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    /// each scan gets its own order, as does each seed
+    fn shuffled_differs_per_scan_and_seed() {
+        let base = shuffled(&words(), 42, "http://localhost/");
+
+        assert_ne!(base, shuffled(&words(), 42, "http://localhost/admin/"));
+        assert_ne!(base, shuffled(&words(), 43, "http://localhost/"));
+    }
+
+    #[test]
+    /// known values, changing them breaks resuming scans saved by older versions
+    fn stable_hash_known_values() {
+        assert_eq!(stable_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(random_seed(), 0);
+    }
+}
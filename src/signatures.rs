@@ -0,0 +1,196 @@
+//! 404 signatures learned by the heuristics phase, kept so they can be reused (--resume-from,
+//! --reuse-signatures)
+//!
+//! every directory whose 404-like responses were figured out gets a signature: the wildcard and
+//! similarity filters built from its probes, along with any probes that came back as wildcard
+//! directories. signatures are saved to the state file; a directory that already has one
+//! applies it instead of probing the host again, so filtering stays the same between runs
+use std::{collections::HashMap, fs, sync::RwLock};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    config::Configuration,
+    filters::{SimilarityFilter, WildcardFilter},
+    response::FeroxResponse,
+    utils::fmt_err,
+};
+
+/// what a single directory's 404-like responses look like
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Signature {
+    /// filters on the status code and size/words/lines of 404-like responses
+    pub wildcards: Vec<WildcardFilter>,
+
+    /// fallback filters on the body of 404-like responses
+    pub similarities: Vec<SimilarityFilter>,
+
+    /// 404-like responses that looked like directories, i.e. the directory is a wildcard
+    /// directory and shouldn't be recursed into
+    pub directories: Vec<FeroxResponse>,
+}
+
+/// implementation of Signature
+impl Signature {
+    /// whether nothing was learned
+    pub fn is_empty(&self) -> bool {
+        self.wildcards.is_empty() && self.similarities.is_empty() && self.directories.is_empty()
+    }
+}
+
+/// key under which the given directory's signature is stored; trailing slashes don't matter
+fn key(url: &str) -> String {
+    format!("{}/", url.trim_end_matches('/'))
+}
+
+/// Learned signatures, keyed by the directory they were learned from
+#[derive(Debug, Default)]
+pub struct Signatures {
+    /// the signatures, keyed by `key`
+    known: RwLock<HashMap<String, Signature>>,
+}
+
+/// implementation of Signatures
+impl Signatures {
+    /// remember what the given directory's 404-like responses look like; empty signatures
+    /// aren't kept, so that a directory whose probes failed gets probed again next time
+    pub fn record(&self, url: &str, signature: Signature) {
+        if signature.is_empty() {
+            return;
+        }
+
+        if let Ok(mut guard) = self.known.write() {
+            guard.insert(key(url), signature);
+        }
+    }
+
+    /// signature learned for the given directory, if any
+    pub fn lookup(&self, url: &str) -> Option<Signature> {
+        self.known.read().ok()?.get(&key(url)).cloned()
+    }
+
+    /// every learned signature, used when saving the state file
+    pub fn snapshot(&self) -> HashMap<String, Signature> {
+        self.known
+            .read()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// pull the signatures out of a .state file's contents; malformed entries are skipped
+fn parse_signatures(contents: &str) -> HashMap<String, Signature> {
+    let Ok(state) = serde_json::from_str::<Value>(contents) else {
+        return HashMap::new();
+    };
+
+    let Some(signatures) = state.get("signatures").and_then(|s| s.as_object()) else {
+        return HashMap::new();
+    };
+
+    signatures
+        .iter()
+        .filter_map(|(url, signature)| {
+            serde_json::from_value::<Signature>(signature.clone())
+                .ok()
+                .map(|signature| (key(url), signature))
+        })
+        .collect()
+}
+
+/// load the signatures saved by a previous run, from the file given to --reuse-signatures and
+/// from the state file being resumed; the latter wins when both know the same directory
+pub fn initialize(config: &Configuration) -> Result<Signatures> {
+    let mut known = HashMap::new();
+    let mut files = Vec::new();
+
+    if !config.reuse_signatures.is_empty() {
+        files.push(&config.reuse_signatures);
+    }
+
+    if config.resumed && !config.resume_from.is_empty() {
+        files.push(&config.resume_from);
+    }
+
+    for file in files {
+        let contents = fs::read_to_string(file)
+            .with_context(|| fmt_err(&format!("Could not read signatures from {file}")))?;
+
+        let loaded = parse_signatures(&contents);
+
+        log::info!("loaded {} 404 signatures from {}", loaded.len(), file);
+
+        known.extend(loaded);
+    }
+
+    Ok(Signatures {
+        known: RwLock::new(known),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// signature with a single wildcard filter on the given status code
+    fn signature(status_code: u16) -> Signature {
+        Signature {
+            wildcards: vec![WildcardFilter {
+                content_length: Some(42),
+                status_code,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// signatures should be found regardless of trailing slashes; empty ones aren't kept
+    fn signatures_record_and_lookup() {
+        let signatures = Signatures::default();
+
+        signatures.record("http://signatures.test/record", signature(404));
+        assert_eq!(
+            signatures
+                .lookup("http://signatures.test/record/")
+                .unwrap()
+                .wildcards,
+            signature(404).wildcards
+        );
+
+        signatures.record("http://signatures.test/empty/", Signature::default());
+        assert!(signatures.lookup("http://signatures.test/empty").is_none());
+        assert!(signatures
+            .lookup("http://signatures.test/unknown")
+            .is_none());
+        assert_eq!(signatures.snapshot().len(), 1);
+    }
+
+    #[test]
+    /// signatures written to a state file should come back out of it
+    fn signatures_parse_from_state_file() {
+        let mut saved = HashMap::new();
+        saved.insert(String::from("http://localhost/"), signature(404));
+        saved.insert(String::from("http://localhost/api"), signature(403));
+
+        let state = serde_json::json!({
+            "version": 3,
+            "signatures": saved,
+        })
+        .to_string();
+
+        let parsed = parse_signatures(&state);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed["http://localhost/"].wildcards[0].status_code, 404);
+        assert_eq!(
+            parsed["http://localhost/api/"].wildcards[0].status_code,
+            403
+        );
+
+        // older state files don't have any
+        assert!(parse_signatures(r#"{"version":2,"responses":[]}"#).is_empty());
+        assert!(parse_signatures("not json").is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+//! rechecking earlier findings for stability (--stability-check)
+//!
+//! a target behind a load balancer doesn't always answer from the same backend, and the
+//! backends don't always agree; a finding that only one of them serves is a ghost that follow-up
+//! work can waste hours on. every --stability-interval seconds, a handful of earlier findings are
+//! requested again, and one that no longer comes back with the same status and roughly the same
+//! size is flagged as unstable, printed alongside the responses and written to --output (as
+//! `{"type":"unstable",...}` with --json)
+//!
+//! findings are taken in the order they were found, picking up where the previous round left
+//! off, so that every finding gets its turn on a long scan. one more round is run once the scans
+//! are done, which covers scans that finish before the first interval is up
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use console::style;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::Notify, task::JoinHandle, time::sleep};
+
+use crate::{
+    config::OutputLevel,
+    event_handlers::{
+        Command::{self, AddToUsizeField},
+        Handles,
+    },
+    response::FeroxResponse,
+    send_command,
+    statistics::StatField::TotalExpected,
+    traits::FeroxSerialize,
+    utils::{fmt_err, logged_request},
+};
+
+/// number of findings requested again per round
+const SAMPLE_SIZE: usize = 5;
+
+/// number of findings kept around to be rechecked; later ones aren't tracked
+const MAX_TRACKED: usize = 10_000;
+
+/// difference in size, in bytes, that's always allowed; small bodies with a timestamp or token
+/// in them shouldn't be flagged over a few characters
+const SIZE_SLACK: u64 = 16;
+
+/// Findings kept around to be rechecked, along with the task that rechecks them
+/// (--stability-check)
+#[derive(Debug, Default)]
+pub struct StabilityChecks {
+    /// findings that can be rechecked, only populated when --stability-check is used
+    tracker: Mutex<Tracker>,
+
+    /// task running the periodic rounds
+    watcher: Mutex<Option<JoinHandle<()>>>,
+
+    /// tells the periodic rounds to stop
+    stop: Notify,
+}
+
+/// How a finding looked when it was first found
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snapshot {
+    /// url of the finding
+    url: Url,
+
+    /// http method used
+    method: String,
+
+    /// status code it was found with
+    status: u16,
+
+    /// size of its response body, in bytes
+    content_length: u64,
+
+    /// whether it was already flagged as unstable; it's not rechecked after that
+    flagged: bool,
+}
+
+/// Every tracked finding, along with where the next round starts
+#[derive(Debug, Default)]
+struct Tracker {
+    /// findings in the order they were found
+    findings: Vec<Snapshot>,
+
+    /// index of the finding the next round starts at
+    next: usize,
+}
+
+/// implementation of Tracker
+impl Tracker {
+    /// the next (up to) SAMPLE_SIZE findings that weren't flagged yet, along with their index;
+    /// starts over from the first finding once the end is reached
+    fn sample(&mut self) -> Vec<(usize, Snapshot)> {
+        let total = self.findings.len();
+        let mut sample = Vec::new();
+
+        for offset in 0..total {
+            if sample.len() == SAMPLE_SIZE {
+                break;
+            }
+
+            let index = (self.next + offset) % total;
+
+            if !self.findings[index].flagged {
+                sample.push((index, self.findings[index].clone()));
+            }
+        }
+
+        if let Some((last, _)) = sample.last() {
+            self.next = (last + 1) % total;
+        }
+
+        sample
+    }
+}
+
+/// A finding that didn't come back the way it was found
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UnstableInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"unstable"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// url of the finding
+    pub url: String,
+
+    /// http method used
+    pub method: String,
+
+    /// status code it was found with
+    pub status: u16,
+
+    /// size of the response body it was found with, in bytes
+    pub content_length: u64,
+
+    /// status code it came back with when requested again, `None` when the request failed
+    pub recheck_status: Option<u16>,
+
+    /// size of the response body it came back with when requested again, in bytes
+    pub recheck_content_length: u64,
+}
+
+/// FeroxSerialize implementation for UnstableInfo
+impl FeroxSerialize for UnstableInfo {
+    /// one line summary, i.e.
+    /// `UNS GET http://localhost/login [found 200 12c, recheck 503 0c]`
+    fn as_str(&self) -> String {
+        let recheck = match self.recheck_status {
+            Some(status) => format!("{} {}c", status, self.recheck_content_length),
+            None => String::from("error"),
+        };
+
+        format!(
+            "{} {:>5} {} {}\n",
+            style("UNS").bright().yellow(),
+            self.method,
+            style(&self.url).yellow(),
+            style(format!(
+                "[found {} {}c, recheck {}]",
+                self.status, self.content_length, recheck
+            ))
+            .dim()
+        )
+    }
+
+    /// Create an NDJSON representation of the unstable finding
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the unstable finding {} to JSON",
+                self.url
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// whether two body sizes are close enough to be the same page; within a tenth of the larger
+/// one, or SIZE_SLACK bytes, whichever allows more
+fn similar_size(first: u64, second: u64) -> bool {
+    first.abs_diff(second) <= (first.max(second) / 10).max(SIZE_SLACK)
+}
+
+/// request the given finding again, reporting it when it doesn't come back the way it was
+/// found; returns whether it was reported
+async fn recheck_one(snapshot: &Snapshot, handles: Arc<Handles>) -> bool {
+    // need to manually adjust stats
+    send_command!(handles.stats.tx, AddToUsizeField(TotalExpected, 1));
+
+    let data = if handles.config.data.is_empty() {
+        None
+    } else {
+        Some(handles.config.data.as_slice())
+    };
+
+    let (recheck_status, recheck_content_length) =
+        match logged_request(&snapshot.url, &snapshot.method, data, handles.clone()).await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let content_length = response
+                    .bytes()
+                    .await
+                    .map(|body| body.len() as u64)
+                    .unwrap_or_default();
+
+                (Some(status), content_length)
+            }
+            Err(e) => {
+                log::warn!("Could not recheck {}: {}", snapshot.url, e);
+                (None, 0)
+            }
+        };
+
+    if recheck_status == Some(snapshot.status)
+        && similar_size(snapshot.content_length, recheck_content_length)
+    {
+        return false;
+    }
+
+    let info = UnstableInfo {
+        kind: String::from("unstable"),
+        url: snapshot.url.to_string(),
+        method: snapshot.method.clone(),
+        status: snapshot.status,
+        content_length: snapshot.content_length,
+        recheck_status,
+        recheck_content_length,
+    };
+
+    log::info!("{:?}", info);
+
+    handles
+        .output
+        .send(Command::ReportUnstable(Box::new(info)))
+        .unwrap_or_default();
+
+    true
+}
+
+/// request the next sample of findings again
+async fn recheck(handles: Arc<Handles>) {
+    let sample = match handles.features.stability.tracker.lock() {
+        Ok(mut tracker) => tracker.sample(),
+        Err(_) => return,
+    };
+
+    log::debug!("rechecking {} findings for stability", sample.len());
+
+    for (index, snapshot) in sample {
+        if recheck_one(&snapshot, handles.clone()).await {
+            if let Ok(mut tracker) = handles.features.stability.tracker.lock() {
+                tracker.findings[index].flagged = true;
+            }
+        }
+    }
+}
+
+/// run a round every --stability-interval seconds until `finish` is called
+async fn watch(handles: Arc<Handles>) {
+    let interval = Duration::from_secs(handles.config.stability_interval.max(1));
+
+    loop {
+        tokio::select! {
+            _ = sleep(interval) => recheck(handles.clone()).await,
+            _ = handles.features.stability.stop.notified() => return,
+        }
+    }
+}
+
+/// start the periodic rounds; does nothing when --stability-check wasn't used
+pub fn start(handles: Arc<Handles>) {
+    if !handles.config.stability_check {
+        return;
+    }
+
+    let task = tokio::spawn(watch(handles.clone()));
+
+    if let Ok(mut watcher) = handles.features.stability.watcher.lock() {
+        *watcher = Some(task);
+    }
+}
+
+/// keep track of the given finding, so it can be rechecked later; does nothing when
+/// --stability-check wasn't used
+pub fn on_finding(response: &FeroxResponse, handles: Arc<Handles>) {
+    if !handles.config.stability_check {
+        return;
+    }
+
+    let Ok(mut tracker) = handles.features.stability.tracker.lock() else {
+        return;
+    };
+
+    if tracker.findings.len() >= MAX_TRACKED {
+        return;
+    }
+
+    tracker.findings.push(Snapshot {
+        url: response.url().clone(),
+        method: response.method().to_string(),
+        status: response.status().as_u16(),
+        content_length: response.content_length(),
+        flagged: false,
+    });
+}
+
+/// stop the periodic rounds and run a last one, so its findings make it out before the output
+/// handler is closed
+pub async fn finish(handles: Arc<Handles>) {
+    if !handles.config.stability_check {
+        return;
+    }
+
+    // a round that's underway is allowed to finish
+    handles.features.stability.stop.notify_one();
+
+    let task = handles
+        .features
+        .stability
+        .watcher
+        .lock()
+        .ok()
+        .and_then(|mut watcher| watcher.take());
+
+    if let Some(task) = task {
+        task.await.unwrap_or_default();
+    }
+
+    recheck(handles).await;
+}
+
+/// whether unstable findings are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a finding at the given path
+    fn snapshot(path: &str, flagged: bool) -> Snapshot {
+        Snapshot {
+            url: Url::parse("http://localhost/").unwrap().join(path).unwrap(),
+            method: String::from("GET"),
+            status: 200,
+            content_length: 100,
+            flagged,
+        }
+    }
+
+    /// paths of the given sample
+    fn paths(sample: &[(usize, Snapshot)]) -> Vec<String> {
+        sample
+            .iter()
+            .map(|(_, snapshot)| snapshot.url.path().to_string())
+            .collect()
+    }
+
+    #[test]
+    /// sizes within a tenth of each other, or a few bytes, are the same page
+    fn similar_size_allows_some_slack() {
+        assert!(similar_size(1000, 1000));
+        assert!(similar_size(1000, 920));
+        assert!(similar_size(12, 0));
+        assert!(!similar_size(1000, 800));
+        assert!(!similar_size(0, 500));
+    }
+
+    #[test]
+    /// rounds pick up where the last one left off, wrapping around and skipping flagged findings
+    fn tracker_samples_in_turn() {
+        let mut tracker = Tracker::default();
+
+        for index in 0..7 {
+            tracker
+                .findings
+                .push(snapshot(&format!("{index}"), index == 1));
+        }
+
+        assert_eq!(paths(&tracker.sample()), ["/0", "/2", "/3", "/4", "/5"]);
+        assert_eq!(paths(&tracker.sample()), ["/6", "/0", "/2", "/3", "/4"]);
+    }
+
+    #[test]
+    /// nothing to sample when there are no findings, or they were all flagged
+    fn tracker_sample_can_be_empty() {
+        let mut tracker = Tracker::default();
+        assert!(tracker.sample().is_empty());
+
+        tracker.findings.push(snapshot("flagged", true));
+        assert!(tracker.sample().is_empty());
+        assert_eq!(tracker.next, 0);
+    }
+
+    #[test]
+    /// an unstable finding is written as a single json line, with both of its results
+    fn unstable_info_as_json() {
+        let info = UnstableInfo {
+            kind: String::from("unstable"),
+            url: String::from("http://localhost/login"),
+            method: String::from("GET"),
+            status: 200,
+            content_length: 12,
+            recheck_status: None,
+            recheck_content_length: 0,
+        };
+
+        assert_eq!(
+            info.as_json().unwrap(),
+            "{\"type\":\"unstable\",\"url\":\"http://localhost/login\",\"method\":\"GET\",\"status\":200,\"content_length\":12,\"recheck_status\":null,\"recheck_content_length\":0}\n"
+        );
+        assert!(info.as_str().contains("[found 200 12c, recheck error]"));
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
     utils::{fmt_err, open_file, write_to},
 };
 
-use super::{error::StatError, field::StatField};
+use super::{error::StatError, expected::ExpectedRequests, field::StatField};
 
 /// Data collection of statistics related to a scan
 #[derive(Default, Debug)]
@@ -30,6 +30,12 @@ pub struct Stats {
     /// tracker for number of timeouts seen by the client
     timeouts: AtomicUsize,
 
+    /// tracker for number of connections that weren't made within --connect-timeout
+    connect_timeouts: AtomicUsize,
+
+    /// tracker for number of response bodies that went quiet for longer than --read-timeout
+    read_timeouts: AtomicUsize,
+
     /// tracker for total number of requests sent by the client
     pub(crate) requests: AtomicUsize,
 
@@ -41,7 +47,7 @@ pub struct Stats {
 
     /// tracker for accumulating total number of requests expected (i.e. as a new scan is started
     /// this value should increase by `expected_requests`
-    total_expected: AtomicUsize,
+    total_expected: ExpectedRequests,
 
     /// tracker for total number of errors encountered by the client
     pub(crate) errors: AtomicUsize,
@@ -65,6 +71,9 @@ pub struct Stats {
     /// tracker for initial number of requested targets
     initial_targets: AtomicUsize,
 
+    /// tracker for number of words read from the wordlist, whatever its source
+    wordlist_words: AtomicUsize,
+
     /// tracker for number of links extracted when `--extract-links` is used; sources are
     /// response bodies and robots.txt as of v1.11.0
     links_extracted: AtomicUsize,
@@ -109,6 +118,10 @@ pub struct Stats {
     /// tracker for overall number of all filtered responses
     responses_filtered: AtomicUsize,
 
+    /// tracker for the number of responses removed by each filter, keyed by the filter's
+    /// description
+    filter_hits: Mutex<HashMap<String, usize>>,
+
     /// tracker for number of files found
     resources_discovered: AtomicUsize,
 
@@ -118,12 +131,47 @@ pub struct Stats {
     /// tracker for number of errors triggered by the `reqwest::RedirectPolicy`
     redirection_errors: AtomicUsize,
 
+    /// tracker for number of redirect chains that looped back on themselves
+    redirect_loop_errors: AtomicUsize,
+
     /// tracker for number of errors related to the connecting
     connection_errors: AtomicUsize,
 
+    /// tracker for number of requests that failed because we ran out of file descriptors
+    file_descriptor_errors: AtomicUsize,
+
     /// tracker for number of errors related to the request used
     request_errors: AtomicUsize,
 
+    /// tracker for number of hostnames that couldn't be resolved
+    dns_errors: AtomicUsize,
+
+    /// tracker for number of hostnames that couldn't be resolved through --doh
+    doh_errors: AtomicUsize,
+
+    /// tracker for number of failed TLS handshakes
+    tls_errors: AtomicUsize,
+
+    /// tracker for number of errors reaching the target through the proxy
+    proxy_errors: AtomicUsize,
+
+    /// tracker for number of times a failing proxy was swapped for the next one
+    /// (--proxy-failover)
+    proxy_failovers: AtomicUsize,
+
+    /// tracker for number of requests sent byte for byte, outside of the client
+    /// (--raw-requests)
+    raw_requests: AtomicUsize,
+
+    /// tracker for number of raw requests that didn't get a response
+    raw_errors: AtomicUsize,
+
+    /// tracker for number of response bodies that couldn't be read in full
+    body_read_errors: AtomicUsize,
+
+    /// tracker for number of responses too large to be parsed
+    too_large_errors: AtomicUsize,
+
     /// tracker for each directory's total scan time in seconds as a float
     directory_scan_times: Mutex<Vec<f64>>,
 
@@ -155,13 +203,15 @@ impl Serialize for Stats {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Stats", 32)?;
+        let mut state = serializer.serialize_struct("Stats", 44)?;
 
         state.serialize_field("type", &self.kind)?;
         state.serialize_field("timeouts", &atomic_load!(self.timeouts))?;
+        state.serialize_field("connect_timeouts", &atomic_load!(self.connect_timeouts))?;
+        state.serialize_field("read_timeouts", &atomic_load!(self.read_timeouts))?;
         state.serialize_field("requests", &atomic_load!(self.requests))?;
         state.serialize_field("expected_per_scan", &atomic_load!(self.expected_per_scan))?;
-        state.serialize_field("total_expected", &atomic_load!(self.total_expected))?;
+        state.serialize_field("total_expected", &self.total_expected.total())?;
         state.serialize_field("errors", &atomic_load!(self.errors))?;
         state.serialize_field("successes", &atomic_load!(self.successes))?;
         state.serialize_field("redirects", &atomic_load!(self.redirects))?;
@@ -169,6 +219,7 @@ impl Serialize for Stats {
         state.serialize_field("server_errors", &atomic_load!(self.server_errors))?;
         state.serialize_field("total_scans", &atomic_load!(self.total_scans))?;
         state.serialize_field("initial_targets", &atomic_load!(self.initial_targets))?;
+        state.serialize_field("wordlist_words", &atomic_load!(self.wordlist_words))?;
         state.serialize_field("links_extracted", &atomic_load!(self.links_extracted))?;
         state.serialize_field(
             "extensions_collected",
@@ -186,14 +237,32 @@ impl Serialize for Stats {
         state.serialize_field("status_508s", &atomic_load!(self.status_508s))?;
         state.serialize_field("wildcards_filtered", &atomic_load!(self.wildcards_filtered))?;
         state.serialize_field("responses_filtered", &atomic_load!(self.responses_filtered))?;
+        state.serialize_field("filter_hits", &self.filter_hits)?;
         state.serialize_field(
             "resources_discovered",
             &atomic_load!(self.resources_discovered),
         )?;
         state.serialize_field("url_format_errors", &atomic_load!(self.url_format_errors))?;
         state.serialize_field("redirection_errors", &atomic_load!(self.redirection_errors))?;
+        state.serialize_field(
+            "redirect_loop_errors",
+            &atomic_load!(self.redirect_loop_errors),
+        )?;
         state.serialize_field("connection_errors", &atomic_load!(self.connection_errors))?;
+        state.serialize_field(
+            "file_descriptor_errors",
+            &atomic_load!(self.file_descriptor_errors),
+        )?;
         state.serialize_field("request_errors", &atomic_load!(self.request_errors))?;
+        state.serialize_field("dns_errors", &atomic_load!(self.dns_errors))?;
+        state.serialize_field("doh_errors", &atomic_load!(self.doh_errors))?;
+        state.serialize_field("tls_errors", &atomic_load!(self.tls_errors))?;
+        state.serialize_field("proxy_errors", &atomic_load!(self.proxy_errors))?;
+        state.serialize_field("proxy_failovers", &atomic_load!(self.proxy_failovers))?;
+        state.serialize_field("raw_requests", &atomic_load!(self.raw_requests))?;
+        state.serialize_field("raw_errors", &atomic_load!(self.raw_errors))?;
+        state.serialize_field("body_read_errors", &atomic_load!(self.body_read_errors))?;
+        state.serialize_field("too_large_errors", &atomic_load!(self.too_large_errors))?;
         state.serialize_field("directory_scan_times", &self.directory_scan_times)?;
         state.serialize_field("total_runtime", &self.total_runtime)?;
 
@@ -221,6 +290,20 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "connect_timeouts" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.connect_timeouts, parsed);
+                        }
+                    }
+                }
+                "read_timeouts" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.read_timeouts, parsed);
+                        }
+                    }
+                }
                 "requests" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
@@ -238,7 +321,7 @@ impl<'a> Deserialize<'a> for Stats {
                 "total_expected" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
-                            atomic_increment!(stats.total_expected, parsed);
+                            stats.total_expected.add(parsed);
                         }
                     }
                 }
@@ -291,6 +374,13 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "wordlist_words" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.wordlist_words, parsed);
+                        }
+                    }
+                }
                 "links_extracted" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
@@ -389,6 +479,17 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "filter_hits" => {
+                    if let Some(obj) = value.as_object() {
+                        for (filter, hits) in obj {
+                            if let Some(num) = hits.as_u64() {
+                                if let Ok(parsed) = usize::try_from(num) {
+                                    stats.add_filter_hits(filter, parsed);
+                                }
+                            }
+                        }
+                    }
+                }
                 "resources_discovered" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
@@ -410,6 +511,13 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "redirect_loop_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.redirect_loop_errors, parsed);
+                        }
+                    }
+                }
                 "connection_errors" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
@@ -417,6 +525,13 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "file_descriptor_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.file_descriptor_errors, parsed);
+                        }
+                    }
+                }
                 "request_errors" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
@@ -424,6 +539,69 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "dns_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.dns_errors, parsed);
+                        }
+                    }
+                }
+                "doh_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.doh_errors, parsed);
+                        }
+                    }
+                }
+                "tls_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.tls_errors, parsed);
+                        }
+                    }
+                }
+                "proxy_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.proxy_errors, parsed);
+                        }
+                    }
+                }
+                "proxy_failovers" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.proxy_failovers, parsed);
+                        }
+                    }
+                }
+                "raw_requests" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.raw_requests, parsed);
+                        }
+                    }
+                }
+                "raw_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.raw_errors, parsed);
+                        }
+                    }
+                }
+                "body_read_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.body_read_errors, parsed);
+                        }
+                    }
+                }
+                "too_large_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.too_large_errors, parsed);
+                        }
+                    }
+                }
                 "directory_scan_times" => {
                     if let Some(arr) = value.as_array() {
                         for val in arr {
@@ -492,9 +670,34 @@ impl Stats {
         atomic_load!(self.status_429s)
     }
 
+    /// public getter for wildcards_filtered
+    pub fn wildcards_filtered(&self) -> usize {
+        atomic_load!(self.wildcards_filtered)
+    }
+
+    /// public getter for proxy_failovers
+    pub fn proxy_failovers(&self) -> usize {
+        atomic_load!(self.proxy_failovers)
+    }
+
+    /// public getter for raw_requests
+    pub fn raw_requests(&self) -> usize {
+        atomic_load!(self.raw_requests)
+    }
+
+    /// public getter for raw_errors
+    pub fn raw_errors(&self) -> usize {
+        atomic_load!(self.raw_errors)
+    }
+
     /// public getter for total_expected
     pub fn total_expected(&self) -> usize {
-        atomic_load!(self.total_expected)
+        self.total_expected.total()
+    }
+
+    /// length the overall progress bar should have, given its current position
+    pub fn expected_bar_length(&self, position: u64) -> u64 {
+        self.total_expected.bar_length(position)
     }
 
     /// public getter for initial_targets
@@ -502,6 +705,11 @@ impl Stats {
         atomic_load!(self.initial_targets)
     }
 
+    /// public getter for wordlist_words
+    pub fn wordlist_words(&self) -> usize {
+        atomic_load!(self.wordlist_words)
+    }
+
     /// increment `requests` field by one
     pub fn add_request(&self) {
         atomic_increment!(self.requests);
@@ -528,28 +736,60 @@ impl Stats {
     /// Inspect the given `StatError` and increment the appropriate fields
     ///
     /// Implies incrementing:
-    ///     - requests
+    ///     - requests (except for BodyRead and ReadTimeout, whose response was already counted)
     ///     - errors
     pub fn add_error(&self, error: StatError) {
-        self.add_request();
+        if !matches!(error, StatError::BodyRead | StatError::ReadTimeout) {
+            self.add_request();
+        }
         atomic_increment!(self.errors);
 
         match error {
             StatError::Timeout => {
                 atomic_increment!(self.timeouts);
             }
+            StatError::ConnectTimeout => {
+                atomic_increment!(self.connect_timeouts);
+            }
+            StatError::ReadTimeout => {
+                atomic_increment!(self.read_timeouts);
+            }
             StatError::UrlFormat => {
                 atomic_increment!(self.url_format_errors);
             }
             StatError::Redirection => {
                 atomic_increment!(self.redirection_errors);
             }
+            StatError::RedirectLoop => {
+                atomic_increment!(self.redirect_loop_errors);
+            }
             StatError::Connection => {
                 atomic_increment!(self.connection_errors);
             }
+            StatError::FileDescriptor => {
+                atomic_increment!(self.file_descriptor_errors);
+            }
             StatError::Request => {
                 atomic_increment!(self.request_errors);
             }
+            StatError::Dns => {
+                atomic_increment!(self.dns_errors);
+            }
+            StatError::Doh => {
+                atomic_increment!(self.doh_errors);
+            }
+            StatError::Tls => {
+                atomic_increment!(self.tls_errors);
+            }
+            StatError::Proxy => {
+                atomic_increment!(self.proxy_errors);
+            }
+            StatError::BodyRead => {
+                atomic_increment!(self.body_read_errors);
+            }
+            StatError::TooLarge => {
+                atomic_increment!(self.too_large_errors);
+            }
             _ => {} // no need to hit Other as we always increment self.errors anyway
         }
     }
@@ -608,6 +848,44 @@ impl Stats {
         }
     }
 
+    /// record that the filter with the given description removed `value` responses
+    pub fn add_filter_hits(&self, filter: &str, value: usize) {
+        if let Ok(mut guard) = self.filter_hits.lock() {
+            *guard.entry(filter.to_string()).or_default() += value;
+        }
+    }
+
+    /// public getter for filter_hits, sorted by the number of responses removed (most first)
+    pub fn filter_hits(&self) -> Vec<(String, usize)> {
+        let mut hits: Vec<_> = self
+            .filter_hits
+            .lock()
+            .map(|guard| guard.iter().map(|(k, v)| (k.clone(), *v)).collect())
+            .unwrap_or_default();
+
+        hits.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        hits
+    }
+
+    /// human readable breakdown of how many responses each filter removed, `None` when nothing
+    /// was filtered
+    pub fn filter_summary(&self) -> Option<String> {
+        let hits = self.filter_hits();
+
+        if hits.is_empty() {
+            return None;
+        }
+
+        let total: usize = hits.iter().map(|(_, count)| count).sum();
+        let mut summary = format!("🚫 {total} responses removed by filters");
+
+        for (filter, count) in hits {
+            summary.push_str(&format!("\n   {count:>8}  {filter}"));
+        }
+
+        Some(summary)
+    }
+
     /// Update a `Stats` field of type f64
     pub fn update_f64_field(&self, field: StatField, value: f64) {
         if let StatField::DirScanTimes = field {
@@ -620,7 +898,7 @@ impl Stats {
     /// subtract a value from the given field
     pub fn subtract_from_usize_field(&self, field: StatField, value: usize) {
         if let StatField::TotalExpected = field {
-            self.total_expected.fetch_sub(value, Ordering::Relaxed);
+            self.total_expected.subtract(value);
         }
     }
 
@@ -632,13 +910,11 @@ impl Stats {
             }
             StatField::TotalScans => {
                 atomic_increment!(self.total_scans, value);
-                atomic_increment!(
-                    self.total_expected,
-                    value * self.expected_per_scan.load(Ordering::Relaxed)
-                );
+                self.total_expected
+                    .add(value * self.expected_per_scan.load(Ordering::Relaxed));
             }
             StatField::TotalExpected => {
-                atomic_increment!(self.total_expected, value);
+                self.total_expected.add(value);
             }
             StatField::LinksExtracted => {
                 atomic_increment!(self.links_extracted, value);
@@ -659,6 +935,18 @@ impl Stats {
             StatField::InitialTargets => {
                 atomic_increment!(self.initial_targets, value);
             }
+            StatField::WordlistWords => {
+                atomic_increment!(self.wordlist_words, value);
+            }
+            StatField::ProxyFailovers => {
+                atomic_increment!(self.proxy_failovers, value);
+            }
+            StatField::RawRequests => {
+                atomic_increment!(self.raw_requests, value);
+            }
+            StatField::RawErrors => {
+                atomic_increment!(self.raw_errors, value);
+            }
             _ => {} // f64 fields
         }
     }
@@ -676,6 +964,11 @@ impl Stats {
             let d_stats = serde_json::from_value::<Stats>(state_stats.clone())?;
             atomic_increment!(self.successes, atomic_load!(d_stats.successes));
             atomic_increment!(self.timeouts, atomic_load!(d_stats.timeouts));
+            atomic_increment!(
+                self.connect_timeouts,
+                atomic_load!(d_stats.connect_timeouts)
+            );
+            atomic_increment!(self.read_timeouts, atomic_load!(d_stats.read_timeouts));
             atomic_increment!(self.requests, atomic_load!(d_stats.requests));
             atomic_increment!(self.errors, atomic_load!(d_stats.errors));
             atomic_increment!(self.redirects, atomic_load!(d_stats.redirects));
@@ -704,6 +997,9 @@ impl Stats {
                 self.responses_filtered,
                 atomic_load!(d_stats.responses_filtered)
             );
+            for (filter, hits) in d_stats.filter_hits() {
+                self.add_filter_hits(&filter, hits);
+            }
             atomic_increment!(
                 self.resources_discovered,
                 atomic_load!(d_stats.resources_discovered)
@@ -716,11 +1012,34 @@ impl Stats {
                 self.connection_errors,
                 atomic_load!(d_stats.connection_errors)
             );
+            atomic_increment!(
+                self.file_descriptor_errors,
+                atomic_load!(d_stats.file_descriptor_errors)
+            );
             atomic_increment!(
                 self.redirection_errors,
                 atomic_load!(d_stats.redirection_errors)
             );
+            atomic_increment!(
+                self.redirect_loop_errors,
+                atomic_load!(d_stats.redirect_loop_errors)
+            );
             atomic_increment!(self.request_errors, atomic_load!(d_stats.request_errors));
+            atomic_increment!(self.dns_errors, atomic_load!(d_stats.dns_errors));
+            atomic_increment!(self.doh_errors, atomic_load!(d_stats.doh_errors));
+            atomic_increment!(self.tls_errors, atomic_load!(d_stats.tls_errors));
+            atomic_increment!(self.proxy_errors, atomic_load!(d_stats.proxy_errors));
+            atomic_increment!(self.proxy_failovers, atomic_load!(d_stats.proxy_failovers));
+            atomic_increment!(self.raw_requests, atomic_load!(d_stats.raw_requests));
+            atomic_increment!(self.raw_errors, atomic_load!(d_stats.raw_errors));
+            atomic_increment!(
+                self.body_read_errors,
+                atomic_load!(d_stats.body_read_errors)
+            );
+            atomic_increment!(
+                self.too_large_errors,
+                atomic_load!(d_stats.too_large_errors)
+            );
 
             if let Ok(scan_times) = d_stats.directory_scan_times.lock() {
                 for scan_time in scan_times.iter() {
@@ -820,6 +1139,24 @@ mod tests {
         assert_eq!(stats.timeouts.load(Ordering::Relaxed), 4);
     }
 
+    #[test]
+    /// connect and read timeouts are counted apart from --timeout; a read timeout's response was
+    /// already counted as a request
+    fn stats_increments_connect_and_read_timeouts() {
+        let config = Configuration::new().unwrap();
+        let stats = Stats::new(config.json);
+
+        stats.add_error(StatError::ConnectTimeout);
+        stats.add_error(StatError::ReadTimeout);
+        stats.add_error(StatError::ReadTimeout);
+
+        assert_eq!(stats.errors.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.requests.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.timeouts.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.connect_timeouts.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.read_timeouts.load(Ordering::Relaxed), 2);
+    }
+
     #[test]
     /// when Stats::update_usize_field receives StatField::WildcardsFiltered, it should increment
     /// the following:
@@ -853,10 +1190,56 @@ mod tests {
         assert_eq!(stats.responses_filtered.load(Ordering::Relaxed), 3);
     }
 
+    #[test]
+    /// filter hits should accumulate per filter and be summarized from most to least hits
+    fn stats_tracks_filter_hits() {
+        let stats = Stats::new(false);
+
+        assert!(stats.filter_summary().is_none());
+
+        stats.add_filter_hits("Word count: 13", 1);
+        stats.add_filter_hits("Status code: 404", 5);
+        stats.add_filter_hits("Word count: 13", 2);
+
+        assert_eq!(
+            stats.filter_hits(),
+            vec![
+                ("Status code: 404".to_string(), 5),
+                ("Word count: 13".to_string(), 3)
+            ]
+        );
+
+        let summary = stats.filter_summary().unwrap();
+        assert!(summary.contains("8 responses removed by filters"));
+        assert!(summary.find("Status code: 404") < summary.find("Word count: 13"));
+    }
+
+    #[test]
+    /// filter hits should survive a round trip through json, as they would in a state file
+    fn stats_filter_hits_serialize_and_merge() {
+        let stats = Stats::new(false);
+        stats.add_filter_hits("Regex: ^ignore", 7);
+
+        let json = stats.as_json().unwrap();
+        assert!(json.contains(r#""filter_hits":{"Regex: ^ignore":7}"#));
+
+        let tfile = NamedTempFile::new().unwrap();
+        write(&tfile, format!(r#"{{"statistics":{json}}}"#)).unwrap();
+
+        let merged = Stats::new(false);
+        merged.add_filter_hits("Regex: ^ignore", 1);
+        merged.merge_from(tfile.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            merged.filter_hits(),
+            vec![("Regex: ^ignore".to_string(), 8)]
+        );
+    }
+
     #[test]
     /// Stats::merge_from should properly increment expected fields and ignore others
     fn stats_merge_from_alters_correct_fields() {
-        let contents = r#"{"statistics":{"type":"statistics","timeouts":1,"requests":9207,"expected_per_scan":707,"total_expected":9191,"errors":3,"successes":720,"redirects":13,"client_errors":8474,"server_errors":2,"total_scans":13,"initial_targets":1,"links_extracted":51,"extensions_collected":4,"status_403s":3,"status_200s":720,"status_301s":12,"status_302s":1,"status_401s":4,"status_429s":2,"status_500s":5,"status_503s":9,"status_504s":6,"status_508s":7,"wildcards_filtered":707,"responses_filtered":707,"resources_discovered":27,"directory_scan_times":[2.211973078,1.989015505,1.898675839,3.9714468910000003,4.938152838,5.256073528,6.021986595,6.065740734,6.42633762,7.095142125,7.336982137,5.319785619,4.843649778],"total_runtime":[11.556575456000001],"url_format_errors":17,"redirection_errors":12,"connection_errors":21,"request_errors":4}}"#;
+        let contents = r#"{"statistics":{"type":"statistics","timeouts":1,"requests":9207,"expected_per_scan":707,"total_expected":9191,"errors":3,"successes":720,"redirects":13,"client_errors":8474,"server_errors":2,"total_scans":13,"initial_targets":1,"links_extracted":51,"extensions_collected":4,"status_403s":3,"status_200s":720,"status_301s":12,"status_302s":1,"status_401s":4,"status_429s":2,"status_500s":5,"status_503s":9,"status_504s":6,"status_508s":7,"wildcards_filtered":707,"responses_filtered":707,"resources_discovered":27,"directory_scan_times":[2.211973078,1.989015505,1.898675839,3.9714468910000003,4.938152838,5.256073528,6.021986595,6.065740734,6.42633762,7.095142125,7.336982137,5.319785619,4.843649778],"total_runtime":[11.556575456000001],"url_format_errors":17,"redirection_errors":12,"redirect_loop_errors":3,"connection_errors":21,"file_descriptor_errors":5,"request_errors":4}}"#;
         let config = Configuration::new().unwrap();
         let stats = Stats::new(config.json);
 
@@ -870,7 +1253,7 @@ mod tests {
         assert_eq!(atomic_load!(stats.timeouts), 1);
         assert_eq!(atomic_load!(stats.requests), 9207);
         assert_eq!(atomic_load!(stats.expected_per_scan), 0); // not updated in merge_from
-        assert_eq!(atomic_load!(stats.total_expected), 0); // not updated in merge_from
+        assert_eq!(stats.total_expected(), 0); // not updated in merge_from
         assert_eq!(atomic_load!(stats.errors), 3);
         assert_eq!(atomic_load!(stats.successes), 720);
         assert_eq!(atomic_load!(stats.redirects), 13);
@@ -895,7 +1278,9 @@ mod tests {
         assert_eq!(atomic_load!(stats.resources_discovered), 27);
         assert_eq!(atomic_load!(stats.url_format_errors), 17);
         assert_eq!(atomic_load!(stats.redirection_errors), 12);
+        assert_eq!(atomic_load!(stats.redirect_loop_errors), 3);
         assert_eq!(atomic_load!(stats.connection_errors), 21);
+        assert_eq!(atomic_load!(stats.file_descriptor_errors), 5);
         assert_eq!(atomic_load!(stats.request_errors), 4);
         assert_eq!(stats.directory_scan_times.lock().unwrap().len(), 13);
         for scan in stats.directory_scan_times.lock().unwrap().iter() {
@@ -1,21 +1,210 @@
-#[derive(Debug, Copy, Clone)]
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{bail, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Enum variants used to inform the `StatCommand` protocol what `Stats` fields should be updated
 pub enum StatError {
-    /// Represents a timeout error
+    /// Represents a request that took longer than --timeout
     Timeout,
 
+    /// Represents a connection that wasn't made within --connect-timeout
+    ConnectTimeout,
+
+    /// Represents a response body that went quiet for longer than --read-timeout
+    ReadTimeout,
+
     /// Represents a URL formatting error
     UrlFormat,
 
     /// Represents an error encountered during redirection
     Redirection,
 
+    /// Represents a redirect that led back to a url already visited in the same chain
+    RedirectLoop,
+
     /// Represents an error encountered during connection
     Connection,
 
+    /// Represents a host name that couldn't be resolved
+    Dns,
+
+    /// Represents a host name that couldn't be resolved through --doh
+    Doh,
+
+    /// Represents a failed TLS handshake or an untrusted certificate
+    Tls,
+
+    /// Represents an error reaching the target through --proxy
+    Proxy,
+
+    /// Represents a response whose body couldn't be read in full
+    BodyRead,
+
+    /// Represents a response too large to be parsed, i.e. an oversized header block
+    TooLarge,
+
+    /// Represents running out of file descriptors locally (EMFILE/ENFILE), which says nothing
+    /// about the target
+    FileDescriptor,
+
     /// Represents an error resulting from the client's request
     Request,
 
     /// Represents any other error not explicitly defined above
     Other,
 }
+
+/// every category, in the order they're shown
+const ALL: [StatError; 16] = [
+    StatError::Dns,
+    StatError::Doh,
+    StatError::Connection,
+    StatError::Tls,
+    StatError::ConnectTimeout,
+    StatError::Timeout,
+    StatError::ReadTimeout,
+    StatError::Proxy,
+    StatError::BodyRead,
+    StatError::TooLarge,
+    StatError::Redirection,
+    StatError::RedirectLoop,
+    StatError::UrlFormat,
+    StatError::FileDescriptor,
+    StatError::Request,
+    StatError::Other,
+];
+
+/// implementation of StatError
+impl StatError {
+    /// short name of the category, as used by --policy-error-weight and the error summary
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatError::Timeout => "timeout",
+            StatError::ConnectTimeout => "connect-timeout",
+            StatError::ReadTimeout => "read-timeout",
+            StatError::UrlFormat => "url-format",
+            StatError::Redirection => "redirect",
+            StatError::RedirectLoop => "redirect-loop",
+            StatError::Connection => "connect",
+            StatError::Dns => "dns",
+            StatError::Doh => "doh",
+            StatError::Tls => "tls",
+            StatError::Proxy => "proxy",
+            StatError::BodyRead => "body-read",
+            StatError::TooLarge => "too-large",
+            StatError::FileDescriptor => "fd",
+            StatError::Request => "request",
+            StatError::Other => "other",
+        }
+    }
+
+    /// how much a single error of this category counts toward --auto-tune/--auto-bail, both
+    /// against --policy-error-threshold and when deciding which way to tune
+    ///
+    /// errors that say something about how the target is coping count once; a failing proxy or
+    /// --doh endpoint, an oversized response, or a badly formatted url doesn't get better by slowing down, so
+    /// those aren't counted unless --policy-error-weight says otherwise
+    pub fn weight(&self) -> usize {
+        match self {
+            StatError::Proxy
+            | StatError::Doh
+            | StatError::TooLarge
+            | StatError::UrlFormat
+            | StatError::FileDescriptor => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// FromStr implementation for StatError, the inverse of `StatError::name`
+impl FromStr for StatError {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        let name = name.trim().to_lowercase();
+
+        match ALL.iter().find(|error| error.name() == name) {
+            Some(error) => Ok(*error),
+            None => bail!(
+                "Unknown error category {}, expected one of: {}",
+                name,
+                ALL.map(|error| error.name()).join(", ")
+            ),
+        }
+    }
+}
+
+/// Weights used in place of the defaults (--policy-error-weight, [policy_error_weights]), keyed
+/// by category
+#[derive(Debug, Default, Clone)]
+pub struct ErrorWeights(HashMap<StatError, usize>);
+
+/// implementation of ErrorWeights
+impl ErrorWeights {
+    /// parse the given weights, keyed by category name
+    pub fn new(weights: &HashMap<String, usize>) -> Result<Self> {
+        let mut parsed = HashMap::new();
+
+        for (name, weight) in weights {
+            parsed.insert(name.parse::<StatError>()?, *weight);
+        }
+
+        Ok(Self(parsed))
+    }
+
+    /// how much a single error of the given category counts, see `StatError::weight`
+    pub fn weight(&self, error: StatError) -> usize {
+        self.0
+            .get(&error)
+            .copied()
+            .unwrap_or_else(|| error.weight())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// every category can be looked up by its name
+    fn stat_error_names_round_trip() {
+        for error in ALL {
+            assert_eq!(error.name().parse::<StatError>().unwrap(), error);
+        }
+
+        assert_eq!("DNS".parse::<StatError>().unwrap(), StatError::Dns);
+        assert!("nope".parse::<StatError>().is_err());
+    }
+
+    #[test]
+    /// weights for categories that don't exist are rejected
+    fn error_weights_rejects_unknown_categories() {
+        let weights = HashMap::from([(String::from("bogus"), 1)]);
+        assert!(ErrorWeights::new(&weights).is_err());
+    }
+
+    #[test]
+    /// given weights replace the defaults of their categories only
+    fn error_weights_override_defaults() {
+        let weights = HashMap::from([(String::from("proxy"), 2), (String::from("dns"), 0)]);
+        let weights = ErrorWeights::new(&weights).unwrap();
+
+        assert_eq!(weights.weight(StatError::Proxy), 2);
+        assert_eq!(weights.weight(StatError::Dns), 0);
+        assert_eq!(weights.weight(StatError::Timeout), 1);
+        assert_eq!(weights.weight(StatError::TooLarge), 0);
+    }
+
+    #[test]
+    /// proxy failures aren't counted by default, everything the target causes is counted once
+    fn stat_error_default_weights() {
+        assert_eq!(StatError::Timeout.weight(), 1);
+        assert_eq!(StatError::ConnectTimeout.weight(), 1);
+        assert_eq!(StatError::ReadTimeout.weight(), 1);
+        assert_eq!(StatError::Dns.weight(), 1);
+        assert_eq!(StatError::Proxy.weight(), 0);
+        assert_eq!(StatError::Doh.weight(), 0);
+        assert_eq!(StatError::TooLarge.weight(), 0);
+    }
+}
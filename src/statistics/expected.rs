@@ -0,0 +1,81 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Accounting for the total number of requests a run is expected to make, which drives the
+/// length of the overall progress bar
+///
+/// Additions (new scans, collected extensions and words, backup urls, extracted links,
+/// wildcard probes) and subtractions (cancelled or bailed scans, directory listings, denied
+/// urls) are tallied separately, so a subtraction that arrives before its matching addition
+/// can't wrap the total around
+#[derive(Default)]
+pub struct ExpectedRequests {
+    /// sum of all additions
+    added: AtomicUsize,
+
+    /// sum of all subtractions
+    removed: AtomicUsize,
+}
+
+impl ExpectedRequests {
+    /// expect `amount` more requests
+    pub fn add(&self, amount: usize) {
+        atomic_increment!(self.added, amount);
+    }
+
+    /// expect `amount` fewer requests
+    pub fn subtract(&self, amount: usize) {
+        atomic_increment!(self.removed, amount);
+    }
+
+    /// current number of expected requests
+    pub fn total(&self) -> usize {
+        atomic_load!(self.added).saturating_sub(atomic_load!(self.removed))
+    }
+
+    /// length of a progress bar that's already `position` requests in; requests made outside of
+    /// any expectation lengthen the bar instead of running it past 100%
+    pub fn bar_length(&self, position: u64) -> u64 {
+        (self.total() as u64).max(position)
+    }
+}
+
+/// debug output is just the total, like the rest of the counters in `Stats`
+impl fmt::Debug for ExpectedRequests {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// subtracting more than was added should bottom out at zero, and later additions should
+    /// still be offset by the earlier subtraction
+    fn expected_requests_subtractions_dont_wrap() {
+        let expected = ExpectedRequests::default();
+
+        expected.subtract(10);
+        assert_eq!(expected.total(), 0);
+
+        expected.add(25);
+        assert_eq!(expected.total(), 15);
+
+        expected.subtract(5);
+        assert_eq!(expected.total(), 10);
+    }
+
+    #[test]
+    /// bar_length should never be shorter than the bar's position
+    fn expected_requests_bar_length_covers_position() {
+        let expected = ExpectedRequests::default();
+        expected.add(100);
+
+        assert_eq!(expected.bar_length(40), 100);
+        assert_eq!(expected.bar_length(120), 120);
+    }
+}
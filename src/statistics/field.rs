@@ -31,6 +31,18 @@ pub enum StatField {
     /// Translates to `initial_targets`
     InitialTargets,
 
+    /// Translates to `wordlist_words`
+    WordlistWords,
+
+    /// Translates to `proxy_failovers`
+    ProxyFailovers,
+
+    /// Translates to `raw_requests`
+    RawRequests,
+
+    /// Translates to `raw_errors`
+    RawErrors,
+
     /// Translates to `directory_scan_times`; assumes a single append to the vector
     DirScanTimes,
 }
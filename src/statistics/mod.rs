@@ -1,12 +1,13 @@
 mod error;
 mod macros;
 mod container;
+mod expected;
 mod field;
 #[cfg(test)]
 mod tests;
 
 pub use self::container::Stats;
-pub use self::error::StatError;
+pub use self::error::{ErrorWeights, StatError};
 pub use self::field::StatField;
 
 #[cfg(test)]
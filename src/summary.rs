@@ -0,0 +1,232 @@
+//! machine-readable rollup of a finished scan (--summary)
+//!
+//! the streaming results (--output, --json) describe each finding as it's found; the summary is
+//! a single JSON document written once every handler has drained, for dashboards that only want
+//! the totals: targets, durations, request counts, a status code histogram, rate limit changes,
+//! bailed scans, what the filters removed, and the most interesting findings
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs,
+    sync::{atomic::Ordering, Mutex},
+};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    atomic_load,
+    event_handlers::Handles,
+    response::FeroxResponse,
+    rules::Severity,
+    scan_manager::{ScanOrder, ScanType},
+    utils::fmt_err,
+    VERSION,
+};
+
+/// number of findings listed under `top_findings`
+const TOP_FINDINGS: usize = 10;
+
+/// Best findings reported so far, only populated when --summary is used
+#[derive(Debug, Default)]
+pub struct Summary {
+    /// findings as they were reported, trimmed down to the best every so often
+    findings: Mutex<Vec<Finding>>,
+}
+
+/// a reported finding, as listed under `top_findings`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct Finding {
+    /// url of the finding
+    url: String,
+
+    /// http method used
+    method: String,
+
+    /// status code of the response
+    status: u16,
+
+    /// length of the response body
+    content_length: u64,
+
+    /// severity given by --rules, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<Severity>,
+
+    /// tags given by --categorize/--rules
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+/// implementation of Finding
+impl Finding {
+    /// how interesting the finding is; higher severities first, then 2xx before anything else,
+    /// then tagged findings
+    fn rank(&self) -> (Option<Severity>, bool, usize) {
+        (
+            self.severity,
+            (200..300).contains(&self.status),
+            self.tags.len(),
+        )
+    }
+}
+
+/// keep the best TOP_FINDINGS of the given findings, best first; ties keep the order in which
+/// they were found
+fn keep_best(findings: &mut Vec<Finding>) {
+    findings.sort_by_key(|finding| Reverse(finding.rank()));
+    findings.truncate(TOP_FINDINGS);
+}
+
+/// implementation of Summary
+impl Summary {
+    /// remember the given finding for `top_findings`
+    pub fn record(&self, response: &FeroxResponse) {
+        let finding = Finding {
+            url: response.url().to_string(),
+            method: response.method().to_string(),
+            status: response.status().as_u16(),
+            content_length: response.content_length(),
+            severity: response.severity(),
+            tags: response.tags().to_vec(),
+        };
+
+        if let Ok(mut findings) = self.findings.lock() {
+            findings.push(finding);
+
+            if findings.len() >= TOP_FINDINGS * 2 {
+                // sorting every time would be wasteful, let the list grow a bit first
+                keep_best(&mut findings);
+            }
+        }
+    }
+}
+
+/// build the summary of the finished scan, which ran for the given number of seconds
+fn build(handles: &Handles, runtime: f64) -> Value {
+    let mut targets = Vec::new();
+    let mut scans = Vec::new();
+    let mut bailed = Vec::new();
+    let mut status_codes: BTreeMap<u16, usize> = BTreeMap::new();
+
+    if let Ok(ferox_scans) = handles.ferox_scans() {
+        if let Ok(guard) = ferox_scans.scans.read() {
+            for scan in guard.iter() {
+                for (code, count) in scan.status_codes() {
+                    *status_codes.entry(code).or_default() += count;
+                }
+
+                if !matches!(scan.scan_type, ScanType::Directory | ScanType::Crawl) {
+                    continue;
+                }
+
+                if matches!(scan.scan_order, ScanOrder::Initial) {
+                    targets.push(scan.url().to_string());
+                }
+
+                if scan.is_bailed() {
+                    bailed.push(scan.url().to_string());
+                }
+
+                let tuning_events: Vec<_> = scan
+                    .tuning_events()
+                    .into_iter()
+                    .map(|(seconds, rate_limit)| json!({"at": seconds, "rate_limit": rate_limit}))
+                    .collect();
+
+                scans.push(json!({
+                    "url": scan.url(),
+                    "scan_type": scan.scan_type,
+                    "status": scan.status,
+                    "runtime": scan.runtime(),
+                    "requests": scan.requests(),
+                    "requeues": scan.requeues(),
+                    "bailed": scan.is_bailed(),
+                    "tuning_events": tuning_events,
+                }));
+            }
+        }
+    }
+
+    let stats = &handles.stats.data;
+
+    let filters: BTreeMap<_, _> = stats.filter_hits().into_iter().collect();
+    let filtered: usize = filters.values().sum();
+
+    let mut findings = handles
+        .features
+        .summary
+        .findings
+        .lock()
+        .map(|findings| findings.clone())
+        .unwrap_or_default();
+    keep_best(&mut findings);
+
+    json!({
+        "type": "summary",
+        "version": VERSION,
+        "targets": targets,
+        "runtime": runtime,
+        "requests": {
+            "sent": atomic_load!(stats.requests),
+            "expected": stats.total_expected(),
+            "errors": stats.errors(),
+        },
+        "resources_discovered": stats.resources_discovered(),
+        "status_codes": status_codes,
+        "scans": scans,
+        "bailed": bailed,
+        "filtered": {
+            "total": filtered,
+            "by_filter": filters,
+        },
+        "top_findings": findings,
+    })
+}
+
+/// write the summary of the finished scan to the given file (--summary)
+pub fn write(path: &str, handles: &Handles, runtime: f64) -> Result<()> {
+    let summary = serde_json::to_string_pretty(&build(handles, runtime))?;
+
+    fs::write(path, summary + "\n")
+        .with_context(|| fmt_err(&format!("Could not write summary {path}")))?;
+
+    log::info!("wrote scan summary to {}", path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// finding with the given status, severity, and number of tags
+    fn finding(status: u16, severity: Option<Severity>, tags: usize) -> Finding {
+        Finding {
+            url: format!("http://localhost/{status}"),
+            method: String::from("GET"),
+            status,
+            content_length: 0,
+            severity,
+            tags: vec![String::from("tag"); tags],
+        }
+    }
+
+    #[test]
+    /// severity beats status, 2xx beats everything else, and only the best are kept
+    fn best_findings_are_kept() {
+        let mut findings = vec![finding(404, None, 0); TOP_FINDINGS];
+        findings.push(finding(200, None, 1));
+        findings.push(finding(403, Some(Severity::High), 0));
+        findings.push(finding(200, Some(Severity::Low), 0));
+        findings.push(finding(301, None, 0));
+
+        keep_best(&mut findings);
+
+        assert_eq!(findings.len(), TOP_FINDINGS);
+        assert_eq!(findings[0], finding(403, Some(Severity::High), 0));
+        assert_eq!(findings[1], finding(200, Some(Severity::Low), 0));
+        assert_eq!(findings[2], finding(200, None, 1));
+        assert_eq!(findings[3], finding(404, None, 0));
+    }
+}
@@ -0,0 +1,401 @@
+//! request templating with FUZZ markers, turning a scan into a simple fuzzer
+//!
+//! when the FUZZ keyword (or a keyword given via --fuzz-wordlist, i.e. `FUZZ2=/some/words`)
+//! shows up in a target url, a header, or --data, each request is built by replacing the keywords
+//! with words from their wordlists instead of adding a word to the end of the target. FUZZ uses
+//! the usual --wordlist
+//!
+//! --fuzz-mode picks how the wordlists are combined:
+//!
+//! - `clusterbomb`: every combination of words, i.e. 100 x 20 words => 2000 requests
+//! - `pitchfork`: the nth word of each list together, stopping at the shortest list
+//!
+//! combinations are worked out up front and take the place of the wordlist, so progress bars,
+//! --resume-from, and the like keep working on a per-combination basis. a combination travels
+//! through the scan as a single word, its values separated by newlines (which a wordlist line
+//! can't contain). templated requests aren't recursed into, and the 404 heuristics are skipped;
+//! use the filters to weed out uninteresting responses
+use std::{
+    cmp::Reverse,
+    fs::File,
+    io::{BufRead, BufReader},
+    sync::RwLock,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{config::Configuration, utils::fmt_err};
+
+/// keyword fed by --wordlist
+pub const DEFAULT_KEYWORD: &str = "FUZZ";
+
+/// separates the values of a combination inside a single word
+const SEPARATOR: char = '\n';
+
+/// most combinations a scan may expand into; a clusterbomb of two big lists gets out of hand
+/// quickly, and each combination is kept in memory for the duration of the scan
+pub const MAX_COMBINATIONS: usize = 5_000_000;
+
+/// Keywords in use by the scan; empty when requests aren't templated
+#[derive(Debug, Default)]
+pub struct Templating {
+    /// keywords in use, in the order their values appear in a combination
+    keywords: RwLock<Vec<String>>,
+}
+
+/// implementation of Templating
+impl Templating {
+    /// whether requests are built from templates instead of appending words to the target
+    pub fn is_active(&self) -> bool {
+        self.keywords
+            .read()
+            .is_ok_and(|keywords| !keywords.is_empty())
+    }
+
+    /// keywords in use, in the order their values appear in a combination
+    pub fn keywords(&self) -> Vec<String> {
+        self.keywords
+            .read()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// split a --fuzz-wordlist value into its keyword and file, i.e. `FUZZ2=/some/words`
+fn parse_fuzz_wordlist(value: &str) -> Result<(&str, &str)> {
+    let Some((keyword, file)) = value.split_once('=') else {
+        bail!("{value} should look like KEYWORD=FILE");
+    };
+
+    if keyword.is_empty() || file.is_empty() {
+        bail!("{value} should look like KEYWORD=FILE");
+    }
+
+    if keyword == DEFAULT_KEYWORD {
+        bail!("{DEFAULT_KEYWORD} always uses --wordlist, pick another keyword for {file}");
+    }
+
+    Ok((keyword, file))
+}
+
+/// words from the given file; comments and blank lines are skipped, same as --wordlist
+fn read_words(path: &str) -> Result<Vec<String>> {
+    let file = File::open(path).with_context(|| fmt_err(&format!("Could not open {path}")))?;
+
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect())
+}
+
+/// every place a keyword may show up: targets, header names and values, and --data
+fn templates<'a>(targets: &'a [String], config: &'a Configuration) -> Vec<&'a str> {
+    let mut templates: Vec<&str> = targets.iter().map(String::as_str).collect();
+
+    for (name, value) in &config.headers {
+        templates.push(name);
+        templates.push(value);
+    }
+
+    if let Ok(data) = std::str::from_utf8(&config.data) {
+        templates.push(data);
+    }
+
+    templates
+}
+
+/// every combination of the given lists, each one joined into a single word
+///
+/// clusterbomb combinations vary the last list the fastest, i.e. [a, b] x [1, 2] =>
+/// a1, a2, b1, b2; pitchfork stops at the end of the shortest list
+pub fn combinations(lists: &[Vec<String>], mode: &str) -> Result<Vec<String>> {
+    if lists.is_empty() || lists.iter().any(Vec::is_empty) {
+        return Ok(Vec::new());
+    }
+
+    if mode == "pitchfork" {
+        let shortest = lists.iter().map(Vec::len).min().unwrap_or_default();
+
+        return Ok((0..shortest)
+            .map(|index| {
+                lists
+                    .iter()
+                    .map(|list| list[index].as_str())
+                    .collect::<Vec<_>>()
+                    .join(&SEPARATOR.to_string())
+            })
+            .collect());
+    }
+
+    let total = lists
+        .iter()
+        .try_fold(1usize, |total, list| total.checked_mul(list.len()))
+        .filter(|total| *total <= MAX_COMBINATIONS);
+
+    let Some(total) = total else {
+        bail!(
+            "{} combinations is more than the {} allowed, try --fuzz-mode pitchfork or smaller wordlists",
+            lists
+                .iter()
+                .map(|list| list.len().to_string())
+                .collect::<Vec<_>>()
+                .join(" x "),
+            MAX_COMBINATIONS
+        );
+    };
+
+    let mut combinations = Vec::with_capacity(total);
+    let mut indices = vec![0; lists.len()];
+
+    for _ in 0..total {
+        combinations.push(
+            indices
+                .iter()
+                .zip(lists)
+                .map(|(index, list)| list[*index].as_str())
+                .collect::<Vec<_>>()
+                .join(&SEPARATOR.to_string()),
+        );
+
+        // odometer style increment, last list first
+        for (position, index) in indices.iter_mut().enumerate().rev() {
+            *index += 1;
+
+            if *index < lists[position].len() {
+                break;
+            }
+
+            *index = 0;
+        }
+    }
+
+    Ok(combinations)
+}
+
+/// figure out whether any keywords are in use and, if so, expand the given wordlist into every
+/// combination of the keywords' words; returns None when nothing is templated
+///
+/// the keywords found are kept in the given `Templating`
+pub fn initialize(
+    targets: &[String],
+    config: &Configuration,
+    words: &[String],
+    templating: &Templating,
+) -> Result<Option<Vec<String>>> {
+    let templates = templates(targets, config);
+    let used = |keyword: &str| templates.iter().any(|template| template.contains(keyword));
+
+    let mut keywords = Vec::new();
+    let mut lists = Vec::new();
+
+    if used(DEFAULT_KEYWORD) {
+        keywords.push(DEFAULT_KEYWORD.to_string());
+
+        // the leading empty word requests the target itself, meaningless here
+        lists.push(
+            words
+                .iter()
+                .filter(|word| !word.is_empty())
+                .cloned()
+                .collect(),
+        );
+    }
+
+    for value in &config.fuzz_wordlists {
+        let (keyword, file) = parse_fuzz_wordlist(value)?;
+
+        if keywords.iter().any(|known| known == keyword) {
+            bail!("{keyword} was given more than one wordlist");
+        }
+
+        if !used(keyword) {
+            log::warn!("{} isn't used in any target, header, or --data", keyword);
+            continue;
+        }
+
+        keywords.push(keyword.to_string());
+        lists.push(read_words(file)?);
+    }
+
+    if keywords.is_empty() {
+        return Ok(None);
+    }
+
+    let combinations = combinations(&lists, &config.fuzz_mode)?;
+
+    if combinations.is_empty() {
+        bail!("{} didn't produce any combinations", keywords.join(", "));
+    }
+
+    log::info!(
+        "{} {} combinations of {:?}",
+        combinations.len(),
+        config.fuzz_mode,
+        keywords
+    );
+
+    if let Ok(mut guard) = templating.keywords.write() {
+        *guard = keywords;
+    }
+
+    Ok(Some(combinations))
+}
+
+/// the values of a single combination, ready to be put into a template
+#[derive(Debug, Clone)]
+pub struct Payload {
+    /// (keyword, value) pairs, longest keyword first so that FUZZ doesn't eat into FUZZ2
+    pairs: Vec<(String, String)>,
+}
+
+/// implementation of Payload
+impl Payload {
+    /// pair up a combination (as a single word) with the given keywords
+    pub fn new(word: &str, keywords: &[String]) -> Self {
+        let mut pairs: Vec<_> = keywords
+            .iter()
+            .cloned()
+            .zip(word.split(SEPARATOR).map(String::from))
+            .collect();
+
+        pairs.sort_by_key(|(keyword, _)| Reverse(keyword.len()));
+
+        Self { pairs }
+    }
+
+    /// pair up a combination with the keywords in use
+    pub fn from_word(word: &str, templating: &Templating) -> Self {
+        Self::new(word, &templating.keywords())
+    }
+
+    /// replace every keyword in the given template with its value, in a single pass so that
+    /// values containing keywords are left alone
+    pub fn render(&self, template: &str) -> String {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+
+        'outer: while let Some(next) = rest.chars().next() {
+            for (keyword, value) in &self.pairs {
+                if let Some(after) = rest.strip_prefix(keyword.as_str()) {
+                    rendered.push_str(value);
+                    rest = after;
+                    continue 'outer;
+                }
+            }
+
+            rendered.push(next);
+            rest = &rest[next.len_utf8()..];
+        }
+
+        rendered
+    }
+
+    /// headers that contain a keyword, rendered; sent on top of the client's defaults
+    pub fn headers(&self, config: &Configuration) -> Vec<(String, String)> {
+        config
+            .headers
+            .iter()
+            .filter(|(name, value)| {
+                self.pairs
+                    .iter()
+                    .any(|(keyword, _)| name.contains(keyword) || value.contains(keyword))
+            })
+            .map(|(name, value)| (self.render(name), self.render(value)))
+            .collect()
+    }
+
+    /// --data, rendered; binary data is left alone
+    pub fn data(&self, data: &[u8]) -> Vec<u8> {
+        match std::str::from_utf8(data) {
+            Ok(text) => self.render(text).into_bytes(),
+            Err(_) => data.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// turn a list of &str into a list of Strings
+    fn list(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    /// clusterbomb should produce every combination, last list varying the fastest
+    fn combinations_clusterbomb() {
+        let lists = [list(&["a", "b"]), list(&["1", "2", "3"])];
+
+        assert_eq!(
+            combinations(&lists, "clusterbomb").unwrap(),
+            ["a\n1", "a\n2", "a\n3", "b\n1", "b\n2", "b\n3"]
+        );
+    }
+
+    #[test]
+    /// pitchfork should walk the lists side by side and stop at the shortest one
+    fn combinations_pitchfork() {
+        let lists = [list(&["a", "b", "c"]), list(&["1", "2"])];
+
+        assert_eq!(combinations(&lists, "pitchfork").unwrap(), ["a\n1", "b\n2"]);
+    }
+
+    #[test]
+    /// too many combinations should be refused before any are made
+    fn combinations_refuses_explosions() {
+        let big: Vec<_> = (0..5000).map(|n| n.to_string()).collect();
+        let lists = [big.clone(), big];
+
+        assert!(combinations(&lists, "clusterbomb").is_err());
+        assert_eq!(combinations(&lists, "pitchfork").unwrap().len(), 5000);
+    }
+
+    #[test]
+    /// longer keywords win over their prefixes, and values aren't rendered a second time
+    fn payload_renders_keywords() {
+        let keywords = list(&["FUZZ", "FUZZ2"]);
+        let payload = Payload::new("admin\nFUZZ", &keywords);
+
+        assert_eq!(
+            payload.render("http://localhost/FUZZ/FUZZ2?q=FUZZ"),
+            "http://localhost/admin/FUZZ?q=admin"
+        );
+        assert_eq!(
+            payload.data(b"user=FUZZ&pass=FUZZ2"),
+            b"user=admin&pass=FUZZ"
+        );
+    }
+
+    #[test]
+    /// only headers that contain a keyword are rendered
+    fn payload_renders_headers() {
+        let mut config = Configuration::new().unwrap();
+        config
+            .headers
+            .insert(String::from("X-Api-Key"), String::from("FUZZ2"));
+        config
+            .headers
+            .insert(String::from("Accept"), String::from("*/*"));
+
+        let payload = Payload::new("admin\nsecret", &list(&["FUZZ", "FUZZ2"]));
+
+        assert_eq!(
+            payload.headers(&config),
+            [(String::from("X-Api-Key"), String::from("secret"))]
+        );
+    }
+
+    #[test]
+    /// --fuzz-wordlist values need a keyword other than FUZZ and a file
+    fn parse_fuzz_wordlist_validates() {
+        assert_eq!(
+            parse_fuzz_wordlist("FUZZ2=/some/words").unwrap(),
+            ("FUZZ2", "/some/words")
+        );
+        assert!(parse_fuzz_wordlist("FUZZ=/some/words").is_err());
+        assert!(parse_fuzz_wordlist("FUZZ2").is_err());
+        assert!(parse_fuzz_wordlist("=/some/words").is_err());
+    }
+}
@@ -0,0 +1,540 @@
+//! TLS details of https targets: protocol version, cipher, and the certificate the server
+//! presented
+//!
+//! each host is inspected once, over a connection of its own; certificates are accepted no
+//! matter what, since expired or mismatched ones are exactly what's worth reporting. the result
+//! is printed alongside the responses and written to --output (as `{"type":"tls",...}` with
+//! --json)
+//!
+//! --pin-sha256 uses the same kind of connection: before a target is scanned, the certificates it
+//! presents are fingerprinted and compared against the pins
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use openssl::{
+    asn1::Asn1Time,
+    hash::MessageDigest,
+    ssl::{SslConnector, SslMethod, SslStream, SslVerifyMode},
+    x509::{X509NameRef, X509Ref, X509},
+};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::OutputLevel,
+    event_handlers::{Command, Handles},
+    traits::FeroxSerialize,
+    utils::fmt_err,
+};
+
+/// Hosts whose TLS details were already reported, as host:port
+#[derive(Debug, Default)]
+pub struct TlsInspections {
+    /// hosts (host:port) that were already inspected
+    inspected: Mutex<HashSet<String>>,
+}
+
+/// TLS details of a single host
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct TlsInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"tls"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// host that was connected to
+    pub host: String,
+
+    /// port that was connected to
+    pub port: u16,
+
+    /// negotiated protocol version, i.e. TLSv1.3
+    pub version: String,
+
+    /// negotiated cipher suite
+    pub cipher: String,
+
+    /// certificate subject, i.e. CN=example.com, O=Example
+    pub subject: String,
+
+    /// certificate issuer
+    pub issuer: String,
+
+    /// dns names and ip addresses from the certificate's subject alternative names
+    pub sans: Vec<String>,
+
+    /// start of the certificate's validity period
+    pub not_before: String,
+
+    /// end of the certificate's validity period
+    pub not_after: String,
+
+    /// whether the certificate is outside of its validity period
+    pub expired: bool,
+
+    /// whether the certificate isn't valid for `host`
+    pub hostname_mismatch: bool,
+}
+
+/// FeroxSerialize implementation for TlsInfo
+impl FeroxSerialize for TlsInfo {
+    /// one line summary, i.e. `TLS TLSv1.3 TLS_AES_256_GCM_SHA384 example.com:443 ...`
+    fn as_str(&self) -> String {
+        let mut problems = Vec::new();
+
+        if self.expired {
+            problems.push("expired");
+        }
+
+        if self.hostname_mismatch {
+            problems.push("hostname mismatch");
+        }
+
+        let problems = if problems.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", style(format!("[{}]", problems.join(", "))).red())
+        };
+
+        format!(
+            "{} {} {} {}:{} subject: {} issuer: {} sans: [{}] expires: {}{}\n",
+            style("TLS").bright().green(),
+            self.version,
+            self.cipher,
+            self.host,
+            self.port,
+            self.subject,
+            self.issuer,
+            self.sans.join(", "),
+            self.not_after,
+            problems
+        )
+    }
+
+    /// Create an NDJSON representation of the TLS details
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert TLS details of {}:{} to JSON",
+                self.host, self.port
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// one-line representation of an X509 name, i.e. CN=example.com, O=Example
+fn format_name(name: &X509NameRef) -> String {
+    name.entries()
+        .map(|entry| {
+            let key = entry.object().nid().short_name().unwrap_or("?");
+            let value = String::from_utf8_lossy(entry.data().as_slice());
+
+            format!("{key}={value}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// whether a certificate name (possibly a wildcard, i.e. *.example.com) covers the given host;
+/// wildcards only stand in for a single, leftmost label
+fn name_matches(name: &str, host: &str) -> bool {
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+
+    match name.strip_prefix("*.") {
+        Some(parent) => host
+            .split_once('.')
+            .is_some_and(|(label, rest)| !label.is_empty() && rest == parent),
+        None => name == host,
+    }
+}
+
+/// whether the certificate is valid for the given host; names come from the subject alternative
+/// names, or the common name when there aren't any
+fn covers_host(cert: &X509, sans: &[String], host: &str) -> bool {
+    if let Ok(ip) = host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        return sans
+            .iter()
+            .any(|san| san.parse::<IpAddr>().is_ok_and(|other| other == ip));
+    }
+
+    if !sans.is_empty() {
+        return sans.iter().any(|san| name_matches(san, host));
+    }
+
+    cert.subject_name()
+        .entries_by_nid(openssl::nid::Nid::COMMONNAME)
+        .map(|entry| String::from_utf8_lossy(entry.data().as_slice()))
+        .any(|cn| name_matches(&cn, host))
+}
+
+/// connect to the given host and complete a TLS handshake, accepting whatever certificate it
+/// presents; blocking
+pub(crate) fn handshake(host: &str, port: u16, timeout: Duration) -> Result<SslStream<TcpStream>> {
+    let address = (host.trim_matches(['[', ']']), port)
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| fmt_err(&format!("Could not resolve {host}")))?;
+
+    handshake_with(address, host, timeout)
+}
+
+/// connect to the given address and complete a TLS handshake that presents `host` (unless it's
+/// an ip address), accepting whatever certificate comes back; blocking
+pub(crate) fn handshake_with(
+    address: SocketAddr,
+    host: &str,
+    timeout: Duration,
+) -> Result<SslStream<TcpStream>> {
+    let stream = TcpStream::connect_timeout(&address, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let mut builder = SslConnector::builder(SslMethod::tls())?;
+    builder.set_verify(SslVerifyMode::NONE);
+
+    builder
+        .build()
+        .configure()?
+        .verify_hostname(false)
+        .use_server_name_indication(host.trim_matches(['[', ']']).parse::<IpAddr>().is_err())
+        .connect(host, stream)
+        .map_err(|e| anyhow::anyhow!("TLS handshake with {host} ({address}) failed: {e}"))
+}
+
+/// connect to the given host and collect its TLS details; blocking
+pub fn inspect(host: &str, port: u16, timeout: Duration) -> Result<TlsInfo> {
+    let stream = handshake(host, port, timeout)?;
+    let ssl = stream.ssl();
+
+    let cert = ssl
+        .peer_certificate()
+        .with_context(|| fmt_err(&format!("{host}:{port} didn't present a certificate")))?;
+
+    let sans = cert
+        .subject_alt_names()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| {
+                    name.dnsname().map(String::from).or_else(|| {
+                        match name.ipaddress()? {
+                            [a, b, c, d] => Some(IpAddr::from([*a, *b, *c, *d])),
+                            bytes => <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from),
+                        }
+                        .map(|ip| ip.to_string())
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let now = Asn1Time::days_from_now(0)?;
+    let expired = cert.not_after() < now || cert.not_before() > now;
+    let hostname_mismatch = !covers_host(&cert, &sans, host);
+
+    Ok(TlsInfo {
+        kind: String::from("tls"),
+        host: host.to_string(),
+        port,
+        version: ssl.version_str().to_string(),
+        cipher: ssl
+            .current_cipher()
+            .map(|cipher| cipher.name().to_string())
+            .unwrap_or_default(),
+        subject: format_name(cert.subject_name()),
+        issuer: format_name(cert.issuer_name()),
+        not_before: cert.not_before().to_string(),
+        not_after: cert.not_after().to_string(),
+        sans,
+        expired,
+        hostname_mismatch,
+    })
+}
+
+/// lowercase hex SHA-256 fingerprint of the given certificate's DER encoding
+fn fingerprint(cert: &X509Ref) -> Result<String> {
+    let digest = cert.digest(MessageDigest::sha256())?;
+
+    Ok(digest.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// normalize a --pin-sha256 value, i.e. `AB:CD:...` becomes `abcd...`; `None` when it isn't a
+/// SHA-256 fingerprint
+pub fn normalize_pin(pin: &str) -> Option<String> {
+    let pin = pin
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    (pin.len() == 64 && pin.chars().all(|c| c.is_ascii_hexdigit())).then_some(pin)
+}
+
+/// connect to the given host and make sure that one of the certificates it presents (leaf or
+/// otherwise) matches one of the pins; blocking
+pub fn check_pins(host: &str, port: u16, pins: &[String], timeout: Duration) -> Result<()> {
+    let pins = pins
+        .iter()
+        .map(|pin| {
+            normalize_pin(pin)
+                .with_context(|| fmt_err(&format!("{pin} is not a SHA-256 fingerprint")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let stream = handshake(host, port, timeout)?;
+    let ssl = stream.ssl();
+
+    let leaf = ssl
+        .peer_certificate()
+        .with_context(|| fmt_err(&format!("{host}:{port} didn't present a certificate")))?;
+
+    let leaf_fingerprint = fingerprint(&leaf)?;
+
+    let mut presented = vec![leaf_fingerprint.clone()];
+
+    if let Some(chain) = ssl.peer_cert_chain() {
+        for cert in chain {
+            presented.push(fingerprint(cert)?);
+        }
+    }
+
+    if presented
+        .iter()
+        .any(|fingerprint| pins.contains(fingerprint))
+    {
+        return Ok(());
+    }
+
+    bail!(
+        "certificate pin mismatch for {host}:{port}; it presented {leaf_fingerprint}, expected one of [{}]",
+        pins.join(", ")
+    )
+}
+
+/// check the given target's certificates against --pin-sha256; plain http targets and scans
+/// without pins always pass
+pub async fn check_target_pins(target: &str, pins: &[String], timeout: Duration) -> Result<()> {
+    if pins.is_empty() {
+        return Ok(());
+    }
+
+    let url = Url::parse(target).with_context(|| fmt_err(&format!("Invalid target {target}")))?;
+
+    if url.scheme() != "https" {
+        return Ok(());
+    }
+
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        bail!("{target} doesn't have a host to check certificate pins against");
+    };
+
+    let host = host.to_string();
+    let pins = pins.to_vec();
+
+    tokio::task::spawn_blocking(move || check_pins(&host, port, &pins, timeout)).await?
+}
+
+/// inspect the given target's host, once per host, and report its TLS details; does nothing
+/// for plain http targets, or when a proxy sits between us and the target
+pub async fn inspect_target(target: String, handles: Arc<Handles>) {
+    let Ok(url) = Url::parse(&target) else {
+        return;
+    };
+
+    if url.scheme() != "https" || !handles.config.proxy.is_empty() {
+        return;
+    }
+
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return;
+    };
+
+    let first_visit = handles
+        .features
+        .tls
+        .inspected
+        .lock()
+        .map(|mut inspected| inspected.insert(format!("{host}:{port}")))
+        .unwrap_or_default();
+
+    if !first_visit {
+        return;
+    }
+
+    let host = host.to_string();
+    let timeout = Duration::from_secs(handles.config.timeout);
+
+    let result = tokio::task::spawn_blocking(move || inspect(&host, port, timeout))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result);
+
+    let info = match result {
+        Ok(info) => info,
+        Err(e) => {
+            log::warn!("Could not inspect the TLS details of {}: {}", target, e);
+            return;
+        }
+    };
+
+    log::info!("{:?}", info);
+
+    handles
+        .output
+        .send(Command::ReportTls(Box::new(info)))
+        .unwrap_or_default();
+}
+
+/// whether TLS details are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::{
+        asn1::Asn1Time,
+        hash::MessageDigest,
+        pkey::PKey,
+        rsa::Rsa,
+        ssl::{SslAcceptor, SslMethod},
+        x509::{extension::SubjectAlternativeName, X509NameBuilder},
+    };
+    use std::net::TcpListener;
+
+    #[test]
+    /// wildcards cover a single leftmost label, names are case-insensitive
+    fn name_matches_handles_wildcards() {
+        assert!(name_matches("example.com", "EXAMPLE.com"));
+        assert!(name_matches("*.example.com", "www.example.com"));
+        assert!(!name_matches("*.example.com", "example.com"));
+        assert!(!name_matches("*.example.com", "a.b.example.com"));
+        assert!(!name_matches("example.com", "www.example.com"));
+    }
+
+    /// self-signed certificate for localhost that expired yesterday
+    fn expired_localhost_cert() -> (X509, PKey<openssl::pkey::Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "localhost").unwrap();
+        let name = name.build();
+
+        let mut cert = X509::builder().unwrap();
+        cert.set_version(2).unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::from_unix(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+
+        let san = SubjectAlternativeName::new()
+            .dns("localhost")
+            .build(&cert.x509v3_context(None, None))
+            .unwrap();
+        cert.append_extension(san).unwrap();
+
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+
+        (cert.build(), key)
+    }
+
+    /// serve the given certificate on a local port, for a single connection
+    fn serve_once(cert: &X509, key: &PKey<openssl::pkey::Private>) -> u16 {
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(key).unwrap();
+        acceptor.set_certificate(cert).unwrap();
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(1).flatten() {
+                let _ = acceptor.accept(stream);
+            }
+        });
+
+        port
+    }
+
+    #[test]
+    /// pins are accepted with or without colons and in either case, anything else is rejected
+    fn normalize_pin_accepts_common_formats() {
+        let pin = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+        assert_eq!(normalize_pin(pin).unwrap(), pin);
+        assert_eq!(normalize_pin(&pin.to_uppercase()).unwrap(), pin);
+
+        let colons = pin
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| std::str::from_utf8(pair).unwrap())
+            .collect::<Vec<_>>()
+            .join(":");
+        assert_eq!(normalize_pin(&colons).unwrap(), pin);
+
+        assert!(normalize_pin(&pin[2..]).is_none());
+        assert!(normalize_pin(&pin.replace('9', "z")).is_none());
+    }
+
+    #[test]
+    /// a pin matching the presented certificate passes, any other pin fails and says why
+    fn check_pins_compares_presented_certificates() {
+        let (cert, key) = expired_localhost_cert();
+        let expected = fingerprint(&cert).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let port = serve_once(&cert, &key);
+        check_pins("127.0.0.1", port, &[expected.to_uppercase()], timeout).unwrap();
+
+        let wrong = "0".repeat(64);
+        let port = serve_once(&cert, &key);
+        let err = check_pins("127.0.0.1", port, std::slice::from_ref(&wrong), timeout).unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains(&format!("127.0.0.1:{port}")));
+        assert!(message.contains(&expected));
+        assert!(message.contains(&wrong));
+    }
+
+    #[test]
+    /// a local tls server's details should be collected, including its certificate's problems
+    fn inspect_reports_expired_and_mismatched_certs() {
+        let (cert, key) = expired_localhost_cert();
+        let port = serve_once(&cert, &key);
+
+        // not_after is "now", give it a moment to be in the past
+        std::thread::sleep(Duration::from_millis(1100));
+
+        let info = inspect("127.0.0.1", port, Duration::from_secs(5)).unwrap();
+
+        assert!(info.version.starts_with("TLS"));
+        assert!(!info.cipher.is_empty());
+        assert_eq!(info.subject, "CN=localhost");
+        assert_eq!(info.issuer, "CN=localhost");
+        assert_eq!(info.sans, ["localhost"]);
+        assert!(info.expired);
+        assert!(info.hostname_mismatch);
+
+        let text = console::strip_ansi_codes(&info.as_str()).to_string();
+        assert!(text.contains(&format!("127.0.0.1:{port}")));
+        assert!(text.contains("[expired, hostname mismatch]"));
+
+        let json: serde_json::Value = serde_json::from_str(&info.as_json().unwrap()).unwrap();
+        assert_eq!(json["type"], "tls");
+        assert_eq!(json["expired"], true);
+        assert_eq!(json["sans"][0], "localhost");
+    }
+}
@@ -0,0 +1,377 @@
+//! request/response logging for after-action review of exactly what was sent (--traffic-log)
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use reqwest::{header::HeaderMap, Response, StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::{
+    config::Configuration,
+    export::{request_headers, UtcTime},
+    parser::SIZESPEC_REGEX,
+    utils::fmt_err,
+};
+
+/// headers whose values are never written to the traffic log
+const SENSITIVE_HEADERS: [&str; 6] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+];
+
+/// placeholder written in place of sensitive header values
+const REDACTED: &str = "[REDACTED]";
+
+/// Writes one NDJSON entry per request (along with the response's metadata) to a directory of
+/// size-rotated files, independent of any other output
+#[derive(Debug)]
+pub struct TrafficLog {
+    /// directory in which log files are created
+    directory: PathBuf,
+
+    /// size, in bytes, at which the current file is closed and a new one started; 0 means
+    /// files are never rotated
+    max_size: u64,
+
+    /// whether full response bodies are logged in addition to metadata
+    bodies: bool,
+
+    /// currently open file, along with the number of bytes written to it and its index
+    writer: Mutex<(BufWriter<File>, u64, usize)>,
+}
+
+impl TrafficLog {
+    /// create the log directory (if necessary) and open the first file; existing log files are
+    /// left alone, numbering picks up after the last one found
+    pub fn new(directory: &str, max_size: u64, bodies: bool) -> Result<Self> {
+        log::trace!(
+            "enter: TrafficLog::new({}, {}, {})",
+            directory,
+            max_size,
+            bodies
+        );
+
+        let directory = PathBuf::from(directory);
+
+        fs::create_dir_all(&directory)
+            .with_context(|| fmt_err(&format!("Could not create {}", directory.display())))?;
+
+        let index = next_index(&directory);
+        let file = open_log_file(&directory, index)?;
+
+        let traffic_log = Self {
+            directory,
+            max_size,
+            bodies,
+            writer: Mutex::new((BufWriter::new(file), 0, index)),
+        };
+
+        log::trace!("exit: TrafficLog::new -> {:?}", traffic_log);
+        Ok(traffic_log)
+    }
+
+    /// write a single entry, rotating to a new file first if the current one is full
+    fn write(&self, entry: &Value) -> Result<()> {
+        let mut line = entry.to_string();
+        line.push('\n');
+
+        if let Ok(mut guard) = self.writer.lock() {
+            let (writer, written, index) = &mut *guard;
+
+            if self.max_size > 0 && *written > 0 && *written + line.len() as u64 > self.max_size {
+                writer.flush()?;
+                *index += 1;
+                *writer = BufWriter::new(open_log_file(&self.directory, *index)?);
+                *written = 0;
+            }
+
+            writer.write_all(line.as_bytes())?;
+            writer.flush()?;
+            *written += line.len() as u64;
+        }
+
+        Ok(())
+    }
+}
+
+/// file name used for the log file at the given index, i.e. `traffic-0003.log`
+fn log_file_name(index: usize) -> String {
+    format!("traffic-{index:04}.log")
+}
+
+/// create the log file at the given index
+fn open_log_file(directory: &Path, index: usize) -> Result<File> {
+    let path = directory.join(log_file_name(index));
+
+    File::create(&path).with_context(|| fmt_err(&format!("Could not open {}", path.display())))
+}
+
+/// index after the highest numbered log file already present in the given directory
+fn next_index(directory: &Path) -> usize {
+    fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    name.strip_prefix("traffic-")?
+                        .strip_suffix(".log")?
+                        .parse::<usize>()
+                        .ok()
+                })
+                .max()
+                .map_or(0, |highest| highest + 1)
+        })
+        .unwrap_or_default()
+}
+
+/// convert a size spec (i.e. 512, 100K, 10M, 1G) into a number of bytes; `None` when the spec
+/// is malformed
+pub fn size_in_bytes(size_spec: &str) -> Option<u64> {
+    let captures = SIZESPEC_REGEX.captures(size_spec)?;
+
+    let number: u64 = captures.name("n")?.as_str().parse().ok()?;
+
+    let multiplier: u64 = match captures
+        .name("u")
+        .map(|unit| unit.as_str().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("k") => 1 << 10,
+        Some("m") => 1 << 20,
+        Some("g") => 1 << 30,
+        _ => 1,
+    };
+
+    number.checked_mul(multiplier)
+}
+
+/// set up the traffic log from the values given on the command line/config file; `None` when
+/// --traffic-log wasn't used
+pub fn initialize(config: &Configuration) -> Result<Option<TrafficLog>> {
+    if config.traffic_log.is_empty() {
+        return Ok(None);
+    }
+
+    let max_size = if config.traffic_log_max.is_empty() {
+        0
+    } else {
+        size_in_bytes(&config.traffic_log_max)
+            .with_context(|| fmt_err(&format!("Invalid size given: {}", config.traffic_log_max)))?
+    };
+
+    let traffic_log = TrafficLog::new(&config.traffic_log, max_size, config.traffic_log_bodies)?;
+
+    Ok(Some(traffic_log))
+}
+
+/// header names/values as json, with sensitive values redacted
+fn sanitize<'a, I>(headers: I) -> Value
+where
+    I: IntoIterator<Item = (&'a str, String)>,
+{
+    headers
+        .into_iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                REDACTED.to_string()
+            } else {
+                value
+            };
+
+            json!({"name": name, "value": value})
+        })
+        .collect()
+}
+
+/// response headers as (name, value) pairs
+fn response_headers(headers: &HeaderMap) -> impl Iterator<Item = (&str, String)> {
+    headers.iter().map(|(name, value)| {
+        (
+            name.as_str(),
+            String::from_utf8_lossy(value.as_bytes()).to_string(),
+        )
+    })
+}
+
+/// build the entry describing a single request and its outcome; `extra_headers` are those set
+/// on this request specifically (i.e. by --random-agent), which take precedence over the
+/// configured ones
+fn exchange_entry(
+    url: &Url,
+    method: &str,
+    body_size: usize,
+    extra_headers: &[(String, String)],
+    config: &Configuration,
+    outcome: Result<&Response, &reqwest::Error>,
+    time: SystemTime,
+) -> Value {
+    let path = match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    };
+
+    let mut sent_headers = request_headers(url, config);
+
+    for (name, value) in extra_headers {
+        sent_headers.retain(|(sent, _)| !sent.eq_ignore_ascii_case(name));
+        sent_headers.push((name.clone(), value.clone()));
+    }
+
+    let mut entry = json!({
+        "type": "exchange",
+        "time": UtcTime::from(time).to_iso8601(),
+        "url": url.as_str(),
+        "request": {
+            "line": format!("{method} {path} HTTP/1.1"),
+            "headers": sanitize(sent_headers.iter().map(|(k, v)| (k.as_str(), v.clone()))),
+            "body_size": body_size,
+        },
+    });
+
+    match outcome {
+        Ok(response) => {
+            entry["response"] = json!({
+                "status": response.status().as_u16(),
+                "version": format!("{:?}", response.version()),
+                "headers": sanitize(response_headers(response.headers())),
+                "content_length": response.content_length(),
+            });
+        }
+        Err(error) => {
+            entry["error"] = json!(error.to_string());
+        }
+    }
+
+    entry
+}
+
+/// entries written for each request/response
+impl TrafficLog {
+    /// log a request along with the response's metadata, or the error that occurred instead
+    pub(crate) fn record_exchange(
+        &self,
+        url: &Url,
+        method: &str,
+        body_size: usize,
+        extra_headers: &[(String, String)],
+        config: &Configuration,
+        outcome: Result<&Response, &reqwest::Error>,
+    ) {
+        let entry = exchange_entry(
+            url,
+            method,
+            body_size,
+            extra_headers,
+            config,
+            outcome,
+            SystemTime::now(),
+        );
+
+        self.write(&entry)
+            .unwrap_or_else(|e| log::warn!("Could not write to traffic log: {}", e));
+    }
+
+    /// log the full body of a response; does nothing unless --traffic-log-bodies was used
+    pub(crate) fn record_body(&self, url: &Url, method: &str, status: StatusCode, body: &str) {
+        if !self.bodies {
+            return;
+        }
+
+        let entry = json!({
+            "type": "body",
+            "time": UtcTime::from(SystemTime::now()).to_iso8601(),
+            "url": url.as_str(),
+            "method": method,
+            "status": status.as_u16(),
+            "body": body,
+        });
+
+        self.write(&entry)
+            .unwrap_or_else(|e| log::warn!("Could not write to traffic log: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+    use tempfile::TempDir;
+
+    #[test]
+    /// size specs should be converted to bytes, with an optional unit and trailing b
+    fn traffic_size_in_bytes_parses_units() {
+        assert_eq!(size_in_bytes("512"), Some(512));
+        assert_eq!(size_in_bytes("100K"), Some(100 * 1024));
+        assert_eq!(size_in_bytes("10mb"), Some(10 * 1024 * 1024));
+        assert_eq!(size_in_bytes("1G"), Some(1024 * 1024 * 1024));
+        assert_eq!(size_in_bytes("1T"), None);
+        assert_eq!(size_in_bytes("-1"), None);
+        assert_eq!(size_in_bytes("99999999999999999999G"), None);
+    }
+
+    #[test]
+    /// sensitive request headers should never make it into an entry
+    fn traffic_exchange_entry_redacts_sensitive_headers() {
+        let mut config = Configuration::new().unwrap();
+        config
+            .headers
+            .insert("Authorization".to_string(), "Bearer secret".to_string());
+        config
+            .headers
+            .insert("X-Stuff".to_string(), "things".to_string());
+        config.data = b"a=b".to_vec();
+
+        let url = Url::parse("http://localhost/admin?id=1").unwrap();
+        let client = reqwest::Client::new();
+        let error = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(client.get("http://[::1]:0/").send())
+            .unwrap_err();
+
+        let entry = exchange_entry(&url, "POST", 3, &[], &config, Err(&error), UNIX_EPOCH);
+        let text = entry.to_string();
+
+        assert_eq!(entry["time"], "1970-01-01T00:00:00.000Z");
+        assert_eq!(entry["request"]["line"], "POST /admin?id=1 HTTP/1.1");
+        assert_eq!(entry["request"]["body_size"], 3);
+        assert!(entry["error"].is_string());
+        assert!(text.contains(r#"{"name":"Authorization","value":"[REDACTED]"}"#));
+        assert!(text.contains(r#"{"name":"X-Stuff","value":"things"}"#));
+        assert!(!text.contains("secret"));
+    }
+
+    #[test]
+    /// files should rotate once the max size is reached, and numbering should continue after
+    /// any files left behind by a previous run
+    fn traffic_log_rotates_files() {
+        let tmp = TempDir::new().unwrap();
+        let directory = tmp.path().to_str().unwrap();
+
+        let traffic_log = TrafficLog::new(directory, 64, false).unwrap();
+        let entry = json!({"type": "exchange", "url": "http://localhost/some/long/path"});
+
+        for _ in 0..3 {
+            traffic_log.write(&entry).unwrap();
+        }
+
+        assert!(tmp.path().join("traffic-0000.log").exists());
+        assert!(tmp.path().join("traffic-0002.log").exists());
+
+        let contents = fs::read_to_string(tmp.path().join("traffic-0001.log")).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+
+        let traffic_log = TrafficLog::new(directory, 0, false).unwrap();
+        traffic_log.write(&entry).unwrap();
+        assert!(tmp.path().join("traffic-0003.log").exists());
+    }
+}
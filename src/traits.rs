@@ -1,7 +1,7 @@
 //! collection of all traits used
 use crate::filters::{
-    LinesFilter, RegexFilter, SimilarityFilter, SizeFilter, StatusCodeFilter, WildcardFilter,
-    WordsFilter,
+    CategoryFilter, LinesFilter, RegexFilter, SimilarityFilter, SizeFilter, StatusCodeFilter,
+    WildcardFilter, WordsFilter,
 };
 use crate::response::FeroxResponse;
 use crate::utils::status_colorizer;
@@ -84,6 +84,8 @@ impl Display for dyn FeroxFilter {
                 "Pages similar to: {}",
                 style(&filter.original_url).cyan()
             )
+        } else if let Some(filter) = self.as_any().downcast_ref::<CategoryFilter>() {
+            write!(f, "Category: {}", style(&filter.category).cyan())
         } else {
             write!(f, "Filter: {self:?}")
         }
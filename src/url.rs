@@ -1,5 +1,7 @@
 use crate::utils::parse_url_with_raw_path;
-use crate::{event_handlers::Handles, statistics::StatError::UrlFormat, Command::AddError};
+use crate::{
+    evasion, event_handlers::Handles, statistics::StatError::UrlFormat, Command::AddError,
+};
 use anyhow::{anyhow, bail, Result};
 use reqwest::Url;
 use std::collections::HashSet;
@@ -46,35 +48,77 @@ impl FeroxUrl {
     ) -> Result<Vec<Url>> {
         log::trace!("enter: formatted_urls({})", word);
 
+        let urls: Vec<Url> = self
+            .formatted_url_groups(word, collected_extensions)?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        log::trace!("exit: formatted_urls -> {:?}", urls);
+        Ok(urls)
+    }
+
+    /// Same urls as `formatted_urls`, grouped by variant: the first group holds the word itself
+    /// (both word and word/ with --dual-slash), followed by a group for each extension, passed
+    /// in first and collected after, in a stable order
+    ///
+    /// a group is left empty when its url is already part of an earlier group, so that the
+    /// position of each extension's group doesn't depend on the word
+    pub fn formatted_url_groups(
+        &self,
+        word: &str,
+        collected_extensions: HashSet<String>,
+    ) -> Result<Vec<Vec<Url>>> {
+        log::trace!("enter: formatted_url_groups({})", word);
+
         let mut urls = vec![];
 
-        let slash = if self.handles.config.add_slash {
-            Some("/")
+        let slashes = if self.handles.config.dual_slash {
+            // --dual-slash; the bare word comes first so its response is available when
+            // word/ is classified
+            vec![None, Some("/")]
+        } else if self.handles.config.add_slash {
+            vec![Some("/")]
         } else {
-            None
+            vec![None]
         };
 
-        match self.format(word, slash) {
-            // default request, i.e. no extension
-            Ok(url) => urls.push(url),
-            Err(_) => self.handles.stats.send(AddError(UrlFormat))?,
+        for slash in slashes {
+            match self.format(word, slash) {
+                // default request, i.e. no extension
+                Ok(url) => urls.push(url),
+                Err(_) => self.handles.stats.send(AddError(UrlFormat))?,
+            }
         }
 
+        // an extension of "/" overlaps with --add-slash/--dual-slash, only request it once
+        let mut seen: HashSet<Url> = urls.iter().cloned().collect();
+        let mut groups = vec![urls];
+
+        let mut collected: Vec<_> = collected_extensions.into_iter().collect();
+        collected.sort();
+
         for ext in self
             .handles
             .config
             .extensions
             .iter()
-            .chain(collected_extensions.iter())
+            .chain(collected.iter())
         {
+            let mut group = vec![];
+
             match self.format(word, Some(ext)) {
                 // any extensions passed in
-                Ok(url) => urls.push(url),
+                Ok(url) if seen.insert(url.clone()) => group.push(url),
+                Ok(_) => {}
                 Err(_) => self.handles.stats.send(AddError(UrlFormat))?,
             }
+
+            groups.push(group);
         }
-        log::trace!("exit: formatted_urls -> {:?}", urls);
-        Ok(urls)
+
+        log::trace!("exit: formatted_url_groups -> {:?}", groups);
+        Ok(groups)
     }
 
     /// Simple helper to generate a `Url`
@@ -97,6 +141,10 @@ impl FeroxUrl {
             bail!(message);
         }
 
+        // --encode happens before anything else is added to the word, so extensions and slashes
+        // stay readable
+        let word = encode_word(word, &self.handles.config.encode);
+
         // from reqwest::Url::join
         //   Note: a trailing slash is significant. Without it, the last path component
         //   is considered to be a “file” name to be removed to get at the “directory”
@@ -128,7 +176,7 @@ impl FeroxUrl {
                 format!("{word}.{ext}")
             }
         } else {
-            String::from(word)
+            word
         };
 
         // We check separately if the current word begins with 2 forward slashes
@@ -146,6 +194,17 @@ impl FeroxUrl {
         let base_url = parse_url_with_raw_path(&url)?;
         let mut joined = base_url.join(&word)?;
 
+        let transforms = &self.handles.features.evasion;
+
+        if !transforms.is_empty() {
+            // --evasion; only the part of the path that came from the word is mutated, so that
+            // recursing into a mutated directory doesn't mutate it a second time
+            if let Some(added) = joined.path().strip_prefix(base_url.path()) {
+                let mutated = format!("{}{}", base_url.path(), evasion::apply(added, transforms));
+                joined.set_path(&mutated);
+            }
+        }
+
         if !self.handles.config.queries.is_empty() {
             // if called, this adds a '?' to the url, whether or not there are queries to be added
             // so we need to check if there are queries to be added before blindly adding the '?'
@@ -158,6 +217,44 @@ impl FeroxUrl {
         Ok(joined)
     }
 
+    /// `host:port` key of a url, used to keep per-host state; includes the port so that
+    /// different services on the same machine are tracked separately, falling back to the
+    /// scheme's default port when the url doesn't give one
+    pub(crate) fn host_key(url: &Url) -> String {
+        format!(
+            "{}:{}",
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        )
+    }
+
+    /// canonical form of a url, used to tell whether two scan targets point at the same place
+    ///
+    /// - the scheme and host are lowercased, and international hosts are punycoded
+    /// - default ports (80 for http, 443 for https) are dropped
+    /// - the fragment is dropped
+    /// - the path ends with a single /, ahead of the query string
+    ///
+    /// the path is otherwise left as is, it's case sensitive on most servers and traversal
+    /// segments (i.e. `/a/../b`) aren't resolved. a url that can't be parsed only has its
+    /// trailing slashes normalized
+    pub fn canonicalize(url: &str) -> String {
+        let Ok(mut parsed) = parse_url_with_raw_path(url) else {
+            return format!("{}/", url.trim_end_matches('/'));
+        };
+
+        parsed.set_fragment(None);
+
+        let query = parsed.query().map(|query| format!("?{query}"));
+        parsed.set_query(None);
+
+        format!(
+            "{}/{}",
+            parsed.as_str().trim_end_matches('/'),
+            query.unwrap_or_default()
+        )
+    }
+
     /// Simple helper to abstract away adding a forward-slash to a url if not present
     ///
     /// used mostly for deduplication purposes and url state tracking
@@ -207,6 +304,42 @@ impl FeroxUrl {
     }
 }
 
+/// apply the given --encode strategy to a word; forward slashes are never encoded so that words
+/// containing them still describe multiple path segments
+///
+/// - `url`: every byte is percent-encoded, i.e. `admin` -> `%61%64%6d%69%6e`
+/// - `double-url`: same as `url`, with the percent signs encoded again, i.e. `%2561%2564...`
+/// - `unicode`: every character is %u encoded (as understood by IIS), i.e. `%u0061%u0064...`
+/// - anything else (i.e. `none`) leaves the word alone
+fn encode_word(word: &str, encoding: &str) -> String {
+    match encoding {
+        "url" | "double-url" => {
+            let prefix = if encoding == "url" { "%" } else { "%25" };
+
+            word.split('/')
+                .map(|segment| {
+                    segment
+                        .bytes()
+                        .map(|byte| format!("{prefix}{byte:02x}"))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        }
+        "unicode" => word
+            .split('/')
+            .map(|segment| {
+                segment
+                    .encode_utf16()
+                    .map(|unit| format!("%u{unit:04x}"))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("/"),
+        _ => word.to_string(),
+    }
+}
+
 /// Display implementation for a FeroxUrl
 impl fmt::Display for FeroxUrl {
     /// formatter for FeroxUrl
@@ -270,7 +403,7 @@ mod tests {
         let pdf = Url::parse("http://localhost/turbo.pdf").unwrap();
         let tar = Url::parse("http://localhost/turbo.tar.gz").unwrap();
 
-        let expected = vec![
+        let expected = [
             vec![base.clone(), js.clone()],
             vec![base.clone(), js.clone(), php.clone()],
             vec![base.clone(), js.clone(), php.clone(), pdf.clone()],
@@ -291,6 +424,72 @@ mod tests {
         }
     }
 
+    #[test]
+    /// host keys fall back to the scheme's default port and ignore everything past the host
+    fn host_key_includes_default_port() {
+        let key = |url: &str| FeroxUrl::host_key(&Url::parse(url).unwrap());
+
+        assert_eq!(key("http://localhost/js"), "localhost:80");
+        assert_eq!(key("https://localhost/js?a=b"), "localhost:443");
+        assert_eq!(key("http://localhost:8080/"), "localhost:8080");
+    }
+
+    #[test]
+    /// urls that differ only by case of the host, default port, fragment, or trailing slashes
+    /// have the same canonical form
+    fn canonicalize_merges_equivalent_urls() {
+        for url in [
+            "http://localhost/js",
+            "http://localhost/js/",
+            "http://localhost/js//",
+            "HTTP://LocalHost/js",
+            "http://localhost:80/js",
+            "http://localhost/js/#top",
+        ] {
+            assert_eq!(FeroxUrl::canonicalize(url), "http://localhost/js/");
+        }
+
+        assert_eq!(
+            FeroxUrl::canonicalize("https://EXAMPLE.com:443"),
+            "https://example.com/"
+        );
+        assert_eq!(
+            FeroxUrl::canonicalize("https://Bücher.example/"),
+            "https://xn--bcher-kva.example/"
+        );
+        assert_eq!(
+            FeroxUrl::canonicalize("http://[::FFFF:7F00:1]:80/api"),
+            "http://[::ffff:7f00:1]/api/"
+        );
+    }
+
+    #[test]
+    /// ports that aren't the scheme's default, path case, queries, and traversal segments all
+    /// make a difference
+    fn canonicalize_keeps_meaningful_differences() {
+        assert_eq!(
+            FeroxUrl::canonicalize("http://localhost:443/js"),
+            "http://localhost:443/js/"
+        );
+        assert_eq!(
+            FeroxUrl::canonicalize("https://localhost:80/js"),
+            "https://localhost:80/js/"
+        );
+        assert_eq!(
+            FeroxUrl::canonicalize("http://localhost/JS"),
+            "http://localhost/JS/"
+        );
+        assert_eq!(
+            FeroxUrl::canonicalize("http://localhost/js/?debug=1#top"),
+            "http://localhost/js/?debug=1"
+        );
+        assert_eq!(
+            FeroxUrl::canonicalize("http://localhost/js/../admin"),
+            "http://localhost/js/../admin/"
+        );
+        assert_eq!(FeroxUrl::canonicalize("not a url//"), "not a url/");
+    }
+
     #[test]
     /// base url returns 1
     fn depth_base_url_returns_1() {
@@ -489,4 +688,160 @@ mod tests {
             Err(err) => panic!("{}", err.to_string()),
         }
     }
+
+    #[test]
+    /// ipv6 literal targets (including those whose address looks like a port) should be joined
+    /// with words without losing the brackets or the port, at any depth
+    fn format_url_ipv6_literal_target() {
+        let handles = Arc::new(Handles::for_testing(None, None).0);
+        let url = FeroxUrl::from_string("http://[2001:db8::8080]:8080/admin", handles);
+
+        assert_eq!(
+            url.format("..%2fstuff", None).unwrap().as_str(),
+            "http://[2001:db8::8080]:8080/admin/..%2fstuff"
+        );
+        assert_eq!(
+            url.format("../stuff", None).unwrap().as_str(),
+            "http://[2001:db8::8080]:8080/stuff"
+        );
+        assert_eq!(url.depth().unwrap(), 2);
+    }
+
+    #[test]
+    /// --encode should be applied to the word only, leaving slashes and extensions alone
+    fn format_url_encodes_words() {
+        let config = Configuration {
+            encode: "url".to_string(),
+            ..Default::default()
+        };
+        let handles = Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0);
+        let url = FeroxUrl::from_string("http://localhost", handles);
+
+        assert_eq!(
+            url.format("a/b", Some("php")).unwrap().as_str(),
+            "http://localhost/%61/%62.php"
+        );
+    }
+
+    #[test]
+    /// --evasion should only mutate the word's part of the path, queries are left alone
+    fn format_url_applies_evasion_profile() {
+        let config = Configuration {
+            evasion: "aggressive".to_string(),
+            queries: vec![("x".to_string(), "y".to_string())],
+            ..Default::default()
+        };
+        let handles = Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0);
+
+        let url = FeroxUrl::from_string("http://localhost/api", handles.clone());
+        assert_eq!(
+            url.format("js/app", Some("js")).unwrap().as_str(),
+            "http://localhost/api//.;//js;x=1//app.js;x=1%20?x=y"
+        );
+
+        // recursing into a mutated directory keeps its path as-is
+        let url = FeroxUrl::from_string("http://localhost/api//.;//js;x=1%20/", handles);
+        assert_eq!(
+            url.format("app", None).unwrap().as_str(),
+            "http://localhost/api//.;//js;x=1%20//.;//app;x=1%20?x=y"
+        );
+    }
+
+    #[test]
+    /// each encoding strategy should produce the expected word
+    fn encode_word_strategies() {
+        assert_eq!(encode_word("ad/é", "none"), "ad/é");
+        assert_eq!(encode_word("ad/é", ""), "ad/é");
+        assert_eq!(encode_word("ad/é", "url"), "%61%64/%c3%a9");
+        assert_eq!(encode_word("ad/é", "double-url"), "%2561%2564/%25c3%25a9");
+        assert_eq!(encode_word("ad/é", "unicode"), "%u0061%u0064/%u00e9");
+        assert_eq!(encode_word("//ad", "url"), "//%61%64");
+    }
+
+    #[test]
+    /// --dual-slash should request the bare word before word/, without duplicating a "/"
+    /// extension
+    fn formatted_urls_with_dual_slash() {
+        let config = Configuration {
+            dual_slash: true,
+            extensions: vec!["/".to_string(), "php".to_string()],
+            ..Default::default()
+        };
+        let handles = Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0);
+        let url = FeroxUrl::from_string("http://localhost", handles);
+
+        assert_eq!(
+            url.formatted_urls("ferox", HashSet::new()).unwrap(),
+            [
+                Url::parse("http://localhost/ferox").unwrap(),
+                Url::parse("http://localhost/ferox/").unwrap(),
+                Url::parse("http://localhost/ferox.php").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    /// each extension gets its own group, collected extensions come last in sorted order, and a
+    /// "/" extension that overlaps with --dual-slash leaves its group empty
+    fn formatted_url_groups_by_variant() {
+        let config = Configuration {
+            dual_slash: true,
+            extensions: vec!["/".to_string(), "php".to_string()],
+            ..Default::default()
+        };
+        let handles = Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0);
+        let url = FeroxUrl::from_string("http://localhost", handles);
+        let collected = HashSet::from(["zip".to_string(), "bak".to_string()]);
+
+        let parse = |url: &str| Url::parse(url).unwrap();
+
+        assert_eq!(
+            url.formatted_url_groups("ferox", collected).unwrap(),
+            [
+                vec![
+                    parse("http://localhost/ferox"),
+                    parse("http://localhost/ferox/")
+                ],
+                vec![],
+                vec![parse("http://localhost/ferox.php")],
+                vec![parse("http://localhost/ferox.bak")],
+                vec![parse("http://localhost/ferox.zip")],
+            ]
+        );
+    }
+
+    #[test]
+    /// the number of requests expected per word should match the urls actually generated for
+    /// each method
+    fn expected_num_requests_multiplier_matches_formatted_urls() {
+        let configs = [
+            Configuration::default(),
+            Configuration {
+                extensions: vec!["js".to_string()],
+                ..Default::default()
+            },
+            Configuration {
+                extensions: vec!["js".to_string(), "php".to_string()],
+                methods: vec!["GET".to_string(), "POST".to_string()],
+                ..Default::default()
+            },
+            Configuration {
+                dual_slash: true,
+                extensions: vec!["/".to_string(), "php".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        for config in configs {
+            let num_methods = config.methods.len();
+            let handles = Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0);
+            let url = FeroxUrl::from_string("http://localhost", handles.clone());
+            let num_urls = url.formatted_urls("ferox", HashSet::new()).unwrap().len();
+
+            assert_eq!(
+                handles.expected_num_requests_multiplier(),
+                num_urls * num_methods
+            );
+        }
+    }
 }
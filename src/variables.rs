@@ -0,0 +1,181 @@
+//! per-request header variables, i.e. `-H 'X-Nonce: {{uuid}}'`
+//!
+//! a header value containing a variable isn't sent as-is with the client's defaults, it's
+//! expanded for every request instead; each occurrence gets a value of its own
+//!
+//! - `{{uuid}}`: a random (v4) uuid
+//! - `{{timestamp}}`: seconds since the unix epoch
+//! - `{{counter}}`: a number that goes up by one each time it's expanded, starting at 1; shared
+//!   by every header and every scan, so no two requests ever get the same one
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Result};
+use uuid::Uuid;
+
+/// names of the variables that can be used
+pub const VARIABLES: [&str; 3] = ["uuid", "timestamp", "counter"];
+
+/// opens a variable
+const OPEN: &str = "{{";
+
+/// closes a variable
+const CLOSE: &str = "}}";
+
+/// State the variables are expanded with
+#[derive(Debug, Default)]
+pub struct HeaderVariables {
+    /// last value handed out for `{{counter}}`
+    counter: AtomicU64,
+}
+
+/// implementation of HeaderVariables
+impl HeaderVariables {
+    /// the given value with each of its variables replaced by a freshly evaluated value
+    pub fn expand(&self, value: &str) -> String {
+        let mut expanded = String::with_capacity(value.len());
+        let mut last = 0;
+
+        for (start, end, variable) in names(value) {
+            if let Some(evaluated) = self.evaluate(variable) {
+                expanded.push_str(&value[last..start]);
+                expanded.push_str(&evaluated);
+                last = end;
+            }
+        }
+
+        expanded.push_str(&value[last..]);
+        expanded
+    }
+
+    /// value of a single variable; anything unknown is left as it was
+    fn evaluate(&self, variable: &str) -> Option<String> {
+        match variable {
+            "uuid" => Some(Uuid::new_v4().to_string()),
+            "timestamp" => Some(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_secs())
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            "counter" => Some((self.counter.fetch_add(1, Ordering::Relaxed) + 1).to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// whether the given value contains anything that looks like a variable
+pub fn is_dynamic(value: &str) -> bool {
+    value.contains(OPEN)
+}
+
+/// the names of the variables in the given value, in order, trimmed of whitespace; a `{{`
+/// without a closing `}}` isn't a variable
+fn names(value: &str) -> Vec<(usize, usize, &str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = value[offset..].find(OPEN).map(|start| offset + start) {
+        let Some(end) = value[start..].find(CLOSE).map(|end| start + end) else {
+            break;
+        };
+
+        found.push((
+            start,
+            end + CLOSE.len(),
+            value[start + OPEN.len()..end].trim(),
+        ));
+        offset = end + CLOSE.len();
+    }
+
+    found
+}
+
+/// make sure every variable in the given header value is one that can be expanded
+pub fn validate(name: &str, value: &str) -> Result<()> {
+    for (_, _, variable) in names(value) {
+        if !VARIABLES.contains(&variable) {
+            bail!(
+                "unknown variable {{{{{}}}}} in the {} header; expected one of {}",
+                variable,
+                name,
+                VARIABLES.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashSet, sync::Arc, thread};
+
+    #[test]
+    /// each variable is replaced, surrounding text and unknown/unclosed ones are left alone
+    fn expand_replaces_known_variables() {
+        let variables = HeaderVariables::default();
+        let expanded = variables.expand("id={{ uuid }};ts={{timestamp}};x={{nope}};{{counter");
+
+        let (id, rest) = expanded
+            .strip_prefix("id=")
+            .unwrap()
+            .split_once(";ts=")
+            .unwrap();
+        let (ts, rest) = rest.split_once(';').unwrap();
+
+        assert!(Uuid::parse_str(id).is_ok());
+        assert!(ts.parse::<u64>().unwrap() > 0);
+        assert_eq!(rest, "x={{nope}};{{counter");
+        assert_eq!(variables.expand("no variables"), "no variables");
+    }
+
+    #[test]
+    /// unknown variables are refused, known ones are fine
+    fn validate_refuses_unknown_variables() {
+        assert!(validate("X-Nonce", "{{uuid}}-{{counter}}").is_ok());
+        assert!(validate("X-Nonce", "{{nonce}}")
+            .unwrap_err()
+            .to_string()
+            .contains("unknown variable {{nonce}} in the X-Nonce header"));
+    }
+
+    #[test]
+    /// expanded from many threads at once, neither counters nor uuids are ever handed out twice
+    fn expand_is_unique_across_threads() {
+        let variables = Arc::new(HeaderVariables::default());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let variables = variables.clone();
+
+                thread::spawn(move || {
+                    (0..250)
+                        .map(|_| variables.expand("{{counter}}/{{uuid}}"))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let values: Vec<String> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+
+        let counters: HashSet<_> = values
+            .iter()
+            .map(|value| value.split_once('/').unwrap().0)
+            .collect();
+        let uuids: HashSet<_> = values
+            .iter()
+            .map(|value| value.split_once('/').unwrap().1)
+            .collect();
+
+        assert_eq!(counters.len(), values.len());
+        assert_eq!(uuids.len(), values.len());
+    }
+}
@@ -0,0 +1,516 @@
+//! exposed version control repositories (--vcs-check)
+//!
+//! a finding inside a `.git/`, `.svn/`, or `.hg/` directory (i.e. `/.git/HEAD`) only says that
+//! something answered there; a site that serves the same page for every path does that too. the
+//! repository it points at is checked with a few requests for the files that make it usable (for
+//! git: HEAD, config, and the branch HEAD points at), and a repository whose files look the part
+//! is reported as a high severity finding, printed alongside the responses and written to --output
+//! (as `{"type":"vcs",...}` with --json)
+//!
+//! each repository is only checked once, with no more than three requests, and no more than
+//! MAX_REPOSITORIES repositories are checked per run
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use console::style;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::{
+    config::OutputLevel,
+    event_handlers::{
+        Command::{self, AddToUsizeField},
+        Handles,
+    },
+    response::FeroxResponse,
+    rules::Severity,
+    send_command,
+    statistics::StatField::TotalExpected,
+    traits::FeroxSerialize,
+    utils::{fmt_err, logged_request},
+    DEFAULT_METHOD,
+};
+
+/// number of repositories checked per run
+const MAX_REPOSITORIES: usize = 10;
+
+/// bytes of a repository file read; the files checked are small, anything else is cut off
+const MAX_FILE_SIZE: usize = 64 * 1024;
+
+/// tag given to every exposed repository
+const VCS_TAG: &str = "exposed-vcs";
+
+/// Repository checks made so far (--vcs-check)
+#[derive(Debug, Default)]
+pub struct VcsChecks {
+    /// repositories that were already checked, by url
+    checked: Mutex<HashSet<String>>,
+
+    /// checks that are still running
+    pending: Mutex<Vec<JoinHandle<()>>>,
+}
+
+/// Version control system a repository belongs to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Vcs {
+    /// git, `.git/`
+    Git,
+
+    /// subversion, `.svn/`
+    Svn,
+
+    /// mercurial, `.hg/`
+    Hg,
+}
+
+/// Display implementation for Vcs
+impl fmt::Display for Vcs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Git => write!(f, "git"),
+            Self::Svn => write!(f, "svn"),
+            Self::Hg => write!(f, "hg"),
+        }
+    }
+}
+
+/// A version control repository that was confirmed to be exposed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VcsInfo {
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"vcs"}`
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// version control system the repository belongs to
+    pub vcs: Vcs,
+
+    /// url of the repository directory, i.e. `http://localhost/.git/`
+    pub repository: String,
+
+    /// the finding that led to the repository
+    pub url: String,
+
+    /// always high, an exposed repository usually means the site's source can be downloaded
+    pub severity: Severity,
+
+    /// tags given to the finding
+    pub tags: Vec<String>,
+
+    /// what was found in the repository's files, i.e. `HEAD: ref: refs/heads/main`
+    pub evidence: Vec<String>,
+}
+
+/// FeroxSerialize implementation for VcsInfo
+impl FeroxSerialize for VcsInfo {
+    /// one line summary, i.e.
+    /// `VCS high git http://localhost/.git/ [HEAD: ref: refs/heads/main, remote: https://...]`
+    fn as_str(&self) -> String {
+        format!(
+            "{} {} {} {} {}\n",
+            style("VCS").bright().red(),
+            self.severity.styled(),
+            self.vcs,
+            style(&self.repository).yellow(),
+            style(format!("[{}]", self.evidence.join(", "))).dim()
+        )
+    }
+
+    /// Create an NDJSON representation of the repository
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self).with_context(|| {
+            fmt_err(&format!(
+                "Could not convert the {} repository {} to JSON",
+                self.vcs, self.repository
+            ))
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// the version control system and repository directory the given url lies in, if any; the
+/// directory is returned with a trailing slash
+pub fn detect(url: &Url) -> Option<(Vcs, Url)> {
+    let segments: Vec<_> = url.path_segments()?.collect();
+
+    for (index, segment) in segments.iter().enumerate() {
+        let vcs = match segment.to_ascii_lowercase().as_str() {
+            ".git" => Vcs::Git,
+            ".svn" => Vcs::Svn,
+            ".hg" => Vcs::Hg,
+            _ => continue,
+        };
+
+        let mut repository = url.clone();
+        repository.set_query(None);
+        repository.set_fragment(None);
+        repository.set_path(&format!("/{}/", segments[..=index].join("/")));
+
+        return Some((vcs, repository));
+    }
+
+    None
+}
+
+/// request the given file of the repository, handing back (the start of) its contents when it
+/// comes back as a 200
+async fn fetch(repository: &Url, file: &str, handles: Arc<Handles>) -> Option<Vec<u8>> {
+    let url = repository.join(file).ok()?;
+
+    // need to manually adjust stats
+    send_command!(handles.stats.tx, AddToUsizeField(TotalExpected, 1));
+
+    let mut response = match logged_request(&url, DEFAULT_METHOD, None, handles).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("Could not request {}: {}", url, e);
+            return None;
+        }
+    };
+
+    if response.status() != StatusCode::OK {
+        return None;
+    }
+
+    let mut body = Vec::new();
+
+    while body.len() < MAX_FILE_SIZE {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+
+    body.truncate(MAX_FILE_SIZE);
+    Some(body)
+}
+
+/// whether the given text is a full git object id
+fn is_object_id(text: &str) -> bool {
+    text.len() == 40 && text.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// what a git HEAD file points at, if the given contents are one; either `ref: refs/...` or an
+/// object id (detached HEAD)
+fn parse_git_head(contents: &str) -> Option<&str> {
+    let head = contents.trim();
+
+    match head.strip_prefix("ref:") {
+        Some(reference) if reference.trim().starts_with("refs/") => Some(reference.trim()),
+        None if is_object_id(head) => Some(head),
+        _ => None,
+    }
+}
+
+/// the remote url configured in a git config file, if the given contents are one; `Some("")`
+/// for a config file without a remote
+fn parse_git_config(contents: &str) -> Option<String> {
+    if !contents.lines().any(|line| line.trim() == "[core]") {
+        return None;
+    }
+
+    let remote = contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("url"))
+        .filter_map(|rest| rest.trim_start().strip_prefix('='))
+        .map(|url| url.trim().to_string())
+        .next()
+        .unwrap_or_default();
+
+    Some(remote)
+}
+
+/// whether the given contents are a mercurial requires file; every repository lists at least
+/// one of these
+fn is_hg_requires(contents: &str) -> bool {
+    contents
+        .lines()
+        .any(|line| matches!(line.trim(), "revlogv1" | "store" | "fncache" | "dotencode"))
+}
+
+/// whether the given contents are a subversion working copy database (1.7+), or an entries file
+/// from before it, which starts with its format number
+fn is_svn_metadata(contents: &[u8], file: &str) -> bool {
+    match file {
+        "wc.db" => contents.starts_with(b"SQLite format 3\0"),
+        _ => String::from_utf8_lossy(contents)
+            .lines()
+            .next()
+            .is_some_and(|line| line.trim().parse::<u8>().is_ok()),
+    }
+}
+
+/// check the files of a git repository; returns what was found when HEAD and config both look
+/// the part
+async fn check_git(repository: &Url, handles: Arc<Handles>) -> Option<Vec<String>> {
+    let head = fetch(repository, "HEAD", handles.clone()).await?;
+    let head = String::from_utf8_lossy(&head);
+    let head = parse_git_head(&head)?.to_string();
+
+    let config = fetch(repository, "config", handles.clone()).await?;
+    let remote = parse_git_config(&String::from_utf8_lossy(&config))?;
+
+    let mut evidence = vec![format!("HEAD: {head}"), String::from("config: [core]")];
+
+    if !remote.is_empty() {
+        evidence.push(format!("remote: {remote}"));
+    }
+
+    // the branch HEAD points at; a loose ref is there unless the refs were packed. the ref comes
+    // from the server, it's not allowed to point outside of the repository
+    if head.starts_with("refs/") && !head.contains("..") {
+        if let Some(reference) = fetch(repository, &head, handles).await {
+            let id = String::from_utf8_lossy(&reference).trim().to_string();
+
+            if is_object_id(&id) {
+                evidence.push(format!("{head}: {id}"));
+            }
+        }
+    }
+
+    Some(evidence)
+}
+
+/// check the files of a subversion working copy
+async fn check_svn(repository: &Url, handles: Arc<Handles>) -> Option<Vec<String>> {
+    for file in ["wc.db", "entries"] {
+        if let Some(contents) = fetch(repository, file, handles.clone()).await {
+            if is_svn_metadata(&contents, file) {
+                return Some(vec![format!("{file}: working copy metadata")]);
+            }
+        }
+    }
+
+    None
+}
+
+/// check the files of a mercurial repository
+async fn check_hg(repository: &Url, handles: Arc<Handles>) -> Option<Vec<String>> {
+    let requires = fetch(repository, "requires", handles.clone()).await?;
+    let requires = String::from_utf8_lossy(&requires);
+
+    if !is_hg_requires(&requires) {
+        return None;
+    }
+
+    let mut evidence = vec![format!(
+        "requires: {}",
+        requires.split_whitespace().collect::<Vec<_>>().join(" ")
+    )];
+
+    if let Some(hgrc) = fetch(repository, "hgrc", handles).await {
+        let hgrc = String::from_utf8_lossy(&hgrc);
+
+        if let Some(remote) = hgrc
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("default"))
+            .filter_map(|rest| rest.trim_start().strip_prefix('='))
+            .map(str::trim)
+            .next()
+        {
+            evidence.push(format!("remote: {remote}"));
+        }
+    }
+
+    Some(evidence)
+}
+
+/// check the given repository, reporting it if it's exposed
+async fn check(vcs: Vcs, repository: Url, url: String, handles: Arc<Handles>) {
+    log::trace!("enter: check({}, {}, {})", vcs, repository, url);
+
+    let evidence = match vcs {
+        Vcs::Git => check_git(&repository, handles.clone()).await,
+        Vcs::Svn => check_svn(&repository, handles.clone()).await,
+        Vcs::Hg => check_hg(&repository, handles.clone()).await,
+    };
+
+    let Some(evidence) = evidence else {
+        log::info!(
+            "{} doesn't look like an exposed {} repository",
+            repository,
+            vcs
+        );
+        return;
+    };
+
+    let info = VcsInfo {
+        kind: String::from("vcs"),
+        vcs,
+        repository: repository.to_string(),
+        url,
+        severity: Severity::High,
+        tags: vec![String::from(VCS_TAG), vcs.to_string()],
+        evidence,
+    };
+
+    log::info!("{:?}", info);
+
+    handles
+        .output
+        .send(Command::ReportVcs(Box::new(info)))
+        .unwrap_or_default();
+
+    log::trace!("exit: check");
+}
+
+/// queue up a check of the repository the given finding lies in, if it hasn't been checked yet
+/// and MAX_REPOSITORIES hasn't been reached; does nothing unless --vcs-check is used
+pub fn on_finding(response: &FeroxResponse, handles: Arc<Handles>) {
+    if !handles.config.vcs_check {
+        return;
+    }
+
+    let Some((vcs, repository)) = detect(response.url()) else {
+        return;
+    };
+
+    let Ok(mut checked) = handles.features.vcs.checked.lock() else {
+        return;
+    };
+
+    if checked.contains(repository.as_str()) {
+        return;
+    }
+
+    if checked.len() >= MAX_REPOSITORIES {
+        log::debug!("repository limit reached, not checking {}", repository);
+        return;
+    }
+
+    checked.insert(repository.to_string());
+    drop(checked);
+
+    log::debug!("checking {} for an exposed {} repository", repository, vcs);
+
+    let task = tokio::spawn(check(
+        vcs,
+        repository,
+        response.url().to_string(),
+        handles.clone(),
+    ));
+
+    if let Ok(mut pending) = handles.features.vcs.pending.lock() {
+        pending.push(task);
+    }
+}
+
+/// wait for every queued repository check to finish, so their findings make it out before the
+/// output handler is closed
+pub async fn finish(handles: &Handles) {
+    loop {
+        let tasks: Vec<_> = match handles.features.vcs.pending.lock() {
+            Ok(mut pending) => pending.drain(..).collect(),
+            Err(_) => return,
+        };
+
+        if tasks.is_empty() {
+            return;
+        }
+
+        for task in tasks {
+            task.await.unwrap_or_default();
+        }
+    }
+}
+
+/// whether exposed repositories are printed to the terminal at the given output level
+pub(crate) fn is_printed(output_level: OutputLevel) -> bool {
+    matches!(output_level, OutputLevel::Default | OutputLevel::Quiet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (vcs, repository) the given url lies in
+    fn detect_str(url: &str) -> Option<(Vcs, String)> {
+        detect(&Url::parse(url).unwrap()).map(|(vcs, repository)| (vcs, repository.to_string()))
+    }
+
+    #[test]
+    /// the repository directory is found anywhere in the path, without the rest of the url
+    fn detect_finds_repository_directory() {
+        assert_eq!(
+            detect_str("http://localhost/.git/HEAD"),
+            Some((Vcs::Git, String::from("http://localhost/.git/")))
+        );
+        assert_eq!(
+            detect_str("http://localhost/app/.svn/entries?x=1"),
+            Some((Vcs::Svn, String::from("http://localhost/app/.svn/")))
+        );
+        assert_eq!(
+            detect_str("http://localhost/.hg"),
+            Some((Vcs::Hg, String::from("http://localhost/.hg/")))
+        );
+        assert_eq!(detect_str("http://localhost/.gitignore"), None);
+        assert_eq!(detect_str("http://localhost/git/HEAD"), None);
+    }
+
+    #[test]
+    /// HEAD files point at a branch or a commit; anything else (a catch-all page) doesn't count
+    fn parse_git_head_accepts_refs_and_ids() {
+        assert_eq!(
+            parse_git_head("ref: refs/heads/main\n"),
+            Some("refs/heads/main")
+        );
+        assert_eq!(
+            parse_git_head("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b\n"),
+            Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b")
+        );
+        assert_eq!(parse_git_head("<html>not found</html>"), None);
+        assert_eq!(parse_git_head("ref: nothing"), None);
+    }
+
+    #[test]
+    /// a git config needs a [core] section; its remote is picked up when there is one
+    fn parse_git_config_finds_remote() {
+        let config = "[core]\n\trepositoryformatversion = 0\n[remote \"origin\"]\n\turl = https://github.com/example/site.git\n";
+        assert_eq!(
+            parse_git_config(config).as_deref(),
+            Some("https://github.com/example/site.git")
+        );
+        assert_eq!(
+            parse_git_config("[core]\n\tbare = false\n").as_deref(),
+            Some("")
+        );
+        assert_eq!(parse_git_config("<html>[core]</html>"), None);
+    }
+
+    #[test]
+    /// svn and mercurial metadata is told apart from catch-all pages
+    fn svn_and_hg_metadata_are_recognized() {
+        assert!(is_svn_metadata(b"SQLite format 3\0rest", "wc.db"));
+        assert!(!is_svn_metadata(b"<html></html>", "wc.db"));
+        assert!(is_svn_metadata(b"10\n\ndir\n", "entries"));
+        assert!(!is_svn_metadata(b"<html></html>", "entries"));
+
+        assert!(is_hg_requires("dotencode\nfncache\nrevlogv1\nstore\n"));
+        assert!(!is_hg_requires("<html>store</html>"));
+    }
+
+    #[test]
+    /// the finding serializes with its type and severity
+    fn vcs_info_serializes_with_type() {
+        let info = VcsInfo {
+            kind: String::from("vcs"),
+            vcs: Vcs::Git,
+            repository: String::from("http://localhost/.git/"),
+            url: String::from("http://localhost/.git/HEAD"),
+            severity: Severity::High,
+            tags: vec![String::from(VCS_TAG), String::from("git")],
+            evidence: vec![String::from("HEAD: refs/heads/main")],
+        };
+
+        let json = info.as_json().unwrap();
+        assert!(json.starts_with(r#"{"type":"vcs","vcs":"git""#));
+        assert!(json.contains(r#""severity":"high""#));
+        assert!(info.as_str().contains("HEAD: refs/heads/main"));
+    }
+}
@@ -0,0 +1,520 @@
+//! where the wordlist comes from; every source streams its lines through the same cleanup, so a
+//! new kind of source only needs to implement `WordlistSource`
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream},
+    FutureExt, StreamExt,
+};
+use reqwest::Client;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio_util::io::StreamReader;
+
+use crate::{
+    config::Configuration, plugins::Plugin, utils::fmt_err, DEFAULT_WORDLIST, SECONDARY_WORDLIST,
+};
+
+/// -w value that reads the wordlist from STDIN
+pub const STDIN: &str = "-";
+
+/// prefix of -w values naming a lua script that generates the wordlist, i.e. `plugin:gen.lua`
+pub const PLUGIN_PREFIX: &str = "plugin:";
+
+/// prefix of -w values naming a wordlist built into feroxbuster, i.e. `builtin:common`
+pub const BUILTIN_PREFIX: &str = "builtin:";
+
+/// wordlists built into the binary, by name
+const BUILTINS: [(&str, &str); 2] = [
+    ("common", include_str!("wordlists/common.txt")),
+    ("api", include_str!("wordlists/api.txt")),
+];
+
+/// lines of a wordlist, handed over as they're read
+pub type WordStream = BoxStream<'static, Result<String>>;
+
+/// Somewhere a wordlist can be read from
+pub trait WordlistSource: Send + Sync {
+    /// what the words are read from, i.e. a path or url
+    fn name(&self) -> &str;
+
+    /// start reading; the returned stream's `size_hint` is exact when the source knows how many
+    /// words it holds up front
+    fn open(&self) -> BoxFuture<'_, Result<WordStream>>;
+}
+
+/// Wordlist on disk
+#[derive(Debug)]
+pub struct FileSource {
+    /// path to the wordlist
+    path: String,
+}
+
+impl WordlistSource for FileSource {
+    fn name(&self) -> &str {
+        &self.path
+    }
+
+    fn open(&self) -> BoxFuture<'_, Result<WordStream>> {
+        async move {
+            let file = tokio::fs::File::open(&self.path)
+                .await
+                .with_context(|| format!("Could not open {}", self.path))?;
+
+            Ok(lines(file))
+        }
+        .boxed()
+    }
+}
+
+/// Wordlist piped in on STDIN (`-w -`)
+#[derive(Debug)]
+pub struct StdinSource;
+
+impl WordlistSource for StdinSource {
+    fn name(&self) -> &str {
+        "STDIN"
+    }
+
+    fn open(&self) -> BoxFuture<'_, Result<WordStream>> {
+        async move { Ok(lines(tokio::io::stdin())) }.boxed()
+    }
+}
+
+/// Wordlist downloaded from a url; a copy is written to the current directory as it's read,
+/// named after the last segment of the url's path
+#[derive(Debug)]
+pub struct UrlSource {
+    /// url of the wordlist
+    url: String,
+
+    /// client used to download it
+    client: Client,
+}
+
+impl WordlistSource for UrlSource {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    fn open(&self) -> BoxFuture<'_, Result<WordStream>> {
+        async move {
+            let response = self.client.get(&self.url).send().await?;
+
+            if !response.status().is_success() {
+                // status code isn't a 200, bail
+                bail!(
+                    "[{}] Unable to download wordlist from url: {}",
+                    response.status().as_str(),
+                    self.url
+                );
+            }
+
+            // attempt to get the filename from the url's path
+            let Some(mut path_segments) = response.url().path_segments() else {
+                bail!("Unable to parse path from url: {}", response.url());
+            };
+
+            let Some(filename) = path_segments.next_back() else {
+                bail!(
+                    "Unable to parse filename from url's path: {}",
+                    response.url().path()
+                );
+            };
+
+            let mut copy = File::create(filename)
+                .with_context(|| fmt_err(&format!("Could not create {filename}")))?;
+
+            let chunks = stream::unfold(Some(response), |response| async move {
+                let mut response = response?;
+
+                match response.chunk().await {
+                    Ok(Some(chunk)) => Some((Ok(chunk), Some(response))),
+                    Ok(None) => None,
+                    Err(e) => Some((Err(io::Error::other(e)), None)),
+                }
+            })
+            .inspect(move |chunk| {
+                if let Ok(chunk) = chunk {
+                    copy.write_all(chunk).unwrap_or_else(|e| {
+                        log::warn!("Could not save a copy of the wordlist: {}", e)
+                    });
+                }
+            })
+            .fuse(); // StreamReader may ask for more after the last chunk
+
+            Ok(lines(StreamReader::new(Box::pin(chunks))))
+        }
+        .boxed()
+    }
+}
+
+/// Wordlist built into feroxbuster (`-w builtin:NAME`), for when there's none on disk
+#[derive(Debug)]
+pub struct BuiltinSource {
+    /// the -w value that picked it, i.e. `builtin:common`
+    name: String,
+
+    /// the wordlist itself
+    contents: &'static str,
+}
+
+impl BuiltinSource {
+    /// look up the built-in wordlist with the given name
+    pub fn new(name: &str) -> Result<Self> {
+        let Some((_, contents)) = BUILTINS.iter().find(|(builtin, _)| *builtin == name) else {
+            bail!(fmt_err(&format!(
+                "There's no built-in wordlist named {name} (choose from: {})",
+                BUILTINS
+                    .iter()
+                    .map(|(builtin, _)| *builtin)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        };
+
+        Ok(Self {
+            name: format!("{BUILTIN_PREFIX}{name}"),
+            contents,
+        })
+    }
+}
+
+impl WordlistSource for BuiltinSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn open(&self) -> BoxFuture<'_, Result<WordStream>> {
+        // comments are left out here so that the size hint is the number of words
+        let words: Vec<_> = self
+            .contents
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(|line| Ok(line.to_string()))
+            .collect();
+
+        async move { Ok(stream::iter(words).boxed()) }.boxed()
+    }
+}
+
+/// Wordlist generated by a lua script's `wordlist()` function (`-w plugin:FILE`)
+#[derive(Debug)]
+pub struct GeneratorSource {
+    /// path to the script
+    path: String,
+
+    /// time the script has to load and generate its words (--plugin-timeout)
+    budget: Duration,
+}
+
+impl WordlistSource for GeneratorSource {
+    fn name(&self) -> &str {
+        &self.path
+    }
+
+    fn open(&self) -> BoxFuture<'_, Result<WordStream>> {
+        async move {
+            let words = Plugin::new(&self.path, self.budget)?.words()?;
+
+            Ok(stream::iter(words.into_iter().map(Ok)).boxed())
+        }
+        .boxed()
+    }
+}
+
+/// split what's read from the given reader into lines, the way `BufRead::lines` would; lines
+/// that aren't valid UTF-8 come through as errors, as does a failed read, which also ends the
+/// stream
+fn lines<R>(reader: R) -> WordStream
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    let segments = BufReader::new(reader).split(b'\n');
+
+    stream::unfold(Some(segments), |segments| async move {
+        let mut segments = segments?;
+
+        match segments.next_segment().await {
+            Ok(Some(mut line)) => {
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+
+                Some((String::from_utf8(line).map_err(Into::into), Some(segments)))
+            }
+            Ok(None) => None,
+            Err(e) => Some((Err(e.into()), None)),
+        }
+    })
+    .boxed()
+}
+
+/// pick the source named by -w/--wordlist
+///
+/// - `-`: STDIN
+/// - `plugin:FILE`: a lua script's `wordlist()` function
+/// - `builtin:NAME`: one of the wordlists built into feroxbuster
+/// - anything starting with `http`: a url
+/// - anything else: a file; when it doesn't exist, the secondary wordlist location is tried
+pub fn source(config: &Configuration) -> Result<Box<dyn WordlistSource>> {
+    let wordlist = config.wordlist.as_str();
+
+    if wordlist == STDIN {
+        if config.stdin {
+            bail!(fmt_err(
+                "--stdin already reads targets from STDIN, the wordlist can't come from there too"
+            ));
+        }
+
+        if config.preview > 0 {
+            bail!(fmt_err(
+                "--preview reads its confirmation from STDIN, the wordlist can't come from there too"
+            ));
+        }
+
+        return Ok(Box::new(StdinSource));
+    }
+
+    if let Some(path) = wordlist.strip_prefix(PLUGIN_PREFIX) {
+        return Ok(Box::new(GeneratorSource {
+            path: path.to_string(),
+            budget: Duration::from_millis(config.plugin_timeout),
+        }));
+    }
+
+    if let Some(name) = wordlist.strip_prefix(BUILTIN_PREFIX) {
+        return Ok(Box::new(BuiltinSource::new(name)?));
+    }
+
+    if wordlist.starts_with("http") {
+        // found a url scheme, the wordlist will need to be downloaded
+        return Ok(Box::new(UrlSource {
+            url: wordlist.to_string(),
+            client: config.client.clone(),
+        }));
+    }
+
+    if !Path::new(wordlist).exists() && Path::new(SECONDARY_WORDLIST).exists() {
+        eprintln!("Found wordlist in secondary location");
+
+        return Ok(Box::new(FileSource {
+            path: SECONDARY_WORDLIST.to_string(),
+        }));
+    }
+
+    Ok(Box::new(FileSource {
+        path: wordlist.to_string(),
+    }))
+}
+
+/// read every word from the given source; comments and blank lines are skipped, and leading
+/// forward-slashes are trimmed (i.e. /word -> word)
+pub async fn read(source: &dyn WordlistSource) -> Result<Arc<Vec<String>>> {
+    log::trace!("enter: read({})", source.name());
+
+    let mut lines = source.open().await?;
+
+    let capacity = match lines.size_hint() {
+        (lower, Some(upper)) if lower == upper => {
+            log::info!("Reading {} words from {}", upper, source.name());
+            upper
+        }
+        (lower, _) => {
+            log::info!("Reading an unknown number of words from {}", source.name());
+            lower
+        }
+    };
+
+    // this empty string ensures that we call Requester::request with the base url, i.e.
+    // `http://localhost/` instead of going straight into `http://localhost/WORD.EXT`.
+    // for vanilla scans, it doesn't matter all that much, but it can be a significant difference
+    // when `-e` is used, depending on the content at the base url.
+    let mut words = Vec::with_capacity(capacity + 1);
+    words.push(String::new());
+
+    let mut trimmed_word = false;
+
+    while let Some(line) = lines.next().await {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                log::warn!("Skipped a line of {}: {}", source.name(), e);
+                continue;
+            }
+        };
+
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('/') {
+            words.push(line.trim_start_matches('/').to_string());
+            trimmed_word = true;
+        } else {
+            words.push(line);
+        }
+    }
+
+    if trimmed_word {
+        log::warn!("Some words in the wordlist started with a leading forward-slash; those words were trimmed (i.e. /word -> word)");
+    }
+
+    log::trace!("exit: read -> Arc<wordlist[{} words...]>", words.len());
+
+    Ok(Arc::new(words))
+}
+
+/// read the wordlist named by -w/--wordlist
+pub async fn load(config: &Configuration) -> Result<Arc<Vec<String>>> {
+    let words = read(source(config)?.as_ref()).await;
+
+    if words.is_err() && config.wordlist == DEFAULT_WORDLIST {
+        // no -w given and seclists isn't where it's expected, point toward the built-in lists
+        bail!(
+            "Could not open {} (-w {BUILTIN_PREFIX}common uses a wordlist built into feroxbuster)",
+            config.wordlist
+        );
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    /// write the given contents to a temporary file
+    fn temp_file(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{contents}").unwrap();
+        file
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// comments and blank lines should be skipped, leading slashes and carriage returns trimmed,
+    /// and the base url's empty word put first
+    async fn read_cleans_up_file_source() {
+        let file = temp_file("# comment\nadmin\r\n\n/api\nlogin");
+        let source = FileSource {
+            path: file.path().to_str().unwrap().to_string(),
+        };
+
+        let words = read(&source).await.unwrap();
+
+        assert_eq!(*words, vec!["", "admin", "api", "login"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// lines that aren't valid UTF-8 should be skipped, not end the wordlist
+    async fn read_skips_invalid_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"admin\n\xff\xfe\nlogin\n").unwrap();
+
+        let source = FileSource {
+            path: file.path().to_str().unwrap().to_string(),
+        };
+
+        let words = read(&source).await.unwrap();
+
+        assert_eq!(*words, vec!["", "admin", "login"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// a missing file should be an error that names the file
+    async fn read_errors_on_missing_file() {
+        let source = FileSource {
+            path: "/definitely/doesnt/exist/wordlist.txt".to_string(),
+        };
+
+        let err = read(&source).await.unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("/definitely/doesnt/exist/wordlist.txt"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// generated wordlists should go through the same cleanup, and know their length up front
+    async fn generator_source_reads_plugin_words() {
+        let script = temp_file("function wordlist() return {'admin', '/api', '# skipped'} end");
+        let source = GeneratorSource {
+            path: script.path().to_str().unwrap().to_string(),
+            budget: Duration::ZERO,
+        };
+
+        assert_eq!(source.open().await.unwrap().size_hint(), (3, Some(3)));
+
+        let words = read(&source).await.unwrap();
+        assert_eq!(*words, vec!["", "admin", "api"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// a script without a wordlist() function can't be used as a wordlist
+    async fn generator_source_requires_wordlist_hook() {
+        let script = temp_file("function on_finding(r) end");
+        let source = GeneratorSource {
+            path: script.path().to_str().unwrap().to_string(),
+            budget: Duration::ZERO,
+        };
+
+        assert!(read(&source).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// every built-in wordlist should load, without its comments, and know its length up front
+    async fn builtin_source_reads_embedded_wordlists() {
+        for (name, contents) in BUILTINS {
+            let source = BuiltinSource::new(name).unwrap();
+            let (lower, upper) = source.open().await.unwrap().size_hint();
+
+            assert_eq!(Some(lower), upper);
+
+            let words = read(&source).await.unwrap();
+
+            assert_eq!(words.len(), lower + 1);
+            assert!(!words.iter().any(|word| word.starts_with('#')));
+            assert!(contents.lines().count() > lower);
+        }
+
+        assert!(BuiltinSource::new("missing").is_err());
+    }
+
+    #[test]
+    /// each kind of -w value should pick the matching source, and STDIN can't be shared with
+    /// --stdin or --preview
+    fn source_picks_by_wordlist_value() {
+        let mut config = Configuration {
+            wordlist: "plugin:gen.lua".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(source(&config).unwrap().name(), "gen.lua");
+
+        config.wordlist = "builtin:api".to_string();
+        assert_eq!(source(&config).unwrap().name(), "builtin:api");
+
+        config.wordlist = "https://localhost/words.txt".to_string();
+        assert_eq!(
+            source(&config).unwrap().name(),
+            "https://localhost/words.txt"
+        );
+
+        config.wordlist = STDIN.to_string();
+        assert_eq!(source(&config).unwrap().name(), "STDIN");
+
+        config.stdin = true;
+        assert!(source(&config).is_err());
+
+        config.stdin = false;
+        config.preview = 2;
+        assert!(source(&config).is_err());
+    }
+}
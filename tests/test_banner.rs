@@ -151,6 +151,37 @@ fn banner_prints_denied_urls() {
         );
 }
 
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + dont scan regex entries given with --dont-scan-regex
+fn banner_prints_dont_scan_regex() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--dont-scan-regex")
+        .arg("/(logout|delete)")
+        .arg("https?://.*/admin/.{1,3}$")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Don't Scan Regex"))
+                .and(predicate::str::contains("/(logout|delete)"))
+                .and(predicate::str::contains("https?://.*/admin/.{1,3}$"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
 /// expect to see all mandatory prints + multiple headers
@@ -605,14 +636,13 @@ fn banner_prints_add_slash() {
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + INFINITE recursion
-fn banner_prints_infinite_depth() {
+/// expect to see all mandatory prints + dual slash
+fn banner_prints_dual_slash() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--depth")
-        .arg("0")
+        .arg("--dual-slash")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -626,22 +656,21 @@ fn banner_prints_infinite_depth() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Recursion Depth"))
-                .and(predicate::str::contains("INFINITE"))
+                .and(predicate::str::contains("Dual Slash"))
+                .and(predicate::str::contains("true"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + recursion depth
-fn banner_prints_recursion_depth() {
+/// expect to see all mandatory prints + detect case
+fn banner_prints_detect_case() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--depth")
-        .arg("343214")
+        .arg("--detect-case")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -655,23 +684,24 @@ fn banner_prints_recursion_depth() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Recursion Depth"))
-                .and(predicate::str::contains("343214"))
+                .and(predicate::str::contains("Detect Case"))
+                .and(predicate::str::contains("true"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + server certs
-fn banner_prints_server_certs() {
+/// expect to see all mandatory prints + baseline
+fn banner_prints_baseline() {
+    let (tmp_dir, baseline) = setup_tmp_directory(&[], "last-scan.json").unwrap();
+
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--server-certs")
-        .arg("tests/mutual-auth/certs/server/server.crt.1")
-        .arg("tests/mutual-auth/certs/server/server.crt.2")
+        .arg("--baseline")
+        .arg(baseline.as_os_str())
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -685,25 +715,26 @@ fn banner_prints_server_certs() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Server Certificates"))
-                .and(predicate::str::contains("server.crt.1"))
-                .and(predicate::str::contains("server.crt.2"))
+                .and(predicate::str::contains("Baseline"))
+                .and(predicate::str::contains("last-scan.json"))
                 .and(predicate::str::contains("─┴─")),
         );
+
+    teardown_tmp_directory(tmp_dir);
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + server certs
-fn banner_prints_client_cert_and_key() {
+/// expect to see all mandatory prints + reused signatures
+fn banner_prints_reuse_signatures() {
+    let (tmp_dir, state) = setup_tmp_directory(&[], "last-scan.state").unwrap();
+
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--client-cert")
-        .arg("tests/mutual-auth/certs/client/client.crt")
-        .arg("--client-key")
-        .arg("tests/mutual-auth/certs/client/client.key")
+        .arg("--reuse-signatures")
+        .arg(state.as_os_str())
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -717,23 +748,24 @@ fn banner_prints_client_cert_and_key() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Client Certificate"))
-                .and(predicate::str::contains("Client Key"))
-                .and(predicate::str::contains("certs/client/client.crt"))
-                .and(predicate::str::contains("certs/client/client.key"))
+                .and(predicate::str::contains("Reused Signatures"))
+                .and(predicate::str::contains("last-scan.state"))
                 .and(predicate::str::contains("─┴─")),
         );
+
+    teardown_tmp_directory(tmp_dir);
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + no recursion
-fn banner_prints_no_recursion() {
+/// expect to see all mandatory prints + encode
+fn banner_prints_encode() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-n")
+        .arg("--encode")
+        .arg("double-url")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -747,39 +779,51 @@ fn banner_prints_no_recursion() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Do Not Recurse"))
-                .and(predicate::str::contains("true"))
+                .and(predicate::str::contains("Word Encoding"))
+                .and(predicate::str::contains("│ double-url"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see nothing
-fn banner_doesnt_print() {
+/// expect to see all mandatory prints + range probe
+fn banner_prints_range_probe() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-q")
+        .arg("--range-probe")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
         .success()
-        .stderr(predicate::str::contains(
-            "Could not open /definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676",
-        ));
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Range Probe"))
+                .and(predicate::str::contains("true"))
+                .and(predicate::str::contains("─┴─")),
+        );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + extract-links
-fn banner_prints_extract_links() {
+/// expect to see all mandatory prints + accept-encoding + decompress
+fn banner_prints_accept_encoding_and_decompress() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-e")
+        .arg("--accept-encoding")
+        .arg("gzip, deflate")
+        .arg("--decompress")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -793,22 +837,23 @@ fn banner_prints_extract_links() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Extract Links"))
-                .and(predicate::str::contains("true"))
+                .and(predicate::str::contains("Accept-Encoding"))
+                .and(predicate::str::contains("gzip, deflate"))
+                .and(predicate::str::contains("Decompress"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + scan-limit
-fn banner_prints_scan_limit() {
+/// expect to see all mandatory prints + INFINITE recursion
+fn banner_prints_infinite_depth() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-L")
-        .arg("4")
+        .arg("--depth")
+        .arg("0")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -822,22 +867,22 @@ fn banner_prints_scan_limit() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Concurrent Scan Limit"))
-                .and(predicate::str::contains("│ 4"))
+                .and(predicate::str::contains("Recursion Depth"))
+                .and(predicate::str::contains("INFINITE"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + filter-status
-fn banner_prints_filter_status() {
+/// expect to see all mandatory prints + recursion depth
+fn banner_prints_recursion_depth() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-C")
-        .arg("200")
+        .arg("--depth")
+        .arg("343214")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -848,25 +893,26 @@ fn banner_prints_filter_status() {
                 .and(predicate::str::contains("http://localhost"))
                 .and(predicate::str::contains("Threads"))
                 .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Status Code Filters"))
-                .and(predicate::str::contains("│ [200]"))
+                .and(predicate::str::contains("Recursion Depth"))
+                .and(predicate::str::contains("343214"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + json
-fn banner_prints_json() {
+/// expect to see all mandatory prints + server certs
+fn banner_prints_server_certs() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--json")
-        .arg("--output")
-        .arg("/dev/null")
+        .arg("--server-certs")
+        .arg("tests/mutual-auth/certs/server/server.crt.1")
+        .arg("tests/mutual-auth/certs/server/server.crt.2")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -880,22 +926,25 @@ fn banner_prints_json() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("JSON Output"))
-                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("Server Certificates"))
+                .and(predicate::str::contains("server.crt.1"))
+                .and(predicate::str::contains("server.crt.2"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + json
-fn banner_prints_debug_log() {
+/// expect to see all mandatory prints + server certs
+fn banner_prints_client_cert_and_key() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--debug-log")
-        .arg("/dev/null")
+        .arg("--client-cert")
+        .arg("tests/mutual-auth/certs/client/client.crt")
+        .arg("--client-key")
+        .arg("tests/mutual-auth/certs/client/client.key")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -909,22 +958,26 @@ fn banner_prints_debug_log() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Debugging Log"))
-                .and(predicate::str::contains("│ /dev/null"))
+                .and(predicate::str::contains("Client Certificate"))
+                .and(predicate::str::contains("Client Key"))
+                .and(predicate::str::contains("certs/client/client.crt"))
+                .and(predicate::str::contains("certs/client/client.key"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + regex filters
-fn banner_prints_filter_regex() {
+/// expect to see all mandatory prints + ca bundle and pinned certificates
+fn banner_prints_ca_bundle_and_pin_sha256() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--filter-regex")
-        .arg("^ignore me$")
+        .arg("--ca-bundle")
+        .arg("tests/mutual-auth/certs/server/ca.crt")
+        .arg("--pin-sha256")
+        .arg("9F:86:D0:81:88:4C:7D:65:9A:2F:EA:A0:C5:5A:D0:15:A3:BF:4F:1B:2B:0B:82:2C:D1:5D:6C:15:B0:F0:0A:08")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -933,27 +986,44 @@ fn banner_prints_filter_regex() {
             predicate::str::contains("─┬─")
                 .and(predicate::str::contains("Target Url"))
                 .and(predicate::str::contains("http://localhost"))
-                .and(predicate::str::contains("Threads"))
-                .and(predicate::str::contains("Wordlist"))
-                .and(predicate::str::contains("Status Codes"))
-                .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Regex Filter"))
-                .and(predicate::str::contains("│ ^ignore me$"))
+                .and(predicate::str::contains("CA Bundle"))
+                .and(predicate::str::contains("certs/server/ca.crt"))
+                .and(predicate::str::contains("Pinned Certificates"))
+                .and(predicate::str::contains(
+                    "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+                ))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
+#[test]
+/// a --pin-sha256 value that isn't a fingerprint should be rejected before anything else
+fn banner_rejects_invalid_pin_sha256() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--pin-sha256")
+        .arg("not-a-fingerprint")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--pin-sha256 expects a SHA-256 fingerprint",
+        ));
+}
+
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + time limit
-fn banner_prints_time_limit() {
+/// expect to see all mandatory prints + evasion profile
+fn banner_prints_evasion() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--time-limit")
-        .arg("10m")
+        .arg("--evasion")
+        .arg("moderate")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -961,28 +1031,45 @@ fn banner_prints_time_limit() {
         .stderr(
             predicate::str::contains("─┬─")
                 .and(predicate::str::contains("Target Url"))
-                .and(predicate::str::contains("http://localhost"))
-                .and(predicate::str::contains("Threads"))
-                .and(predicate::str::contains("Wordlist"))
-                .and(predicate::str::contains("Status Codes"))
-                .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Time Limit"))
-                .and(predicate::str::contains("│ 10m"))
+                .and(predicate::str::contains("Evasion Profile"))
+                .and(predicate::str::contains(
+                    "moderate (path-params, dup-slash)",
+                ))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
+#[test]
+/// an unknown --evasion profile should be reported
+fn banner_rejects_unknown_evasion() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--evasion")
+        .arg("stealthy")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "stealthy is not a known evasion profile or transform",
+        ));
+}
+
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + similarity filter
-fn banner_prints_similarity_filter() {
+/// expect to see all mandatory prints + 403 bypasses
+fn banner_prints_bypass_403() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--filter-similar-to")
-        .arg("https://somesite.com")
+        .arg("--bypass-403")
+        .arg("--bypass-limit")
+        .arg("5")
+        .arg("--bypass-rate")
+        .arg("2")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -990,28 +1077,25 @@ fn banner_prints_similarity_filter() {
         .stderr(
             predicate::str::contains("─┬─")
                 .and(predicate::str::contains("Target Url"))
-                .and(predicate::str::contains("http://localhost"))
-                .and(predicate::str::contains("Threads"))
-                .and(predicate::str::contains("Wordlist"))
-                .and(predicate::str::contains("Status Codes"))
-                .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Similarity Filter"))
-                .and(predicate::str::contains("│ https://somesite.com"))
+                .and(predicate::str::contains("403 Bypasses"))
+                .and(predicate::str::contains("up to 5 urls, 2 req/s"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + rate limit
-fn banner_prints_rate_limit() {
+/// expect to see all mandatory prints + favicon hashing
+fn banner_prints_favicon() -> Result<(), Box<dyn std::error::Error>> {
+    let (tmp_dir, db) = setup_tmp_directory(&["81586312,Jenkins".to_string()], "favicons")?;
+
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--rate-limit")
-        .arg("6735")
+        .arg("--favicon")
+        .arg("--favicon-db")
+        .arg(db.as_os_str())
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1019,27 +1103,33 @@ fn banner_prints_rate_limit() {
         .stderr(
             predicate::str::contains("─┬─")
                 .and(predicate::str::contains("Target Url"))
-                .and(predicate::str::contains("http://localhost"))
-                .and(predicate::str::contains("Threads"))
-                .and(predicate::str::contains("Wordlist"))
-                .and(predicate::str::contains("Status Codes"))
-                .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Requests per Second"))
-                .and(predicate::str::contains("│ 6735"))
+                .and(predicate::str::contains("Favicon Hashes"))
+                .and(predicate::str::contains("Favicon Database"))
+                .and(predicate::str::contains("favicons"))
                 .and(predicate::str::contains("─┴─")),
         );
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + auto tune
-fn banner_prints_auto_tune() {
+/// expect to see all mandatory prints + screenshots
+fn banner_prints_screenshots() -> Result<(), Box<dyn std::error::Error>> {
+    let (tmp_dir, _) = setup_tmp_directory(&[], "wordlist")?;
+    let screenshots = tmp_dir.path().join("shots");
+
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--auto-tune")
+        .arg("--screenshots")
+        .arg(&screenshots)
+        .arg("--chrome")
+        .arg("/bin/true")
+        .arg("--screenshot-codes")
+        .arg("200,401")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1047,27 +1137,29 @@ fn banner_prints_auto_tune() {
         .stderr(
             predicate::str::contains("─┬─")
                 .and(predicate::str::contains("Target Url"))
-                .and(predicate::str::contains("http://localhost"))
-                .and(predicate::str::contains("Threads"))
-                .and(predicate::str::contains("Wordlist"))
-                .and(predicate::str::contains("Status Codes"))
-                .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Auto Tune"))
-                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("Screenshots"))
+                .and(predicate::str::contains("Screenshot Browser"))
+                .and(predicate::str::contains("/bin/true"))
+                .and(predicate::str::contains(
+                    "status [200, 401] and type [text/html]",
+                ))
                 .and(predicate::str::contains("─┴─")),
         );
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + auto bail
-fn banner_prints_auto_bail() {
+/// expect to see all mandatory prints + ports
+fn banner_prints_ports() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--auto-bail")
+        .arg("--ports")
+        .arg("80,443,8080")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1075,107 +1167,120 @@ fn banner_prints_auto_bail() {
         .stderr(
             predicate::str::contains("─┬─")
                 .and(predicate::str::contains("Target Url"))
-                .and(predicate::str::contains("http://localhost"))
-                .and(predicate::str::contains("Threads"))
-                .and(predicate::str::contains("Wordlist"))
-                .and(predicate::str::contains("Status Codes"))
-                .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Auto Bail"))
-                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("Ports"))
+                .and(predicate::str::contains("[80, 443, 8080]"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see no banner output
-fn banner_doesnt_print_when_silent() {
+/// expect to see all mandatory prints + scheme probes
+fn banner_prints_scheme_probes() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--silent")
+        .arg("--scheme-probes")
+        .arg("http:8080,https:8443")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
         .success()
         .stderr(
             predicate::str::contains("─┬─")
-                .not()
-                .and(predicate::str::contains("Target Url").not())
-                .and(predicate::str::contains("http://localhost").not())
-                .and(predicate::str::contains("Threads").not())
-                .and(predicate::str::contains("Wordlist").not())
-                .and(predicate::str::contains("Status Codes").not())
-                .and(predicate::str::contains("Timeout (secs)").not())
-                .and(predicate::str::contains("User-Agent").not()),
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Scheme Probes"))
+                .and(predicate::str::contains("[http:8080, https:8443]"))
+                .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see no banner output
-fn banner_doesnt_print_when_quiet() {
+/// expect to see all mandatory prints + no recursion
+fn banner_prints_no_recursion() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--quiet")
+        .arg("-n")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
         .success()
         .stderr(
             predicate::str::contains("─┬─")
-                .not()
-                .and(predicate::str::contains("Target Url").not())
-                .and(predicate::str::contains("http://localhost").not())
-                .and(predicate::str::contains("Threads").not())
-                .and(predicate::str::contains("Wordlist").not())
-                .and(predicate::str::contains("Status Codes").not())
-                .and(predicate::str::contains("Timeout (secs)").not())
-                .and(predicate::str::contains("User-Agent").not()),
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Do Not Recurse"))
+                .and(predicate::str::contains("true"))
+                .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see nothing as --parallel forces --silent to be true
-fn banner_prints_parallel() {
+/// expect to see nothing
+fn banner_doesnt_print() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
-        .arg("--stdin")
-        .arg("--parallel")
-        .arg("4316")
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("-q")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Could not open /definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676",
+        ));
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + extract-links
+fn banner_prints_extract_links() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("-e")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
         .success()
         .stderr(
             predicate::str::contains("─┬─")
-                .and(predicate::str::contains("Parallel Scans"))
-                .and(predicate::str::contains("4316"))
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
                 .and(predicate::str::contains("Threads"))
                 .and(predicate::str::contains("Wordlist"))
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
-                .and(predicate::str::contains("User-Agent")),
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Extract Links"))
+                .and(predicate::str::contains("true"))
+                .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + methods
-fn banner_prints_methods() {
+/// expect to see all mandatory prints + scan-limit
+fn banner_prints_scan_limit() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-m")
-        .arg("PUT")
-        .arg("--methods")
-        .arg("OPTIONS")
+        .arg("-L")
+        .arg("4")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1189,26 +1294,24 @@ fn banner_prints_methods() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("HTTP methods"))
-                .and(predicate::str::contains("[PUT, OPTIONS]"))
+                .and(predicate::str::contains("Concurrent Scan Limit"))
+                .and(predicate::str::contains("│ 4"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + data body
-fn banner_prints_data() {
+/// expect to see all mandatory prints + scan-limit + recursion strategy
+fn banner_prints_recursion_strategy() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("-m")
-        .arg("PUT")
-        .arg("--methods")
-        .arg("POST")
-        .arg("--data")
-        .arg("some_data")
+        .arg("-L")
+        .arg("4")
+        .arg("--recursion-strategy")
+        .arg("dfs")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1222,21 +1325,52 @@ fn banner_prints_data() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("HTTP Body"))
-                .and(predicate::str::contains("some_data"))
+                .and(predicate::str::contains("Concurrent Scan Limit"))
+                .and(predicate::str::contains("Recursion Strategy"))
+                .and(predicate::str::contains("│ dfs"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + ignored extensions
-fn banner_prints_collect_extensions_and_dont_collect_default() {
+/// expect to see all mandatory prints + filter-status
+fn banner_prints_filter_status() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--collect-extensions")
+        .arg("-C")
+        .arg("200")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Status Code Filters"))
+                .and(predicate::str::contains("│ [200]"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + json
+fn banner_prints_json() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--json")
+        .arg("--output")
+        .arg("/dev/null")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1250,25 +1384,21 @@ fn banner_prints_collect_extensions_and_dont_collect_default() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Collect Extensions"))
-                .and(predicate::str::contains("Ignored Extensions"))
-                .and(predicate::str::contains("Images, Movies, Audio, etc..."))
+                .and(predicate::str::contains("JSON Output"))
+                .and(predicate::str::contains("│ true"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect extensions
-fn banner_prints_collect_extensions_and_dont_collect_with_input() {
+/// expect to see all mandatory prints + punycode
+fn banner_prints_punycode() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--collect-extensions")
-        .arg("--dont-collect")
-        .arg("pdf")
-        .arg("xps")
+        .arg("--punycode")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1282,22 +1412,22 @@ fn banner_prints_collect_extensions_and_dont_collect_with_input() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Collect Extensions"))
-                .and(predicate::str::contains("Ignored Extensions"))
-                .and(predicate::str::contains("[pdf, xps]"))
+                .and(predicate::str::contains("Punycode Hosts"))
+                .and(predicate::str::contains("│ true"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect backups
-fn banner_prints_collect_backups() {
+/// expect to see all mandatory prints + json
+fn banner_prints_debug_log() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--collect-backups")
+        .arg("--debug-log")
+        .arg("/dev/null")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1311,20 +1441,56 @@ fn banner_prints_collect_backups() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Collect Backups"))
+                .and(predicate::str::contains("Debugging Log"))
+                .and(predicate::str::contains("│ /dev/null"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect words
-fn banner_prints_collect_words() {
+/// expect to see all mandatory prints + nmap import
+fn banner_prints_import_nmap() -> Result<(), Box<dyn std::error::Error>> {
+    let xml = vec![String::from(
+        r#"<host><address addr="127.0.0.1" addrtype="ipv4"/><ports><port protocol="tcp" portid="80"><state state="open"/></port></ports></host>"#,
+    )];
+    let (tmp_dir, file) = setup_tmp_directory(&xml, "scan.xml")?;
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--import-nmap")
+        .arg(file.as_os_str())
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Nmap Import"))
+                .and(predicate::str::contains("scan.xml"))
+                .and(predicate::str::contains("─┴─")),
+        );
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + burp export
+fn banner_prints_burp_export() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--collect-words")
+        .arg("--burp-export")
+        .arg("/dev/null")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1338,20 +1504,22 @@ fn banner_prints_collect_words() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Collect Words"))
+                .and(predicate::str::contains("Burp Export"))
+                .and(predicate::str::contains("│ /dev/null"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect words
-fn banner_prints_all_composite_settings_smart() {
+/// expect to see all mandatory prints + har export
+fn banner_prints_har_export() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--smart")
+        .arg("--har-export")
+        .arg("/dev/null")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1365,23 +1533,22 @@ fn banner_prints_all_composite_settings_smart() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Collect Words"))
-                .and(predicate::str::contains("Collect Backups"))
-                .and(predicate::str::contains("Extract Links"))
-                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("HAR Export"))
+                .and(predicate::str::contains("│ /dev/null"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect words
-fn banner_prints_all_composite_settings_thorough() {
+/// expect to see all mandatory prints + quiet stream
+fn banner_prints_quiet_stream() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--thorough")
+        .arg("--quiet-stream")
+        .arg("/dev/null")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1395,23 +1562,27 @@ fn banner_prints_all_composite_settings_thorough() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Collect Words"))
-                .and(predicate::str::contains("Collect Extensions"))
-                .and(predicate::str::contains("Collect Backups"))
-                .and(predicate::str::contains("Extract Links"))
-                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("Quiet Stream"))
+                .and(predicate::str::contains("│ /dev/null"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
+
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect words
-fn banner_prints_all_composite_settings_burp() {
+/// expect to see all mandatory prints + traffic log settings
+fn banner_prints_traffic_log() {
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--burp")
+        .arg("--traffic-log")
+        .arg(tmp_dir.path())
+        .arg("--traffic-log-max")
+        .arg("1G")
+        .arg("--traffic-log-bodies")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1425,21 +1596,27 @@ fn banner_prints_all_composite_settings_burp() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Proxy"))
-                .and(predicate::str::contains("Insecure"))
+                .and(predicate::str::contains("Traffic Log"))
+                .and(predicate::str::contains("Traffic Log Max Size"))
+                .and(predicate::str::contains("│ 1G"))
+                .and(predicate::str::contains("Traffic Log Bodies"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + collect words
-fn banner_prints_all_composite_settings_burp_replay() {
+/// expect to see all mandatory prints + policy audit
+fn banner_prints_policy_audit() {
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    let audit = tmp_dir.path().join("policy-audit.jsonl");
+
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--burp-replay")
+        .arg("--policy-audit")
+        .arg(&audit)
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1453,21 +1630,23 @@ fn banner_prints_all_composite_settings_burp_replay() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Replay Proxy"))
-                .and(predicate::str::contains("Insecure"))
+                .and(predicate::str::contains("Policy Audit"))
+                .and(predicate::str::contains("policy-audit.jsonl"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + force recursion
-fn banner_prints_force_recursion() {
+/// expect to see all mandatory prints + prune extensions
+fn banner_prints_prune_extensions() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
         .arg("--url")
         .arg("http://localhost")
-        .arg("--force-recursion")
+        .arg("--prune-extensions")
+        .arg("--prune-after")
+        .arg("100")
         .arg("--wordlist")
         .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
@@ -1481,19 +1660,1980 @@ fn banner_prints_force_recursion() {
                 .and(predicate::str::contains("Status Codes"))
                 .and(predicate::str::contains("Timeout (secs)"))
                 .and(predicate::str::contains("User-Agent"))
-                .and(predicate::str::contains("Force Recursion"))
+                .and(predicate::str::contains("Prune Extensions After (reqs)"))
+                .and(predicate::str::contains("│ 100"))
                 .and(predicate::str::contains("─┴─")),
         );
 }
 
 #[test]
 /// test allows non-existent wordlist to trigger the banner printing to stderr
-/// expect to see all mandatory prints + force recursion
-fn banner_prints_update_app() {
+/// expect to see all mandatory prints + pipe to
+fn banner_prints_pipe_to() {
     Command::cargo_bin("feroxbuster")
         .unwrap()
-        .arg("--update")
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--pipe-to")
+        .arg("cat > /dev/null")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
         .assert()
         .success()
-        .stdout(predicate::str::contains("Checking target-arch..."));
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Pipe To"))
+                .and(predicate::str::contains("│ cat > /dev/null"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + plugins
+fn banner_prints_plugins() {
+    let (tmp_dir, plugin) =
+        setup_tmp_directory(&["-- no hooks".to_string()], "plugin.lua").unwrap();
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--plugin")
+        .arg(plugin.as_os_str())
+        .arg("--plugin-timeout")
+        .arg("250")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Plugin"))
+                .and(predicate::str::contains("plugin.lua"))
+                .and(predicate::str::contains("Plugin Timeout (ms)"))
+                .and(predicate::str::contains("│ 250"))
+                .and(predicate::str::contains("─┴─")),
+        );
+
+    teardown_tmp_directory(tmp_dir);
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + host header + sni
+fn banner_prints_host_header_and_sni() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--host-header")
+        .arg("internal.app")
+        .arg("--sni")
+        .arg("cdn.internal.app")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Host Header"))
+                .and(predicate::str::contains("│ internal.app"))
+                .and(predicate::str::contains("TLS SNI"))
+                .and(predicate::str::contains("│ cdn.internal.app"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + auto resume
+fn banner_prints_auto_resume() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-resume")
+        .arg("30")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Auto Resume (secs)"))
+                .and(predicate::str::contains("│ 30"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + canary and its interval
+fn banner_prints_canary() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--canary")
+        .arg("/health")
+        .arg("--canary-interval")
+        .arg("45")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Canary"))
+                .and(predicate::str::contains("│ /health"))
+                .and(predicate::str::contains("Canary Interval (secs)"))
+                .and(predicate::str::contains("│ 45"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + server change alerts
+fn banner_prints_server_alerts() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--server-alerts")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Server Change Alerts"))
+                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + regex filters
+fn banner_prints_filter_regex() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--filter-regex")
+        .arg("^ignore me$")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Regex Filter"))
+                .and(predicate::str::contains("│ ^ignore me$"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + time limit
+fn banner_prints_time_limit() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--time-limit")
+        .arg("10m")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Time Limit"))
+                .and(predicate::str::contains("│ 10m"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + similarity filter
+fn banner_prints_similarity_filter() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--filter-similar-to")
+        .arg("https://somesite.com")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Similarity Filter"))
+                .and(predicate::str::contains("│ https://somesite.com"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + rate limit
+fn banner_prints_rate_limit() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--rate-limit")
+        .arg("6735")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Requests per Second"))
+                .and(predicate::str::contains("│ 6735"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + global rate limit
+fn banner_prints_bandwidth_limit() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--bandwidth-limit")
+        .arg("768K")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Bandwidth Limit (per sec)"))
+                .and(predicate::str::contains("│ 768K"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + global rate limit
+fn banner_prints_rate_limit_global() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--rate-limit-global")
+        .arg("4821")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Requests per Second (all scans)"))
+                .and(predicate::str::contains("│ 4821"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + auto tune
+fn banner_prints_auto_tune() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-tune")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + auto tune + tune strategy
+fn banner_prints_tune_strategy() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-tune")
+        .arg("--tune-strategy")
+        .arg("gradient")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("Tune Strategy"))
+                .and(predicate::str::contains("│ gradient"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + aimd tuning bounds
+fn banner_prints_aimd_bounds() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-tune")
+        .arg("--tune-strategy")
+        .arg("aimd")
+        .arg("--tune-step")
+        .arg("15")
+        .arg("--tune-floor")
+        .arg("20")
+        .arg("--tune-ceiling")
+        .arg("300")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("Tune Strategy"))
+                .and(predicate::str::contains("│ aimd"))
+                .and(predicate::str::contains("Tune Step"))
+                .and(predicate::str::contains("│ 15"))
+                .and(predicate::str::contains("Tune Floor"))
+                .and(predicate::str::contains("│ 20"))
+                .and(predicate::str::contains("Tune Ceiling"))
+                .and(predicate::str::contains("│ 300"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + tune on latency
+fn banner_prints_tune_on_latency() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-tune")
+        .arg("--tune-on-latency")
+        .arg("2x-baseline")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("Tune On Latency"))
+                .and(predicate::str::contains("│ 2x-baseline"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + policy thresholds
+fn banner_prints_policy_thresholds() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-bail")
+        .arg("--policy-403-ratio")
+        .arg("0.99")
+        .arg("--policy-429-ratio")
+        .arg("0.15")
+        .arg("--policy-error-threshold")
+        .arg("75")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Auto Bail"))
+                .and(predicate::str::contains("Policy 403 Ratio"))
+                .and(predicate::str::contains("│ 0.99"))
+                .and(predicate::str::contains("Policy 429 Ratio"))
+                .and(predicate::str::contains("│ 0.15"))
+                .and(predicate::str::contains("Policy Error Threshold"))
+                .and(predicate::str::contains("│ 75"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + policy status codes
+fn banner_prints_policy_status() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-tune")
+        .arg("--policy-status")
+        .arg("503,520")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("Policy Status Codes"))
+                .and(predicate::str::contains("503"))
+                .and(predicate::str::contains("520"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + policy error weights
+fn banner_prints_policy_error_weights() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-bail")
+        .arg("--policy-error-weight")
+        .arg("tls=0,timeout=3")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Auto Bail"))
+                .and(predicate::str::contains("Policy Error Weights"))
+                .and(predicate::str::contains("[timeout=3, tls=0]"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + auto requeue
+fn banner_prints_auto_requeue() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-requeue")
+        .arg("3")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Auto Requeue (attempts)"))
+                .and(predicate::str::contains("│ 3"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + auto bail
+fn banner_prints_auto_bail() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--auto-bail")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Auto Bail"))
+                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see no banner output
+fn banner_doesnt_print_when_silent() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--silent")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .not()
+                .and(predicate::str::contains("Target Url").not())
+                .and(predicate::str::contains("http://localhost").not())
+                .and(predicate::str::contains("Threads").not())
+                .and(predicate::str::contains("Wordlist").not())
+                .and(predicate::str::contains("Status Codes").not())
+                .and(predicate::str::contains("Timeout (secs)").not())
+                .and(predicate::str::contains("User-Agent").not()),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see no banner output
+fn banner_doesnt_print_when_quiet() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--quiet")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .not()
+                .and(predicate::str::contains("Target Url").not())
+                .and(predicate::str::contains("http://localhost").not())
+                .and(predicate::str::contains("Threads").not())
+                .and(predicate::str::contains("Wordlist").not())
+                .and(predicate::str::contains("Status Codes").not())
+                .and(predicate::str::contains("Timeout (secs)").not())
+                .and(predicate::str::contains("User-Agent").not()),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see nothing as --parallel forces --silent to be true
+fn banner_prints_parallel() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--stdin")
+        .arg("--parallel")
+        .arg("4316")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Parallel Scans"))
+                .and(predicate::str::contains("4316"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + methods
+fn banner_prints_methods() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("-m")
+        .arg("PUT")
+        .arg("--methods")
+        .arg("OPTIONS")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("HTTP methods"))
+                .and(predicate::str::contains("[PUT, OPTIONS]"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + data body
+fn banner_prints_data() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("-m")
+        .arg("PUT")
+        .arg("--methods")
+        .arg("POST")
+        .arg("--data")
+        .arg("some_data")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("HTTP Body"))
+                .and(predicate::str::contains("some_data"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + ignored extensions
+fn banner_prints_collect_extensions_and_dont_collect_default() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--collect-extensions")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Collect Extensions"))
+                .and(predicate::str::contains("Ignored Extensions"))
+                .and(predicate::str::contains("Images, Movies, Audio, etc..."))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect extensions
+fn banner_prints_collect_extensions_and_dont_collect_with_input() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--collect-extensions")
+        .arg("--dont-collect")
+        .arg("pdf")
+        .arg("xps")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Collect Extensions"))
+                .and(predicate::str::contains("Ignored Extensions"))
+                .and(predicate::str::contains("[pdf, xps]"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect backups
+fn banner_prints_collect_backups() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--collect-backups")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Collect Backups"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect words
+fn banner_prints_collect_words() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--collect-words")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Collect Words"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect words
+fn banner_prints_all_composite_settings_smart() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--smart")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Collect Words"))
+                .and(predicate::str::contains("Collect Backups"))
+                .and(predicate::str::contains("Extract Links"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect words
+fn banner_prints_all_composite_settings_thorough() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--thorough")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Collect Words"))
+                .and(predicate::str::contains("Collect Extensions"))
+                .and(predicate::str::contains("Collect Backups"))
+                .and(predicate::str::contains("Extract Links"))
+                .and(predicate::str::contains("Auto Tune"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect words
+fn banner_prints_all_composite_settings_burp() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--burp")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Proxy"))
+                .and(predicate::str::contains("Insecure"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + collect words
+fn banner_prints_all_composite_settings_burp_replay() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--burp-replay")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Replay Proxy"))
+                .and(predicate::str::contains("Insecure"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + force recursion
+fn banner_prints_force_recursion() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--force-recursion")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Force Recursion"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + directories first
+fn banner_prints_directories_first() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--directories-first")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Directories First"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + safe methods only
+fn banner_prints_safe_methods_only() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--safe-methods-only")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Safe Methods Only"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + inherited headers
+fn banner_prints_inherit_headers() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--inherit-headers")
+        .arg("Set-Cookie,X-CSRF-Token")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Inherited Headers"))
+                .and(predicate::str::contains("Set-Cookie, X-CSRF-Token"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + force recursion
+fn banner_prints_update_app() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--update")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checking target-arch..."));
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + fuzz wordlists and mode
+fn banner_prints_fuzz_wordlists() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost/FUZZ")
+        .arg("--fuzz-wordlist")
+        .arg("FUZZ2=/some/users")
+        .arg("--fuzz-mode")
+        .arg("pitchfork")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Fuzz Wordlists"))
+                .and(predicate::str::contains("FUZZ2=/some/users (pitchfork)"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + fuzzy hash bodies
+fn banner_prints_fuzzy_hash() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--fuzzy-hash")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Fuzzy Hash Bodies"))
+                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + page titles
+fn banner_prints_titles() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--titles")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Page Titles"))
+                .and(predicate::str::contains("│ true"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + extension order
+fn banner_prints_extension_order() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("-x")
+        .arg("php")
+        .arg("--extension-order")
+        .arg("interleaved")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Extensions"))
+                .and(predicate::str::contains("Extension Order"))
+                .and(predicate::str::contains("│ interleaved"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + the given shuffle seed
+fn banner_prints_shuffle_seed() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--shuffle")
+        .arg("1337")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Shuffle Seed"))
+                .and(predicate::str::contains("│ 1337"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + preview requests
+fn banner_prints_preview() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--preview")
+        .arg("5")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Preview Requests"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + a randomly picked shuffle seed
+fn banner_prints_random_shuffle_seed() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--shuffle")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Shuffle Seed"))
+                .and(predicate::str::contains("│ 0\n").not())
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + pacing
+fn banner_prints_pacing() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--pacing")
+        .arg("low-and-slow")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Pacing"))
+                .and(predicate::str::contains("│ low-and-slow"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// an unknown pacing profile is rejected by the parser
+fn banner_rejects_unknown_pacing_profile() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--pacing")
+        .arg("sneaky")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("low-and-slow"));
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + the extraction limits that were set
+fn banner_prints_extraction_limits() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--extract-page-limit")
+        .arg("25")
+        .arg("--extract-depth")
+        .arg("2")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Extract Links"))
+                .and(predicate::str::contains("Extraction Limits"))
+                .and(predicate::str::contains("│ page: 25, depth: 2"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + crawl
+fn banner_prints_crawl() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--crawl")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Extract Links"))
+                .and(predicate::str::contains("Crawl (no wordlist)"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + well-known
+fn banner_prints_well_known() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--well-known")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Well-Known URIs"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + bucket listing
+fn banner_prints_bucket_listing() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--bucket-listing")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Bucket Listing"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + categorize, category rules, and category filters
+fn banner_prints_categories() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--categorize")
+        .arg("--category-rule")
+        .arg("login-page:/login")
+        .arg("--filter-category")
+        .arg("backup-file")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Categorize"))
+                .and(predicate::str::contains("Category Rule"))
+                .and(predicate::str::contains("login-page:/login"))
+                .and(predicate::str::contains("Category Filter"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + rules
+fn banner_prints_rules() {
+    let (tmp_dir, rules) = setup_tmp_directory(
+        &["[[rule]]\nname = \"secret\"\nbody = \"password\"".to_string()],
+        "rules.toml",
+    )
+    .unwrap();
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--rules")
+        .arg(rules.as_os_str())
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Rules"))
+                .and(predicate::str::contains("rules.toml"))
+                .and(predicate::str::contains("─┴─")),
+        );
+
+    teardown_tmp_directory(tmp_dir);
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + api export settings
+fn banner_prints_api_export() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--api-export")
+        .arg("https://dojo.example.com/api/v2/import-scan/")
+        .arg("--api-export-format")
+        .arg("dojo")
+        .arg("--dojo-engagement")
+        .arg("42")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("API Export"))
+                .and(predicate::str::contains("API Export Format"))
+                .and(predicate::str::contains("API Batch Size"))
+                .and(predicate::str::contains("DefectDojo Engagement"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + junit report and fail on rule
+fn banner_prints_junit_and_fail_on_rule() {
+    let (tmp_dir, rules) = setup_tmp_directory(
+        &["[[rule]]\nname = \"git\"\nurl = \"/\\\\.git/\"\nforbidden = true".to_string()],
+        "rules.toml",
+    )
+    .unwrap();
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--rules")
+        .arg(rules.as_os_str())
+        .arg("--junit")
+        .arg("ferox-junit.xml")
+        .arg("--fail-on-rule")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("JUnit Report"))
+                .and(predicate::str::contains("ferox-junit.xml"))
+                .and(predicate::str::contains("Fail On Rule"))
+                .and(predicate::str::contains("─┴─")),
+        );
+
+    teardown_tmp_directory(tmp_dir);
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + exit codes and exit error ratio
+fn banner_prints_exit_codes() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--exit-code")
+        .arg("bail=4,errors=3")
+        .arg("--exit-error-ratio")
+        .arg("0.25")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Exit Codes"))
+                .and(predicate::str::contains("[bail=4, errors=3]"))
+                .and(predicate::str::contains("Exit Error Ratio"))
+                .and(predicate::str::contains("0.25"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + summary
+fn banner_prints_summary() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--summary")
+        .arg("ferox-summary.json")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Summary"))
+                .and(predicate::str::contains("ferox-summary.json"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + session recording
+fn banner_prints_record() {
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    let session = tmp_dir.path().join("ferox-session.bin");
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--record")
+        .arg(session.as_os_str())
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Session Recording"))
+                .and(predicate::str::contains("ferox-session.bin"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + proxy failover
+fn banner_prints_proxy_failover() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--proxy")
+        .arg("http://127.0.0.1:8080")
+        .arg("--proxy-failover")
+        .arg("http://127.0.0.1:8082")
+        .arg("--proxy-failover-direct")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Proxy Failover"))
+                .and(predicate::str::contains("[http://127.0.0.1:8082]"))
+                .and(predicate::str::contains("Proxy Failover Direct"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + replay proxy headers and client certificate
+fn banner_prints_replay_headers_and_client_cert() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--replay-proxy")
+        .arg("http://127.0.0.1:8081")
+        .arg("--replay-headers")
+        .arg("X-Tag:ferox")
+        .arg("--replay-client-cert")
+        .arg("tests/mutual-auth/certs/client/client.crt")
+        .arg("--replay-client-key")
+        .arg("tests/mutual-auth/certs/client/client.key")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Replay Proxy"))
+                .and(predicate::str::contains("Replay Header"))
+                .and(predicate::str::contains("X-Tag: ferox"))
+                .and(predicate::str::contains("Replay Client Certificate"))
+                .and(predicate::str::contains("Replay Client Key"))
+                .and(predicate::str::contains(
+                    "tests/mutual-auth/certs/client/client.key",
+                ))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + request id header
+fn banner_prints_request_id() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--request-id")
+        .arg("X-Ferox-Scan-Id")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Request ID Header"))
+                .and(predicate::str::contains("X-Ferox-Scan-Id: "))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + connect and read timeouts
+fn banner_prints_connect_and_read_timeouts() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--connect-timeout")
+        .arg("2")
+        .arg("--read-timeout")
+        .arg("3")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Connect Timeout (secs)"))
+                .and(predicate::str::contains("Read Timeout (secs)"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + search regex
+fn banner_prints_search_regex() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--search-regex")
+        .arg("api[_-]?key")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Search Regex"))
+                .and(predicate::str::contains("api[_-]?key"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + entropy threshold
+fn banner_prints_entropy_threshold() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--entropy-secrets")
+        .arg("--entropy-threshold")
+        .arg("4.2")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Entropy Threshold (bits)"))
+                .and(predicate::str::contains("4.2"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + vcs exposure check
+fn banner_prints_vcs_check() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--vcs-check")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("VCS Exposure Check"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + archive listing
+fn banner_prints_archive_peek() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--archive-peek")
+        .arg("--archive-max-size")
+        .arg("2M")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Archive Listing"))
+                .and(predicate::str::contains("up to 2M"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + baseline url
+fn banner_prints_baseline_url() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--baseline-url")
+        .arg("http://localhost/home")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Baseline Url"))
+                .and(predicate::str::contains("http://localhost/home"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + dns-over-https endpoint
+fn banner_prints_doh() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--doh")
+        .arg("https://1.1.1.1/dns-query")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("DNS-over-HTTPS"))
+                .and(predicate::str::contains("https://1.1.1.1/dns-query"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + ip family
+fn banner_prints_ip_family() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("-4")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("IP Family"))
+                .and(predicate::str::contains("IPv4 only"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + ip fan-out
+fn banner_prints_fan_out_ips() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--fan-out-ips")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("IP Fan-out"))
+                .and(predicate::str::contains("true"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + stability check interval
+fn banner_prints_stability_check() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--stability-check")
+        .arg("--stability-interval")
+        .arg("90")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("Stability Check (secs)"))
+                .and(predicate::str::contains("│ 90"))
+                .and(predicate::str::contains("─┴─")),
+        );
+}
+
+#[test]
+/// test allows non-existent wordlist to trigger the banner printing to stderr
+/// expect to see all mandatory prints + raw requests
+fn banner_prints_raw_requests() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("http://localhost")
+        .arg("--raw-requests")
+        .arg("--wordlist")
+        .arg("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("─┬─")
+                .and(predicate::str::contains("Target Url"))
+                .and(predicate::str::contains("http://localhost"))
+                .and(predicate::str::contains("Threads"))
+                .and(predicate::str::contains("Wordlist"))
+                .and(predicate::str::contains("Status Codes"))
+                .and(predicate::str::contains("Timeout (secs)"))
+                .and(predicate::str::contains("User-Agent"))
+                .and(predicate::str::contains("Raw Requests"))
+                .and(predicate::str::contains("─┴─")),
+        );
 }
@@ -280,3 +280,65 @@ fn deny_list_prevents_url_that_denies_base_url() {
 
     assert_eq!(mock.hits(), 0);
 }
+
+#[test]
+/// test that --dont-scan-regex keeps matching urls from being requested, both from the wordlist
+/// and in directories found by recursion, and that patterns with commas aren't split apart
+fn dont_scan_regex_works_during_scan_and_recursion() {
+    let srv = MockServer::start();
+    let urls = [
+        "account".to_string(),
+        "logout".to_string(),
+        "delete".to_string(),
+        "profile".to_string(),
+    ];
+    let (tmp_dir, file) = setup_tmp_directory(&urls, "wordlist").unwrap();
+
+    let account_mock = srv.mock(|when, then| {
+        when.method(GET).path("/account");
+        then.status(301).header("Location", srv.url("/account/"));
+    });
+
+    let profile_mock = srv.mock(|when, then| {
+        when.method(GET).path("/account/profile");
+        then.status(200).body("this is a test");
+    });
+
+    let denied: Vec<_> = ["/logout", "/delete", "/account/logout", "/account/delete"]
+        .iter()
+        .map(|path| {
+            srv.mock(|when, then| {
+                when.method(GET).path(*path);
+                then.status(200).body("state changing");
+            })
+        })
+        .collect();
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("-t")
+        .arg("1")
+        .arg("--dont-scan-regex")
+        .arg("/logout$")
+        .arg("/d[e]{1,2}lete$")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/account/profile")
+            .and(predicate::str::contains("logout").not())
+            .and(predicate::str::contains("delete").not()),
+    );
+
+    assert_eq!(account_mock.hits(), 2);
+    assert_eq!(profile_mock.hits(), 1);
+
+    for mock in denied {
+        assert_eq!(mock.hits(), 0);
+    }
+
+    teardown_tmp_directory(tmp_dir);
+}
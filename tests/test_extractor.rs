@@ -640,3 +640,125 @@ fn extractor_recurses_into_403_directories() -> Result<(), Box<dyn std::error::E
     teardown_tmp_directory(tmp_dir);
     Ok(())
 }
+
+#[test]
+/// a page that "redirects" via meta refresh should be reported with its destination, and the
+/// destination should be requested like any other extracted link
+fn extractor_follows_meta_refresh() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["portal".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/portal");
+        then.status(200).body(
+            r#"<html><head><meta http-equiv="refresh" content="0; url=/legacy/login.php"></head></html>"#,
+        );
+    });
+
+    let mock_two = srv.mock(|when, then| {
+        when.method(GET).path("/legacy/login.php");
+        then.status(200).body("login");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--dont-filter")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains(format!(
+            "{} => {}",
+            srv.url("/portal"),
+            srv.url("/legacy/login.php")
+        ))
+        .and(predicate::str::contains("/legacy/login.php")),
+    );
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(mock_two.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --well-known requests the host's /.well-known/ resources, reports the ones that exist, and
+/// follows the endpoints inside of them that belong to the target's host
+fn extractor_follows_well_known_endpoints() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let openid = srv.mock(|when, then| {
+        when.method(GET).path("/.well-known/openid-configuration");
+        then.status(200).body(format!(
+            r#"{{"issuer":"{}","token_endpoint":"{}","jwks_uri":"https://elsewhere.example/keys"}}"#,
+            srv.url("/"),
+            srv.url("/oauth/token")
+        ));
+    });
+
+    let token = srv.mock(|when, then| {
+        when.method(GET).path("/oauth/token");
+        then.status(405).body("method not allowed");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--well-known")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::is_match("200.*/.well-known/openid-configuration")
+            .unwrap()
+            .and(predicate::str::is_match("405.*/oauth/token").unwrap())
+            .and(predicate::str::contains("security.txt").not()),
+    );
+
+    assert_eq!(openid.hits(), 1);
+    assert_eq!(token.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// links to cloud storage are reported as buckets instead of being dropped as out of scope
+fn extractor_reports_cloud_storage_buckets() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body(
+            r#"<a href="https://somebucket.s3.amazonaws.com/file.txt">file</a>
+               <img src="https://somebucket.s3.amazonaws.com/other.png">
+               <a href="https://example.com/not-a-bucket">nope</a>"#,
+        );
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::is_match("BKT s3 somebucket https://somebucket.s3.amazonaws.com/")
+            .unwrap()
+            .and(predicate::str::contains("example.com").not()),
+    );
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
@@ -247,3 +247,121 @@ fn filters_similar_should_filter_response() {
     assert_eq!(not_similar.hits(), 1);
     teardown_tmp_directory(tmp_dir);
 }
+
+#[test]
+/// the number of responses removed by each filter should be shown once the scan completes and
+/// saved along with the rest of the statistics
+fn filters_hits_are_reported_in_summary_and_statistics() {
+    let srv = MockServer::start();
+    let (tmp_dir, file) =
+        setup_tmp_directory(&["LICENSE".to_string(), "file.js".to_string()], "wordlist").unwrap();
+    let outfile = tmp_dir.path().join("output");
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(302).body("this is a test");
+    });
+
+    let mock_two = srv.mock(|when, then| {
+        when.method(GET).path("/file.js");
+        then.status(200)
+            .body("this is also a test of some import\nwith 2 lines, no less");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--filter-words")
+        .arg("13")
+        .arg("--json")
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .unwrap();
+
+    cmd.assert().success().stderr(
+        predicate::str::contains("responses removed by filters")
+            .and(predicate::str::contains("Word count: 13")),
+    );
+
+    let contents = std::fs::read_to_string(outfile).unwrap();
+    let stats = contents
+        .lines()
+        .find(|line| line.contains(r#""type":"statistics""#))
+        .unwrap();
+    let stats: serde_json::Value = serde_json::from_str(stats).unwrap();
+
+    assert_eq!(stats["filter_hits"]["Word count: 13"], 1);
+
+    // the auto-filter for 404-like responses may have removed things as well, but every
+    // filtered response should be attributed to some filter
+    let total: u64 = stats["filter_hits"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|hits| hits.as_u64().unwrap())
+        .sum();
+    assert_eq!(stats["responses_filtered"], total);
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(mock_two.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+}
+
+#[test]
+/// responses are tagged by the built-in and user category rules, and --filter-category hides
+/// the ones tagged with the given category
+fn filters_category_should_filter_response() {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(
+        &[
+            "index.php.bak".to_string(),
+            ".env".to_string(),
+            "signin".to_string(),
+        ],
+        "wordlist",
+    )
+    .unwrap();
+
+    let backup = srv.mock(|when, then| {
+        when.method(GET).path("/index.php.bak");
+        then.status(200).body("<?php echo 'hi'; ?>");
+    });
+
+    let env = srv.mock(|when, then| {
+        when.method(GET).path("/.env");
+        then.status(200).body("DB_PASSWORD=hunter2");
+    });
+
+    let signin = srv.mock(|when, then| {
+        when.method(GET).path("/signin");
+        then.status(200).body("please log in");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--categorize")
+        .arg("--category-rule")
+        .arg("login-page:/signin$")
+        .arg("--filter-category")
+        .arg("backup-file")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/index.php.bak")
+            .not()
+            .and(predicate::str::is_match(r"/\.env.*config-file").unwrap())
+            .and(predicate::str::is_match(r"/signin.*login-page").unwrap()),
+    );
+
+    assert_eq!(backup.hits(), 1);
+    assert_eq!(env.hits(), 1);
+    assert_eq!(signin.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+}
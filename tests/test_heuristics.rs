@@ -193,6 +193,50 @@ fn test_static_wildcard_request_found() -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+#[test]
+/// a previous run's 404 signature for the target is reused, so the wildcard probes are never sent
+/// but the same filter is still created
+fn heuristics_reuse_signatures_skips_wildcard_probes() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let state = format!(
+        r#"{{"version":3,"signatures":{{"{}":{{"wildcards":[{{"content_length":14,"line_count":1,"word_count":4,"method":"GET","status_code":200,"dont_filter":false}}],"similarities":[],"directories":[]}}}}}}"#,
+        srv.url("/")
+    );
+    let (state_dir, state_file) = setup_tmp_directory(&[state], "ferox.state")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET)
+            .path_matches(Regex::new("/[.a-zA-Z0-9]{32,}/").unwrap());
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--add-slash")
+        .arg("--reuse-signatures")
+        .arg(state_file.as_os_str())
+        .unwrap();
+
+    teardown_tmp_directory(tmp_dir);
+    teardown_tmp_directory(state_dir);
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("Auto-filtering found 404-like response and created new filter")
+            .and(predicate::str::contains("200"))
+            .and(predicate::str::contains("1l")),
+    );
+
+    assert_eq!(mock.hits(), 0);
+
+    Ok(())
+}
+
 #[test]
 /// uses dont_filter, so the normal wildcard test should never happen
 fn heuristics_static_wildcard_request_with_dont_filter() -> Result<(), Box<dyn std::error::Error>> {
@@ -490,3 +534,58 @@ fn heuristics_wildcard_test_that_auto_filtering_403s_still_allows_for_recursion_
 // }
 
 // todo figure out why ci hates these tests
+
+#[test]
+/// a target that answers every path with the same index page is detected as a single-page app;
+/// its catch-all page is filtered, the scripts it loads are requested, and api paths are
+/// requested ahead of the wordlist
+fn heuristics_detects_single_page_app() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let script = srv.mock(|when, then| {
+        when.method(GET).path("/static/main.js");
+        then.status(200)
+            .header("Content-Type", "application/javascript")
+            .body("fetch('/static/chunk.js');");
+    });
+
+    let api = srv.mock(|when, then| {
+        when.method(GET).path("/api/v1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{"version":1}"#);
+    });
+
+    // every other path is answered by the app's index page
+    let index = srv.mock(|when, then| {
+        when.method(GET)
+            .path_matches(Regex::new("^/(LICENSE|[a-f0-9]{32}(/[a-f0-9]{32})?)?$").unwrap());
+        then.status(200).body(
+            r#"<html><head><script src="/static/main.js"></script></head><body><div id="root"></div></body></html>"#,
+        );
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .unwrap();
+
+    teardown_tmp_directory(tmp_dir);
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("Single-page app")
+            .and(predicate::str::contains("/api/v1"))
+            .and(predicate::str::contains("/static/main.js"))
+            .and(predicate::str::contains("LICENSE").not()),
+    );
+
+    assert_eq!(script.hits(), 1);
+    assert_eq!(api.hits(), 1);
+    assert!(index.hits() >= 4);
+
+    Ok(())
+}
@@ -58,6 +58,82 @@ fn main_use_empty_wordlist() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+/// give a target without a scheme; https gets no answer from the mock server, so http should be
+/// picked, shown in the banner, and scanned
+fn main_detects_scheme_of_schemeless_target() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let target = srv.address().to_string();
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(&target)
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("Detected Scheme").and(predicate::str::contains(format!(
+                "{target} => http://{target}"
+            ))),
+        )
+        .stdout(predicate::str::contains(format!("http://{target}/LICENSE")));
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --ports should turn the host into a target on the mock server's port, whose scheme gets
+/// detected, and summarize the results by host
+fn main_ports_expands_targets() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let port = srv.address().port();
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg("127.0.0.1")
+        .arg("--ports")
+        .arg(port.to_string())
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("Ports")
+                .and(predicate::str::contains("Status codes by host"))
+                .and(predicate::str::contains(format!(
+                    "127.0.0.1 [http://127.0.0.1:{port}]"
+                ))),
+        )
+        .stdout(predicate::str::contains(format!(
+            "http://127.0.0.1:{port}/LICENSE"
+        )));
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
 #[test]
 /// send nothing over stdin, expect heuristics to be upset during connectivity test
 fn main_use_empty_stdin_targets() -> Result<(), Box<dyn std::error::Error>> {
@@ -149,6 +225,63 @@ fn main_parallel_spawns_children() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+/// --import-nmap=FILE with --parallel, expect the children to get a target via -u and not the
+/// xml file, in either of its forms
+fn main_parallel_strips_import_nmap() -> Result<(), Box<dyn std::error::Error>> {
+    let t1 = MockServer::start();
+    let t2 = MockServer::start();
+
+    let (word_tmp_dir, wordlist) = setup_tmp_directory(&[String::from("LICENSE")], "wordlist")?;
+    let (output_dir, outfile) = setup_tmp_directory(&[], "output-file")?;
+
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+<nmaprun scanner="nmap">
+<host><address addr="127.0.0.1" addrtype="ipv4"/><ports>
+<port protocol="tcp" portid="{}"><state state="open"/><service name="http"/></port>
+<port protocol="tcp" portid="{}"><state state="open"/><service name="http"/></port>
+</ports></host>
+</nmaprun>"#,
+        t1.port(),
+        t2.port()
+    );
+
+    let nmap_file = word_tmp_dir.path().join("scan.xml");
+    std::fs::write(&nmap_file, xml)?;
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg(format!("--import-nmap={}", nmap_file.display()))
+        .arg("--parallel")
+        .arg("2")
+        .arg("-vvvv")
+        .arg("--debug-log")
+        .arg(outfile.as_os_str())
+        .arg("--wordlist")
+        .arg(wordlist.as_os_str())
+        .assert()
+        .success();
+
+    let contents = read_to_string(outfile).unwrap();
+
+    let children: Vec<_> = contents
+        .lines()
+        .filter(|line| line.contains("parallel exec:"))
+        .collect();
+
+    assert_eq!(children.len(), 2);
+    assert!(children.iter().all(|child| !child.contains("import-nmap")));
+    assert!(children
+        .iter()
+        .any(|child| child.contains(&format!("-u http://127.0.0.1:{}", t1.port()))));
+
+    teardown_tmp_directory(word_tmp_dir);
+    teardown_tmp_directory(output_dir);
+
+    Ok(())
+}
+
 #[test]
 /// send three targets over stdin with --output enabled, expect parallel to create a new directory
 /// and the log files therein
@@ -261,3 +394,281 @@ fn main_download_wordlist_from_url() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+/// read the wordlist from stdin with `-w -`
+fn main_read_wordlist_from_stdin() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+
+    let (tmp_dir, file) =
+        setup_tmp_directory(&["stuff".to_string(), "things".to_string()], "wordlist")?;
+
+    let mock1 = srv.mock(|when, then| {
+        when.method(GET).path("/stuff");
+        then.status(200);
+    });
+
+    let mock2 = srv.mock(|when, then| {
+        when.method(GET).path("/things");
+        then.status(200);
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg("-")
+        .pipe_stdin(file)
+        .unwrap()
+        .assert()
+        .success();
+
+    teardown_tmp_directory(tmp_dir);
+
+    assert_eq!(mock1.hits(), 1);
+    assert_eq!(mock2.hits(), 1);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+/// send SIGTERM to a running scan and verify results gathered so far are flushed to disk
+fn main_sigterm_flushes_output_and_exports() -> Result<(), Box<dyn std::error::Error>> {
+    use std::process::Stdio;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    let srv = MockServer::start();
+    let mut words = vec![String::from("LICENSE")];
+    words.extend((0..500).map(|i| format!("word{i}")));
+    let (tmp_dir, file) = setup_tmp_directory(&words, "wordlist")?;
+
+    let found = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    // everything else hangs long enough for the signal to arrive mid-scan
+    srv.mock(|when, then| {
+        when.method(GET)
+            .path_matches(Regex::new("/word[0-9]+$").unwrap());
+        then.status(404).delay(Duration::new(5, 0));
+    });
+
+    let outfile = tmp_dir.path().join("output");
+    let harfile = tmp_dir.path().join("export.har");
+
+    let child = std::process::Command::new(assert_cmd::cargo::cargo_bin("feroxbuster"))
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--dont-filter")
+        .arg("--threads")
+        .arg("4")
+        .arg("--no-state")
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .arg("--har-export")
+        .arg(harfile.as_os_str())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    sleep(Duration::new(3, 0));
+
+    std::process::Command::new("kill")
+        .arg("-TERM")
+        .arg(child.id().to_string())
+        .status()?;
+
+    let output = child.wait_with_output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("Caught") && stdout.contains("SIGTERM"));
+    assert!(stdout.contains("resources discovered"));
+
+    assert!(read_to_string(&outfile)?.contains(&srv.url("/LICENSE")));
+
+    // the export should have been closed out, i.e. it's valid json
+    let har: serde_json::Value = serde_json::from_str(&read_to_string(&harfile)?)?;
+    assert!(har["log"]["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|entry| entry["request"]["url"] == srv.url("/LICENSE")));
+
+    assert_eq!(found.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --safe-methods-only with a method other than GET/HEAD should refuse to start the scan
+fn main_safe_methods_only_refuses_unsafe_methods() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--methods")
+        .arg("GET,POST")
+        .arg("--safe-methods-only")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--safe-methods-only refuses to send POST; only GET and HEAD are allowed",
+        ));
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --safe-methods-only should point out words that look like state-changing actions, and still
+/// request them
+fn main_safe_methods_only_warns_about_risky_words() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) =
+        setup_tmp_directory(&["LICENSE".to_string(), "logout".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/logout");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--safe-methods-only")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("Safe Methods Only").and(predicate::str::contains(
+                "1 words look like state-changing actions and will be requested: logout",
+            )),
+        );
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --preview should print the first requests, fully built, and send nothing when the scan
+/// isn't confirmed
+fn main_preview_declined_sends_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) =
+        setup_tmp_directory(&["LICENSE".to_string(), "admin".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.any_request();
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--extensions")
+        .arg("php")
+        .arg("--headers")
+        .arg("X-Stuff: things")
+        .arg("--preview")
+        .arg("4")
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("Preview Requests")
+                .and(predicate::str::contains(format!(
+                    "GET {}\nUser-Agent: feroxbuster",
+                    srv.url("/LICENSE")
+                )))
+                .and(predicate::str::contains("X-Stuff: things"))
+                .and(predicate::str::contains(format!(
+                    "GET {}",
+                    srv.url("/LICENSE.php")
+                )))
+                .and(predicate::str::contains("[4/4]"))
+                .and(predicate::str::contains(format!("GET {}", srv.url("/admin"))).not())
+                .and(predicate::str::contains("Scan not started")),
+        );
+
+    assert_eq!(mock.hits(), 0);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --preview should go on with the scan once it's confirmed
+fn main_preview_confirmed_starts_scan() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--preview")
+        .arg("1")
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(srv.url("/LICENSE")));
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --preview asks for confirmation on STDIN, so a wordlist piped in there (-w -) is refused
+/// instead of the answer being read from the wordlist
+fn main_preview_refuses_wordlist_from_stdin() {
+    let srv = MockServer::start();
+
+    let mock = srv.mock(|when, then| {
+        when.any_request();
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg("-")
+        .arg("--preview")
+        .arg("1")
+        .write_stdin("y\nLICENSE\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "--preview reads its confirmation from STDIN",
+        ));
+
+    assert_eq!(mock.hits(), 0);
+}
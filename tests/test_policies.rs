@@ -75,6 +75,8 @@ fn auto_bail_cancels_scan_with_timeouts() {
 
     let debug_log = read_to_string(logfile).unwrap();
 
+    let re = Regex::new("total_expected: ([0-9]+),").unwrap();
+
     // read debug log to get the number of errors enforced
     for line in debug_log.lines() {
         let log: serde_json::Value = serde_json::from_str(line).unwrap_or_default();
@@ -82,7 +84,6 @@ fn auto_bail_cancels_scan_with_timeouts() {
             let str_msg = message.as_str().unwrap_or_default().to_string();
 
             if str_msg.starts_with("Stats") {
-                let re = Regex::new("total_expected: ([0-9]+),").unwrap();
                 assert!(re.is_match(&str_msg));
                 let total_expected = re
                     .captures(&str_msg)
@@ -154,6 +155,8 @@ fn auto_bail_cancels_scan_with_403s() {
     println!("log filesize: {}", logfile.metadata().unwrap().len());
     let debug_log = read_to_string(logfile).unwrap();
 
+    let re = Regex::new("total_expected: ([0-9]+),").unwrap();
+
     // read debug log to get the number of errors enforced
     for line in debug_log.lines() {
         let log: serde_json::Value = serde_json::from_str(line).unwrap_or_default();
@@ -162,7 +165,6 @@ fn auto_bail_cancels_scan_with_403s() {
 
             if str_msg.starts_with("Stats") {
                 println!("{str_msg}");
-                let re = Regex::new("total_expected: ([0-9]+),").unwrap();
                 assert!(re.is_match(&str_msg));
                 let total_expected = re
                     .captures(&str_msg)
@@ -236,6 +238,8 @@ fn auto_bail_cancels_scan_with_429s() {
     println!("log filesize: {}", logfile.metadata().unwrap().len());
     let debug_log = read_to_string(logfile).unwrap();
 
+    let re = Regex::new("total_expected: ([0-9]+),").unwrap();
+
     // read debug log to get the number of errors enforced
     for line in debug_log.lines() {
         let log: serde_json::Value = serde_json::from_str(line).unwrap_or_default();
@@ -244,7 +248,6 @@ fn auto_bail_cancels_scan_with_429s() {
 
             if str_msg.starts_with("Stats") {
                 println!("{str_msg}");
-                let re = Regex::new("total_expected: ([0-9]+),").unwrap();
                 assert!(re.is_match(&str_msg));
                 let total_expected = re
                     .captures(&str_msg)
@@ -430,3 +433,50 @@ fn auto_tune_slows_scan_with_general_errors() {
     assert!(error_mock.hits() <= 180); // may or may not see all other error requests
     assert!(start.elapsed().as_millis() >= 7000); // scan should hit time limit due to limiting
 }
+
+#[test]
+/// --exit-code errors=CODE exits with CODE once the share of failed requests reaches
+/// --exit-error-ratio, and with 0 below it
+fn exit_code_errors_follows_exit_error_ratio() {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(
+        &[
+            "slow-one".to_string(),
+            "slow-two".to_string(),
+            "fast-one".to_string(),
+            "fast-two".to_string(),
+        ],
+        "wordlist",
+    )
+    .unwrap();
+
+    srv.mock(|when, then| {
+        when.method(GET).path_matches(Regex::new("/slow-").unwrap());
+        then.delay(Duration::new(3, 0)).status(200);
+    });
+
+    srv.mock(|when, then| {
+        when.method(GET).path_matches(Regex::new("/fast-").unwrap());
+        then.status(200).body("quick");
+    });
+
+    for (ratio, code) in [("0.2", 3), ("0.9", 0)] {
+        Command::cargo_bin("feroxbuster")
+            .unwrap()
+            .arg("--url")
+            .arg(srv.url("/"))
+            .arg("--wordlist")
+            .arg(file.as_os_str())
+            .arg("--dont-filter")
+            .arg("--timeout")
+            .arg("1")
+            .arg("--exit-code")
+            .arg("errors=3,bail=4")
+            .arg("--exit-error-ratio")
+            .arg(ratio)
+            .assert()
+            .code(code);
+    }
+
+    teardown_tmp_directory(tmp_dir);
+}